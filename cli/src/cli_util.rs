@@ -14,6 +14,7 @@
 
 use std::borrow::Cow;
 use std::cell::OnceCell;
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -21,7 +22,9 @@ use std::env;
 use std::ffi::OsString;
 use std::fmt;
 use std::fmt::Debug;
+use std::fs;
 use std::io;
+use std::io::Read as _;
 use std::io::Write as _;
 use std::mem;
 use std::path::Path;
@@ -47,6 +50,7 @@ use clap::error::ContextKind;
 use clap::error::ContextValue;
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
 use indexmap::IndexMap;
 use indexmap::IndexSet;
 use indoc::indoc;
@@ -74,6 +78,7 @@ use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::id_prefix::IdPrefixContext;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::MergedTreeValue;
+use jj_lib::merge_driver::MergeDrivers;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_heads_store;
@@ -117,6 +122,7 @@ use jj_lib::revset::UserRevsetExpression;
 use jj_lib::rewrite::restore_tree;
 use jj_lib::settings::HumanByteSize;
 use jj_lib::settings::UserSettings;
+use jj_lib::store::Store;
 use jj_lib::str_util::StringPattern;
 use jj_lib::transaction::Transaction;
 use jj_lib::view::View;
@@ -145,6 +151,7 @@ use tracing_subscriber::prelude::*;
 
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
+use crate::command_error::config_error;
 use crate::command_error::config_error_with_message;
 use crate::command_error::handle_command_result;
 use crate::command_error::internal_error;
@@ -152,6 +159,7 @@ use crate::command_error::internal_error_with_message;
 use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::commit_templater::CommitTemplateLanguageExtension;
 use crate::complete;
@@ -167,6 +175,9 @@ use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
 use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
+use crate::gc_util;
+use crate::gc_util::AutoGcSettings;
+use crate::gc_util::NO_AUTO_GC_ENV_VAR;
 use crate::merge_tools::DiffEditor;
 use crate::merge_tools::MergeEditor;
 use crate::merge_tools::MergeToolConfigError;
@@ -186,6 +197,10 @@ use crate::ui::Ui;
 
 const SHORT_CHANGE_ID_TEMPLATE_TEXT: &str = "format_short_change_id(self.change_id())";
 
+/// Number of commits listed when asking for confirmation to mutate many
+/// commits.
+const LARGE_REVSET_SAMPLE_SIZE: usize = 10;
+
 #[derive(Clone)]
 struct ChromeTracingFlushGuard {
     _inner: Option<Rc<tracing_chrome::FlushGuard>>,
@@ -287,6 +302,25 @@ pub struct CommandHelper {
     data: Rc<CommandHelperData>,
 }
 
+/// When to snapshot the working copy automatically.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub enum AutoSnapshot {
+    /// Snapshot before every command.
+    Always,
+    /// Don't snapshot before read-only queries unless they read the
+    /// working-copy commit.
+    QueriesSkip,
+    /// Never snapshot, as if `--ignore-working-copy` were specified.
+    Never,
+}
+
+impl AutoSnapshot {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        settings.get("snapshot.auto")
+    }
+}
+
 struct CommandHelperData {
     app: Command,
     cwd: PathBuf,
@@ -297,6 +331,7 @@ struct CommandHelperData {
     config_migrations: Vec<ConfigMigrationRule>,
     raw_config: RawConfig,
     settings: UserSettings,
+    auto_snapshot: AutoSnapshot,
     revset_extensions: Arc<RevsetExtensions>,
     commit_template_extensions: Vec<Arc<dyn CommitTemplateLanguageExtension>>,
     operation_template_extensions: Vec<Arc<dyn OperationTemplateLanguageExtension>>,
@@ -304,6 +339,9 @@ struct CommandHelperData {
     store_factories: StoreFactories,
     working_copy_factories: WorkingCopyFactories,
     workspace_loader_factory: Box<dyn WorkspaceLoaderFactory>,
+    /// Stores loaded by the command, whose statistics are printed with
+    /// `--debug`.
+    loaded_stores: RefCell<Vec<Arc<Store>>>,
 }
 
 impl CommandHelper {
@@ -426,6 +464,26 @@ impl CommandHelper {
         Ok(workspace_command)
     }
 
+    /// Loads workspace and repo for a read-only query command.
+    ///
+    /// The working copy is snapshotted as usual unless `snapshot.auto` is set
+    /// to `queries-skip`. In that case, the caller should check
+    /// [`WorkspaceCommandHelper::query_needs_snapshot()`] once the revisions
+    /// to read are resolved.
+    #[instrument(skip(self, ui))]
+    pub fn workspace_helper_for_query(
+        &self,
+        ui: &Ui,
+    ) -> Result<WorkspaceCommandHelper, CommandError> {
+        if self.data.auto_snapshot == AutoSnapshot::QueriesSkip {
+            let mut workspace_command = self.workspace_helper_no_snapshot(ui)?;
+            workspace_command.snapshot_deferred = workspace_command.may_update_working_copy;
+            Ok(workspace_command)
+        } else {
+            self.workspace_helper(ui)
+        }
+    }
+
     /// Loads workspace and repo, then snapshots the working copy if allowed and
     /// returns the SnapshotStats.
     ///
@@ -491,7 +549,7 @@ impl CommandHelper {
     #[instrument(skip_all)]
     pub fn load_workspace(&self) -> Result<Workspace, CommandError> {
         let loader = self.workspace_loader()?;
-        loader
+        let workspace = loader
             .load(
                 &self.data.settings,
                 &self.data.store_factories,
@@ -499,7 +557,26 @@ impl CommandHelper {
             )
             .map_err(|err| {
                 map_workspace_load_error(err, self.data.global_args.repository.as_deref())
-            })
+            })?;
+        if self.data.global_args.debug {
+            let store = workspace.repo_loader().store();
+            self.data.loaded_stores.borrow_mut().push(store.clone());
+        }
+        Ok(workspace)
+    }
+
+    /// Prints the statistics of the caches of the loaded stores.
+    fn print_store_stats(&self, ui: &Ui) -> io::Result<()> {
+        for store in self.data.loaded_stores.borrow().iter() {
+            let stats = store.materialized_conflict_cache_stats();
+            writeln!(
+                ui.stderr(),
+                "Materialized conflict cache: {hits} hits, {misses} misses",
+                hits = stats.hits,
+                misses = stats.misses
+            )?;
+        }
+        Ok(())
     }
 
     /// Loads workspace located at the specified path.
@@ -615,7 +692,13 @@ impl CommandHelper {
     /// Returns true if the working copy to be loaded is writable, and therefore
     /// should usually be snapshotted.
     pub fn is_working_copy_writable(&self) -> bool {
-        self.is_at_head_operation() && !self.data.global_args.ignore_working_copy
+        self.is_at_head_operation() && !self.ignores_working_copy()
+    }
+
+    /// Returns true if the working copy shouldn't be touched, either because
+    /// of `--ignore-working-copy` or `snapshot.auto = "never"`.
+    pub fn ignores_working_copy(&self) -> bool {
+        self.data.global_args.ignore_working_copy || self.data.auto_snapshot == AutoSnapshot::Never
     }
 
     /// Returns true if the current operation is considered to be the head.
@@ -824,6 +907,20 @@ impl WorkspaceCommandEnvironment {
         &self.workspace_name
     }
 
+    /// Starts a transaction recording the workspace and the current directory
+    /// the command runs in.
+    fn start_repo_transaction(&self, repo: &Arc<ReadonlyRepo>) -> Transaction {
+        let mut tx = start_repo_transaction(repo, self.command.string_args());
+        let cwd = match &self.path_converter {
+            RepoPathUiConverter::Fs { cwd, base } => cwd
+                .strip_prefix(base)
+                .ok()
+                .and_then(|path| RepoPathBuf::from_relative_path(path).ok()),
+        };
+        tx.set_workspace(self.workspace_name.clone(), cwd);
+        tx
+    }
+
     pub(crate) fn revset_parse_context(&self) -> RevsetParseContext<'_> {
         let workspace_context = RevsetWorkspaceContext {
             path_converter: &self.path_converter,
@@ -1004,6 +1101,7 @@ pub struct WorkspaceCommandHelper {
     commit_summary_template_text: String,
     op_summary_template_text: String,
     may_update_working_copy: bool,
+    snapshot_deferred: bool,
     working_copy_shared_with_git: bool,
 }
 
@@ -1040,10 +1138,13 @@ impl WorkspaceCommandHelper {
         let settings = workspace.settings();
         let commit_summary_template_text = settings.get_string("templates.commit_summary")?;
         let op_summary_template_text = settings.get_string("templates.op_summary")?;
-        let may_update_working_copy =
-            loaded_at_head && !env.command.global_args().ignore_working_copy;
+        let may_update_working_copy = loaded_at_head && !env.command.ignores_working_copy();
         let working_copy_shared_with_git =
             crate::git_util::is_colocated_git_workspace(&workspace, &repo);
+        if settings.get_bool("diff.persist-conflict-cache")? {
+            repo.store()
+                .set_materialized_conflict_cache_dir(workspace.repo_path().join("conflict_cache"));
+        }
 
         let helper = Self {
             workspace,
@@ -1052,6 +1153,7 @@ impl WorkspaceCommandHelper {
             commit_summary_template_text,
             op_summary_template_text,
             may_update_working_copy,
+            snapshot_deferred: false,
             working_copy_shared_with_git,
         };
         // Parse commit_summary template early to report error before starting
@@ -1073,6 +1175,8 @@ impl WorkspaceCommandHelper {
         } else {
             let hint = if self.env.command.global_args().ignore_working_copy {
                 "Don't use --ignore-working-copy."
+            } else if self.env.command.ignores_working_copy() {
+                "Don't set `snapshot.auto = \"never\"`."
             } else {
                 "Don't use --at-op."
             };
@@ -1091,11 +1195,31 @@ impl WorkspaceCommandHelper {
         if !self.may_update_working_copy {
             return Ok(SnapshotStats::default());
         }
+        self.snapshot_deferred = false;
 
         #[cfg(feature = "git")]
-        if self.working_copy_shared_with_git {
+        let git_auto_import = if self.working_copy_shared_with_git {
+            crate::git_util::GitAutoImport::from_settings(self.settings())
+                .map_err(snapshot_command_error)?
+        } else {
+            crate::git_util::GitAutoImport::Never
+        };
+        #[cfg(feature = "git")]
+        if self.working_copy_shared_with_git
+            && self
+                .settings()
+                .get_bool("git.restore-jj-gitignore")
+                .map_err(snapshot_command_error)?
+        {
+            crate::git_util::restore_jj_gitignore(ui, &self.workspace, self.repo())
+                .map_err(snapshot_command_error)?;
+        }
+        #[cfg(feature = "git")]
+        if git_auto_import != crate::git_util::GitAutoImport::Never {
             self.import_git_head(ui).map_err(snapshot_command_error)?;
         }
+        #[cfg(feature = "git")]
+        let old_op_id = self.repo().op_id().clone();
         // Because the Git refs (except HEAD) aren't imported yet, the ref
         // pointing to the new working-copy commit might not be exported.
         // In that situation, the ref would be conflicted anyway, so export
@@ -1104,8 +1228,14 @@ impl WorkspaceCommandHelper {
 
         // import_git_refs() can rebase the working-copy commit.
         #[cfg(feature = "git")]
-        if self.working_copy_shared_with_git {
-            self.import_git_refs(ui).map_err(snapshot_command_error)?;
+        match git_auto_import {
+            crate::git_util::GitAutoImport::Always => {
+                self.import_git_refs(ui).map_err(snapshot_command_error)?;
+            }
+            crate::git_util::GitAutoImport::OnSnapshot if *self.repo().op_id() != old_op_id => {
+                self.import_git_refs(ui).map_err(snapshot_command_error)?;
+            }
+            crate::git_util::GitAutoImport::OnSnapshot | crate::git_util::GitAutoImport::Never => {}
         }
         Ok(stats)
     }
@@ -1121,6 +1251,21 @@ impl WorkspaceCommandHelper {
         Ok(())
     }
 
+    /// Returns true if the snapshot was skipped by
+    /// [`CommandHelper::workspace_helper_for_query()`], but the working-copy
+    /// commit is one of the `commit_ids` to be read. The caller should then
+    /// [`maybe_snapshot()`](Self::maybe_snapshot) and resolve the revisions
+    /// again.
+    pub fn query_needs_snapshot<'a>(
+        &self,
+        mut commit_ids: impl Iterator<Item = &'a CommitId>,
+    ) -> bool {
+        self.snapshot_deferred
+            && self
+                .get_wc_commit_id()
+                .is_some_and(|wc_commit_id| commit_ids.any(|id| id == wc_commit_id))
+    }
+
     /// Imports new HEAD from the colocated Git repo.
     ///
     /// If the Git HEAD has changed, this function checks out the new Git HEAD.
@@ -1189,11 +1334,27 @@ impl WorkspaceCommandHelper {
     #[cfg(feature = "git")]
     #[instrument(skip_all)]
     fn import_git_refs(&mut self, ui: &Ui) -> Result<(), CommandError> {
+        // Skip the import if neither the Git refs nor the refs recorded in the
+        // view have changed since the last import.
+        let refs_state = crate::git_util::git_refs_state(self.repo());
+        if refs_state.as_ref().is_some_and(|state| {
+            crate::git_util::is_git_import_state_unchanged(self.repo_path(), state)
+        }) {
+            tracing::debug!("Git refs unchanged since the last import");
+            return Ok(());
+        }
+
         let git_settings = self.settings().git_settings()?;
         let mut tx = self.start_transaction();
         let stats = jj_lib::git::import_refs(tx.repo_mut(), &git_settings)?;
         crate::git_util::print_git_import_stats(ui, tx.repo(), &stats, false)?;
         if !tx.repo().has_changes() {
+            // Keep warning about the refs that failed to import.
+            if stats.failed_ref_names.is_empty() {
+                if let Some(state) = &refs_state {
+                    crate::git_util::save_git_import_state(self.repo_path(), state)?;
+                }
+            }
             return Ok(());
         }
 
@@ -1207,10 +1368,24 @@ impl WorkspaceCommandHelper {
             )?;
         }
         self.finish_transaction(ui, tx, "import git refs")?;
-        writeln!(
-            ui.status(),
-            "Done importing changes from the underlying Git repo."
-        )?;
+        // The refs may have been exported while finishing the transaction.
+        if stats.failed_ref_names.is_empty() {
+            if let Some(state) = crate::git_util::git_refs_state(self.repo()) {
+                crate::git_util::save_git_import_state(self.repo_path(), &state)?;
+            }
+        }
+        let num_imported = stats.changed_remote_bookmarks.len() + stats.changed_remote_tags.len();
+        if num_imported > 0 {
+            writeln!(
+                ui.status(),
+                "Imported {num_imported} refs from the underlying Git repo."
+            )?;
+        } else {
+            writeln!(
+                ui.status(),
+                "Done importing changes from the underlying Git repo."
+            )?;
+        }
         Ok(())
     }
 
@@ -1255,6 +1430,22 @@ impl WorkspaceCommandHelper {
         Ok((locked_ws, wc_commit))
     }
 
+    /// Switches to the `operation` which replaced the current operation, such
+    /// as after the operation history has been rewritten. The working copy is
+    /// updated to refer to the new operation.
+    pub fn replace_current_operation(&mut self, operation: &Operation) -> Result<(), CommandError> {
+        let old_repo = self.repo().clone();
+        let repo = old_repo.reload_at(operation)?;
+        if self.may_update_working_copy {
+            let mut locked_ws = self.workspace.start_working_copy_mutation()?;
+            if locked_ws.locked_wc().old_operation_id() == old_repo.op_id() {
+                locked_ws.finish(operation.id().clone())?;
+            }
+        }
+        self.user_repo = ReadonlyUserRepo::new(repo);
+        Ok(())
+    }
+
     pub fn start_working_copy_mutation(
         &mut self,
     ) -> Result<(LockedWorkspace<'_>, Commit), CommandError> {
@@ -1462,7 +1653,12 @@ to the current parents may contain changes from multiple commits.
         args: &DiffFormatArgs,
     ) -> Result<DiffRenderer<'_>, CommandError> {
         let formats = diff_util::diff_formats_for(self.settings(), args)?;
-        Ok(self.diff_renderer(formats))
+        let renderer = self.diff_renderer(formats);
+        if diff_util::diff_relative_paths_for(self.settings(), args)? {
+            Ok(renderer)
+        } else {
+            Ok(renderer.with_root_relative_paths())
+        }
     }
 
     /// Loads textual diff renderer from the settings and log-like command
@@ -1474,7 +1670,15 @@ to the current parents may contain changes from multiple commits.
         patch: bool,
     ) -> Result<Option<DiffRenderer<'_>>, CommandError> {
         let formats = diff_util::diff_formats_for_log(self.settings(), args, patch)?;
-        Ok((!formats.is_empty()).then(|| self.diff_renderer(formats)))
+        if formats.is_empty() {
+            return Ok(None);
+        }
+        let renderer = self.diff_renderer(formats);
+        if diff_util::diff_relative_paths_for(self.settings(), args)? {
+            Ok(Some(renderer))
+        } else {
+            Ok(Some(renderer.with_root_relative_paths()))
+        }
     }
 
     /// Loads diff editor from the settings.
@@ -1629,6 +1833,7 @@ to the current parents may contain changes from multiple commits.
         let (expression, modifier) =
             revset::parse_with_modifier(&mut diagnostics, revision_arg.as_ref(), &context)?;
         print_parse_diagnostics(ui, "In revset expression", &diagnostics)?;
+        revset_util::warn_shadowed_change_names(ui, self.repo().as_ref(), &expression)?;
         Ok((self.attach_revset_evaluator(expression), modifier))
     }
 
@@ -1647,6 +1852,7 @@ to the current parents may contain changes from multiple commits.
             .try_collect()?;
         print_parse_diagnostics(ui, "In revset expression", &diagnostics)?;
         let expression = RevsetExpression::union_all(&expressions);
+        revset_util::warn_shadowed_change_names(ui, self.repo().as_ref(), &expression)?;
         Ok(self.attach_revset_evaluator(expression))
     }
 
@@ -1830,6 +2036,59 @@ to the current parents may contain changes from multiple commits.
         Err(error)
     }
 
+    /// Asks for confirmation if the commits resolved from user-provided
+    /// revsets are more than `ui.large-revset-warning-threshold`.
+    ///
+    /// The check is skipped if `no_limit` is set or the threshold is 0.
+    pub fn check_large_revset<'a>(
+        &self,
+        ui: &Ui,
+        commits: impl IntoIterator<Item = &'a CommitId>,
+        no_limit: bool,
+    ) -> Result<(), CommandError> {
+        if no_limit {
+            return Ok(());
+        }
+        let threshold: usize = self.settings().get("ui.large-revset-warning-threshold")?;
+        let commit_ids = commits.into_iter().collect_vec();
+        if threshold == 0 || commit_ids.len() <= threshold {
+            return Ok(());
+        }
+        if let Some(mut formatter) = ui.status_formatter() {
+            writeln!(
+                formatter.labeled("warning").with_heading("Warning: "),
+                "The revisions matched {} commits, which is more than \
+                 `ui.large-revset-warning-threshold` ({threshold}):",
+                commit_ids.len()
+            )?;
+            // Only a sample of the commits is loaded and listed.
+            let commits: Vec<_> = commit_ids
+                .iter()
+                .take(LARGE_REVSET_SAMPLE_SIZE)
+                .map(|id| self.repo().store().get_commit(id))
+                .try_collect()?;
+            print_updated_commits(
+                formatter.as_mut(),
+                &self.commit_summary_template(),
+                &commits,
+            )?;
+            if commit_ids.len() > commits.len() {
+                writeln!(
+                    formatter,
+                    "  ... and {} more",
+                    commit_ids.len() - commits.len()
+                )?;
+            }
+        }
+        if !ui.prompt_yes_no("Continue?", Some(false))? {
+            return Err(user_error_with_hint(
+                "Operation was canceled",
+                "Pass `--no-limit` to skip this check.",
+            ));
+        }
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     fn snapshot_working_copy(
         &mut self,
@@ -1924,8 +2183,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 .map_err(snapshot_command_error)?
         };
         if new_tree_id != *wc_commit.tree_id() {
-            let mut tx =
-                start_repo_transaction(&self.user_repo.repo, self.env.command.string_args());
+            let mut tx = self.env.start_repo_transaction(&self.user_repo.repo);
             tx.set_is_snapshot(true);
             let mut_repo = tx.repo_mut();
             let commit = mut_repo
@@ -2033,7 +2291,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
     }
 
     pub fn start_transaction(&mut self) -> WorkspaceCommandTransaction<'_> {
-        let tx = start_repo_transaction(self.repo(), self.env.command.string_args());
+        let tx = self.env.start_repo_transaction(&self.user_repo.repo);
         let id_prefix_context = mem::take(&mut self.user_repo.id_prefix_context);
         WorkspaceCommandTransaction {
             helper: self,
@@ -2056,6 +2314,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         if num_rebased > 0 {
             writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
         }
+        warn_skipped_merge_drivers(ui, tx.repo().store())?;
 
         for (name, wc_commit_id) in &tx.repo().view().wc_commit_ids().clone() {
             if self
@@ -2152,6 +2411,45 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 )?;
             }
         }
+
+        self.maybe_run_auto_gc(ui)?;
+        Ok(())
+    }
+
+    /// Runs garbage collection if enabled by the `gc.auto` config and the
+    /// configured interval has elapsed. Failures are reported as warnings.
+    fn maybe_run_auto_gc(&mut self, ui: &Ui) -> Result<(), CommandError> {
+        let command = &self.env.command;
+        if command.global_args().ignore_working_copy
+            || !command.is_at_head_operation()
+            || env::var_os(NO_AUTO_GC_ENV_VAR).is_some_and(|value| !value.is_empty())
+        {
+            return Ok(());
+        }
+        let result = AutoGcSettings::from_settings(self.settings())
+            .map_err(CommandError::from)
+            .and_then(|gc_settings| {
+                if !gc_settings.enabled {
+                    return Ok(false);
+                }
+                gc_util::run_gc_if_due(self, gc_settings.interval)
+            });
+        match result {
+            Ok(true) => {
+                writeln!(
+                    ui.status(),
+                    "Ran automatic garbage collection (see `jj util gc`)."
+                )?;
+            }
+            Ok(false) => {}
+            Err(err) => {
+                writeln!(
+                    ui.warning_default(),
+                    "Automatic garbage collection failed: {}",
+                    err.error
+                )?;
+            }
+        }
         Ok(())
     }
 
@@ -2526,6 +2824,7 @@ jj git init --colocate",
             err @ (StoreLoadError::ReadError { .. } | StoreLoadError::Backend(_)),
         ) => internal_error_with_message("The repository appears broken or inaccessible", err),
         WorkspaceLoadError::StoreLoadError(StoreLoadError::Signing(err)) => user_error(err),
+        WorkspaceLoadError::StoreLoadError(StoreLoadError::MergeDrivers(err)) => user_error(err),
         WorkspaceLoadError::WorkingCopyState(err) => internal_error(err),
         WorkspaceLoadError::DecodeRepoPath(_) | WorkspaceLoadError::Path(_) => user_error(err),
     }
@@ -2832,6 +3131,59 @@ pub fn print_unmatched_explicit_paths<'a>(
     Ok(())
 }
 
+/// Reports path arguments that don't match any changed file between the
+/// given pairs of trees.
+///
+/// Unmatched arguments are printed as a warning, or reported as an error if
+/// `strict` or the `ui.strict-filesets` setting is set.
+pub fn check_path_args_match_changes<'a>(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    path_args: &[String],
+    tree_pairs: impl IntoIterator<Item = (&'a MergedTree, &'a MergedTree)>,
+    strict: bool,
+) -> Result<(), CommandError> {
+    if path_args.is_empty() {
+        return Ok(());
+    }
+    let tree_pairs = tree_pairs.into_iter().collect_vec();
+    let mut unmatched_args = vec![];
+    for arg in path_args {
+        // Diagnostics have already been reported when parsing the whole set of
+        // arguments.
+        let mut diagnostics = FilesetDiagnostics::new();
+        let expression =
+            fileset::parse_maybe_bare(&mut diagnostics, arg, workspace_command.path_converter())?;
+        let matcher = expression.to_matcher();
+        let matched = tree_pairs.iter().any(|(from_tree, to_tree)| {
+            from_tree
+                .diff_stream(to_tree, &matcher)
+                .next()
+                .block_on()
+                .is_some()
+        });
+        if !matched {
+            unmatched_args.push(arg.as_str());
+        }
+    }
+    if unmatched_args.is_empty() {
+        return Ok(());
+    }
+    let message = format!(
+        "No changes match the given paths: {}",
+        unmatched_args.join(", ")
+    );
+    if strict
+        || workspace_command
+            .settings()
+            .get_bool("ui.strict-filesets")?
+    {
+        return Err(user_error(message));
+    }
+    writeln!(ui.warning_default(), "{message}")?;
+    Ok(())
+}
+
 pub fn update_working_copy(
     repo: &Arc<ReadonlyRepo>,
     workspace: &mut Workspace,
@@ -2987,16 +3339,37 @@ impl DiffSelector {
         right_tree: &MergedTree,
         matcher: &dyn Matcher,
         format_instructions: impl FnOnce() -> String,
+    ) -> Result<MergedTreeId, CommandError> {
+        self.select_from(left_tree, right_tree, None, matcher, format_instructions)
+    }
+
+    /// Like [`Self::select()`], but starts from the `previous_selection`
+    /// instead of all the diffs if specified.
+    pub fn select_from(
+        &self,
+        left_tree: &MergedTree,
+        right_tree: &MergedTree,
+        previous_selection: Option<&MergedTree>,
+        matcher: &dyn Matcher,
+        format_instructions: impl FnOnce() -> String,
     ) -> Result<MergedTreeId, CommandError> {
         let selected_tree_id = restore_tree(right_tree, left_tree, matcher).block_on()?;
         match self {
-            Self::NonInteractive => Ok(selected_tree_id),
+            Self::NonInteractive => Ok(previous_selection
+                .map(|tree| tree.id())
+                .unwrap_or(selected_tree_id)),
             Self::Interactive(editor) => {
                 // edit_diff_external() is designed to edit the right tree,
                 // whereas we want to update the left tree. Unmatched paths
                 // shouldn't be based off the right tree.
                 let right_tree = right_tree.store().get_root_tree(&selected_tree_id)?;
-                Ok(editor.edit(left_tree, &right_tree, matcher, format_instructions)?)
+                Ok(editor.edit_with_initial_tree(
+                    left_tree,
+                    &right_tree,
+                    previous_selection,
+                    matcher,
+                    format_instructions,
+                )?)
             }
         }
     }
@@ -3235,6 +3608,9 @@ pub struct GlobalArgs {
     /// Enable debug logging
     #[arg(long, global = true)]
     pub debug: bool,
+    /// Ignore the default arguments set by the `command-defaults` config
+    #[arg(long, global = true)]
+    pub no_command_defaults: bool,
 
     #[command(flatten)]
     pub early_args: EarlyArgs,
@@ -3329,6 +3705,79 @@ impl ValueParserFactory for RevisionArg {
     }
 }
 
+/// Reads the contents of a file given as an argument, or the standard input if
+/// the path is `-`.
+pub fn read_arg_file(path: &Path) -> Result<String, CommandError> {
+    let result = if path == Path::new("-") {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer).map(|_| buffer)
+    } else {
+        fs::read_to_string(path)
+    };
+    result.map_err(|err| user_error_with_message(format!("Failed to read {}", path.display()), err))
+}
+
+/// `--revisions-file` argument of commands taking revisions.
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct RevisionsFileArgs {
+    /// Read more revisions from a file, one revset per line
+    ///
+    /// The revisions are added to the ones given on the command line. Use `-`
+    /// to read them from the standard input.
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    revisions_file: Option<PathBuf>,
+}
+
+impl RevisionsFileArgs {
+    /// Returns `revisions` followed by the revset expressions read from the
+    /// file. Empty lines are ignored. Returns `None` if no revisions are
+    /// given on the command line or by a file, even if the file is empty.
+    pub fn with_revisions(
+        &self,
+        revisions: &[RevisionArg],
+    ) -> Result<Option<Vec<RevisionArg>>, CommandError> {
+        let Some(path) = &self.revisions_file else {
+            return Ok((!revisions.is_empty()).then(|| revisions.to_vec()));
+        };
+        let text = read_arg_file(path)?;
+        let mut revisions = revisions.to_vec();
+        revisions.extend(
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| RevisionArg::from(line.to_owned())),
+        );
+        Ok(Some(revisions))
+    }
+}
+
+/// `--template-file` argument of commands taking a `--template`.
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct TemplateFileArgs {
+    /// Render using the template read from a file
+    ///
+    /// Use `-` to read the template from the standard input.
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with = "template"
+    )]
+    template_file: Option<PathBuf>,
+}
+
+impl TemplateFileArgs {
+    /// Returns the `template` given on the command line, or the template read
+    /// from the file, if any.
+    pub fn template_text(&self, template: Option<&str>) -> Result<Option<String>, CommandError> {
+        match (template, &self.template_file) {
+            (Some(text), _) => Ok(Some(text.to_owned())),
+            (None, Some(path)) => read_arg_file(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
 /// Merges multiple clap args in order of appearance.
 ///
 /// The `id_values` is a list of `(id, values)` pairs, where `id` is the name of
@@ -3381,7 +3830,7 @@ fn resolve_default_command(
         .clone()
         .allow_external_subcommands(true)
         .ignore_errors(true);
-    let matches = app_clone.try_get_matches_from(&string_args).ok();
+    let matches = app_clone.clone().try_get_matches_from(&string_args).ok();
 
     if let Some(matches) = matches {
         if matches.subcommand_name().is_none() {
@@ -3400,6 +3849,16 @@ fn resolve_default_command(
 
             // Insert the default command directly after the path to the binary.
             string_args.splice(1..1, default_command);
+
+            // The default command is expanded only once, so it must name a
+            // command rather than resolving to the default command again.
+            if let Ok(matches) = app_clone.try_get_matches_from(&string_args) {
+                if matches.subcommand_name().is_none() {
+                    return Err(config_error(
+                        "ui.default-command must start with a command name",
+                    ));
+                }
+            }
         }
     }
     Ok(string_args)
@@ -3462,6 +3921,149 @@ fn resolve_aliases(
     }
 }
 
+/// Inserts the arguments configured in `command-defaults.<command>` right
+/// after the command name. Options that are also given by the user are
+/// omitted from the defaults, so the user arguments take precedence.
+fn resolve_command_defaults(
+    ui: &Ui,
+    config: &StackedConfig,
+    app: &Command,
+    mut string_args: Vec<String>,
+) -> Result<Vec<String>, CommandError> {
+    const HELP_FLAGS: &[&str] = &["--help", "-h"];
+
+    if config.table_keys("command-defaults").next().is_none() {
+        return Ok(string_args);
+    }
+    let flags = || string_args.iter().skip(1).take_while(|arg| *arg != "--");
+    if flags().any(|arg| arg == "--no-command-defaults") {
+        return Ok(string_args);
+    }
+    let Some((commands, insert_index)) = find_command_path(app, &string_args) else {
+        return Ok(string_args);
+    };
+    let command_name = commands.iter().skip(1).map(|c| c.get_name()).join(" ");
+    let Some(defaults) = config
+        .get::<Vec<String>>(["command-defaults", &command_name])
+        .optional()?
+    else {
+        return Ok(string_args);
+    };
+    if flags().any(|arg| HELP_FLAGS.contains(&arg.as_str())) {
+        writeln!(
+            ui.hint_default(),
+            "Default arguments are configured for `jj {command_name}`: {defaults}",
+            defaults = defaults.join(" "),
+        )?;
+        return Ok(string_args);
+    }
+
+    let user_arg_ids: HashSet<_> = flags()
+        .filter_map(|arg| find_option_arg(&commands, arg))
+        .map(|arg| arg.get_id())
+        .collect();
+    let mut default_args = vec![];
+    let mut defaults = defaults.into_iter();
+    while let Some(token) = defaults.next() {
+        let Some(arg) = find_option_arg(&commands, &token) else {
+            default_args.push(token);
+            continue;
+        };
+        let value = option_takes_separate_value(arg, &token)
+            .then(|| defaults.next())
+            .flatten();
+        if !user_arg_ids.contains(arg.get_id()) {
+            default_args.push(token);
+            default_args.extend(value);
+        }
+    }
+    string_args.splice(insert_index..insert_index, default_args);
+    // The defaults are inserted only once. They must not select another
+    // command, which would otherwise need its defaults to be resolved, too.
+    let new_commands = find_command_path(app, &string_args).map(|(commands, _)| commands);
+    if !new_commands.is_some_and(|new_commands| {
+        new_commands
+            .iter()
+            .map(|c| c.get_name())
+            .eq(commands.iter().map(|c| c.get_name()))
+    }) {
+        return Err(user_error_with_hint(
+            format!("Default arguments for `jj {command_name}` cannot select another command"),
+            format!("Check the `command-defaults.\"{command_name}\"` config."),
+        ));
+    }
+    Ok(string_args)
+}
+
+/// Returns the chain of commands selected by the args starting from the root
+/// command, and the index just after the last command name.
+fn find_command_path<'a>(
+    app: &'a Command,
+    string_args: &[String],
+) -> Option<(Vec<&'a Command>, usize)> {
+    let matches = app
+        .clone()
+        .allow_external_subcommands(true)
+        // Do not emit DisplayHelp error
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .ignore_errors(true)
+        .try_get_matches_from(string_args)
+        .ok()?;
+    let mut commands = vec![app];
+    let mut matches = &matches;
+    let mut index = 0;
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        // Not a real command, e.g. unknown alias
+        let subcommand = commands.last().unwrap().find_subcommand(name)?;
+        let is_name = |arg: &String| {
+            arg == subcommand.get_name() || subcommand.get_all_aliases().any(|alias| arg == alias)
+        };
+        index += 1 + string_args[index + 1..].iter().position(is_name)?;
+        commands.push(subcommand);
+        matches = sub_matches;
+    }
+    (commands.len() > 1).then_some((commands, index + 1))
+}
+
+/// Looks up the option specified by `--name[=value]` or `-n[value]` in the
+/// chain of commands.
+fn find_option_arg<'a>(commands: &[&'a Command], token: &str) -> Option<&'a clap::Arg> {
+    let mut args = commands
+        .iter()
+        .rev()
+        .flat_map(|command| command.get_arguments());
+    if let Some(name) = token.strip_prefix("--") {
+        let name = name.split_once('=').map_or(name, |(name, _)| name);
+        args.find(|arg| {
+            arg.get_long() == Some(name)
+                || arg
+                    .get_all_aliases()
+                    .is_some_and(|aliases| aliases.contains(&name))
+        })
+    } else {
+        let short = token.strip_prefix('-')?.chars().next()?;
+        args.find(|arg| {
+            arg.get_short() == Some(short)
+                || arg
+                    .get_all_short_aliases()
+                    .is_some_and(|aliases| aliases.contains(&short))
+        })
+    }
+}
+
+/// Returns true if the value of the option is given as the next token.
+fn option_takes_separate_value(arg: &clap::Arg, token: &str) -> bool {
+    let takes_value = arg.get_num_args().is_none_or(|range| range.takes_values())
+        && arg.get_action().takes_values();
+    let has_inline_value = if token.starts_with("--") {
+        token.contains('=')
+    } else {
+        token.len() > 2
+    };
+    takes_value && !has_inline_value
+}
+
 /// Parse args that must be interpreted early, e.g. before printing help.
 fn parse_early_args(
     app: &Command,
@@ -3585,7 +4187,8 @@ pub fn expand_args(
 ) -> Result<Vec<String>, CommandError> {
     let string_args = to_string_args(args_os)?;
     let string_args = resolve_default_command(ui, config, app, string_args)?;
-    resolve_aliases(ui, config, app, string_args)
+    let string_args = resolve_aliases(ui, config, app, string_args)?;
+    resolve_command_defaults(ui, config, app, string_args)
 }
 
 fn to_string_args(
@@ -3635,7 +4238,8 @@ pub fn format_template<C: Clone>(ui: &Ui, arg: &C, template: &TemplateRenderer<C
 /// CLI command builder and runner.
 #[must_use]
 pub struct CliRunner<'a> {
-    tracing_subscription: TracingSubscription,
+    tracing_subscription: Option<TracingSubscription>,
+    args_os: Vec<OsString>,
     app: Command,
     config_layers: Vec<ConfigLayer>,
     config_migrations: Vec<ConfigMigrationRule>,
@@ -3664,9 +4268,24 @@ impl<'a> CliRunner<'a> {
     /// as early as possible.
     pub fn init() -> Self {
         let tracing_subscription = TracingSubscription::init();
+        Self::new_inner(Some(tracing_subscription), env::args_os().collect())
+    }
+
+    /// Initializes CLI environment to run the given command in-process. The
+    /// global tracing subscriber isn't installed.
+    #[cfg(feature = "testing")]
+    pub(crate) fn init_in_process(args_os: Vec<OsString>) -> Self {
+        Self::new_inner(None, args_os)
+    }
+
+    fn new_inner(
+        tracing_subscription: Option<TracingSubscription>,
+        args_os: Vec<OsString>,
+    ) -> Self {
         crate::cleanup_guard::init();
         Self {
             tracing_subscription,
+            args_os,
             app: crate::commands::default_app(),
             config_layers: crate::config::default_config_layers(),
             config_migrations: crate::config::default_config_migrations(),
@@ -3859,7 +4478,7 @@ impl<'a> CliRunner<'a> {
             return handle_shell_completion(&Ui::null(), &self.app, &config, &cwd);
         }
 
-        let string_args = expand_args(ui, &self.app, env::args_os(), &config)?;
+        let string_args = expand_args(ui, &self.app, self.args_os.clone(), &config)?;
         let (args, config_layers) = parse_early_args(&self.app, &string_args)?;
         if !config_layers.is_empty() {
             raw_config.as_mut().extend_layers(config_layers);
@@ -3869,14 +4488,16 @@ impl<'a> CliRunner<'a> {
         }
 
         if args.has_config_args() {
-            warn_if_args_mismatch(ui, &self.app, &config, &string_args)?;
+            warn_if_args_mismatch(ui, &self.app, &self.args_os, &config, &string_args)?;
         }
 
         let (matches, args) = parse_args(&self.app, &string_args)
             .map_err(|err| map_clap_cli_error(err, ui, &config))?;
         if args.global_args.debug {
             // TODO: set up debug logging as early as possible
-            self.tracing_subscription.enable_debug_logging()?;
+            if let Some(tracing_subscription) = &self.tracing_subscription {
+                tracing_subscription.enable_debug_logging()?;
+            }
         }
         for process_global_args_fn in self.process_global_args_fns {
             process_global_args_fn(ui, &matches)?;
@@ -3918,12 +4539,17 @@ impl<'a> CliRunner<'a> {
                 "Deprecated {source_str} config: {desc}"
             )?;
         }
+        print_unknown_config_keys(ui, &config)?;
 
         if args.global_args.repository.is_some() {
-            warn_if_args_mismatch(ui, &self.app, &config, &string_args)?;
+            warn_if_args_mismatch(ui, &self.app, &self.args_os, &config, &string_args)?;
         }
 
         let settings = UserSettings::from_config(config)?;
+        let auto_snapshot = AutoSnapshot::from_settings(&settings)?;
+        if let Ok(loader) = &maybe_workspace_loader {
+            confirm_merge_drivers(ui, &settings, loader.as_ref())?;
+        }
         let command_helper_data = CommandHelperData {
             app: self.app,
             cwd,
@@ -3934,6 +4560,7 @@ impl<'a> CliRunner<'a> {
             config_migrations: self.config_migrations,
             raw_config,
             settings,
+            auto_snapshot,
             revset_extensions: self.revset_extensions.into(),
             commit_template_extensions: self.commit_template_extensions,
             operation_template_extensions: self.operation_template_extensions,
@@ -3941,6 +4568,7 @@ impl<'a> CliRunner<'a> {
             store_factories: self.store_factories,
             working_copy_factories: self.working_copy_factories,
             workspace_loader_factory: self.workspace_loader_factory,
+            loaded_stores: RefCell::new(vec![]),
         };
         let command_helper = CommandHelper {
             data: Rc::new(command_helper_data),
@@ -3953,7 +4581,11 @@ impl<'a> CliRunner<'a> {
                 })
             },
         );
-        (dispatch_fn)(ui, &command_helper)
+        let result = (dispatch_fn)(ui, &command_helper);
+        if command_helper.global_args().debug {
+            command_helper.print_store_stats(ui)?;
+        }
+        result
     }
 
     #[must_use]
@@ -3971,6 +4603,19 @@ impl<'a> CliRunner<'a> {
         ui.finalize_pager();
         exit_code
     }
+
+    /// Runs the command, capturing its output instead of writing to the
+    /// terminal. Returns the exit code and the stdout and stderr contents.
+    #[cfg(feature = "testing")]
+    pub(crate) fn run_captured(mut self) -> (u8, Vec<u8>, Vec<u8>) {
+        let config = config_from_environment(self.config_layers.drain(..));
+        let mut ui = Ui::captured(config.as_ref())
+            .expect("default config should be valid, env vars are stringly typed");
+        let result = self.run_internal(&mut ui, config);
+        let exit_code = handle_command_result(&mut ui, result);
+        let (stdout, stderr) = ui.take_captured_output().unwrap();
+        (exit_code, stdout, stderr)
+    }
 }
 
 fn map_clap_cli_error(err: clap::Error, ui: &Ui, config: &StackedConfig) -> CommandError {
@@ -4031,14 +4676,40 @@ fn format_template_aliases_hint(template_aliases: &TemplateAliasesMap) -> String
     hint
 }
 
+/// Warns about config keys that aren't known to jj, suggesting similar known
+/// keys if any.
+fn print_unknown_config_keys(ui: &Ui, config: &StackedConfig) -> io::Result<()> {
+    for unknown in crate::config::find_unknown_config_keys(config) {
+        let location = match (&unknown.path, unknown.line) {
+            (Some(path), Some(line)) => format!("{}:{line}", path.display()),
+            (Some(path), None) => path.display().to_string(),
+            (None, _) => match unknown.source {
+                ConfigSource::CommandArg => "--config argument".to_owned(),
+                _ => "config".to_owned(),
+            },
+        };
+        writeln!(
+            ui.warning_default(),
+            "Unknown config key `{name}` in {location}",
+            name = unknown.name
+        )?;
+        if !unknown.similar.is_empty() {
+            let quoted_names = unknown.similar.iter().map(|s| format!("`{s}`")).join(", ");
+            writeln!(ui.hint_default(), "Did you mean {quoted_names}?")?;
+        }
+    }
+    Ok(())
+}
+
 // If -R or --config* is specified, check if the expanded arguments differ.
 fn warn_if_args_mismatch(
     ui: &Ui,
     app: &Command,
+    args_os: &[OsString],
     config: &StackedConfig,
     expected_args: &[String],
 ) -> Result<(), CommandError> {
-    let new_string_args = expand_args(ui, app, env::args_os(), config).ok();
+    let new_string_args = expand_args(ui, app, args_os.iter().cloned(), config).ok();
     if new_string_args.as_deref() != Some(expected_args) {
         writeln!(
             ui.warning_default(),
@@ -4049,6 +4720,68 @@ fn warn_if_args_mismatch(
     Ok(())
 }
 
+/// Asks whether to trust the merge drivers defined in the repo config. The
+/// answers are recorded in the repo, so each command is asked only once.
+///
+/// If the user can't be asked, untrusted drivers are ignored, and a warning
+/// is printed once they would have run.
+fn confirm_merge_drivers(
+    ui: &Ui,
+    settings: &UserSettings,
+    loader: &dyn WorkspaceLoader,
+) -> Result<(), CommandError> {
+    if !Ui::can_prompt() {
+        return Ok(());
+    }
+    let mut merge_drivers =
+        MergeDrivers::load(settings, loader.repo_path(), loader.workspace_root())
+            .map_err(user_error)?;
+    let new_drivers = merge_drivers
+        .drivers()
+        .iter()
+        .filter(|driver| driver.trusted().is_none())
+        .map(|driver| {
+            (
+                driver.pattern().to_owned(),
+                driver.program().to_owned(),
+                driver.command().to_owned(),
+            )
+        })
+        .collect_vec();
+    for (pattern, program, command) in new_drivers {
+        let answered = merge_drivers
+            .drivers()
+            .iter()
+            .any(|driver| driver.command() == command && driver.trusted().is_some());
+        if answered {
+            continue;
+        }
+        let trusted = ui.prompt_yes_no(
+            &format!(
+                "The repo config defines merge driver `{program}` for {pattern}. Allow it to run?"
+            ),
+            Some(false),
+        )?;
+        merge_drivers
+            .set_trusted(&command, trusted)
+            .map_err(user_error)?;
+    }
+    Ok(())
+}
+
+/// Warns about the untrusted merge drivers which would have run.
+fn warn_skipped_merge_drivers(ui: &Ui, store: &Store) -> io::Result<()> {
+    for driver in store.merge_drivers().take_skipped() {
+        writeln!(
+            ui.warning_default(),
+            "Ignoring untrusted merge driver `{program}` for {pattern}",
+            program = driver.program(),
+            pattern = driver.pattern(),
+        )?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use clap::CommandFactory as _;
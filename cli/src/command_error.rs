@@ -73,6 +73,7 @@ use crate::merge_tools::ConflictResolveError;
 use crate::merge_tools::DiffEditError;
 use crate::merge_tools::MergeToolConfigError;
 use crate::merge_tools::MergeToolPartialResolutionError;
+use crate::patch_util::PatchError;
 use crate::revset_util::BookmarkNameParseError;
 use crate::revset_util::UserRevsetEvaluationError;
 use crate::template_parser::TemplateParseError;
@@ -86,6 +87,9 @@ pub enum CommandErrorKind {
     /// Invalid command line. The inner error type may be `clap::Error`.
     Cli,
     BrokenPipe,
+    /// Not an error, but a result to be reported by exit status 1. Nothing is
+    /// printed.
+    Silent,
     Internal,
 }
 
@@ -94,6 +98,9 @@ pub struct CommandError {
     pub kind: CommandErrorKind,
     pub error: Arc<dyn error::Error + Send + Sync>,
     pub hints: Vec<ErrorHint>,
+    /// Whether to exit with status 2 instead of 1, which is used by
+    /// [`silent_error()`] to report a result.
+    distinct_from_silent: bool,
 }
 
 impl CommandError {
@@ -105,6 +112,7 @@ impl CommandError {
             kind,
             error: Arc::from(err.into()),
             hints: vec![],
+            distinct_from_silent: false,
         }
     }
 
@@ -127,6 +135,13 @@ impl CommandError {
         self.hints.push(ErrorHint::PlainText(hint.into()));
     }
 
+    /// Makes error exit with status 2 instead of 1 so it can be told apart
+    /// from [`silent_error()`] reporting a result by status 1.
+    pub fn distinct_from_silent(mut self) -> Self {
+        self.distinct_from_silent = self.kind != CommandErrorKind::Silent;
+        self
+    }
+
     /// Appends formatted `hint` to the error.
     pub fn add_formatted_hint(&mut self, hint: FormatRecorder) {
         self.hints.push(ErrorHint::Formatted(hint));
@@ -179,6 +194,12 @@ pub fn user_error(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> Comman
     CommandError::new(CommandErrorKind::User, err)
 }
 
+/// Returns error which makes the command exit with status 1 without printing
+/// anything. For example, `jj diff --exit-code` uses it to report differences.
+pub fn silent_error(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> CommandError {
+    CommandError::new(CommandErrorKind::Silent, err)
+}
+
 pub fn user_error_with_hint(
     err: impl Into<Box<dyn error::Error + Send + Sync>>,
     hint: impl Into<String>,
@@ -398,9 +419,18 @@ impl From<OpStoreError> for CommandError {
     }
 }
 
+/// Hint for errors caused by a broken commit index.
+pub(crate) const INDEX_REBUILD_HINT: &str =
+    "Run `jj debug index rebuild` to rebuild the commit index.";
+
 impl From<RepoLoaderError> for CommandError {
     fn from(err: RepoLoaderError) -> Self {
-        internal_error_with_message("Failed to load the repo", err)
+        let is_index_error = matches!(err, RepoLoaderError::IndexRead(_));
+        let mut cmd_err = internal_error_with_message("Failed to load the repo", err);
+        if is_index_error {
+            cmd_err.add_hint(INDEX_REBUILD_HINT);
+        }
+        cmd_err
     }
 }
 
@@ -440,6 +470,18 @@ impl From<DiffRenderError> for CommandError {
             DiffRenderError::AccessDenied { .. } => user_error(err),
             DiffRenderError::InvalidRepoPath(_) => user_error(err),
             DiffRenderError::Io(err) => err.into(),
+            DiffRenderError::NoSuchParent { .. } => user_error(err),
+        }
+    }
+}
+
+impl From<PatchError> for CommandError {
+    fn from(err: PatchError) -> Self {
+        match err {
+            PatchError::Backend(err) => err.into(),
+            PatchError::Parse { .. } | PatchError::Apply { .. } | PatchError::File { .. } => {
+                user_error(err)
+            }
         }
     }
 }
@@ -893,6 +935,9 @@ fn revset_resolution_error_hints(err: &RevsetResolutionError) -> Vec<String> {
             format!("Use `change_id({symbol})` to select all revisions"),
             "To abandon unneeded revisions, run `jj abandon <commit_id>`".to_owned(),
         ],
+        RevsetResolutionError::ChangeNameHidden { name } => vec![format!(
+            "To remove the name, run `jj change-name delete {name}`"
+        )],
         RevsetResolutionError::ConflictedRef {
             kind: "bookmark",
             symbol,
@@ -949,7 +994,14 @@ fn template_parse_error_hint(err: &TemplateParseError) -> Option<String> {
 const BROKEN_PIPE_EXIT_CODE: u8 = 3;
 
 pub(crate) fn handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> u8 {
-    try_handle_command_result(ui, result).unwrap_or(BROKEN_PIPE_EXIT_CODE)
+    let distinct_from_silent = result
+        .as_ref()
+        .is_err_and(|cmd_err| cmd_err.distinct_from_silent);
+    match try_handle_command_result(ui, result) {
+        Ok(1) if distinct_from_silent => 2,
+        Ok(exit_code) => exit_code,
+        Err(_) => BROKEN_PIPE_EXIT_CODE,
+    }
 }
 
 fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> io::Result<u8> {
@@ -984,6 +1036,7 @@ fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> i
             // A broken pipe is not an error, but a signal to exit gracefully.
             Ok(BROKEN_PIPE_EXIT_CODE)
         }
+        CommandErrorKind::Silent => Ok(1),
         CommandErrorKind::Internal => {
             print_error(ui, "Internal error: ", err, hints)?;
             Ok(255)
@@ -1024,7 +1077,8 @@ pub fn print_error_sources(ui: &Ui, source: Option<&dyn error::Error>) -> io::Re
         })
 }
 
-fn print_error_hints(ui: &Ui, hints: &[ErrorHint]) -> io::Result<()> {
+/// Prints the given `hints` one by one.
+pub fn print_error_hints(ui: &Ui, hints: &[ErrorHint]) -> io::Result<()> {
     for hint in hints {
         ui.stderr_formatter().with_label("hint", |formatter| {
             write!(formatter.labeled("heading"), "Hint: ")?;
@@ -13,12 +13,16 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::io::Write as _;
 
 use clap_complete::ArgValueCompleter;
 use indexmap::IndexSet;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::refs::diff_named_ref_targets;
+use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::rewrite::RewriteRefsOptions;
@@ -26,11 +30,16 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::RevisionsFileArgs;
 #[cfg(feature = "git")]
 use crate::cli_util::has_tracked_remote_bookmarks;
 use crate::cli_util::print_updated_commits;
+use crate::cli_util::short_change_hash;
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::complete;
+use crate::description_util::TextEditor;
 use crate::ui::Ui;
 
 /// Abandon a revision
@@ -56,6 +65,8 @@ pub(crate) struct AbandonArgs {
         add = ArgValueCompleter::new(complete::revset_expression_mutable),
     )]
     revisions_opt: Vec<RevisionArg>,
+    #[command(flatten)]
+    revisions_file: RevisionsFileArgs,
     // TODO: Remove in jj 0.34+
     #[arg(long, short, hide = true)]
     summary: bool,
@@ -67,6 +78,21 @@ pub(crate) struct AbandonArgs {
     /// Do not modify the content of the children of the abandoned commits
     #[arg(long)]
     restore_descendants: bool,
+    /// Choose which of the matched revisions to abandon in an editor
+    ///
+    /// The matched revisions are listed one per line. Remove the lines of the
+    /// revisions that should be kept.
+    #[arg(long, short)]
+    interactive: bool,
+    /// Only print which revisions would be abandoned
+    ///
+    /// Nothing is written to the repository.
+    #[arg(long)]
+    dry_run: bool,
+    /// Do not ask for confirmation if the revisions match more commits than
+    /// `ui.large-revset-warning-threshold`
+    #[arg(long)]
+    no_limit: bool,
 }
 
 #[instrument(skip_all)]
@@ -80,9 +106,11 @@ pub(crate) fn cmd_abandon(
     }
     let mut workspace_command = command.workspace_helper(ui)?;
     let to_abandon = {
-        let targets: Vec<_> = if !args.revisions_pos.is_empty() || !args.revisions_opt.is_empty() {
-            workspace_command
-                .parse_union_revsets(ui, &[&*args.revisions_pos, &*args.revisions_opt].concat())?
+        let targets: Vec<_> = if let Some(revisions) = args
+            .revisions_file
+            .with_revisions(&[&*args.revisions_pos, &*args.revisions_opt].concat())?
+        {
+            workspace_command.parse_union_revsets(ui, &revisions)?
         } else {
             workspace_command.parse_revset(ui, &RevisionArg::AT)?
         }
@@ -100,7 +128,12 @@ pub(crate) fn cmd_abandon(
                 n = targets.len() - visible.len()
             )?;
         }
-        visible
+        if args.interactive && !visible.is_empty() {
+            let text_editor = workspace_command.text_editor()?;
+            select_commits_to_abandon(&text_editor, workspace_command.repo(), visible)?
+        } else {
+            visible
+        }
     };
     if to_abandon.is_empty() {
         writeln!(ui.status(), "No revisions to abandon.")?;
@@ -108,6 +141,23 @@ pub(crate) fn cmd_abandon(
     }
     workspace_command.check_rewritable(&to_abandon)?;
 
+    if args.dry_run {
+        if let Some(mut formatter) = ui.status_formatter() {
+            writeln!(formatter, "Would abandon {} commits:", to_abandon.len())?;
+            let commits: Vec<_> = to_abandon
+                .iter()
+                .map(|id| workspace_command.repo().store().get_commit(id))
+                .try_collect()?;
+            print_updated_commits(
+                formatter.as_mut(),
+                &workspace_command.commit_summary_template(),
+                &commits,
+            )?;
+        }
+        return Ok(());
+    }
+    workspace_command.check_large_revset(ui, &to_abandon, args.no_limit)?;
+
     let mut tx = workspace_command.start_transaction();
     let options = RewriteRefsOptions {
         delete_abandoned_bookmarks: !args.retain_bookmarks,
@@ -200,3 +250,72 @@ pub(crate) fn cmd_abandon(
     }
     Ok(())
 }
+
+/// Lets the user remove commits from `candidates` by editing the list in an
+/// editor. The order of the remaining commits is preserved.
+fn select_commits_to_abandon(
+    editor: &TextEditor,
+    repo: &ReadonlyRepo,
+    candidates: IndexSet<CommitId>,
+) -> Result<IndexSet<CommitId>, CommandError> {
+    let mut content = String::new();
+    writeln!(
+        content,
+        "JJ: Remove the lines of the revisions you don't want to abandon."
+    )
+    .unwrap();
+    writeln!(content, "JJ: Lines starting with \"JJ:\" are ignored.").unwrap();
+    writeln!(content).unwrap();
+    for id in &candidates {
+        let commit = repo.store().get_commit(id)?;
+        let subject = commit.description().lines().next().unwrap_or("");
+        write!(
+            content,
+            "{} {} {}",
+            short_change_hash(commit.change_id()),
+            short_commit_hash(id),
+            if subject.is_empty() {
+                "(no description set)"
+            } else {
+                subject
+            }
+        )
+        .unwrap();
+        let bookmarks = repo
+            .view()
+            .local_bookmarks_for_commit(id)
+            .map(|(name, _)| name.as_symbol())
+            .join(" ");
+        if !bookmarks.is_empty() {
+            write!(content, " ({bookmarks})").unwrap();
+        }
+        writeln!(content).unwrap();
+    }
+
+    let content = editor
+        .edit_str(content, Some(".jjabandon"))
+        .map_err(|err| err.with_name("abandon list"))?;
+
+    let mut selected = IndexSet::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("JJ:") {
+            continue;
+        }
+        let commit_hash = line.split_whitespace().nth(1).unwrap_or("");
+        let id = candidates
+            .iter()
+            .find(|id| !commit_hash.is_empty() && id.hex().starts_with(commit_hash))
+            .ok_or_else(|| {
+                user_error(format!(
+                    "Line doesn't refer to a revision to abandon: {line}"
+                ))
+            })?;
+        selected.insert(id.clone());
+    }
+    // Keep the original order regardless of how the lines were edited.
+    Ok(candidates
+        .into_iter()
+        .filter(|id| selected.contains(id))
+        .collect())
+}
@@ -0,0 +1,74 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use jj_lib::ref_name::RefNameBuf;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::revset_util;
+use crate::ui::Ui;
+
+/// Set the description of a local bookmark
+///
+/// The description is kept when the bookmark is moved or renamed, and is
+/// dropped when the bookmark is deleted. It is shown by `jj bookmark list`.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BookmarkDescribeArgs {
+    /// The bookmark to describe
+    #[arg(
+        value_parser = revset_util::parse_bookmark_name,
+        add = ArgValueCandidates::new(complete::local_bookmarks),
+    )]
+    name: RefNameBuf,
+
+    /// The description to use (an empty description removes it)
+    #[arg(long, short, value_name = "MESSAGE")]
+    message: String,
+}
+
+pub fn cmd_bookmark_describe(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BookmarkDescribeArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let view = workspace_command.repo().view();
+    let name = &args.name;
+    if view.get_local_bookmark(name).is_absent() {
+        return Err(user_error(format!(
+            "No such bookmark: {name}",
+            name = name.as_symbol()
+        )));
+    }
+    if view
+        .get_local_bookmark_description(name)
+        .unwrap_or_default()
+        == args.message
+    {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    tx.repo_mut()
+        .set_local_bookmark_description(name, args.message.clone());
+    tx.finish(
+        ui,
+        format!("describe bookmark {name}", name = name.as_symbol()),
+    )?;
+    Ok(())
+}
@@ -125,6 +125,13 @@ pub struct BookmarkListArgs {
     /// This defaults to the `ui.bookmark-list-sort-keys` setting.
     #[arg(long, value_name = "SORT_KEY", value_enum, value_delimiter = ',')]
     sort: Vec<SortKey>,
+
+    /// Show full bookmark descriptions instead of only their first line
+    ///
+    /// This uses the `templates.bookmark_list_verbose` setting instead of
+    /// `templates.bookmark_list`.
+    #[arg(long, conflicts_with = "template")]
+    verbose: bool,
 }
 
 pub fn cmd_bookmark_list(
@@ -132,7 +139,7 @@ pub fn cmd_bookmark_list(
     command: &CommandHelper,
     args: &BookmarkListArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
+    let workspace_command = command.workspace_helper_for_query(ui)?;
     let repo = workspace_command.repo();
     let view = repo.view();
 
@@ -175,6 +182,9 @@ pub fn cmd_bookmark_list(
         let language = workspace_command.commit_template_language();
         let text = match &args.template {
             Some(value) => value.to_owned(),
+            None if args.verbose => workspace_command
+                .settings()
+                .get("templates.bookmark_list_verbose")?,
             None => workspace_command
                 .settings()
                 .get("templates.bookmark_list")?,
@@ -214,10 +224,11 @@ pub fn cmd_bookmark_list(
 
         let include_local_only = !args.tracked && args.remotes.is_none();
         if include_local_only && local_target.is_present() || !tracked_remote_refs.is_empty() {
-            let primary = CommitRef::local(
+            let primary = CommitRef::local_with_description(
                 name,
                 local_target.clone(),
                 remote_refs.iter().map(|&(_, remote_ref)| remote_ref),
+                view.get_local_bookmark_description(name),
             );
             let tracked = tracked_remote_refs
                 .iter()
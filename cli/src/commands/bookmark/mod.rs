@@ -14,6 +14,7 @@
 
 mod create;
 mod delete;
+mod describe;
 mod forget;
 mod list;
 mod r#move;
@@ -36,6 +37,8 @@ use self::create::BookmarkCreateArgs;
 use self::create::cmd_bookmark_create;
 use self::delete::BookmarkDeleteArgs;
 use self::delete::cmd_bookmark_delete;
+use self::describe::BookmarkDescribeArgs;
+use self::describe::cmd_bookmark_describe;
 use self::forget::BookmarkForgetArgs;
 use self::forget::cmd_bookmark_forget;
 use self::list::BookmarkListArgs;
@@ -71,6 +74,7 @@ pub enum BookmarkCommand {
     Create(BookmarkCreateArgs),
     #[command(visible_alias("d"))]
     Delete(BookmarkDeleteArgs),
+    Describe(BookmarkDescribeArgs),
     #[command(visible_alias("f"))]
     Forget(BookmarkForgetArgs),
     #[command(visible_alias("l"))]
@@ -94,6 +98,7 @@ pub fn cmd_bookmark(
     match subcommand {
         BookmarkCommand::Create(args) => cmd_bookmark_create(ui, command, args),
         BookmarkCommand::Delete(args) => cmd_bookmark_delete(ui, command, args),
+        BookmarkCommand::Describe(args) => cmd_bookmark_describe(ui, command, args),
         BookmarkCommand::Forget(args) => cmd_bookmark_forget(ui, command, args),
         BookmarkCommand::List(args) => cmd_bookmark_list(ui, command, args),
         BookmarkCommand::Move(args) => cmd_bookmark_move(ui, command, args),
@@ -65,9 +65,16 @@ pub fn cmd_bookmark_rename(
         )));
     }
 
+    let description = view
+        .get_local_bookmark_description(old_bookmark)
+        .unwrap_or_default()
+        .to_owned();
+
     let mut tx = workspace_command.start_transaction();
     tx.repo_mut()
         .set_local_bookmark_target(new_bookmark, ref_target);
+    tx.repo_mut()
+        .set_local_bookmark_description(new_bookmark, description);
     tx.repo_mut()
         .set_local_bookmark_target(old_bookmark, RefTarget::absent());
     tx.finish(
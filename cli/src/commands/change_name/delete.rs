@@ -0,0 +1,60 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Delete change names
+///
+/// The named changes are left untouched.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ChangeNameDeleteArgs {
+    /// The names to delete
+    #[arg(required = true)]
+    names: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_change_name_delete(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ChangeNameDeleteArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let view = workspace_command.repo().view();
+    let names = args.names.iter().unique().collect_vec();
+    if let Some(name) = names
+        .iter()
+        .find(|name| view.get_change_name(name).is_none())
+    {
+        return Err(user_error(format!("No such change name: {name}")));
+    }
+    let mut tx = workspace_command.start_transaction();
+    for name in &names {
+        tx.repo_mut().set_change_name(name, None);
+    }
+    writeln!(ui.status(), "Deleted {} change names.", names.len())?;
+    tx.finish(
+        ui,
+        format!("delete change name {}", names.iter().join(", ")),
+    )?;
+    Ok(())
+}
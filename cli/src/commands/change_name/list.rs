@@ -0,0 +1,60 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List change names and the revisions they refer to
+///
+/// Names whose change has no visible revisions, e.g. because it was abandoned,
+/// are listed with their change ID.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ChangeNameListArgs {}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_change_name_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &ChangeNameListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().as_ref();
+    let template = workspace_command.commit_summary_template();
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (name, change_id) in repo.view().change_names() {
+        let commit_ids = repo.resolve_change_id(change_id).unwrap_or_default();
+        if commit_ids.is_empty() {
+            writeln!(
+                formatter,
+                "{name}: {} (no visible revisions)",
+                change_id.reverse_hex()
+            )?;
+        }
+        for commit_id in commit_ids {
+            let commit = repo.store().get_commit(&commit_id)?;
+            write!(formatter, "{name}: ")?;
+            template.format(&commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod delete;
+mod list;
+mod set;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::delete::ChangeNameDeleteArgs;
+use self::delete::cmd_change_name_delete;
+use self::list::ChangeNameListArgs;
+use self::list::cmd_change_name_list;
+use self::set::ChangeNameSetArgs;
+use self::set::cmd_change_name_set;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage names of changes
+///
+/// A change name is a personal alias for a change. Unlike a bookmark, it
+/// refers to the change ID rather than to a commit, so it follows the change
+/// when it's rewritten, e.g. by `jj rebase` or `jj squash`. Change names are
+/// local to the repo and are not pushed to or fetched from Git remotes.
+///
+/// A change name can be used anywhere a revision is expected. If a bookmark
+/// or tag has the same name, the bookmark or tag takes precedence.
+///
+/// In templates, `commit.change_names()` lists the names of a commit.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum ChangeNameCommand {
+    #[command(visible_alias = "d")]
+    Delete(ChangeNameDeleteArgs),
+    #[command(visible_alias = "l")]
+    List(ChangeNameListArgs),
+    #[command(visible_alias = "s")]
+    Set(ChangeNameSetArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_change_name(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &ChangeNameCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        ChangeNameCommand::Delete(args) => cmd_change_name_delete(ui, command, args),
+        ChangeNameCommand::List(args) => cmd_change_name_list(ui, command, args),
+        ChangeNameCommand::Set(args) => cmd_change_name_set(ui, command, args),
+    }
+}
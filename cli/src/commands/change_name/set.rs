@@ -0,0 +1,73 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::revset_util;
+use crate::ui::Ui;
+
+/// Name the change of a revision
+///
+/// If the name already refers to another change, it's moved to this one.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ChangeNameSetArgs {
+    /// The name to give to the change
+    #[arg(value_parser = revset_util::parse_change_name)]
+    name: String,
+    /// The revision whose change to name
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_change_name_set(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ChangeNameSetArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let name = &args.name;
+    let view = workspace_command.repo().view();
+    if view.get_local_bookmark(name.as_ref()).is_present() {
+        writeln!(
+            ui.warning_default(),
+            "Bookmark {name} has the same name, so `{name}` will resolve to the bookmark"
+        )?;
+    } else if view.get_tag(name.as_ref()).is_present() {
+        writeln!(
+            ui.warning_default(),
+            "Tag {name} has the same name, so `{name}` will resolve to the tag"
+        )?;
+    }
+    let mut tx = workspace_command.start_transaction();
+    tx.repo_mut()
+        .set_change_name(name, Some(commit.change_id().clone()));
+    tx.finish(
+        ui,
+        format!("name change {} as {name}", commit.change_id().reverse_hex()),
+    )?;
+    Ok(())
+}
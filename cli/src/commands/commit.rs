@@ -14,15 +14,21 @@
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
 use indoc::writedoc;
 use jj_lib::backend::Signature;
+use jj_lib::matchers::EverythingMatcher;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::check_path_args_match_changes;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::commands::identity::warn_if_unknown_identity;
 use crate::complete;
 use crate::description_util::add_trailers;
 use crate::description_util::description_template;
@@ -67,6 +73,17 @@ pub(crate) struct CommitArgs {
         add = ArgValueCompleter::new(complete::modified_files),
     )]
     paths: Vec<String>,
+    /// Fail if any of the given paths doesn't match a changed file
+    ///
+    /// By default, such paths are only reported as a warning. This can also
+    /// be enabled by the `ui.strict-filesets` setting.
+    #[arg(long)]
+    strict_paths: bool,
+    /// Don't ask for confirmation when changes are left in the working copy
+    ///
+    /// See the `ui.confirm-partial-commit` setting.
+    #[arg(long)]
+    yes: bool,
     /// Reset the author to the configured user
     ///
     /// This resets the author name, email, and timestamp.
@@ -96,6 +113,7 @@ pub(crate) fn cmd_commit(
     args: &CommitArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
+    warn_if_unknown_identity(ui, workspace_command.settings())?;
 
     let commit_id = workspace_command
         .get_wc_commit_id()
@@ -110,6 +128,13 @@ pub(crate) fn cmd_commit(
     let text_editor = workspace_command.text_editor()?;
     let mut tx = workspace_command.start_transaction();
     let base_tree = commit.parent_tree(tx.repo())?;
+    check_path_args_match_changes(
+        ui,
+        tx.base_workspace_helper(),
+        &args.paths,
+        [(&base_tree, &commit.tree()?)],
+        args.strict_paths,
+    )?;
     let format_instructions = || {
         format!(
             "\
@@ -128,12 +153,29 @@ new working-copy commit.
         matcher.as_ref(),
         format_instructions,
     )?;
-    if !args.paths.is_empty() && tree_id == base_tree.id() {
-        writeln!(
-            ui.warning_default(),
-            "The given paths do not match any file: {}",
-            args.paths.join(" ")
-        )?;
+    let num_remaining_files = if args.paths.is_empty() {
+        0
+    } else {
+        let selected_tree = tx.repo().store().get_root_tree(&tree_id)?;
+        selected_tree
+            .diff_stream(&commit.tree()?, &EverythingMatcher)
+            .count()
+            .block_on()
+    };
+    if num_remaining_files > 0
+        && !args.yes
+        && tx.settings().get_bool("ui.confirm-partial-commit")?
+    {
+        let prompt = format!(
+            "{} with changes will be left in the working copy, continue?",
+            format_file_count(num_remaining_files)
+        );
+        if !ui.prompt_yes_no(&prompt, Some(false))? {
+            return Err(user_error_with_hint(
+                "Commit was canceled",
+                "Pass `--yes` to commit only the changes matching the given paths.",
+            ));
+        }
     }
 
     let mut commit_builder = tx.repo_mut().rewrite_commit(&commit).detach();
@@ -182,6 +224,15 @@ new working-copy commit.
     };
     commit_builder.set_description(description);
     let new_commit = commit_builder.write(tx.repo_mut())?;
+    if num_remaining_files > 0 {
+        if let Some(mut formatter) = ui.status_formatter() {
+            writeln!(
+                formatter,
+                "{} with changes left in the working copy",
+                format_file_count(num_remaining_files)
+            )?;
+        }
+    }
 
     let workspace_names = tx.repo().view().workspaces_for_wc_commit_id(commit.id());
     if !workspace_names.is_empty() {
@@ -200,3 +251,11 @@ new working-copy commit.
     tx.finish(ui, format!("commit {}", commit.id().hex()))?;
     Ok(())
 }
+
+fn format_file_count(count: usize) -> String {
+    if count == 1 {
+        "1 file".to_owned()
+    } else {
+        format!("{count} files")
+    }
+}
@@ -16,6 +16,7 @@ mod edit;
 mod get;
 mod list;
 mod path;
+mod schema;
 mod set;
 mod unset;
 
@@ -34,6 +35,8 @@ use self::list::ConfigListArgs;
 use self::list::cmd_config_list;
 use self::path::ConfigPathArgs;
 use self::path::cmd_config_path;
+use self::schema::ConfigSchemaArgs;
+use self::schema::cmd_config_schema;
 use self::set::ConfigSetArgs;
 use self::set::cmd_config_set;
 use self::unset::ConfigUnsetArgs;
@@ -84,7 +87,7 @@ impl ConfigLevelArgs {
         }
     }
 
-    fn edit_config_file(
+    pub(crate) fn edit_config_file(
         &self,
         ui: &Ui,
         command: &CommandHelper,
@@ -142,6 +145,7 @@ pub(crate) enum ConfigCommand {
     List(ConfigListArgs),
     #[command(visible_alias("p"))]
     Path(ConfigPathArgs),
+    Schema(ConfigSchemaArgs),
     #[command(visible_alias("s"))]
     Set(ConfigSetArgs),
     #[command(visible_alias("u"))]
@@ -159,6 +163,7 @@ pub(crate) fn cmd_config(
         ConfigCommand::Get(args) => cmd_config_get(ui, command, args),
         ConfigCommand::List(args) => cmd_config_list(ui, command, args),
         ConfigCommand::Path(args) => cmd_config_path(ui, command, args),
+        ConfigCommand::Schema(args) => cmd_config_schema(ui, command, args),
         ConfigCommand::Set(args) => cmd_config_set(ui, command, args),
         ConfigCommand::Unset(args) => cmd_config_unset(ui, command, args),
     }
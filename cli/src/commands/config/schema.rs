@@ -0,0 +1,153 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::config::CONFIG_SCHEMA;
+use crate::ui::Ui;
+
+/// List known config variables with their types and descriptions
+///
+/// Free-form parts of variable names, such as the name of a merge tool, are
+/// shown as `<name>`.
+///
+/// Use `jj util config-schema` to print the JSON schema itself.
+#[derive(clap::Args, Clone, Debug)]
+pub struct ConfigSchemaArgs {
+    /// Only list variables starting with this name, e.g. `ui` or `git.fetch`
+    #[arg(add = ArgValueCandidates::new(complete::config_keys))]
+    prefix: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub fn cmd_config_schema(
+    ui: &mut Ui,
+    _command: &CommandHelper,
+    args: &ConfigSchemaArgs,
+) -> Result<(), CommandError> {
+    let schema: serde_json::Value = serde_json::from_str(CONFIG_SCHEMA).unwrap();
+    let mut entries = Vec::new();
+    collect_schema_entries(&schema, &schema, "", &mut entries);
+    if let Some(prefix) = &args.prefix {
+        entries.retain(|entry| {
+            entry
+                .name
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        });
+        if entries.is_empty() {
+            return Err(user_error(format!(
+                "No known config variables start with `{prefix}`"
+            )));
+        }
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for entry in &entries {
+        writeln!(formatter, "{}: {}", entry.name, entry.type_name)?;
+        if let Some(description) = &entry.description {
+            for line in description.lines() {
+                writeln!(formatter, "    {line}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+struct SchemaEntry {
+    name: String,
+    type_name: String,
+    description: Option<String>,
+}
+
+fn collect_schema_entries(
+    root: &serde_json::Value,
+    schema: &serde_json::Value,
+    prefix: &str,
+    acc: &mut Vec<SchemaEntry>,
+) {
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+    let additional = schema
+        .get("additionalProperties")
+        .filter(|value| value.is_object());
+    if properties.is_none() && additional.is_none() {
+        acc.push(SchemaEntry {
+            name: prefix.to_owned(),
+            type_name: schema_type_name(root, schema),
+            description: schema
+                .get("description")
+                .and_then(|d| d.as_str())
+                .map(ToOwned::to_owned),
+        });
+        return;
+    }
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+    for (key, value) in properties.into_iter().flatten() {
+        // Conditional tables are documented separately.
+        if key.starts_with("--") {
+            continue;
+        }
+        collect_schema_entries(root, value, &join(key), acc);
+    }
+    if let Some(value) = additional {
+        collect_schema_entries(root, value, &join("<name>"), acc);
+    }
+}
+
+fn schema_type_name(root: &serde_json::Value, schema: &serde_json::Value) -> String {
+    if let Some(target) = schema
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|pointer| pointer.strip_prefix('#'))
+        .and_then(|p| root.pointer(p))
+    {
+        return schema_type_name(root, target);
+    }
+    if let Some(values) = schema.get("enum").and_then(|v| v.as_array()) {
+        return values.iter().map(|value| value.to_string()).join(" | ");
+    }
+    if let Some(variants) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(|v| v.as_array())
+    {
+        return variants
+            .iter()
+            .map(|variant| schema_type_name(root, variant))
+            .unique()
+            .join(" | ");
+    }
+    match schema.get("type") {
+        Some(serde_json::Value::String(name)) => name.clone(),
+        Some(serde_json::Value::Array(names)) => {
+            names.iter().filter_map(|name| name.as_str()).join(" | ")
+        }
+        _ => "any".to_owned(),
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+
+use bstr::ByteSlice as _;
+use itertools::Itertools as _;
+use jj_lib::backend::TreeValue;
+use jj_lib::conflicts::extract_as_single_hunk;
+use jj_lib::diff::Diff;
+use jj_lib::diff::DiffHunkKind;
+use jj_lib::merge::Merge;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use pollster::FutureExt as _;
+use serde::Serialize;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Print conflicts at a revision as JSON lines
+///
+/// One JSON object is printed per conflicted path:
+///
+/// * `path`: repository-relative path of the file.
+/// * `sides`, `bases`: list of terms of the conflict. Each term is `null` if
+///   the file is deleted on that side, or an object with `type` (`"file"`,
+///   `"symlink"`, `"tree"`, or `"git-submodule"`) and `id`. File terms also
+///   have `executable`.
+/// * `hunks`: list of regions where the sides disagree, or `null` if the
+///   conflict isn't between text files. Each region has `sides` and `bases`
+///   line ranges (`start` is 1-based, `len` is the number of lines).
+///
+/// File contents aren't included. Use `jj debug object file <path> <id>` to
+/// fetch them.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugConflictsArgs {
+    #[arg(long, short = 'r', value_name = "REVSET", default_value = "@")]
+    revision: RevisionArg,
+    #[arg(value_name = "FILESETS")]
+    paths: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ConflictEntry {
+    path: String,
+    sides: Vec<Option<ConflictTerm>>,
+    bases: Vec<Option<ConflictTerm>>,
+    hunks: Option<Vec<ConflictHunk>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum ConflictTerm {
+    File { id: String, executable: bool },
+    Symlink { id: String },
+    Tree { id: String },
+    GitSubmodule { id: String },
+}
+
+#[derive(Serialize)]
+struct ConflictHunk {
+    sides: Vec<LineRange>,
+    bases: Vec<LineRange>,
+}
+
+#[derive(Clone, Copy, Serialize)]
+struct LineRange {
+    start: usize,
+    len: usize,
+}
+
+pub fn cmd_debug_conflicts(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugConflictsArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher();
+    let store = workspace_command.repo().store();
+    let tree = commit.tree()?;
+    for (path, conflict) in tree.conflicts() {
+        if !matcher.matches(&path) {
+            continue;
+        }
+        let conflict = conflict?.simplify();
+        let hunks = if let Some(file_ids) = conflict.to_file_merge() {
+            let contents = extract_as_single_hunk(&file_ids, store, &path).block_on()?;
+            if contents.iter().any(|content| content.contains(&0)) {
+                None
+            } else {
+                Some(conflict_hunks(&contents))
+            }
+        } else {
+            None
+        };
+        let to_term = |value: &Option<TreeValue>| value.as_ref().and_then(conflict_term);
+        let entry = ConflictEntry {
+            path: path.as_internal_file_string().to_owned(),
+            sides: conflict.adds().map(to_term).collect(),
+            bases: conflict.removes().map(to_term).collect(),
+            hunks,
+        };
+        writeln!(ui.stdout(), "{}", serde_json::to_string(&entry).unwrap())?;
+    }
+    Ok(())
+}
+
+fn conflict_term(value: &TreeValue) -> Option<ConflictTerm> {
+    match value {
+        TreeValue::File { id, executable, .. } => Some(ConflictTerm::File {
+            id: id.hex(),
+            executable: *executable,
+        }),
+        TreeValue::Symlink(id) => Some(ConflictTerm::Symlink { id: id.hex() }),
+        TreeValue::Tree(id) => Some(ConflictTerm::Tree { id: id.hex() }),
+        TreeValue::GitSubmodule(id) => Some(ConflictTerm::GitSubmodule { id: id.hex() }),
+        TreeValue::Conflict(_) => None,
+    }
+}
+
+/// Splits file `contents` into hunks, and returns the line ranges of the
+/// hunks which couldn't be resolved.
+fn conflict_hunks(contents: &Merge<impl AsRef<[u8]>>) -> Vec<ConflictHunk> {
+    // Hunks are split in the same way as files::merge_hunks(), but line
+    // positions are tracked per term since the terms of a resolved hunk can
+    // differ in length.
+    let num_bases = contents.removes().len();
+    let diff = Diff::by_line(contents.removes().chain(contents.adds()));
+    // Next line number of each term, bases first
+    let mut positions = vec![1; contents.as_slice().len()];
+    let mut conflict_hunks = vec![];
+    for hunk in diff.hunks() {
+        let ranges = hunk
+            .contents
+            .iter()
+            .zip(&mut positions)
+            .map(|(text, pos)| {
+                let range = LineRange {
+                    start: *pos,
+                    len: text.lines_with_terminator().count(),
+                };
+                *pos += range.len;
+                range
+            })
+            .collect_vec();
+        if hunk.kind == DiffHunkKind::Matching {
+            continue;
+        }
+        let merge = Merge::from_removes_adds(
+            hunk.contents[..num_bases].iter(),
+            hunk.contents[num_bases..].iter(),
+        );
+        if merge.resolve_trivial().is_none() {
+            conflict_hunks.push(ConflictHunk {
+                sides: ranges[num_bases..].to_vec(),
+                bases: ranges[..num_bases].to_vec(),
+            });
+        }
+    }
+    conflict_hunks
+}
@@ -42,14 +42,21 @@ pub fn cmd_debug_copy_detection(
     let store = ws.repo().store();
 
     let commit = ws.resolve_single_rev(ui, &args.revision)?;
+    let tree = commit.tree()?;
     for parent_id in commit.parent_ids() {
         for CopyRecord { target, source, .. } in
             block_on_stream(store.get_copy_records(None, parent_id, commit.id())?)
                 .filter_map(|r| r.ok())
         {
+            // The source of a rename no longer exists.
+            let suffix = if tree.path_value(&source)?.is_present() {
+                " (copied)"
+            } else {
+                ""
+            };
             writeln!(
                 ui.stdout(),
-                "{} -> {}",
+                "{} -> {}{suffix}",
                 source.as_internal_file_string(),
                 target.as_internal_file_string()
             )?;
@@ -15,23 +15,61 @@
 use std::fmt::Debug;
 use std::io::Write as _;
 
+use itertools::Itertools as _;
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::default_index::DefaultIndexStoreError;
 use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::default_index::IndexVerificationIssue;
+use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::short_operation_hash;
 use crate::command_error::CommandError;
+use crate::command_error::INDEX_REBUILD_HINT;
 use crate::command_error::internal_error;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::ui::Ui;
 
 /// Show commit index stats
+///
+/// Use the `verify` and `rebuild` subcommands to diagnose and repair a
+/// corrupted index.
 #[derive(clap::Args, Clone, Debug)]
-pub struct DebugIndexArgs {}
+#[command(args_conflicts_with_subcommands = true)]
+pub struct DebugIndexArgs {
+    #[command(subcommand)]
+    command: Option<DebugIndexCommand>,
+}
+
+#[derive(clap::Subcommand, Clone, Debug)]
+enum DebugIndexCommand {
+    Verify(DebugIndexVerifyArgs),
+    Rebuild(DebugIndexRebuildArgs),
+}
+
+/// Check the commit index against the segment checksums, the operation's
+/// view, and the commit store
+#[derive(clap::Args, Clone, Debug)]
+struct DebugIndexVerifyArgs {}
+
+/// Discard the commit index and rebuild it from scratch
+#[derive(clap::Args, Clone, Debug)]
+struct DebugIndexRebuildArgs {}
 
 pub fn cmd_debug_index(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &DebugIndexArgs,
+    args: &DebugIndexArgs,
 ) -> Result<(), CommandError> {
+    match &args.command {
+        None => cmd_debug_index_stats(ui, command),
+        Some(DebugIndexCommand::Verify(_)) => cmd_debug_index_verify(ui, command),
+        Some(DebugIndexCommand::Rebuild(_)) => cmd_debug_index_rebuild(ui, command),
+    }
+}
+
+fn cmd_debug_index_stats(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
     // Resolve the operation without loading the repo, so this command won't
     // update the index.
     let workspace = command.load_workspace()?;
@@ -86,3 +124,105 @@ pub fn cmd_debug_index(
     }
     Ok(())
 }
+
+fn cmd_debug_index_verify(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    // Resolve the operation without loading the repo, so this command won't
+    // update the index.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    let index_store = repo_loader.index_store();
+    let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>() else {
+        return Err(user_error(format!(
+            "Cannot verify indexes of type '{}'",
+            index_store.name()
+        )));
+    };
+    let mut progress = crate::progress::counter_progress(ui, "Verifying commits");
+    let result = default_index_store.verify_index_at_operation(
+        &op,
+        repo_loader.store(),
+        &mut |done, total| {
+            if let Some(progress) = &mut progress {
+                progress(done, total);
+            }
+        },
+    );
+    drop(progress);
+    let issues = match result {
+        Ok(issues) => issues,
+        Err(
+            err @ (DefaultIndexStoreError::LoadAssociation(_)
+            | DefaultIndexStoreError::LoadIndex(_)),
+        ) => {
+            return Err(user_error_with_hint(
+                format!("Failed to load index: {err}"),
+                INDEX_REBUILD_HINT,
+            ));
+        }
+        Err(err) => return Err(internal_error(err)),
+    };
+    if issues.is_empty() {
+        writeln!(ui.status(), "No problems found in the index.")?;
+        return Ok(());
+    }
+    for issue in &issues {
+        match issue {
+            IndexVerificationIssue::ChecksumMismatch { segment_name } => {
+                writeln!(ui.stdout(), "Checksum mismatch in segment {segment_name}")?;
+            }
+            IndexVerificationIssue::MissingCommit { commit_id } => {
+                writeln!(ui.stdout(), "Missing commit {commit_id}")?;
+            }
+            IndexVerificationIssue::UnknownCommit { commit_id } => {
+                writeln!(ui.stdout(), "Indexed commit {commit_id} not found in store")?;
+            }
+            IndexVerificationIssue::ParentMismatch {
+                commit_id,
+                indexed_parent_ids,
+                actual_parent_ids,
+            } => {
+                writeln!(
+                    ui.stdout(),
+                    "Bad parents of commit {commit_id}: indexed [{}], actual [{}]",
+                    indexed_parent_ids.iter().join(", "),
+                    actual_parent_ids.iter().join(", "),
+                )?;
+            }
+        }
+    }
+    Err(user_error_with_hint(
+        format!("Found {} problem(s) in the index", issues.len()),
+        INDEX_REBUILD_HINT,
+    ))
+}
+
+fn cmd_debug_index_rebuild(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    // Resolve the operation without loading the repo. The index might be
+    // unreadable.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    let index_store = repo_loader.index_store();
+    let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>() else {
+        return Err(user_error(format!(
+            "Cannot rebuild indexes of type '{}'",
+            index_store.name()
+        )));
+    };
+    writeln!(
+        ui.status(),
+        "Rebuilding index at operation {}...",
+        short_operation_hash(op.id())
+    )?;
+    let default_index = default_index_store
+        .rebuild_index_at_operation(&op, repo_loader.store())
+        .block_on()
+        .map_err(internal_error)?;
+    writeln!(
+        ui.status(),
+        "Finished indexing {} commits.",
+        default_index.num_commits()
+    )?;
+    Ok(())
+}
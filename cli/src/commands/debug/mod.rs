@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod conflicts;
 mod copy_detection;
 mod fileset;
 mod index;
@@ -19,6 +20,7 @@ mod index_changed_paths;
 mod init_simple;
 mod local_working_copy;
 mod object;
+mod op_bundle;
 mod reindex;
 mod revset;
 mod snapshot;
@@ -33,6 +35,8 @@ use std::fmt::Debug;
 use clap::Subcommand;
 use jj_lib::local_working_copy::LocalWorkingCopy;
 
+use self::conflicts::DebugConflictsArgs;
+use self::conflicts::cmd_debug_conflicts;
 use self::copy_detection::CopyDetectionArgs;
 use self::copy_detection::cmd_debug_copy_detection;
 use self::fileset::DebugFilesetArgs;
@@ -47,6 +51,8 @@ use self::local_working_copy::DebugLocalWorkingCopyArgs;
 use self::local_working_copy::cmd_debug_local_working_copy;
 use self::object::DebugObjectArgs;
 use self::object::cmd_debug_object;
+use self::op_bundle::DebugOpBundleCommand;
+use self::op_bundle::cmd_debug_op_bundle;
 use self::reindex::DebugReindexArgs;
 use self::reindex::cmd_debug_reindex;
 use self::revset::DebugRevsetArgs;
@@ -70,6 +76,7 @@ use crate::ui::Ui;
 #[derive(Subcommand, Clone, Debug)]
 #[command(hide = true)]
 pub enum DebugCommand {
+    Conflicts(DebugConflictsArgs),
     CopyDetection(CopyDetectionArgs),
     Fileset(DebugFilesetArgs),
     Index(DebugIndexArgs),
@@ -78,6 +85,8 @@ pub enum DebugCommand {
     LocalWorkingCopy(DebugLocalWorkingCopyArgs),
     #[command(subcommand)]
     Object(DebugObjectArgs),
+    #[command(subcommand)]
+    OpBundle(DebugOpBundleCommand),
     Reindex(DebugReindexArgs),
     Revset(DebugRevsetArgs),
     Snapshot(DebugSnapshotArgs),
@@ -94,6 +103,7 @@ pub fn cmd_debug(
     subcommand: &DebugCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        DebugCommand::Conflicts(args) => cmd_debug_conflicts(ui, command, args),
         DebugCommand::CopyDetection(args) => cmd_debug_copy_detection(ui, command, args),
         DebugCommand::Fileset(args) => cmd_debug_fileset(ui, command, args),
         DebugCommand::Index(args) => cmd_debug_index(ui, command, args),
@@ -101,6 +111,7 @@ pub fn cmd_debug(
         DebugCommand::InitSimple(args) => cmd_debug_init_simple(ui, command, args),
         DebugCommand::LocalWorkingCopy(args) => cmd_debug_local_working_copy(ui, command, args),
         DebugCommand::Object(args) => cmd_debug_object(ui, command, args),
+        DebugCommand::OpBundle(args) => cmd_debug_op_bundle(ui, command, args),
         DebugCommand::Reindex(args) => cmd_debug_reindex(ui, command, args),
         DebugCommand::Revset(args) => cmd_debug_revset(ui, command, args),
         DebugCommand::Snapshot(args) => cmd_debug_snapshot(ui, command, args),
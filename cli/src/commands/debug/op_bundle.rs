@@ -0,0 +1,740 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::slice;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use clap::Subcommand;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Timestamp;
+use jj_lib::content_hash::blake2b_hash;
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::default_submodule_store::DefaultSubmoduleStore;
+use jj_lib::graph::GraphEdge;
+use jj_lib::hex_util;
+use jj_lib::merge::Merge;
+use jj_lib::object_id::HexPrefix;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::object_id::PrefixResolution;
+use jj_lib::op_store;
+use jj_lib::op_store::OpStore;
+use jj_lib::op_store::OpStoreError;
+use jj_lib::op_store::OpStoreResult;
+use jj_lib::op_store::OperationId;
+use jj_lib::op_store::OperationMetadata;
+use jj_lib::op_store::RefTarget;
+use jj_lib::op_store::RemoteRef;
+use jj_lib::op_store::RemoteRefState;
+use jj_lib::op_store::TimestampRange;
+use jj_lib::op_store::ViewId;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::repo::RepoLoader;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::signing::Signer;
+use jj_lib::simple_backend::SimpleBackend;
+use jj_lib::simple_op_heads_store::SimpleOpHeadsStore;
+use jj_lib::store::Store;
+use jj_lib::view::View;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::LogContentFormat;
+use crate::cli_util::format_template;
+use crate::cli_util::short_commit_hash;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::commands::operation::diff::show_view_refs_diff;
+use crate::commands::operation::diff::write_ref_target_summary;
+use crate::formatter::Formatter;
+use crate::graphlog::GraphStyle;
+use crate::graphlog::get_graphlog;
+use crate::operation_templater::OperationTemplateLanguage;
+use crate::ui::Ui;
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// Export or inspect operation history without the repository contents
+///
+/// A bundle contains the metadata of operations and summaries of the views
+/// (heads, working-copy commits, bookmarks, and tags) at these operations. It
+/// doesn't contain any commit or file contents.
+#[derive(Subcommand, Clone, Debug)]
+pub enum DebugOpBundleCommand {
+    Export(DebugOpBundleExportArgs),
+    Inspect(DebugOpBundleInspectArgs),
+}
+
+/// Write recent operations to a bundle file
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugOpBundleExportArgs {
+    /// File to write the bundle to
+    #[arg(long, short, value_hint = clap::ValueHint::FilePath)]
+    output: PathBuf,
+    /// Limit number of operations to export
+    #[arg(long, short = 'n')]
+    limit: Option<usize>,
+    /// Replace bookmark and tag names with hashes
+    ///
+    /// The names are also replaced where they appear in operation descriptions
+    /// and tags.
+    #[arg(long)]
+    redact: bool,
+}
+
+/// Show the operation log stored in a bundle file
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugOpBundleInspectArgs {
+    /// Bundle file written by `jj debug op-bundle export`
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    path: PathBuf,
+    /// Don't show the graph, show a flat list of operations
+    #[arg(long)]
+    no_graph: bool,
+    /// Show changes to the view at each operation
+    ///
+    /// Since the bundle doesn't contain commits, commits are shown by their
+    /// IDs.
+    #[arg(long, short = 'd')]
+    op_diff: bool,
+}
+
+pub fn cmd_debug_op_bundle(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &DebugOpBundleCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        DebugOpBundleCommand::Export(args) => cmd_debug_op_bundle_export(ui, command, args),
+        DebugOpBundleCommand::Inspect(args) => cmd_debug_op_bundle_inspect(ui, command, args),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct OpBundle {
+    version: u32,
+    root_operation: String,
+    current_operation: String,
+    /// Operations in topological order, children first.
+    operations: Vec<BundleOperation>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BundleOperation {
+    id: String,
+    parents: Vec<String>,
+    start_time: BundleTimestamp,
+    end_time: BundleTimestamp,
+    description: String,
+    hostname: String,
+    username: String,
+    is_snapshot: bool,
+    tags: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    workspace_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cwd: Option<String>,
+    view: BundleView,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BundleTimestamp {
+    millis_since_epoch: i64,
+    tz_offset: i32,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct BundleView {
+    heads: Vec<String>,
+    working_copies: BTreeMap<String, String>,
+    local_bookmarks: BTreeMap<String, BundleRefTarget>,
+    /// Remote bookmarks keyed by remote name and then bookmark name.
+    remote_bookmarks: BTreeMap<String, BTreeMap<String, BundleRemoteRef>>,
+    tags: BTreeMap<String, BundleRefTarget>,
+}
+
+/// Terms of the ref target in `Merge` order. `None` means absent.
+type BundleRefTarget = Vec<Option<String>>;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct BundleRemoteRef {
+    target: BundleRefTarget,
+    tracked: bool,
+}
+
+fn cmd_debug_op_bundle_export(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugOpBundleExportArgs,
+) -> Result<(), CommandError> {
+    // Like `jj op log`, don't load the repo so that the history can be
+    // exported even if the repo state is broken.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let current_op = command.resolve_operation(ui, repo_loader)?;
+    let operations: Vec<_> = op_walk::walk_ancestors(slice::from_ref(&current_op))
+        .take(args.limit.unwrap_or(usize::MAX))
+        .map(|op| -> Result<_, CommandError> {
+            let op = op?;
+            let view = op.view()?;
+            Ok(bundle_operation(&op, &view))
+        })
+        .try_collect()?;
+    let mut bundle = OpBundle {
+        version: BUNDLE_VERSION,
+        root_operation: repo_loader.op_store().root_operation_id().hex(),
+        current_operation: current_op.id().hex(),
+        operations,
+    };
+    if args.redact {
+        redact_ref_names(&mut bundle);
+    }
+
+    let text = serde_json::to_string_pretty(&bundle)
+        .map_err(|err| user_error_with_message("Failed to serialize operation bundle", err))?;
+    fs::write(&args.output, text).map_err(|err| {
+        user_error_with_message(
+            format!("Failed to write bundle to {}", args.output.display()),
+            err,
+        )
+    })?;
+    writeln!(
+        ui.status(),
+        "Exported {} operations to {}",
+        bundle.operations.len(),
+        args.output.display()
+    )?;
+    Ok(())
+}
+
+fn bundle_operation(op: &Operation, view: &View) -> BundleOperation {
+    let metadata = op.metadata();
+    let bundle_timestamp = |timestamp: &Timestamp| BundleTimestamp {
+        millis_since_epoch: timestamp.timestamp.0,
+        tz_offset: timestamp.tz_offset,
+    };
+    let bundle_target = |target: &RefTarget| -> BundleRefTarget {
+        target
+            .as_merge()
+            .iter()
+            .map(|id| id.as_ref().map(|id| id.hex()))
+            .collect()
+    };
+    let store_view = view.store_view();
+    BundleOperation {
+        id: op.id().hex(),
+        parents: op.parent_ids().iter().map(|id| id.hex()).collect(),
+        start_time: bundle_timestamp(&metadata.time.start),
+        end_time: bundle_timestamp(&metadata.time.end),
+        description: metadata.description.clone(),
+        hostname: metadata.hostname.clone(),
+        username: metadata.username.clone(),
+        is_snapshot: metadata.is_snapshot,
+        tags: metadata
+            .tags
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        workspace_name: metadata
+            .workspace_name
+            .as_ref()
+            .map(|name| name.as_str().to_owned()),
+        cwd: metadata
+            .cwd
+            .as_ref()
+            .map(|path| path.as_internal_file_string().to_owned()),
+        view: BundleView {
+            heads: store_view
+                .head_ids
+                .iter()
+                .map(|id| id.hex())
+                .sorted()
+                .collect(),
+            working_copies: store_view
+                .wc_commit_ids
+                .iter()
+                .map(|(name, id)| (name.as_str().to_owned(), id.hex()))
+                .collect(),
+            local_bookmarks: store_view
+                .local_bookmarks
+                .iter()
+                .map(|(name, target)| (name.as_str().to_owned(), bundle_target(target)))
+                .collect(),
+            remote_bookmarks: store_view
+                .remote_views
+                .iter()
+                .map(|(remote, remote_view)| {
+                    let bookmarks = remote_view
+                        .bookmarks
+                        .iter()
+                        .map(|(name, remote_ref)| {
+                            let remote_ref = BundleRemoteRef {
+                                target: bundle_target(&remote_ref.target),
+                                tracked: remote_ref.is_tracked(),
+                            };
+                            (name.as_str().to_owned(), remote_ref)
+                        })
+                        .collect();
+                    (remote.as_str().to_owned(), bookmarks)
+                })
+                .collect(),
+            tags: store_view
+                .tags
+                .iter()
+                .map(|(name, target)| (name.as_str().to_owned(), bundle_target(target)))
+                .collect(),
+        },
+    }
+}
+
+/// Replaces bookmark and tag names with hashes, including where the names
+/// appear in operation descriptions and tags (e.g. the command arguments).
+fn redact_ref_names(bundle: &mut OpBundle) {
+    let redacted_names: HashMap<String, String> = bundle
+        .operations
+        .iter()
+        .flat_map(|op| {
+            let view = &op.view;
+            itertools::chain!(
+                view.local_bookmarks.keys(),
+                view.remote_bookmarks.values().flat_map(|refs| refs.keys()),
+                view.tags.keys(),
+            )
+        })
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let hash = hex_util::encode_hex(&blake2b_hash(name)[..6]);
+            (name.clone(), format!("redacted-{hash}"))
+        })
+        .collect();
+    // Try longer names first so a name isn't replaced by its prefix.
+    let names_to_replace = redacted_names
+        .iter()
+        .sorted_by_key(|(name, _)| (usize::MAX - name.len(), name.as_str()))
+        .collect_vec();
+    for op in &mut bundle.operations {
+        op.description = redact_text(&op.description, &names_to_replace);
+        for value in op.tags.values_mut() {
+            *value = redact_text(value, &names_to_replace);
+        }
+        let view = &mut op.view;
+        redact_keys(&mut view.local_bookmarks, &redacted_names);
+        for refs in view.remote_bookmarks.values_mut() {
+            redact_keys(refs, &redacted_names);
+        }
+        redact_keys(&mut view.tags, &redacted_names);
+    }
+}
+
+fn redact_keys<V>(map: &mut BTreeMap<String, V>, redacted_names: &HashMap<String, String>) {
+    *map = std::mem::take(map)
+        .into_iter()
+        .map(|(name, value)| (redacted_names[&name].clone(), value))
+        .collect();
+}
+
+/// Replaces occurrences of the names in `text` which aren't part of a longer
+/// word.
+fn redact_text(text: &str, names: &[(&String, &String)]) -> String {
+    let is_name_char = |c: char| c.is_alphanumeric() || "-_/".contains(c);
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut prev_char = None;
+    'outer: while let Some(c) = rest.chars().next() {
+        if !prev_char.is_some_and(is_name_char) {
+            for (name, replacement) in names {
+                if let Some(after) = rest.strip_prefix(name.as_str()) {
+                    if !after.starts_with(is_name_char) {
+                        redacted.push_str(replacement);
+                        prev_char = name.chars().next_back();
+                        rest = after;
+                        continue 'outer;
+                    }
+                }
+            }
+        }
+        redacted.push(c);
+        prev_char = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    redacted
+}
+
+fn cmd_debug_op_bundle_inspect(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugOpBundleInspectArgs,
+) -> Result<(), CommandError> {
+    let text = fs::read_to_string(&args.path).map_err(|err| {
+        user_error_with_message(
+            format!("Failed to read bundle from {}", args.path.display()),
+            err,
+        )
+    })?;
+    let bundle: OpBundle = serde_json::from_str(&text)
+        .map_err(|err| user_error_with_message("Failed to parse operation bundle", err))?;
+    if bundle.version != BUNDLE_VERSION {
+        return Err(user_error(format!(
+            "Unsupported operation bundle version {}",
+            bundle.version
+        )));
+    }
+    let op_store = Arc::new(BundleOpStore::new(&bundle)?);
+    let current_op_id = parse_operation_id(&bundle.current_operation)?;
+    let operations: Vec<_> = bundle
+        .operations
+        .iter()
+        .map(|op| -> Result<_, CommandError> {
+            let id = parse_operation_id(&op.id)?;
+            let data = op_store.operations[&id].clone();
+            let view = View::new(load_view(&op.view)?);
+            Ok((Operation::new(op_store.clone(), id, data), view))
+        })
+        .try_collect()?;
+
+    let settings = command.settings();
+    let repo_loader = bundle_repo_loader(command, op_store.clone());
+    let graph_style = GraphStyle::from_settings(settings)?;
+    let with_content_format = LogContentFormat::new(ui, settings)?;
+    let language =
+        OperationTemplateLanguage::new(&repo_loader, Some(&current_op_id), &[] as &[Box<_>]);
+    let template = command
+        .parse_template(ui, &language, &settings.get_string("templates.op_log")?)?
+        .labeled(["op_log", "operation"]);
+    let op_node_template = command
+        .parse_template(
+            ui,
+            &language,
+            &settings.get_string("templates.op_log_node")?,
+        )?
+        .labeled(["op_log", "operation", "node"]);
+    let views_by_id: HashMap<&OperationId, &View> = operations
+        .iter()
+        .map(|(op, view)| (op.id(), view))
+        .collect();
+    let show_op_diff = |formatter: &mut dyn Formatter,
+                        op: &Operation,
+                        view: &View,
+                        with_content_format: &LogContentFormat|
+     -> Result<(), CommandError> {
+        // Like `jj op log --op-diff`, merge operations aren't diffed.
+        if let [parent_id] = op.parent_ids() {
+            if let Some(parent_view) = views_by_id.get(parent_id) {
+                write_view_diff(formatter, parent_view, view, with_content_format)?;
+            }
+        }
+        Ok(())
+    };
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let formatter = formatter.as_mut();
+    if !args.no_graph {
+        let mut raw_output = formatter.raw()?;
+        let mut graph = get_graphlog(graph_style, raw_output.as_mut());
+        for (op, view) in &operations {
+            // Parents which weren't exported are shown as missing.
+            let edges = op
+                .parent_ids()
+                .iter()
+                .map(|id| {
+                    if views_by_id.contains_key(id) {
+                        GraphEdge::direct(id.clone())
+                    } else {
+                        GraphEdge::missing(id.clone())
+                    }
+                })
+                .collect_vec();
+            let mut buffer = vec![];
+            let within_graph = with_content_format.sub_width(graph.width(op.id(), &edges));
+            within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
+                template.format(op, formatter)
+            })?;
+            if !buffer.ends_with(b"\n") {
+                buffer.push(b'\n');
+            }
+            if args.op_diff {
+                let mut formatter = ui.new_formatter(&mut buffer);
+                show_op_diff(formatter.as_mut(), op, view, &within_graph)?;
+            }
+            let node_symbol = format_template(ui, op, &op_node_template);
+            graph.add_node(
+                op.id(),
+                &edges,
+                &node_symbol,
+                &String::from_utf8_lossy(&buffer),
+            )?;
+        }
+    } else {
+        for (op, view) in &operations {
+            with_content_format.write(formatter, |formatter| template.format(op, formatter))?;
+            if args.op_diff {
+                show_op_diff(formatter, op, view, &with_content_format)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Creates a repo loader which the operation templates can be evaluated with.
+fn bundle_repo_loader(command: &CommandHelper, op_store: Arc<BundleOpStore>) -> RepoLoader {
+    // Only the operation store is accessed by the operation templates. The
+    // other stores are never read, so they don't need to exist on disk.
+    let path = Path::new("");
+    RepoLoader::new(
+        command.settings().clone(),
+        Store::new(
+            Box::new(SimpleBackend::load(path)),
+            Signer::new(None, vec![]),
+            Default::default(),
+        ),
+        op_store,
+        Arc::new(SimpleOpHeadsStore::load(path)),
+        Arc::new(DefaultIndexStore::load(path)),
+        Arc::new(DefaultSubmoduleStore::load(path)),
+    )
+}
+
+fn parse_operation_id(hex: &str) -> Result<OperationId, CommandError> {
+    OperationId::try_from_hex(hex)
+        .ok_or_else(|| user_error(format!("Invalid operation ID in bundle: {hex}")))
+}
+
+fn parse_commit_id(hex: &str) -> Result<CommitId, CommandError> {
+    CommitId::try_from_hex(hex)
+        .ok_or_else(|| user_error(format!("Invalid commit ID in bundle: {hex}")))
+}
+
+fn load_ref_target(terms: &BundleRefTarget) -> Result<RefTarget, CommandError> {
+    if terms.len() % 2 != 1 {
+        return Err(user_error("Invalid ref target in bundle"));
+    }
+    let terms: Vec<_> = terms
+        .iter()
+        .map(|term| term.as_deref().map(parse_commit_id).transpose())
+        .try_collect()?;
+    Ok(RefTarget::from_merge(Merge::from_vec(terms)))
+}
+
+fn load_view(view: &BundleView) -> Result<op_store::View, CommandError> {
+    let mut store_view = op_store::View::empty();
+    for head in &view.heads {
+        store_view.head_ids.insert(parse_commit_id(head)?);
+    }
+    for (name, id) in &view.working_copies {
+        store_view
+            .wc_commit_ids
+            .insert(name.into(), parse_commit_id(id)?);
+    }
+    for (name, target) in &view.local_bookmarks {
+        store_view
+            .local_bookmarks
+            .insert(name.into(), load_ref_target(target)?);
+    }
+    for (remote, refs) in &view.remote_bookmarks {
+        let remote_view = store_view.remote_views.entry(remote.into()).or_default();
+        for (name, remote_ref) in refs {
+            let remote_ref = RemoteRef {
+                target: load_ref_target(&remote_ref.target)?,
+                state: if remote_ref.tracked {
+                    RemoteRefState::Tracked
+                } else {
+                    RemoteRefState::New
+                },
+            };
+            remote_view.bookmarks.insert(name.into(), remote_ref);
+        }
+    }
+    for (name, target) in &view.tags {
+        store_view
+            .tags
+            .insert(name.into(), load_ref_target(target)?);
+    }
+    Ok(store_view)
+}
+
+/// Writes changes between the views in the same format as `jj op diff`, but
+/// with commit IDs instead of commit summaries.
+fn write_view_diff(
+    formatter: &mut dyn Formatter,
+    from_view: &View,
+    to_view: &View,
+    with_content_format: &LogContentFormat,
+) -> Result<(), CommandError> {
+    let write_commit_id =
+        |formatter: &mut dyn Formatter, commit_id: &CommitId| -> Result<(), CommandError> {
+            write!(
+                formatter.labeled("commit_id"),
+                "{}",
+                short_commit_hash(commit_id)
+            )?;
+            Ok(())
+        };
+    let added_heads = to_view.heads().difference(from_view.heads()).sorted();
+    let removed_heads = from_view.heads().difference(to_view.heads()).sorted();
+    if to_view.heads() != from_view.heads() {
+        writeln!(formatter)?;
+        with_content_format.write(formatter, |formatter| writeln!(formatter, "Changed heads:"))?;
+        for (id, added) in itertools::chain(
+            added_heads.map(|id| (id, true)),
+            removed_heads.map(|id| (id, false)),
+        ) {
+            with_content_format.write(formatter, |formatter| {
+                let target = RefTarget::normal(id.clone());
+                write_ref_target_summary(formatter, &target, added, None, &write_commit_id)
+            })?;
+        }
+    }
+    show_view_refs_diff(
+        formatter,
+        from_view,
+        to_view,
+        with_content_format,
+        &write_commit_id,
+    )
+}
+
+/// Read-only operation store serving the operations of a bundle.
+#[derive(Debug)]
+struct BundleOpStore {
+    root_operation_id: OperationId,
+    operations: HashMap<OperationId, op_store::Operation>,
+}
+
+impl BundleOpStore {
+    fn new(bundle: &OpBundle) -> Result<Self, CommandError> {
+        let to_timestamp = |timestamp: &BundleTimestamp| Timestamp {
+            timestamp: MillisSinceEpoch(timestamp.millis_since_epoch),
+            tz_offset: timestamp.tz_offset,
+        };
+        let operations = bundle
+            .operations
+            .iter()
+            .map(|op| -> Result<_, CommandError> {
+                let id = parse_operation_id(&op.id)?;
+                let data = op_store::Operation {
+                    // Views aren't stored in the op store.
+                    view_id: ViewId::new(id.to_bytes()),
+                    parents: op
+                        .parents
+                        .iter()
+                        .map(|id| parse_operation_id(id))
+                        .try_collect()?,
+                    metadata: OperationMetadata {
+                        time: TimestampRange {
+                            start: to_timestamp(&op.start_time),
+                            end: to_timestamp(&op.end_time),
+                        },
+                        description: op.description.clone(),
+                        hostname: op.hostname.clone(),
+                        username: op.username.clone(),
+                        is_snapshot: op.is_snapshot,
+                        tags: op
+                            .tags
+                            .iter()
+                            .map(|(key, value)| (key.clone(), value.clone()))
+                            .collect(),
+                        workspace_name: op.workspace_name.clone().map(Into::into),
+                        cwd: op
+                            .cwd
+                            .as_ref()
+                            .map(|cwd| RepoPathBuf::from_internal_string(cwd.as_str()))
+                            .transpose()
+                            .map_err(user_error)?,
+                    },
+                    commit_predecessors: None,
+                };
+                Ok((id, data))
+            })
+            .try_collect()?;
+        Ok(Self {
+            root_operation_id: parse_operation_id(&bundle.root_operation)?,
+            operations,
+        })
+    }
+
+    fn not_found(object_type: &str, hash: String) -> OpStoreError {
+        OpStoreError::ObjectNotFound {
+            object_type: object_type.to_owned(),
+            hash,
+            source: "Object is not included in the bundle".into(),
+        }
+    }
+
+    fn read_only() -> OpStoreError {
+        OpStoreError::Other("Operation bundle is read-only".into())
+    }
+}
+
+impl OpStore for BundleOpStore {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "bundle"
+    }
+
+    fn root_operation_id(&self) -> &OperationId {
+        &self.root_operation_id
+    }
+
+    fn read_view(&self, id: &ViewId) -> OpStoreResult<op_store::View> {
+        Err(Self::not_found("view", id.hex()))
+    }
+
+    fn write_view(&self, _contents: &op_store::View) -> OpStoreResult<ViewId> {
+        Err(Self::read_only())
+    }
+
+    fn read_operation(&self, id: &OperationId) -> OpStoreResult<op_store::Operation> {
+        self.operations
+            .get(id)
+            .cloned()
+            .ok_or_else(|| Self::not_found("operation", id.hex()))
+    }
+
+    fn write_operation(&self, _contents: &op_store::Operation) -> OpStoreResult<OperationId> {
+        Err(Self::read_only())
+    }
+
+    fn resolve_operation_id_prefix(
+        &self,
+        prefix: &HexPrefix,
+    ) -> OpStoreResult<PrefixResolution<OperationId>> {
+        let mut matches = self.operations.keys().filter(|id| prefix.matches(*id));
+        Ok(match (matches.next(), matches.next()) {
+            (None, _) => PrefixResolution::NoMatch,
+            (Some(id), None) => PrefixResolution::SingleMatch(id.clone()),
+            (Some(_), Some(_)) => PrefixResolution::AmbiguousMatch,
+        })
+    }
+
+    fn gc(&self, _head_ids: &[OperationId], _keep_newer: SystemTime) -> OpStoreResult<()> {
+        Ok(())
+    }
+}
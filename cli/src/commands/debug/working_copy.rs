@@ -15,23 +15,52 @@
 use std::fmt::Debug;
 use std::io::Write as _;
 
+use super::check_local_disk_wc;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
 
 /// Show information about the working copy state
 #[derive(clap::Args, Clone, Debug)]
-pub struct DebugWorkingCopyArgs {}
+pub struct DebugWorkingCopyArgs {
+    /// List files that the next snapshot will re-hash because they were
+    /// modified too close to the last snapshot to trust their metadata
+    ///
+    /// This requires a standard local-disk working copy.
+    #[arg(long)]
+    check_racy: bool,
+}
 
 pub fn cmd_debug_working_copy(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &DebugWorkingCopyArgs,
+    args: &DebugWorkingCopyArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper_no_snapshot(ui)?;
     let wc = workspace_command.working_copy();
     writeln!(ui.stdout(), "Type: {:?}", wc.name())?;
     writeln!(ui.stdout(), "Current operation: {:?}", wc.operation_id())?;
     writeln!(ui.stdout(), "Current tree: {:?}", wc.tree_id()?)?;
+    if args.check_racy {
+        let wc = check_local_disk_wc(wc.as_any())?;
+        writeln!(
+            ui.stdout(),
+            "Timestamp granularity: {}ms",
+            wc.mtime_granularity_millis()?
+        )?;
+        let racy_file_states = wc.racy_file_states()?;
+        if racy_file_states.is_empty() {
+            writeln!(ui.stdout(), "No racy files")?;
+        } else {
+            writeln!(ui.stdout(), "Racy files:")?;
+            for (path, state) in racy_file_states {
+                if state.mtime.0 == 0 {
+                    writeln!(ui.stdout(), "  {path:?} (mtime cleared)")?;
+                } else {
+                    writeln!(ui.stdout(), "  {path:?} (mtime {})", state.mtime.0)?;
+                }
+            }
+        }
+    }
     Ok(())
 }
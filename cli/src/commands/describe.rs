@@ -26,8 +26,10 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::RevisionsFileArgs;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::commands::identity::warn_if_unknown_identity;
 use crate::complete;
 use crate::description_util::ParsedBulkEditMessage;
 use crate::description_util::add_trailers_with_template;
@@ -58,6 +60,8 @@ pub(crate) struct DescribeArgs {
         add = ArgValueCompleter::new(complete::revset_expression_mutable)
     )]
     revisions_opt: Vec<RevisionArg>,
+    #[command(flatten)]
+    revisions_file: RevisionsFileArgs,
     /// The change description to use (don't open editor)
     ///
     /// If multiple revisions are specified, the same description will be used
@@ -107,6 +111,10 @@ pub(crate) struct DescribeArgs {
         value_parser = parse_author
     )]
     author: Option<(String, String)>,
+    /// Do not ask for confirmation if the revisions match more commits than
+    /// `ui.large-revset-warning-threshold`
+    #[arg(long)]
+    no_limit: bool,
 }
 
 #[instrument(skip_all)]
@@ -116,9 +124,12 @@ pub(crate) fn cmd_describe(
     args: &DescribeArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let commits: Vec<_> = if !args.revisions_pos.is_empty() || !args.revisions_opt.is_empty() {
-        workspace_command
-            .parse_union_revsets(ui, &[&*args.revisions_pos, &*args.revisions_opt].concat())?
+    warn_if_unknown_identity(ui, workspace_command.settings())?;
+    let commits: Vec<_> = if let Some(revisions) = args
+        .revisions_file
+        .with_revisions(&[&*args.revisions_pos, &*args.revisions_opt].concat())?
+    {
+        workspace_command.parse_union_revsets(ui, &revisions)?
     } else {
         workspace_command.parse_revset(ui, &RevisionArg::AT)?
     }
@@ -129,6 +140,7 @@ pub(crate) fn cmd_describe(
         return Ok(());
     }
     workspace_command.check_rewritable(commits.iter().ids())?;
+    workspace_command.check_large_revset(ui, commits.iter().ids(), args.no_limit)?;
     let text_editor = workspace_command.text_editor()?;
 
     let mut tx = workspace_command.start_transaction();
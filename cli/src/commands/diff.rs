@@ -15,21 +15,36 @@
 use clap_complete::ArgValueCompleter;
 use indexmap::IndexSet;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::TreeValue;
+use jj_lib::commit::Commit;
+use jj_lib::conflicts::MaterializedTreeValue;
+use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::copies::CopyRecords;
+use jj_lib::files;
+use jj_lib::files::MergeResult;
+use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
 use jj_lib::rewrite::merge_commit_trees;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::check_path_args_match_changes;
 use crate::cli_util::print_unmatched_explicit_paths;
 use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
+use crate::command_error::silent_error;
+use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::get_copy_records;
+use crate::diff_util::has_diff;
 use crate::diff_util::show_templated;
 use crate::ui::Ui;
 
@@ -43,7 +58,9 @@ use crate::ui::Ui;
 /// With the `--from` and/or `--to` options, shows the difference from/to the
 /// given revisions. If either is left out, it defaults to the working-copy
 /// commit. For example, `jj diff --from main` shows the changes from "main"
-/// (perhaps a bookmark name) to the working-copy commit.
+/// (perhaps a bookmark name) to the working-copy commit. With `--merge-base`,
+/// the changes are shown from the common ancestor of the two revisions
+/// instead, like `git diff main...@`.
 ///
 /// If no option is specified, it defaults to `-r @`.
 #[derive(clap::Args, Clone, Debug)]
@@ -93,6 +110,12 @@ pub(crate) struct DiffArgs {
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
     to: Option<RevisionArg>,
+    /// Show changes from the common ancestor of `--from` and `--to`
+    ///
+    /// This shows only the changes made on the `--to` side since the histories
+    /// forked. It is an error if there are multiple common ancestors.
+    #[arg(long, requires = "from")]
+    merge_base: bool,
     /// Restrict the diff to these paths
     #[arg(
         value_name = "FILESETS",
@@ -100,6 +123,12 @@ pub(crate) struct DiffArgs {
         add = ArgValueCompleter::new(complete::modified_revision_or_range_files),
     )]
     paths: Vec<String>,
+    /// Fail if any of the given paths doesn't match a changed file
+    ///
+    /// By default, such paths are only reported as a warning. This can also
+    /// be enabled by the `ui.strict-filesets` setting.
+    #[arg(long)]
+    strict_paths: bool,
     /// Render each file diff entry using the given template
     ///
     /// All 0-argument methods of the [`TreeDiffEntry` type] are available as
@@ -120,6 +149,28 @@ pub(crate) struct DiffArgs {
     template: Option<String>,
     #[command(flatten)]
     format: DiffFormatArgs,
+    /// Exit with status 1 if there are differences, and 0 otherwise
+    ///
+    /// Changes in whitespace are ignored if `--ignore-all-space` or
+    /// `--ignore-space-change` is specified. If the global `--quiet` flag is
+    /// also specified, the diff isn't printed.
+    #[arg(long)]
+    exit_code: bool,
+    /// Show what the sides of conflicted files disagree on
+    ///
+    /// For each conflict in the files of the revision given by `-r` (`@` by
+    /// default), shows the diff from the first side to each other side. Sides
+    /// are labeled with the parent commit they came from if it can be
+    /// determined.
+    #[arg(
+        long,
+        alias = "conflict-sides",
+        conflicts_with_all = ["from", "to", "template", "short-format", "tool", "exit_code"],
+    )]
+    conflict: bool,
+    /// With `--conflict`, also show the diff from the base to each side
+    #[arg(long, requires = "conflict")]
+    conflict_base: bool,
 }
 
 #[instrument(skip_all)]
@@ -128,25 +179,252 @@ pub(crate) fn cmd_diff(
     command: &CommandHelper,
     args: &DiffArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
-    let repo = workspace_command.repo();
+    let result = diff_impl(ui, command, args);
+    if args.exit_code {
+        result.map_err(CommandError::distinct_from_silent)
+    } else {
+        result
+    }
+}
+
+fn diff_impl(ui: &mut Ui, command: &CommandHelper, args: &DiffArgs) -> Result<(), CommandError> {
+    if args.conflict {
+        return diff_conflict_sides(ui, command, args);
+    }
+    let mut workspace_command =
+        if args.from.is_none() && args.to.is_none() && args.revisions.is_none() {
+            // The working-copy commit is diffed by default.
+            command.workspace_helper(ui)?
+        } else {
+            command.workspace_helper_for_query(ui)?
+        };
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
+    let mut trees = resolve_diff_trees(ui, &workspace_command, args, matcher.as_ref())?;
+    if workspace_command.query_needs_snapshot(trees.commit_ids.iter()) {
+        workspace_command.maybe_snapshot(ui)?;
+        trees = resolve_diff_trees(ui, &workspace_command, args, matcher.as_ref())?;
+    }
+    let DiffTrees {
+        from_tree,
+        to_tree,
+        copy_records,
+        commit_ids: _,
+    } = trees;
+    // Paths that don't exist in either tree are reported as a warning after
+    // the diff is shown, so the changes only need to be checked if strict.
+    if args.strict_paths
+        || workspace_command
+            .settings()
+            .get_bool("ui.strict-filesets")?
+    {
+        check_path_args_match_changes(
+            ui,
+            &workspace_command,
+            &args.paths,
+            [(&from_tree, &to_tree)],
+            true,
+        )?;
+    }
+    let repo = workspace_command.repo();
+
+    let diff_found = if args.exit_code {
+        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
+        has_diff(repo.store(), tree_diff, &args.format).block_on()?
+    } else {
+        false
+    };
+    let diff_result = || {
+        if diff_found {
+            Err(silent_error("Differences found"))
+        } else {
+            Ok(())
+        }
+    };
+    if args.exit_code && workspace_command.settings().get_bool("ui.quiet")? {
+        return diff_result();
+    }
+
+    // -T disables both short/long rendering formats, but it might be okay to
+    // enable long format if explicitly specified (assuming -T is for short or
+    // summary output.)
+    let maybe_template;
+    let diff_renderer;
+    if let Some(text) = &args.template {
+        let language = workspace_command.commit_template_language();
+        let template = workspace_command
+            .parse_template(ui, &language, text)?
+            .labeled(["diff"]);
+        maybe_template = Some(template);
+        diff_renderer = workspace_command.diff_renderer(vec![]);
+    } else {
+        maybe_template = None;
+        diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+    }
+
+    ui.request_pager();
+    if let Some(template) = &maybe_template {
+        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
+        show_templated(ui.stdout_formatter().as_mut(), tree_diff, template).block_on()?;
+    }
+    diff_renderer
+        .show_diff(
+            ui,
+            ui.stdout_formatter().as_mut(),
+            [&from_tree, &to_tree],
+            &matcher,
+            &copy_records,
+            ui.term_width(),
+        )
+        .block_on()?;
+    print_unmatched_explicit_paths(
+        ui,
+        &workspace_command,
+        &fileset_expression,
+        [&from_tree, &to_tree],
+    )?;
+    diff_result()
+}
+
+/// Shows the diffs between the terms of each conflict in the conflicted files.
+fn diff_conflict_sides(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DiffArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let revision = match args.revisions.as_deref() {
+        None => &RevisionArg::AT,
+        Some([revision]) => revision,
+        Some(_) => return Err(user_error("--conflict requires a single revision")),
+    };
+    let commit = workspace_command.resolve_single_rev(ui, revision)?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher();
+    let tree = commit.tree()?;
+    let conflicts = tree
+        .conflicts()
+        .filter(|(path, _)| matcher.matches(path))
+        .collect_vec();
+    if conflicts.is_empty() {
+        return Err(user_error(if args.paths.is_empty() {
+            "No conflicts found at this revision"
+        } else {
+            "No conflicts found at the given path(s)"
+        }));
+    }
+    let store = workspace_command.repo().store();
+    let parents: Vec<_> = commit.parents().try_collect()?;
+    let parent_trees: Vec<_> = parents.iter().map(|parent| parent.tree()).try_collect()?;
+    let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (path, value) in conflicts {
+        let ui_path = workspace_command.format_file_path(&path);
+        let MaterializedTreeValue::FileConflict(file) =
+            materialize_tree_value(store, &path, value?).block_on()?
+        else {
+            writeln!(
+                ui.warning_default(),
+                "Skipping {ui_path}: the conflict is not between regular files"
+            )?;
+            continue;
+        };
 
-    let from_tree;
-    let to_tree;
+        // A side came from a parent if the parent has the same file.
+        let mut side_labels = vec![];
+        for (i, side_id) in file.ids.adds().enumerate() {
+            let mut label = format!("side #{}", i + 1);
+            for (parent, parent_tree) in parents.iter().zip(&parent_trees) {
+                let parent_value = parent_tree.path_value(&path)?;
+                if let Some(Some(TreeValue::File { id, .. })) = parent_value.as_resolved() {
+                    if Some(id) == side_id.as_ref() {
+                        let summary = workspace_command.format_commit_summary(parent);
+                        label = format!("{label} (from {summary})");
+                        break;
+                    }
+                }
+            }
+            side_labels.push(label);
+        }
+        let base_labels = (1..=file.ids.removes().len())
+            .map(|i| format!("base #{i}"))
+            .collect_vec();
+
+        let MergeResult::Conflict(hunks) = files::merge_hunks(&file.contents) else {
+            continue;
+        };
+        let conflict_hunks = hunks
+            .iter()
+            .filter(|hunk| !hunk.is_resolved())
+            .collect_vec();
+        let num_conflicts = conflict_hunks.len();
+        for (i, hunk) in conflict_hunks.into_iter().enumerate() {
+            let title = format!("Conflict {} of {num_conflicts} in {ui_path}", i + 1);
+            let sides = hunk.adds().collect_vec();
+            let bases = hunk.removes().collect_vec();
+            for (&side, label) in sides.iter().zip(&side_labels).skip(1) {
+                diff_renderer.show_diff_texts(
+                    formatter.as_mut(),
+                    &title,
+                    [&side_labels[0], label],
+                    [sides[0], side],
+                )?;
+            }
+            if args.conflict_base {
+                for (j, (&side, label)) in sides.iter().zip(&side_labels).enumerate() {
+                    // Side #1 and side #2 are both compared to base #1.
+                    let base = j.saturating_sub(1);
+                    diff_renderer.show_diff_texts(
+                        formatter.as_mut(),
+                        &title,
+                        [&base_labels[base], label],
+                        [bases[base], side],
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+struct DiffTrees {
+    from_tree: MergedTree,
+    to_tree: MergedTree,
+    copy_records: CopyRecords,
+    /// Commits the trees were computed from.
+    commit_ids: Vec<CommitId>,
+}
+
+fn resolve_diff_trees(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &DiffArgs,
+    matcher: &dyn Matcher,
+) -> Result<DiffTrees, CommandError> {
+    let repo = workspace_command.repo();
     let mut copy_records = CopyRecords::default();
     if args.from.is_some() || args.to.is_some() {
         let resolve_revision = |r: &Option<RevisionArg>| {
             workspace_command.resolve_single_rev(ui, r.as_ref().unwrap_or(&RevisionArg::AT))
         };
-        let from = resolve_revision(&args.from)?;
+        let mut from = resolve_revision(&args.from)?;
         let to = resolve_revision(&args.to)?;
-        from_tree = from.tree()?;
-        to_tree = to.tree()?;
-
-        let records = get_copy_records(repo.store(), from.id(), to.id(), &matcher)?;
+        let mut commit_ids = vec![from.id().clone(), to.id().clone()];
+        if args.merge_base {
+            from = resolve_merge_base(workspace_command, &from, &to)?;
+            commit_ids.push(from.id().clone());
+        }
+        let records = get_copy_records(repo.store(), from.id(), to.id(), matcher)?;
         copy_records.add_records(records)?;
+        Ok(DiffTrees {
+            from_tree: from.tree()?,
+            to_tree: to.tree()?,
+            copy_records,
+            commit_ids,
+        })
     } else {
         let revision_args = args
             .revisions
@@ -183,54 +461,60 @@ pub(crate) fn cmd_diff(
         // Collect parents outside of revset to preserve parent order
         let parents: IndexSet<_> = roots.iter().flat_map(|c| c.parents()).try_collect()?;
         let parents = parents.into_iter().collect_vec();
-        from_tree = merge_commit_trees(repo.as_ref(), &parents).block_on()?;
-        to_tree = merge_commit_trees(repo.as_ref(), &heads).block_on()?;
+        let from_tree = merge_commit_trees(repo.as_ref(), &parents).block_on()?;
+        let to_tree = merge_commit_trees(repo.as_ref(), &heads).block_on()?;
 
         for p in &parents {
             for to in &heads {
-                let records = get_copy_records(repo.store(), p.id(), to.id(), &matcher)?;
+                let records = get_copy_records(repo.store(), p.id(), to.id(), matcher)?;
                 copy_records.add_records(records)?;
             }
         }
+        let commit_ids = parents
+            .iter()
+            .chain(&heads)
+            .map(|commit| commit.id().clone())
+            .collect();
+        Ok(DiffTrees {
+            from_tree,
+            to_tree,
+            copy_records,
+            commit_ids,
+        })
     }
+}
 
-    // -T disables both short/long rendering formats, but it might be okay to
-    // enable long format if explicitly specified (assuming -T is for short or
-    // summary output.)
-    let maybe_template;
-    let diff_renderer;
-    if let Some(text) = &args.template {
-        let language = workspace_command.commit_template_language();
-        let template = workspace_command
-            .parse_template(ui, &language, text)?
-            .labeled(["diff"]);
-        maybe_template = Some(template);
-        diff_renderer = workspace_command.diff_renderer(vec![]);
-    } else {
-        maybe_template = None;
-        diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
-    }
-
-    ui.request_pager();
-    if let Some(template) = &maybe_template {
-        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
-        show_templated(ui.stdout_formatter().as_mut(), tree_diff, template).block_on()?;
+/// Returns the single common ancestor of `from` and `to` that isn't an
+/// ancestor of another common ancestor.
+fn resolve_merge_base(
+    workspace_command: &WorkspaceCommandHelper,
+    from: &Commit,
+    to: &Commit,
+) -> Result<Commit, CommandError> {
+    let expression = RevsetExpression::commit(from.id().clone())
+        .ancestors()
+        .intersection(&RevsetExpression::commit(to.id().clone()).ancestors())
+        .heads();
+    let merge_bases: Vec<_> = workspace_command
+        .attach_revset_evaluator(expression)
+        .evaluate_to_commits()?
+        .try_collect()?;
+    match <[Commit; 1]>::try_from(merge_bases) {
+        Ok([merge_base]) => Ok(merge_base),
+        Err(merge_bases) => Err(user_error_with_hint(
+            format!(
+                "Revisions {from} and {to} have {count} merge bases",
+                from = short_commit_hash(from.id()),
+                to = short_commit_hash(to.id()),
+                count = merge_bases.len()
+            ),
+            format!(
+                "Use --from with one of them instead: {}",
+                merge_bases
+                    .iter()
+                    .map(|commit| short_commit_hash(commit.id()))
+                    .join(", ")
+            ),
+        )),
     }
-    diff_renderer
-        .show_diff(
-            ui,
-            ui.stdout_formatter().as_mut(),
-            [&from_tree, &to_tree],
-            &matcher,
-            &copy_records,
-            ui.term_width(),
-        )
-        .block_on()?;
-    print_unmatched_explicit_paths(
-        ui,
-        &workspace_command,
-        &fileset_expression,
-        [&from_tree, &to_tree],
-    )?;
-    Ok(())
 }
@@ -28,6 +28,7 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::RevisionsFileArgs;
 use crate::cli_util::compute_commit_location;
 use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
@@ -49,6 +50,13 @@ use crate::ui::Ui;
 /// children indicated by the arguments will be rebased onto the heads of the
 /// specified commits.
 ///
+/// With `--descendants`, all descendants of the specified commits are
+/// duplicated too. With `--ancestors-to`, the ancestors of the specified
+/// commits down to the given revisions are included. In either case, parent
+/// relationships within the duplicated set are preserved, while parents
+/// outside of it keep pointing to the original commits. Bookmarks are not
+/// copied to the duplicated commits.
+///
 /// By default, the duplicated commits retain the descriptions of the originals.
 /// This can be customized with the `templates.duplicate_description` setting.
 #[derive(clap::Args, Clone, Debug)]
@@ -66,6 +74,19 @@ pub(crate) struct DuplicateArgs {
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
     revisions_opt: Vec<RevisionArg>,
+    #[command(flatten)]
+    revisions_file: RevisionsFileArgs,
+    /// Also duplicate all descendants of the specified revisions
+    #[arg(long)]
+    descendants: bool,
+    /// Also duplicate the ancestors of the specified revisions, down to and
+    /// including these revisions
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    ancestors_to: Vec<RevisionArg>,
     /// The revision(s) to duplicate onto (can be repeated to create a merge
     /// commit)
     #[arg(
@@ -106,13 +127,28 @@ pub(crate) fn cmd_duplicate(
     args: &DuplicateArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let to_duplicate: Vec<CommitId> =
-        if !args.revisions_pos.is_empty() || !args.revisions_opt.is_empty() {
-            workspace_command
-                .parse_union_revsets(ui, &[&*args.revisions_pos, &*args.revisions_opt].concat())?
-        } else {
-            workspace_command.parse_revset(ui, &RevisionArg::AT)?
-        }
+    let mut expression = if let Some(revisions) = args
+        .revisions_file
+        .with_revisions(&[&*args.revisions_pos, &*args.revisions_opt].concat())?
+    {
+        workspace_command.parse_union_revsets(ui, &revisions)?
+    } else {
+        workspace_command.parse_revset(ui, &RevisionArg::AT)?
+    }
+    .expression()
+    .clone();
+    if !args.ancestors_to.is_empty() {
+        let roots = workspace_command
+            .parse_union_revsets(ui, &args.ancestors_to)?
+            .expression()
+            .clone();
+        expression = expression.union(&roots.dag_range_to(&expression));
+    }
+    if args.descendants {
+        expression = expression.descendants();
+    }
+    let to_duplicate: Vec<CommitId> = workspace_command
+        .attach_revset_evaluator(expression)
         .evaluate_to_commit_ids()?
         .try_collect()?; // in reverse topological order
     if to_duplicate.is_empty() {
@@ -12,24 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::evolution::CommitEvolutionEntry;
 use jj_lib::evolution::walk_predecessors;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::graph::reverse_graph;
-use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::FilesMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::TemplateFileArgs;
 use crate::cli_util::format_template;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::commands::log::follow_copy_sources;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::graphlog::GraphStyle;
@@ -52,6 +62,19 @@ pub(crate) struct EvologArgs {
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
     revisions: Vec<RevisionArg>,
+    /// Show patches of the given paths only
+    #[arg(
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::log_files),
+    )]
+    paths: Vec<String>,
+    /// Follow a single file across renames and copies
+    ///
+    /// The patches include the file given as the only path, and the file it
+    /// was renamed or copied from in a later version of the change.
+    #[arg(long, requires = "paths")]
+    follow: bool,
     /// Limit number of revisions to show
     ///
     /// Applied after revisions are reordered topologically, but before being
@@ -79,6 +102,8 @@ pub(crate) struct EvologArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
     /// Show patch compared to the previous version of this change
     ///
     /// If the previous version has different parents, it will be temporarily
@@ -102,6 +127,15 @@ pub(crate) fn cmd_evolog(
         .parse_union_revsets(ui, &args.revisions)?
         .evaluate_to_commit_ids()?
         .try_collect()?;
+    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let followed_path = if args.follow {
+        let [path] = args.paths.as_slice() else {
+            return Err(user_error("--follow requires a single file path"));
+        };
+        Some(workspace_command.parse_file_path(path)?)
+    } else {
+        None
+    };
 
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
     let graph_style = GraphStyle::from_settings(workspace_command.settings())?;
@@ -111,8 +145,8 @@ pub(crate) fn cmd_evolog(
     let node_template: TemplateRenderer<Option<Commit>>;
     {
         let language = workspace_command.commit_template_language();
-        let template_string = match &args.template {
-            Some(value) => value.clone(),
+        let template_string = match args.template_file.template_text(args.template.as_deref())? {
+            Some(value) => value,
             None => workspace_command.settings().get("templates.evolog")?,
         };
         template = workspace_command
@@ -135,6 +169,17 @@ pub(crate) fn cmd_evolog(
     let formatter = formatter.as_mut();
 
     let repo = workspace_command.repo();
+    let matcher = fileset_expression.to_matcher();
+    let followed_matchers = if let Some(path) = followed_path {
+        follow_file_evolution(repo.as_ref(), &start_commit_ids, path)?
+    } else {
+        HashMap::new()
+    };
+    let matcher_for = |commit: &Commit| -> &dyn Matcher {
+        followed_matchers
+            .get(commit.id())
+            .map_or(matcher.as_ref(), |matcher| matcher)
+    };
     let evolution_entries = walk_predecessors(repo, &start_commit_ids);
     if !args.no_graph {
         let mut raw_output = formatter.raw()?;
@@ -186,7 +231,7 @@ pub(crate) fn cmd_evolog(
                         formatter.as_mut(),
                         &predecessors,
                         &entry.commit,
-                        &EverythingMatcher,
+                        matcher_for(&entry.commit),
                         within_graph.width(),
                     )
                     .block_on()?;
@@ -220,7 +265,7 @@ pub(crate) fn cmd_evolog(
                         formatter,
                         &predecessors,
                         &entry.commit,
-                        &EverythingMatcher,
+                        matcher_for(&entry.commit),
                         width,
                     )
                     .block_on()?;
@@ -230,3 +275,41 @@ pub(crate) fn cmd_evolog(
 
     Ok(())
 }
+
+/// Returns the matchers of the paths `path` had in the commits evolved from
+/// `start_commit_ids`, following renames and copies from the predecessors.
+fn follow_file_evolution(
+    repo: &ReadonlyRepo,
+    start_commit_ids: &[CommitId],
+    path: RepoPathBuf,
+) -> Result<HashMap<CommitId, FilesMatcher>, CommandError> {
+    let store = repo.store();
+    let mut paths = vec![path];
+    let mut matchers = HashMap::new();
+    for entry in walk_predecessors(repo, start_commit_ids) {
+        let entry = entry?;
+        let tree = entry.commit.tree()?;
+        for predecessor in entry.predecessors() {
+            let predecessor = predecessor?;
+            let predecessor_tree = predecessor.tree()?;
+            let mut added_paths = vec![];
+            for path in &paths {
+                if predecessor_tree.path_value(path)?.is_absent()
+                    && tree.path_value(path)?.is_present()
+                {
+                    added_paths.push(path.clone());
+                }
+            }
+            follow_copy_sources(
+                store,
+                predecessor.id(),
+                entry.commit.id(),
+                &added_paths,
+                &mut paths,
+            )?;
+        }
+        // The patch of this version includes the names it was copied from
+        matchers.insert(entry.commit.id().clone(), FilesMatcher::new(&paths));
+    }
+    Ok(matchers)
+}
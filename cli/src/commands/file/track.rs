@@ -12,11 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
 use std::io;
 use std::io::Write as _;
+use std::path::Path;
+use std::sync::Arc;
 
 use indoc::writedoc;
 use itertools::Itertools as _;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::gitignore::GitIgnoreMatch;
+use jj_lib::matchers::Matcher;
+use jj_lib::matchers::Visit;
+use jj_lib::matchers::VisitDirs;
+use jj_lib::matchers::VisitFiles;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::repo_path::RepoPathComponent;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::working_copy::SnapshotStats;
 use jj_lib::working_copy::UntrackedReason;
@@ -37,11 +52,19 @@ use crate::ui::Ui;
 /// `snapshot.auto-track` (e.g. to `"none()"` or `"glob:**/*.rs"`). Files that
 /// don't match the pattern can be manually tracked using this command. The
 /// default pattern is `all()`.
+///
+/// The command reports which of the matching files started being tracked,
+/// which were already tracked, and which were skipped because they are
+/// ignored (along with the ignore pattern responsible). Ignored files are
+/// never tracked.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileTrackArgs {
     /// Paths to track
     #[arg(required = true, value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Only report which files would be tracked, without tracking them
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[instrument(skip_all)]
@@ -55,22 +78,188 @@ pub(crate) fn cmd_file_track(
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
     let options = workspace_command.snapshot_options_with_start_tracking_matcher(&matcher)?;
+    let workspace_root = workspace_command.workspace_root().to_owned();
+    let base_ignores = workspace_command.base_ignores()?;
 
     let mut tx = workspace_command.start_transaction().into_inner();
-    let (mut locked_ws, _wc_commit) = workspace_command.start_working_copy_mutation()?;
-    let (_tree_id, track_stats) = locked_ws.locked_wc().snapshot(&options)?;
-    let num_rebased = tx.repo_mut().rebase_descendants()?;
-    if num_rebased > 0 {
-        writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+    let (mut locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
+    let (tree_id, track_stats) = locked_ws.locked_wc().snapshot(&options)?;
+    let old_tree = wc_commit.tree()?;
+    let new_tree = tx.repo().store().get_root_tree(&tree_id)?;
+    let mut report = TrackReport::default();
+    for (path, value) in new_tree.entries_matching(&matcher) {
+        if value?.is_absent() {
+            continue;
+        }
+        if old_tree.path_value(&path)?.is_absent() {
+            report.newly_tracked.push(path);
+        } else {
+            report.num_already_tracked += 1;
+        }
     }
-    let repo = tx.commit("track paths")?;
-    locked_ws.finish(repo.op_id().clone())?;
-    print_track_snapshot_stats(
-        ui,
-        auto_stats,
-        track_stats,
-        workspace_command.env().path_converter(),
+    collect_ignored_files(
+        &workspace_root,
+        RepoPath::root(),
+        &base_ignores,
+        &matcher,
+        &new_tree,
+        &mut report.ignored,
     )?;
+    // Files refused for e.g. their size are reported separately.
+    report.has_refused = track_stats
+        .untracked_paths
+        .values()
+        .any(|reason| !matches!(reason, UntrackedReason::FileNotAutoTracked));
+
+    if !args.dry_run {
+        let num_rebased = tx.repo_mut().rebase_descendants()?;
+        if num_rebased > 0 {
+            writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+        }
+        let repo = tx.commit("track paths")?;
+        locked_ws.finish(repo.op_id().clone())?;
+    }
+    let path_converter = workspace_command.env().path_converter();
+    print_track_snapshot_stats(ui, auto_stats, track_stats, path_converter)?;
+    print_track_report(ui, &report, &workspace_root, path_converter, args.dry_run)?;
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct TrackReport {
+    newly_tracked: Vec<RepoPathBuf>,
+    num_already_tracked: usize,
+    ignored: Vec<(RepoPathBuf, GitIgnoreMatch)>,
+    /// Whether some matching files were refused for e.g. their size.
+    has_refused: bool,
+}
+
+/// Collects files on disk which match the `matcher` but aren't tracked in the
+/// `tree` because of ignore patterns.
+///
+/// Only the directories the `matcher` may match are visited. If the matcher
+/// names specific entries, the directory isn't listed.
+fn collect_ignored_files(
+    workspace_root: &Path,
+    dir: &RepoPath,
+    parent_ignores: &Arc<GitIgnoreFile>,
+    matcher: &dyn Matcher,
+    tree: &MergedTree,
+    ignored: &mut Vec<(RepoPathBuf, GitIgnoreMatch)>,
+) -> Result<(), CommandError> {
+    let visit = matcher.visit(dir);
+    if visit.is_nothing() {
+        return Ok(());
+    }
+    let disk_dir = dir.to_fs_path_unchecked(workspace_root);
+    let git_ignore = parent_ignores
+        .chain_with_file(&dir.to_internal_dir_string(), disk_dir.join(".gitignore"))?;
+    let entries = match visit {
+        Visit::Specific {
+            dirs: VisitDirs::Set(dirs),
+            files: VisitFiles::Set(files),
+        } => {
+            let mut entries = vec![];
+            for name in dirs.into_iter().chain(files).sorted().dedup() {
+                let disk_path = disk_dir.join(name.as_internal_str());
+                match disk_path.symlink_metadata() {
+                    Ok(metadata) => entries.push((name, metadata.file_type())),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err).context(&disk_path)?,
+                }
+            }
+            entries
+        }
+        _ => {
+            let mut entries = vec![];
+            for entry in fs::read_dir(&disk_dir).context(&disk_dir)? {
+                let entry = entry.context(&disk_dir)?;
+                let file_type = entry.file_type().context(entry.path())?;
+                let file_name = entry.file_name();
+                let Some(name) = file_name
+                    .to_str()
+                    .and_then(|name| RepoPathComponent::new(name).ok())
+                else {
+                    continue;
+                };
+                entries.push((name.to_owned(), file_type));
+            }
+            entries.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+            entries
+        }
+    };
+    for (name, file_type) in entries {
+        if name.as_internal_str() == ".jj" || name.as_internal_str() == ".git" {
+            continue;
+        }
+        let path = dir.join(&name);
+        if file_type.is_dir() {
+            collect_ignored_files(workspace_root, &path, &git_ignore, matcher, tree, ignored)?;
+        } else if matcher.matches(&path) && tree.path_value(&path)?.is_absent() {
+            if let Some(pattern) = git_ignore.matching_pattern(path.as_internal_file_string()) {
+                ignored.push((path, pattern));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_track_report(
+    ui: &Ui,
+    report: &TrackReport,
+    workspace_root: &Path,
+    path_converter: &RepoPathUiConverter,
+    dry_run: bool,
+) -> io::Result<()> {
+    let Some(mut formatter) = ui.status_formatter() else {
+        return Ok(());
+    };
+    let TrackReport {
+        newly_tracked,
+        num_already_tracked,
+        ignored,
+        has_refused,
+    } = report;
+    if !newly_tracked.is_empty() {
+        let verb = if dry_run {
+            "Would start tracking"
+        } else {
+            "Started tracking"
+        };
+        writeln!(formatter, "{verb} {} files:", newly_tracked.len())?;
+        for path in newly_tracked {
+            writeln!(formatter, "  {}", path_converter.format_file_path(path))?;
+        }
+    }
+    if *num_already_tracked > 0 {
+        writeln!(formatter, "Already tracked {num_already_tracked} files")?;
+    }
+    if !ignored.is_empty() {
+        writeln!(formatter, "Skipped {} ignored files:", ignored.len())?;
+        for (path, pattern) in ignored {
+            // Ignore files in the workspace are shown relative to the cwd
+            // like other paths.
+            let source = pattern
+                .source
+                .strip_prefix(workspace_root)
+                .ok()
+                .and_then(|path| RepoPathBuf::from_relative_path(path).ok())
+                .map_or_else(
+                    || pattern.source.display().to_string(),
+                    |path| path_converter.format_file_path(&path),
+                );
+            writeln!(
+                formatter,
+                "  {}: ignored by `{}` in {source}",
+                path_converter.format_file_path(path),
+                pattern.pattern,
+            )?;
+        }
+    }
+    if newly_tracked.is_empty() && *num_already_tracked == 0 && ignored.is_empty() && !*has_refused
+    {
+        writeln!(formatter, "No matching files")?;
+    }
     Ok(())
 }
 
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::num::NonZeroU32;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::default_index::DefaultIndexStore;
 use jj_lib::git;
+use jj_lib::git::FetchTagsOverride;
 use jj_lib::git::GitFetch;
 use jj_lib::git::IgnoredRefspec;
 use jj_lib::git::IgnoredRefspecs;
@@ -27,12 +30,14 @@ use jj_lib::git::get_git_backend;
 use jj_lib::ref_name::RemoteName;
 use jj_lib::repo::Repo as _;
 use jj_lib::str_util::StringPattern;
+use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::CommandError;
 use crate::command_error::config_error;
+use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::commands::git::get_single_remote;
 use crate::complete;
@@ -87,8 +92,29 @@ pub struct GitFetchArgs {
     /// Fetch from all remotes
     #[arg(long, conflicts_with = "remotes")]
     all_remotes: bool,
+    /// Fetch all tags from the remote(s)
+    ///
+    /// This defaults to the `git.fetch-tags` setting. If that is not
+    /// configured, the remote's `tagOpt` setting decides which tags are
+    /// fetched, which by default are the tags pointing to fetched commits.
+    #[arg(long, conflicts_with = "no_tags")]
+    tags: bool,
+    /// Do not fetch any tags
+    #[arg(long)]
+    no_tags: bool,
+    /// Fetch the full history of a shallow repository
+    ///
+    /// Commits at the former shallow boundary get their actual parents, and
+    /// the commit index is rebuilt accordingly. This does nothing if the
+    /// repository isn't shallow.
+    #[arg(long)]
+    unshallow: bool,
 }
 
+// git interprets this depth as infinite, which is what `git fetch --unshallow`
+// does under the hood.
+const INFINITE_DEPTH: NonZeroU32 = NonZeroU32::new(0x7fff_ffff).unwrap();
+
 #[tracing::instrument(skip_all)]
 pub fn cmd_git_fetch(
     ui: &mut Ui,
@@ -157,12 +183,27 @@ pub fn cmd_git_fetch(
         }
     };
 
+    let fetch_tags_override = if args.tags {
+        Some(FetchTagsOverride::AllTags)
+    } else if args.no_tags {
+        Some(FetchTagsOverride::NoTags)
+    } else {
+        match tx.settings().get_bool("git.fetch-tags").optional()? {
+            Some(true) => Some(FetchTagsOverride::AllTags),
+            Some(false) => Some(FetchTagsOverride::NoTags),
+            None => None,
+        }
+    };
+
+    let unshallow = args.unshallow && get_git_backend(tx.repo().store())?.git_repo().is_shallow();
+    let depth = unshallow.then_some(INFINITE_DEPTH);
+
     let git_settings = tx.settings().git_settings()?;
     let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
 
     for (remote, expanded) in expansions {
         with_remote_git_callbacks(ui, |callbacks| {
-            git_fetch.fetch(remote, expanded, callbacks, None, None)
+            git_fetch.fetch(remote, expanded, callbacks, depth, fetch_tags_override)
         })?;
     }
 
@@ -176,6 +217,29 @@ pub fn cmd_git_fetch(
             remotes.iter().map(|n| n.as_symbol()).join(",")
         ),
     )?;
+    if unshallow {
+        rebuild_index(ui, command)?;
+    }
+    Ok(())
+}
+
+/// Rebuilds the commit index, in which the commits at the former shallow
+/// boundary are still recorded as children of the root commit.
+fn rebuild_index(ui: &Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    // Load the repo again since the Git backend caches the shallow commits.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    let index_store = repo_loader.index_store();
+    // Other index implementations are supposed to handle the history change
+    // by themselves.
+    if let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>() {
+        default_index_store.reinit().map_err(internal_error)?;
+        default_index_store
+            .build_index_at_operation(&op, repo_loader.store())
+            .block_on()
+            .map_err(internal_error)?;
+    }
     Ok(())
 }
 
@@ -53,7 +53,7 @@ use self::root::cmd_git_root;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
-use crate::command_error::user_error_with_message;
+use crate::git_util::write_jj_gitignore;
 use crate::ui::Ui;
 
 /// Commands for working with Git remotes and the underlying Git repo
@@ -97,14 +97,7 @@ pub fn cmd_git(
 
 pub fn maybe_add_gitignore(workspace_command: &WorkspaceCommandHelper) -> Result<(), CommandError> {
     if workspace_command.working_copy_shared_with_git() {
-        std::fs::write(
-            workspace_command
-                .workspace_root()
-                .join(".jj")
-                .join(".gitignore"),
-            "/*\n",
-        )
-        .map_err(|e| user_error_with_message("Failed to write .jj/.gitignore file", e))
+        write_jj_gitignore(workspace_command.workspace_root())
     } else {
         Ok(())
     }
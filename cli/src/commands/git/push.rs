@@ -32,6 +32,7 @@ use jj_lib::git;
 use jj_lib::git::GitBranchPushTargets;
 use jj_lib::git::GitPushStats;
 use jj_lib::op_store::RefTarget;
+use jj_lib::op_store::RemoteRef;
 use jj_lib::ref_name::RefName;
 use jj_lib::ref_name::RefNameBuf;
 use jj_lib::ref_name::RemoteName;
@@ -57,6 +58,8 @@ use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::command_error::cli_error_with_message;
+use crate::command_error::print_error_hints;
+use crate::command_error::print_error_sources;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::user_error_with_message;
@@ -64,6 +67,7 @@ use crate::commands::git::get_single_remote;
 use crate::complete;
 use crate::formatter::Formatter;
 use crate::git_util::with_remote_git_callbacks;
+use crate::lint_util::missing_good_signature_reason;
 use crate::revset_util::parse_bookmark_name;
 use crate::ui::Ui;
 
@@ -75,8 +79,12 @@ use crate::ui::Ui;
 /// bookmark names based on the change IDs of specific commits.
 ///
 /// Unlike in Git, the remote to push to is not derived from the tracked remote
-/// bookmarks. Use `--remote` to select the remote Git repository by name. There
-/// is no option to push to multiple remotes.
+/// bookmarks. Use `--remote` to select the remote Git repository by name.
+///
+/// If `--remote` is repeated, the same bookmarks are pushed to each remote in
+/// turn. The bookmarks to push are determined separately for each remote from
+/// its own remote-tracking bookmarks. If pushing to a remote fails, the others
+/// are still pushed to, unless `--atomic-across-remotes` is given.
 ///
 /// Before the command actually moves, creates, or deletes a remote bookmark, it
 /// makes several [safety checks]. If there is a problem, you may need to run
@@ -93,12 +101,40 @@ use crate::ui::Ui;
 #[command(group(ArgGroup::new("specific").args(&["bookmark", "change", "revisions", "named"]).multiple(true)))]
 #[command(group(ArgGroup::new("what").args(&["all", "tracked"]).conflicts_with("specific")))]
 pub struct GitPushArgs {
-    /// The remote to push to (only named remotes are supported)
+    /// The remote to push to (only named remotes are supported, can be
+    /// repeated)
     ///
-    /// This defaults to the `git.push` setting. If that is not configured, and
+    /// This defaults to the `git.push-remotes` setting, or the `git.push`
+    /// setting if the former is not configured. If neither is configured, and
     /// if there are multiple remotes, the remote named "origin" will be used.
-    #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
-    remote: Option<RemoteNameBuf>,
+    #[arg(
+        long = "remote",
+        value_name = "REMOTE",
+        add = ArgValueCandidates::new(complete::git_remotes),
+    )]
+    remotes: Vec<RemoteNameBuf>,
+    /// Push to this Git URL without configuring a remote
+    ///
+    /// No remote is added to the repository, and remote-tracking bookmarks
+    /// aren't updated unless `--track-as` is specified. Since the state of the
+    /// bookmarks on the remote isn't known, they are expected to not exist
+    /// there, and new bookmarks are allowed. The bookmarks to push must be
+    /// specified explicitly unless `--track-as` is specified.
+    #[arg(long, value_name = "URL", conflicts_with = "remotes")]
+    to_url: Option<String>,
+    /// Record the bookmarks pushed by `--to-url` as tracked remote bookmarks
+    /// of this remote name
+    ///
+    /// The name must not be a configured remote. Subsequent pushes with the
+    /// same name will expect the bookmarks to be where they were recorded.
+    #[arg(long, value_name = "REMOTE", requires = "to_url")]
+    track_as: Option<RemoteNameBuf>,
+    /// Stop at the first remote that fails when pushing to multiple remotes
+    ///
+    /// By default, the remaining remotes are still pushed to, and the command
+    /// fails after all of them have been tried.
+    #[arg(long)]
+    atomic_across_remotes: bool,
     /// Push only this bookmark, or bookmarks matching a pattern (can be
     /// repeated)
     ///
@@ -115,6 +151,9 @@ pub struct GitPushArgs {
     )]
     bookmark: Vec<StringPattern>,
     /// Push all bookmarks (including new bookmarks)
+    ///
+    /// If there are multiple remotes, the remote to push to must be specified
+    /// by `--remote` or the `git.push` setting.
     #[arg(long)]
     all: bool,
     /// Push all tracked bookmarks
@@ -188,6 +227,12 @@ pub struct GitPushArgs {
         add = ArgValueCompleter::new(complete::branch_name_equals_any_revision)
     )]
     named: Vec<String>,
+    /// Also push bookmarks on ancestors of the pushed commits if they are out
+    /// of date on the remote
+    ///
+    /// Without this flag, such bookmarks are only reported by a warning.
+    #[arg(long, conflicts_with = "what")]
+    also_stale_ancestors: bool,
     /// Only display what will change on the remote
     #[arg(long)]
     dry_run: bool,
@@ -216,80 +261,167 @@ pub fn cmd_git_push(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
 
-    let default_remote;
-    let remote = if let Some(name) = &args.remote {
-        name
+    if let Some(url) = &args.to_url {
+        let Some(remote) = &args.track_as else {
+            return push_to_url(ui, &mut workspace_command, url, args);
+        };
+        let store = workspace_command.repo().store();
+        if git::get_all_remote_names(store)?.contains(remote) {
+            return Err(user_error_with_hint(
+                format!(
+                    "Remote {remote} is configured and can't be used with --track-as",
+                    remote = remote.as_symbol()
+                ),
+                "Use --remote to push to a configured remote.",
+            ));
+        }
+        return push_to_remote(ui, &mut workspace_command, remote, Some(url), args);
+    }
+
+    let remotes = if args.remotes.is_empty() {
+        get_default_push_remotes(ui, &workspace_command, args.all)?
     } else {
-        default_remote = get_default_push_remote(ui, &workspace_command)?;
-        &default_remote
+        args.remotes.iter().unique().cloned().collect()
     };
+    if let [remote] = remotes.as_slice() {
+        return push_to_remote(ui, &mut workspace_command, remote, None, args);
+    }
+
+    let mut failed_remotes = vec![];
+    for remote in &remotes {
+        let Err(err) = push_to_remote(ui, &mut workspace_command, remote, None, args) else {
+            continue;
+        };
+        if args.atomic_across_remotes {
+            return Err(err);
+        }
+        print_remote_push_error(ui, remote, &err)?;
+        failed_remotes.push(remote);
+    }
+    if !failed_remotes.is_empty() {
+        return Err(user_error(format!(
+            "Failed to push to {num_failed} of {num_remotes} remotes: {names}",
+            num_failed = failed_remotes.len(),
+            num_remotes = remotes.len(),
+            names = failed_remotes
+                .iter()
+                .map(|remote| remote.as_symbol())
+                .join(", "),
+        )));
+    }
+    Ok(())
+}
+
+/// Prints the error of pushing to one of multiple remotes without aborting the
+/// command.
+fn print_remote_push_error(
+    ui: &Ui,
+    remote: &RemoteName,
+    err: &CommandError,
+) -> Result<(), CommandError> {
+    writeln!(
+        ui.warning_default(),
+        "Failed to push to {remote}: {err}",
+        remote = remote.as_symbol(),
+        err = err.error
+    )?;
+    print_error_sources(ui, err.error.source())?;
+    print_error_hints(ui, &err.hints)?;
+    Ok(())
+}
+
+/// Where the bookmarks are pushed to.
+#[derive(Clone, Copy, Debug)]
+enum PushDestination<'a> {
+    /// Configured remote.
+    Remote(&'a RemoteName),
+    /// Git URL. The pushed bookmarks are recorded as remote bookmarks of the
+    /// `track_as` remote if specified.
+    Url {
+        url: &'a str,
+        track_as: Option<&'a RemoteName>,
+    },
+}
 
+impl PushDestination<'_> {
+    /// Returns the remote whose bookmarks are known to be on the destination.
+    fn remote(&self) -> Option<&RemoteName> {
+        match *self {
+            Self::Remote(remote) => Some(remote),
+            Self::Url { track_as, .. } => track_as,
+        }
+    }
+}
+
+impl fmt::Display for PushDestination<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Remote(remote)
+            | Self::Url {
+                track_as: Some(remote),
+                ..
+            } => write!(f, "{}", remote.as_symbol()),
+            Self::Url {
+                url,
+                track_as: None,
+            } => write!(f, "{url}"),
+        }
+    }
+}
+
+/// Pushes bookmarks to the `remote`, or to the `url` if specified. In the
+/// latter case, the `remote` is only used to look up and record the remote
+/// bookmarks.
+fn push_to_remote(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    remote: &RemoteName,
+    url: Option<&str>,
+    args: &GitPushArgs,
+) -> Result<(), CommandError> {
     let mut tx = workspace_command.start_transaction();
     let view = tx.repo().view();
     let tx_description;
-    let mut bookmark_updates = vec![];
+    let mut bookmark_updates;
     if args.all {
-        for (name, targets) in view.local_remote_bookmarks(remote) {
-            let allow_new = true; // implied by --all
-            match classify_bookmark_update(
-                name.to_remote_symbol(remote),
-                targets,
-                allow_new,
-                args.deleted,
-            ) {
-                Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
-                Ok(None) => {}
-                Err(reason) => reason.print(ui)?,
-            }
-        }
+        let allow_new = true; // implied by --all
+        bookmark_updates =
+            classify_all_bookmarks(ui, view, remote, |_| true, allow_new, args.deleted)?;
         tx_description = format!(
             "push all bookmarks to git remote {remote}",
             remote = remote.as_symbol()
         );
     } else if args.tracked {
-        for (name, targets) in view.local_remote_bookmarks(remote) {
-            if !targets.remote_ref.is_tracked() {
-                continue;
-            }
-            let allow_new = false; // doesn't matter
-            match classify_bookmark_update(
-                name.to_remote_symbol(remote),
-                targets,
-                allow_new,
-                args.deleted,
-            ) {
-                Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
-                Ok(None) => {}
-                Err(reason) => reason.print(ui)?,
-            }
-        }
+        let allow_new = false; // doesn't matter
+        bookmark_updates = classify_all_bookmarks(
+            ui,
+            view,
+            remote,
+            |targets| targets.remote_ref.is_tracked(),
+            allow_new,
+            args.deleted,
+        )?;
         tx_description = format!(
             "push all tracked bookmarks to git remote {remote}",
             remote = remote.as_symbol()
         );
     } else if args.deleted {
-        for (name, targets) in view.local_remote_bookmarks(remote) {
-            if targets.local_target.is_present() {
-                continue;
-            }
-            let allow_new = false; // doesn't matter
-            let allow_delete = true;
-            match classify_bookmark_update(
-                name.to_remote_symbol(remote),
-                targets,
-                allow_new,
-                allow_delete,
-            ) {
-                Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
-                Ok(None) => {}
-                Err(reason) => reason.print(ui)?,
-            }
-        }
+        let allow_new = false; // doesn't matter
+        let allow_delete = true;
+        bookmark_updates = classify_all_bookmarks(
+            ui,
+            view,
+            remote,
+            |targets| targets.local_target.is_absent(),
+            allow_new,
+            allow_delete,
+        )?;
         tx_description = format!(
             "push all deleted bookmarks to git remote {remote}",
             remote = remote.as_symbol()
         );
     } else {
+        bookmark_updates = vec![];
         let mut seen_bookmarks: HashSet<&RefName> = HashSet::new();
 
         // --change and --named don't move existing bookmarks. If they did, be
@@ -330,7 +462,8 @@ pub fn cmd_git_push(
         }
 
         let view = tx.repo().view();
-        let allow_new = args.allow_new || tx.settings().get("git.push-new-bookmarks")?;
+        let allow_new =
+            args.allow_new || url.is_some() || tx.settings().get("git.push-new-bookmarks")?;
         let bookmarks_by_name = find_bookmarks_to_push(view, &args.bookmark, remote)?;
         for &(name, targets) in &bookmarks_by_name {
             if !seen_bookmarks.insert(name) {
@@ -377,6 +510,44 @@ pub fn cmd_git_push(
             }
         }
 
+        let stale_bookmarks = find_stale_ancestor_bookmarks(
+            tx.base_workspace_helper(),
+            tx.repo().view(),
+            remote,
+            &bookmark_updates,
+        )?;
+        if args.also_stale_ancestors {
+            for (name, targets) in stale_bookmarks {
+                let allow_new = false; // doesn't matter
+                let allow_delete = false;
+                match classify_bookmark_update(
+                    name.to_remote_symbol(remote),
+                    targets,
+                    allow_new,
+                    allow_delete,
+                ) {
+                    Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
+                    Ok(None) => {}
+                    Err(reason) => reason.print(ui)?,
+                }
+            }
+        } else if !stale_bookmarks.is_empty() {
+            writeln!(
+                ui.warning_default(),
+                "The following bookmarks on ancestors of the pushed commits are out of date on \
+                 {remote}:",
+                remote = remote.as_symbol()
+            )?;
+            for (name, _) in &stale_bookmarks {
+                writeln!(ui.warning_no_heading(), "  {}", name.as_symbol())?;
+            }
+            writeln!(
+                ui.hint_default(),
+                "Push them together with `--bookmark`, or use `--also-stale-ancestors` to include \
+                 them."
+            )?;
+        }
+
         tx_description = format!(
             "push {names} to git remote {remote}",
             names = make_bookmark_term(
@@ -388,6 +559,125 @@ pub fn cmd_git_push(
             remote = remote.as_symbol()
         );
     }
+    let destination = match url {
+        Some(url) => PushDestination::Url {
+            url,
+            track_as: Some(remote),
+        },
+        None => PushDestination::Remote(remote),
+    };
+    push_bookmark_updates(ui, tx, bookmark_updates, destination, args, tx_description)
+}
+
+/// Pushes bookmarks to the `url` without configuring a remote.
+///
+/// Since the state of the bookmarks at the URL isn't known, the selected
+/// bookmarks are pushed as new bookmarks.
+fn push_to_url(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    url: &str,
+    args: &GitPushArgs,
+) -> Result<(), CommandError> {
+    if args.tracked || args.deleted {
+        return Err(user_error_with_hint(
+            "--tracked and --deleted can't be used when pushing to an untracked URL",
+            "Use --track-as to record the pushed bookmarks under a remote name.",
+        ));
+    }
+    if !args.all
+        && args.bookmark.is_empty()
+        && args.change.is_empty()
+        && args.revisions.is_empty()
+        && args.named.is_empty()
+    {
+        return Err(user_error_with_hint(
+            "No bookmarks to push to the URL were specified",
+            "Use --bookmark, --revisions, --change, --named, or --all to select the bookmarks to \
+             push.",
+        ));
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let change_bookmark_names = create_change_bookmarks(ui, &mut tx, &args.change)?;
+    let created_bookmark_names: Vec<RefNameBuf> = args
+        .named
+        .iter()
+        .map(|name_revision| create_explicitly_named_bookmarks(ui, &mut tx, name_revision))
+        .try_collect()?;
+
+    let view = tx.repo().view();
+    let mut bookmarks: Vec<(&RefName, &RefTarget)> = change_bookmark_names
+        .iter()
+        .chain(&created_bookmark_names)
+        .map(|name| (name.as_ref(), view.get_local_bookmark(name)))
+        .collect();
+    if args.all {
+        bookmarks.extend(view.local_bookmarks());
+    }
+    let mut unmatched_patterns = vec![];
+    for pattern in &args.bookmark {
+        let mut matches = view.local_bookmarks_matching(pattern).peekable();
+        if matches.peek().is_none() {
+            unmatched_patterns.push(pattern);
+        }
+        bookmarks.extend(matches);
+    }
+    check_unmatched_bookmark_patterns(&unmatched_patterns)?;
+    let revision_commit_ids =
+        resolve_bookmarked_revisions(ui, tx.base_workspace_helper(), &args.revisions)?;
+    bookmarks.extend(view.local_bookmarks().filter(|(_, target)| {
+        let mut local_ids = target.added_ids();
+        local_ids.any(|id| revision_commit_ids.contains(id))
+    }));
+
+    let mut bookmark_updates = vec![];
+    for (name, local_target) in bookmarks.into_iter().unique_by(|&(name, _)| name) {
+        let targets = LocalAndRemoteRef {
+            local_target,
+            remote_ref: RemoteRef::absent_ref(),
+        };
+        match classify_bookmark_push_action(targets) {
+            BookmarkPushAction::Update(update) => {
+                bookmark_updates.push((name.to_owned(), update));
+            }
+            BookmarkPushAction::LocalConflicted => {
+                return Err(user_error_with_hint(
+                    format!("Bookmark {name} is conflicted", name = name.as_symbol()),
+                    "Run `jj bookmark list` to inspect, and use `jj bookmark set` to fix it up.",
+                ));
+            }
+            BookmarkPushAction::AlreadyMatches
+            | BookmarkPushAction::RemoteConflicted
+            | BookmarkPushAction::RemoteUntracked => {}
+        }
+    }
+    let tx_description = format!(
+        "push {names} to git url {url}",
+        names = make_bookmark_term(
+            &bookmark_updates
+                .iter()
+                .map(|(name, _)| name.as_symbol())
+                .collect_vec()
+        ),
+    );
+    let destination = PushDestination::Url {
+        url,
+        track_as: None,
+    };
+    push_bookmark_updates(ui, tx, bookmark_updates, destination, args, tx_description)
+}
+
+/// Pushes the `bookmark_updates` to the `destination`, signing the commits
+/// first if configured.
+fn push_bookmark_updates(
+    ui: &mut Ui,
+    mut tx: WorkspaceCommandTransaction,
+    mut bookmark_updates: Vec<(RefNameBuf, BookmarkPushUpdate)>,
+    destination: PushDestination<'_>,
+    args: &GitPushArgs,
+    tx_description: String,
+) -> Result<(), CommandError> {
     if bookmark_updates.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
@@ -398,8 +688,14 @@ pub fn cmd_git_push(
     } else {
         None
     };
-    let commits_to_sign =
-        validate_commits_ready_to_push(ui, &bookmark_updates, remote, &tx, args, sign_behavior)?;
+    let commits_to_sign = validate_commits_ready_to_push(
+        ui,
+        &bookmark_updates,
+        destination.remote(),
+        &tx,
+        args,
+        sign_behavior,
+    )?;
     if !args.dry_run && !commits_to_sign.is_empty() {
         if let Some(sign_behavior) = sign_behavior {
             let num_updated_signatures = commits_to_sign.len();
@@ -426,11 +722,7 @@ pub fn cmd_git_push(
     }
 
     if let Some(mut formatter) = ui.status_formatter() {
-        writeln!(
-            formatter,
-            "Changes to push to {remote}:",
-            remote = remote.as_symbol()
-        )?;
+        writeln!(formatter, "Changes to push to {destination}:")?;
         print_commits_ready_to_push(formatter.as_mut(), tx.repo(), &bookmark_updates)?;
     }
 
@@ -443,10 +735,27 @@ pub fn cmd_git_push(
         branch_updates: bookmark_updates,
     };
     let git_settings = tx.settings().git_settings()?;
-    let push_stats = with_remote_git_callbacks(ui, |cb| {
-        git::push_branches(tx.repo_mut(), &git_settings, remote, &targets, cb)
-    })?;
-    process_push_stats(&push_stats)?;
+    let push_result = with_remote_git_callbacks(ui, |cb| match destination {
+        PushDestination::Remote(remote) => {
+            git::push_branches(tx.repo_mut(), &git_settings, remote, &targets, cb)
+        }
+        PushDestination::Url { url, track_as } => {
+            git::push_branches_to_url(tx.repo_mut(), &git_settings, url, track_as, &targets, cb)
+        }
+    });
+    if let Err(mut err) = push_result
+        .map_err(CommandError::from)
+        .and_then(|push_stats| process_push_stats(&push_stats))
+    {
+        if let PushDestination::Url { url, .. } = destination {
+            err.add_hint(format!("No remote was configured for {url}."));
+        }
+        return Err(err);
+    }
+    if !tx.repo().has_changes() {
+        // Nothing is recorded for bookmarks pushed to an untracked URL.
+        return Ok(());
+    }
     tx.finish(ui, tx_description)?;
     Ok(())
 }
@@ -503,7 +812,7 @@ fn process_push_stats(push_stats: &GitPushStats) -> Result<(), CommandError> {
 fn validate_commits_ready_to_push(
     ui: &Ui,
     bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
-    remote: &RemoteName,
+    remote: Option<&RemoteName>,
     tx: &WorkspaceCommandTransaction,
     args: &GitPushArgs,
     sign_behavior: Option<SignBehavior>,
@@ -515,9 +824,9 @@ fn validate_commits_ready_to_push(
         .iter()
         .filter_map(|(_, update)| update.new_target.clone())
         .collect_vec();
-    let old_heads = repo
-        .view()
-        .remote_bookmarks(remote)
+    let old_heads = remote
+        .into_iter()
+        .flat_map(|remote| repo.view().remote_bookmarks(remote))
         .flat_map(|(_, old_head)| old_head.target.added_ids())
         .cloned()
         .collect_vec();
@@ -537,7 +846,10 @@ fn validate_commits_ready_to_push(
         sign_settings
     });
 
+    let require_signed = settings.get_bool("git.push-require-signed")?;
+
     let mut commits_to_sign = vec![];
+    let mut unverified_commits = vec![];
 
     for commit in workspace_helper
         .attach_revset_evaluator(commits_to_push)
@@ -587,9 +899,30 @@ fn validate_commits_ready_to_push(
         if let Some(sign_settings) = &sign_settings {
             if !commit.is_signed() && sign_settings.should_sign(commit.store_commit()) {
                 commits_to_sign.push(commit);
+                continue;
+            }
+        }
+        if require_signed {
+            if let Some(reason) = missing_good_signature_reason(&commit) {
+                unverified_commits.push((commit, reason));
             }
         }
     }
+    if !unverified_commits.is_empty() {
+        let mut error = user_error(format!(
+            "Won't push {} commit(s) without a good signature",
+            unverified_commits.len()
+        ));
+        for (commit, reason) in &unverified_commits {
+            error.add_formatted_hint_with(|formatter| {
+                write!(formatter, "Rejected commit ({reason}): ")?;
+                workspace_helper.write_commit_summary(formatter, commit)?;
+                Ok(())
+            });
+        }
+        error.add_hint("Configured git.push-require-signed: true");
+        return Err(error);
+    }
     Ok(commits_to_sign)
 }
 
@@ -659,7 +992,15 @@ fn print_commits_ready_to_push(
         }
     };
 
-    for (bookmark_name, update) in bookmark_updates {
+    // Group the changes by action so new and deleted bookmarks stand out.
+    let sorted_updates = bookmark_updates.iter().sorted_by_key(|(_, update)| {
+        match (&update.old_target, &update.new_target) {
+            (None, _) => 0,
+            (Some(_), Some(_)) => 1,
+            (Some(_), None) => 2,
+        }
+    });
+    for (bookmark_name, update) in sorted_updates {
         match (&update.old_target, &update.new_target) {
             (Some(old_target), Some(new_target)) => {
                 let bookmark_name = bookmark_name.as_symbol();
@@ -708,9 +1049,36 @@ fn print_commits_ready_to_push(
     Ok(())
 }
 
+/// Determines the remotes to push to if `--remote` isn't specified.
+///
+/// If `push_all` is true, falling back to the "origin" remote is refused when
+/// there are multiple remotes, so that all bookmarks aren't published to a
+/// remote the user didn't choose.
+fn get_default_push_remotes(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    push_all: bool,
+) -> Result<Vec<RemoteNameBuf>, CommandError> {
+    let settings = workspace_command.settings();
+    if let Some(remotes) = settings
+        .get::<Vec<String>>("git.push-remotes")
+        .optional()?
+        .filter(|remotes| !remotes.is_empty())
+    {
+        Ok(remotes.into_iter().unique().map(Into::into).collect())
+    } else {
+        Ok(vec![get_default_push_remote(
+            ui,
+            workspace_command,
+            push_all,
+        )?])
+    }
+}
+
 fn get_default_push_remote(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
+    push_all: bool,
 ) -> Result<RemoteNameBuf, CommandError> {
     let settings = workspace_command.settings();
     if let Some(remote) = settings.get_string("git.push").optional()? {
@@ -725,6 +1093,12 @@ fn get_default_push_remote(
             )?;
         }
         Ok(remote)
+    } else if push_all && git::get_all_remote_names(workspace_command.repo().store())?.len() > 1 {
+        Err(user_error_with_hint(
+            "Refusing to push all bookmarks without an explicit remote when multiple remotes \
+             exist",
+            "Use --remote to specify the remote to push to, or set `git.push`.",
+        ))
     } else {
         Ok(DEFAULT_REMOTE.to_owned())
     }
@@ -755,6 +1129,35 @@ impl From<RejectedBookmarkUpdateReason> for CommandError {
     }
 }
 
+/// Classifies updates of all bookmarks selected by `filter`. Bookmarks that
+/// can't be pushed are reported as warnings and skipped.
+fn classify_all_bookmarks(
+    ui: &Ui,
+    view: &View,
+    remote: &RemoteName,
+    filter: impl Fn(&LocalAndRemoteRef) -> bool,
+    allow_new: bool,
+    allow_delete: bool,
+) -> io::Result<Vec<(RefNameBuf, BookmarkPushUpdate)>> {
+    let mut bookmark_updates = vec![];
+    for (name, targets) in view.local_remote_bookmarks(remote) {
+        if !filter(&targets) {
+            continue;
+        }
+        match classify_bookmark_update(
+            name.to_remote_symbol(remote),
+            targets,
+            allow_new,
+            allow_delete,
+        ) {
+            Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
+            Ok(None) => {}
+            Err(reason) => reason.print(ui)?,
+        }
+    }
+    Ok(bookmark_updates)
+}
+
 fn classify_bookmark_update(
     remote_symbol: RemoteRefSymbol<'_>,
     targets: LocalAndRemoteRef,
@@ -904,6 +1307,13 @@ fn create_change_bookmarks(
                 if name.is_empty() {
                     return Err(user_error("Empty bookmark name generated"));
                 }
+                gix::validate::reference::name_partial(name.as_str().into()).map_err(|err| {
+                    user_error_with_message(
+                        format!("Invalid bookmark name generated: {name:?}"),
+                        err,
+                    )
+                    .hinted("Check the `templates.git_push_bookmark` config")
+                })?;
                 Ok(RefNameBuf::from(name))
             })
             .try_collect()?
@@ -949,8 +1359,13 @@ fn find_bookmarks_to_push<'a>(
         }
         matching_bookmarks.extend(matches);
     }
-    match &unmatched_patterns[..] {
-        [] => Ok(matching_bookmarks),
+    check_unmatched_bookmark_patterns(&unmatched_patterns)?;
+    Ok(matching_bookmarks)
+}
+
+fn check_unmatched_bookmark_patterns(patterns: &[&StringPattern]) -> Result<(), CommandError> {
+    match patterns {
+        [] => Ok(()),
         [pattern] if pattern.is_exact() => Err(user_error(format!("No such bookmark: {pattern}"))),
         patterns => Err(user_error(format!(
             "No matching bookmarks for patterns: {}",
@@ -959,6 +1374,48 @@ fn find_bookmarks_to_push<'a>(
     }
 }
 
+/// Finds tracked bookmarks pointing to the commits to be pushed, which aren't
+/// pushed themselves and whose remote bookmark is out of date.
+fn find_stale_ancestor_bookmarks<'a>(
+    workspace_command: &WorkspaceCommandHelper,
+    view: &'a View,
+    remote: &RemoteName,
+    bookmark_updates: &[(RefNameBuf, BookmarkPushUpdate)],
+) -> Result<Vec<(&'a RefName, LocalAndRemoteRef<'a>)>, CommandError> {
+    let new_heads = bookmark_updates
+        .iter()
+        .filter_map(|(_, update)| update.new_target.clone())
+        .collect_vec();
+    let old_heads = view
+        .remote_bookmarks(remote)
+        .flat_map(|(_, old_head)| old_head.target.added_ids())
+        .cloned()
+        .collect_vec();
+    let commits_to_push = RevsetExpression::commits(old_heads)
+        .union(workspace_command.env().immutable_heads_expression())
+        .range(&RevsetExpression::commits(new_heads));
+    let is_pushed = workspace_command
+        .attach_revset_evaluator(commits_to_push)
+        .evaluate()?
+        .containing_fn();
+    let mut stale_bookmarks = vec![];
+    for (name, targets) in view.local_remote_bookmarks(remote) {
+        if !targets.remote_ref.is_tracked()
+            || targets.local_target == &targets.remote_ref.target
+            || bookmark_updates.iter().any(|(n, _)| n == name)
+        {
+            continue;
+        }
+        for id in targets.local_target.added_ids() {
+            if is_pushed(id)? {
+                stale_bookmarks.push((name, targets));
+                break;
+            }
+        }
+    }
+    Ok(stale_bookmarks)
+}
+
 fn find_bookmarks_targeted_by_revisions<'a>(
     ui: &Ui,
     workspace_command: &'a WorkspaceCommandHelper,
@@ -993,6 +1450,31 @@ fn find_bookmarks_targeted_by_revisions<'a>(
             revision_commit_ids.insert(commit_id?);
         }
     }
+    revision_commit_ids.extend(resolve_bookmarked_revisions(
+        ui,
+        workspace_command,
+        revisions,
+    )?);
+    let bookmarks_targeted = workspace_command
+        .repo()
+        .view()
+        .local_remote_bookmarks(remote)
+        .filter(|(_, targets)| {
+            let mut local_ids = targets.local_target.added_ids();
+            local_ids.any(|id| revision_commit_ids.contains(id))
+        })
+        .collect_vec();
+    Ok(bookmarks_targeted)
+}
+
+/// Resolves the `revisions` to the commits pointed to by bookmarks. Warns about
+/// the revisions which no bookmarks point to.
+fn resolve_bookmarked_revisions(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    revisions: &[RevisionArg],
+) -> Result<HashSet<CommitId>, CommandError> {
+    let mut revision_commit_ids = HashSet::new();
     for rev_arg in revisions {
         let mut expression = workspace_command.parse_revset(ui, rev_arg)?;
         expression.intersect_with(&RevsetExpression::bookmarks(StringPattern::everything()));
@@ -1007,14 +1489,5 @@ fn find_bookmarks_targeted_by_revisions<'a>(
             revision_commit_ids.insert(commit_id?);
         }
     }
-    let bookmarks_targeted = workspace_command
-        .repo()
-        .view()
-        .local_remote_bookmarks(remote)
-        .filter(|(_, targets)| {
-            let mut local_ids = targets.local_target.added_ids();
-            local_ids.any(|id| revision_commit_ids.contains(id))
-        })
-        .collect_vec();
-    Ok(bookmarks_targeted)
+    Ok(revision_commit_ids)
 }
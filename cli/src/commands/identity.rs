@@ -0,0 +1,213 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::config::ConfigGetError;
+use jj_lib::settings::UserSettings;
+use tracing::instrument;
+
+use super::config::ConfigLevelArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Manage identity profiles
+///
+/// Identity profiles are named sets of `user.name`, `user.email`, and
+/// `signing.key` settings defined in the `identities` config table:
+///
+/// ```toml
+/// [identities.work]
+/// name = "Your Name"
+/// email = "you@work.example.com"
+/// signing-key = "..."
+/// ```
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum IdentityCommand {
+    #[command(visible_alias("l"))]
+    List(IdentityListArgs),
+    Use(IdentityUseArgs),
+}
+
+/// List identity profiles
+///
+/// The profile matching the current settings is marked as active.
+#[derive(clap::Args, Clone, Debug)]
+pub struct IdentityListArgs {}
+
+/// Switch to an identity profile
+///
+/// Copies the profile's settings into the chosen config file. The settings
+/// only apply to new commits. Use `jj describe --reset-author --no-edit` to
+/// update the author of the working-copy commit.
+#[derive(clap::Args, Clone, Debug)]
+pub struct IdentityUseArgs {
+    /// The name of the profile
+    #[arg(add = ArgValueCandidates::new(complete::identity_profiles))]
+    profile: String,
+    #[command(flatten)]
+    level: ConfigLevelArgs,
+}
+
+/// Entry of the `identities` config table.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct IdentityProfile {
+    name: Option<String>,
+    email: Option<String>,
+    signing_key: Option<String>,
+}
+
+impl IdentityProfile {
+    /// Returns `(config_name, value)` pairs of the settings defined by this
+    /// profile.
+    fn config_values(&self) -> impl Iterator<Item = ([&'static str; 2], &str)> {
+        [
+            (["user", "name"], &self.name),
+            (["user", "email"], &self.email),
+            (["signing", "key"], &self.signing_key),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value.as_deref()?)))
+    }
+
+    /// Returns true if all settings defined by this profile are in effect.
+    fn is_active(&self, settings: &UserSettings) -> bool {
+        self.config_values()
+            .all(|(name, value)| settings.get_string(name).is_ok_and(|s| s == value))
+    }
+}
+
+fn get_identity_profiles(
+    settings: &UserSettings,
+) -> Result<Vec<(String, IdentityProfile)>, ConfigGetError> {
+    settings
+        .table_keys("identities")
+        .sorted()
+        .map(|name| Ok((name.to_owned(), settings.get(["identities", name])?)))
+        .try_collect()
+}
+
+#[instrument(skip_all)]
+pub fn cmd_identity(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &IdentityCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        IdentityCommand::List(args) => cmd_identity_list(ui, command, args),
+        IdentityCommand::Use(args) => cmd_identity_use(ui, command, args),
+    }
+}
+
+fn cmd_identity_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &IdentityListArgs,
+) -> Result<(), CommandError> {
+    let settings = command.settings();
+    let profiles = get_identity_profiles(settings)?;
+    if profiles.is_empty() {
+        writeln!(ui.warning_default(), "No identity profiles are configured")?;
+        return Ok(());
+    }
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (name, profile) in &profiles {
+        write!(formatter.labeled("identity"), "{name}")?;
+        write!(formatter, ":")?;
+        if let Some(user_name) = &profile.name {
+            write!(formatter, " {user_name}")?;
+        }
+        if let Some(email) = &profile.email {
+            write!(formatter, " <{email}>")?;
+        }
+        if profile.is_active(settings) {
+            write!(formatter.labeled("active"), " (active)")?;
+        }
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+fn cmd_identity_use(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &IdentityUseArgs,
+) -> Result<(), CommandError> {
+    let settings = command.settings();
+    if !settings
+        .table_keys("identities")
+        .contains(&args.profile.as_str())
+    {
+        return Err(user_error(format!(
+            "No such identity profile: {}",
+            args.profile
+        )));
+    }
+    let profile: IdentityProfile = settings.get(["identities", &args.profile])?;
+    let mut file = args.level.edit_config_file(ui, command)?;
+    for (name, value) in profile.config_values() {
+        file.set_value(name, value).map_err(|err| {
+            user_error_with_message(format!("Failed to set {}", name.join(".")), err)
+        })?;
+    }
+    file.save()?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(
+            formatter,
+            "Switched to identity profile {} in {}",
+            args.profile,
+            file.path().display()
+        )?;
+    }
+    Ok(())
+}
+
+/// Warns if `ui.warn-unknown-identity` is enabled and the configured email
+/// doesn't match any identity profile.
+pub(crate) fn warn_if_unknown_identity(
+    ui: &Ui,
+    settings: &UserSettings,
+) -> Result<(), CommandError> {
+    if !settings.get_bool("ui.warn-unknown-identity")? {
+        return Ok(());
+    }
+    let profiles = get_identity_profiles(settings)?;
+    let email = settings.user_email();
+    if profiles
+        .iter()
+        .any(|(_, profile)| profile.email.as_deref() == Some(email))
+    {
+        return Ok(());
+    }
+    writeln!(
+        ui.warning_default(),
+        "The configured email <{email}> doesn't match any identity profile"
+    )?;
+    if !profiles.is_empty() {
+        writeln!(
+            ui.hint_default(),
+            "Use `jj identity use <profile>` to switch to one of: {}",
+            profiles.iter().map(|(name, _)| name).join(", ")
+        )?;
+    }
+    Ok(())
+}
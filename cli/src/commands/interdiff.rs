@@ -16,14 +16,19 @@ use std::slice;
 
 use clap::ArgGroup;
 use clap_complete::ArgValueCompleter;
+use jj_lib::copies::CopyRecords;
+use jj_lib::repo::Repo as _;
+use jj_lib::rewrite::rebase_to_dest_parent;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
+use crate::command_error::silent_error;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::has_diff;
 use crate::ui::Ui;
 
 /// Compare the changes of two commits
@@ -61,6 +66,14 @@ pub(crate) struct InterdiffArgs {
     paths: Vec<String>,
     #[command(flatten)]
     format: DiffFormatArgs,
+    /// Exit with status 1 if there are differences in file contents, and 0
+    /// otherwise
+    ///
+    /// Changes in whitespace are ignored if `--ignore-all-space` or
+    /// `--ignore-space-change` is specified. If the global `--quiet` flag is
+    /// also specified, the diff isn't printed.
+    #[arg(long)]
+    exit_code: bool,
 }
 
 #[instrument(skip_all)]
@@ -68,6 +81,19 @@ pub(crate) fn cmd_interdiff(
     ui: &mut Ui,
     command: &CommandHelper,
     args: &InterdiffArgs,
+) -> Result<(), CommandError> {
+    let result = interdiff_impl(ui, command, args);
+    if args.exit_code {
+        result.map_err(CommandError::distinct_from_silent)
+    } else {
+        result
+    }
+}
+
+fn interdiff_impl(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &InterdiffArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
     let from =
@@ -77,6 +103,27 @@ pub(crate) fn cmd_interdiff(
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
+    let diff_found = if args.exit_code {
+        let repo = workspace_command.repo();
+        let from_tree = rebase_to_dest_parent(repo.as_ref(), slice::from_ref(&from), &to)?;
+        let to_tree = to.tree()?;
+        let copy_records = CopyRecords::default();
+        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
+        has_diff(repo.store(), tree_diff, &args.format).block_on()?
+    } else {
+        false
+    };
+    let diff_result = || {
+        if diff_found {
+            Err(silent_error("Differences found"))
+        } else {
+            Ok(())
+        }
+    };
+    if args.exit_code && workspace_command.settings().get_bool("ui.quiet")? {
+        return diff_result();
+    }
+
     let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
     ui.request_pager();
     diff_renderer
@@ -89,5 +136,5 @@ pub(crate) fn cmd_interdiff(
             ui.term_width(),
         )
         .block_on()?;
-    Ok(())
+    diff_result()
 }
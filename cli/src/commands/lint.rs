@@ -0,0 +1,128 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetIteratorExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::lint_util::LintSettings;
+use crate::lint_util::LintViolation;
+use crate::ui::Ui;
+
+/// Check revisions for common problems
+///
+/// The checks are configured by the `lint` config table. See the [lint
+/// settings] for the available checks. The command fails if any revision
+/// doesn't pass the checks.
+///
+/// [lint settings]:
+///     https://jj-vcs.github.io/jj/latest/config/#linting-revisions
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct LintArgs {
+    /// The revision(s) to check
+    ///
+    /// If no revisions are specified, this defaults to the `revsets.lint`
+    /// setting.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_lint(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &LintArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let lint_settings = LintSettings::from_settings(workspace_command.settings())?;
+    let revset_expression = if args.revisions.is_empty() {
+        let revset_string = workspace_command.settings().get_string("revsets.lint")?;
+        workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+    } else {
+        workspace_command.parse_union_revsets(ui, &args.revisions)?
+    };
+    let repo = workspace_command.repo().as_ref();
+    let template = workspace_command.commit_summary_template();
+
+    let mut num_checked = 0;
+    let mut num_failed = 0;
+    let commits = revset_expression.evaluate()?.iter().commits(repo.store());
+    for commit in commits {
+        let commit = commit?;
+        num_checked += 1;
+        let violations = lint_settings.check_commit(repo, &commit)?;
+        if violations.is_empty() {
+            continue;
+        }
+        num_failed += 1;
+        let mut formatter = ui.stdout_formatter();
+        template.format(&commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+        for violation in &violations {
+            writeln!(
+                formatter,
+                "  {}",
+                format_violation(&workspace_command, violation)
+            )?;
+        }
+    }
+
+    if num_failed > 0 {
+        return Err(user_error(format!(
+            "{num_failed} of {num_checked} commits failed the checks"
+        )));
+    }
+    writeln!(
+        ui.status(),
+        "Checked {num_checked} commits, no problems found"
+    )?;
+    Ok(())
+}
+
+fn format_violation(
+    workspace_command: &WorkspaceCommandHelper,
+    violation: &LintViolation,
+) -> String {
+    match violation {
+        LintViolation::EmptyDescription => "Description is empty".to_owned(),
+        LintViolation::SubjectTooLong { length, max } => {
+            format!("Subject is {length} characters long, more than {max}")
+        }
+        LintViolation::ForbiddenPattern { pattern } => {
+            format!("Description contains forbidden pattern: {pattern}")
+        }
+        LintViolation::MergeCommit => "Merge commits are not allowed".to_owned(),
+        LintViolation::NotSigned { reason } => {
+            format!("Commit doesn't have a good signature ({reason})")
+        }
+        LintViolation::FileTooLarge { path, max } => format!(
+            "File {} is larger than {max}",
+            workspace_command.format_file_path(path)
+        ),
+    }
+}
@@ -12,32 +12,68 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::io;
+use std::slice;
+use std::sync::Arc;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend;
+use jj_lib::backend::BackendResult;
+use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::MergedTreeId;
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Signature;
+use jj_lib::backend::Timestamp;
 use jj_lib::commit::Commit;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::graph::reverse_graph;
-use jj_lib::repo::Repo as _;
+use jj_lib::matchers::FilesMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::op_walk;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::revset;
+use jj_lib::revset::Revset;
+use jj_lib::revset::RevsetContainingFn;
+use jj_lib::revset::RevsetDiagnostics;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
-use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::store::Store;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::RevisionsFileArgs;
+use crate::cli_util::TemplateFileArgs;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::format_template;
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
+use crate::command_error::config_error_with_message;
+use crate::command_error::print_parse_diagnostics;
+use crate::command_error::user_error;
+use crate::commit_templater::is_index_only_template;
 use crate::complete;
+use crate::diff_util;
 use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::MergeDiffArgs;
+use crate::formatter::Formatter;
+use crate::graphlog::GraphLog;
 use crate::graphlog::GraphStyle;
 use crate::graphlog::get_graphlog;
+use crate::template_parser;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
@@ -76,6 +112,8 @@ pub(crate) struct LogArgs {
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
     revisions: Vec<RevisionArg>,
+    #[command(flatten)]
+    revisions_file: RevisionsFileArgs,
     /// Show revisions modifying the given paths
     #[arg(
         value_name = "FILESETS",
@@ -83,6 +121,14 @@ pub(crate) struct LogArgs {
         add = ArgValueCompleter::new(complete::log_files),
     )]
     paths: Vec<String>,
+    /// Follow the history of a single file across renames and copies
+    ///
+    /// Revisions are shown if they modified the file given as the only path,
+    /// or the file it was renamed or copied from in a later revision. If no
+    /// revisions are specified, the file is followed from the working-copy
+    /// commit.
+    #[arg(long, requires = "paths")]
+    follow: bool,
     /// Limit number of revisions to show
     ///
     /// Applied after revisions are filtered and reordered topologically, but
@@ -95,6 +141,13 @@ pub(crate) struct LogArgs {
     /// Don't show the graph, show a flat list of revisions
     #[arg(long)]
     no_graph: bool,
+    /// Select revisions from all commits including hidden ones
+    ///
+    /// Abandoned and rewritten revisions are included in the revset as if
+    /// they were visible. Hidden revisions are labeled `hidden`, and
+    /// annotated with the visible revision they were rewritten as, if any.
+    #[arg(long)]
+    hidden: bool,
     /// Render each revision using the given template
     ///
     /// Run `jj log -T` to list the built-in templates.
@@ -112,11 +165,21 @@ pub(crate) struct LogArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+    #[command(flatten)]
+    template_file: TemplateFileArgs,
+    /// Show a summary of each revision's cryptographic signature
+    ///
+    /// The summary is printed before the rendered revision. Unsigned
+    /// revisions are shown as usual.
+    #[arg(long)]
+    show_signatures: bool,
     /// Show patch
     #[arg(long, short = 'p')]
     patch: bool,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
+    #[command(flatten)]
+    merge_diff: MergeDiffArgs,
 }
 
 #[instrument(skip_all)]
@@ -125,38 +188,79 @@ pub(crate) fn cmd_log(
     command: &CommandHelper,
     args: &LogArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
+    // Diffs should be computed from the up-to-date working-copy commit.
+    let workspace_command =
+        if diff_util::diff_formats_for_log(command.settings(), &args.diff_format, args.patch)?
+            .is_empty()
+        {
+            command.workspace_helper_for_query(ui)?
+        } else {
+            command.workspace_helper(ui)?
+        };
     let settings = workspace_command.settings();
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
-    let revset_expression = {
+    let revisions = args.revisions_file.with_revisions(&args.revisions)?;
+    let followed_path = if args.follow {
+        let [path] = args.paths.as_slice() else {
+            return Err(user_error("--follow requires a single file path"));
+        };
+        Some(workspace_command.parse_file_path(path)?)
+    } else {
+        None
+    };
+    let mut revset_expression = {
         // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
+        let mut expression = if let Some(revisions) = &revisions {
+            workspace_command.parse_union_revsets(ui, revisions)?
+        } else if args.paths.is_empty() {
             let revset_string = settings.get_string("revsets.log")?;
             workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
-        } else if !args.revisions.is_empty() {
-            workspace_command.parse_union_revsets(ui, &args.revisions)?
+        } else if args.follow {
+            // like `git log --follow`, walk back from the working-copy commit
+            let name = workspace_command.workspace_name().to_owned();
+            let expression = RevsetExpression::working_copy(name).ancestors();
+            workspace_command.attach_revset_evaluator(expression)
         } else {
             // a path was specified so we use all() and add path filter later
             workspace_command.attach_revset_evaluator(RevsetExpression::all())
         };
-        if !args.paths.is_empty() {
+        if !args.paths.is_empty() && followed_path.is_none() {
             // Beware that args.paths = ["root:."] is not identical to []. The
             // former will filter out empty commits.
             let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
             expression.intersect_with(&RevsetExpression::filter(predicate));
         }
+        if args.hidden {
+            expression.include_hidden().map_err(user_error)?;
+        }
         expression
     };
     let prio_revset = settings.get_string("revsets.log-graph-prioritize")?;
     let prio_revset = workspace_command.parse_revset(ui, &RevisionArg::from(prio_revset))?;
 
     let repo = workspace_command.repo();
-    let matcher = fileset_expression.to_matcher();
+    let matcher: Box<dyn Matcher> = if let Some(path) = followed_path {
+        // The file may have been renamed between the shown revisions, so walk
+        // the revisions connecting them, but not their other ancestors.
+        let expression = revset_expression.expression();
+        let mut walk_expression =
+            workspace_command.attach_revset_evaluator(expression.dag_range_to(expression));
+        if args.hidden {
+            walk_expression.include_hidden().map_err(user_error)?;
+        }
+        let (commit_ids, paths) =
+            follow_file_history(repo.as_ref(), &*walk_expression.evaluate()?, path)?;
+        revset_expression.intersect_with(&RevsetExpression::commits(commit_ids));
+        Box::new(FilesMatcher::new(paths))
+    } else {
+        fileset_expression.to_matcher()
+    };
     let revset = revset_expression.evaluate()?;
 
     let store = repo.store();
     let diff_renderer = workspace_command.diff_renderer_for_log(&args.diff_format, args.patch)?;
+    let merge_diff_mode = args.merge_diff.mode();
     let graph_style = GraphStyle::from_settings(settings)?;
 
     let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
@@ -164,20 +268,54 @@ pub(crate) fn cmd_log(
 
     let template: TemplateRenderer<Commit>;
     let node_template: TemplateRenderer<Option<Commit>>;
+    let index_only;
     {
         let language = workspace_command.commit_template_language();
-        let template_string = match &args.template {
-            Some(value) => value.clone(),
+        let template_string = match args.template_file.template_text(args.template.as_deref())? {
+            Some(value) => value,
             None => settings.get_string("templates.log")?,
         };
+        let template_string = if args.show_signatures {
+            format!("format_signature_summary(self.signature()) ++ ({template_string})")
+        } else {
+            template_string
+        };
         template = workspace_command
             .parse_template(ui, &language, &template_string)?
             .labeled(["log", "commit"]);
+        // The graph needs the parents, and the node template can't be
+        // analyzed as easily.
+        index_only = args.no_graph
+            && diff_renderer.is_none()
+            && template_parser::parse(
+                &template_string,
+                workspace_command.env().template_aliases_map(),
+            )
+            .is_ok_and(|node| is_index_only_template(&node));
         node_template = workspace_command
             .parse_template(ui, &language, &settings.get_string("templates.log_node")?)?
             .labeled(["log", "commit", "node"]);
     }
 
+    let revset_labels = load_revset_labels(ui, &workspace_command)?;
+    let successors = if args.hidden {
+        Some(SuccessorMap::load(repo)?)
+    } else {
+        None
+    };
+    // Returns the visible successor if the commit is hidden.
+    let hidden_commit_info = |commit: &Commit| -> Result<Option<Option<CommitId>>, CommandError> {
+        let Some(successors) = &successors else {
+            return Ok(None);
+        };
+        if !commit.is_hidden(repo.as_ref()) {
+            return Ok(None);
+        }
+        Ok(Some(
+            successors.visible_successor(repo.as_ref(), commit.id())?,
+        ))
+    };
+
     {
         ui.request_pager();
         let mut formatter = ui.stdout_formatter();
@@ -209,88 +347,63 @@ pub(crate) fn cmd_log(
             };
             for node in iter {
                 let (commit_id, edges) = node?;
-
-                // The graph is keyed by (CommitId, is_synthetic)
-                let mut graphlog_edges = vec![];
-                // TODO: Should we update revset.iter_graph() to yield a `has_missing` flag
-                // instead of all the missing edges since we don't care about
-                // where they point here anyway?
-                let mut missing_edge_id = None;
-                let mut elided_targets = vec![];
-                for edge in edges {
-                    match edge.edge_type {
-                        GraphEdgeType::Missing => {
-                            missing_edge_id = Some(edge.target);
+                let commit = store.get_commit(&commit_id)?;
+                let mut labels = revset_labels.matching_labels(commit.id())?;
+                let hidden_info = hidden_commit_info(&commit)?;
+                if hidden_info.is_some() {
+                    labels.push("hidden");
+                }
+                let options = LogGraphOptions {
+                    with_content_format: &with_content_format,
+                    node_template: &node_template,
+                    use_elided_nodes,
+                };
+                add_commit_node(
+                    ui,
+                    graph.as_mut(),
+                    &options,
+                    commit,
+                    edges,
+                    |buffer, commit, within_graph| {
+                        within_graph.write(
+                            ui.new_formatter(&mut *buffer).as_mut(),
+                            |formatter| {
+                                write_labeled(formatter, &labels, |formatter| {
+                                    template.format(commit, formatter)
+                                })
+                            },
+                        )?;
+                        if !buffer.ends_with(b"\n") {
+                            buffer.push(b'\n');
                         }
-                        GraphEdgeType::Direct => {
-                            graphlog_edges.push(GraphEdge::direct((edge.target, false)));
+                        if let Some(Some(successor_id)) = &hidden_info {
+                            let mut formatter = ui.new_formatter(&mut *buffer);
+                            within_graph.write(formatter.as_mut(), |formatter| {
+                                write_rewritten_as(formatter, successor_id)
+                            })?;
                         }
-                        GraphEdgeType::Indirect => {
-                            if use_elided_nodes {
-                                elided_targets.push(edge.target.clone());
-                                graphlog_edges.push(GraphEdge::direct((edge.target, true)));
-                            } else {
-                                graphlog_edges.push(GraphEdge::indirect((edge.target, false)));
-                            }
+                        if let Some(renderer) = &diff_renderer {
+                            let mut formatter = ui.new_formatter(buffer);
+                            renderer
+                                .show_merge_patch(
+                                    ui,
+                                    formatter.as_mut(),
+                                    commit,
+                                    matcher.as_ref(),
+                                    within_graph.width(),
+                                    merge_diff_mode,
+                                )
+                                .block_on()?;
                         }
-                    }
-                }
-                if let Some(missing_edge_id) = missing_edge_id {
-                    graphlog_edges.push(GraphEdge::missing((missing_edge_id, false)));
-                }
-                let mut buffer = vec![];
-                let key = (commit_id, false);
-                let commit = store.get_commit(&key.0)?;
-                let within_graph =
-                    with_content_format.sub_width(graph.width(&key, &graphlog_edges));
-                within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
-                    template.format(&commit, formatter)
-                })?;
-                if !buffer.ends_with(b"\n") {
-                    buffer.push(b'\n');
-                }
-                if let Some(renderer) = &diff_renderer {
-                    let mut formatter = ui.new_formatter(&mut buffer);
-                    renderer
-                        .show_patch(
-                            ui,
-                            formatter.as_mut(),
-                            &commit,
-                            matcher.as_ref(),
-                            within_graph.width(),
-                        )
-                        .block_on()?;
-                }
-
-                let node_symbol = format_template(ui, &Some(commit), &node_template);
-                graph.add_node(
-                    &key,
-                    &graphlog_edges,
-                    &node_symbol,
-                    &String::from_utf8_lossy(&buffer),
+                        Ok(())
+                    },
                 )?;
-                for elided_target in elided_targets {
-                    let elided_key = (elided_target, true);
-                    let real_key = (elided_key.0.clone(), false);
-                    let edges = [GraphEdge::direct(real_key)];
-                    let mut buffer = vec![];
-                    let within_graph =
-                        with_content_format.sub_width(graph.width(&elided_key, &edges));
-                    within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
-                        writeln!(formatter.labeled("elided"), "(elided revisions)")
-                    })?;
-                    let node_symbol = format_template(ui, &None, &node_template);
-                    graph.add_node(
-                        &elided_key,
-                        &edges,
-                        &node_symbol,
-                        &String::from_utf8_lossy(&buffer),
-                    )?;
-                }
             }
         } else {
-            let iter: Box<dyn Iterator<Item = Result<CommitId, RevsetEvaluationError>>> = {
-                let forward_iter = revset.iter().take(args.limit.unwrap_or(usize::MAX));
+            let iter: Box<dyn Iterator<Item = Result<_, RevsetEvaluationError>>> = {
+                let forward_iter = revset
+                    .commit_change_ids()
+                    .take(args.limit.unwrap_or(usize::MAX));
                 if args.reversed {
                     let entries: Vec<_> = forward_iter.try_collect()?;
                     Box::new(entries.into_iter().rev().map(Ok))
@@ -298,23 +411,51 @@ pub(crate) fn cmd_log(
                     Box::new(forward_iter)
                 }
             };
-            for commit_or_error in iter.commits(store) {
-                let commit = commit_or_error?;
-                with_content_format
-                    .write(formatter, |formatter| template.format(&commit, formatter))?;
+            let mut num_loaded_commits = 0;
+            for ids_or_error in iter {
+                let (commit_id, change_id) = ids_or_error?;
+                let commit = if index_only {
+                    index_only_commit(store, commit_id, change_id)
+                } else {
+                    num_loaded_commits += 1;
+                    store.get_commit(&commit_id)?
+                };
+                let mut labels = revset_labels.matching_labels(commit.id())?;
+                let hidden_info = hidden_commit_info(&commit)?;
+                if hidden_info.is_some() {
+                    labels.push("hidden");
+                }
+                with_content_format.write(formatter, |formatter| {
+                    write_labeled(formatter, &labels, |formatter| {
+                        template.format(&commit, formatter)
+                    })
+                })?;
+                if let Some(Some(successor_id)) = &hidden_info {
+                    with_content_format.write(formatter, |formatter| {
+                        write_rewritten_as(formatter, successor_id)
+                    })?;
+                }
                 if let Some(renderer) = &diff_renderer {
                     let width = ui.term_width();
                     renderer
-                        .show_patch(ui, formatter, &commit, matcher.as_ref(), width)
+                        .show_merge_patch(
+                            ui,
+                            formatter,
+                            &commit,
+                            matcher.as_ref(),
+                            width,
+                            merge_diff_mode,
+                        )
                         .block_on()?;
                 }
             }
+            tracing::debug!(index_only, num_loaded_commits, "rendered log without graph");
         }
     }
 
     // Check to see if the user might have specified a path when they intended
     // to specify a revset.
-    if let ([], [only_path]) = (args.revisions.as_slice(), args.paths.as_slice()) {
+    if let (None, [only_path]) = (&revisions, args.paths.as_slice()) {
         if only_path == "." && workspace_command.parse_file_path(only_path)?.is_root() {
             // For users of e.g. Mercurial, where `.` indicates the current commit.
             writeln!(
@@ -338,3 +479,296 @@ pub(crate) fn cmd_log(
 
     Ok(())
 }
+
+/// Settings shared by the commit nodes of a log graph.
+pub(crate) struct LogGraphOptions<'a> {
+    pub with_content_format: &'a LogContentFormat,
+    pub node_template: &'a TemplateRenderer<'a, Option<Commit>>,
+    /// Whether indirect edges are drawn through synthetic "(elided
+    /// revisions)" nodes.
+    pub use_elided_nodes: bool,
+}
+
+/// Adds the node of `commit` to the `graph` as `jj log` draws it, followed by
+/// the synthetic nodes of elided revisions. The graph is keyed by `(CommitId,
+/// is_synthetic)`.
+///
+/// `write_content` writes the text next to the node, given the format of the
+/// content within the graph.
+pub(crate) fn add_commit_node(
+    ui: &Ui,
+    graph: &mut dyn GraphLog<(CommitId, bool)>,
+    options: &LogGraphOptions,
+    commit: Commit,
+    edges: Vec<GraphEdge<CommitId>>,
+    write_content: impl FnOnce(&mut Vec<u8>, &Commit, &LogContentFormat) -> Result<(), CommandError>,
+) -> Result<(), CommandError> {
+    let mut graphlog_edges = vec![];
+    // TODO: Should we update revset.iter_graph() to yield a `has_missing` flag
+    // instead of all the missing edges since we don't care about
+    // where they point here anyway?
+    let mut missing_edge_id = None;
+    let mut elided_targets = vec![];
+    for edge in edges {
+        match edge.edge_type {
+            GraphEdgeType::Missing => {
+                missing_edge_id = Some(edge.target);
+            }
+            GraphEdgeType::Direct => {
+                graphlog_edges.push(GraphEdge::direct((edge.target, false)));
+            }
+            GraphEdgeType::Indirect => {
+                if options.use_elided_nodes {
+                    elided_targets.push(edge.target.clone());
+                    graphlog_edges.push(GraphEdge::direct((edge.target, true)));
+                } else {
+                    graphlog_edges.push(GraphEdge::indirect((edge.target, false)));
+                }
+            }
+        }
+    }
+    if let Some(missing_edge_id) = missing_edge_id {
+        graphlog_edges.push(GraphEdge::missing((missing_edge_id, false)));
+    }
+    let mut buffer = vec![];
+    let key = (commit.id().clone(), false);
+    let within_graph = options
+        .with_content_format
+        .sub_width(graph.width(&key, &graphlog_edges));
+    write_content(&mut buffer, &commit, &within_graph)?;
+
+    let node_symbol = format_template(ui, &Some(commit), options.node_template);
+    graph.add_node(
+        &key,
+        &graphlog_edges,
+        &node_symbol,
+        &String::from_utf8_lossy(&buffer),
+    )?;
+    for elided_target in elided_targets {
+        let elided_key = (elided_target, true);
+        let real_key = (elided_key.0.clone(), false);
+        let edges = [GraphEdge::direct(real_key)];
+        let mut buffer = vec![];
+        let within_graph = options
+            .with_content_format
+            .sub_width(graph.width(&elided_key, &edges));
+        within_graph.write(ui.new_formatter(&mut buffer).as_mut(), |formatter| {
+            writeln!(formatter.labeled("elided"), "(elided revisions)")
+        })?;
+        let node_symbol = format_template(ui, &None, options.node_template);
+        graph.add_node(
+            &elided_key,
+            &edges,
+            &node_symbol,
+            &String::from_utf8_lossy(&buffer),
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the commits in `revset` that modified `path`, and the paths it was
+/// renamed or copied from.
+///
+/// Like `git log --follow`, the revisions are walked from children to parents,
+/// and the source of a copy is followed in all the remaining revisions.
+fn follow_file_history(
+    repo: &dyn Repo,
+    revset: &dyn Revset,
+    path: RepoPathBuf,
+) -> Result<(Vec<CommitId>, Vec<RepoPathBuf>), CommandError> {
+    let store = repo.store();
+    let mut paths = vec![path];
+    let mut commit_ids = vec![];
+    for commit_id in revset.iter() {
+        let commit = store.get_commit(&commit_id?)?;
+        let parent_tree = commit.parent_tree(repo)?;
+        let tree = commit.tree()?;
+        let mut modified = false;
+        let mut added_paths = vec![];
+        for path in &paths {
+            let parent_value = parent_tree.path_value(path)?;
+            if tree.path_value(path)? != parent_value {
+                modified = true;
+                if parent_value.is_absent() {
+                    added_paths.push(path.clone());
+                }
+            }
+        }
+        if !modified {
+            continue;
+        }
+        for parent_id in commit.parent_ids() {
+            follow_copy_sources(store, parent_id, commit.id(), &added_paths, &mut paths)?;
+        }
+        commit_ids.push(commit.id().clone());
+    }
+    Ok((commit_ids, paths))
+}
+
+/// Adds the sources of the files `added_paths` copied between `from_id` and
+/// `to_id` to `paths`.
+pub(crate) fn follow_copy_sources(
+    store: &Store,
+    from_id: &CommitId,
+    to_id: &CommitId,
+    added_paths: &[RepoPathBuf],
+    paths: &mut Vec<RepoPathBuf>,
+) -> Result<(), CommandError> {
+    if added_paths.is_empty() {
+        return Ok(());
+    }
+    let matcher = FilesMatcher::new(added_paths);
+    for record in diff_util::get_copy_records(store, from_id, to_id, &matcher)? {
+        let source = record?.source;
+        if !paths.contains(&source) {
+            paths.push(source);
+        }
+    }
+    Ok(())
+}
+
+/// Labels to be applied to commits matching revsets, configured by the
+/// `colors.revsets` table.
+struct RevsetLabels<'a> {
+    rules: Vec<(Vec<String>, Box<RevsetContainingFn<'a>>)>,
+}
+
+impl RevsetLabels<'_> {
+    fn matching_labels(&self, id: &CommitId) -> Result<Vec<&str>, RevsetEvaluationError> {
+        let mut labels = vec![];
+        for (rule_labels, contains) in &self.rules {
+            if contains(id)? {
+                labels.extend(rule_labels.iter().map(String::as_str));
+            }
+        }
+        Ok(labels)
+    }
+}
+
+fn load_revset_labels<'a>(
+    ui: &Ui,
+    workspace_command: &'a WorkspaceCommandHelper,
+) -> Result<RevsetLabels<'a>, CommandError> {
+    let settings = workspace_command.settings();
+    let rules = settings
+        .table_keys("colors.revsets")
+        // Sort keys so that the labels are applied in a stable order.
+        .sorted()
+        .map(|key| -> Result<_, CommandError> {
+            let revset_str: String = settings.get(["colors", "revsets", key])?;
+            let mut diagnostics = RevsetDiagnostics::new();
+            let expression = revset::parse(
+                &mut diagnostics,
+                &revset_str,
+                &workspace_command.env().revset_parse_context(),
+            )
+            .map_err(|err| {
+                config_error_with_message(format!("Invalid `colors.revsets.{key}`"), err)
+            })?;
+            print_parse_diagnostics(ui, &format!("In `colors.revsets.{key}`"), &diagnostics)?;
+            let contains = workspace_command
+                .attach_revset_evaluator(expression)
+                .evaluate()?
+                .containing_fn();
+            let labels = key.split_whitespace().map(ToString::to_string).collect();
+            Ok((labels, contains))
+        })
+        .try_collect()?;
+    Ok(RevsetLabels { rules })
+}
+
+/// Reverse mapping of the predecessor records in the operation history.
+struct SuccessorMap {
+    successors: HashMap<CommitId, Vec<CommitId>>,
+}
+
+impl SuccessorMap {
+    fn load(repo: &ReadonlyRepo) -> Result<Self, CommandError> {
+        let mut successors: HashMap<CommitId, Vec<CommitId>> = HashMap::new();
+        for op in op_walk::walk_ancestors(slice::from_ref(repo.operation())) {
+            let op = op?;
+            // Operations created by old jj versions don't record predecessors.
+            let Some(map) = &op.store_operation().commit_predecessors else {
+                break;
+            };
+            for (new_id, old_ids) in map {
+                for old_id in old_ids {
+                    successors
+                        .entry(old_id.clone())
+                        .or_default()
+                        .push(new_id.clone());
+                }
+            }
+        }
+        Ok(Self { successors })
+    }
+
+    /// Returns the closest visible commit rewritten from the commit `id`.
+    fn visible_successor(&self, repo: &dyn Repo, id: &CommitId) -> BackendResult<Option<CommitId>> {
+        let mut visited = HashSet::new();
+        let mut to_visit = VecDeque::from([id]);
+        while let Some(id) = to_visit.pop_front() {
+            for successor_id in self.successors.get(id).into_iter().flatten() {
+                if !visited.insert(successor_id) {
+                    continue;
+                }
+                let successor = repo.store().get_commit(successor_id)?;
+                if !successor.is_hidden(repo) {
+                    return Ok(Some(successor_id.clone()));
+                }
+                to_visit.push_back(successor_id);
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn write_rewritten_as(formatter: &mut dyn Formatter, successor_id: &CommitId) -> io::Result<()> {
+    write_labeled(formatter, &["hidden"], |formatter| {
+        writeln!(
+            formatter.labeled("rewritten"),
+            "(rewritten as {})",
+            short_commit_hash(successor_id)
+        )
+    })
+}
+
+fn write_labeled<E: From<io::Error>>(
+    formatter: &mut dyn Formatter,
+    labels: &[&str],
+    write_inner: impl FnOnce(&mut dyn Formatter) -> Result<(), E>,
+) -> Result<(), E> {
+    for label in labels {
+        formatter.push_label(label)?;
+    }
+    write_inner(formatter)?;
+    for _ in labels {
+        formatter.pop_label()?;
+    }
+    Ok(())
+}
+
+/// Creates commit object from the ids available in the index. The other fields
+/// are placeholders, so the commit must only be rendered by templates accepted
+/// by [`is_index_only_template()`].
+fn index_only_commit(store: &Arc<Store>, id: CommitId, change_id: ChangeId) -> Commit {
+    let signature = Signature {
+        name: String::new(),
+        email: String::new(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    };
+    let data = backend::Commit {
+        parents: vec![],
+        predecessors: vec![],
+        root_tree: MergedTreeId::resolved(store.empty_tree_id().clone()),
+        change_id,
+        description: String::new(),
+        author: signature.clone(),
+        committer: signature,
+        secure_sig: None,
+    };
+    Commit::new(store.clone(), id, Arc::new(data))
+}
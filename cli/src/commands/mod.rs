@@ -18,6 +18,7 @@ mod backout;
 #[cfg(feature = "bench")]
 mod bench;
 mod bookmark;
+mod change_name;
 mod commit;
 mod config;
 mod debug;
@@ -32,12 +33,16 @@ mod fix;
 #[cfg(feature = "git")]
 mod git;
 mod help;
+mod identity;
 mod interdiff;
+mod lint;
 mod log;
 mod new;
 mod next;
+mod note;
 mod operation;
 mod parallelize;
+mod plumbing;
 mod prev;
 mod rebase;
 mod redo;
@@ -53,6 +58,7 @@ mod sparse;
 mod split;
 mod squash;
 mod status;
+mod summary;
 mod tag;
 mod touch;
 mod undo;
@@ -98,6 +104,8 @@ enum Command {
     Bench(bench::BenchCommand),
     #[command(subcommand)]
     Bookmark(bookmark::BookmarkCommand),
+    #[command(subcommand)]
+    ChangeName(change_name::ChangeNameCommand),
     Commit(commit::CommitArgs),
     #[command(subcommand)]
     Config(config::ConfigCommand),
@@ -117,14 +125,21 @@ enum Command {
     #[command(subcommand)]
     Git(git::GitCommand),
     Help(help::HelpArgs),
+    #[command(subcommand)]
+    Identity(identity::IdentityCommand),
     Interdiff(interdiff::InterdiffArgs),
+    Lint(lint::LintArgs),
     Log(log::LogArgs),
     New(new::NewArgs),
     Next(next::NextArgs),
     #[command(subcommand)]
+    Note(note::NoteCommand),
+    #[command(subcommand)]
     #[command(visible_alias = "op")]
     Operation(operation::OperationCommand),
     Parallelize(parallelize::ParallelizeArgs),
+    #[command(subcommand)]
+    Plumbing(plumbing::PlumbingCommand),
     Prev(prev::PrevArgs),
     Rebase(rebase::RebaseArgs),
     Redo(redo::RedoArgs),
@@ -143,6 +158,7 @@ enum Command {
     Split(split::SplitArgs),
     Squash(squash::SquashArgs),
     Status(status::StatusArgs),
+    Summary(summary::SummaryArgs),
     #[command(subcommand)]
     Tag(tag::TagCommand),
     Touch(touch::TouchArgs),
@@ -169,6 +185,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         #[cfg(feature = "bench")]
         Command::Bench(args) => bench::cmd_bench(ui, command_helper, args),
         Command::Bookmark(args) => bookmark::cmd_bookmark(ui, command_helper, args),
+        Command::ChangeName(args) => change_name::cmd_change_name(ui, command_helper, args),
         Command::Commit(args) => commit::cmd_commit(ui, command_helper, args),
         Command::Config(args) => config::cmd_config(ui, command_helper, args),
         Command::Debug(args) => debug::cmd_debug(ui, command_helper, args),
@@ -183,12 +200,16 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         #[cfg(feature = "git")]
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
         Command::Help(args) => help::cmd_help(ui, command_helper, args),
+        Command::Identity(args) => identity::cmd_identity(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
+        Command::Lint(args) => lint::cmd_lint(ui, command_helper, args),
         Command::Log(args) => log::cmd_log(ui, command_helper, args),
         Command::New(args) => new::cmd_new(ui, command_helper, args),
         Command::Next(args) => next::cmd_next(ui, command_helper, args),
+        Command::Note(args) => note::cmd_note(ui, command_helper, args),
         Command::Operation(args) => operation::cmd_operation(ui, command_helper, args),
         Command::Parallelize(args) => parallelize::cmd_parallelize(ui, command_helper, args),
+        Command::Plumbing(args) => plumbing::cmd_plumbing(ui, command_helper, args),
         Command::Prev(args) => prev::cmd_prev(ui, command_helper, args),
         Command::Rebase(args) => rebase::cmd_rebase(ui, command_helper, args),
         Command::Redo(args) => redo::cmd_redo(ui, command_helper, args),
@@ -206,6 +227,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Split(args) => split::cmd_split(ui, command_helper, args),
         Command::Squash(args) => squash::cmd_squash(ui, command_helper, args),
         Command::Status(args) => status::cmd_status(ui, command_helper, args),
+        Command::Summary(args) => summary::cmd_summary(ui, command_helper, args),
         Command::Tag(args) => tag::cmd_tag(ui, command_helper, args),
         Command::Touch(args) => touch::cmd_touch(ui, command_helper, args),
         Command::Undo(args) => undo::cmd_undo(ui, command_helper, args),
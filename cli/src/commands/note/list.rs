@@ -0,0 +1,75 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetIteratorExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// List the notes attached to revisions
+///
+/// Revisions without notes are skipped.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct NoteListArgs {
+    /// The revisions to list the notes of
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_note_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().as_ref();
+    let template = workspace_command.commit_summary_template();
+    let commits = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate()?
+        .iter()
+        .commits(repo.store());
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for commit in commits {
+        let commit = commit?;
+        let notes = repo.view().notes(commit.change_id()).collect_vec();
+        if notes.is_empty() {
+            continue;
+        }
+        template.format(&commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+        for (key, value) in notes {
+            writeln!(formatter, "  {key}: {value}")?;
+        }
+    }
+    Ok(())
+}
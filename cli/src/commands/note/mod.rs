@@ -0,0 +1,63 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod list;
+mod remove;
+mod set;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::list::NoteListArgs;
+use self::list::cmd_note_list;
+use self::remove::NoteRemoveArgs;
+use self::remove::cmd_note_remove;
+use self::set::NoteSetArgs;
+use self::set::cmd_note_set;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage notes attached to revisions
+///
+/// Notes are key-value annotations, such as CI results or review URLs. They
+/// are stored in the repo view instead of in the commits, so adding a note
+/// doesn't rewrite the commit, and immutable commits can be annotated too.
+///
+/// Notes are keyed by change ID, so they follow a change when it's rewritten,
+/// e.g. by `jj rebase`. They are local to the repo and are not pushed to or
+/// fetched from Git remotes.
+///
+/// In templates, `commit.notes()` lists the notes of a commit and
+/// `commit.note(key)` returns the value of a single note.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum NoteCommand {
+    List(NoteListArgs),
+    Remove(NoteRemoveArgs),
+    #[command(visible_alias = "add")]
+    Set(NoteSetArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_note(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &NoteCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        NoteCommand::List(args) => cmd_note_list(ui, command, args),
+        NoteCommand::Remove(args) => cmd_note_remove(ui, command, args),
+        NoteCommand::Set(args) => cmd_note_set(ui, command, args),
+    }
+}
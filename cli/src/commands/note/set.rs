@@ -0,0 +1,79 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::git::GIT_NOTES_KEY_PREFIX;
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Attach a note to a revision
+///
+/// An existing note with the same key is replaced.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct NoteSetArgs {
+    /// The revision to annotate
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+    /// The key of the note
+    #[arg(long, value_name = "KEY")]
+    key: String,
+    /// The content of the note
+    #[arg(long, short, value_name = "MESSAGE")]
+    message: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_note_set(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &NoteSetArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    if args.key.starts_with(GIT_NOTES_KEY_PREFIX) {
+        return Err(user_error(format!(
+            "Notes with keys starting with `{GIT_NOTES_KEY_PREFIX}` are imported from Git and \
+             can't be modified"
+        )));
+    }
+    if args.key.is_empty() {
+        return Err(user_error("The note key cannot be empty"));
+    }
+    if args.message.is_empty() {
+        return Err(user_error(
+            "The note content cannot be empty. Use `jj note remove` to remove a note.",
+        ));
+    }
+    let mut tx = workspace_command.start_transaction();
+    tx.repo_mut()
+        .set_note(commit.change_id(), &args.key, args.message.clone());
+    tx.finish(
+        ui,
+        format!("set note {} on commit {}", args.key, commit.id().hex()),
+    )?;
+    Ok(())
+}
@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::io;
 use std::slice;
 use std::sync::Arc;
 
@@ -29,11 +30,13 @@ use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::op_store::RemoteRefState;
 use jj_lib::refs::diff_named_commit_ids;
+use jj_lib::refs::diff_named_descriptions;
 use jj_lib::refs::diff_named_ref_targets;
 use jj_lib::refs::diff_named_remote_refs;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::view::View;
 use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
@@ -150,6 +153,12 @@ pub fn cmd_op_diff(
     op_summary_template.format(&to_op, &mut *formatter)?;
     writeln!(formatter)?;
 
+    if from_repo.view().store_view() == to_repo.view().store_view() {
+        writeln!(formatter)?;
+        writeln!(formatter, "No changes.")?;
+        return Ok(());
+    }
+
     show_op_diff(
         ui,
         formatter.as_mut(),
@@ -250,11 +259,32 @@ pub fn show_op_diff(
         }
     }
 
-    let changed_working_copies = diff_named_commit_ids(
-        from_repo.view().wc_commit_ids(),
-        to_repo.view().wc_commit_ids(),
+    let write_commit_summary =
+        |formatter: &mut dyn Formatter, commit_id: &CommitId| -> Result<(), CommandError> {
+            let commit = current_repo.store().get_commit(commit_id)?;
+            commit_summary_template.format(&commit, formatter)?;
+            Ok(())
+        };
+    show_view_refs_diff(
+        formatter,
+        from_repo.view(),
+        to_repo.view(),
+        with_content_format,
+        &write_commit_summary,
     )
-    .collect_vec();
+}
+
+/// Writes changes to the working copies, bookmarks, and tags between the
+/// views. Commits are summarized by `write_commit`.
+pub fn show_view_refs_diff(
+    formatter: &mut dyn Formatter,
+    from_view: &View,
+    to_view: &View,
+    with_content_format: &LogContentFormat,
+    write_commit: &dyn Fn(&mut dyn Formatter, &CommitId) -> Result<(), CommandError>,
+) -> Result<(), CommandError> {
+    let changed_working_copies =
+        diff_named_commit_ids(from_view.wc_commit_ids(), to_view.wc_commit_ids()).collect_vec();
     if !changed_working_copies.is_empty() {
         writeln!(formatter)?;
         for (name, (from_commit, to_commit)) in changed_working_copies {
@@ -266,29 +296,25 @@ pub fn show_op_diff(
                 writeln!(formatter, ":")?;
                 write_ref_target_summary(
                     formatter,
-                    current_repo,
-                    commit_summary_template,
                     &RefTarget::resolved(to_commit.cloned()),
                     true,
                     None,
+                    write_commit,
                 )?;
                 write_ref_target_summary(
                     formatter,
-                    current_repo,
-                    commit_summary_template,
                     &RefTarget::resolved(from_commit.cloned()),
                     false,
                     None,
+                    write_commit,
                 )
             })?;
         }
     }
 
-    let changed_local_bookmarks = diff_named_ref_targets(
-        from_repo.view().local_bookmarks(),
-        to_repo.view().local_bookmarks(),
-    )
-    .collect_vec();
+    let changed_local_bookmarks =
+        diff_named_ref_targets(from_view.local_bookmarks(), to_view.local_bookmarks())
+            .collect_vec();
     if !changed_local_bookmarks.is_empty() {
         writeln!(formatter)?;
         with_content_format.write(formatter, |formatter| {
@@ -297,58 +323,48 @@ pub fn show_op_diff(
         for (name, (from_target, to_target)) in changed_local_bookmarks {
             with_content_format.write(formatter, |formatter| {
                 writeln!(formatter, "{name}:", name = name.as_symbol())?;
-                write_ref_target_summary(
-                    formatter,
-                    current_repo,
-                    commit_summary_template,
-                    to_target,
-                    true,
-                    None,
-                )?;
-                write_ref_target_summary(
-                    formatter,
-                    current_repo,
-                    commit_summary_template,
-                    from_target,
-                    false,
-                    None,
-                )
+                write_ref_target_summary(formatter, to_target, true, None, write_commit)?;
+                write_ref_target_summary(formatter, from_target, false, None, write_commit)
             })?;
         }
     }
 
-    let changed_tags =
-        diff_named_ref_targets(from_repo.view().tags(), to_repo.view().tags()).collect_vec();
+    let changed_bookmark_descriptions = diff_named_descriptions(
+        from_view.local_bookmark_descriptions(),
+        to_view.local_bookmark_descriptions(),
+    )
+    .collect_vec();
+    if !changed_bookmark_descriptions.is_empty() {
+        writeln!(formatter)?;
+        with_content_format.write(formatter, |formatter| {
+            writeln!(formatter, "Changed bookmark descriptions:")
+        })?;
+        for (name, (from_description, to_description)) in changed_bookmark_descriptions {
+            with_content_format.write(formatter, |formatter| {
+                writeln!(formatter, "{name}:", name = name.as_symbol())?;
+                write_description_summary(formatter, to_description, true)?;
+                write_description_summary(formatter, from_description, false)
+            })?;
+        }
+    }
+
+    let changed_tags = diff_named_ref_targets(from_view.tags(), to_view.tags()).collect_vec();
     if !changed_tags.is_empty() {
         writeln!(formatter)?;
         with_content_format.write(formatter, |formatter| writeln!(formatter, "Changed tags:"))?;
         for (name, (from_target, to_target)) in changed_tags {
             with_content_format.write(formatter, |formatter| {
                 writeln!(formatter, "{name}:", name = name.as_symbol())?;
-                write_ref_target_summary(
-                    formatter,
-                    current_repo,
-                    commit_summary_template,
-                    to_target,
-                    true,
-                    None,
-                )?;
-                write_ref_target_summary(
-                    formatter,
-                    current_repo,
-                    commit_summary_template,
-                    from_target,
-                    false,
-                    None,
-                )
+                write_ref_target_summary(formatter, to_target, true, None, write_commit)?;
+                write_ref_target_summary(formatter, from_target, false, None, write_commit)
             })?;
         }
         writeln!(formatter)?;
     }
 
     let changed_remote_bookmarks = diff_named_remote_refs(
-        from_repo.view().all_remote_bookmarks(),
-        to_repo.view().all_remote_bookmarks(),
+        from_view.all_remote_bookmarks(),
+        to_view.all_remote_bookmarks(),
     )
     // Skip updates to the local git repo, since they should typically be covered in
     // local branches.
@@ -368,19 +384,17 @@ pub fn show_op_diff(
                 writeln!(formatter, "{symbol}:")?;
                 write_ref_target_summary(
                     formatter,
-                    current_repo,
-                    commit_summary_template,
                     &to_ref.target,
                     true,
                     Some(get_remote_ref_prefix(to_ref)),
+                    write_commit,
                 )?;
                 write_ref_target_summary(
                     formatter,
-                    current_repo,
-                    commit_summary_template,
                     &from_ref.target,
                     false,
                     Some(get_remote_ref_prefix(from_ref)),
+                    write_commit,
                 )
             })?;
         }
@@ -412,14 +426,32 @@ fn write_modified_change_summary(
     Ok(())
 }
 
+/// Writes the first line of the old or new description of a changed commit.
+fn write_description_summary(
+    formatter: &mut dyn Formatter,
+    description: Option<&str>,
+    added: bool,
+) -> io::Result<()> {
+    formatter.with_label("diff", |formatter| {
+        write!(
+            formatter.labeled(if added { "added" } else { "removed" }),
+            "{}",
+            if added { "+" } else { "-" }
+        )
+    })?;
+    match description {
+        Some(description) => writeln!(formatter, " {}", description.lines().next().unwrap_or("")),
+        None => writeln!(formatter, " (absent)"),
+    }
+}
+
 /// Writes a summary for the given `RefTarget`.
-fn write_ref_target_summary(
+pub fn write_ref_target_summary(
     formatter: &mut dyn Formatter,
-    repo: &dyn Repo,
-    commit_summary_template: &TemplateRenderer<Commit>,
     ref_target: &RefTarget,
     added: bool,
     prefix: Option<&str>,
+    write_commit: &dyn Fn(&mut dyn Formatter, &CommitId) -> Result<(), CommandError>,
 ) -> Result<(), CommandError> {
     let write_prefix = |formatter: &mut dyn Formatter,
                         added: bool,
@@ -445,22 +477,19 @@ fn write_ref_target_summary(
         for commit_id in ref_target.added_ids() {
             write_prefix(formatter, added, prefix)?;
             write!(formatter, "(added) ")?;
-            let commit = repo.store().get_commit(commit_id)?;
-            commit_summary_template.format(&commit, formatter)?;
+            write_commit(formatter, commit_id)?;
             writeln!(formatter)?;
         }
         for commit_id in ref_target.removed_ids() {
             write_prefix(formatter, added, prefix)?;
             write!(formatter, "(removed) ")?;
-            let commit = repo.store().get_commit(commit_id)?;
-            commit_summary_template.format(&commit, formatter)?;
+            write_commit(formatter, commit_id)?;
             writeln!(formatter)?;
         }
     } else {
         write_prefix(formatter, added, prefix)?;
         let commit_id = ref_target.as_normal().unwrap();
-        let commit = repo.store().get_commit(commit_id)?;
-        commit_summary_template.format(&commit, formatter)?;
+        write_commit(formatter, commit_id)?;
         writeln!(formatter)?;
     }
     Ok(())
@@ -22,6 +22,7 @@ use jj_lib::op_store::OpStoreError;
 use jj_lib::op_walk;
 use jj_lib::operation::Operation;
 use jj_lib::repo::RepoLoader;
+use jj_lib::time_util::DatePattern;
 
 use super::diff::show_op_diff;
 use crate::cli_util::CommandHelper;
@@ -29,6 +30,7 @@ use crate::cli_util::LogContentFormat;
 use crate::cli_util::WorkspaceCommandEnvironment;
 use crate::cli_util::format_template;
 use crate::command_error::CommandError;
+use crate::command_error::user_error_with_message;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
@@ -49,10 +51,19 @@ use crate::ui::Ui;
 pub struct OperationLogArgs {
     /// Limit number of operations to show
     ///
-    /// Applied after operations are reordered topologically, but before being
-    /// reversed.
+    /// Applied after operations are reordered topologically and filtered by
+    /// `--since` and `--until`, but before being reversed.
     #[arg(long, short = 'n')]
     limit: Option<usize>,
+    /// Show only operations that started at or after the given date
+    ///
+    /// The date is parsed the same way as in the `committer_date()` revset
+    /// function, e.g. `2024-02-01T12:00` or `"2 days ago"`.
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+    /// Show only operations that started before the given date
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
     /// Show operations in the opposite order (older operations first)
     #[arg(long)]
     reversed: bool,
@@ -92,7 +103,7 @@ pub fn cmd_op_log(
     args: &OperationLogArgs,
 ) -> Result<(), CommandError> {
     if command.is_working_copy_writable() {
-        let workspace_command = command.workspace_helper(ui)?;
+        let workspace_command = command.workspace_helper_for_query(ui)?;
         let current_op = workspace_command.repo().operation();
         let repo_loader = workspace_command.workspace().repo_loader();
         do_op_log(ui, workspace_command.env(), repo_loader, current_op, args)
@@ -117,6 +128,18 @@ fn do_op_log(
 ) -> Result<(), CommandError> {
     let settings = repo_loader.settings();
     let graph_style = GraphStyle::from_settings(settings)?;
+    let date_patterns: Vec<DatePattern> = {
+        let context = workspace_env.revset_parse_context().date_pattern_context;
+        [("after", &args.since), ("before", &args.until)]
+            .into_iter()
+            .filter_map(|(kind, date)| Some((kind, date.as_deref()?)))
+            .map(|(kind, date)| {
+                context.parse_relative(date, kind).map_err(|err| {
+                    user_error_with_message(format!("Invalid date pattern: {date}"), err)
+                })
+            })
+            .try_collect()?
+    };
     let with_content_format = LogContentFormat::new(ui, settings)?;
 
     let template: TemplateRenderer<Operation>;
@@ -199,8 +222,12 @@ fn do_op_log(
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
-    let iter =
-        op_walk::walk_ancestors(slice::from_ref(current_op)).take(args.limit.unwrap_or(usize::MAX));
+    let iter = op_walk::walk_ancestors(slice::from_ref(current_op))
+        .filter_ok(|op| {
+            let start = &op.metadata().time.start;
+            date_patterns.iter().all(|pattern| pattern.matches(start))
+        })
+        .take(args.limit.unwrap_or(usize::MAX));
 
     if !args.no_graph {
         let mut raw_output = formatter.raw()?;
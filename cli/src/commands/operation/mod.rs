@@ -13,17 +13,22 @@
 // limitations under the License.
 
 mod abandon;
-mod diff;
+pub mod diff;
 mod log;
+mod picker;
 mod restore;
 pub mod revert;
 mod show;
 
+use std::io;
+
 use abandon::OperationAbandonArgs;
 use abandon::cmd_op_abandon;
 use clap::Subcommand;
 use diff::OperationDiffArgs;
 use diff::cmd_op_diff;
+use itertools::Itertools as _;
+use jj_lib::repo::Repo as _;
 use log::OperationLogArgs;
 use log::cmd_op_log;
 use restore::OperationRestoreArgs;
@@ -34,6 +39,7 @@ use show::OperationShowArgs;
 use show::cmd_op_show;
 
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::CommandError;
 use crate::commands::renamed_cmd;
 use crate::ui::Ui;
@@ -116,5 +122,123 @@ pub(crate) fn view_with_desired_portions_restored(
         git_refs: current_view.git_refs.clone(),
         git_head: current_view.git_head.clone(),
         wc_commit_ids: repo_source.wc_commit_ids.clone(),
+        bookmark_descriptions: repo_source.bookmark_descriptions.clone(),
+        notes: repo_source.notes.clone(),
+        change_names: repo_source.change_names.clone(),
+    }
+}
+
+/// Warns about other workspaces whose working-copy commit is changed or
+/// removed by the view restored in `tx`.
+///
+/// Their working copies will become stale (or orphaned) once the transaction
+/// is committed.
+pub(crate) fn warn_about_affected_workspaces(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+) -> io::Result<()> {
+    let current_name = tx.base_workspace_helper().workspace_name();
+    let old_wc_commit_ids = tx.base_repo().view().wc_commit_ids();
+    let new_view = tx.repo().view();
+    let affected = old_wc_commit_ids
+        .iter()
+        .filter(|(name, _)| *name != current_name)
+        .filter_map(|(name, old_id)| match new_view.get_wc_commit_id(name) {
+            Some(new_id) if new_id == old_id => None,
+            Some(_) => Some((name, false)),
+            None => Some((name, true)),
+        })
+        .collect_vec();
+    if affected.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        ui.warning_default(),
+        "The working-copy commits of other workspaces will be changed:"
+    )?;
+    for (name, removed) in &affected {
+        if *removed {
+            writeln!(
+                ui.warning_no_heading(),
+                "  {} (forgotten)",
+                name.as_symbol()
+            )?;
+        } else {
+            writeln!(ui.warning_no_heading(), "  {}", name.as_symbol())?;
+        }
+    }
+    if affected.iter().any(|(_, removed)| !removed) {
+        writeln!(
+            ui.hint_default(),
+            "Run `jj workspace update-stale` in those workspaces to update their working \
+             copies."
+        )?;
+    }
+    Ok(())
+}
+
+/// Warns about remote bookmarks restored by `tx` whose Git remotes don't
+/// exist.
+///
+/// The Git config isn't recorded in the operation log, so undoing `jj git
+/// remote rename` restores the remote bookmarks under the old remote name, but
+/// leaves the Git remote renamed.
+#[cfg(feature = "git")]
+pub(crate) fn warn_about_unconfigured_remotes(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+) -> io::Result<()> {
+    let Ok(configured_names) = jj_lib::git::get_all_remote_names(tx.repo().store()) else {
+        return Ok(());
+    };
+    let old_remote_views = &tx.base_repo().view().store_view().remote_views;
+    let new_remote_views = &tx.repo().view().store_view().remote_views;
+    let missing_names = new_remote_views
+        .keys()
+        .filter(|name| {
+            *name != jj_lib::git::REMOTE_NAME_FOR_LOCAL_GIT_REPO
+                && !configured_names.contains(name)
+                && !old_remote_views.contains_key(*name)
+        })
+        .collect_vec();
+    if missing_names.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        ui.warning_default(),
+        "Restored remote bookmarks of Git remotes that don't exist:"
+    )?;
+    for name in &missing_names {
+        writeln!(ui.warning_no_heading(), "  {}", name.as_symbol())?;
     }
+    let dropped_names = configured_names
+        .iter()
+        .filter(|name| {
+            old_remote_views.contains_key(*name) && !new_remote_views.contains_key(*name)
+        })
+        .collect_vec();
+    if let ([missing_name], [dropped_name]) = (missing_names.as_slice(), dropped_names.as_slice()) {
+        writeln!(
+            ui.hint_default(),
+            "The Git remote {current} may have been renamed from {restored}. Run `jj git \
+             remote rename {current} {restored}` to rename it back.",
+            current = dropped_name.as_symbol(),
+            restored = missing_name.as_symbol()
+        )?;
+    } else {
+        writeln!(
+            ui.hint_default(),
+            "Git remotes aren't restored by the operation log. Use `jj git remote add` to add \
+             them back."
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "git"))]
+pub(crate) fn warn_about_unconfigured_remotes(
+    _ui: &Ui,
+    _tx: &WorkspaceCommandTransaction,
+) -> io::Result<()> {
+    Ok(())
 }
@@ -0,0 +1,342 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Terminal UI to pick an entry from a list, with a preview of the focused
+//! entry.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+
+use crossterm::cursor;
+use crossterm::event;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::queue;
+use crossterm::style::Attribute;
+use crossterm::style::Print;
+use crossterm::style::SetAttribute;
+use crossterm::terminal;
+use crossterm::terminal::ClearType;
+
+use crate::command_error::CommandError;
+use crate::text_util;
+
+const HELP_TEXT: &str = "up/down: move  page up/down: scroll preview  enter: select  esc: cancel";
+
+/// Entries listed by the picker. Entries are loaded on demand as the list is
+/// scrolled.
+pub(super) trait PickerSource {
+    /// Returns the one-line summary of the entry at `index`, or `None` if there
+    /// are fewer entries.
+    fn summary(&mut self, index: usize) -> Result<Option<&str>, CommandError>;
+
+    /// Returns the preview of the entry at `index`. This is only called for
+    /// the focused entry.
+    fn preview(&mut self, index: usize) -> Result<String, CommandError>;
+}
+
+/// Runs the picker in the alternate screen of the terminal. Returns the index
+/// of the selected entry, or `None` if the picker was cancelled.
+pub(super) fn run_picker_in_terminal(
+    source: &mut dyn PickerSource,
+) -> Result<Option<usize>, CommandError> {
+    let size = terminal::size()?;
+    let _guard = TerminalGuard::enter()?;
+    run_picker(source, &mut event::read, &mut io::stdout(), size)
+}
+
+/// Runs the picker with the given events and output. Returns the index of the
+/// selected entry, or `None` if the picker was cancelled.
+pub(super) fn run_picker(
+    source: &mut dyn PickerSource,
+    next_event: &mut dyn FnMut() -> io::Result<Event>,
+    output: &mut impl Write,
+    (width, height): (u16, u16),
+) -> Result<Option<usize>, CommandError> {
+    if source.summary(0)?.is_none() {
+        return Ok(None);
+    }
+    let mut state = PickerState {
+        width,
+        height,
+        focused: 0,
+        list_offset: 0,
+        preview_offset: 0,
+        previews: HashMap::new(),
+    };
+    loop {
+        state.draw(source, output)?;
+        let page = usize::from(state.layout().preview_rows);
+        match next_event()? {
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) => match code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    state.focus(state.focused.saturating_sub(1));
+                }
+                KeyCode::Down | KeyCode::Char('j')
+                    if source.summary(state.focused + 1)?.is_some() =>
+                {
+                    state.focus(state.focused + 1);
+                }
+                KeyCode::PageUp => {
+                    state.preview_offset = state.preview_offset.saturating_sub(page);
+                }
+                KeyCode::PageDown => {
+                    // Clamped to the preview length when drawn.
+                    state.preview_offset += page;
+                }
+                KeyCode::Enter => return Ok(Some(state.focused)),
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(None);
+                }
+                _ => {}
+            },
+            Event::Resize(width, height) => {
+                state.width = width;
+                state.height = height;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Switches the terminal to raw mode and the alternate screen until dropped.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Self;
+        queue!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        _ = queue!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        _ = stdout.flush();
+        _ = terminal::disable_raw_mode();
+    }
+}
+
+struct PickerLayout {
+    list_rows: u16,
+    preview_rows: u16,
+}
+
+struct PickerState {
+    width: u16,
+    height: u16,
+    focused: usize,
+    /// Index of the first visible entry.
+    list_offset: usize,
+    /// Index of the first visible line of the preview.
+    preview_offset: usize,
+    /// Lines of the previews loaded so far, by entry index.
+    previews: HashMap<usize, Vec<String>>,
+}
+
+impl PickerState {
+    /// Splits the screen into the list, a separator line, the preview, and a
+    /// help line.
+    fn layout(&self) -> PickerLayout {
+        let rows = self.height.saturating_sub(2).max(2);
+        let list_rows = (rows / 3).max(1);
+        PickerLayout {
+            list_rows,
+            preview_rows: rows - list_rows,
+        }
+    }
+
+    fn focus(&mut self, index: usize) {
+        if index != self.focused {
+            self.focused = index;
+            self.preview_offset = 0;
+        }
+    }
+
+    fn draw(
+        &mut self,
+        source: &mut dyn PickerSource,
+        output: &mut impl Write,
+    ) -> Result<(), CommandError> {
+        let width = usize::from(self.width);
+        let layout = self.layout();
+        let list_rows = usize::from(layout.list_rows);
+        let preview_rows = usize::from(layout.preview_rows);
+        if self.focused < self.list_offset {
+            self.list_offset = self.focused;
+        } else if self.focused >= self.list_offset + list_rows {
+            self.list_offset = self.focused + 1 - list_rows;
+        }
+
+        queue!(output, terminal::Clear(ClearType::All))?;
+        for (index, row) in (self.list_offset..self.list_offset + list_rows).zip(0..) {
+            let Some(summary) = source.summary(index)? else {
+                break;
+            };
+            let (text, _) = text_util::elide_end(summary, "", width.saturating_sub(2));
+            queue!(output, cursor::MoveTo(0, row))?;
+            if index == self.focused {
+                queue!(
+                    output,
+                    SetAttribute(Attribute::Reverse),
+                    Print(format!("> {text}")),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(output, Print(format!("  {text}")))?;
+            }
+        }
+
+        queue!(
+            output,
+            cursor::MoveTo(0, layout.list_rows),
+            Print("─".repeat(width))
+        )?;
+        if !self.previews.contains_key(&self.focused) {
+            let preview = source.preview(self.focused)?;
+            let lines = preview
+                .trim_start_matches('\n')
+                .lines()
+                .map(ToOwned::to_owned)
+                .collect();
+            self.previews.insert(self.focused, lines);
+        }
+        let lines = &self.previews[&self.focused];
+        self.preview_offset = self
+            .preview_offset
+            .min(lines.len().saturating_sub(preview_rows));
+        for (line, row) in lines
+            .iter()
+            .skip(self.preview_offset)
+            .take(preview_rows)
+            .zip(layout.list_rows + 1..)
+        {
+            let (text, _) = text_util::elide_end(line, "", width);
+            queue!(output, cursor::MoveTo(0, row), Print(text))?;
+        }
+
+        let (help, _) = text_util::elide_end(HELP_TEXT, "", width);
+        queue!(
+            output,
+            cursor::MoveTo(0, layout.list_rows + 1 + layout.preview_rows),
+            SetAttribute(Attribute::Dim),
+            Print(help),
+            SetAttribute(Attribute::Reset)
+        )?;
+        output.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools as _;
+
+    use super::*;
+
+    struct TestSource {
+        summaries: Vec<String>,
+        previewed: Vec<usize>,
+    }
+
+    impl PickerSource for TestSource {
+        fn summary(&mut self, index: usize) -> Result<Option<&str>, CommandError> {
+            Ok(self.summaries.get(index).map(String::as_str))
+        }
+
+        fn preview(&mut self, index: usize) -> Result<String, CommandError> {
+            self.previewed.push(index);
+            Ok(format!("preview of {}\n", self.summaries[index]))
+        }
+    }
+
+    fn run_with_keys(source: &mut TestSource, keys: &[KeyCode]) -> (Option<usize>, String) {
+        let mut events = keys
+            .iter()
+            .map(|&code| Event::Key(KeyEvent::from(code)))
+            .collect::<Vec<_>>()
+            .into_iter();
+        let mut next_event = || Ok(events.next().expect("picker should exit before"));
+        let mut output = vec![];
+        let selected = run_picker(source, &mut next_event, &mut output, (40, 12)).unwrap();
+        (selected, String::from_utf8(output).unwrap())
+    }
+
+    fn make_source(summaries: &[&str]) -> TestSource {
+        TestSource {
+            summaries: summaries.iter().map(|s| s.to_string()).collect(),
+            previewed: vec![],
+        }
+    }
+
+    #[test]
+    fn test_picker_select_second() {
+        let mut source = make_source(&["op 3", "op 2", "op 1"]);
+        let (selected, output) = run_with_keys(&mut source, &[KeyCode::Down, KeyCode::Enter]);
+        assert_eq!(selected, Some(1));
+        assert!(output.contains("preview of op 2"));
+        // Only the focused entries are previewed
+        assert_eq!(source.previewed, [0, 1]);
+    }
+
+    #[test]
+    fn test_picker_move_past_ends() {
+        let mut source = make_source(&["op 2", "op 1"]);
+        let keys = [
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::Down,
+            KeyCode::Down,
+            KeyCode::Enter,
+        ];
+        let (selected, _) = run_with_keys(&mut source, &keys);
+        assert_eq!(selected, Some(1));
+        // Previews are loaded once
+        assert_eq!(source.previewed, [0, 1]);
+    }
+
+    #[test]
+    fn test_picker_cancel() {
+        let mut source = make_source(&["op 2", "op 1"]);
+        let (selected, _) = run_with_keys(&mut source, &[KeyCode::Down, KeyCode::Esc]);
+        assert_eq!(selected, None);
+    }
+
+    #[test]
+    fn test_picker_scroll_list() {
+        let summaries = (0..20).map(|i| format!("op {i}")).collect_vec();
+        let mut source = TestSource {
+            summaries,
+            previewed: vec![],
+        };
+        let mut keys = vec![KeyCode::Down; 15];
+        keys.push(KeyCode::Enter);
+        let (selected, output) = run_with_keys(&mut source, &keys);
+        assert_eq!(selected, Some(15));
+        assert!(output.contains("> op 15"));
+    }
+}
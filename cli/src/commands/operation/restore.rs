@@ -12,21 +12,45 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
+use std::io::IsTerminal as _;
+use std::slice;
+
 use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OpStoreResult;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
 
 use super::DEFAULT_REVERT_WHAT;
 use super::RevertWhatToRestore;
+use super::diff::show_op_diff;
+use super::picker::PickerSource;
+use super::picker::run_picker_in_terminal;
 use super::view_with_desired_portions_restored;
+use super::warn_about_affected_workspaces;
+use super::warn_about_unconfigured_remotes;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::LogContentFormat;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::complete;
+use crate::formatter::PlainTextFormatter;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// Create a new operation that restores the repo to an earlier state
 ///
 /// This restores the repo to the state at the specified operation, effectively
 /// undoing all later operations. It does so by creating a new operation.
+///
+/// If no operation is given and the terminal is interactive, a picker listing
+/// the operations is opened. The operation diff of the highlighted operation
+/// is previewed. Press enter to restore to it.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationRestoreArgs {
     /// The operation to restore to
@@ -35,7 +59,7 @@ pub struct OperationRestoreArgs {
     /// --at-op=<operation ID> log` before restoring to an operation to see the
     /// state of the repo at that operation.
     #[arg(add = ArgValueCandidates::new(complete::operations))]
-    operation: String,
+    operation: Option<String>,
 
     /// What portions of the local state to restore (can be repeated)
     ///
@@ -50,7 +74,18 @@ pub fn cmd_op_restore(
     args: &OperationRestoreArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let target_op = workspace_command.resolve_single_op(&args.operation)?;
+    let target_op = match &args.operation {
+        Some(operation) => workspace_command.resolve_single_op(operation)?,
+        None => {
+            if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+                return Err(user_error_with_hint(
+                    "No operation to restore to was specified",
+                    "Use `jj op log` to find an operation to restore to.",
+                ));
+            }
+            pick_operation(ui, &workspace_command)?
+        }
+    };
     let mut tx = workspace_command.start_transaction();
     let new_view = view_with_desired_portions_restored(
         target_op.view()?.store_view(),
@@ -64,7 +99,90 @@ pub fn cmd_op_restore(
         template.format(&target_op, formatter.as_mut())?;
         writeln!(formatter)?;
     }
+    warn_about_affected_workspaces(ui, &tx)?;
+    warn_about_unconfigured_remotes(ui, &tx)?;
     tx.finish(ui, format!("restore to operation {}", target_op.id().hex()))?;
 
     Ok(())
 }
+
+/// Operations listed by the picker, walked from the current operation as the
+/// list is scrolled.
+struct OperationPickerSource<'a> {
+    ui: &'a Ui,
+    workspace_command: &'a WorkspaceCommandHelper,
+    summary_template: TemplateRenderer<'a, Operation>,
+    commit_summary_template: TemplateRenderer<'a, Commit>,
+    with_content_format: LogContentFormat,
+    op_iter: Box<dyn Iterator<Item = OpStoreResult<Operation>>>,
+    ops: Vec<(Operation, String)>,
+}
+
+impl PickerSource for OperationPickerSource<'_> {
+    fn summary(&mut self, index: usize) -> Result<Option<&str>, CommandError> {
+        while self.ops.len() <= index {
+            let Some(op) = self.op_iter.next().transpose()? else {
+                return Ok(None);
+            };
+            let mut summary = vec![];
+            self.summary_template
+                .format(&op, &mut PlainTextFormatter::new(&mut summary))?;
+            let summary = String::from_utf8_lossy(&summary).into_owned();
+            self.ops.push((op, summary));
+        }
+        Ok(Some(&self.ops[index].1))
+    }
+
+    fn preview(&mut self, index: usize) -> Result<String, CommandError> {
+        let (op, _) = &self.ops[index];
+        let mut preview = vec![];
+        // The diff of a merge operation isn't shown, like in `jj op show`.
+        if let Ok(Some(parent_op)) = op.parents().at_most_one() {
+            let repo_loader = self.workspace_command.workspace().repo_loader();
+            let parent_repo = repo_loader.load_at(&parent_op?)?;
+            let repo = repo_loader.load_at(op)?;
+            show_op_diff(
+                self.ui,
+                &mut PlainTextFormatter::new(&mut preview),
+                repo.as_ref(),
+                &parent_repo,
+                &repo,
+                &self.commit_summary_template,
+                None,
+                &self.with_content_format,
+                None,
+            )?;
+        }
+        Ok(String::from_utf8_lossy(&preview).into_owned())
+    }
+}
+
+/// Lists operations in a picker and returns the selected one.
+fn pick_operation(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<Operation, CommandError> {
+    let settings = workspace_command.settings();
+    let commit_summary_template = {
+        let language = workspace_command.commit_template_language();
+        let text = settings.get_string("templates.commit_summary")?;
+        workspace_command
+            .env()
+            .parse_template(ui, &language, &text)?
+            .labeled(["op_show", "commit"])
+    };
+    let current_op = workspace_command.repo().operation();
+    let mut source = OperationPickerSource {
+        ui,
+        workspace_command,
+        summary_template: workspace_command.operation_summary_template(),
+        commit_summary_template,
+        with_content_format: LogContentFormat::new(ui, settings)?,
+        op_iter: Box::new(op_walk::walk_ancestors(slice::from_ref(current_op))),
+        ops: vec![],
+    };
+    match run_picker_in_terminal(&mut source)? {
+        Some(index) => Ok(source.ops.swap_remove(index).0),
+        None => Err(user_error("Operation selection was cancelled")),
+    }
+}
@@ -21,6 +21,8 @@ use jj_lib::repo::Repo as _;
 use super::DEFAULT_REVERT_WHAT;
 use super::RevertWhatToRestore;
 use super::view_with_desired_portions_restored;
+use super::warn_about_affected_workspaces;
+use super::warn_about_unconfigured_remotes;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
@@ -80,6 +82,8 @@ pub fn cmd_op_revert(
         template.format(&bad_op, formatter.as_mut())?;
         writeln!(formatter)?;
     }
+    warn_about_affected_workspaces(ui, &tx)?;
+    warn_about_unconfigured_remotes(ui, &tx)?;
     tx.finish(ui, tx_description(&bad_op))?;
 
     Ok(())
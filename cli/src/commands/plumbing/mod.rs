@@ -0,0 +1,56 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod resolve_rev;
+mod tree_entry;
+mod wc_commit;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::resolve_rev::PlumbingResolveRevArgs;
+use self::resolve_rev::cmd_plumbing_resolve_rev;
+use self::tree_entry::PlumbingTreeEntryArgs;
+use self::tree_entry::cmd_plumbing_tree_entry;
+use self::wc_commit::PlumbingWcCommitArgs;
+use self::wc_commit::cmd_plumbing_wc_commit;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Low-level commands with stable output for use in scripts
+///
+/// Unlike other commands, the output format of these commands is guaranteed
+/// not to change between releases, except for additions that are documented
+/// in each command's help. Output is never colored, paged, or affected by
+/// templates or other user configuration.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum PlumbingCommand {
+    ResolveRev(PlumbingResolveRevArgs),
+    TreeEntry(PlumbingTreeEntryArgs),
+    WcCommit(PlumbingWcCommitArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_plumbing(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &PlumbingCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        PlumbingCommand::ResolveRev(args) => cmd_plumbing_resolve_rev(ui, command, args),
+        PlumbingCommand::TreeEntry(args) => cmd_plumbing_tree_entry(ui, command, args),
+        PlumbingCommand::WcCommit(args) => cmd_plumbing_wc_commit(ui, command, args),
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Print the full commit ids of revisions
+///
+/// Each commit id is printed as a lowercase hexadecimal string on its own line,
+/// in the same order as `jj log --no-graph` would list them.
+///
+/// Exits with an error if the revisions are empty, unless `--allow-empty` is
+/// given.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct PlumbingResolveRevArgs {
+    /// The revisions to resolve
+    #[arg(
+        value_name = "REVSETS",
+        required = true,
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Print nothing instead of failing if the revisions are empty
+    #[arg(long)]
+    allow_empty: bool,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_plumbing_resolve_rev(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &PlumbingResolveRevArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let expression = workspace_command.parse_union_revsets(ui, &args.revisions)?;
+    let mut num_commits = 0;
+    for commit_id in expression.evaluate_to_commit_ids()? {
+        writeln!(ui.stdout(), "{}", commit_id?.hex())?;
+        num_commits += 1;
+    }
+    if num_commits == 0 && !args.allow_empty {
+        return Err(user_error("Empty revision set"));
+    }
+    Ok(())
+}
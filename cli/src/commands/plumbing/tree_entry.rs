@@ -0,0 +1,83 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use jj_lib::backend::TreeValue;
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Print the tree entry at a path
+///
+/// Prints a single line with three space-separated fields: the kind of entry
+/// (`file`, `symlink`, `tree`, or `submodule`), its full object id in
+/// lowercase hexadecimal, and `true` if it's an executable file or `false`
+/// otherwise. For submodules, the id is the commit id of the submodule.
+///
+/// Exits with an error if the path doesn't exist or is conflicted at the
+/// revision.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct PlumbingTreeEntryArgs {
+    /// The revision to look up the path in
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+    /// The path to look up, relative to the current directory
+    #[arg(
+        value_hint = clap::ValueHint::FilePath,
+        add = ArgValueCompleter::new(complete::all_revision_files),
+    )]
+    path: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_plumbing_tree_entry(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &PlumbingTreeEntryArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let path = workspace_command.parse_file_path(&args.path)?;
+    let value = commit.tree()?.path_value(&path)?;
+    let ui_path = workspace_command.format_file_path(&path);
+    let value = match value.into_resolved() {
+        Ok(Some(value)) => value,
+        Ok(None) => return Err(user_error(format!("No such path: {ui_path}"))),
+        Err(_) => return Err(user_error(format!("Path is conflicted: {ui_path}"))),
+    };
+    let (kind, id, executable) = match &value {
+        TreeValue::File { id, executable, .. } => ("file", id.hex(), *executable),
+        TreeValue::Symlink(id) => ("symlink", id.hex(), false),
+        TreeValue::Tree(id) => ("tree", id.hex(), false),
+        TreeValue::GitSubmodule(id) => ("submodule", id.hex(), false),
+        TreeValue::Conflict(_) => {
+            return Err(user_error(format!("Path is conflicted: {ui_path}")));
+        }
+    };
+    writeln!(ui.stdout(), "{kind} {id} {executable}")?;
+    Ok(())
+}
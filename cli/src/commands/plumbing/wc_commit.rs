@@ -0,0 +1,45 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::object_id::ObjectId as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::command_error::user_error;
+use crate::ui::Ui;
+
+/// Print the commit id of the current workspace's working-copy commit
+///
+/// Prints the full commit id in lowercase hexadecimal on a single line. The
+/// working copy is not snapshotted, so the commit may not reflect changes made
+/// on disk since the last command.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct PlumbingWcCommitArgs {}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_plumbing_wc_commit(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &PlumbingWcCommitArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This workspace has no working-copy commit"))?;
+    writeln!(ui.stdout(), "{}", commit_id.hex())?;
+    Ok(())
+}
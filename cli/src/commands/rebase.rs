@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::io::Write as _;
 use std::sync::Arc;
 
@@ -28,6 +29,7 @@ use jj_lib::rewrite::EmptyBehavior;
 use jj_lib::rewrite::MoveCommitsLocation;
 use jj_lib::rewrite::MoveCommitsStats;
 use jj_lib::rewrite::MoveCommitsTarget;
+use jj_lib::rewrite::PlannedRebase;
 use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::RewriteRefsOptions;
 use jj_lib::rewrite::compute_move_commits;
@@ -37,6 +39,7 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
 use crate::cli_util::compute_commit_location;
 use crate::cli_util::print_updated_commits;
 use crate::cli_util::short_commit_hash;
@@ -127,6 +130,10 @@ use crate::ui::Ui;
 /// J           J
 /// ```
 ///
+/// Use `--exclude` to leave some revisions of the branch and their descendants
+/// in place. For example, `jj rebase -b M -d O --exclude L` would rebase K, M,
+/// and N onto O, and L would become a child of J.
+///
 /// With `--revisions/-r`, the command rebases only the specified revisions to
 /// the destination. Any "hole" left behind will be filled by rebasing
 /// descendants onto the specified revisions' parent(s). For example,
@@ -281,6 +288,19 @@ pub(crate) struct RebaseArgs {
     )]
     branch: Vec<RevisionArg>,
 
+    /// Leave the given revisions and their descendants in place when rebasing
+    /// branches
+    ///
+    /// The excluded revisions are rebased onto the closest ancestors that
+    /// aren't rebased, so they stay where the branch used to be.
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        conflicts_with_all = ["source", "revisions"],
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    exclude: Vec<RevisionArg>,
+
     /// Rebase specified revision(s) together with their trees of descendants
     /// (can be repeated)
     ///
@@ -328,6 +348,27 @@ pub(crate) struct RebaseArgs {
     /// destination with identical changes.
     #[arg(long)]
     keep_divergent: bool,
+
+    /// Only print which revisions would be rebased and onto which parents
+    ///
+    /// Each rebased revision is printed as `<old commit ID> -> (<new
+    /// parents>)`. New parents that are rebased themselves are shown by their
+    /// current commit IDs. No commits are written and no operation is
+    /// recorded.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// With `--dry-run`, predict which revisions would become conflicted
+    ///
+    /// This requires merging the trees of the rebased revisions, which can be
+    /// slow. The command fails if any new conflicts are predicted.
+    #[arg(long, requires = "dry_run")]
+    check_conflicts: bool,
+
+    /// Do not ask for confirmation if the revisions match more commits than
+    /// `ui.large-revset-warning-threshold`
+    #[arg(long)]
+    no_limit: bool,
 }
 
 #[derive(clap::Args, Clone, Debug)]
@@ -388,8 +429,27 @@ pub(crate) fn cmd_rebase(
     } else if !args.source.is_empty() {
         plan_rebase_source(ui, &workspace_command, &args.source, &args.destination)?
     } else {
-        plan_rebase_branch(ui, &workspace_command, &args.branch, &args.destination)?
+        plan_rebase_branch(
+            ui,
+            &workspace_command,
+            &args.branch,
+            &args.exclude,
+            &args.destination,
+        )?
     };
+    if !args.dry_run {
+        // The descendants of the moved commits and of the new children are
+        // rebased as well.
+        let (MoveCommitsTarget::Commits(target_ids) | MoveCommitsTarget::Roots(target_ids)) =
+            &loc.target;
+        let to_rewrite: Vec<_> = RevsetExpression::commits(target_ids.clone())
+            .union(&RevsetExpression::commits(loc.new_child_ids.clone()))
+            .descendants()
+            .evaluate(workspace_command.repo().as_ref())?
+            .iter()
+            .try_collect()?;
+        workspace_command.check_large_revset(ui, &to_rewrite, args.no_limit)?;
+    }
 
     let mut tx = workspace_command.start_transaction();
     let mut computed_move = compute_move_commits(tx.repo(), &loc)?;
@@ -401,7 +461,12 @@ pub(crate) fn cmd_rebase(
             if let Some(mut formatter) = ui.status_formatter() {
                 writeln!(
                     formatter,
-                    "Abandoned {} divergent commits that were already present in the destination:",
+                    "{} {} divergent commits that were already present in the destination:",
+                    if args.dry_run {
+                        "Would abandon"
+                    } else {
+                        "Abandoned"
+                    },
                     abandoned_divergent.len(),
                 )?;
                 print_updated_commits(
@@ -412,6 +477,10 @@ pub(crate) fn cmd_rebase(
             }
         }
     };
+    if args.dry_run {
+        let plan = computed_move.plan(tx.repo(), &rebase_options, args.check_conflicts)?;
+        return print_rebase_plan(ui, &tx, &plan);
+    }
     let stats = computed_move.apply(tx.repo_mut(), &rebase_options)?;
     print_move_commits_stats(ui, &stats)?;
     tx.finish(ui, tx_description(&loc.target))?;
@@ -419,6 +488,67 @@ pub(crate) fn cmd_rebase(
     Ok(())
 }
 
+/// Prints the new parents of the commits in the rebase `plan`.
+fn print_rebase_plan(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    plan: &[PlannedRebase],
+) -> Result<(), CommandError> {
+    let base_repo = tx.base_repo().as_ref();
+    let plan_by_id: HashMap<&CommitId, &PlannedRebase> = plan
+        .iter()
+        .map(|planned| (planned.old_commit.id(), planned))
+        .collect();
+    let old_ids = plan_by_id.keys().copied().cloned().collect_vec();
+    let template = tx.base_workspace_helper().commit_summary_template();
+    let mut num_conflicted = 0;
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Would rebase {} commits:", old_ids.len())?;
+    }
+    for old_id in RevsetExpression::commits(old_ids)
+        .evaluate(base_repo)?
+        .iter()
+    {
+        let old_id = old_id?;
+        let planned = plan_by_id[&old_id];
+        let old_commit = &planned.old_commit;
+        let new_parents = match &planned.new_parent_ids {
+            Some(new_parent_ids) => {
+                let parents = new_parent_ids.iter().map(short_commit_hash).join(", ");
+                let conflicted = planned
+                    .new_tree
+                    .as_ref()
+                    .is_some_and(|tree| tree.has_conflict());
+                if conflicted && !old_commit.has_conflict()? {
+                    num_conflicted += 1;
+                    format!("({parents}) (conflict)")
+                } else {
+                    format!("({parents})")
+                }
+            }
+            None => "(abandoned)".to_owned(),
+        };
+        if let Some(mut formatter) = ui.status_formatter() {
+            write!(
+                formatter,
+                "  {} -> {new_parents}: ",
+                short_commit_hash(&old_id)
+            )?;
+            template.format(old_commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
+    }
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Dry-run requested, not rebasing.")?;
+    }
+    if num_conflicted > 0 {
+        return Err(user_error(format!(
+            "Rebasing would create conflicts in {num_conflicted} commits"
+        )));
+    }
+    Ok(())
+}
+
 fn plan_rebase_revisions(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
@@ -492,6 +622,7 @@ fn plan_rebase_branch(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
     branch: &[RevisionArg],
+    exclude: &[RevisionArg],
     rebase_destination: &RebaseDestinationArgs,
 ) -> Result<MoveCommitsLocation, CommandError> {
     let branch_commit_ids: Vec<_> = if branch.is_empty() {
@@ -532,10 +663,28 @@ fn plan_rebase_branch(
         }
     }
 
+    let target = if exclude.is_empty() {
+        MoveCommitsTarget::Roots(root_commit_ids)
+    } else {
+        // Move the rest of the branches as a set of commits so the excluded
+        // commits aren't rebased along with their ancestors.
+        let excluded_commit_ids: Vec<_> = workspace_command
+            .parse_union_revsets(ui, exclude)?
+            .evaluate_to_commit_ids()?
+            .try_collect()?;
+        let target_commit_ids: Vec<_> = RevsetExpression::commits(root_commit_ids)
+            .descendants()
+            .minus(&RevsetExpression::commits(excluded_commit_ids).descendants())
+            .evaluate(workspace_command.repo().as_ref())
+            .unwrap()
+            .iter()
+            .try_collect()?; // in reverse topological order
+        MoveCommitsTarget::Commits(target_commit_ids)
+    };
     Ok(MoveCommitsLocation {
         new_parent_ids,
         new_child_ids,
-        target: MoveCommitsTarget::Roots(root_commit_ids),
+        target,
     })
 }
 
@@ -22,6 +22,8 @@ use crate::command_error::internal_error;
 use crate::command_error::user_error;
 use crate::commands::operation::DEFAULT_REVERT_WHAT;
 use crate::commands::operation::view_with_desired_portions_restored;
+use crate::commands::operation::warn_about_affected_workspaces;
+use crate::commands::operation::warn_about_unconfigured_remotes;
 use crate::commands::undo::UNDO_OP_DESC_PREFIX;
 use crate::ui::Ui;
 
@@ -159,6 +161,8 @@ pub fn cmd_redo(ui: &mut Ui, command: &CommandHelper, _: &RedoArgs) -> Result<()
         template.format(&op_to_restore, formatter.as_mut())?;
         writeln!(formatter)?;
     }
+    warn_about_affected_workspaces(ui, &tx)?;
+    warn_about_unconfigured_remotes(ui, &tx)?;
     tx.finish(
         ui,
         format!("{REDO_OP_DESC_PREFIX}{}", op_to_restore.id().hex()),
@@ -16,13 +16,16 @@ use std::io::Write as _;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
 use indoc::formatdoc;
 use itertools::Itertools as _;
 use jj_lib::object_id::ObjectId as _;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::check_path_args_match_changes;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::complete;
@@ -42,6 +45,11 @@ use crate::ui::Ui;
 /// to `jj abandon`, except that it leaves an empty revision with its
 /// description and other metadata preserved.
 ///
+/// When `jj restore` is run in a terminal without any arguments, it asks for
+/// confirmation before discarding all changes in the working copy. Answering
+/// `i` lets you choose the changes to discard in the diff editor instead, like
+/// `jj restore --interactive`. Use `--yes` to skip the prompt.
+///
 /// See `jj diffedit` if you'd like to restore portions of files rather than
 /// entire files.
 #[derive(clap::Args, Clone, Debug)]
@@ -53,6 +61,12 @@ pub(crate) struct RestoreArgs {
         add = ArgValueCompleter::new(complete::modified_range_files),
     )]
     paths: Vec<String>,
+    /// Fail if any of the given paths doesn't match a changed file
+    ///
+    /// By default, such paths are only reported as a warning. This can also
+    /// be enabled by the `ui.strict-filesets` setting.
+    #[arg(long)]
+    strict_paths: bool,
     /// Revision to restore from (source)
     #[arg(
         long,
@@ -106,6 +120,18 @@ pub(crate) struct RestoreArgs {
     /// Preserve the content (not the diff) when rebasing descendants
     #[arg(long)]
     restore_descendants: bool,
+    /// Don't ask for confirmation before discarding all changes in the working
+    /// copy
+    #[arg(long, short)]
+    yes: bool,
+}
+
+/// Response to the confirmation prompt of `jj restore` without arguments.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DiscardChoice {
+    All,
+    Nothing,
+    Interactive,
 }
 
 #[instrument(skip_all)]
@@ -143,9 +169,33 @@ pub(crate) fn cmd_restore(
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
-    let diff_selector =
-        workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
     let to_tree = to_commit.tree()?;
+    check_path_args_match_changes(
+        ui,
+        &workspace_command,
+        &args.paths,
+        [(&from_tree, &to_tree)],
+        args.strict_paths,
+    )?;
+    let mut interactive = args.interactive;
+    let is_bare_invocation = args.paths.is_empty()
+        && args.from.is_none()
+        && args.into.is_none()
+        && args.changes_in.is_none();
+    if is_bare_invocation && !interactive && args.tool.is_none() && !args.yes && Ui::can_prompt() {
+        let num_files = from_tree.diff_stream(&to_tree, &matcher).count().block_on();
+        if num_files > 0 {
+            match prompt_discard_choice(ui, num_files)? {
+                DiscardChoice::All => {}
+                DiscardChoice::Nothing => {
+                    writeln!(ui.status(), "Nothing changed.")?;
+                    return Ok(());
+                }
+                DiscardChoice::Interactive => interactive = true,
+            }
+        }
+    }
+    let diff_selector = workspace_command.diff_selector(ui, args.tool.as_deref(), interactive)?;
     let format_instructions = || {
         formatdoc! {"
             You are restoring changes from: {from_commits}
@@ -193,3 +243,20 @@ pub(crate) fn cmd_restore(
     }
     Ok(())
 }
+
+fn prompt_discard_choice(ui: &Ui, num_files: usize) -> Result<DiscardChoice, CommandError> {
+    let choice = ui.prompt_choice_with(
+        &format!(
+            "Discard all changes in {num_files} files? (y = yes, n = no, i = choose \
+             interactively) [n]"
+        ),
+        Some("n"),
+        |input| match input.to_ascii_lowercase().as_str() {
+            "y" | "yes" => Ok(DiscardChoice::All),
+            "n" | "no" => Ok(DiscardChoice::Nothing),
+            "i" | "interactive" => Ok(DiscardChoice::Interactive),
+            _ => Err("unrecognized response"),
+        },
+    )?;
+    Ok(choice)
+}
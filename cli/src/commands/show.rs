@@ -20,9 +20,11 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::TemplateFileArgs;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::MergeDiffArgs;
 use crate::ui::Ui;
 
 /// Show commit description and changes in a revision
@@ -53,9 +55,19 @@ pub(crate) struct ShowArgs {
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
     #[command(flatten)]
+    template_file: TemplateFileArgs,
+    /// Show a summary of the revision's cryptographic signature
+    ///
+    /// The summary is printed before the rendered revision. Unsigned
+    /// revisions are shown as usual.
+    #[arg(long)]
+    show_signatures: bool,
+    #[command(flatten)]
     format: DiffFormatArgs,
+    #[command(flatten)]
+    merge_diff: MergeDiffArgs,
     /// Do not show the patch
-    #[arg(long, conflicts_with = "DiffFormatArgs")]
+    #[arg(long, conflicts_with_all = ["DiffFormatArgs", "MergeDiffArgs"])]
     no_patch: bool,
 }
 
@@ -65,12 +77,21 @@ pub(crate) fn cmd_show(
     command: &CommandHelper,
     args: &ShowArgs,
 ) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
-    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
-    let template_string = match &args.template {
-        Some(value) => value.clone(),
+    let mut workspace_command = command.workspace_helper_for_query(ui)?;
+    let mut commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    if workspace_command.query_needs_snapshot([commit.id()].into_iter()) {
+        workspace_command.maybe_snapshot(ui)?;
+        commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    }
+    let template_string = match args.template_file.template_text(args.template.as_deref())? {
+        Some(value) => value,
         None => workspace_command.settings().get_string("templates.show")?,
     };
+    let template_string = if args.show_signatures {
+        format!("format_signature_summary(self.signature()) ++ ({template_string})")
+    } else {
+        template_string
+    };
     let template = workspace_command
         .parse_commit_template(ui, &template_string)?
         .labeled(["show", "commit"]);
@@ -81,7 +102,14 @@ pub(crate) fn cmd_show(
     template.format(&commit, formatter)?;
     if !args.no_patch {
         diff_renderer
-            .show_patch(ui, formatter, &commit, &EverythingMatcher, ui.term_width())
+            .show_merge_patch(
+                ui,
+                formatter,
+                &commit,
+                &EverythingMatcher,
+                ui.term_width(),
+                args.merge_diff.mode(),
+            )
             .block_on()?;
     }
     Ok(())
@@ -59,6 +59,11 @@ pub struct SignArgs {
     /// The key used for signing
     #[arg(long)]
     key: Option<String>,
+
+    /// Do not ask for confirmation if the revisions match more commits than
+    /// `ui.large-revset-warning-threshold`
+    #[arg(long)]
+    no_limit: bool,
 }
 
 pub fn cmd_sign(ui: &mut Ui, command: &CommandHelper, args: &SignArgs) -> Result<(), CommandError> {
@@ -81,6 +86,7 @@ pub fn cmd_sign(ui: &mut Ui, command: &CommandHelper, args: &SignArgs) -> Result
     let to_sign: IndexSet<Commit> = revset_expression.evaluate_to_commits()?.try_collect()?;
 
     workspace_command.check_rewritable(to_sign.iter().ids())?;
+    workspace_command.check_large_revset(ui, to_sign.iter().ids(), args.no_limit)?;
 
     let mut tx = workspace_command.start_transaction();
 
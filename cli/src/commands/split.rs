@@ -13,14 +13,22 @@
 // limitations under the License.
 use std::collections::HashMap;
 use std::io::Write as _;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::matchers::DifferenceMatcher;
+use jj_lib::matchers::FilesMatcher;
 use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::rewrite::CommitWithSelection;
 use jj_lib::rewrite::EmptyBehavior;
 use jj_lib::rewrite::MoveCommitsLocation;
@@ -29,7 +37,11 @@ use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
 use jj_lib::rewrite::move_commits;
+use jj_lib::rewrite::restore_tree;
+use jj_lib::rewrite::squash_commits;
+use jj_lib::trailer::parse_description_trailers;
 use pollster::FutureExt as _;
+use regex::Regex;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -37,14 +49,30 @@ use crate::cli_util::DiffSelector;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
+use crate::cli_util::check_path_args_match_changes;
 use crate::cli_util::compute_commit_location;
+use crate::cli_util::read_arg_file;
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
+use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
 use crate::complete;
+use crate::description_util::EditedDescription;
+use crate::description_util::TextEditor;
+use crate::description_util::add_inherited_trailers;
 use crate::description_util::add_trailers;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
+use crate::description_util::edit_description_or_restart;
 use crate::description_util::join_message_paragraphs;
+use crate::description_util::strip_lone_inherited_trailers;
+use crate::formatter::Formatter;
+use crate::merge_tools::MatchingHunk;
+use crate::merge_tools::select_matching_hunks;
+use crate::patch_util::FilePatch;
+use crate::patch_util::apply_patch_to_tree;
+use crate::patch_util::parse_patch;
 use crate::ui::Ui;
 
 /// Split a revision in two
@@ -62,6 +90,17 @@ use crate::ui::Ui;
 /// description, the remaining changes will not get a description, and you will
 /// be asked for a description only for the selected changes.
 ///
+/// When the changes are selected interactively, adding a line
+/// `JJ: restart-selection` to the description of the selected changes opens
+/// the diff editor again, starting from the previous selection. The
+/// description entered so far is kept.
+///
+/// With `--into`, the selected changes are moved into an existing revision
+/// instead of a new one, as if by `jj squash --from <revision> --into <into>`.
+///
+/// When splitting a merge commit, the paths that are conflicted in the merged
+/// parents can't be split. Their changes are kept in the remaining changes.
+///
 /// Splitting an empty commit is not supported because the same effect can be
 /// achieved with `jj new`.
 #[derive(clap::Args, Clone, Debug)]
@@ -120,16 +159,123 @@ pub(crate) struct SplitArgs {
         add = ArgValueCompleter::new(complete::revset_expression_mutable),
     )]
     insert_before: Option<Vec<RevisionArg>>,
+    /// The revision(s) to insert the remaining changes after (can be repeated
+    /// to create a merge commit)
+    ///
+    /// The selected changes stay in place of the split revision.
+    #[arg(
+        long,
+        conflicts_with_all = ["destination", "insert_after", "insert_before", "parallel"],
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    insert_remaining_after: Option<Vec<RevisionArg>>,
+    /// The revision(s) to insert the remaining changes before (can be repeated
+    /// to create a merge commit)
+    ///
+    /// The selected changes stay in place of the split revision.
+    #[arg(
+        long,
+        conflicts_with_all = ["destination", "insert_after", "insert_before", "parallel"],
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    insert_remaining_before: Option<Vec<RevisionArg>>,
+    /// Move the selected changes into this existing revision
+    ///
+    /// The destination keeps its description. It can't be the revision being
+    /// split or one of its descendants.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "destination",
+            "insert_after",
+            "insert_before",
+            "insert_remaining_after",
+            "insert_remaining_before",
+            "parallel",
+            "message_paragraphs",
+            "no_edit",
+            "both_inherit_trailers",
+        ],
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    into: Option<RevisionArg>,
     /// The change description to use (don't open editor)
     ///
     /// The description is used for the commit with the selected changes. The
     /// source commit description is kept unchanged.
     #[arg(long = "message", short, value_name = "MESSAGE")]
     message_paragraphs: Vec<String>,
+    /// Don't open an editor
+    ///
+    /// The commit with the selected changes keeps the source commit
+    /// description, and the commit with the remaining changes gets an empty
+    /// description.
+    #[arg(long, conflicts_with = "message_paragraphs")]
+    no_edit: bool,
+    /// Split the revision into up to this many revisions
+    ///
+    /// The diff editor is opened repeatedly to select the changes for each new
+    /// revision from the changes left over by the previous rounds. Selecting
+    /// no changes or all the remaining changes stops early. The remaining
+    /// changes are put in the last revision.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(2..),
+        conflicts_with_all = [
+            "destination",
+            "insert_after",
+            "insert_before",
+            "insert_remaining_after",
+            "insert_remaining_before",
+            "into",
+            "parallel",
+            "message_paragraphs",
+            "both_inherit_trailers",
+        ],
+    )]
+    num: Option<u32>,
     /// Split the revision into two parallel revisions instead of a parent and
     /// child
     #[arg(long, short)]
     parallel: bool,
+    /// Which revision the bookmarks pointing to the split revision move to
+    ///
+    /// With `--parallel`, this is the new head the bookmarks follow. Defaults
+    /// to `second` if `split.legacy-bookmark-behavior` is enabled, and to
+    /// `first` otherwise.
+    #[arg(
+        long,
+        value_name = "WHICH",
+        conflicts_with_all = [
+            "destination",
+            "insert_after",
+            "insert_before",
+            "insert_remaining_after",
+            "insert_remaining_before",
+            "into",
+        ],
+    )]
+    bookmark_to: Option<SplitBookmarkTo>,
+    /// Keep editing the revision with the selected changes
+    ///
+    /// By default, the working copy moves to the revision with the remaining
+    /// changes if the split revision was the working-copy commit.
+    #[arg(
+        long,
+        conflicts_with_all = ["destination", "insert_after", "insert_before", "into"],
+    )]
+    edit_first: bool,
+    /// Copy the trailers of the original description to both descriptions
+    ///
+    /// Only the trailers with the keys listed in the `split.inherited-trailers`
+    /// setting are copied. They are removed again from a description that is
+    /// left with nothing else.
+    #[arg(long)]
+    both_inherit_trailers: bool,
     /// Files matching any of these filesets are put in the selected changes
     #[arg(
         value_name = "FILESETS",
@@ -137,6 +283,47 @@ pub(crate) struct SplitArgs {
         add = ArgValueCompleter::new(complete::modified_revision_files),
     )]
     paths: Vec<String>,
+    /// Fail if any of the given paths doesn't match a changed file
+    ///
+    /// By default, such paths are only reported as a warning. This can also
+    /// be enabled by the `ui.strict-filesets` setting.
+    #[arg(long)]
+    strict_paths: bool,
+    /// Select the changes by applying a patch instead of using a diff editor
+    ///
+    /// The patch is a unified diff, such as the output of `jj diff --git`. It
+    /// is applied to the parent of the revision to produce the selected
+    /// changes. Use `-` to read the patch from the standard input.
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["interactive", "tool", "paths", "num"],
+    )]
+    from_patch: Option<PathBuf>,
+    /// Select the hunks with an added or removed line matching this regular
+    /// expression instead of using a diff editor
+    ///
+    /// The hunks are split as in the builtin diff editor. If filesets are
+    /// given, only the hunks in the matching files are selected.
+    #[arg(
+        long,
+        value_name = "REGEX",
+        conflicts_with_all = ["interactive", "tool", "from_patch", "num"],
+    )]
+    select_matching: Option<String>,
+    /// List the hunks that `--select-matching` would select without splitting
+    #[arg(long, requires = "select_matching")]
+    dry_run: bool,
+}
+
+/// The revision the bookmarks of the split revision are moved to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum SplitBookmarkTo {
+    /// The revision with the selected changes
+    First,
+    /// The revision with the remaining changes
+    Second,
 }
 
 impl SplitArgs {
@@ -158,14 +345,67 @@ impl SplitArgs {
             ));
         }
         workspace_command.check_rewritable([target_commit.id()])?;
-        let matcher = workspace_command
+        let mut matcher = workspace_command
             .parse_file_patterns(ui, &self.paths)?
             .to_matcher();
+        let parent_tree = target_commit.parent_tree(workspace_command.repo().as_ref())?;
+        check_path_args_match_changes(
+            ui,
+            workspace_command,
+            &self.paths,
+            [(&parent_tree, &target_commit.tree()?)],
+            self.strict_paths,
+        )?;
+        if target_commit.parent_ids().len() > 1 {
+            matcher = exclude_conflicted_parent_paths(
+                ui,
+                workspace_command,
+                &parent_tree,
+                matcher,
+                !self.paths.is_empty(),
+            )?;
+        }
         let diff_selector = workspace_command.diff_selector(
             ui,
             self.tool.as_deref(),
-            self.interactive || self.paths.is_empty(),
+            self.from_patch.is_none()
+                && self.select_matching.is_none()
+                && (self.interactive || self.paths.is_empty() || self.num.is_some()),
         )?;
+        let patch = match &self.from_patch {
+            Some(path) => Some(parse_patch(&read_arg_file(path)?)?),
+            None => None,
+        };
+        let select_matching = match &self.select_matching {
+            Some(pattern) => Some(Regex::new(pattern).map_err(|err| {
+                user_error_with_message(format!("Invalid regular expression: {pattern}"), err)
+            })?),
+            None => None,
+        };
+        let into_commit = if let Some(into) = &self.into {
+            let into_commit = workspace_command.resolve_single_rev(ui, into)?;
+            if workspace_command
+                .repo()
+                .index()
+                .is_ancestor(target_commit.id(), into_commit.id())
+            {
+                let target = short_commit_hash(target_commit.id());
+                if into_commit.id() == target_commit.id() {
+                    return Err(user_error(format!("Cannot split {target} into itself")));
+                }
+                return Err(user_error_with_hint(
+                    format!(
+                        "Cannot split {target} into its descendant {into}",
+                        into = short_commit_hash(into_commit.id()),
+                    ),
+                    "Use `jj squash --from` to move the changes into a descendant.",
+                ));
+            }
+            workspace_command.check_rewritable([into_commit.id()])?;
+            Some(into_commit)
+        } else {
+            None
+        };
         let use_move_flags = self.destination.is_some()
             || self.insert_after.is_some()
             || self.insert_before.is_some();
@@ -181,26 +421,108 @@ impl SplitArgs {
         } else {
             Default::default()
         };
+        let remaining_location =
+            if self.insert_remaining_after.is_some() || self.insert_remaining_before.is_some() {
+                Some(compute_commit_location(
+                    ui,
+                    workspace_command,
+                    None,
+                    self.insert_remaining_after.as_deref(),
+                    self.insert_remaining_before.as_deref(),
+                    "remaining commit",
+                )?)
+            } else {
+                None
+            };
+        let bookmark_to = match self.bookmark_to {
+            Some(bookmark_to) => bookmark_to,
+            None if workspace_command
+                .settings()
+                .get_bool("split.legacy-bookmark-behavior")? =>
+            {
+                SplitBookmarkTo::Second
+            }
+            None => SplitBookmarkTo::First,
+        };
         Ok(ResolvedSplitArgs {
             target_commit,
+            into_commit,
             matcher,
             diff_selector,
+            patch,
+            select_matching,
             parallel: self.parallel,
+            bookmark_to,
             use_move_flags,
             new_parent_ids,
             new_child_ids,
+            remaining_location,
         })
     }
 }
 
+/// Excludes the paths that are conflicted in the auto-merged parent tree of a
+/// merge commit from the matcher.
+///
+/// The conflicts would have to be materialized in the diff editor, so these
+/// paths can't be split. If the user named some of them explicitly, this is
+/// an error. Otherwise, their changes are left in the remaining changes.
+fn exclude_conflicted_parent_paths(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    parent_tree: &MergedTree,
+    matcher: Box<dyn Matcher>,
+    has_path_args: bool,
+) -> Result<Box<dyn Matcher>, CommandError> {
+    let conflicted_paths: Vec<RepoPathBuf> = parent_tree
+        .conflicts()
+        .map(|(path, _)| path)
+        .filter(|path| matcher.matches(path))
+        .collect();
+    if conflicted_paths.is_empty() {
+        return Ok(matcher);
+    }
+    let ui_paths = conflicted_paths
+        .iter()
+        .map(|path| workspace_command.format_file_path(path))
+        .join(", ");
+    if has_path_args {
+        return Err(user_error_with_hint(
+            format!("Cannot split paths conflicted in the parents of the merge commit: {ui_paths}"),
+            "Only paths without conflicts in the merged parents can be split.",
+        ));
+    }
+    writeln!(
+        ui.warning_default(),
+        "The following paths are conflicted in the parents of the merge commit and cannot be \
+         split interactively: {ui_paths}"
+    )?;
+    writeln!(
+        ui.hint_default(),
+        "Their changes will be kept in the remaining changes."
+    )?;
+    Ok(Box::new(DifferenceMatcher::new(
+        matcher,
+        FilesMatcher::new(conflicted_paths),
+    )))
+}
+
 struct ResolvedSplitArgs {
     target_commit: Commit,
+    into_commit: Option<Commit>,
     matcher: Box<dyn Matcher>,
     diff_selector: DiffSelector,
+    /// The patch defining the selected changes, if not using `diff_selector`.
+    patch: Option<Vec<FilePatch>>,
+    /// The pattern of the selected hunks, if not using `diff_selector`.
+    select_matching: Option<Regex>,
     parallel: bool,
+    bookmark_to: SplitBookmarkTo,
     use_move_flags: bool,
     new_parent_ids: Vec<CommitId>,
     new_child_ids: Vec<CommitId>,
+    /// The new parents and children of the second commit, if it's moved.
+    remaining_location: Option<(Vec<CommitId>, Vec<CommitId>)>,
 }
 
 #[instrument(skip_all)]
@@ -212,21 +534,72 @@ pub(crate) fn cmd_split(
     let mut workspace_command = command.workspace_helper(ui)?;
     let ResolvedSplitArgs {
         target_commit,
+        into_commit,
         matcher,
         diff_selector,
+        patch,
+        select_matching,
         parallel,
+        bookmark_to,
         use_move_flags,
         new_parent_ids,
         new_child_ids,
+        remaining_location,
     } = args.resolve(ui, &workspace_command)?;
     let text_editor = workspace_command.text_editor()?;
     let mut tx = workspace_command.start_transaction();
 
+    if let Some(num) = args.num {
+        return split_into_parts(
+            ui,
+            tx,
+            &target_commit,
+            matcher.as_ref(),
+            &diff_selector,
+            &text_editor,
+            num,
+            args.no_edit,
+            bookmark_to,
+            args.edit_first,
+        );
+    }
+
     // Prompt the user to select the changes they want for the first commit.
-    let target = select_diff(ui, &tx, &target_commit, &matcher, &diff_selector)?;
+    let mut target = if let Some(patch) = &patch {
+        select_from_patch(ui, &tx, &target_commit, patch)?
+    } else if let Some(pattern) = &select_matching {
+        let (selection, hunks) = select_hunks_matching(&tx, &target_commit, &matcher, pattern)?;
+        if args.dry_run {
+            return print_matching_hunks(ui, &tx, &hunks);
+        }
+        warn_if_full_or_empty_selection(ui, &selection)?;
+        selection
+    } else {
+        select_diff(ui, &tx, &target_commit, None, &matcher, &diff_selector)?
+    };
+
+    if let Some(into_commit) = into_commit {
+        return split_into(ui, tx, &target, &into_commit);
+    }
+
+    let inherited_trailers = if args.both_inherit_trailers {
+        let keys: Vec<String> = tx.settings().get("split.inherited-trailers")?;
+        parse_description_trailers(target.commit.description())
+            .into_iter()
+            .filter(|trailer| {
+                keys.iter()
+                    .any(|key| key.eq_ignore_ascii_case(&trailer.key))
+            })
+            .collect_vec()
+    } else {
+        vec![]
+    };
 
     // Create the first commit, which includes the changes selected by the user.
-    let first_commit = {
+    // The user can go back to the change selection from the description
+    // editor, in which case the description entered so far is kept.
+    let mut entered_description: Option<String> = None;
+    let first_commit = loop {
         let mut commit_builder = tx.repo_mut().rewrite_commit(&target.commit).detach();
         commit_builder.set_tree_id(target.selected_tree.id());
         if use_move_flags {
@@ -238,21 +611,52 @@ pub(crate) fn cmd_split(
         let description = if !args.message_paragraphs.is_empty() {
             let description = join_message_paragraphs(&args.message_paragraphs);
             if !description.is_empty() {
-                commit_builder.set_description(description);
+                commit_builder
+                    .set_description(add_inherited_trailers(&description, &inherited_trailers));
                 add_trailers(ui, &tx, &commit_builder)?
             } else {
                 description
             }
+        } else if args.no_edit {
+            add_trailers(ui, &tx, &commit_builder)?
         } else {
+            if let Some(description) = entered_description.take() {
+                commit_builder.set_description(description);
+            } else {
+                commit_builder.set_description(add_inherited_trailers(
+                    commit_builder.description(),
+                    &inherited_trailers,
+                ));
+            }
             let new_description = add_trailers(ui, &tx, &commit_builder)?;
             commit_builder.set_description(new_description);
             let temp_commit = commit_builder.write_hidden()?;
             let intro = "Enter a description for the selected changes.";
             let template = description_template(ui, &tx, intro, &temp_commit)?;
-            edit_description(&text_editor, &template)?
+            let description = if diff_selector.is_interactive() {
+                match edit_description_or_restart(&text_editor, &template)? {
+                    EditedDescription::Done(description) => description,
+                    EditedDescription::Restart(description) => {
+                        entered_description = Some(description);
+                        let previous_selection = target.selected_tree.clone();
+                        target = select_diff(
+                            ui,
+                            &tx,
+                            &target_commit,
+                            Some(&previous_selection),
+                            &matcher,
+                            &diff_selector,
+                        )?;
+                        continue;
+                    }
+                }
+            } else {
+                edit_description(&text_editor, &template)?
+            };
+            strip_lone_inherited_trailers(description, &inherited_trailers)
         };
         commit_builder.set_description(description);
-        commit_builder.write(tx.repo_mut())?
+        break commit_builder.write(tx.repo_mut())?;
     };
 
     // Create the second commit, which includes everything the user didn't
@@ -260,12 +664,8 @@ pub(crate) fn cmd_split(
     let second_commit = {
         let target_tree = target.commit.tree()?;
         let new_tree = if parallel {
-            // Merge the original commit tree with its parent using the tree
-            // containing the user selected changes as the base for the merge.
-            // This results in a tree with the changes the user didn't select.
-            target_tree
-                .merge(target.selected_tree.clone(), target.parent_tree.clone())
-                .block_on()?
+            // Apply the changes the user didn't select to the parent tree.
+            target.unselected_tree().block_on()?
         } else {
             target_tree
         };
@@ -284,7 +684,7 @@ pub(crate) fn cmd_split(
             // become divergent.
             commit_builder.generate_new_change_id();
         }
-        let description = if target.commit.description().is_empty() {
+        let description = if target.commit.description().is_empty() || args.no_edit {
             // If there was no description before, don't ask for one for the
             // second commit.
             "".to_string()
@@ -297,7 +697,8 @@ pub(crate) fn cmd_split(
             let temp_commit = commit_builder.write_hidden()?;
             let intro = "Enter a description for the remaining changes.";
             let template = description_template(ui, &tx, intro, &temp_commit)?;
-            edit_description(&text_editor, &template)?
+            let description = edit_description(&text_editor, &template)?;
+            strip_lone_inherited_trailers(description, &inherited_trailers)
         };
         commit_builder.set_description(description);
         commit_builder.write(tx.repo_mut())?
@@ -312,9 +713,97 @@ pub(crate) fn cmd_split(
             new_parent_ids,
             new_child_ids,
         )?
+    } else if let Some((new_parent_ids, new_child_ids)) = &remaining_location {
+        move_second_commit(
+            &mut tx,
+            &target,
+            first_commit,
+            second_commit,
+            new_parent_ids,
+            new_child_ids,
+            args.edit_first,
+        )?
     } else {
-        rewrite_descendants(&mut tx, &target, first_commit, second_commit, parallel)?
+        rewrite_descendants(
+            &mut tx,
+            &target,
+            first_commit,
+            second_commit,
+            parallel,
+            bookmark_to,
+            args.edit_first,
+        )?
     };
+    if let Some(mut formatter) = ui.status_formatter() {
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+        write!(formatter, "Selected changes : ")?;
+        tx.write_commit_summary(formatter.as_mut(), &first_commit)?;
+        writeln!(formatter)?;
+        if remaining_location.is_some() {
+            write_parent_summaries(&tx, formatter.as_mut(), &first_commit)?;
+        }
+        write!(formatter, "Remaining changes: ")?;
+        tx.write_commit_summary(formatter.as_mut(), &second_commit)?;
+        writeln!(formatter)?;
+        if remaining_location.is_some() {
+            write_parent_summaries(&tx, formatter.as_mut(), &second_commit)?;
+        }
+    }
+    let tx_description = match args.bookmark_to {
+        None => format!("split commit {}", target.commit.id().hex()),
+        Some(bookmark_to) => format!(
+            "split commit {} with bookmarks moved to the {} commit",
+            target.commit.id().hex(),
+            match bookmark_to {
+                SplitBookmarkTo::First => "first",
+                SplitBookmarkTo::Second => "second",
+            },
+        ),
+    };
+    tx.finish(ui, tx_description)?;
+    Ok(())
+}
+
+/// Moves the selected changes of the target commit into an existing commit.
+fn split_into(
+    ui: &Ui,
+    mut tx: WorkspaceCommandTransaction,
+    target: &CommitWithSelection,
+    into_commit: &Commit,
+) -> Result<(), CommandError> {
+    let keep_emptied = true;
+    let Some(squashed) = squash_commits(
+        tx.repo_mut(),
+        slice::from_ref(target),
+        into_commit,
+        keep_emptied,
+    )?
+    else {
+        return Err(user_error("No changes selected"));
+    };
+    let first_commit = squashed.commit_builder.write()?;
+    // The target isn't an ancestor of the destination, so it has only been
+    // rewritten once by squash_commits().
+    let [rewritten_target_id] = &*tx.repo().new_parents(slice::from_ref(target.commit.id())) else {
+        panic!("target commit should be rewritten to a single commit");
+    };
+    let mut second_commit = tx.repo().store().get_commit(rewritten_target_id)?;
+    let mut num_rebased = 0;
+    tx.repo_mut().rebase_descendants_with_options(
+        &RebaseOptions::default(),
+        |old_commit, rebased| {
+            if old_commit.id() == second_commit.id() {
+                match rebased {
+                    RebasedCommit::Rewritten(commit) => second_commit = commit,
+                    RebasedCommit::Abandoned { .. } => panic!("all commits should be kept"),
+                }
+            } else {
+                num_rebased += 1;
+            }
+        },
+    )?;
     if let Some(mut formatter) = ui.status_formatter() {
         if num_rebased > 0 {
             writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
@@ -325,7 +814,156 @@ pub(crate) fn cmd_split(
         tx.write_commit_summary(formatter.as_mut(), &second_commit)?;
         writeln!(formatter)?;
     }
-    tx.finish(ui, format!("split commit {}", target.commit.id().hex()))?;
+    tx.finish(
+        ui,
+        format!(
+            "split commit {} into commit {}",
+            target.commit.id().hex(),
+            into_commit.id().hex()
+        ),
+    )?;
+    Ok(())
+}
+
+/// Splits the target commit into a chain of up to `num` commits by prompting
+/// the user to select the changes for each of them in turn.
+#[expect(clippy::too_many_arguments)]
+fn split_into_parts(
+    ui: &Ui,
+    mut tx: WorkspaceCommandTransaction,
+    target_commit: &Commit,
+    matcher: &dyn Matcher,
+    diff_selector: &DiffSelector,
+    text_editor: &TextEditor,
+    num: u32,
+    no_edit: bool,
+    bookmark_to: SplitBookmarkTo,
+    edit_first: bool,
+) -> Result<(), CommandError> {
+    let parent_tree = target_commit.parent_tree(tx.repo())?;
+    let target_tree = target_commit.tree()?;
+    let mut base_tree = parent_tree.clone();
+    let mut parts: Vec<Commit> = vec![];
+    for part_num in 1..num {
+        let format_instructions = || {
+            format!(
+                "\
+You are splitting a commit into up to {num} parts: {}
+
+The diff initially shows the changes left for part {part_num} and later.
+
+Adjust the right side until it shows the contents you want to split into
+part {part_num}.
+The changes that are not selected will be left for the next parts.
+",
+                tx.format_commit_summary(target_commit)
+            )
+        };
+        let selected_tree_id =
+            diff_selector.select(&base_tree, &target_tree, matcher, format_instructions)?;
+        if selected_tree_id == base_tree.id() {
+            break;
+        }
+        let selected_tree = tx.repo().store().get_root_tree(&selected_tree_id)?;
+
+        let mut commit_builder = tx.repo_mut().rewrite_commit(target_commit).detach();
+        commit_builder.set_tree_id(selected_tree_id.clone());
+        if let Some(previous) = parts.last() {
+            // Only the first part is a rewrite of the target commit.
+            commit_builder.set_parents(vec![previous.id().clone()]);
+            commit_builder.clear_rewrite_source();
+            commit_builder.generate_new_change_id();
+        }
+        let description = if no_edit {
+            if parts.is_empty() {
+                add_trailers(ui, &tx, &commit_builder)?
+            } else {
+                String::new()
+            }
+        } else {
+            let new_description = add_trailers(ui, &tx, &commit_builder)?;
+            commit_builder.set_description(new_description);
+            let temp_commit = commit_builder.write_hidden()?;
+            let intro = format!("Enter a description for part {part_num} of the split.");
+            let template = description_template(ui, &tx, &intro, &temp_commit)?;
+            edit_description(text_editor, &template)?
+        };
+        commit_builder.set_description(description);
+        parts.push(commit_builder.write(tx.repo_mut())?);
+
+        base_tree = selected_tree;
+        if base_tree.id() == target_tree.id() {
+            break;
+        }
+    }
+    let Some(first_commit) = parts.first().cloned() else {
+        return Err(user_error("No changes selected"));
+    };
+    if base_tree.id() == target_tree.id() {
+        writeln!(
+            ui.warning_default(),
+            "All changes have been selected, so the original revision will become empty"
+        )?;
+    }
+
+    // Create the last commit, which includes everything the user didn't
+    // select.
+    let remaining_commit = {
+        let mut commit_builder = tx.repo_mut().rewrite_commit(target_commit).detach();
+        commit_builder
+            .set_parents(vec![parts.last().unwrap().id().clone()])
+            .set_tree_id(target_tree.id());
+        commit_builder.clear_rewrite_source();
+        commit_builder.generate_new_change_id();
+        let description = if target_commit.description().is_empty() || no_edit {
+            String::new()
+        } else {
+            let new_description = add_trailers(ui, &tx, &commit_builder)?;
+            commit_builder.set_description(new_description);
+            let temp_commit = commit_builder.write_hidden()?;
+            let intro = "Enter a description for the remaining changes.";
+            let template = description_template(ui, &tx, intro, &temp_commit)?;
+            edit_description(text_editor, &template)?
+        };
+        commit_builder.set_description(description);
+        commit_builder.write(tx.repo_mut())?
+    };
+
+    let target = CommitWithSelection {
+        commit: target_commit.clone(),
+        selected_tree: first_commit.tree()?,
+        parent_tree,
+    };
+    let (_, remaining_commit, num_rebased) = rewrite_descendants(
+        &mut tx,
+        &target,
+        first_commit,
+        remaining_commit,
+        false,
+        bookmark_to,
+        edit_first,
+    )?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+        for part in &parts {
+            write!(formatter, "Selected changes : ")?;
+            tx.write_commit_summary(formatter.as_mut(), part)?;
+            writeln!(formatter)?;
+        }
+        write!(formatter, "Remaining changes: ")?;
+        tx.write_commit_summary(formatter.as_mut(), &remaining_commit)?;
+        writeln!(formatter)?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "split commit {} into {} parts",
+            target_commit.id().hex(),
+            parts.len() + 1
+        ),
+    )?;
     Ok(())
 }
 
@@ -396,15 +1034,114 @@ fn move_first_commit(
     Ok((first_commit, second_commit, num_rebased))
 }
 
+/// Writes the summaries of the parents of a split commit, to show where it
+/// ended up.
+fn write_parent_summaries(
+    tx: &WorkspaceCommandTransaction,
+    formatter: &mut dyn Formatter,
+    commit: &Commit,
+) -> Result<(), CommandError> {
+    for parent in commit.parents() {
+        write!(formatter, "  with parent    : ")?;
+        tx.write_commit_summary(formatter, &parent?)?;
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+/// Moves the second commit to the given location. The descendants of the
+/// target commit are rebased onto the first commit, which takes the place of
+/// the target commit.
+fn move_second_commit(
+    tx: &mut WorkspaceCommandTransaction,
+    target: &CommitWithSelection,
+    mut first_commit: Commit,
+    mut second_commit: Commit,
+    new_parent_ids: &[CommitId],
+    new_child_ids: &[CommitId],
+    edit_first: bool,
+) -> Result<(Commit, Commit, usize), CommandError> {
+    let mut rewritten_commits: HashMap<CommitId, CommitId> = HashMap::new();
+    rewritten_commits.insert(target.commit.id().clone(), first_commit.id().clone());
+    tx.repo_mut()
+        .transform_descendants(vec![target.commit.id().clone()], async |rewriter| {
+            let old_commit_id = rewriter.old_commit().id().clone();
+            let new_commit = rewriter.rebase().await?.write()?;
+            rewritten_commits.insert(old_commit_id, new_commit.id().clone());
+            Ok(())
+        })?;
+
+    let new_parent_ids: Vec<_> = new_parent_ids
+        .iter()
+        .map(|commit_id| rewritten_commits.get(commit_id).unwrap_or(commit_id))
+        .cloned()
+        .collect();
+    let new_child_ids: Vec<_> = new_child_ids
+        .iter()
+        .map(|commit_id| rewritten_commits.get(commit_id).unwrap_or(commit_id))
+        .cloned()
+        .collect();
+    let stats = move_commits(
+        tx.repo_mut(),
+        &MoveCommitsLocation {
+            new_parent_ids,
+            new_child_ids,
+            target: MoveCommitsTarget::Commits(vec![second_commit.id().clone()]),
+        },
+        &RebaseOptions {
+            empty: EmptyBehavior::Keep,
+            rewrite_refs: RewriteRefsOptions {
+                delete_abandoned_bookmarks: false,
+            },
+            simplify_ancestor_merge: false,
+        },
+    )?;
+
+    // 1 for the transformation of the original commit to the first commit
+    // that was inserted in rewritten_commits
+    let mut num_new_rebased = 1;
+    if let Some(RebasedCommit::Rewritten(commit)) = stats.rebased_commits.get(second_commit.id()) {
+        second_commit = commit.clone();
+        num_new_rebased += 1;
+    }
+    if let Some(RebasedCommit::Rewritten(commit)) = stats.rebased_commits.get(first_commit.id()) {
+        first_commit = commit.clone();
+    }
+    // Move the working copy commit (@) like rewrite_descendants() does.
+    let new_wc_commit = if edit_first {
+        &first_commit
+    } else {
+        &second_commit
+    };
+    for (name, working_copy_commit) in tx.base_repo().clone().view().wc_commit_ids() {
+        if working_copy_commit == target.commit.id() {
+            tx.repo_mut().edit(name.clone(), new_wc_commit)?;
+        }
+    }
+
+    let num_rebased = rewritten_commits.len() + stats.rebased_commits.len()
+        // don't count the commits generated by the split in the rebased commits
+        - num_new_rebased
+        // only count once a commit that may have been rewritten twice in the process
+        - rewritten_commits
+            .iter()
+            .filter(|(_, rewritten)| stats.rebased_commits.contains_key(rewritten))
+            .count();
+
+    Ok((first_commit, second_commit, num_rebased))
+}
+
 fn rewrite_descendants(
     tx: &mut WorkspaceCommandTransaction,
     target: &CommitWithSelection,
     first_commit: Commit,
     second_commit: Commit,
     parallel: bool,
+    bookmark_to: SplitBookmarkTo,
+    edit_first: bool,
 ) -> Result<(Commit, Commit, usize), CommandError> {
-    let legacy_bookmark_behavior = tx.settings().get_bool("split.legacy-bookmark-behavior")?;
-    if legacy_bookmark_behavior {
+    let bookmarks_to_second = bookmark_to == SplitBookmarkTo::Second;
+    if bookmarks_to_second {
         // Mark the commit being split as rewritten to the second commit. This
         // moves any bookmarks pointing to the target commit to the second
         // commit.
@@ -416,7 +1153,7 @@ fn rewrite_descendants(
         vec![target.commit.id().clone()],
         async |mut rewriter| {
             num_rebased += 1;
-            if parallel && legacy_bookmark_behavior {
+            if parallel && bookmarks_to_second {
                 // The old_parent is the second commit due to the rewrite above.
                 rewriter
                     .replace_parent(second_commit.id(), [first_commit.id(), second_commit.id()]);
@@ -429,11 +1166,17 @@ fn rewrite_descendants(
             Ok(())
         },
     )?;
-    // Move the working copy commit (@) to the second commit for any workspaces
-    // where the target commit is the working copy commit.
+    // Move the working copy commit (@) to the second commit (or the first one
+    // with --edit-first) for any workspaces where the target commit is the
+    // working copy commit.
+    let new_wc_commit = if edit_first {
+        &first_commit
+    } else {
+        &second_commit
+    };
     for (name, working_copy_commit) in tx.base_repo().clone().view().wc_commit_ids() {
         if working_copy_commit == target.commit.id() {
-            tx.repo_mut().edit(name.clone(), &second_commit)?;
+            tx.repo_mut().edit(name.clone(), new_wc_commit)?;
         }
     }
 
@@ -446,6 +1189,7 @@ fn select_diff(
     ui: &Ui,
     tx: &WorkspaceCommandTransaction,
     target_commit: &Commit,
+    previous_selection: Option<&MergedTree>,
     matcher: &dyn Matcher,
     diff_selector: &DiffSelector,
 ) -> Result<CommitWithSelection, CommandError> {
@@ -464,9 +1208,12 @@ The changes that are not selected will replace the original commit.
         )
     };
     let parent_tree = target_commit.parent_tree(tx.repo())?;
-    let selected_tree_id = diff_selector.select(
+    // When selecting again, all the changes in the commit are shown, and the
+    // previously selected ones are initially selected.
+    let selected_tree_id = diff_selector.select_from(
         &parent_tree,
         &target_commit.tree()?,
+        previous_selection,
         matcher,
         format_instructions,
     )?;
@@ -475,6 +1222,100 @@ The changes that are not selected will replace the original commit.
         selected_tree: tx.repo().store().get_root_tree(&selected_tree_id)?,
         parent_tree,
     };
+    warn_if_full_or_empty_selection(ui, &selection)?;
+
+    Ok(selection)
+}
+
+/// Selects the changes by applying `patch` to the parent tree of the target
+/// commit.
+fn select_from_patch(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    target_commit: &Commit,
+    patch: &[FilePatch],
+) -> Result<CommitWithSelection, CommandError> {
+    let parent_tree = target_commit.parent_tree(tx.repo())?;
+    let store = tx.repo().store();
+    let selected_tree_id = apply_patch_to_tree(store, &parent_tree, patch)?;
+    let selection = CommitWithSelection {
+        commit: target_commit.clone(),
+        selected_tree: store.get_root_tree(&selected_tree_id)?,
+        parent_tree,
+    };
+    warn_if_full_or_empty_selection(ui, &selection)?;
+    Ok(selection)
+}
+
+/// Selects the hunks of the target commit that have a changed line matching
+/// `pattern`.
+fn select_hunks_matching(
+    tx: &WorkspaceCommandTransaction,
+    target_commit: &Commit,
+    matcher: &dyn Matcher,
+    pattern: &Regex,
+) -> Result<(CommitWithSelection, Vec<MatchingHunk>), CommandError> {
+    let parent_tree = target_commit.parent_tree(tx.repo())?;
+    let store = tx.repo().store();
+    // Unmatched paths shouldn't be taken from the target tree.
+    let right_tree_id = restore_tree(&target_commit.tree()?, &parent_tree, matcher).block_on()?;
+    let right_tree = store.get_root_tree(&right_tree_id)?;
+    let (selected_tree_id, hunks) = select_matching_hunks(
+        &parent_tree,
+        &right_tree,
+        matcher,
+        tx.base_workspace_helper().env().conflict_marker_style(),
+        pattern,
+    )?;
+    let selection = CommitWithSelection {
+        commit: target_commit.clone(),
+        selected_tree: store.get_root_tree(&selected_tree_id)?,
+        parent_tree,
+    };
+    Ok((selection, hunks))
+}
+
+/// Prints the hunks selected by `--select-matching` for `--dry-run`.
+fn print_matching_hunks(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    hunks: &[MatchingHunk],
+) -> Result<(), CommandError> {
+    if hunks.is_empty() {
+        writeln!(ui.status(), "No hunks match the pattern")?;
+        return Ok(());
+    }
+    let workspace_command = tx.base_workspace_helper();
+    let mut formatter = ui.stdout_formatter();
+    for (path, hunks) in &hunks.iter().chunk_by(|hunk| &hunk.path) {
+        writeln!(formatter, "{}:", workspace_command.format_file_path(path))?;
+        for hunk in hunks {
+            writeln!(
+                formatter,
+                "  @@ -{} +{} @@",
+                format_hunk_range(&hunk.left_range),
+                format_hunk_range(&hunk.right_range)
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats a 0-based line range like in a unified diff hunk header.
+fn format_hunk_range(range: &Range<usize>) -> String {
+    // An empty range is identified by the line before it.
+    let start = if range.is_empty() {
+        range.start
+    } else {
+        range.start + 1
+    };
+    format!("{start},{}", range.len())
+}
+
+fn warn_if_full_or_empty_selection(
+    ui: &Ui,
+    selection: &CommitWithSelection,
+) -> Result<(), CommandError> {
     if selection.is_full_selection() {
         writeln!(
             ui.warning_default(),
@@ -486,6 +1327,5 @@ The changes that are not selected will replace the original commit.
             "No changes have been selected, so the new revision will be empty"
         )?;
     }
-
-    Ok(selection)
+    Ok(())
 }
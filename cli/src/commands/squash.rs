@@ -33,10 +33,12 @@ use crate::cli_util::CommandHelper;
 use crate::cli_util::DiffSelector;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandTransaction;
+use crate::cli_util::check_path_args_match_changes;
 use crate::cli_util::compute_commit_location;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::commands::workspace::metadata::WorkspaceMetadata;
 use crate::complete;
 use crate::description_util::add_trailers;
 use crate::description_util::combine_messages_for_editing;
@@ -173,9 +175,29 @@ pub(crate) struct SquashArgs {
     )]
     paths: Vec<String>,
 
+    /// Fail if any of the given paths doesn't match a changed file
+    ///
+    /// By default, such paths are only reported as a warning. This can also
+    /// be enabled by the `ui.strict-filesets` setting.
+    #[arg(long)]
+    strict_paths: bool,
+
     /// The source revision will not be abandoned
     #[arg(long, short)]
     keep_emptied: bool,
+
+    /// Do not ask for confirmation if the revisions match more commits than
+    /// `ui.large-revset-warning-threshold`
+    #[arg(long)]
+    no_limit: bool,
+
+    /// Rewrite the working-copy commits of other workspaces even if they have
+    /// changes which haven't been snapshotted yet
+    ///
+    /// Those changes may be overwritten when the other workspaces are updated
+    /// by `jj workspace update-stale`.
+    #[arg(long)]
+    ignore_unsnapshotted_workspaces: bool,
 }
 
 #[instrument(skip_all)]
@@ -228,6 +250,9 @@ pub(crate) fn cmd_squash(
     };
 
     workspace_command.check_rewritable(sources.iter().chain(&pre_existing_destination).ids())?;
+    if !args.from.is_empty() {
+        workspace_command.check_large_revset(ui, sources.iter().ids(), args.no_limit)?;
+    }
 
     // prepare the tx description before possibly rebasing the source commits
     let source_ids: Vec<_> = sources.iter().ids().collect();
@@ -301,6 +326,17 @@ pub(crate) fn cmd_squash(
     let text_editor = tx.base_workspace_helper().text_editor()?;
     let description = SquashedDescription::from_args(args);
 
+    let source_trees: Vec<_> = sources
+        .iter()
+        .map(|source| Ok::<_, CommandError>((source.parent_tree(tx.repo())?, source.tree()?)))
+        .try_collect()?;
+    check_path_args_match_changes(
+        ui,
+        tx.base_workspace_helper(),
+        &args.paths,
+        source_trees.iter().map(|(from, to)| (from, to)),
+        args.strict_paths,
+    )?;
     let source_commits = select_diff(&tx, &sources, &destination, &matcher, &diff_selector)?;
     if let Some(squashed) = rewrite::squash_commits(
         tx.repo_mut(),
@@ -366,6 +402,9 @@ pub(crate) fn cmd_squash(
         }
         let commit = commit_builder.write(tx.repo_mut())?;
         let num_rebased = tx.repo_mut().rebase_descendants()?;
+        if !args.ignore_unsnapshotted_workspaces {
+            check_other_workspaces_snapshotted(ui, command, &tx)?;
+        }
         if let Some(mut formatter) = ui.status_formatter() {
             if insert_destination_commit {
                 write!(formatter, "Created new commit ")?;
@@ -376,6 +415,7 @@ pub(crate) fn cmd_squash(
                 writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
             }
         }
+        warn_other_workspaces_rewritten(ui, &tx, &destination)?;
     } else {
         if diff_selector.is_interactive() {
             return Err(user_error("No changes selected"));
@@ -412,6 +452,102 @@ pub(crate) fn cmd_squash(
     Ok(())
 }
 
+/// Fails if other workspaces whose working-copy commits are rewritten by `tx`
+/// have changes which haven't been snapshotted yet.
+///
+/// Workspaces whose root directory isn't known can't be checked.
+fn check_other_workspaces_snapshotted(
+    ui: &Ui,
+    command: &CommandHelper,
+    tx: &WorkspaceCommandTransaction,
+) -> Result<(), CommandError> {
+    let workspace_command = tx.base_workspace_helper();
+    let current_name = workspace_command.workspace_name();
+    let new_view = tx.repo().view();
+    let rewritten_names = tx
+        .base_repo()
+        .view()
+        .wc_commit_ids()
+        .iter()
+        .filter(|&(name, id)| name != current_name && new_view.get_wc_commit_id(name) != Some(id))
+        .map(|(name, _)| name)
+        .collect_vec();
+    if rewritten_names.is_empty() {
+        return Ok(());
+    }
+    let metadata = WorkspaceMetadata::load(workspace_command.repo_path())?;
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let options =
+        workspace_command.snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)?;
+    let mut unsnapshotted_names = vec![];
+    for name in rewritten_names {
+        let Some(root) = metadata.root(name) else {
+            continue;
+        };
+        // The workspace may have been deleted without being forgotten.
+        let Ok(mut workspace) = command.load_workspace_at(&root, command.settings()) else {
+            continue;
+        };
+        if workspace.workspace_name() != name {
+            continue;
+        }
+        // The snapshot isn't saved, so the working copy is left as it was.
+        let mut locked_ws = workspace.start_working_copy_mutation()?;
+        let old_tree_id = locked_ws.locked_wc().old_tree_id().clone();
+        let (new_tree_id, _stats) = locked_ws.locked_wc().snapshot(&options)?;
+        if new_tree_id != old_tree_id {
+            unsnapshotted_names.push(name.as_symbol().to_string());
+        }
+    }
+    if unsnapshotted_names.is_empty() {
+        return Ok(());
+    }
+    Err(user_error_with_hint(
+        format!(
+            "Workspace {} has changes which haven't been snapshotted",
+            unsnapshotted_names.join(", ")
+        ),
+        "Run a command such as `jj status` in that workspace to snapshot them, or use \
+         `--ignore-unsnapshotted-workspaces` to squash anyway.",
+    ))
+}
+
+/// Warns about other workspaces whose working-copy commit was the squash
+/// destination.
+///
+/// The rewritten commit is checked out in those workspaces by the transaction,
+/// but their files on disk can't be updated from here. Changes made there
+/// since their last snapshot may conflict with the squashed changes.
+fn warn_other_workspaces_rewritten(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    destination: &Commit,
+) -> Result<(), CommandError> {
+    let current_name = tx.base_workspace_helper().workspace_name();
+    let names = tx
+        .base_repo()
+        .view()
+        .wc_commit_ids()
+        .iter()
+        .filter(|&(name, id)| name != current_name && id == destination.id())
+        .map(|(name, _)| name.as_symbol().to_string())
+        .collect_vec();
+    if names.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        ui.warning_default(),
+        "Squashed into the working-copy commit of workspace {}",
+        names.join(", ")
+    )?;
+    writeln!(
+        ui.hint_default(),
+        "Run `jj workspace update-stale` in that workspace to update its files. Changes made \
+         there since its last snapshot may conflict with the squashed changes."
+    )?;
+    Ok(())
+}
+
 enum SquashedDescription {
     // Use this exact description.
     Exact(String),
@@ -15,7 +15,7 @@
 use itertools::Itertools as _;
 use jj_lib::copies::CopyRecords;
 use jj_lib::merged_tree::MergedTree;
-use jj_lib::repo::Repo as _;
+use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetExpression;
@@ -29,6 +29,7 @@ use crate::cli_util::print_snapshot_stats;
 use crate::command_error::CommandError;
 use crate::diff_util::DiffFormat;
 use crate::diff_util::get_copy_records;
+use crate::formatter::Formatter;
 use crate::ui::Ui;
 
 /// Show high-level repo status [default alias: st]
@@ -181,6 +182,16 @@ pub(crate) fn cmd_status(
         writeln!(formatter, "No working copy")?;
     }
 
+    print_conflicted_bookmarks(formatter, repo.as_ref())?;
+
+    Ok(())
+}
+
+/// Prints warnings about local and remote bookmarks that have conflicts.
+pub(crate) fn print_conflicted_bookmarks(
+    formatter: &mut dyn Formatter,
+    repo: &dyn Repo,
+) -> Result<(), CommandError> {
     let conflicted_local_bookmarks = repo
         .view()
         .local_bookmarks()
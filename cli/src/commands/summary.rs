@@ -0,0 +1,142 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::commit::Commit;
+use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use super::log::LogGraphOptions;
+use super::log::add_commit_node;
+use super::status::print_conflicted_bookmarks;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::LogContentFormat;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::graphlog::GraphStyle;
+use crate::graphlog::get_graphlog;
+use crate::templater::TemplateRenderer;
+use crate::ui::Ui;
+
+/// Show a compact overview of the repo
+///
+/// This includes:
+///
+///  * The working copy commit and its parents
+///  * The graph of revisions between `trunk()` and the working copy, rendered
+///    with the `templates.log` template
+///  * [Conflicted bookmarks]
+///
+/// It is meant to be used as the default command by running `jj config set
+/// --user ui.default-command summary`.
+///
+/// [Conflicted bookmarks]:
+///     https://jj-vcs.github.io/jj/latest/bookmarks/#conflicts
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct SummaryArgs {}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_summary(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    SummaryArgs {}: &SummaryArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let settings = workspace_command.settings();
+    let repo = workspace_command.repo();
+    let maybe_wc_commit = workspace_command
+        .get_wc_commit_id()
+        .map(|id| repo.store().get_commit(id))
+        .transpose()?;
+
+    let revset = if maybe_wc_commit.is_some() {
+        let expression =
+            workspace_command.parse_revset(ui, &RevisionArg::from("trunk()..@".to_owned()))?;
+        Some(expression.evaluate()?)
+    } else {
+        None
+    };
+    let graph_style = GraphStyle::from_settings(settings)?;
+    let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
+    let with_content_format = LogContentFormat::new(ui, settings)?;
+    let template: TemplateRenderer<Commit>;
+    let node_template: TemplateRenderer<Option<Commit>>;
+    {
+        let language = workspace_command.commit_template_language();
+        template = workspace_command
+            .parse_template(ui, &language, &settings.get_string("templates.log")?)?
+            .labeled(["log", "commit"]);
+        node_template = workspace_command
+            .parse_template(ui, &language, &settings.get_string("templates.log_node")?)?
+            .labeled(["log", "commit", "node"]);
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let formatter = formatter.as_mut();
+
+    if let Some(wc_commit) = &maybe_wc_commit {
+        let template = workspace_command.commit_summary_template();
+        write!(formatter, "Working copy  (@) : ")?;
+        template.format(wc_commit, formatter)?;
+        writeln!(formatter)?;
+        for parent in wc_commit.parents() {
+            let parent = parent?;
+            //                "Working copy  (@) : "
+            write!(formatter, "Parent commit (@-): ")?;
+            template.format(&parent, formatter)?;
+            writeln!(formatter)?;
+        }
+    } else {
+        writeln!(formatter, "No working copy")?;
+    }
+
+    if let Some(revset) = &revset {
+        let mut nodes = TopoGroupedGraphIterator::new(revset.iter_graph(), |id| id).peekable();
+        if nodes.peek().is_some() {
+            writeln!(formatter)?;
+            let mut raw_output = formatter.raw()?;
+            let mut graph = get_graphlog(graph_style, raw_output.as_mut());
+            let options = LogGraphOptions {
+                with_content_format: &with_content_format,
+                node_template: &node_template,
+                use_elided_nodes,
+            };
+            for node in nodes {
+                let (commit_id, edges) = node?;
+                let commit = repo.store().get_commit(&commit_id)?;
+                add_commit_node(
+                    ui,
+                    graph.as_mut(),
+                    &options,
+                    commit,
+                    edges,
+                    |buffer, commit, within_graph| {
+                        within_graph
+                            .write(ui.new_formatter(&mut *buffer).as_mut(), |formatter| {
+                                template.format(commit, formatter)
+                            })?;
+                        if !buffer.ends_with(b"\n") {
+                            buffer.push(b'\n');
+                        }
+                        Ok(())
+                    },
+                )?;
+            }
+        }
+    }
+
+    print_conflicted_bookmarks(formatter, repo.as_ref())?;
+    Ok(())
+}
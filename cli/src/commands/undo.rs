@@ -26,6 +26,8 @@ use crate::commands::operation::RevertWhatToRestore;
 use crate::commands::operation::revert::OperationRevertArgs;
 use crate::commands::operation::revert::cmd_op_revert;
 use crate::commands::operation::view_with_desired_portions_restored;
+use crate::commands::operation::warn_about_affected_workspaces;
+use crate::commands::operation::warn_about_unconfigured_remotes;
 use crate::complete;
 use crate::ui::Ui;
 
@@ -190,6 +192,8 @@ pub fn cmd_undo(ui: &mut Ui, command: &CommandHelper, args: &UndoArgs) -> Result
         template.format(&op_to_restore, formatter.as_mut())?;
         writeln!(formatter)?;
     }
+    warn_about_affected_workspaces(ui, &tx)?;
+    warn_about_unconfigured_remotes(ui, &tx)?;
     tx.finish(
         ui,
         format!("{UNDO_OP_DESC_PREFIX}{}", op_to_restore.id().hex()),
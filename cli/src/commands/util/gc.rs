@@ -12,21 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::slice;
+use std::io::Write as _;
 use std::time::Duration;
 use std::time::SystemTime;
 
-use jj_lib::repo::Repo as _;
-
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::gc_util;
+use crate::gc_util::AutoGcSettings;
 use crate::ui::Ui;
 
 /// Run backend-dependent garbage collection.
 ///
 /// To garbage-collect old operations and the commits/objects referenced by
 /// them, run `jj op abandon ..<some old operation>` before `jj util gc`.
+///
+/// Garbage collection can also run automatically after commands that create
+/// an operation. See the `gc.auto` config option.
+///
+/// The cached file contents of the working copy (see the
+/// `working-copy.use-reflinks` config option) which aren't in the
+/// working-copy commit are removed as well.
 #[derive(clap::Args, Clone, Debug)]
 pub struct UtilGcArgs {
     /// Time threshold
@@ -39,6 +46,10 @@ pub struct UtilGcArgs {
     /// release.
     #[arg(long)]
     expire: Option<String>,
+    /// Only run if `gc.auto-interval` has elapsed since the last automatic
+    /// garbage collection
+    #[arg(long, conflicts_with = "expire")]
+    auto: bool,
 }
 
 pub fn cmd_util_gc(
@@ -52,15 +63,24 @@ pub fn cmd_util_gc(
         ));
     }
     let keep_newer = match args.expire.as_deref() {
-        None => SystemTime::now() - Duration::from_secs(14 * 86400),
+        None => SystemTime::now() - gc_util::DEFAULT_GC_EXPIRE,
         Some("now") => SystemTime::now() - Duration::ZERO,
         _ => return Err(user_error("--expire only accepts 'now'")),
     };
-    let workspace_command = command.workspace_helper(ui)?;
+    let mut workspace_command = command.workspace_helper(ui)?;
 
-    let repo = workspace_command.repo();
-    repo.op_store()
-        .gc(slice::from_ref(repo.op_id()), keep_newer)?;
-    repo.store().gc(repo.index(), keep_newer)?;
+    if args.auto {
+        let gc_settings = AutoGcSettings::from_settings(command.settings())?;
+        let ran = gc_util::run_gc_if_due(&mut workspace_command, gc_settings.interval)?;
+        if !ran {
+            writeln!(
+                ui.status(),
+                "Nothing to do, garbage collection ran recently."
+            )?;
+        }
+        return Ok(());
+    }
+    gc_util::run_gc(workspace_command.repo(), keep_newer)?;
+    gc_util::prune_working_copy_cache(&workspace_command)?;
     Ok(())
 }
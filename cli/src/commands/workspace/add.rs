@@ -15,9 +15,11 @@
 use std::fs;
 
 use itertools::Itertools as _;
+use jj_lib::backend::Signature;
 use jj_lib::commit::CommitIteratorExt as _;
 use jj_lib::file_util;
 use jj_lib::file_util::IoResultExt as _;
+use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::merge_commit_trees;
@@ -27,9 +29,13 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::commands::workspace::metadata::WorkspaceMetadata;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// How to handle sparse patterns when creating a new workspace.
@@ -47,6 +53,9 @@ enum SparseInheritance {
 ///
 /// By default, the new workspace inherits the sparse patterns of the current
 /// workspace. You can override this with the `--sparse-patterns` option.
+///
+/// The description given by `--message` is stored in the repo and shown by
+/// `jj workspace list`. It is removed when the workspace is forgotten.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceAddArgs {
     /// Where to create the new workspace
@@ -58,6 +67,19 @@ pub struct WorkspaceAddArgs {
     /// directory.
     #[arg(long)]
     name: Option<WorkspaceNameBuf>,
+    /// Generate the workspace name from the given template
+    ///
+    /// The template is evaluated with the [`Signature`] of the current user
+    /// at the current time, e.g. `'ci-' ++ email.local() ++ '-' ++
+    /// timestamp.format('%Y%m%d-%H%M%S')`.
+    ///
+    /// [`Signature`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/#signature-type
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "name")]
+    name_template: Option<String>,
+    /// A description of the purpose of the workspace
+    #[arg(long, short, value_name = "MESSAGE")]
+    message: Option<String>,
     /// A list of parent revisions for the working-copy commit of the newly
     /// created workspace. You may specify nothing, or any number of parents.
     ///
@@ -87,11 +109,11 @@ pub fn cmd_workspace_add(
     let destination_path = command.cwd().join(&args.destination);
     if destination_path.exists() {
         return Err(user_error("Workspace already exists"));
-    } else {
-        fs::create_dir(&destination_path).context(&destination_path)?;
     }
     let workspace_name = if let Some(name) = &args.name {
         name.to_owned()
+    } else if let Some(template_text) = &args.name_template {
+        generate_workspace_name(ui, &old_workspace_command, template_text)?
     } else {
         let file_name = destination_path.file_name().unwrap();
         file_name
@@ -99,6 +121,7 @@ pub fn cmd_workspace_add(
             .ok_or_else(|| user_error("Destination path is not valid UTF-8"))?
             .into()
     };
+    validate_workspace_name(&workspace_name)?;
     let repo = old_workspace_command.repo();
     if repo.view().get_wc_commit_id(&workspace_name).is_some() {
         return Err(user_error(format!(
@@ -106,6 +129,7 @@ pub fn cmd_workspace_add(
             name = workspace_name.as_symbol()
         )));
     }
+    fs::create_dir(&destination_path).context(&destination_path)?;
 
     let working_copy_factory = command.get_working_copy_factory()?;
     let repo_path = old_workspace_command.repo_path();
@@ -134,6 +158,13 @@ pub fn cmd_workspace_add(
         )?;
     }
 
+    let mut metadata = WorkspaceMetadata::load(old_workspace_command.repo_path())?;
+    metadata.set_root(&workspace_name, new_workspace.workspace_root().to_owned());
+    if let Some(description) = &args.message {
+        metadata.set_description(&workspace_name, description.clone());
+    }
+    metadata.save()?;
+
     let mut new_workspace_command = command.for_workable_repo(ui, new_workspace, repo)?;
 
     let sparsity = match args.sparse_patterns {
@@ -201,3 +232,31 @@ pub fn cmd_workspace_add(
     )?;
     Ok(())
 }
+
+fn generate_workspace_name(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    template_text: &str,
+) -> Result<WorkspaceNameBuf, CommandError> {
+    let language = workspace_command.commit_template_language();
+    let template: TemplateRenderer<Signature> =
+        workspace_command.parse_template(ui, &language, template_text)?;
+    let output = template.format_plain_text(&workspace_command.settings().signature());
+    let name = String::from_utf8(output).map_err(|err| {
+        user_error_with_message("Invalid character in workspace name", err.utf8_error())
+    })?;
+    Ok(name.into())
+}
+
+fn validate_workspace_name(name: &WorkspaceName) -> Result<(), CommandError> {
+    if name.as_str().trim().is_empty() {
+        Err(user_error("Workspace name cannot be empty"))
+    } else if name.as_str().contains(char::is_control) {
+        Err(user_error(format!(
+            "Workspace name cannot contain control characters: {name}",
+            name = name.as_symbol()
+        )))
+    } else {
+        Ok(())
+    }
+}
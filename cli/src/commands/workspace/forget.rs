@@ -20,13 +20,15 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::commands::workspace::metadata::WorkspaceMetadata;
 use crate::complete;
 use crate::ui::Ui;
 
 /// Stop tracking a workspace's working-copy commit in the repo
 ///
 /// The workspace will not be touched on disk. It can be deleted from disk
-/// before or after running this command.
+/// before or after running this command. The workspace description is removed
+/// from the repo.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceForgetArgs {
     /// Names of the workspaces to forget. By default, forgets only the current
@@ -75,5 +77,14 @@ pub fn cmd_workspace_forget(
     };
 
     tx.finish(ui, description)?;
+
+    let mut metadata = WorkspaceMetadata::load(workspace_command.repo_path())?;
+    let mut metadata_changed = false;
+    for ws in &wss {
+        metadata_changed |= metadata.remove(ws);
+    }
+    if metadata_changed {
+        metadata.save()?;
+    }
     Ok(())
 }
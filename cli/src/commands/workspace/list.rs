@@ -17,6 +17,7 @@ use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
+use crate::commands::workspace::metadata::WorkspaceMetadata;
 use crate::commit_templater::WorkspaceRef;
 use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
@@ -63,11 +64,13 @@ pub fn cmd_workspace_list(
     };
 
     let repo = workspace_command.repo();
+    let metadata = WorkspaceMetadata::load(workspace_command.repo_path())?;
     let mut formatter = ui.stdout_formatter();
 
     for (name, wc_commit_id) in repo.view().wc_commit_ids() {
         let commit = repo.store().get_commit(wc_commit_id)?;
-        let ws_ref = WorkspaceRef::new(name.clone(), commit);
+        let description = metadata.description(name).map(ToOwned::to_owned);
+        let ws_ref = WorkspaceRef::new(name.clone(), commit).with_description(description);
 
         template.format(&ws_ref, formatter.as_mut())?;
     }
@@ -0,0 +1,157 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Repo-local metadata of workspaces, such as descriptions given by `jj
+//! workspace add --message`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::file_util::persist_temp_file;
+use jj_lib::ref_name::WorkspaceName;
+use jj_lib::ref_name::WorkspaceNameBuf;
+use serde::Deserialize;
+use serde::Serialize;
+use tempfile::NamedTempFile;
+
+use crate::command_error::CommandError;
+use crate::command_error::internal_error_with_message;
+
+const METADATA_FILE_NAME: &str = "workspace_metadata.toml";
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct WorkspaceMetadataEntry {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    root: Option<PathBuf>,
+}
+
+/// Metadata of all workspaces, stored in `.jj/repo/workspace_metadata.toml`.
+#[derive(Clone, Debug)]
+pub(crate) struct WorkspaceMetadata {
+    path: PathBuf,
+    workspaces: BTreeMap<WorkspaceNameBuf, WorkspaceMetadataEntry>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct WorkspaceMetadataFile {
+    #[serde(default)]
+    workspaces: BTreeMap<String, WorkspaceMetadataEntry>,
+}
+
+impl WorkspaceMetadata {
+    /// Loads metadata from the `repo_path` directory. Missing file is
+    /// considered empty.
+    pub fn load(repo_path: &Path) -> Result<Self, CommandError> {
+        let path = repo_path.join(METADATA_FILE_NAME);
+        let file: WorkspaceMetadataFile = match fs::read_to_string(&path) {
+            Ok(text) => toml_edit::de::from_str(&text).map_err(|err| {
+                internal_error_with_message("Failed to parse workspace metadata", err)
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Default::default(),
+            Err(err) => Err(err).context(&path)?,
+        };
+        let workspaces = file
+            .workspaces
+            .into_iter()
+            .map(|(name, entry)| (name.into(), entry))
+            .collect();
+        Ok(Self { path, workspaces })
+    }
+
+    /// Writes metadata back to the file. The file is replaced atomically so
+    /// that it's never left partially written.
+    pub fn save(&self) -> Result<(), CommandError> {
+        let file = WorkspaceMetadataFile {
+            workspaces: self
+                .workspaces
+                .iter()
+                .map(|(name, entry)| (name.as_str().to_owned(), entry.clone()))
+                .collect(),
+        };
+        let text = toml_edit::ser::to_string_pretty(&file).map_err(|err| {
+            internal_error_with_message("Failed to serialize workspace metadata", err)
+        })?;
+        let dir = self.path.parent().unwrap();
+        let mut temp_file = NamedTempFile::new_in(dir).context(dir)?;
+        temp_file
+            .write_all(text.as_bytes())
+            .context(temp_file.path())?;
+        persist_temp_file(temp_file, &self.path).context(&self.path)?;
+        Ok(())
+    }
+
+    /// Returns the description of the workspace, or `None` if not set.
+    pub fn description(&self, name: &WorkspaceName) -> Option<&str> {
+        let entry = self.workspaces.get(name)?;
+        (!entry.description.is_empty()).then_some(&entry.description)
+    }
+
+    /// Sets the description of the workspace.
+    pub fn set_description(&mut self, name: &WorkspaceName, description: String) {
+        let entry = self.workspaces.entry(name.to_owned()).or_default();
+        entry.description = description;
+    }
+
+    /// Returns the root directory of the workspace, or `None` if unknown.
+    ///
+    /// Only workspaces added by `jj workspace add` are recorded. Otherwise,
+    /// the workspace containing the repo directory is returned, which the
+    /// caller should check is the named workspace.
+    pub fn root(&self, name: &WorkspaceName) -> Option<PathBuf> {
+        if let Some(root) = self
+            .workspaces
+            .get(name)
+            .and_then(|entry| entry.root.clone())
+        {
+            return Some(root);
+        }
+        let repo_dir = self.path.parent()?;
+        let jj_dir = repo_dir.parent()?;
+        if repo_dir.file_name()? == "repo" && jj_dir.file_name()? == ".jj" {
+            jj_dir.parent().map(ToOwned::to_owned)
+        } else {
+            None
+        }
+    }
+
+    /// Records the root directory of the workspace.
+    pub fn set_root(&mut self, name: &WorkspaceName, root: PathBuf) {
+        let entry = self.workspaces.entry(name.to_owned()).or_default();
+        entry.root = Some(root);
+    }
+
+    /// Moves metadata of the workspace to the `new_name`. Returns true if there
+    /// was metadata to move.
+    pub fn rename(&mut self, old_name: &WorkspaceName, new_name: &WorkspaceName) -> bool {
+        if let Some(entry) = self.workspaces.remove(old_name) {
+            self.workspaces.insert(new_name.to_owned(), entry);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes metadata of the workspace. Returns true if there was metadata
+    /// to remove.
+    pub fn remove(&mut self, name: &WorkspaceName) -> bool {
+        self.workspaces.remove(name).is_some()
+    }
+}
@@ -15,6 +15,7 @@
 mod add;
 mod forget;
 mod list;
+pub(crate) mod metadata;
 mod rename;
 mod root;
 mod update_stale;
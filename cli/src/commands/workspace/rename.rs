@@ -18,6 +18,7 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::command_error::user_error;
+use crate::commands::workspace::metadata::WorkspaceMetadata;
 use crate::ui::Ui;
 
 /// Renames the current workspace
@@ -72,5 +73,9 @@ pub fn cmd_workspace_rename(
     ))?;
     locked_ws.finish(repo.op_id().clone())?;
 
+    let mut metadata = WorkspaceMetadata::load(workspace_command.repo_path())?;
+    if metadata.rename(&old_name, new_name) {
+        metadata.save()?;
+    }
     Ok(())
 }
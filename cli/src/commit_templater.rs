@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::cmp::max;
 use std::collections::HashMap;
@@ -29,6 +30,7 @@ use itertools::Itertools as _;
 use jj_lib::backend::BackendResult;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::Timestamp;
 use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::conflicts;
@@ -94,6 +96,7 @@ use crate::template_builder::TemplateLanguage;
 use crate::template_builder::expect_stringify_expression;
 use crate::template_builder::merge_fn_map;
 use crate::template_parser;
+use crate::template_parser::ExpressionKind;
 use crate::template_parser::ExpressionNode;
 use crate::template_parser::FunctionCallNode;
 use crate::template_parser::TemplateDiagnostics;
@@ -550,6 +553,14 @@ impl<'repo> CoreTemplatePropertyVar<'repo> for CommitTemplatePropertyKind<'repo>
         }
     }
 
+    fn try_into_timestamp(self) -> Option<BoxedTemplateProperty<'repo, Timestamp>> {
+        match self {
+            Self::Core(property) => property.try_into_timestamp(),
+            Self::Operation(property) => property.try_into_timestamp(),
+            _ => None,
+        }
+    }
+
     fn try_into_stringify(self) -> Option<BoxedTemplateProperty<'repo, String>> {
         match self {
             Self::Core(property) => property.try_into_stringify(),
@@ -910,6 +921,7 @@ pub struct CommitKeywordCache<'repo> {
     tags_index: OnceCell<Rc<CommitRefsIndex>>,
     git_refs_index: OnceCell<Rc<CommitRefsIndex>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
+    contained_in_fns: RefCell<HashMap<String, Rc<RevsetContainingFn<'repo>>>>,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
@@ -942,6 +954,23 @@ impl<'repo> CommitKeywordCache<'repo> {
             Ok(revset.containing_fn().into())
         })
     }
+
+    /// Returns containing function for the `revset` text, evaluating it only
+    /// once even if the same revset is used in multiple places.
+    pub fn contained_in_fn(
+        &self,
+        revset: &str,
+        evaluate: impl FnOnce() -> TemplateParseResult<Box<dyn Revset + 'repo>>,
+    ) -> TemplateParseResult<Rc<RevsetContainingFn<'repo>>> {
+        if let Some(is_contained) = self.contained_in_fns.borrow().get(revset) {
+            return Ok(is_contained.clone());
+        }
+        let is_contained: Rc<RevsetContainingFn<'repo>> = evaluate()?.containing_fn().into();
+        self.contained_in_fns
+            .borrow_mut()
+            .insert(revset.to_owned(), is_contained.clone());
+        Ok(is_contained)
+    }
 }
 
 fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Commit> {
@@ -965,6 +994,74 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "notes",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property = self_property.map(|commit| {
+                repo.view()
+                    .notes(commit.change_id())
+                    .map(|(key, value)| Trailer {
+                        key: key.to_owned(),
+                        value: value.to_owned(),
+                    })
+                    .collect_vec()
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "note",
+        |language, diagnostics, build_ctx, self_property, function| {
+            let [key_node] = function.expect_exact_arguments()?;
+            let key_property =
+                expect_stringify_expression(language, diagnostics, build_ctx, key_node)?;
+            let repo = language.repo;
+            let out_property = (self_property, key_property).map(|(commit, key)| {
+                repo.view()
+                    .get_note(commit.change_id(), &key)
+                    .unwrap_or_default()
+                    .to_owned()
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "git_notes",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property = self_property.map(|commit| {
+                repo.view()
+                    .notes(commit.change_id())
+                    .filter_map(|(key, value)| {
+                        let name = key.strip_prefix(jj_lib::git::GIT_NOTES_KEY_PREFIX)?;
+                        Some(Trailer {
+                            key: name.to_owned(),
+                            value: value.to_owned(),
+                        })
+                    })
+                    .collect_vec()
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "change_names",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property = self_property.map(|commit| {
+                repo.view()
+                    .change_names()
+                    .filter(|&(_, change_id)| change_id == commit.change_id())
+                    .map(|(name, _)| name.to_owned())
+                    .collect_vec()
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "change_id",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -1174,8 +1271,9 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             let is_contained =
                 template_parser::catch_aliases(diagnostics, revset_node, |diagnostics, node| {
                     let text = template_parser::expect_string_literal(node)?;
-                    let revset = evaluate_user_revset(language, diagnostics, node.span, text)?;
-                    Ok(revset.containing_fn())
+                    language.keyword_cache.contained_in_fn(text, || {
+                        evaluate_user_revset(language, diagnostics, node.span, text)
+                    })
                 })?;
 
             let out_property = self_property.and_then(move |commit| Ok(is_contained(commit.id())?));
@@ -1254,6 +1352,53 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
     map
 }
 
+/// Returns true if the commit template only uses the commit and change ids,
+/// which are available in the index. Such template can be rendered without
+/// loading commit objects.
+///
+/// The analysis is conservative. Any keyword, function, or method not known
+/// to be index-only makes this return false.
+pub fn is_index_only_template(node: &ExpressionNode) -> bool {
+    const KEYWORDS: &[&str] = &["change_id", "commit_id"];
+    const FUNCTIONS: &[&str] = &["coalesce", "concat", "if", "indent", "label", "separate"];
+    const METHODS: &[&str] = &[
+        "hex",
+        "lower",
+        "normal_hex",
+        "prefix",
+        "rest",
+        "short",
+        "shortest",
+        "upper",
+    ];
+    let is_index_only_function = |function: &FunctionCallNode| {
+        function.args.iter().all(is_index_only_template)
+            && function
+                .keyword_args
+                .iter()
+                .all(|arg| is_index_only_template(&arg.value))
+    };
+    match &node.kind {
+        ExpressionKind::Identifier(name) => KEYWORDS.contains(name),
+        ExpressionKind::Boolean(_) | ExpressionKind::Integer(_) | ExpressionKind::String(_) => true,
+        ExpressionKind::Unary(_, arg) => is_index_only_template(arg),
+        ExpressionKind::Binary(_, lhs, rhs) => {
+            is_index_only_template(lhs) && is_index_only_template(rhs)
+        }
+        ExpressionKind::Concat(nodes) => nodes.iter().all(is_index_only_template),
+        ExpressionKind::FunctionCall(function) => {
+            FUNCTIONS.contains(&function.name) && is_index_only_function(function)
+        }
+        ExpressionKind::MethodCall(method) => {
+            METHODS.contains(&method.function.name)
+                && is_index_only_template(&method.object)
+                && is_index_only_function(&method.function)
+        }
+        ExpressionKind::Lambda(_) => false,
+        ExpressionKind::AliasExpanded(_, node) => is_index_only_template(node),
+    }
+}
+
 fn extract_working_copies(repo: &dyn Repo, commit: &Commit) -> Vec<WorkspaceRef> {
     if repo.view().wc_commit_ids().len() <= 1 {
         // No non-default working copies, return empty list.
@@ -1374,6 +1519,9 @@ pub struct CommitRef {
     /// ref is synchronized with the local.
     #[serde(skip)] // internal state used mainly for Template impl
     synced: bool,
+    /// Description of the local bookmark.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
 }
 
 #[derive(Debug)]
@@ -1396,6 +1544,17 @@ impl CommitRef {
         name: impl Into<String>,
         target: RefTarget,
         remote_refs: impl IntoIterator<Item = &'a RemoteRef>,
+    ) -> Rc<Self> {
+        Self::local_with_description(name, target, remote_refs, None)
+    }
+
+    /// Creates local bookmark representation with the given description which
+    /// might track some of the `remote_refs`.
+    pub fn local_with_description<'a>(
+        name: impl Into<String>,
+        target: RefTarget,
+        remote_refs: impl IntoIterator<Item = &'a RemoteRef>,
+        description: Option<&str>,
     ) -> Rc<Self> {
         let synced = remote_refs
             .into_iter()
@@ -1406,6 +1565,7 @@ impl CommitRef {
             target,
             tracking_ref: None,
             synced,
+            description: description.map(str::to_owned),
         })
     }
 
@@ -1441,6 +1601,7 @@ impl CommitRef {
             target: remote_ref.target,
             tracking_ref,
             synced,
+            description: None,
         })
     }
 
@@ -1456,6 +1617,7 @@ impl CommitRef {
             target,
             tracking_ref: None,
             synced: false, // has no local counterpart
+            description: None,
         })
     }
 
@@ -1576,12 +1738,25 @@ pub struct WorkspaceRef {
     name: WorkspaceNameBuf,
     /// Working-copy commit of this workspace.
     target: Commit,
+    /// Description of this workspace if loaded from the workspace metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
 }
 
 impl WorkspaceRef {
     /// Creates a new workspace reference from the workspace name and commit.
     pub fn new(name: WorkspaceNameBuf, target: Commit) -> Self {
-        Self { name, target }
+        Self {
+            name,
+            target,
+            description: None,
+        }
+    }
+
+    /// Sets the description of this workspace.
+    pub fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
     }
 
     /// Returns the workspace name symbol.
@@ -1625,6 +1800,14 @@ fn builtin_workspace_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'rep
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "description",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|ws_ref| ws_ref.description.unwrap_or_default());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
@@ -1659,6 +1842,15 @@ fn builtin_commit_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "description",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.map(|commit_ref| commit_ref.description.clone().unwrap_or_default());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "present",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -1777,10 +1969,11 @@ fn build_bookmarks_index(repo: &dyn Repo) -> CommitRefsIndex {
         let local_target = bookmark_target.local_target;
         let remote_refs = bookmark_target.remote_refs;
         if local_target.is_present() {
-            let commit_ref = CommitRef::local(
+            let commit_ref = CommitRef::local_with_description(
                 bookmark_name,
                 local_target.clone(),
                 remote_refs.iter().map(|&(_, remote_ref)| remote_ref),
+                repo.view().get_local_bookmark_description(bookmark_name),
             );
             index.insert(local_target.added_ids(), commit_ref);
         }
@@ -2652,6 +2845,7 @@ fn builtin_trailer_list_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo
 
 #[cfg(test)]
 mod tests {
+    use std::cell::Cell;
     use std::path::Path;
     use std::sync::Arc;
 
@@ -2791,6 +2985,26 @@ mod tests {
         UserSettings::from_config(config).unwrap()
     }
 
+    #[test]
+    fn test_is_index_only_template() {
+        let aliases_map = TemplateAliasesMap::new();
+        let is_index_only = |text: &str| {
+            let node = template_parser::parse(text, &aliases_map).unwrap();
+            is_index_only_template(&node)
+        };
+        assert!(is_index_only(r#"commit_id ++ "\n""#));
+        assert!(is_index_only(
+            r#"separate(" ", commit_id.short(), change_id.shortest(8).prefix())"#
+        ));
+        assert!(is_index_only(r#"if(true, label("x", change_id.hex()))"#));
+        assert!(!is_index_only("description"));
+        assert!(!is_index_only(r#"commit_id ++ author"#));
+        assert!(!is_index_only("self.commit_id()"));
+        assert!(!is_index_only("commit_id.short().len()"));
+        assert!(!is_index_only("parents.map(|c| c.commit_id())"));
+        assert!(!is_index_only(r#"if(empty, commit_id)"#));
+    }
+
     #[test]
     fn test_ref_symbol_type() {
         let mut env = CommitTemplateTestEnv::init();
@@ -2946,4 +3160,28 @@ mod tests {
         insta::assert_snapshot!(
             env.render_ok("json(self)", &id), @r#"{"prefix":"012","rest":"3abcdef"}"#);
     }
+
+    #[test]
+    fn test_contained_in_fn_evaluated_once() {
+        let env = CommitTemplateTestEnv::init();
+        let repo = env.test_workspace.repo.as_ref();
+        let cache = CommitKeywordCache::default();
+        let num_evaluations = Cell::new(0);
+        let evaluate = || {
+            num_evaluations.set(num_evaluations.get() + 1);
+            Ok(RevsetExpression::root().evaluate(repo).unwrap())
+        };
+        let root_id = repo.store().root_commit_id();
+
+        let is_contained = cache.contained_in_fn("root()", evaluate).unwrap();
+        assert!(is_contained(root_id).unwrap());
+        assert_eq!(num_evaluations.get(), 1);
+        let is_contained = cache.contained_in_fn("root()", evaluate).unwrap();
+        assert!(is_contained(root_id).unwrap());
+        assert_eq!(num_evaluations.get(), 1);
+
+        // Different revset text is evaluated separately
+        cache.contained_in_fn("root() ", evaluate).unwrap();
+        assert_eq!(num_evaluations.get(), 2);
+    }
 }
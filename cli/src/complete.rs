@@ -191,6 +191,16 @@ pub fn git_remotes() -> Vec<CompletionCandidate> {
     })
 }
 
+pub fn identity_profiles() -> Vec<CompletionCandidate> {
+    with_jj(|_, settings| {
+        Ok(settings
+            .table_keys("identities")
+            .map(CompletionCandidate::new)
+            .sorted()
+            .collect())
+    })
+}
+
 pub fn template_aliases() -> Vec<CompletionCandidate> {
     with_jj(|_, settings| {
         let Ok(template_aliases) = load_template_aliases(&Ui::null(), settings.config()) else {
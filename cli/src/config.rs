@@ -801,6 +801,154 @@ pub fn default_config_migrations() -> Vec<ConfigMigrationRule> {
     ]
 }
 
+/// Top-level tables which are reserved for third-party tools or internal use,
+/// and therefore aren't validated against the schema.
+const UNCHECKED_CONFIG_TABLES: &[&str] = &["custom", "debug"];
+
+/// Config variable which isn't defined in the [`CONFIG_SCHEMA`].
+#[derive(Clone, Debug)]
+pub struct UnknownConfigKey {
+    /// Full name of the unknown variable.
+    pub name: ConfigNamePathBuf,
+    /// Source of the layer in which the variable is defined.
+    pub source: ConfigSource,
+    /// Path to the config file, if the layer was loaded from a file.
+    pub path: Option<PathBuf>,
+    /// 1-based line number in the config file, if known.
+    pub line: Option<usize>,
+    /// Known variable names similar to the unknown one.
+    pub similar: Vec<String>,
+}
+
+/// Looks for user-provided config variables that aren't defined in the
+/// schema. Variables listed in `ui.ignore-unknown-config` (and their
+/// sub-tables) are skipped.
+pub fn find_unknown_config_keys(config: &StackedConfig) -> Vec<UnknownConfigKey> {
+    let schema: serde_json::Value = serde_json::from_str(CONFIG_SCHEMA).unwrap();
+    let ignored: Vec<ConfigNamePathBuf> = config
+        .get::<Vec<String>>("ui.ignore-unknown-config")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|name| name.parse().ok())
+        .collect();
+    let mut unknown_keys = Vec::new();
+    for layer in config.layers() {
+        if matches!(
+            layer.source,
+            ConfigSource::Default | ConfigSource::EnvBase | ConfigSource::EnvOverrides
+        ) {
+            continue;
+        }
+        let mut found = Vec::new();
+        collect_unknown_config_keys(
+            &schema,
+            layer.data.as_table(),
+            &ConfigNamePathBuf::root(),
+            &mut found,
+        );
+        found.retain(|(name, _)| !ignored.iter().any(|base| name.starts_with(base)));
+        if found.is_empty() {
+            continue;
+        }
+        let source_text = layer
+            .path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok());
+        let source_doc = source_text
+            .as_ref()
+            .and_then(|text| toml_edit::Document::parse(text.as_str()).ok());
+        for (name, similar) in found {
+            let line = source_text
+                .as_deref()
+                .zip(source_doc.as_ref())
+                .and_then(|(text, doc)| find_key_line(text, doc.as_table(), &name));
+            unknown_keys.push(UnknownConfigKey {
+                name,
+                source: layer.source,
+                path: layer.path.clone(),
+                line,
+                similar,
+            });
+        }
+    }
+    unknown_keys
+}
+
+fn collect_unknown_config_keys(
+    schema: &serde_json::Value,
+    table: &dyn toml_edit::TableLike,
+    prefix: &ConfigNamePathBuf,
+    found: &mut Vec<(ConfigNamePathBuf, Vec<String>)>,
+) {
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+    let additional = schema.get("additionalProperties");
+    if properties.is_none() && additional.is_none() {
+        // Free-form value
+        return;
+    }
+    for (key, item) in table.iter() {
+        let mut name = prefix.clone();
+        name.push(key);
+        if prefix.is_root() && UNCHECKED_CONFIG_TABLES.contains(&key) {
+            continue;
+        }
+        let sub_schema = match (properties.and_then(|p| p.get(key)), additional) {
+            (Some(sub_schema), _) => sub_schema,
+            (None, Some(serde_json::Value::Bool(true))) => continue,
+            (None, Some(sub_schema)) if sub_schema.is_object() => sub_schema,
+            // Empty table may be left by config migration
+            (None, _) if item.as_table_like().is_some_and(|t| t.is_empty()) => continue,
+            (None, _) => {
+                let known_keys = properties.into_iter().flat_map(|p| p.keys());
+                let similar = if prefix.is_root() {
+                    // Unknown top-level tables are likely owned by third-party
+                    // tools, so report them only if they look like a typo.
+                    let similar = known_keys
+                        .filter(|known| strsim::normalized_levenshtein(key, known) >= 0.7)
+                        .map(|known| known.to_owned())
+                        .collect_vec();
+                    if similar.is_empty() {
+                        continue;
+                    }
+                    similar
+                } else {
+                    dsl_util::collect_similar(key, known_keys)
+                        .into_iter()
+                        .map(|similar_key| {
+                            let mut similar_name = prefix.clone();
+                            similar_name.push(similar_key);
+                            similar_name.to_string()
+                        })
+                        .collect()
+                };
+                found.push((name, similar));
+                continue;
+            }
+        };
+        if let Some(sub_table) = item.as_table_like() {
+            collect_unknown_config_keys(sub_schema, sub_table, &name, found);
+        }
+    }
+}
+
+fn find_key_line(
+    text: &str,
+    table: &dyn toml_edit::TableLike,
+    name: &ConfigNamePathBuf,
+) -> Option<usize> {
+    let mut table = table;
+    let mut span = None;
+    for component in name.components() {
+        let (key, item) = table.get_key_value(component.get())?;
+        span = key.span();
+        if let Some(sub_table) = item.as_table_like() {
+            table = sub_table;
+        }
+    }
+    let offset = span?.start;
+    Some(text[..offset].matches('\n').count() + 1)
+}
+
 /// Command name and arguments specified by config.
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
 #[serde(untagged)]
@@ -18,6 +18,7 @@ use jj_lib::config::ConfigGetError;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::file_util::PathError;
 use jj_lib::settings::UserSettings;
+use jj_lib::trailer::Trailer;
 use jj_lib::trailer::parse_description_trailers;
 use jj_lib::trailer::parse_trailers;
 use thiserror::Error;
@@ -172,15 +173,62 @@ where
 }
 
 pub fn edit_description(editor: &TextEditor, description: &str) -> Result<String, CommandError> {
+    let description = edit_description_text(editor, description, "")?;
+    Ok(cleanup_description_lines(description.lines()))
+}
+
+/// Opens the editor with the `description` followed by the `instructions` and
+/// the usual "JJ:" comment, and returns the text as edited by the user.
+fn edit_description_text(
+    editor: &TextEditor,
+    description: &str,
+    instructions: &str,
+) -> Result<String, CommandError> {
     let mut description = description.to_owned();
     append_blank_line(&mut description);
+    description.push_str(instructions);
     description.push_str("JJ: Lines starting with \"JJ:\" (like this one) will be removed.\n");
 
     let description = editor
         .edit_str(description, Some(".jjdescription"))
         .map_err(|err| err.with_name("description"))?;
+    Ok(description)
+}
 
-    Ok(cleanup_description_lines(description.lines()))
+/// Line the user can add to a description to go back to the change selection.
+pub const RESTART_SELECTION_MARKER: &str = "JJ: restart-selection";
+
+/// Description entered by [`edit_description_or_restart()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EditedDescription {
+    /// The description was accepted.
+    Done(String),
+    /// The user asked to select the changes again. The description entered so
+    /// far should be used as the initial text of the next edit.
+    Restart(String),
+}
+
+/// Like [`edit_description()`], but lets the user request another round of
+/// change selection by adding a [`RESTART_SELECTION_MARKER`] line.
+pub fn edit_description_or_restart(
+    editor: &TextEditor,
+    description: &str,
+) -> Result<EditedDescription, CommandError> {
+    let description = edit_description_text(
+        editor,
+        description,
+        &format!("JJ: Add a line \"{RESTART_SELECTION_MARKER}\" to select the changes again.\n"),
+    )?;
+    let restart = description
+        .lines()
+        .take_while(|line| !line.starts_with("JJ: ignore-rest"))
+        .any(|line| line.trim_end() == RESTART_SELECTION_MARKER);
+    let description = cleanup_description_lines(description.lines());
+    if restart {
+        Ok(EditedDescription::Restart(description))
+    } else {
+        Ok(EditedDescription::Done(description))
+    }
 }
 
 /// Edits the descriptions of the given commits in a single editor session.
@@ -405,7 +453,17 @@ pub fn add_trailers_with_template(
         .into_string()
         .map_err(|_| user_error("Trailers should be valid utf-8"))?;
     let new_trailers = parse_trailers(&trailer_lines)?;
-    let mut description = commit.description().to_owned();
+    Ok(append_trailers(
+        commit.description(),
+        &trailers,
+        &new_trailers,
+    ))
+}
+
+/// Appends `new_trailers` which aren't in the existing `trailers` to the
+/// `description`.
+fn append_trailers(description: &str, trailers: &[Trailer], new_trailers: &[Trailer]) -> String {
+    let mut description = description.to_owned();
     if trailers.is_empty() && !new_trailers.is_empty() {
         if description.is_empty() {
             // a first empty line where the user will edit the commit summary
@@ -415,11 +473,37 @@ pub fn add_trailers_with_template(
         description.push('\n');
     }
     for new_trailer in new_trailers {
-        if !trailers.contains(&new_trailer) {
+        if !trailers.contains(new_trailer) {
             description.push_str(&format!("{}: {}\n", new_trailer.key, new_trailer.value));
         }
     }
-    Ok(description)
+    description
+}
+
+/// Adds the `inherited` trailers to the last paragraph of the `description`
+/// unless they are already there.
+pub fn add_inherited_trailers(description: &str, inherited: &[Trailer]) -> String {
+    let trailers = parse_description_trailers(description);
+    append_trailers(description, &trailers, inherited)
+}
+
+/// Returns an empty description if the `description` consists only of the
+/// `inherited` trailers, so that emptying a description doesn't leave them
+/// behind.
+pub fn strip_lone_inherited_trailers(description: String, inherited: &[Trailer]) -> String {
+    let has_other_lines = description
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .any(|line| {
+            !inherited
+                .iter()
+                .any(|trailer| line == format!("{}: {}", trailer.key, trailer.value))
+        });
+    if has_other_lines {
+        description
+    } else {
+        String::new()
+    }
 }
 
 /// Add the trailers from `templates.commit_trailers` in the last paragraph of
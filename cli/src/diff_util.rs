@@ -18,6 +18,7 @@ use std::cmp::max;
 use std::io;
 use std::iter;
 use std::mem;
+use std::num::NonZeroUsize;
 use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
@@ -38,10 +39,13 @@ use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::MaterializedFileConflictValue;
 use jj_lib::conflicts::MaterializedFileValue;
 use jj_lib::conflicts::MaterializedTreeDiffEntry;
 use jj_lib::conflicts::MaterializedTreeValue;
+use jj_lib::conflicts::materialize_file_conflict_to_bytes;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::conflicts::materialized_diff_stream;
 use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopiesTreeDiffEntryPath;
@@ -54,6 +58,12 @@ use jj_lib::diff::Diff;
 use jj_lib::diff::DiffHunk;
 use jj_lib::diff::DiffHunkKind;
 use jj_lib::diff::find_line_ranges;
+use jj_lib::diff::find_nonword_ranges;
+use jj_lib::diff::find_unicode_nonword_ranges;
+use jj_lib::diff::find_unicode_word_ranges;
+use jj_lib::diff::find_whitespace_delimited_ranges;
+use jj_lib::diff::find_whitespace_ranges;
+use jj_lib::diff::find_word_ranges;
 use jj_lib::files;
 use jj_lib::files::ConflictDiffHunk;
 use jj_lib::files::DiffLineHunkSide;
@@ -77,6 +87,7 @@ use thiserror::Error;
 use tracing::instrument;
 use unicode_width::UnicodeWidthStr as _;
 
+use crate::cli_util::short_commit_hash;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::commit_templater;
@@ -95,7 +106,7 @@ use crate::ui::Ui;
 
 #[derive(clap::Args, Clone, Debug)]
 #[command(next_help_heading = "Diff Formatting Options")]
-#[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "types", "name_only"])))]
+#[command(group(clap::ArgGroup::new("short-format").args(&["summary", "stat", "types", "name_only", "name_status"])))]
 #[command(group(clap::ArgGroup::new("long-format").args(&["git", "color_words"])))]
 pub struct DiffFormatArgs {
     /// For each path, show only whether it was modified, added, or deleted
@@ -119,12 +130,25 @@ pub struct DiffFormatArgs {
     ///    `jj diff -r @- --name-only | xargs perl -pi -e's/OLD/NEW/g`
     #[arg(long)]
     pub name_only: bool,
+    /// For each path, show its status and path separated by a tab
+    ///
+    /// The status is 'A' for added, 'M' for modified, and 'D' for deleted
+    /// paths. Renamed and copied paths are shown as 'R' and 'C' followed by
+    /// the similarity percentage, with the source and target paths, like
+    /// `git diff --name-status`.
+    #[arg(long, conflicts_with_all = ["git", "color_words"])]
+    pub name_status: bool,
     /// Show a Git-format diff
     #[arg(long)]
     pub git: bool,
     /// Show a word-level diff with changes indicated only by color
     #[arg(long)]
     pub color_words: bool,
+    /// How lines are split into words in color-words diffs
+    ///
+    /// This overrides the `diff.color-words.tokenizer` setting.
+    #[arg(long)]
+    tokenizer: Option<ColorWordsTokenizer>,
     /// Generate diff by external command
     ///
     /// A builtin format can also be specified as `:<name>`. For example,
@@ -137,6 +161,15 @@ pub struct DiffFormatArgs {
     /// Number of lines of context to show
     #[arg(long)]
     context: Option<usize>,
+    /// Show paths relative to the current directory
+    ///
+    /// This is the default unless `diff.relative-paths` is set to false.
+    /// Git-format diffs always show paths relative to the workspace root.
+    #[arg(long, overrides_with = "no_relative_paths")]
+    relative_paths: bool,
+    /// Show paths relative to the workspace root
+    #[arg(long, overrides_with = "relative_paths")]
+    no_relative_paths: bool,
 
     // Short flags are set by command to avoid future conflicts.
     /// Ignore whitespace when comparing lines.
@@ -147,6 +180,58 @@ pub struct DiffFormatArgs {
     ignore_space_change: bool,
 }
 
+/// Options to choose the parents merge commits are diffed against.
+#[derive(clap::Args, Clone, Debug)]
+#[command(next_help_heading = "Diff Formatting Options")]
+#[command(group(clap::ArgGroup::new("merge-diff").args(&["diff_parent", "first_parent_diffs", "all_parent_diffs"])))]
+pub struct MergeDiffArgs {
+    /// Show the changes in merge commits compared to the Nth parent
+    ///
+    /// Parents are numbered from 1. By default, merge commits are compared to
+    /// the auto-merged parents, which shows only the changes introduced by the
+    /// merge itself, such as conflict resolutions. Other commits are always
+    /// compared to their only parent.
+    #[arg(long, value_name = "N")]
+    diff_parent: Option<NonZeroUsize>,
+    /// Show the changes in merge commits compared to the first parent
+    ///
+    /// This is equivalent to `--diff-parent=1`.
+    #[arg(long)]
+    first_parent_diffs: bool,
+    /// Show the changes in merge commits compared to each parent
+    ///
+    /// One diff is shown per parent, each preceded by a header naming the
+    /// parent.
+    #[arg(long)]
+    all_parent_diffs: bool,
+}
+
+impl MergeDiffArgs {
+    pub fn mode(&self) -> MergeDiffMode {
+        if let Some(n) = self.diff_parent {
+            MergeDiffMode::Parent(n.get() - 1)
+        } else if self.first_parent_diffs {
+            MergeDiffMode::Parent(0)
+        } else if self.all_parent_diffs {
+            MergeDiffMode::AllParents
+        } else {
+            MergeDiffMode::MergedParents
+        }
+    }
+}
+
+/// Parents merge commits are diffed against.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MergeDiffMode {
+    /// Compare to the auto-merged parents.
+    #[default]
+    MergedParents,
+    /// Compare to the parent at the given (0-based) index.
+    Parent(usize),
+    /// Compare to each parent separately.
+    AllParents,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DiffFormat {
     // Non-trivial parameters are boxed in order to keep the variants small
@@ -154,6 +239,7 @@ pub enum DiffFormat {
     Stat(Box<DiffStatOptions>),
     Types,
     NameOnly,
+    NameStatus,
     Git(Box<UnifiedDiffOptions>),
     ColorWords(Box<ColorWordsDiffOptions>),
     Tool(Box<ExternalMergeTool>),
@@ -165,6 +251,7 @@ enum BuiltinFormatKind {
     Stat,
     Types,
     NameOnly,
+    NameStatus,
     Git,
     ColorWords,
 }
@@ -178,6 +265,7 @@ impl BuiltinFormatKind {
         Self::Stat,
         Self::Types,
         Self::NameOnly,
+        Self::NameStatus,
         Self::Git,
         Self::ColorWords,
     ];
@@ -188,6 +276,7 @@ impl BuiltinFormatKind {
             "stat" => Ok(Self::Stat),
             "types" => Ok(Self::Types),
             "name-only" => Ok(Self::NameOnly),
+            "name-status" => Ok(Self::NameStatus),
             "git" => Ok(Self::Git),
             "color-words" => Ok(Self::ColorWords),
             _ => Err(format!("Invalid builtin diff format: {name}")),
@@ -203,6 +292,8 @@ impl BuiltinFormatKind {
             Some(Self::Types)
         } else if args.name_only {
             Some(Self::NameOnly)
+        } else if args.name_status {
+            Some(Self::NameStatus)
         } else {
             None
         }
@@ -220,7 +311,7 @@ impl BuiltinFormatKind {
 
     fn is_short(self) -> bool {
         match self {
-            Self::Summary | Self::Stat | Self::Types | Self::NameOnly => true,
+            Self::Summary | Self::Stat | Self::Types | Self::NameOnly | Self::NameStatus => true,
             Self::Git | Self::ColorWords => false,
         }
     }
@@ -231,6 +322,7 @@ impl BuiltinFormatKind {
             Self::Stat => "stat",
             Self::Types => "types",
             Self::NameOnly => "name-only",
+            Self::NameStatus => "name-status",
             Self::Git => "git",
             Self::ColorWords => "color-words",
         }
@@ -250,6 +342,7 @@ impl BuiltinFormatKind {
             }
             Self::Types => Ok(DiffFormat::Types),
             Self::NameOnly => Ok(DiffFormat::NameOnly),
+            Self::NameStatus => Ok(DiffFormat::NameStatus),
             Self::Git => {
                 let mut options = UnifiedDiffOptions::from_settings(settings)?;
                 options.merge_args(args);
@@ -320,6 +413,20 @@ pub fn diff_formats_for_log(
     Ok([short_format, long_format].into_iter().flatten().collect())
 }
 
+/// Returns whether paths should be shown relative to the current directory.
+pub fn diff_relative_paths_for(
+    settings: &UserSettings,
+    args: &DiffFormatArgs,
+) -> Result<bool, ConfigGetError> {
+    if args.relative_paths {
+        Ok(true)
+    } else if args.no_relative_paths {
+        Ok(false)
+    } else {
+        settings.get_bool("diff.relative-paths")
+    }
+}
+
 fn diff_formats_from_args(
     settings: &UserSettings,
     args: &DiffFormatArgs,
@@ -399,12 +506,17 @@ pub enum DiffRenderError {
     InvalidRepoPath(#[from] InvalidRepoPathError),
     #[error(transparent)]
     Io(#[from] io::Error),
+    #[error("Merge commit {commit_hash} has no parent number {parent_number}")]
+    NoSuchParent {
+        commit_hash: String,
+        parent_number: usize,
+    },
 }
 
 /// Configuration and environment to render textual diff.
 pub struct DiffRenderer<'a> {
     repo: &'a dyn Repo,
-    path_converter: &'a RepoPathUiConverter,
+    path_converter: Cow<'a, RepoPathUiConverter>,
     conflict_marker_style: ConflictMarkerStyle,
     formats: Vec<DiffFormat>,
 }
@@ -418,12 +530,23 @@ impl<'a> DiffRenderer<'a> {
     ) -> Self {
         Self {
             repo,
-            path_converter,
+            path_converter: Cow::Borrowed(path_converter),
             conflict_marker_style,
             formats,
         }
     }
 
+    /// Shows paths relative to the workspace root instead of the current
+    /// directory.
+    pub fn with_root_relative_paths(mut self) -> Self {
+        let RepoPathUiConverter::Fs { base, .. } = self.path_converter.as_ref();
+        self.path_converter = Cow::Owned(RepoPathUiConverter::Fs {
+            cwd: base.clone(),
+            base: base.clone(),
+        });
+        self
+    }
+
     /// Generates diff between `trees`.
     pub async fn show_diff(
         &self,
@@ -452,7 +575,7 @@ impl<'a> DiffRenderer<'a> {
         width: usize,
     ) -> Result<(), DiffRenderError> {
         let store = self.repo.store();
-        let path_converter = self.path_converter;
+        let path_converter = self.path_converter.as_ref();
         for format in &self.formats {
             match format {
                 DiffFormat::Summary => {
@@ -478,6 +601,11 @@ impl<'a> DiffRenderer<'a> {
                         from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
                     show_names(formatter, tree_diff, path_converter).await?;
                 }
+                DiffFormat::NameStatus => {
+                    let tree_diff =
+                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                    show_name_status(formatter, store, tree_diff, path_converter).await?;
+                }
                 DiffFormat::Git(options) => {
                     let tree_diff =
                         from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
@@ -555,7 +683,8 @@ impl<'a> DiffRenderer<'a> {
                 DiffFormat::Summary
                 | DiffFormat::Stat(_)
                 | DiffFormat::Types
-                | DiffFormat::NameOnly => {}
+                | DiffFormat::NameOnly
+                | DiffFormat::NameStatus => {}
                 DiffFormat::Git(options) => {
                     // Git format must be parsable, so use dummy file path.
                     show_git_diff_texts(
@@ -583,6 +712,51 @@ impl<'a> DiffRenderer<'a> {
         Ok(())
     }
 
+    /// Generates diff between two versions of file contents, such as two
+    /// terms of a conflict. `labels` describe the versions. Short formats and
+    /// external tools aren't supported and print nothing.
+    pub fn show_diff_texts<T: AsRef<[u8]>>(
+        &self,
+        formatter: &mut dyn Formatter,
+        title: &str,
+        labels: [&str; 2],
+        contents: [&T; 2],
+    ) -> Result<(), DiffRenderError> {
+        let contents = contents.map(Merge::resolved);
+        formatter.with_label("diff", |formatter| {
+            for format in &self.formats {
+                match format {
+                    DiffFormat::Summary
+                    | DiffFormat::Stat(_)
+                    | DiffFormat::Types
+                    | DiffFormat::NameOnly
+                    | DiffFormat::NameStatus
+                    | DiffFormat::Tool(_) => {}
+                    DiffFormat::Git(options) => {
+                        show_git_diff_texts(
+                            formatter,
+                            labels,
+                            [&contents[0], &contents[1]],
+                            options,
+                            self.conflict_marker_style,
+                        )?;
+                    }
+                    DiffFormat::ColorWords(options) => {
+                        let [left, right] = labels;
+                        writeln!(formatter.labeled("header"), "{title}, {left} => {right}:")?;
+                        show_color_words_diff_hunks(
+                            formatter,
+                            [&contents[0], &contents[1]],
+                            options,
+                            self.conflict_marker_style,
+                        )?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Generates diff between `from_commits` and `to_commit` based off their
     /// parents. The `from_commits` will temporarily be rebased onto the
     /// `to_commit` parents to exclude unrelated changes.
@@ -655,6 +829,81 @@ impl<'a> DiffRenderer<'a> {
         )
         .await
     }
+
+    /// Generates diff of the given commit compared to its parents, choosing
+    /// the parents of merge commits by `mode`.
+    pub async fn show_merge_patch(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        commit: &Commit,
+        matcher: &dyn Matcher,
+        width: usize,
+        mode: MergeDiffMode,
+    ) -> Result<(), DiffRenderError> {
+        let parent_ids = commit.parent_ids();
+        match mode {
+            MergeDiffMode::Parent(index) if parent_ids.len() > 1 => {
+                let parent_id =
+                    parent_ids
+                        .get(index)
+                        .ok_or_else(|| DiffRenderError::NoSuchParent {
+                            commit_hash: short_commit_hash(commit.id()),
+                            parent_number: index + 1,
+                        })?;
+                self.show_patch_against_parent(ui, formatter, commit, parent_id, matcher, width)
+                    .await
+            }
+            MergeDiffMode::AllParents if parent_ids.len() > 1 => {
+                for (index, parent_id) in parent_ids.iter().enumerate() {
+                    formatter.with_label("diff", |formatter| {
+                        writeln!(
+                            formatter.labeled("header"),
+                            "Diff against parent {number} ({hash}):",
+                            number = index + 1,
+                            hash = short_commit_hash(parent_id),
+                        )
+                    })?;
+                    self.show_patch_against_parent(
+                        ui, formatter, commit, parent_id, matcher, width,
+                    )
+                    .await?;
+                }
+                Ok(())
+            }
+            _ => self.show_patch(ui, formatter, commit, matcher, width).await,
+        }
+    }
+
+    async fn show_patch_against_parent(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        commit: &Commit,
+        parent_id: &CommitId,
+        matcher: &dyn Matcher,
+        width: usize,
+    ) -> Result<(), DiffRenderError> {
+        let from_tree = self
+            .repo
+            .store()
+            .get_commit(parent_id)?
+            .tree_async()
+            .await?;
+        let to_tree = commit.tree_async().await?;
+        let mut copy_records = CopyRecords::default();
+        let records = get_copy_records(self.repo.store(), parent_id, commit.id(), matcher)?;
+        copy_records.add_records(records)?;
+        self.show_diff(
+            ui,
+            formatter,
+            [&from_tree, &to_tree],
+            matcher,
+            &copy_records,
+            width,
+        )
+        .await
+    }
 }
 
 pub fn get_copy_records<'a>(
@@ -680,6 +929,20 @@ pub enum ConflictDiffMethod {
     Pair,
 }
 
+/// How lines are split into words in color-words diffs.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorWordsTokenizer {
+    /// Split at any character other than ASCII letters, digits, and
+    /// underscores
+    #[default]
+    Words,
+    /// Split at whitespace only
+    Whitespace,
+    /// Split at Unicode word boundaries
+    UnicodeWords,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct LineDiffOptions {
     /// How equivalence of lines is tested.
@@ -741,6 +1004,8 @@ pub struct ColorWordsDiffOptions {
     pub line_diff: LineDiffOptions,
     /// Maximum number of removed/added word alternation to inline.
     pub max_inline_alternation: Option<usize>,
+    /// How lines are split into words.
+    pub tokenizer: ColorWordsTokenizer,
 }
 
 impl ColorWordsDiffOptions {
@@ -761,6 +1026,7 @@ impl ColorWordsDiffOptions {
             context: settings.get("diff.color-words.context")?,
             line_diff: LineDiffOptions::default(),
             max_inline_alternation,
+            tokenizer: settings.get("diff.color-words.tokenizer")?,
         })
     }
 
@@ -768,10 +1034,32 @@ impl ColorWordsDiffOptions {
         if let Some(context) = args.context {
             self.context = context;
         }
+        if let Some(tokenizer) = args.tokenizer {
+            self.tokenizer = tokenizer;
+        }
         self.line_diff.merge_args(args);
     }
 }
 
+fn diff_by_word<'input>(
+    inputs: impl IntoIterator<Item = &'input BStr>,
+    tokenizer: ColorWordsTokenizer,
+) -> Diff<'input> {
+    type FindRanges = fn(&[u8]) -> Vec<Range<usize>>;
+    let (find_words, find_nonwords): (FindRanges, FindRanges) = match tokenizer {
+        ColorWordsTokenizer::Words => (find_word_ranges, find_nonword_ranges),
+        ColorWordsTokenizer::Whitespace => {
+            (find_whitespace_delimited_ranges, find_whitespace_ranges)
+        }
+        ColorWordsTokenizer::UnicodeWords => {
+            (find_unicode_word_ranges, find_unicode_nonword_ranges)
+        }
+    };
+    let mut diff = Diff::for_tokenizer(inputs, find_words, CompareBytesExactly);
+    diff.refine_changed_regions(find_nonwords, CompareBytesExactly);
+    diff
+}
+
 fn show_color_words_diff_hunks<T: AsRef<[u8]>>(
     formatter: &mut dyn Formatter,
     [lefts, rights]: [&Merge<T>; 2],
@@ -1067,7 +1355,9 @@ fn show_color_words_diff_lines(
     labels: [&str; 2],
     options: &ColorWordsDiffOptions,
 ) -> io::Result<DiffLineNumber> {
-    let word_diff_hunks = Diff::by_word(contents).hunks().collect_vec();
+    let word_diff_hunks = diff_by_word(contents, options.tokenizer)
+        .hunks()
+        .collect_vec();
     let can_inline = match options.max_inline_alternation {
         None => true,     // unlimited
         Some(0) => false, // no need to count alternation
@@ -1246,6 +1536,7 @@ fn file_content_for_diff<T>(
 }
 
 fn diff_content(
+    store: &Store,
     path: &RepoPath,
     value: MaterializedTreeValue,
     conflict_marker_style: ConflictMarkerStyle,
@@ -1254,7 +1545,7 @@ fn diff_content(
         path,
         value,
         |content| content,
-        |contents| materialize_merge_result_to_bytes(&contents, conflict_marker_style),
+        |file| materialize_file_conflict_to_bytes(store, &file, conflict_marker_style),
     )
 }
 
@@ -1262,14 +1553,14 @@ fn diff_content_as_merge(
     path: &RepoPath,
     value: MaterializedTreeValue,
 ) -> BackendResult<FileContent<Merge<BString>>> {
-    diff_content_with(path, value, Merge::resolved, |contents| contents)
+    diff_content_with(path, value, Merge::resolved, |file| file.contents)
 }
 
 fn diff_content_with<T>(
     path: &RepoPath,
     value: MaterializedTreeValue,
     map_resolved: impl FnOnce(BString) -> T,
-    map_conflict: impl FnOnce(Merge<BString>) -> T,
+    map_conflict: impl FnOnce(MaterializedFileConflictValue) -> T,
 ) -> BackendResult<FileContent<T>> {
     match value {
         MaterializedTreeValue::Absent => Ok(FileContent {
@@ -1295,7 +1586,7 @@ fn diff_content_with<T>(
         // TODO: are we sure this is never binary?
         MaterializedTreeValue::FileConflict(file) => Ok(FileContent {
             is_binary: false,
-            contents: map_conflict(file.contents),
+            contents: map_conflict(file),
         }),
         MaterializedTreeValue::OtherConflict { id } => Ok(FileContent {
             is_binary: false,
@@ -1490,7 +1781,7 @@ pub async fn show_file_by_file_diff(
      -> Result<PathBuf, DiffRenderError> {
         let fs_path = path.to_fs_path(wc_dir)?;
         std::fs::create_dir_all(fs_path.parent().unwrap())?;
-        let content = diff_content(path, value, conflict_marker_style)?;
+        let content = diff_content(store, path, value, conflict_marker_style)?;
         std::fs::write(&fs_path, content.contents)?;
         Ok(fs_path)
     };
@@ -1551,6 +1842,7 @@ struct GitDiffPart {
 }
 
 fn git_diff_part(
+    store: &Store,
     path: &RepoPath,
     value: MaterializedTreeValue,
     conflict_marker_style: ConflictMarkerStyle,
@@ -1607,7 +1899,7 @@ fn git_diff_part(
             hash = DUMMY_HASH.to_owned();
             content = FileContent {
                 is_binary: false, // TODO: are we sure this is never binary?
-                contents: materialize_merge_result_to_bytes(&file.contents, conflict_marker_style),
+                contents: materialize_file_conflict_to_bytes(store, &file, conflict_marker_style),
             };
         }
         MaterializedTreeValue::OtherConflict { id } => {
@@ -1893,8 +2185,8 @@ pub async fn show_git_diff(
         let right_path_string = right_path.as_internal_file_string();
         let (left_value, right_value) = values?;
 
-        let left_part = git_diff_part(left_path, left_value, conflict_marker_style)?;
-        let right_part = git_diff_part(right_path, right_value, conflict_marker_style)?;
+        let left_part = git_diff_part(store, left_path, left_value, conflict_marker_style)?;
+        let right_part = git_diff_part(store, right_path, right_value, conflict_marker_style)?;
 
         formatter.with_label("file_header", |formatter| {
             writeln!(
@@ -2061,8 +2353,9 @@ impl DiffStats {
         let entries = materialized_diff_stream(store, tree_diff)
             .map(|MaterializedTreeDiffEntry { path, values }| {
                 let (left, right) = values?;
-                let left_content = diff_content(path.source(), left, conflict_marker_style)?;
-                let right_content = diff_content(path.target(), right, conflict_marker_style)?;
+                let left_content = diff_content(store, path.source(), left, conflict_marker_style)?;
+                let right_content =
+                    diff_content(store, path.target(), right, conflict_marker_style)?;
                 let stat = get_diff_stat_entry(path, [&left_content, &right_content], options);
                 BackendResult::Ok(stat)
             })
@@ -2146,17 +2439,24 @@ pub fn show_diff_stats(
     let ui_paths = stats
         .entries()
         .iter()
-        .map(|stat| {
-            if stat.path.copy_operation().is_some() {
+        .map(|stat| match stat.path.copy_operation() {
+            Some(CopyOperation::Rename) => {
                 path_converter.format_copied_path(stat.path.source(), stat.path.target())
-            } else {
-                path_converter.format_file_path(stat.path.target())
             }
+            Some(CopyOperation::Copy) => format!(
+                "{} (copied from {})",
+                path_converter.format_file_path(stat.path.target()),
+                path_converter.format_file_path(stat.path.source())
+            ),
+            None => path_converter.format_file_path(stat.path.target()),
         })
         .collect_vec();
 
     // Entries format like:
     //   path/to/file | 123 ++--
+    // or, for renamed and copied files:
+    //   path/{old => new} | 123 ++--
+    //   path/new (copied from path/old) | 123 ++--
     // or, for binary files:
     //   path/to/file | (binary) +1234 bytes
     //
@@ -2310,6 +2610,46 @@ fn diff_summary_char(value: &MergedTreeValue) -> char {
     }
 }
 
+/// Returns true if the `tree_diff` has any changes. Changes in whitespace are
+/// ignored as specified by the `args`.
+pub async fn has_diff(
+    store: &Store,
+    tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    args: &DiffFormatArgs,
+) -> Result<bool, DiffRenderError> {
+    let mut line_diff_options = LineDiffOptions::default();
+    line_diff_options.merge_args(args);
+    if line_diff_options.compare_mode == LineCompareMode::Exact {
+        let mut tree_diff = tree_diff;
+        return Ok(tree_diff.next().await.is_some());
+    }
+    let mut diff_stream = materialized_diff_stream(store, tree_diff);
+    while let Some(MaterializedTreeDiffEntry { path, values }) = diff_stream.next().await {
+        let (left_value, right_value) = values?;
+        let (MaterializedTreeValue::File(mut left), MaterializedTreeValue::File(mut right)) =
+            (left_value, right_value)
+        else {
+            return Ok(true);
+        };
+        if path.copy_operation().is_some() || left.executable != right.executable {
+            return Ok(true);
+        }
+        let left_content = file_content_for_diff(path.source(), &mut left, |x| x)?;
+        let right_content = file_content_for_diff(path.target(), &mut right, |x| x)?;
+        if left_content.is_binary || right_content.is_binary {
+            return Ok(true);
+        }
+        let contents = [&left_content.contents, &right_content.contents];
+        if diff_by_line(contents, &line_diff_options)
+            .hunks()
+            .any(|hunk| hunk.kind == DiffHunkKind::Different)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 pub async fn show_names(
     formatter: &mut dyn Formatter,
     mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
@@ -2325,6 +2665,63 @@ pub async fn show_names(
     Ok(())
 }
 
+pub async fn show_name_status(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    path_converter: &RepoPathUiConverter,
+) -> Result<(), DiffRenderError> {
+    while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().await {
+        let (before, after) = values?;
+        let (label, sigil) = diff_status_label_and_char(&path, &before, &after);
+        if path.copy_operation().is_some() {
+            let score = copy_similarity_score(store, &path, before, after).await?;
+            writeln!(
+                formatter.labeled(label),
+                "{sigil}{score:03}\t{}\t{}",
+                path_converter.format_file_path(path.source()),
+                path_converter.format_file_path(path.target())
+            )?;
+        } else {
+            writeln!(
+                formatter.labeled(label),
+                "{sigil}\t{}",
+                path_converter.format_file_path(path.target())
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the percentage of the larger file content that is unchanged
+/// between the copy source and target.
+async fn copy_similarity_score(
+    store: &Store,
+    path: &CopiesTreeDiffEntryPath,
+    before: MergedTreeValue,
+    after: MergedTreeValue,
+) -> BackendResult<usize> {
+    let before = materialize_tree_value(store, path.source(), before).await?;
+    let after = materialize_tree_value(store, path.target(), after).await?;
+    let (MaterializedTreeValue::File(mut before), MaterializedTreeValue::File(mut after)) =
+        (before, after)
+    else {
+        return Ok(100);
+    };
+    let before = before.read_all(path.source()).await?;
+    let after = after.read_all(path.target()).await?;
+    let max_len = max(before.len(), after.len());
+    if max_len == 0 {
+        return Ok(100);
+    }
+    let matching_len: usize = Diff::by_line([&before, &after])
+        .hunks()
+        .filter(|hunk| hunk.kind == DiffHunkKind::Matching)
+        .map(|hunk| hunk.contents[0].len())
+        .sum();
+    Ok(matching_len * 100 / max_len)
+}
+
 pub async fn show_templated(
     formatter: &mut dyn Formatter,
     mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
@@ -444,6 +444,8 @@ impl<W: Write> ColorFormatter<W> {
 fn rules_from_config(config: &StackedConfig) -> Result<Rules, ConfigGetError> {
     config
         .table_keys("colors")
+        // Labels applied by revsets are configured separately.
+        .filter(|&key| key != "revsets")
         .map(|key| {
             let labels = key
                 .split_whitespace()
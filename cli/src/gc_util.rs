@@ -0,0 +1,242 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Garbage collection of operations and backend objects, including the
+//! automatic maintenance configured by the `gc` table.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::slice;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigValue;
+use jj_lib::local_working_copy::LocalWorkingCopy;
+use jj_lib::op_walk;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo as _;
+use jj_lib::settings::UserSettings;
+
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+
+/// Environment variable that disables automatic garbage collection for a
+/// single invocation.
+pub const NO_AUTO_GC_ENV_VAR: &str = "JJ_NO_AUTO_GC";
+
+/// File under the repo directory recording when automatic garbage collection
+/// last ran.
+const LAST_AUTO_GC_FILE: &str = "last_auto_gc";
+
+/// Default age below which obsolete objects and operations are kept.
+pub const DEFAULT_GC_EXPIRE: Duration = Duration::from_secs(14 * 86400);
+
+/// Prunes operations that are unreachable from the current operation and
+/// unreachable objects, keeping those newer than `keep_newer`. Operations in
+/// the operation log aren't abandoned.
+pub fn run_gc(repo: &ReadonlyRepo, keep_newer: SystemTime) -> Result<(), CommandError> {
+    repo.op_store()
+        .gc(slice::from_ref(repo.op_id()), keep_newer)?;
+    repo.store().gc(repo.index(), keep_newer)?;
+    Ok(())
+}
+
+/// Removes the file contents which aren't in the current working-copy commit
+/// from the reflink cache of the working copy.
+pub fn prune_working_copy_cache(
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<(), CommandError> {
+    let working_copy = workspace_command.working_copy().as_any();
+    if let Some(working_copy) = working_copy.downcast_ref::<LocalWorkingCopy>() {
+        working_copy.prune_reflink_cache()?;
+    }
+    Ok(())
+}
+
+/// Abandons the operations which completed before `keep_newer`, as
+/// `jj op abandon ..<operation>` does for the newest of them. The current
+/// operation is kept. Returns the number of abandoned operations.
+pub fn abandon_expired_operations(
+    workspace_command: &mut WorkspaceCommandHelper,
+    keep_newer: SystemTime,
+) -> Result<usize, CommandError> {
+    let repo = workspace_command.repo().clone();
+    let head_op = repo.operation();
+    let root_op = repo.loader().root_operation();
+    let cutoff = MillisSinceEpoch(
+        keep_newer
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            .try_into()
+            .unwrap_or(i64::MAX),
+    );
+    let mut expired_op = None;
+    for op in op_walk::walk_ancestors(slice::from_ref(head_op)).skip(1) {
+        let op = op?;
+        if op.id() == root_op.id() {
+            break;
+        } else if op.metadata().time.end.timestamp < cutoff {
+            expired_op = Some(op);
+            break;
+        }
+    }
+    let Some(expired_op) = expired_op else {
+        return Ok(0);
+    };
+    let stats = op_walk::reparent_range(
+        repo.op_store().as_ref(),
+        slice::from_ref(&expired_op),
+        slice::from_ref(head_op),
+        &root_op,
+    )?;
+    let new_head_op = repo.loader().load_operation(&stats.new_head_ids[0])?;
+    repo.op_heads_store()
+        .update_op_heads(slice::from_ref(head_op.id()), new_head_op.id())?;
+    workspace_command.replace_current_operation(&new_head_op)?;
+    Ok(stats.unreachable_count)
+}
+
+/// Settings of the automatic garbage collection.
+#[derive(Clone, Debug)]
+pub struct AutoGcSettings {
+    pub enabled: bool,
+    /// Minimum time between two automatic runs.
+    pub interval: Duration,
+}
+
+impl AutoGcSettings {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        Ok(Self {
+            enabled: settings.get_bool("gc.auto")?,
+            interval: settings.get_value_with("gc.auto-interval", parse_interval_config)?,
+        })
+    }
+}
+
+/// Abandons expired operations and runs garbage collection if the configured
+/// interval has elapsed since the last automatic run. Returns whether it ran.
+///
+/// The timestamp is updated before collecting, so a failing collection isn't
+/// retried by every subsequent command.
+pub fn run_gc_if_due(
+    workspace_command: &mut WorkspaceCommandHelper,
+    interval: Duration,
+) -> Result<bool, CommandError> {
+    let timestamp_path = workspace_command.repo_path().join(LAST_AUTO_GC_FILE);
+    let now = SystemTime::now();
+    let last_run = read_last_run(&timestamp_path)?;
+    if last_run.is_some_and(|last_run| {
+        now.duration_since(last_run)
+            .is_ok_and(|elapsed| elapsed < interval)
+    }) {
+        return Ok(false);
+    }
+    let secs = now
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    fs::write(&timestamp_path, secs.to_string())?;
+    let keep_newer = now - DEFAULT_GC_EXPIRE;
+    abandon_expired_operations(workspace_command, keep_newer)?;
+    run_gc(workspace_command.repo(), keep_newer)?;
+    prune_working_copy_cache(workspace_command)?;
+    Ok(true)
+}
+
+fn read_last_run(path: &Path) -> io::Result<Option<SystemTime>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    // An unparsable timestamp is treated as if gc never ran.
+    Ok(content
+        .trim()
+        .parse()
+        .ok()
+        .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)))
+}
+
+fn parse_interval_config(value: ConfigValue) -> Result<Duration, &'static str> {
+    if let Some(n) = value.as_integer() {
+        let secs = u64::try_from(n).map_err(|_| "Integer out of range")?;
+        Ok(Duration::from_secs(secs))
+    } else if let Some(s) = value.as_str() {
+        parse_interval(s)
+    } else {
+        Err("Expected a number of seconds or a string in '<number> <unit>' form")
+    }
+}
+
+/// Parses an interval such as `7 days` or `12h`.
+fn parse_interval(s: &str) -> Result<Duration, &'static str> {
+    let s = s.trim();
+    let digit_end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if digit_end == 0 {
+        return Err("must start with a number");
+    }
+    let (digits, unit) = s.split_at(digit_end);
+    let n: u64 = digits.parse().map_err(|_| "number out of range")?;
+    let unit_secs = match unit.trim_start() {
+        "" | "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hour" | "hours" => 3600,
+        "d" | "day" | "days" => 86400,
+        "w" | "week" | "weeks" => 7 * 86400,
+        _ => return Err("unrecognized unit"),
+    };
+    n.checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or("number out of range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("0"), Ok(Duration::ZERO));
+        assert_eq!(parse_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval("1 minute"), Ok(Duration::from_secs(60)));
+        assert_eq!(parse_interval("12h"), Ok(Duration::from_secs(12 * 3600)));
+        assert_eq!(parse_interval("7 days"), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(
+            parse_interval("2 weeks"),
+            Ok(Duration::from_secs(14 * 86400))
+        );
+        assert!(parse_interval("").is_err());
+        assert!(parse_interval("days").is_err());
+        assert!(parse_interval("7 fortnights").is_err());
+        assert!(parse_interval("99999999999999999999 days").is_err());
+    }
+
+    #[test]
+    fn test_parse_interval_config() {
+        assert_eq!(
+            parse_interval_config(ConfigValue::from(60)),
+            Ok(Duration::from_secs(60))
+        );
+        assert!(parse_interval_config(ConfigValue::from(-1)).is_err());
+        assert_eq!(
+            parse_interval_config(ConfigValue::from("1 day")),
+            Ok(Duration::from_secs(86400))
+        );
+        assert!(parse_interval_config(ConfigValue::from(true)).is_err());
+    }
+}
@@ -15,6 +15,7 @@
 //! Git utilities shared by various commands.
 
 use std::error;
+use std::fs;
 use std::io;
 use std::io::Read as _;
 use std::io::Write as _;
@@ -30,17 +31,21 @@ use crossterm::terminal::Clear;
 use crossterm::terminal::ClearType;
 use indoc::writedoc;
 use itertools::Itertools as _;
+use jj_lib::config::ConfigGetError;
+use jj_lib::content_hash::blake2b_hash;
 use jj_lib::fmt_util::binary_prefix;
 use jj_lib::git;
 use jj_lib::git::FailedRefExportReason;
 use jj_lib::git::GitExportStats;
 use jj_lib::git::GitImportStats;
 use jj_lib::git::GitRefKind;
+use jj_lib::hex_util;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
+use jj_lib::settings::UserSettings;
 use jj_lib::workspace::Workspace;
 use unicode_width::UnicodeWidthStr as _;
 
@@ -48,6 +53,7 @@ use crate::cleanup_guard::CleanupGuard;
 use crate::command_error::CommandError;
 use crate::command_error::cli_error;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::formatter::Formatter;
 use crate::ui::ProgressOutput;
 use crate::ui::Ui;
@@ -70,6 +76,133 @@ pub fn is_colocated_git_workspace(workspace: &Workspace, repo: &ReadonlyRepo) ->
     dunce::canonicalize(git_workdir).ok().as_deref() == dot_git_path.parent()
 }
 
+/// Writes `.jj/.gitignore` which hides the `.jj` directory from Git in a
+/// colocated workspace.
+pub fn write_jj_gitignore(workspace_root: &Path) -> Result<(), CommandError> {
+    fs::write(workspace_root.join(".jj").join(".gitignore"), "/*\n")
+        .map_err(|err| user_error_with_message("Failed to write .jj/.gitignore file", err))
+}
+
+/// Recreates `.jj/.gitignore` of the colocated workspace if it's missing, and
+/// if the `.jj` directory isn't excluded by the Git repo otherwise.
+pub fn restore_jj_gitignore(
+    ui: &Ui,
+    workspace: &Workspace,
+    repo: &ReadonlyRepo,
+) -> Result<(), CommandError> {
+    let workspace_root = workspace.workspace_root();
+    if workspace_root.join(".jj").join(".gitignore").exists() {
+        return Ok(());
+    }
+    let git_backend = git::get_git_backend(repo.store())?;
+    if is_jj_dir_excluded(&git_backend.git_repo()) {
+        return Ok(());
+    }
+    write_jj_gitignore(workspace_root)?;
+    writeln!(
+        ui.status(),
+        "Recreated missing .jj/.gitignore file to hide the .jj directory from Git"
+    )?;
+    Ok(())
+}
+
+/// Whether `.git/info/exclude` has an entry for the `.jj` directory.
+fn is_jj_dir_excluded(git_repo: &gix::Repository) -> bool {
+    let exclude_path = git_repo.common_dir().join("info").join("exclude");
+    let Ok(content) = fs::read_to_string(exclude_path) else {
+        return false;
+    };
+    content
+        .lines()
+        .map(str::trim)
+        .any(|line| matches!(line, ".jj" | ".jj/" | "/.jj" | "/.jj/"))
+}
+
+/// When to import refs from the Git repo of a colocated workspace.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub enum GitAutoImport {
+    /// Import Git HEAD and refs whenever the working copy is snapshotted.
+    Always,
+    /// Import Git HEAD whenever the working copy is snapshotted, but import
+    /// refs only if the snapshot recorded new changes.
+    OnSnapshot,
+    /// Never import automatically. `jj git import` has to be run instead.
+    Never,
+}
+
+impl GitAutoImport {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        settings.get("git.auto-import")
+    }
+}
+
+/// File under the repo directory recording the state of the Git refs as of
+/// the last automatic import.
+const GIT_IMPORT_STATE_FILE: &str = "git_import_state";
+
+/// Returns a checksum of the files storing the Git refs and the Git refs
+/// recorded in the view, or `None` if the Git refs can't be inspected.
+///
+/// The contents of the files are hashed rather than their modification times,
+/// which can't tell apart changes made within the timestamp granularity. The
+/// files are small compared to the cost of importing the refs.
+pub fn git_refs_state(repo: &ReadonlyRepo) -> Option<String> {
+    let git_backend = git::get_git_backend(repo.store()).ok()?;
+    let git_dir = git_backend.git_repo_path();
+    let mut files = vec![];
+    // Other refs such as refs/jj/keep/* are never imported, and are updated
+    // whenever commits are created.
+    let names = [
+        "packed-refs",
+        "refs/heads",
+        "refs/remotes",
+        "refs/tags",
+        "reftable",
+    ];
+    for name in names {
+        collect_file_hashes(git_dir, Path::new(name), &mut files).ok()?;
+    }
+    files.sort_unstable();
+    let git_refs_hash = blake2b_hash(repo.view().git_refs());
+    let hash = blake2b_hash(&(git_refs_hash.to_vec(), files));
+    Some(hex_util::encode_hex(&hash))
+}
+
+fn collect_file_hashes(
+    git_dir: &Path,
+    path: &Path,
+    files: &mut Vec<(String, Vec<u8>)>,
+) -> io::Result<()> {
+    let metadata = match fs::symlink_metadata(git_dir.join(path)) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if metadata.is_dir() {
+        for entry in fs::read_dir(git_dir.join(path))? {
+            collect_file_hashes(git_dir, &path.join(entry?.file_name()), files)?;
+        }
+    } else {
+        let content = fs::read(git_dir.join(path))?;
+        files.push((
+            path.to_string_lossy().into_owned(),
+            blake2b_hash(&content).to_vec(),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns true if `state` matches the one saved by the last automatic import.
+pub fn is_git_import_state_unchanged(repo_path: &Path, state: &str) -> bool {
+    fs::read_to_string(repo_path.join(GIT_IMPORT_STATE_FILE)).is_ok_and(|saved| saved == state)
+}
+
+/// Saves the state of the Git refs as of an automatic import.
+pub fn save_git_import_state(repo_path: &Path, state: &str) -> io::Result<()> {
+    fs::write(repo_path.join(GIT_IMPORT_STATE_FILE), state)
+}
+
 /// Parses user-specified remote URL or path to absolute form.
 pub fn absolute_git_url(cwd: &Path, source: &str) -> Result<String, CommandError> {
     // Git appears to turn URL-like source to absolute path if local git directory
@@ -307,6 +440,17 @@ pub fn print_git_import_stats(
                 status.output(max_width, &mut *formatter)?;
             }
         }
+        if !stats.non_commit_ref_names.is_empty() {
+            writeln!(
+                ui.warning_default(),
+                "Skipped Git refs pointing to non-commit objects:"
+            )?;
+            for name in &stats.non_commit_ref_names {
+                write!(formatter, "  ")?;
+                write!(formatter.labeled("git_ref"), "{}", name.as_str())?;
+                writeln!(formatter)?;
+            }
+        }
     }
 
     if !stats.abandoned_commits.is_empty() {
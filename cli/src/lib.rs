@@ -24,6 +24,7 @@ pub mod config;
 pub mod description_util;
 pub mod diff_util;
 pub mod formatter;
+pub mod gc_util;
 pub mod generic_templater;
 #[cfg(feature = "git")]
 pub mod git_util;
@@ -39,14 +40,18 @@ pub mod git_util {
     }
 }
 pub mod graphlog;
+pub mod lint_util;
 pub mod merge_tools;
 pub mod movement_util;
 pub mod operation_templater;
+pub mod patch_util;
 mod progress;
 pub mod revset_util;
 pub mod template_builder;
 pub mod template_parser;
 pub mod templater;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod text_util;
 pub mod time_util;
 pub mod ui;
@@ -0,0 +1,175 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks of commits configured by the `lint` table.
+
+use futures::StreamExt as _;
+use jj_lib::backend::BackendError;
+use jj_lib::backend::TreeValue;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetError;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::settings::HumanByteSize;
+use jj_lib::settings::UserSettings;
+use jj_lib::signing::SigStatus;
+use pollster::FutureExt as _;
+use tokio::io::AsyncReadExt as _;
+
+/// Problem found in a commit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LintViolation {
+    EmptyDescription,
+    SubjectTooLong {
+        length: usize,
+        max: usize,
+    },
+    ForbiddenPattern {
+        pattern: String,
+    },
+    MergeCommit,
+    NotSigned {
+        reason: &'static str,
+    },
+    FileTooLarge {
+        path: RepoPathBuf,
+        max: HumanByteSize,
+    },
+}
+
+/// Commit checks loaded from the `lint` config table.
+#[derive(Clone, Debug)]
+pub struct LintSettings {
+    pub forbid_empty_description: bool,
+    pub forbid_merge_commits: bool,
+    /// Substrings not allowed in descriptions.
+    pub forbidden_patterns: Vec<String>,
+    /// Maximum size of files added or modified by a commit. 0 disables the
+    /// check.
+    pub max_file_size: u64,
+    /// Maximum number of characters in the first line of descriptions. 0
+    /// disables the check.
+    pub max_subject_length: usize,
+    pub require_signed: bool,
+}
+
+impl LintSettings {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let HumanByteSize(max_file_size) =
+            settings.get_value_with("lint.max-file-size", TryInto::try_into)?;
+        Ok(Self {
+            forbid_empty_description: settings.get_bool("lint.forbid-empty-description")?,
+            forbid_merge_commits: settings.get_bool("lint.forbid-merge-commits")?,
+            forbidden_patterns: settings.get("lint.forbidden-patterns")?,
+            max_file_size,
+            max_subject_length: settings.get("lint.max-subject-length")?,
+            require_signed: settings.get_bool("lint.require-signed")?,
+        })
+    }
+
+    /// Runs the enabled checks on the `commit`.
+    pub fn check_commit(
+        &self,
+        repo: &dyn Repo,
+        commit: &Commit,
+    ) -> Result<Vec<LintViolation>, BackendError> {
+        let mut violations = vec![];
+        let description = commit.description();
+        if self.forbid_empty_description && description.trim().is_empty() {
+            violations.push(LintViolation::EmptyDescription);
+        }
+        if self.max_subject_length > 0 {
+            let length = description.lines().next().unwrap_or("").chars().count();
+            if length > self.max_subject_length {
+                violations.push(LintViolation::SubjectTooLong {
+                    length,
+                    max: self.max_subject_length,
+                });
+            }
+        }
+        for pattern in &self.forbidden_patterns {
+            if description.contains(pattern.as_str()) {
+                violations.push(LintViolation::ForbiddenPattern {
+                    pattern: pattern.clone(),
+                });
+            }
+        }
+        if self.forbid_merge_commits && commit.parent_ids().len() > 1 {
+            violations.push(LintViolation::MergeCommit);
+        }
+        if self.require_signed {
+            if let Some(reason) = missing_good_signature_reason(commit) {
+                violations.push(LintViolation::NotSigned { reason });
+            }
+        }
+        if self.max_file_size > 0 {
+            for path in self.find_large_files(repo, commit).block_on()? {
+                violations.push(LintViolation::FileTooLarge {
+                    path,
+                    max: HumanByteSize(self.max_file_size),
+                });
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Returns paths of files added or modified by the `commit` which are
+    /// larger than `max_file_size`.
+    async fn find_large_files(
+        &self,
+        repo: &dyn Repo,
+        commit: &Commit,
+    ) -> Result<Vec<RepoPathBuf>, BackendError> {
+        let store = repo.store();
+        let from_tree = commit.parent_tree(repo)?;
+        let to_tree = commit.tree()?;
+        let mut diff_stream = from_tree.diff_stream(&to_tree, &EverythingMatcher);
+        let mut paths = vec![];
+        while let Some(entry) = diff_stream.next().await {
+            let (_, after) = entry.values?;
+            let Some(Some(TreeValue::File { id, .. })) = after.as_resolved() else {
+                continue;
+            };
+            // Read no more than needed to tell if the file is too large.
+            let reader = store.read_file(&entry.path, id).await?;
+            let mut reader = reader.take(self.max_file_size + 1);
+            let size = tokio::io::copy(&mut reader, &mut tokio::io::sink())
+                .await
+                .map_err(|err| BackendError::ReadFile {
+                    path: entry.path.clone(),
+                    id: id.clone(),
+                    source: err.into(),
+                })?;
+            if size > self.max_file_size {
+                paths.push(entry.path);
+            }
+        }
+        Ok(paths)
+    }
+}
+
+/// Returns why the `commit` doesn't have a good signature, or `None` if it
+/// does.
+pub fn missing_good_signature_reason(commit: &Commit) -> Option<&'static str> {
+    match commit.verification() {
+        Ok(Some(verification)) => match verification.status {
+            SigStatus::Good => None,
+            SigStatus::Unknown => Some("unknown key"),
+            SigStatus::Bad => Some("bad signature"),
+        },
+        Ok(None) => Some("unsigned"),
+        Err(_) => Some("invalid signature"),
+    }
+}
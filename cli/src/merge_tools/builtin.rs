@@ -1,7 +1,15 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
+use std::iter;
+use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
 
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use futures::StreamExt as _;
 use futures::stream::BoxStream;
 use itertools::Itertools as _;
@@ -14,6 +22,7 @@ use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::conflicts::MIN_CONFLICT_MARKER_LEN;
 use jj_lib::conflicts::MaterializedTreeValue;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::conflicts::materialized_diff_stream;
 use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopyRecords;
@@ -29,11 +38,15 @@ use jj_lib::merged_tree::MergedTreeBuilder;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
 use pollster::FutureExt as _;
+use regex::Regex;
 use thiserror::Error;
 
+use super::MergeToolConfigError;
 use super::MergeToolFile;
+use crate::text_util;
 
 #[derive(Debug, Error)]
 pub enum BuiltinToolError {
@@ -65,6 +78,22 @@ enum FileContents {
 }
 
 impl FileContents {
+    /// Returns the text contents, or an empty string if the file is absent.
+    fn text(&self) -> Option<&str> {
+        match self {
+            Self::Absent => Some(""),
+            Self::Text {
+                contents,
+                hash: _,
+                num_bytes: _,
+            } => Some(contents),
+            Self::Binary {
+                hash: _,
+                num_bytes: _,
+            } => None,
+        }
+    }
+
     fn describe(&self) -> Option<String> {
         match self {
             Self::Absent => None,
@@ -376,6 +405,91 @@ async fn make_diff_files(
     Ok((changed_files, files))
 }
 
+/// Checks the changes in `files` which are included in the `initial_tree`, so
+/// the selection starts from there.
+fn check_initial_changes(
+    store: &Arc<Store>,
+    left_tree: &MergedTree,
+    right_tree: &MergedTree,
+    initial_tree: &MergedTree,
+    changed_files: &[RepoPathBuf],
+    files: &mut [scm_record::File],
+    conflict_marker_style: ConflictMarkerStyle,
+) -> Result<(), BuiltinToolError> {
+    for (path, file) in changed_files.iter().zip(files) {
+        let left_value = left_tree.path_value(path)?;
+        let right_value = right_tree.path_value(path)?;
+        let initial_value = initial_tree.path_value(path)?;
+        if initial_value == left_value {
+            continue;
+        } else if initial_value == right_value {
+            file.set_checked(true);
+            continue;
+        }
+        let read_info = |value| {
+            let materialized = materialize_tree_value(store, path, value).block_on()?;
+            read_file_contents(materialized, path, conflict_marker_style)
+        };
+        let left_info = read_info(left_value)?;
+        let right_info = read_info(right_value)?;
+        let initial_info = read_info(initial_value)?;
+        let (Some(left_text), Some(right_text), Some(initial_text)) = (
+            left_info.contents.text(),
+            right_info.contents.text(),
+            initial_info.contents.text(),
+        ) else {
+            continue;
+        };
+        // A removed line is selected if it's gone from the initial file, and
+        // an added line is selected if it's in the initial file.
+        let left_lines_kept = lines_kept_in(left_text, initial_text);
+        let right_lines_kept = lines_kept_in(right_text, initial_text);
+        let mut left_line = 0;
+        let mut right_line = 0;
+        for section in &mut file.sections {
+            match section {
+                scm_record::Section::Unchanged { lines } => {
+                    left_line += lines.len();
+                    right_line += lines.len();
+                }
+                scm_record::Section::Changed { lines } => {
+                    for line in lines {
+                        line.is_checked = match line.change_type {
+                            scm_record::ChangeType::Removed => {
+                                let kept = left_lines_kept.get(left_line);
+                                left_line += 1;
+                                kept == Some(&false)
+                            }
+                            scm_record::ChangeType::Added => {
+                                let kept = right_lines_kept.get(right_line);
+                                right_line += 1;
+                                kept == Some(&true)
+                            }
+                        };
+                    }
+                }
+                scm_record::Section::FileMode { is_checked, mode } => {
+                    *is_checked = initial_info.file_mode == *mode;
+                }
+                scm_record::Section::Binary { .. } => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether each line of `from` is kept in `to`.
+fn lines_kept_in(from: &str, to: &str) -> Vec<bool> {
+    let diff = Diff::by_line([from.as_bytes(), to.as_bytes()]);
+    let mut kept = Vec::new();
+    for hunk in diff.hunks() {
+        let num_lines = hunk.contents[0].split_inclusive(|b| *b == b'\n').count();
+        let is_matching = hunk.kind == DiffHunkKind::Matching;
+        kept.extend(iter::repeat_n(is_matching, num_lines));
+    }
+    kept
+}
+
 fn apply_diff_builtin(
     store: &Arc<Store>,
     left_tree: &MergedTree,
@@ -533,19 +647,253 @@ fn override_file_executable_bit(
     merged_tree_value
 }
 
+/// Action of the builtin diff editor that can be bound to a key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum BuiltinEditorAction {
+    Toggle,
+    NextFile,
+    PrevFile,
+    ToggleFile,
+    Confirm,
+    Abort,
+    ScrollHalfPageUp,
+    ScrollHalfPageDown,
+}
+
+impl BuiltinEditorAction {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "toggle" => Some(Self::Toggle),
+            "next-file" => Some(Self::NextFile),
+            "prev-file" => Some(Self::PrevFile),
+            "toggle-file" => Some(Self::ToggleFile),
+            "confirm" => Some(Self::Confirm),
+            "abort" => Some(Self::Abort),
+            "scroll-half-page-up" => Some(Self::ScrollHalfPageUp),
+            "scroll-half-page-down" => Some(Self::ScrollHalfPageDown),
+            _ => None,
+        }
+    }
+
+    fn to_events(self, term_height: u16) -> Vec<scm_record::Event> {
+        use scm_record::Event;
+        // The editor can only scroll by a line or by a full page.
+        let half_page = usize::from(term_height / 2).max(1);
+        match self {
+            Self::Toggle => vec![Event::ToggleItem],
+            Self::NextFile => vec![Event::FocusNextSameKind],
+            Self::PrevFile => vec![Event::FocusPrevSameKind],
+            // Move the focus from a line up to its file, then toggle the file.
+            Self::ToggleFile => vec![
+                Event::FocusOuter {
+                    fold_section: false,
+                },
+                Event::FocusOuter {
+                    fold_section: false,
+                },
+                Event::ToggleItem,
+            ],
+            Self::Confirm => vec![Event::QuitAccept],
+            Self::Abort => vec![Event::QuitCancel],
+            Self::ScrollHalfPageUp => vec![Event::ScrollUp; half_page],
+            Self::ScrollHalfPageDown => vec![Event::ScrollDown; half_page],
+        }
+    }
+}
+
+/// Parses key binding such as `t`, `space`, or `ctrl-d`.
+fn parse_key_binding(text: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = text;
+    loop {
+        if let Some(tail) = rest.strip_prefix("ctrl-") {
+            modifiers |= KeyModifiers::CONTROL;
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("alt-") {
+            modifiers |= KeyModifiers::ALT;
+            rest = tail;
+        } else {
+            break;
+        }
+    }
+    let code = match rest {
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        _ => KeyCode::Char(rest.chars().exactly_one().ok()?),
+    };
+    Some((code, modifiers))
+}
+
+/// Key and mouse settings of the builtin diff editor.
+#[derive(Clone, Debug, Default)]
+pub struct BuiltinEditorOptions {
+    key_bindings: HashMap<(KeyCode, KeyModifiers), BuiltinEditorAction>,
+    /// Custom key bindings shown at the bottom of the editor.
+    help_text: String,
+    mouse: bool,
+}
+
+impl BuiltinEditorOptions {
+    /// Loads `ui.diff-editor-keys` and `ui.diff-editor-mouse` from the
+    /// settings.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, MergeToolConfigError> {
+        let keys: BTreeMap<String, String> = settings.get("ui.diff-editor-keys")?;
+        let help_text = keys
+            .iter()
+            .map(|(name, key)| format!("{key}: {name}"))
+            .join("  ");
+        let key_bindings = keys
+            .into_iter()
+            .map(|(name, key)| {
+                let action = BuiltinEditorAction::from_name(&name).ok_or_else(|| {
+                    MergeToolConfigError::UnknownEditorAction {
+                        action: name.clone(),
+                    }
+                })?;
+                let binding = parse_key_binding(&key)
+                    .ok_or(MergeToolConfigError::InvalidKeyBinding { action: name, key })?;
+                Ok::<_, MergeToolConfigError>((binding, action))
+            })
+            .try_collect()?;
+        Ok(Self {
+            key_bindings,
+            help_text,
+            mouse: settings.get_bool("ui.diff-editor-mouse")?,
+        })
+    }
+
+    /// Translates terminal event to the events of the editor. Configured key
+    /// bindings take precedence over the default ones.
+    fn translate_event(
+        &self,
+        event: crossterm::event::Event,
+        term_height: u16,
+    ) -> Vec<scm_record::Event> {
+        match &event {
+            crossterm::event::Event::Key(key) if key.kind == KeyEventKind::Press => {
+                let mut modifiers = key.modifiers;
+                if matches!(key.code, KeyCode::Char(_)) {
+                    // Uppercase letters are reported with the shift modifier.
+                    modifiers.remove(KeyModifiers::SHIFT);
+                }
+                if let Some(action) = self.key_bindings.get(&(key.code, modifiers)) {
+                    return action.to_events(term_height);
+                }
+            }
+            crossterm::event::Event::Mouse(_) if !self.mouse => {
+                return vec![scm_record::Event::None];
+            }
+            _ => {}
+        }
+        vec![event.into()]
+    }
+}
+
+/// Reads terminal events and translates them per [`BuiltinEditorOptions`].
+struct BuiltinEditorInput<'a> {
+    options: &'a BuiltinEditorOptions,
+    mouse_captured: bool,
+}
+
+impl<'a> BuiltinEditorInput<'a> {
+    fn new(options: &'a BuiltinEditorOptions) -> Self {
+        Self {
+            options,
+            mouse_captured: false,
+        }
+    }
+
+    fn draw_help_footer(&self, term_width: u16, term_height: u16) -> io::Result<()> {
+        use crossterm::style::Attribute;
+        use crossterm::style::Print;
+        use crossterm::style::SetAttribute;
+        let (text, _) = text_util::elide_end(&self.options.help_text, "", usize::from(term_width));
+        crossterm::execute!(
+            io::stdout(),
+            crossterm::cursor::SavePosition,
+            crossterm::cursor::MoveTo(0, term_height.saturating_sub(1)),
+            crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine),
+            SetAttribute(Attribute::Reverse),
+            Print(text),
+            SetAttribute(Attribute::Reset),
+            crossterm::cursor::RestorePosition,
+        )
+    }
+}
+
+impl scm_record::RecordInput for BuiltinEditorInput<'_> {
+    fn terminal_kind(&self) -> scm_record::TerminalKind {
+        scm_record::TerminalKind::Crossterm
+    }
+
+    fn next_events(&mut self) -> Result<Vec<scm_record::Event>, scm_record::RecordError> {
+        // The terminal is set up by the recorder before the first read.
+        if self.options.mouse && !self.mouse_captured {
+            crossterm::execute!(io::stdout(), crossterm::event::EnableMouseCapture)
+                .map_err(scm_record::RecordError::SetUpTerminal)?;
+            self.mouse_captured = true;
+        }
+        let (term_width, term_height) =
+            crossterm::terminal::size().map_err(scm_record::RecordError::ReadInput)?;
+        if !self.options.help_text.is_empty() {
+            // Draw over the last row of the screen rendered by the recorder.
+            self.draw_help_footer(term_width, term_height)
+                .map_err(scm_record::RecordError::RenderFrame)?;
+        }
+        let event = crossterm::event::read().map_err(scm_record::RecordError::ReadInput)?;
+        Ok(self.options.translate_event(event, term_height))
+    }
+
+    fn edit_commit_message(&mut self, message: &str) -> Result<String, scm_record::RecordError> {
+        scm_record::helpers::CrosstermInput.edit_commit_message(message)
+    }
+}
+
+impl Drop for BuiltinEditorInput<'_> {
+    fn drop(&mut self) {
+        if self.mouse_captured {
+            crossterm::execute!(io::stdout(), crossterm::event::DisableMouseCapture).ok();
+        }
+    }
+}
+
 pub fn edit_diff_builtin(
     left_tree: &MergedTree,
     right_tree: &MergedTree,
+    initial_tree: Option<&MergedTree>,
     matcher: &dyn Matcher,
     conflict_marker_style: ConflictMarkerStyle,
+    options: &BuiltinEditorOptions,
 ) -> Result<MergedTreeId, BuiltinToolError> {
     let store = left_tree.store().clone();
     // TODO: handle copy tracking
     let copy_records = CopyRecords::default();
     let tree_diff = left_tree.diff_stream_with_copies(right_tree, matcher, &copy_records);
-    let (changed_files, files) =
+    let (changed_files, mut files) =
         make_diff_files(&store, tree_diff, conflict_marker_style).block_on()?;
-    let mut input = scm_record::helpers::CrosstermInput;
+    if let Some(initial_tree) = initial_tree {
+        check_initial_changes(
+            &store,
+            left_tree,
+            right_tree,
+            initial_tree,
+            &changed_files,
+            &mut files,
+            conflict_marker_style,
+        )?;
+    }
+    let mut input = BuiltinEditorInput::new(options);
     let recorder = scm_record::Recorder::new(
         scm_record::RecordState {
             is_read_only: false,
@@ -567,6 +915,98 @@ pub fn edit_diff_builtin(
     Ok(tree_id)
 }
 
+/// A hunk selected by [`select_matching_hunks()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchingHunk {
+    pub path: RepoPathBuf,
+    /// 0-based range of the removed lines in the left file.
+    pub left_range: Range<usize>,
+    /// 0-based range of the added lines in the right file.
+    pub right_range: Range<usize>,
+}
+
+/// Selects the hunks of the diff between the trees that have an added or
+/// removed line matching `pattern`, without user interaction.
+///
+/// The hunks are the same as the ones shown by the builtin diff editor. If a
+/// file is added or deleted, the file is created or deleted along with its
+/// selected hunks.
+pub fn select_matching_hunks(
+    left_tree: &MergedTree,
+    right_tree: &MergedTree,
+    matcher: &dyn Matcher,
+    conflict_marker_style: ConflictMarkerStyle,
+    pattern: &Regex,
+) -> Result<(MergedTreeId, Vec<MatchingHunk>), BuiltinToolError> {
+    let store = left_tree.store().clone();
+    let copy_records = CopyRecords::default();
+    let tree_diff = left_tree.diff_stream_with_copies(right_tree, matcher, &copy_records);
+    let (changed_files, mut files) =
+        make_diff_files(&store, tree_diff, conflict_marker_style).block_on()?;
+    let mut hunks = Vec::new();
+    for (path, file) in changed_files.iter().zip(&mut files) {
+        let mut left_line = 0;
+        let mut right_line = 0;
+        let mut any_selected = false;
+        for section in &mut file.sections {
+            match section {
+                scm_record::Section::Unchanged { lines } => {
+                    left_line += lines.len();
+                    right_line += lines.len();
+                }
+                scm_record::Section::Changed { lines } => {
+                    let num_removed = lines
+                        .iter()
+                        .filter(|line| matches!(line.change_type, scm_record::ChangeType::Removed))
+                        .count();
+                    let num_added = lines.len() - num_removed;
+                    if lines
+                        .iter()
+                        .any(|line| pattern.is_match(line.line.trim_end_matches('\n')))
+                    {
+                        for line in lines.iter_mut() {
+                            line.is_checked = true;
+                        }
+                        any_selected = true;
+                        hunks.push(MatchingHunk {
+                            path: path.clone(),
+                            left_range: left_line..left_line + num_removed,
+                            right_range: right_line..right_line + num_added,
+                        });
+                    }
+                    left_line += num_removed;
+                    right_line += num_added;
+                }
+                scm_record::Section::FileMode { .. } | scm_record::Section::Binary { .. } => {}
+            }
+        }
+        // Create or delete the file along with its contents.
+        if any_selected {
+            let is_added = file.file_mode == mode::ABSENT;
+            for section in &mut file.sections {
+                if let scm_record::Section::FileMode {
+                    is_checked,
+                    mode: new_mode,
+                } = section
+                {
+                    if is_added || *new_mode == mode::ABSENT {
+                        *is_checked = true;
+                    }
+                }
+            }
+        }
+    }
+    let tree_id = apply_diff_builtin(
+        &store,
+        left_tree,
+        right_tree,
+        changed_files,
+        &files,
+        conflict_marker_style,
+    )?;
+    Ok((tree_id, hunks))
+}
+
 fn make_merge_sections(
     merge_result: MergeResult,
 ) -> Result<Vec<scm_record::Section<'static>>, BuiltinToolError> {
@@ -720,6 +1160,8 @@ pub fn edit_merge_builtin(
 mod tests {
     use std::collections::BTreeSet;
 
+    use assert_matches::assert_matches;
+
     use jj_lib::backend::FileId;
     use jj_lib::conflicts::extract_as_single_hunk;
     use jj_lib::matchers::EverythingMatcher;
@@ -778,6 +1220,110 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn test_parse_key_binding() {
+        assert_eq!(
+            parse_key_binding("t"),
+            Some((KeyCode::Char('t'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse_key_binding("space"),
+            Some((KeyCode::Char(' '), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse_key_binding("ctrl-alt-d"),
+            Some((
+                KeyCode::Char('d'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(parse_key_binding(""), None);
+        assert_eq!(parse_key_binding("ctrl-"), None);
+        assert_eq!(parse_key_binding("tt"), None);
+    }
+
+    #[test]
+    fn test_builtin_editor_remapped_key() {
+        use crossterm::event::Event as TermEvent;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::MouseEvent;
+        use crossterm::event::MouseEventKind;
+        use scm_record::Event;
+
+        let options = BuiltinEditorOptions {
+            key_bindings: HashMap::from([
+                (parse_key_binding("t").unwrap(), BuiltinEditorAction::Toggle),
+                (
+                    parse_key_binding("ctrl-s").unwrap(),
+                    BuiltinEditorAction::Confirm,
+                ),
+                (
+                    parse_key_binding("ctrl-d").unwrap(),
+                    BuiltinEditorAction::ScrollHalfPageDown,
+                ),
+            ]),
+            help_text: String::new(),
+            mouse: false,
+        };
+        let key = |code, modifiers| TermEvent::Key(KeyEvent::new(code, modifiers));
+
+        assert_matches!(
+            options.translate_event(key(KeyCode::Char('t'), KeyModifiers::NONE), 24)[..],
+            [Event::ToggleItem]
+        );
+        // Shift isn't distinguished from the character itself
+        assert_matches!(
+            options.translate_event(key(KeyCode::Char('t'), KeyModifiers::SHIFT), 24)[..],
+            [Event::ToggleItem]
+        );
+        assert_matches!(
+            options.translate_event(key(KeyCode::Char('s'), KeyModifiers::CONTROL), 24)[..],
+            [Event::QuitAccept]
+        );
+        // Scrolls by half of the terminal height
+        assert_eq!(
+            options.translate_event(key(KeyCode::Char('d'), KeyModifiers::CONTROL), 24),
+            vec![Event::ScrollDown; 12]
+        );
+        // The default binding is still available
+        assert_matches!(
+            options.translate_event(key(KeyCode::Char(' '), KeyModifiers::NONE), 24)[..],
+            [Event::ToggleItem]
+        );
+
+        // Mouse events are ignored unless enabled
+        let wheel = TermEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_matches!(
+            options.translate_event(wheel.clone(), 24)[..],
+            [Event::None]
+        );
+        let options = BuiltinEditorOptions {
+            mouse: true,
+            ..options
+        };
+        assert_matches!(options.translate_event(wheel, 24)[..], [Event::ScrollDown]);
+    }
+
+    #[test]
+    fn test_builtin_editor_help_text() {
+        use jj_lib::config::ConfigLayer;
+        use jj_lib::config::ConfigSource;
+        use jj_lib::config::StackedConfig;
+
+        let mut config = StackedConfig::with_defaults();
+        config.extend_layers(crate::config::default_config_layers());
+        let text = "ui.diff-editor-keys = { toggle = 't', toggle-file = 'alt-a' }";
+        config.add_layer(ConfigLayer::parse(ConfigSource::User, text).unwrap());
+        let settings = UserSettings::from_config(config).unwrap();
+        let options = BuiltinEditorOptions::from_settings(&settings).unwrap();
+        insta::assert_snapshot!(options.help_text, @"t: toggle  alt-a: toggle-file");
+    }
+
     #[test]
     fn test_edit_diff_builtin() {
         let test_repo = TestRepo::init();
@@ -907,6 +1453,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_edit_diff_builtin_initial_tree() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        let changed_path = repo_path("changed");
+        let added_path = repo_path("added");
+        let removed_path = repo_path("removed");
+        let left_tree = testutils::create_tree(
+            &test_repo.repo,
+            &[
+                (changed_path, "line1\nline2\nline3\n"),
+                (removed_path, "removed\n"),
+            ],
+        );
+        let right_tree = testutils::create_tree(
+            &test_repo.repo,
+            &[
+                (changed_path, "line1\nchanged1\nchanged2\nline3\nadded1\n"),
+                (added_path, "added\n"),
+            ],
+        );
+        // Part of the changes to the changed file, and the removal
+        let initial_tree = testutils::create_tree(
+            &test_repo.repo,
+            &[(changed_path, "line1\nchanged1\nline3\nadded1\n")],
+        );
+
+        let (changed_files, mut files) = make_diff(store, &left_tree, &right_tree);
+        check_initial_changes(
+            store,
+            &left_tree,
+            &right_tree,
+            &initial_tree,
+            &changed_files,
+            &mut files,
+            ConflictMarkerStyle::Diff,
+        )
+        .unwrap();
+        let checked_lines = files
+            .iter()
+            .map(|file| {
+                let lines = file
+                    .sections
+                    .iter()
+                    .flat_map(|section| match section {
+                        scm_record::Section::Changed { lines } => lines
+                            .iter()
+                            .map(|line| (line.line.to_string(), line.is_checked))
+                            .collect(),
+                        scm_record::Section::FileMode { is_checked, mode } => {
+                            vec![(format!("{mode:?}"), *is_checked)]
+                        }
+                        _ => vec![],
+                    })
+                    .collect_vec();
+                (file.path.to_string_lossy().into_owned(), lines)
+            })
+            .collect_vec();
+        insta::assert_debug_snapshot!(checked_lines, @r#"
+        [
+            (
+                "added",
+                [
+                    (
+                        "Unix(33188)",
+                        false,
+                    ),
+                    (
+                        "added\n",
+                        false,
+                    ),
+                ],
+            ),
+            (
+                "changed",
+                [
+                    (
+                        "line2\n",
+                        true,
+                    ),
+                    (
+                        "changed1\n",
+                        true,
+                    ),
+                    (
+                        "changed2\n",
+                        false,
+                    ),
+                    (
+                        "added1\n",
+                        true,
+                    ),
+                ],
+            ),
+            (
+                "removed",
+                [
+                    (
+                        "Absent",
+                        true,
+                    ),
+                    (
+                        "removed\n",
+                        true,
+                    ),
+                ],
+            ),
+        ]
+        "#);
+
+        let tree_id = apply_diff(store, &left_tree, &right_tree, &changed_files, &files);
+        assert_tree_eq!(
+            &initial_tree.id(),
+            &tree_id,
+            store,
+            "initial tree was different",
+        );
+    }
+
     #[test]
     fn test_edit_diff_builtin_add_empty_file() {
         let test_repo = TestRepo::init();
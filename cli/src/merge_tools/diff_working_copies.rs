@@ -127,10 +127,14 @@ pub(crate) enum DiffType {
 
 /// Check out the two trees in temporary directories. Only include changed files
 /// in the sparse checkout patterns.
+///
+/// The side to be edited (the right side, or the output with
+/// [`DiffType::ThreeWay`]) is checked out from `initial_tree` if specified.
 pub(crate) fn check_out_trees(
     store: &Arc<Store>,
     left_tree: &MergedTree,
     right_tree: &MergedTree,
+    initial_tree: Option<&MergedTree>,
     matcher: &dyn Matcher,
     diff_type: DiffType,
     options: &CheckoutOptions,
@@ -161,11 +165,14 @@ pub(crate) fn check_out_trees(
         Ok(state)
     };
 
+    let initial_tree = initial_tree.unwrap_or(right_tree);
     let left = check_out("left", left_tree)?;
-    let right = check_out("right", right_tree)?;
-    let output = match diff_type {
-        DiffType::TwoWay => None,
-        DiffType::ThreeWay => Some(check_out("output", right_tree)?),
+    let (right, output) = match diff_type {
+        DiffType::TwoWay => (check_out("right", initial_tree)?, None),
+        DiffType::ThreeWay => (
+            check_out("right", right_tree)?,
+            Some(check_out("output", initial_tree)?),
+        ),
     };
     Ok(DiffWorkingCopies {
         _temp_dir: temp_dir,
@@ -183,17 +190,26 @@ pub(crate) struct DiffEditWorkingCopies {
 impl DiffEditWorkingCopies {
     /// Checks out the trees, populates JJ_INSTRUCTIONS, and makes appropriate
     /// sides readonly.
+    #[expect(clippy::too_many_arguments)]
     pub fn check_out(
         store: &Arc<Store>,
         left_tree: &MergedTree,
         right_tree: &MergedTree,
+        initial_tree: Option<&MergedTree>,
         matcher: &dyn Matcher,
         diff_type: DiffType,
         instructions: Option<&str>,
         options: &CheckoutOptions,
     ) -> Result<Self, DiffEditError> {
-        let working_copies =
-            check_out_trees(store, left_tree, right_tree, matcher, diff_type, options)?;
+        let working_copies = check_out_trees(
+            store,
+            left_tree,
+            right_tree,
+            initial_tree,
+            matcher,
+            diff_type,
+            options,
+        )?;
         working_copies.set_left_readonly()?;
         if diff_type == DiffType::ThreeWay {
             working_copies.set_right_readonly()?;
@@ -376,10 +376,12 @@ pub fn run_mergetool_external(
     Ok((new_tree, partial_resolution_error))
 }
 
+#[expect(clippy::too_many_arguments)]
 pub fn edit_diff_external(
     editor: &ExternalMergeTool,
     left_tree: &MergedTree,
     right_tree: &MergedTree,
+    initial_tree: Option<&MergedTree>,
     matcher: &dyn Matcher,
     instructions: Option<&str>,
     base_ignores: Arc<GitIgnoreFile>,
@@ -403,6 +405,7 @@ pub fn edit_diff_external(
         store,
         left_tree,
         right_tree,
+        initial_tree,
         matcher,
         diff_type,
         instructions,
@@ -449,6 +452,7 @@ pub fn generate_diff(
         store,
         left_tree,
         right_tree,
+        None,
         matcher,
         DiffType::TwoWay,
         &options,
@@ -506,21 +510,17 @@ pub fn invoke_external_diff(
             source,
         })?;
     let copy_result = io::copy(&mut child.stdout.take().unwrap(), writer);
-    // Non-zero exit code isn't an error. For example, the traditional diff command
-    // will exit with 1 if inputs are different.
+    // Expected non-zero exit codes aren't errors. For example, the traditional
+    // diff command will exit with 1 if inputs are different.
     let exit_status = child.wait().map_err(ExternalToolError::Io)?;
     tracing::info!(?cmd, ?exit_status, "The external diff generator exited:");
     let exit_ok = exit_status
         .code()
         .is_some_and(|status| tool.diff_expected_exit_codes.contains(&status));
+    copy_result.map_err(ExternalToolError::Io)?;
     if !exit_ok {
-        writeln!(
-            ui.warning_default(),
-            "Tool exited with {exit_status} (run with --debug to see the exact invocation)",
-        )
-        .ok();
+        return Err(ExternalToolError::ToolAborted { exit_status }.into());
     }
-    copy_result.map_err(ExternalToolError::Io)?;
     Ok(())
 }
 
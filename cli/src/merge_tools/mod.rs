@@ -42,9 +42,12 @@ use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::settings::UserSettings;
 use jj_lib::working_copy::SnapshotError;
 use pollster::FutureExt as _;
+use regex::Regex;
 use thiserror::Error;
 
+use self::builtin::BuiltinEditorOptions;
 use self::builtin::BuiltinToolError;
+pub use self::builtin::MatchingHunk;
 use self::builtin::edit_diff_builtin;
 use self::builtin::edit_merge_builtin;
 use self::diff_working_copies::DiffCheckoutError;
@@ -132,6 +135,10 @@ pub enum MergeToolConfigError {
     MergeArgsNotConfigured { tool_name: String },
     #[error("The tool `{tool_name}` cannot be used as a diff editor")]
     EditArgsNotConfigured { tool_name: String },
+    #[error("Unknown action `{action}` in `ui.diff-editor-keys`")]
+    UnknownEditorAction { action: String },
+    #[error("Invalid key `{key}` for action `{action}` in `ui.diff-editor-keys`")]
+    InvalidKeyBinding { action: String, key: String },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -237,6 +244,7 @@ pub struct DiffEditor {
     base_ignores: Arc<GitIgnoreFile>,
     use_instructions: bool,
     conflict_marker_style: ConflictMarkerStyle,
+    builtin_options: BuiltinEditorOptions,
 }
 
 impl DiffEditor {
@@ -282,11 +290,16 @@ impl DiffEditor {
                 tool_name: name.to_string(),
             });
         }
+        let builtin_options = match &tool {
+            DiffEditTool::Builtin => BuiltinEditorOptions::from_settings(settings)?,
+            DiffEditTool::External(_) => BuiltinEditorOptions::default(),
+        };
         Ok(Self {
             tool,
             base_ignores,
             use_instructions: settings.get_bool("ui.diff-instructions")?,
             conflict_marker_style,
+            builtin_options,
         })
     }
 
@@ -297,20 +310,41 @@ impl DiffEditor {
         right_tree: &MergedTree,
         matcher: &dyn Matcher,
         format_instructions: impl FnOnce() -> String,
+    ) -> Result<MergedTreeId, DiffEditError> {
+        self.edit_with_initial_tree(left_tree, right_tree, None, matcher, format_instructions)
+    }
+
+    /// Starts a diff editor on the two directories, with the edited side
+    /// initially set to `initial_tree` instead of the `right_tree`.
+    ///
+    /// The `initial_tree` is supposed to contain a subset of the changes
+    /// between the `left_tree` and the `right_tree`, such as a previous
+    /// selection.
+    pub fn edit_with_initial_tree(
+        &self,
+        left_tree: &MergedTree,
+        right_tree: &MergedTree,
+        initial_tree: Option<&MergedTree>,
+        matcher: &dyn Matcher,
+        format_instructions: impl FnOnce() -> String,
     ) -> Result<MergedTreeId, DiffEditError> {
         match &self.tool {
-            DiffEditTool::Builtin => {
-                Ok(
-                    edit_diff_builtin(left_tree, right_tree, matcher, self.conflict_marker_style)
-                        .map_err(Box::new)?,
-                )
-            }
+            DiffEditTool::Builtin => Ok(edit_diff_builtin(
+                left_tree,
+                right_tree,
+                initial_tree,
+                matcher,
+                self.conflict_marker_style,
+                &self.builtin_options,
+            )
+            .map_err(Box::new)?),
             DiffEditTool::External(editor) => {
                 let instructions = self.use_instructions.then(format_instructions);
                 edit_diff_external(
                     editor,
                     left_tree,
                     right_tree,
+                    initial_tree,
                     matcher,
                     instructions.as_deref(),
                     self.base_ignores.clone(),
@@ -321,6 +355,25 @@ impl DiffEditor {
     }
 }
 
+/// Selects the hunks of the diff between the trees that have a changed line
+/// matching `pattern`. The hunks are split as in the builtin diff editor.
+pub fn select_matching_hunks(
+    left_tree: &MergedTree,
+    right_tree: &MergedTree,
+    matcher: &dyn Matcher,
+    conflict_marker_style: ConflictMarkerStyle,
+    pattern: &Regex,
+) -> Result<(MergedTreeId, Vec<MatchingHunk>), DiffEditError> {
+    Ok(builtin::select_matching_hunks(
+        left_tree,
+        right_tree,
+        matcher,
+        conflict_marker_style,
+        pattern,
+    )
+    .map_err(Box::new)?)
+}
+
 /// A file to be merged by a merge tool.
 struct MergeToolFile {
     repo_path: RepoPathBuf,
@@ -784,6 +837,42 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn test_get_diff_editor_key_bindings() {
+        let get = |text| {
+            let config = config_from_string(text);
+            let settings = UserSettings::from_config(config).unwrap();
+            DiffEditor::with_name(
+                ":builtin",
+                &settings,
+                GitIgnoreFile::empty(),
+                ConflictMarkerStyle::Diff,
+            )
+            .map(|_| ())
+        };
+
+        assert!(get("ui.diff-editor-keys = { toggle = 't', confirm = 'ctrl-s' }").is_ok());
+        insta::assert_snapshot!(
+            get("ui.diff-editor-keys = { toggle = 'ctrl-' }").unwrap_err(),
+            @"Invalid key `ctrl-` for action `toggle` in `ui.diff-editor-keys`");
+        insta::assert_snapshot!(
+            get("ui.diff-editor-keys = { explode = 'x' }").unwrap_err(),
+            @"Unknown action `explode` in `ui.diff-editor-keys`");
+
+        // Key bindings aren't loaded for external tools
+        let config = config_from_string("ui.diff-editor-keys = { explode = 'x' }");
+        let settings = UserSettings::from_config(config).unwrap();
+        assert!(
+            DiffEditor::with_name(
+                "my diff",
+                &settings,
+                GitIgnoreFile::empty(),
+                ConflictMarkerStyle::Diff,
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn test_get_merge_editor_with_name() {
         let get = |name, config_text| {
@@ -18,6 +18,7 @@ use std::collections::HashMap;
 use std::io;
 
 use itertools::Itertools as _;
+use jj_lib::backend::Timestamp;
 use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::op_store::OperationId;
@@ -206,6 +207,10 @@ impl<'a> OperationTemplatePropertyKind<'a> {
         None
     }
 
+    pub fn try_into_timestamp(self) -> Option<BoxedTemplateProperty<'a, Timestamp>> {
+        None
+    }
+
     pub fn try_into_stringify(self) -> Option<BoxedTemplateProperty<'a, String>> {
         let template = self.try_into_template()?;
         Some(PlainTextFormattedProperty::new(template).into_dyn())
@@ -311,6 +316,13 @@ impl CoreTemplatePropertyVar<'static> for OperationTemplateLanguagePropertyKind
         }
     }
 
+    fn try_into_timestamp(self) -> Option<BoxedTemplateProperty<'static, Timestamp>> {
+        match self {
+            Self::Core(property) => property.try_into_timestamp(),
+            Self::Operation(property) => property.try_into_timestamp(),
+        }
+    }
+
     fn try_into_stringify(self) -> Option<BoxedTemplateProperty<'static, String>> {
         match self {
             Self::Core(property) => property.try_into_stringify(),
@@ -522,6 +534,31 @@ where
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "workspace",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|op| {
+                op.metadata()
+                    .workspace_name
+                    .as_ref()
+                    .map_or_else(String::new, |name| name.as_str().to_owned())
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "cwd",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|op| {
+                op.metadata().cwd.as_ref().map_or_else(String::new, |path| {
+                    path.as_internal_file_string().to_owned()
+                })
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "time",
         |_language, _diagnostics, _build_ctx, self_property, function| {
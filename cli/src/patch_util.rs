@@ -0,0 +1,422 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing and application of unified diffs.
+
+use std::sync::Arc;
+
+use jj_lib::backend::BackendError;
+use jj_lib::backend::CopyId;
+use jj_lib::backend::MergedTreeId;
+use jj_lib::backend::TreeValue;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::merged_tree::MergedTreeBuilder;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::store::Store;
+use pollster::FutureExt as _;
+use thiserror::Error;
+use tokio::io::AsyncReadExt as _;
+
+#[derive(Debug, Error)]
+pub enum PatchError {
+    #[error("Invalid patch at line {line}: {message}")]
+    Parse { line: usize, message: String },
+    #[error("Patch for {path} does not apply: hunk #{hunk} at patch line {line}: {message}")]
+    Apply {
+        path: String,
+        hunk: usize,
+        line: usize,
+        message: String,
+    },
+    #[error("Patch for {path} does not apply: {message}")]
+    File { path: String, message: String },
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// Changes to a single file in a patch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilePatch {
+    /// Path before the change, or `None` if the file is added.
+    pub old_path: Option<String>,
+    /// Path after the change, or `None` if the file is deleted.
+    pub new_path: Option<String>,
+    /// Whether a new file should be executable.
+    pub executable: bool,
+    pub hunks: Vec<PatchHunk>,
+}
+
+impl FilePatch {
+    fn display_path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Single `@@` section of a file patch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatchHunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<PatchLine>,
+    /// 1-based line number of the `@@` header in the patch.
+    pub patch_line: usize,
+}
+
+/// Line of a hunk, including its line terminator if any.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PatchLine {
+    Context(Vec<u8>),
+    Removed(Vec<u8>),
+    Added(Vec<u8>),
+}
+
+impl PatchLine {
+    fn content_mut(&mut self) -> &mut Vec<u8> {
+        match self {
+            Self::Context(content) | Self::Removed(content) | Self::Added(content) => content,
+        }
+    }
+}
+
+/// Parses a unified diff, such as the output of `jj diff --git`.
+///
+/// Lines outside of file patches (e.g. commit headers) are ignored.
+pub fn parse_patch(text: &str) -> Result<Vec<FilePatch>, PatchError> {
+    let mut patches: Vec<FilePatch> = vec![];
+    let mut lines = text.split_inclusive('\n').enumerate().peekable();
+    let mut new_file_executable = false;
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        let parse_error = |message: &str| PatchError::Parse {
+            line: line_number,
+            message: message.to_owned(),
+        };
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        if trimmed.starts_with("diff ") {
+            new_file_executable = false;
+        } else if let Some(mode) = trimmed.strip_prefix("new file mode ") {
+            new_file_executable = mode == "100755";
+        } else if let Some(old) = trimmed.strip_prefix("--- ") {
+            let Some((_, new_line)) = lines.next() else {
+                return Err(parse_error("expected `+++` line"));
+            };
+            let Some(new) = new_line.trim_end_matches(['\n', '\r']).strip_prefix("+++ ") else {
+                return Err(parse_error("expected `+++` line"));
+            };
+            patches.push(FilePatch {
+                old_path: parse_path(old, "a/"),
+                new_path: parse_path(new, "b/"),
+                executable: new_file_executable,
+                hunks: vec![],
+            });
+        } else if trimmed.starts_with("@@ ") {
+            let Some(patch) = patches.last_mut() else {
+                return Err(parse_error("hunk without file header"));
+            };
+            let (old_start, old_len, new_start, new_len) =
+                parse_hunk_header(trimmed).ok_or_else(|| parse_error("invalid hunk header"))?;
+            let mut hunk = PatchHunk {
+                old_start,
+                old_len,
+                new_start,
+                new_len,
+                lines: vec![],
+                patch_line: line_number,
+            };
+            let (mut old_remaining, mut new_remaining) = (old_len, new_len);
+            while old_remaining > 0 || new_remaining > 0 {
+                let Some((_, line)) = lines.next() else {
+                    return Err(parse_error("hunk is shorter than its header says"));
+                };
+                let (kind, content) = match line.as_bytes() {
+                    [kind, content @ ..] => (*kind, content.to_vec()),
+                    [] => unreachable!("split lines should be non-empty"),
+                };
+                let patch_line = match kind {
+                    b' ' if old_remaining > 0 && new_remaining > 0 => {
+                        old_remaining -= 1;
+                        new_remaining -= 1;
+                        PatchLine::Context(content)
+                    }
+                    // Some tools strip the space of empty context lines.
+                    b'\n' if old_remaining > 0 && new_remaining > 0 => {
+                        old_remaining -= 1;
+                        new_remaining -= 1;
+                        PatchLine::Context(b"\n".to_vec())
+                    }
+                    b'-' if old_remaining > 0 => {
+                        old_remaining -= 1;
+                        PatchLine::Removed(content)
+                    }
+                    b'+' if new_remaining > 0 => {
+                        new_remaining -= 1;
+                        PatchLine::Added(content)
+                    }
+                    b'\\' => {
+                        strip_last_newline(&mut hunk)
+                            .ok_or_else(|| parse_error("unexpected end-of-file marker"))?;
+                        continue;
+                    }
+                    _ => return Err(parse_error("hunk doesn't match its header")),
+                };
+                hunk.lines.push(patch_line);
+            }
+            if lines.next_if(|(_, next)| next.starts_with('\\')).is_some() {
+                strip_last_newline(&mut hunk)
+                    .ok_or_else(|| parse_error("unexpected end-of-file marker"))?;
+            }
+            patch.hunks.push(hunk);
+        }
+    }
+    Ok(patches)
+}
+
+fn parse_path(text: &str, prefix: &str) -> Option<String> {
+    // Drop the timestamp that some tools append after a tab.
+    let text = text.split('\t').next().unwrap();
+    if text == "/dev/null" {
+        None
+    } else {
+        Some(text.strip_prefix(prefix).unwrap_or(text).to_owned())
+    }
+}
+
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let ranges = line.strip_prefix("@@ -")?;
+    let (ranges, _) = ranges.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_len) = parse_range(old)?;
+    let (new_start, new_len) = parse_range(new)?;
+    Some((old_start, old_len, new_start, new_len))
+}
+
+fn strip_last_newline(hunk: &mut PatchHunk) -> Option<()> {
+    let content = hunk.lines.last_mut()?.content_mut();
+    if content.ends_with(b"\n") {
+        content.pop();
+    }
+    Some(())
+}
+
+/// Applies the hunks of `patch` to the `old` file contents.
+pub fn apply_hunks(patch: &FilePatch, old: &[u8]) -> Result<Vec<u8>, PatchError> {
+    let old_lines: Vec<&[u8]> = old.split_inclusive(|b| *b == b'\n').collect();
+    let mut new = vec![];
+    let mut pos = 0;
+    for (index, hunk) in patch.hunks.iter().enumerate() {
+        let apply_error = |message: String| PatchError::Apply {
+            path: patch.display_path().to_owned(),
+            hunk: index + 1,
+            line: hunk.patch_line,
+            message,
+        };
+        // An empty range starts after the given line.
+        let start = if hunk.old_len == 0 {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        if start < pos {
+            return Err(apply_error("hunk overlaps the previous one".to_owned()));
+        }
+        if start > old_lines.len() {
+            return Err(apply_error(format!(
+                "file has only {} lines",
+                old_lines.len()
+            )));
+        }
+        new.extend(old_lines[pos..start].concat());
+        pos = start;
+        for line in &hunk.lines {
+            match line {
+                PatchLine::Context(content) | PatchLine::Removed(content) => {
+                    if old_lines.get(pos) != Some(&content.as_slice()) {
+                        return Err(apply_error(format!(
+                            "line {} of the file doesn't match",
+                            pos + 1
+                        )));
+                    }
+                    if let PatchLine::Context(content) = line {
+                        new.extend_from_slice(content);
+                    }
+                    pos += 1;
+                }
+                PatchLine::Added(content) => new.extend_from_slice(content),
+            }
+        }
+    }
+    new.extend(old_lines[pos..].concat());
+    Ok(new)
+}
+
+/// Applies the file patches to `base_tree` and returns the resulting tree.
+pub fn apply_patch_to_tree(
+    store: &Arc<Store>,
+    base_tree: &MergedTree,
+    patches: &[FilePatch],
+) -> Result<MergedTreeId, PatchError> {
+    let mut tree_builder = MergedTreeBuilder::new(base_tree.id());
+    for patch in patches {
+        let file_error = |message: &str| PatchError::File {
+            path: patch.display_path().to_owned(),
+            message: message.to_owned(),
+        };
+        let to_repo_path = |path: &str| {
+            RepoPathBuf::from_internal_string(path).map_err(|_| file_error("invalid path"))
+        };
+        let (old_contents, executable) = if let Some(old_path) = &patch.old_path {
+            let old_path = to_repo_path(old_path)?;
+            let old_value = base_tree.path_value(&old_path)?;
+            match old_value.into_resolved() {
+                Ok(Some(TreeValue::File { id, executable, .. })) => {
+                    let mut contents = vec![];
+                    let mut reader = store.read_file(&old_path, &id).block_on()?;
+                    reader
+                        .read_to_end(&mut contents)
+                        .block_on()
+                        .map_err(|err| BackendError::ReadFile {
+                            path: old_path.clone(),
+                            id: id.clone(),
+                            source: err.into(),
+                        })?;
+                    (contents, executable)
+                }
+                Ok(None) => return Err(file_error("file doesn't exist")),
+                Ok(Some(_)) => return Err(file_error("not a regular file")),
+                Err(_) => return Err(file_error("file is conflicted")),
+            }
+        } else {
+            (vec![], patch.executable)
+        };
+        let new_contents = apply_hunks(patch, &old_contents)?;
+        if let Some(old_path) = &patch.old_path {
+            if patch.new_path.as_ref() != Some(old_path) {
+                tree_builder.set_or_remove(to_repo_path(old_path)?, Merge::absent());
+            }
+        }
+        if let Some(new_path) = &patch.new_path {
+            let new_path = to_repo_path(new_path)?;
+            if patch.old_path.is_none() && base_tree.path_value(&new_path)?.is_present() {
+                return Err(file_error("file already exists"));
+            }
+            let id = store
+                .write_file(&new_path, &mut new_contents.as_slice())
+                .block_on()?;
+            tree_builder.set_or_remove(
+                new_path,
+                Merge::normal(TreeValue::File {
+                    id,
+                    executable,
+                    copy_id: CopyId::placeholder(),
+                }),
+            );
+        }
+    }
+    Ok(tree_builder.write_tree(store)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_patch() {
+        let patches = parse_patch(indoc! {"
+            diff --git a/file1 b/file1
+            index 257cc56..3bd1f0e 100644
+            --- a/file1
+            +++ b/file1
+            @@ -1,2 +1,2 @@
+             foo
+            -bar
+            +baz
+            diff --git a/file2 b/file2
+            new file mode 100755
+            index 0000000..e69de29
+            --- /dev/null
+            +++ b/file2
+            @@ -0,0 +1 @@
+            +new
+            \\ No newline at end of file
+        "})
+        .unwrap();
+        assert_eq!(patches.len(), 2);
+        assert_eq!(patches[0].old_path.as_deref(), Some("file1"));
+        assert_eq!(patches[0].new_path.as_deref(), Some("file1"));
+        assert!(!patches[0].executable);
+        assert_eq!(
+            patches[0].hunks[0].lines,
+            [
+                PatchLine::Context(b"foo\n".to_vec()),
+                PatchLine::Removed(b"bar\n".to_vec()),
+                PatchLine::Added(b"baz\n".to_vec()),
+            ]
+        );
+        assert_eq!(patches[1].old_path, None);
+        assert!(patches[1].executable);
+        assert_eq!(
+            patches[1].hunks[0].lines,
+            [PatchLine::Added(b"new".to_vec())]
+        );
+    }
+
+    #[test]
+    fn test_parse_patch_invalid() {
+        let result = parse_patch(indoc! {"
+            --- a/file1
+            +++ b/file1
+            @@ -1,2 +1,2 @@
+             foo
+        "});
+        assert!(matches!(result, Err(PatchError::Parse { line: 3, .. })));
+    }
+
+    #[test]
+    fn test_apply_hunks() {
+        let patches = parse_patch(indoc! {"
+            --- a/file
+            +++ b/file
+            @@ -2,1 +2,1 @@
+            -2
+            +two
+            @@ -4,0 +5,1 @@
+            +four and a half
+        "})
+        .unwrap();
+        assert_eq!(
+            apply_hunks(&patches[0], b"1\n2\n3\n4\n5\n").unwrap(),
+            b"1\ntwo\n3\n4\nfour and a half\n5\n"
+        );
+        let err = apply_hunks(&patches[0], b"1\nother\n3\n4\n5\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Patch for file does not apply: hunk #1 at patch line 3: line 2 of the file doesn't \
+             match"
+        );
+    }
+}
@@ -64,3 +64,28 @@ pub fn snapshot_progress(ui: &Ui) -> Option<impl Fn(&RepoPath) + use<>> {
         _ = state.output.flush();
     })
 }
+
+/// Returns callback that displays `"{message} {done}/{total}"` progress.
+pub fn counter_progress(ui: &Ui, message: &str) -> Option<impl FnMut(u32, u32) + use<>> {
+    let mut output = ui.progress_output()?;
+    let message = message.to_owned();
+    let mut guard: Option<OutputGuard> = None;
+    // Don't clutter the output during fast operations.
+    let mut next_display_time = Instant::now() + INITIAL_DELAY;
+    Some(move |done: u32, total: u32| {
+        let now = Instant::now();
+        if now < next_display_time {
+            return;
+        }
+        next_display_time = now + Duration::from_secs(1) / UPDATE_HZ;
+        if guard.is_none() {
+            guard = Some(output.output_guard(format!("\r{}", Clear(ClearType::CurrentLine))));
+        }
+        _ = write!(
+            output,
+            "\r{}{message} {done}/{total}",
+            Clear(ClearType::CurrentLine),
+        );
+        _ = output.flush();
+    })
+}
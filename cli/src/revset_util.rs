@@ -26,6 +26,7 @@ use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
 use jj_lib::id_prefix::IdPrefixContext;
+use jj_lib::index::AllHeadsForGcUnsupported;
 use jj_lib::ref_name::RefNameBuf;
 use jj_lib::repo::Repo;
 use jj_lib::revset;
@@ -94,6 +95,17 @@ impl<'repo> RevsetExpressionEvaluator<'repo> {
         self.expression = self.expression.intersection(other);
     }
 
+    /// Makes the expression evaluated within all indexed commits, including
+    /// hidden ones, instead of the ancestors of the visible heads.
+    pub fn include_hidden(&mut self) -> Result<(), AllHeadsForGcUnsupported> {
+        let all_heads = self.repo.index().all_heads_for_gc()?.collect();
+        self.expression = Rc::new(RevsetExpression::WithinVisibility {
+            candidates: self.expression.clone(),
+            visible_heads: all_heads,
+        });
+        Ok(())
+    }
+
     /// Resolves user symbols in the expression, returns new expression.
     pub fn resolve(&self) -> Result<Rc<ResolvedRevsetExpression>, RevsetResolutionError> {
         let symbol_resolver = default_symbol_resolver(
@@ -258,6 +270,33 @@ pub(super) fn warn_unresolvable_trunk(
     Ok(())
 }
 
+/// Warns about symbols in `expression` which name a change but will resolve to
+/// a tag or bookmark of the same name instead.
+pub(super) fn warn_shadowed_change_names(
+    ui: &Ui,
+    repo: &dyn Repo,
+    expression: &Rc<UserRevsetExpression>,
+) -> io::Result<()> {
+    let view = repo.view();
+    for name in expression.symbols().iter().unique() {
+        if view.get_change_name(name).is_none() {
+            continue;
+        }
+        let kind = if view.get_tag(name.as_ref()).is_present() {
+            "tag"
+        } else if view.get_local_bookmark(name.as_ref()).is_present() {
+            "bookmark"
+        } else {
+            continue;
+        };
+        writeln!(
+            ui.warning_default(),
+            "`{name}` resolves to the {kind} {name}, not to the change named {name}"
+        )?;
+    }
+    Ok(())
+}
+
 pub(super) fn evaluate_revset_to_single_commit<'a>(
     revision_str: &str,
     expression: &RevsetExpressionEvaluator<'_>,
@@ -321,6 +360,21 @@ pub struct BookmarkNameParseError {
     pub source: RevsetParseError,
 }
 
+#[derive(Debug, Error)]
+#[error("Failed to parse change name: {}", source.kind())]
+pub struct ChangeNameParseError {
+    pub input: String,
+    pub source: RevsetParseError,
+}
+
+/// Parses change name specified in revset syntax.
+pub fn parse_change_name(text: &str) -> Result<String, ChangeNameParseError> {
+    revset::parse_symbol(text).map_err(|source| ChangeNameParseError {
+        input: text.to_owned(),
+        source,
+    })
+}
+
 /// Parses bookmark name specified in revset syntax.
 pub fn parse_bookmark_name(text: &str) -> Result<RefNameBuf, BookmarkNameParseError> {
     revset::parse_symbol(text)
@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use std::io;
 use std::iter;
 
+use chrono::TimeDelta;
 use itertools::Itertools as _;
 use jj_lib::backend::Signature;
 use jj_lib::backend::Timestamp;
@@ -163,6 +164,7 @@ where
     Self: WrapTemplateProperty<'a, SizeHint>,
     Self: WrapTemplateProperty<'a, Timestamp>,
     Self: WrapTemplateProperty<'a, TimestampRange>,
+    Self: WrapTemplateProperty<'a, TimeDelta>,
 {
     fn wrap_template(template: Box<dyn Template + 'a>) -> Self;
     fn wrap_list_template(template: Box<dyn ListTemplate + 'a>) -> Self;
@@ -172,6 +174,7 @@ where
 
     fn try_into_boolean(self) -> Option<BoxedTemplateProperty<'a, bool>>;
     fn try_into_integer(self) -> Option<BoxedTemplateProperty<'a, i64>>;
+    fn try_into_timestamp(self) -> Option<BoxedTemplateProperty<'a, Timestamp>>;
 
     /// Transforms into a string property by formatting the value if needed.
     fn try_into_stringify(self) -> Option<BoxedTemplateProperty<'a, String>>;
@@ -197,6 +200,7 @@ pub enum CoreTemplatePropertyKind<'a> {
     SizeHint(BoxedTemplateProperty<'a, SizeHint>),
     Timestamp(BoxedTemplateProperty<'a, Timestamp>),
     TimestampRange(BoxedTemplateProperty<'a, TimestampRange>),
+    Duration(BoxedTemplateProperty<'a, TimeDelta>),
 
     // Both TemplateProperty and Template can represent a value to be evaluated
     // dynamically, which suggests that `Box<dyn Template + 'a>` could be
@@ -230,6 +234,7 @@ macro_rules! impl_core_property_wrappers {
             SizeHint($crate::templater::SizeHint),
             Timestamp(jj_lib::backend::Timestamp),
             TimestampRange(jj_lib::op_store::TimestampRange),
+            Duration(chrono::TimeDelta),
         });
     };
 }
@@ -260,6 +265,7 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
             Self::SizeHint(_) => "SizeHint",
             Self::Timestamp(_) => "Timestamp",
             Self::TimestampRange(_) => "TimestampRange",
+            Self::Duration(_) => "Duration",
             Self::Template(_) => "Template",
             Self::ListTemplate(_) => "ListTemplate",
         }
@@ -278,6 +284,7 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
             Self::SizeHint(_) => None,
             Self::Timestamp(_) => None,
             Self::TimestampRange(_) => None,
+            Self::Duration(_) => None,
             // Template types could also be evaluated to boolean, but it's less likely
             // to apply label() or .map() and use the result as conditional. It's also
             // unclear whether ListTemplate should behave as a "list" or a "template".
@@ -294,6 +301,13 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
         }
     }
 
+    fn try_into_timestamp(self) -> Option<BoxedTemplateProperty<'a, Timestamp>> {
+        match self {
+            Self::Timestamp(property) => Some(property),
+            _ => None,
+        }
+    }
+
     fn try_into_stringify(self) -> Option<BoxedTemplateProperty<'a, String>> {
         match self {
             Self::String(property) => Some(property),
@@ -319,6 +333,7 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
             Self::SizeHint(property) => Some(property.into_serialize()),
             Self::Timestamp(property) => Some(property.into_serialize()),
             Self::TimestampRange(property) => Some(property.into_serialize()),
+            Self::Duration(_) => None,
             Self::Template(_) => None,
             Self::ListTemplate(_) => None,
         }
@@ -337,6 +352,7 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
             Self::SizeHint(_) => None,
             Self::Timestamp(property) => Some(property.into_template()),
             Self::TimestampRange(property) => Some(property.into_template()),
+            Self::Duration(property) => Some(property.into_template()),
             Self::Template(template) => Some(template),
             Self::ListTemplate(template) => Some(template.into_template()),
         }
@@ -382,6 +398,7 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
             (Self::SizeHint(_), _) => None,
             (Self::Timestamp(_), _) => None,
             (Self::TimestampRange(_), _) => None,
+            (Self::Duration(_), _) => None,
             (Self::Template(_), _) => None,
             (Self::ListTemplate(_), _) => None,
         }
@@ -401,6 +418,9 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
             (Self::IntegerOpt(lhs), Self::IntegerOpt(rhs)) => {
                 Some((lhs, rhs).map(|(l, r)| l.cmp(&r)).into_dyn())
             }
+            (Self::Duration(lhs), Self::Duration(rhs)) => {
+                Some((lhs, rhs).map(|(l, r)| l.cmp(&r)).into_dyn())
+            }
             (Self::String(_), _) => None,
             (Self::StringList(_), _) => None,
             (Self::Boolean(_), _) => None,
@@ -412,6 +432,7 @@ impl<'a> CoreTemplatePropertyVar<'a> for CoreTemplatePropertyKind<'a> {
             (Self::SizeHint(_), _) => None,
             (Self::Timestamp(_), _) => None,
             (Self::TimestampRange(_), _) => None,
+            (Self::Duration(_), _) => None,
             (Self::Template(_), _) => None,
             (Self::ListTemplate(_), _) => None,
         }
@@ -473,6 +494,7 @@ pub struct CoreTemplateBuildFnTable<'a, L: ?Sized, P = <L as TemplateLanguage<'a
     pub size_hint_methods: TemplateBuildMethodFnMap<'a, L, SizeHint, P>,
     pub timestamp_methods: TemplateBuildMethodFnMap<'a, L, Timestamp, P>,
     pub timestamp_range_methods: TemplateBuildMethodFnMap<'a, L, TimestampRange, P>,
+    pub duration_methods: TemplateBuildMethodFnMap<'a, L, TimeDelta, P>,
     pub template_methods: BuildTemplateMethodFnMap<'a, L, P>,
     pub list_template_methods: BuildListTemplateMethodFnMap<'a, L, P>,
 }
@@ -499,6 +521,7 @@ impl<'a, L: ?Sized, P> CoreTemplateBuildFnTable<'a, L, P> {
             size_hint_methods: HashMap::new(),
             timestamp_methods: HashMap::new(),
             timestamp_range_methods: HashMap::new(),
+            duration_methods: HashMap::new(),
             template_methods: HashMap::new(),
             list_template_methods: HashMap::new(),
         }
@@ -517,6 +540,7 @@ impl<'a, L: ?Sized, P> CoreTemplateBuildFnTable<'a, L, P> {
             size_hint_methods,
             timestamp_methods,
             timestamp_range_methods,
+            duration_methods,
             template_methods,
             list_template_methods,
         } = other;
@@ -532,6 +556,7 @@ impl<'a, L: ?Sized, P> CoreTemplateBuildFnTable<'a, L, P> {
         merge_fn_map(&mut self.size_hint_methods, size_hint_methods);
         merge_fn_map(&mut self.timestamp_methods, timestamp_methods);
         merge_fn_map(&mut self.timestamp_range_methods, timestamp_range_methods);
+        merge_fn_map(&mut self.duration_methods, duration_methods);
         merge_fn_map(&mut self.template_methods, template_methods);
         merge_fn_map(&mut self.list_template_methods, list_template_methods);
     }
@@ -555,6 +580,7 @@ where
             size_hint_methods: builtin_size_hint_methods(),
             timestamp_methods: builtin_timestamp_methods(),
             timestamp_range_methods: builtin_timestamp_range_methods(),
+            duration_methods: builtin_duration_methods(),
             template_methods: HashMap::new(),
             list_template_methods: builtin_list_template_methods(),
         }
@@ -642,6 +668,11 @@ where
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(language, diagnostics, build_ctx, property, function)
             }
+            CoreTemplatePropertyKind::Duration(property) => {
+                let table = &self.duration_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(language, diagnostics, build_ctx, property, function)
+            }
             CoreTemplatePropertyKind::Template(template) => {
                 let table = &self.template_methods;
                 let build = template_parser::lookup_method(type_name, table, function)?;
@@ -687,6 +718,10 @@ impl<'a, P: CoreTemplatePropertyVar<'a>> Expression<P> {
         self.property.try_into_integer()
     }
 
+    pub fn try_into_timestamp(self) -> Option<BoxedTemplateProperty<'a, Timestamp>> {
+        self.property.try_into_timestamp()
+    }
+
     pub fn try_into_stringify(self) -> Option<BoxedTemplateProperty<'a, String>> {
         self.property.try_into_stringify()
     }
@@ -1252,6 +1287,15 @@ fn builtin_timestamp_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "iso",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property
+                .and_then(|timestamp| Ok(time_util::format_iso_timestamp(&timestamp)?));
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "utc",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -1299,6 +1343,18 @@ fn builtin_timestamp_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
         },
     );
     map.insert("before", map["after"]);
+    map.insert(
+        "duration_since",
+        |language, diagnostics, build_ctx, self_property, function| {
+            let [other_node] = function.expect_exact_arguments()?;
+            let other_property =
+                expect_timestamp_expression(language, diagnostics, build_ctx, other_node)?;
+            let out_property = (self_property, other_property).map(|(timestamp, other)| {
+                TimeDelta::milliseconds(timestamp.timestamp.0 - other.timestamp.0)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map
 }
 
@@ -1344,6 +1400,46 @@ fn builtin_timestamp_range_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
     map
 }
 
+fn builtin_duration_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
+-> TemplateBuildMethodFnMap<'a, L, TimeDelta> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = TemplateBuildMethodFnMap::<L, TimeDelta>::new();
+    map.insert(
+        "days",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|duration| duration.num_days());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "hours",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|duration| duration.num_hours());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "minutes",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|duration| duration.num_minutes());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "seconds",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|duration| duration.num_seconds());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map
+}
+
 fn builtin_list_template_methods<'a, L: TemplateLanguage<'a> + ?Sized>()
 -> BuildListTemplateMethodFnMap<'a, L> {
     // Not using maplit::hashmap!{} or custom declarative macro here because
@@ -2055,6 +2151,22 @@ pub fn expect_integer_expression<'a, L: TemplateLanguage<'a> + ?Sized>(
     )
 }
 
+pub fn expect_timestamp_expression<'a, L: TemplateLanguage<'a> + ?Sized>(
+    language: &L,
+    diagnostics: &mut TemplateDiagnostics,
+    build_ctx: &BuildContext<L::Property>,
+    node: &ExpressionNode,
+) -> TemplateParseResult<BoxedTemplateProperty<'a, Timestamp>> {
+    expect_expression_of_type(
+        language,
+        diagnostics,
+        build_ctx,
+        node,
+        "Timestamp",
+        |expression| expression.try_into_timestamp(),
+    )
+}
+
 /// If the given expression `node` is of `Integer` type, converts it to `isize`.
 pub fn expect_isize_expression<'a, L: TemplateLanguage<'a> + ?Sized>(
     language: &L,
@@ -3165,6 +3277,10 @@ mod tests {
             env.render_ok(r#"t0.format("%Y%m%d %H:%M:%S")"#),
             @"19700101 00:00:00");
 
+        env.add_keyword("t1", || literal(new_timestamp(0, 420)));
+        insta::assert_snapshot!(env.render_ok("t0.iso()"), @"1970-01-01T00:00:00+00:00");
+        insta::assert_snapshot!(env.render_ok("t1.iso()"), @"1970-01-01T07:00:00+07:00");
+
         // Invalid format string
         insta::assert_snapshot!(env.parse_err(r#"t0.format("%_")"#), @r#"
          --> 1:11
@@ -3215,6 +3331,56 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn test_timestamp_comparison_method() {
+        let mut env = TestTemplateEnv::new();
+        env.add_keyword("t0", || literal(new_timestamp(0, 0)));
+        env.add_keyword("t1", || {
+            literal(new_timestamp(90 * 86400 * 1000 + 3_600_000, 0))
+        });
+
+        // Absolute dates
+        insta::assert_snapshot!(env.render_ok(r#"t0.after("1969-12-31")"#), @"true");
+        insta::assert_snapshot!(env.render_ok(r#"t0.before("1970-01-02")"#), @"true");
+        insta::assert_snapshot!(env.render_ok(r#"t1.before("1970-01-02")"#), @"false");
+
+        // Relative dates
+        insta::assert_snapshot!(env.render_ok(r#"t0.before("90 days ago")"#), @"true");
+        insta::assert_snapshot!(env.render_ok(r#"t1.after("90 days ago")"#), @"false");
+
+        // Invalid date pattern
+        insta::assert_snapshot!(env.parse_err(r#"t0.after("foo")"#), @r#"
+         --> 1:10
+          |
+        1 | t0.after("foo")
+          |          ^---^
+          |
+          = Invalid date pattern
+        expected unsupported identifier as position 0..3
+        "#);
+
+        // Durations
+        insta::assert_snapshot!(env.render_ok(r#"t1.duration_since(t0)"#), @"2 months");
+        insta::assert_snapshot!(env.render_ok(r#"t0.duration_since(t1)"#), @"-2 months");
+        insta::assert_snapshot!(env.render_ok(r#"t1.duration_since(t0).days()"#), @"90");
+        insta::assert_snapshot!(env.render_ok(r#"t1.duration_since(t0).hours()"#), @"2161");
+        insta::assert_snapshot!(env.render_ok(r#"t0.duration_since(t1).days()"#), @"-90");
+        insta::assert_snapshot!(
+            env.render_ok(r#"t1.duration_since(t0).seconds() == 90 * 86400 + 3600"#), @"true");
+        insta::assert_snapshot!(
+            env.render_ok(r#"t1.duration_since(t0) > t0.duration_since(t0)"#), @"true");
+
+        // Invalid type
+        insta::assert_snapshot!(env.parse_err(r#"t0.duration_since("1970-01-01")"#), @r#"
+         --> 1:19
+          |
+        1 | t0.duration_since("1970-01-01")
+          |                   ^----------^
+          |
+          = Expected expression of type `Timestamp`, but actual type is `String`
+        "#);
+    }
+
     #[test]
     fn test_fill_function() {
         let mut env = TestTemplateEnv::new();
@@ -157,6 +157,18 @@ impl Template for TimestampRange {
     }
 }
 
+impl Template for chrono::TimeDelta {
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        let mut f = timeago::Formatter::new();
+        f.min_unit(timeago::TimeUnit::Seconds).ago("");
+        if *self < Self::zero() {
+            write!(formatter, "-")?;
+        }
+        let duration = self.abs().to_std().unwrap_or_default();
+        write!(formatter, "{}", f.convert(duration))
+    }
+}
+
 impl Template for Vec<String> {
     fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
         format_joined(formatter, self, " ")
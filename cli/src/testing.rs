@@ -0,0 +1,169 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers to run `jj` commands in-process, for integration tests of tools
+//! built on top of `jj`.
+
+use std::env;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::iter;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use crate::cli_util::CliRunner;
+
+/// Output of a command run by [`run_command()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapturedOutput {
+    /// Contents written to stdout, lossily converted to UTF-8.
+    pub stdout: String,
+    /// Contents written to stderr, lossily converted to UTF-8.
+    pub stderr: String,
+    /// Exit code the `jj` binary would exit with.
+    pub exit_code: u8,
+}
+
+impl CapturedOutput {
+    /// Whether the command succeeded.
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
+// Serializes commands since the environment and the current directory are
+// process-global.
+static RUN_LOCK: Mutex<()> = Mutex::new(());
+
+/// Runs `jj` with the given arguments in-process, and returns the captured
+/// output.
+///
+/// `args` shouldn't include the program name. The command runs with exactly
+/// the environment variables in `env_vars` (the process environment is
+/// cleared while the command runs), and in the `cwd` directory. The
+/// environment and the current directory are restored afterwards, so state
+/// doesn't leak from one call to the next. Commands run by concurrent calls
+/// are serialized.
+///
+/// Output of child processes such as `git` isn't captured. Stdin is inherited
+/// from the current process.
+///
+/// # Examples
+///
+/// ```
+/// use jj_cli::testing::run_command;
+///
+/// let temp_dir = tempfile::tempdir().unwrap();
+/// let home_dir = temp_dir.path().to_str().unwrap();
+/// let env_vars = [
+///     ("HOME", home_dir),
+///     ("JJ_CONFIG", ""),
+///     ("JJ_USER", "Test User"),
+///     ("JJ_EMAIL", "test.user@example.com"),
+/// ];
+///
+/// // SAFETY: No other threads are running.
+/// let output = unsafe { run_command(["git", "init", "repo"], env_vars, temp_dir.path()) };
+/// assert!(output.success(), "{}", output.stderr);
+///
+/// let repo_dir = temp_dir.path().join("repo");
+/// // SAFETY: No other threads are running.
+/// let output = unsafe {
+///     run_command(
+///         ["log", "--no-graph", "-r@", "-Tauthor.name()"],
+///         env_vars,
+///         &repo_dir,
+///     )
+/// };
+/// assert_eq!(output.stdout, "Test User");
+/// ```
+///
+/// # Safety
+///
+/// The process environment is modified while the command runs, which is
+/// only safe if no other thread reads or writes the environment in the
+/// meantime, except by calling this function. See [`env::set_var()`].
+///
+/// # Panics
+///
+/// Panics if the current directory can't be changed to `cwd`.
+pub unsafe fn run_command<K, V>(
+    args: impl IntoIterator<Item = impl Into<OsString>>,
+    env_vars: impl IntoIterator<Item = (K, V)>,
+    cwd: &Path,
+) -> CapturedOutput
+where
+    K: AsRef<OsStr>,
+    V: AsRef<OsStr>,
+{
+    let args_os = iter::once(OsString::from("jj"))
+        .chain(args.into_iter().map(Into::into))
+        .collect();
+    let _lock = RUN_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    let _restore_guard = ProcessStateGuard::save();
+    // SAFETY: Commands are serialized by RUN_LOCK, and the caller guarantees
+    // that the environment isn't accessed from other threads.
+    unsafe {
+        for (key, _) in env::vars_os() {
+            env::remove_var(key);
+        }
+        for (key, value) in env_vars {
+            env::set_var(key, value);
+        }
+    }
+    env::set_current_dir(cwd).unwrap_or_else(|err| {
+        panic!("failed to change directory to {}: {err}", cwd.display());
+    });
+    let (exit_code, stdout, stderr) = CliRunner::init_in_process(args_os).run_captured();
+    CapturedOutput {
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        exit_code,
+    }
+}
+
+/// Restores the environment variables and the current directory on drop,
+/// even if the command panicked.
+struct ProcessStateGuard {
+    vars: Vec<(OsString, OsString)>,
+    cwd: Option<PathBuf>,
+}
+
+impl ProcessStateGuard {
+    fn save() -> Self {
+        Self {
+            vars: env::vars_os().collect(),
+            cwd: env::current_dir().ok(),
+        }
+    }
+}
+
+impl Drop for ProcessStateGuard {
+    fn drop(&mut self) {
+        // SAFETY: See run_command().
+        unsafe {
+            for (key, _) in env::vars_os() {
+                env::remove_var(key);
+            }
+            for (key, value) in &self.vars {
+                env::set_var(key, value);
+            }
+        }
+        if let Some(cwd) = &self.cwd {
+            env::set_current_dir(cwd).ok();
+        }
+    }
+}
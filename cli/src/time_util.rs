@@ -48,6 +48,12 @@ pub fn format_absolute_timestamp(timestamp: &Timestamp) -> Result<String, Timest
     format_absolute_timestamp_with(timestamp, &DEFAULT_FORMAT)
 }
 
+pub fn format_iso_timestamp(timestamp: &Timestamp) -> Result<String, TimestampOutOfRange> {
+    static ISO_FORMAT: LazyLock<FormattingItems> =
+        LazyLock::new(|| FormattingItems::parse("%Y-%m-%dT%H:%M:%S%:z").unwrap());
+    format_absolute_timestamp_with(timestamp, &ISO_FORMAT)
+}
+
 pub fn format_absolute_timestamp_with(
     timestamp: &Timestamp,
     format: &FormattingItems,
@@ -27,6 +27,7 @@ use std::mem;
 use std::process::Child;
 use std::process::ChildStdin;
 use std::process::Stdio;
+use std::sync::Mutex;
 use std::thread;
 use std::thread::JoinHandle;
 
@@ -60,6 +61,10 @@ enum UiOutput {
         err_wr: PipeWriter,
         pager_thread: JoinHandle<streampager::Result<()>>,
     },
+    Captured {
+        stdout: Mutex<Vec<u8>>,
+        stderr: Mutex<Vec<u8>>,
+    },
     Null,
 }
 
@@ -150,7 +155,7 @@ impl UiOutput {
                     }
                 }
             }
-            Self::Null => {}
+            Self::Captured { .. } | Self::Null => {}
         }
     }
 }
@@ -159,6 +164,7 @@ pub enum UiStdout<'a> {
     Terminal(StdoutLock<'static>),
     Paged(&'a ChildStdin),
     Builtin(&'a PipeWriter),
+    Captured(CapturedWriter<'a>),
     Null(io::Sink),
 }
 
@@ -166,15 +172,30 @@ pub enum UiStderr<'a> {
     Terminal(StderrLock<'static>),
     Paged(&'a ChildStdin),
     Builtin(&'a PipeWriter),
+    Captured(CapturedWriter<'a>),
     Null(io::Sink),
 }
 
+/// Writer that appends to an in-memory output buffer.
+pub struct CapturedWriter<'a>(&'a Mutex<Vec<u8>>);
+
+impl Write for CapturedWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 macro_rules! for_outputs {
     ($ty:ident, $output:expr, $pat:pat => $expr:expr) => {
         match $output {
             $ty::Terminal($pat) => $expr,
             $ty::Paged($pat) => $expr,
             $ty::Builtin($pat) => $expr,
+            $ty::Captured($pat) => $expr,
             $ty::Null($pat) => $expr,
         }
     };
@@ -239,9 +260,8 @@ impl fmt::Display for ColorChoice {
 
 fn prepare_formatter_factory(
     config: &StackedConfig,
-    stdout: &Stdout,
+    terminal: bool,
 ) -> Result<FormatterFactory, ConfigGetError> {
-    let terminal = stdout.is_terminal();
     let (color, debug) = match config.get("ui.color")? {
         ColorChoice::Always => (true, false),
         ColorChoice::Never => (false, false),
@@ -348,7 +368,7 @@ impl Ui {
     }
 
     pub fn with_config(config: &StackedConfig) -> Result<Self, CommandError> {
-        let formatter_factory = prepare_formatter_factory(config, &io::stdout())?;
+        let formatter_factory = prepare_formatter_factory(config, io::stdout().is_terminal())?;
         Ok(Self {
             quiet: config.get("ui.quiet")?,
             formatter_factory,
@@ -358,14 +378,45 @@ impl Ui {
         })
     }
 
+    /// Creates ui that writes to in-memory buffers instead of the terminal.
+    /// The output can be obtained by [`Ui::take_captured_output()`].
+    pub fn captured(config: &StackedConfig) -> Result<Self, CommandError> {
+        Ok(Self {
+            quiet: config.get("ui.quiet")?,
+            formatter_factory: prepare_formatter_factory(config, false)?,
+            pager: PagerConfig::from_config(config)?,
+            progress_indicator: config.get("ui.progress-indicator")?,
+            output: UiOutput::Captured {
+                stdout: Mutex::new(vec![]),
+                stderr: Mutex::new(vec![]),
+            },
+        })
+    }
+
     pub fn reset(&mut self, config: &StackedConfig) -> Result<(), CommandError> {
         self.quiet = config.get("ui.quiet")?;
         self.pager = PagerConfig::from_config(config)?;
         self.progress_indicator = config.get("ui.progress-indicator")?;
-        self.formatter_factory = prepare_formatter_factory(config, &io::stdout())?;
+        let terminal = match &self.output {
+            UiOutput::Captured { .. } => false,
+            _ => io::stdout().is_terminal(),
+        };
+        self.formatter_factory = prepare_formatter_factory(config, terminal)?;
         Ok(())
     }
 
+    /// Returns the stdout and stderr contents written so far if this ui was
+    /// created by [`Ui::captured()`].
+    pub fn take_captured_output(&mut self) -> Option<(Vec<u8>, Vec<u8>)> {
+        match &mut self.output {
+            UiOutput::Captured { stdout, stderr } => Some((
+                mem::take(stdout.get_mut().unwrap()),
+                mem::take(stderr.get_mut().unwrap()),
+            )),
+            _ => None,
+        }
+    }
+
     /// Switches the output to use the pager, if allowed.
     #[instrument(skip_all)]
     pub fn request_pager(&mut self) {
@@ -427,6 +478,7 @@ impl Ui {
             UiOutput::Terminal { stdout, .. } => UiStdout::Terminal(stdout.lock()),
             UiOutput::Paged { child_stdin, .. } => UiStdout::Paged(child_stdin),
             UiOutput::BuiltinPaged { out_wr, .. } => UiStdout::Builtin(out_wr),
+            UiOutput::Captured { stdout, .. } => UiStdout::Captured(CapturedWriter(stdout)),
             UiOutput::Null => UiStdout::Null(io::sink()),
         }
     }
@@ -445,6 +497,7 @@ impl Ui {
             UiOutput::Terminal { stderr, .. } => UiStderr::Terminal(stderr.lock()),
             UiOutput::Paged { child_stdin, .. } => UiStderr::Paged(child_stdin),
             UiOutput::BuiltinPaged { err_wr, .. } => UiStderr::Builtin(err_wr),
+            UiOutput::Captured { stderr, .. } => UiStderr::Captured(CapturedWriter(stderr)),
             UiOutput::Null => UiStderr::Null(io::sink()),
         }
     }
@@ -460,7 +513,8 @@ impl Ui {
             UiOutput::Terminal { .. } => Ok(Stdio::inherit()),
             UiOutput::Paged { child_stdin, .. } => Ok(duplicate_child_stdin(child_stdin)?.into()),
             UiOutput::BuiltinPaged { err_wr, .. } => Ok(err_wr.try_clone()?.into()),
-            UiOutput::Null => Ok(Stdio::null()),
+            // Child processes can't write to the in-memory buffer.
+            UiOutput::Captured { .. } | UiOutput::Null => Ok(Stdio::null()),
         }
     }
 
@@ -471,6 +525,7 @@ impl Ui {
             UiOutput::Terminal { stderr, .. } => self.progress_indicator && stderr.is_terminal(),
             UiOutput::Paged { .. } => false,
             UiOutput::BuiltinPaged { .. } => false,
+            UiOutput::Captured { .. } => false,
             UiOutput::Null => false,
         }
     }
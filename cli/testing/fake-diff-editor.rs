@@ -60,7 +60,14 @@ fn main() {
     let args: Args = Args::parse();
     let edit_script_path = PathBuf::from(std::env::var_os("DIFF_EDIT_SCRIPT").unwrap());
     let edit_script = String::from_utf8(std::fs::read(&edit_script_path).unwrap()).unwrap();
-    for instruction in edit_script.split('\0') {
+    let mut instructions = edit_script.split('\0').collect_vec();
+    if let Some(pos) = instructions.iter().position(|&i| i == "next invocation\n") {
+        // Overwrite the edit script. The next time `fake-diff-editor` is called,
+        // it will only see the part after the `next invocation` command.
+        std::fs::write(&edit_script_path, instructions[pos + 1..].join("\0")).unwrap();
+        instructions.truncate(pos);
+    }
+    for instruction in instructions {
         let (command, payload) = instruction.split_once('\n').unwrap_or((instruction, ""));
         let parts = command.split(' ').collect_vec();
         match parts.as_slice() {
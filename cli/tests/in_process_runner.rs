@@ -0,0 +1,120 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests of `jj_cli::testing`. These live in a separate test binary because
+//! running commands in-process temporarily replaces the process environment.
+
+use std::env;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use jj_cli::testing::CapturedOutput;
+use jj_cli::testing::run_command;
+
+// Tests inspect the process environment, which shouldn't be replaced by
+// commands run by other tests at the same time.
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_test() -> MutexGuard<'static, ()> {
+    TEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn run_jj(
+    _lock: &MutexGuard<'static, ()>,
+    home_dir: &Path,
+    cwd: &Path,
+    args: &[&str],
+) -> CapturedOutput {
+    let env_vars = [
+        ("HOME", home_dir.to_str().unwrap()),
+        ("JJ_CONFIG", ""),
+        ("JJ_USER", "Test User"),
+        ("JJ_EMAIL", "test.user@example.com"),
+        ("JJ_TIMESTAMP", "2001-02-03T04:05:06+07:00"),
+        ("JJ_OP_TIMESTAMP", "2001-02-03T04:05:06+07:00"),
+        ("JJ_RANDOMNESS_SEED", "0"),
+        ("GIT_CONFIG_SYSTEM", "/dev/null"),
+        ("GIT_CONFIG_GLOBAL", "/dev/null"),
+    ];
+    // SAFETY: The caller holds TEST_LOCK, which all tests in this file acquire
+    // before accessing the environment.
+    unsafe { run_command(args.iter().copied(), env_vars, cwd) }
+}
+
+#[test]
+fn test_run_command_in_temp_repo() {
+    let lock = lock_test();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let home_dir = temp_dir.path();
+    let repo_dir = temp_dir.path().join("repo");
+
+    let output = run_jj(&lock, home_dir, home_dir, &["git", "init", "repo"]);
+    assert_eq!(output.exit_code, 0, "{}", output.stderr);
+    insta::assert_snapshot!(output.stdout, @"");
+    insta::assert_snapshot!(output.stderr, @r#"Initialized repo in "repo""#);
+
+    let output = run_jj(&lock, home_dir, &repo_dir, &["describe", "-m", "first"]);
+    assert_eq!(output.exit_code, 0, "{}", output.stderr);
+
+    let output = run_jj(
+        &lock,
+        home_dir,
+        &repo_dir,
+        &[
+            "log",
+            "--no-graph",
+            "-r@",
+            "-T",
+            r#"description ++ author.name() ++ "\n""#,
+        ],
+    );
+    assert!(output.success(), "{}", output.stderr);
+    insta::assert_snapshot!(output.stdout, @r"
+    first
+    Test User
+    ");
+    insta::assert_snapshot!(output.stderr, @"");
+}
+
+#[test]
+fn test_run_command_error() {
+    let lock = lock_test();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let home_dir = temp_dir.path();
+
+    let output = run_jj(&lock, home_dir, home_dir, &["log"]);
+    assert_eq!(output.exit_code, 1);
+    insta::assert_snapshot!(output.stdout, @"");
+    insta::assert_snapshot!(output.stderr, @r#"
+    Error: There is no jj repo in "."
+    "#);
+}
+
+#[test]
+fn test_run_command_restores_process_state() {
+    let lock = lock_test();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let home_dir = temp_dir.path();
+    let orig_cwd = env::current_dir().unwrap();
+    let orig_home = env::var_os("HOME");
+
+    let output = run_jj(&lock, home_dir, home_dir, &["git", "init", "repo"]);
+    assert!(output.success(), "{}", output.stderr);
+
+    assert_eq!(env::current_dir().unwrap(), orig_cwd);
+    assert_eq!(env::var_os("HOME"), orig_home);
+    assert_eq!(env::var_os("JJ_USER"), None);
+}
@@ -16,6 +16,8 @@ mod test_alias;
 mod test_backout_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
+mod test_change_name_command;
+mod test_command_defaults;
 mod test_commit_command;
 mod test_commit_template;
 mod test_completion;
@@ -49,14 +51,19 @@ mod test_git_root;
 mod test_gitignores;
 mod test_global_opts;
 mod test_help_command;
+mod test_identity_command;
 mod test_immutable_commits;
 mod test_interdiff_command;
+mod test_lint_command;
 mod test_log_command;
+mod test_merge_drivers;
 mod test_new_command;
 mod test_next_prev_commands;
+mod test_note_command;
 mod test_op_revert_command;
 mod test_operations;
 mod test_parallelize_command;
+mod test_plumbing_command;
 mod test_rebase_command;
 mod test_repo_change_report;
 mod test_resolve_command;
@@ -71,6 +78,7 @@ mod test_sparse_command;
 mod test_split_command;
 mod test_squash_command;
 mod test_status_command;
+mod test_summary_command;
 mod test_tag_command;
 mod test_templater;
 mod test_touch_command;
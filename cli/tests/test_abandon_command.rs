@@ -523,6 +523,211 @@ fn test_abandon_tracking_bookmarks() {
     ");
 }
 
+#[test]
+fn test_abandon_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["abandon", "--dry-run", "a", "b"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would abandon 2 commits:
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    [EOF]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+}
+
+#[test]
+fn test_abandon_large_revset() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.large-revset-warning-threshold = 2");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    let setup_opid = work_dir.current_operation_id();
+
+    // Confirmation is declined without a terminal
+    let output = work_dir.run_jj(["abandon", "a::"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: The revisions matched 3 commits, which is more than `ui.large-revset-warning-threshold` (2):
+      royxmykx dffaa0d4 c | c
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Continue? (yN): n
+    Error: Operation was canceled
+    Hint: Pass `--no-limit` to skip this check.
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+
+    // Dry runs don't ask for confirmation
+    let output = work_dir.run_jj(["abandon", "--dry-run", "a::"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would abandon 3 commits:
+      royxmykx dffaa0d4 c | c
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    [EOF]
+    ");
+
+    // Other commands check the resolved revisions too
+    let output = work_dir.run_jj(["describe", "a::", "-m", "x"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Warning: The revisions matched 3 commits, which is more than `ui.large-revset-warning-threshold` (2):
+      royxmykx dffaa0d4 c | c
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Continue? (yN): n
+    Error: Operation was canceled
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+
+    // Descendants of the rebased commits are counted too
+    let output = work_dir.run_jj(["rebase", "-s", "a", "-d", "root()"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Warning: The revisions matched 3 commits, which is more than `ui.large-revset-warning-threshold` (2):
+      royxmykx dffaa0d4 c | c
+      zsuskuln 123b4d91 b | b
+      rlvkpnrz 7d980be7 a | a
+    Continue? (yN): n
+    Error: Operation was canceled
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+
+    work_dir.run_jj(["abandon", "--no-limit", "a::"]).success();
+    assert_ne!(work_dir.current_operation_id(), setup_opid);
+
+    // Only a sample of the commits is listed
+    for i in 0..12 {
+        work_dir
+            .run_jj(["new", "root()", "-m", &format!("commit {i}")])
+            .success();
+    }
+    let output = work_dir.run_jj(["describe", "mutable()", "-m", "x"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Warning: The revisions matched 12 commits, which is more than `ui.large-revset-warning-threshold` (2):
+      pzsxstzt 6d29837e (empty) commit 11
+      nlrtlrxv d145c003 (empty) commit 10
+      wvuyspvk 47ec7fdb (empty) commit 9
+      tlkvzzqu 3c6ee5ca (empty) commit 8
+      nmzmmopx 5e35f59f (empty) commit 7
+      xtnwkqum 8c394027 (empty) commit 6
+      xznxytkn a1b54e9f (empty) commit 5
+      uyznsvlq 41ed1354 (empty) commit 4
+      nkmrtpmo f6670fb2 (empty) commit 3
+      kxryzmor 9e463104 (empty) commit 2
+      ... and 2 more
+    Continue? (yN): n
+    Error: Operation was canceled
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_abandon_interactive() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    work_dir.run_jj(["describe", "-rc", "-m", ""]).success();
+    let setup_opid = work_dir.current_operation_id();
+    let commit_hash = |rev: &str| {
+        let output = work_dir.run_jj(["log", "--no-graph", "-r", rev, "-T", "commit_id.short(12)"]);
+        output.stdout.into_raw()
+    };
+
+    // Keep only "b"
+    std::fs::write(
+        &edit_script,
+        format!("dump editor0\0write\nxxx {} b\n", commit_hash("b")),
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["abandon", "--interactive", "a::"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 1 commits:
+      zsuskuln 123b4d91 b | b
+    Deleted bookmarks: b
+    Rebased 1 descendant commits onto parents of abandoned commits
+    Working copy  (@) now at: royxmykx 65e3760a c | (no description set)
+    Parent commit (@-)      : rlvkpnrz 7d980be7 a | a
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor0")).unwrap(), @r#"
+    JJ: Remove the lines of the revisions you don't want to abandon.
+    JJ: Lines starting with "JJ:" are ignored.
+
+    royxmykxtrkr aed2ad3c54e2 (no description set) (c)
+    zsuskulnrvyr 123b4d91f6e5 b (b)
+    rlvkpnrzqnoo 7d980be7a1d4 a (a)
+    "#);
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  [roy] c
+    ○  [rlv] a
+    ◆  [zzz]
+    [EOF]
+    ");
+
+    // Removing all lines abandons nothing
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    std::fs::write(&edit_script, "write\nJJ: nothing\n").unwrap();
+    let output = work_dir.run_jj(["abandon", "-i", "a::"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No revisions to abandon.
+    [EOF]
+    ");
+
+    // Combined with --dry-run
+    let opid = work_dir.current_operation_id();
+    std::fs::write(&edit_script, format!("write\nxxx {}\n", commit_hash("a"))).unwrap();
+    let output = work_dir.run_jj(["abandon", "-i", "--dry-run", "a::"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would abandon 1 commits:
+      rlvkpnrz 7d980be7 a | a
+    [EOF]
+    ");
+    assert_eq!(work_dir.current_operation_id(), opid);
+
+    // Lines must refer to the matched revisions
+    std::fs::write(&edit_script, "write\nxxx 000000000000 root\n").unwrap();
+    let output = work_dir.run_jj(["abandon", "-i", "a::"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Line doesn't refer to a revision to abandon: xxx 000000000000 root
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(" ", "[" ++ change_id.short(3) ++ "]", bookmarks)"#;
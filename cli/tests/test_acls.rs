@@ -72,12 +72,12 @@ fn test_diff() {
     ");
     let output = work_dir.run_jj(["diff", "--stat"]);
     insta::assert_snapshot!(output.normalize_backslash(), @r"
-    a-first                   | 2 +-
-    {a-first => added-secret} | 2 +-
-    deleted-secret            | 1 -
-    dir/secret                | 0
-    modified-secret           | 0
-    z-last                    | 2 +-
+    a-first                            | 2 +-
+    added-secret (copied from a-first) | 2 +-
+    deleted-secret                     | 1 -
+    dir/secret                         | 0
+    modified-secret                    | 0
+    z-last                             | 2 +-
     6 files changed, 3 insertions(+), 4 deletions(-)
     [EOF]
     ");
@@ -194,8 +194,8 @@ fn test_advance_bookmarks_overrides(make_commit: CommitFn) {
     // Now enable advance bookmarks for "test_bookmark", move the bookmark, and
     // commit again.
     test_env.add_config(
-        r#"[experimental-advance-bookmarks]
-    enabled-bookmarks = ["test_bookmark"]
+        r#"[experimental-advance-branches]
+    enabled-branches = ["test_bookmark"]
     "#,
     );
     work_dir
@@ -213,8 +213,8 @@ fn test_advance_bookmarks_overrides(make_commit: CommitFn) {
     insta::allow_duplicates! {
     insta::assert_snapshot!(get_log_output_with_bookmarks(&work_dir), @r"
     @  bookmarks{} desc:
-    ○  bookmarks{} desc: second
-    ○  bookmarks{test_bookmark} desc: first
+    ○  bookmarks{test_bookmark} desc: second
+    ○  bookmarks{} desc: first
     ◆  bookmarks{} desc:
     [EOF]
     ");
@@ -223,9 +223,9 @@ fn test_advance_bookmarks_overrides(make_commit: CommitFn) {
     // Now disable advance bookmarks for "test_bookmark" and "second_bookmark",
     // which we will use later. Disabling always takes precedence over enabling.
     test_env.add_config(
-        r#"[experimental-advance-bookmarks]
-    enabled-bookmarks = ["test_bookmark", "second_bookmark"]
-    disabled-bookmarks = ["test_bookmark"]
+        r#"[experimental-advance-branches]
+    enabled-branches = ["test_bookmark", "second_bookmark"]
+    disabled-branches = ["test_bookmark"]
     "#,
     );
     make_commit(&work_dir, "third");
@@ -233,8 +233,8 @@ fn test_advance_bookmarks_overrides(make_commit: CommitFn) {
     insta::assert_snapshot!(get_log_output_with_bookmarks(&work_dir), @r"
     @  bookmarks{} desc:
     ○  bookmarks{} desc: third
-    ○  bookmarks{} desc: second
-    ○  bookmarks{test_bookmark} desc: first
+    ○  bookmarks{test_bookmark} desc: second
+    ○  bookmarks{} desc: first
     ◆  bookmarks{} desc:
     [EOF]
     ");
@@ -263,8 +263,8 @@ fn test_advance_bookmarks_overrides(make_commit: CommitFn) {
     insta::allow_duplicates! {
     insta::assert_snapshot!(get_log_output_with_bookmarks(&work_dir), @r"
     @  bookmarks{} desc:
-    ○  bookmarks{} desc: fourth
-    ○  bookmarks{second_bookmark test_bookmark} desc: third
+    ○  bookmarks{second_bookmark} desc: fourth
+    ○  bookmarks{test_bookmark} desc: third
     ○  bookmarks{} desc: second
     ○  bookmarks{} desc: first
     ◆  bookmarks{} desc:
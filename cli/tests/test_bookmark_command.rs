@@ -640,6 +640,143 @@ fn test_bookmark_rename() {
     ");
 }
 
+#[test]
+fn test_bookmark_describe() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["bookmark", "describe", "release", "-m", "frozen"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such bookmark: release
+    [EOF]
+    [exit status: 1]
+    ");
+
+    work_dir.run_jj(["describe", "-m=commit-0"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "release", "main"])
+        .success();
+    let long_description = "frozen for 1.4, only cherry-picks are allowed on this bookmark \
+                            from now on\n\nAsk the release manager before pushing.";
+    work_dir
+        .run_jj(["bookmark", "describe", "release", "-m", long_description])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "list"]);
+    insta::assert_snapshot!(output, @r"
+    main: qpvuntsm 1937d547 (empty) commit-0
+    release: qpvuntsm 1937d547 (empty) commit-0
+      frozen for 1.4, only cherry-picks are allowed on this bookm…
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["bookmark", "list", "--verbose"]);
+    insta::assert_snapshot!(output, @r"
+    main: qpvuntsm 1937d547 (empty) commit-0
+    release: qpvuntsm 1937d547 (empty) commit-0
+      frozen for 1.4, only cherry-picks are allowed on this bookmark from now on
+
+      Ask the release manager before pushing.
+    [EOF]
+    ");
+    let template = r#"if(description, name ++ ": " ++ description ++ "\n")"#;
+    let output = work_dir.run_jj(["bookmark", "list", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    release: frozen for 1.4, only cherry-picks are allowed on this bookmark from now on
+
+    Ask the release manager before pushing.
+    [EOF]
+    ");
+
+    // Setting the same description again is a no-op.
+    let output = work_dir.run_jj(["bookmark", "describe", "release", "-m", long_description]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // The description survives moves and renames.
+    work_dir.run_jj(["new", "-m=commit-1"]).success();
+    work_dir
+        .run_jj(["bookmark", "move", "release", "--to=@"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "rename", "release", "release-1.4"])
+        .success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "describe",
+            "release-1.4",
+            "-m",
+            "frozen for 1.4",
+        ])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "list"]);
+    insta::assert_snapshot!(output, @r"
+    main: qpvuntsm 1937d547 (empty) commit-0
+    release-1.4: znkkpsqq 54baf366 (empty) commit-1
+      frozen for 1.4
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "diff"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: 65c61d18f1ea (2001-02-03 08:05:18) rename bookmark release to release-1.4
+      To operation: d235aae67f27 (2001-02-03 08:05:19) describe bookmark release-1.4
+
+    Changed bookmark descriptions:
+    release-1.4:
+    + frozen for 1.4
+    - frozen for 1.4, only cherry-picks are allowed on this bookmark from now on
+    [EOF]
+    ");
+
+    // The description is dropped when the bookmark is deleted.
+    work_dir
+        .run_jj(["bookmark", "delete", "release-1.4"])
+        .success();
+    let output = work_dir.run_jj(["op", "diff"]);
+    insta::assert_snapshot!(output, @r"
+    From operation: d235aae67f27 (2001-02-03 08:05:19) describe bookmark release-1.4
+      To operation: 4abc1de608f6 (2001-02-03 08:05:22) delete bookmark release-1.4
+
+    Changed local bookmarks:
+    release-1.4:
+    + (absent)
+    - znkkpsqq 54baf366 (empty) commit-1
+
+    Changed bookmark descriptions:
+    release-1.4:
+    + (absent)
+    - frozen for 1.4
+    [EOF]
+    ");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "release-1.4"])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "list"]);
+    insta::assert_snapshot!(output, @r"
+    main: qpvuntsm 1937d547 (empty) commit-0
+    release-1.4: znkkpsqq 54baf366 (empty) commit-1
+    [EOF]
+    ");
+
+    // An empty description removes it.
+    work_dir
+        .run_jj(["bookmark", "describe", "main", "-m", "main bookmark"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "describe", "main", "-m", ""])
+        .success();
+    let output = work_dir.run_jj(["bookmark", "list"]);
+    insta::assert_snapshot!(output, @r"
+    main: qpvuntsm 1937d547 (empty) commit-0
+    release-1.4: znkkpsqq 54baf366 (empty) commit-1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bookmark_rename_colocated() {
     let test_env = TestEnvironment::default();
@@ -0,0 +1,116 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_change_name() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "c"]).success();
+
+    let output = work_dir.run_jj(["change-name", "set", "fix-login", "-r=description(a)"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["change-name", "list"]);
+    insta::assert_snapshot!(output, @r"
+    fix-login: a
+    [EOF]
+    ");
+
+    // The name follows the change when it's rewritten
+    work_dir
+        .run_jj(["rebase", "-r=fix-login", "-d=description(c)"])
+        .success();
+    work_dir
+        .run_jj(["describe", "-r=fix-login", "-m=a2"])
+        .success();
+    let template = r#"separate(" ", description.first_line(), change_names) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=fix-login::", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    a2 fix-login
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=fix-login-", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    c
+    [EOF]
+    ");
+
+    // Bookmarks take precedence over change names
+    work_dir
+        .run_jj(["bookmark", "create", "review", "-r=description(b)"])
+        .success();
+    let output = work_dir.run_jj(["change-name", "set", "review", "-r=description(c)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Bookmark review has the same name, so `review` will resolve to the bookmark
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=review", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    b
+    [EOF]
+    ------- stderr -------
+    Warning: `review` resolves to the bookmark review, not to the change named review
+    [EOF]
+    ");
+
+    // A tag or bookmark created after the name also shadows it
+    work_dir
+        .run_jj(["bookmark", "create", "fix-login", "-r=description(b)"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=fix-login", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    b
+    [EOF]
+    ------- stderr -------
+    Warning: `fix-login` resolves to the bookmark fix-login, not to the change named fix-login
+    [EOF]
+    ");
+    work_dir
+        .run_jj(["bookmark", "delete", "fix-login"])
+        .success();
+
+    let output = work_dir.run_jj(["change-name", "delete", "review", "nonexistent"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such change name: nonexistent
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["change-name", "delete", "review"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Deleted 1 change names.
+    [EOF]
+    ");
+
+    // Abandoned changes can no longer be resolved by name
+    work_dir.run_jj(["abandon", "-r=fix-login"]).success();
+    let output = work_dir.run_jj(["log", "-r=fix-login"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Change name `fix-login` refers to a change with no visible revisions
+    Hint: To remove the name, run `jj change-name delete fix-login`
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-r=present(fix-login)"]);
+    insta::assert_snapshot!(output, @"");
+}
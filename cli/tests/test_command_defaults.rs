@@ -0,0 +1,121 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_command_defaults_basic() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+
+    test_env.add_config(
+        r#"
+        [command-defaults]
+        log = ["--no-graph", "-T", "description", "-r", "@"]
+        "bookmark list" = ["-T", "'bookmark: ' ++ name ++ '\n'"]
+        "#,
+    );
+    let output = work_dir.run_jj(["log"]);
+    insta::assert_snapshot!(output, @r"
+    second
+    [EOF]
+    ");
+
+    // Arguments given by the user override the defaults
+    let output = work_dir.run_jj(["log", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    first
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "change_id.short() ++ '\n'"]);
+    insta::assert_snapshot!(output, @r"
+    kkmpptxzrspx
+    [EOF]
+    ");
+
+    // Global arguments before the command name are preserved
+    let output = work_dir.run_jj(["--ignore-working-copy", "log", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    first
+    [EOF]
+    ");
+
+    // Defaults apply to nested commands, including when invoked by alias
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+    let output = work_dir.run_jj(["b", "l"]);
+    insta::assert_snapshot!(output, @r"
+    bookmark: foo
+    [EOF]
+    ");
+
+    // Defaults apply to the command an alias expands to
+    test_env.add_config(r#"aliases.l = ["log", "-r@-"]"#);
+    let output = work_dir.run_jj(["l"]);
+    insta::assert_snapshot!(output, @r"
+    first
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_command_defaults_escape_hatch() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    test_env.add_config(r#"command-defaults.log = ["-r", "none()"]"#);
+    // Nothing is shown because of the defaults
+    let output = work_dir.run_jj(["log", "-T", "description"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["log", "-T", "description", "--no-command-defaults"]);
+    insta::assert_snapshot!(output, @r"
+    @  first
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_command_defaults_help() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"command-defaults.log = ["--no-graph", "-n", "20"]"#);
+    let output = test_env.run_jj_in(".", ["log", "--help"]);
+    insta::assert_snapshot!(output.stderr, @r"
+    Hint: Default arguments are configured for `jj log`: --no-graph -n 20
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_command_defaults_select_another_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    test_env.add_config(r#"command-defaults.operation = ["log"]"#);
+    let output = work_dir.run_jj(["operation"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Default arguments for `jj operation` cannot select another command
+    Hint: Check the `command-defaults."operation"` config.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
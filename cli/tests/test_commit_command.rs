@@ -17,6 +17,7 @@ use std::path::PathBuf;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
+use crate::common::force_interactive;
 
 #[test]
 fn test_commit_with_description_from_cli() {
@@ -255,6 +256,7 @@ fn test_commit_interactive_with_paths() {
     let output = work_dir.run_jj(["commit", "-i", "file1", "file2"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    2 files with changes left in the working copy
     Working copy  (@) now at: kkmpptxz 50f426df (no description set)
     Parent commit (@-)      : rlvkpnrz eb640375 edit
     [EOF]
@@ -445,6 +447,59 @@ fn test_commit_paths() {
     ");
 }
 
+#[test]
+fn test_commit_paths_confirm() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.confirm-partial-commit = true");
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.write_file("file3", "baz\n");
+
+    // Declining the prompt leaves the working copy untouched
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["commit", "-m=first", "file1"])
+            .write_stdin("n\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    2 files with changes will be left in the working copy, continue? (yN): Error: Commit was canceled
+    Hint: Pass `--yes` to commit only the changes matching the given paths.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-r@-", "--no-graph", "-T", "description"]);
+    insta::assert_snapshot!(output, @"");
+
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["commit", "-m=first", "file1"])
+            .write_stdin("y\n")
+    });
+    insta::assert_snapshot!(output.success().stderr.take_n_lines(1), @r"
+    2 files with changes will be left in the working copy, continue? (yN): 2 files with changes left in the working copy
+    [EOF]
+    ");
+
+    // No prompt with --yes, or if no changes are left behind
+    let output = work_dir.run_jj(["commit", "-m=second", "--yes", "file2"]);
+    insta::assert_snapshot!(output.success().stderr.take_n_lines(1), @r"
+    1 file with changes left in the working copy
+    [EOF]
+    ");
+    work_dir.run_jj(["commit", "-m=third", "file3"]).success();
+    let output = work_dir.run_jj(["log", "-r::@-", "--no-graph", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    third
+    second
+    first
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_commit_paths_warning() {
     let test_env = TestEnvironment::default();
@@ -457,7 +512,8 @@ fn test_commit_paths_warning() {
     let output = work_dir.run_jj(["commit", "-m=first", "file3"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Warning: The given paths do not match any file: file3
+    Warning: No changes match the given paths: file3
+    2 files with changes left in the working copy
     Working copy  (@) now at: rlvkpnrz 4c6f0146 (no description set)
     Parent commit (@-)      : qpvuntsm 68a50538 (empty) first
     [EOF]
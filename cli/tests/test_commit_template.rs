@@ -581,13 +581,13 @@ fn test_log_evolog_divergence() {
     insta::assert_snapshot!(output, @r"
     @  qpvuntsm?? test.user@example.com 2001-02-03 08:05:08 556daeb7
     │  description 1
-    │  -- operation fec5a045b947 describe commit d0c049cd993a8d3a2e69ba6df98788e264ea9fa1
+    │  -- operation 3bf75932de70 describe commit d0c049cd993a8d3a2e69ba6df98788e264ea9fa1
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 d0c049cd
     │  (no description set)
-    │  -- operation 911e64a1b666 snapshot working copy
+    │  -- operation 70053d72d77c snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 add workspace 'default'
+       -- operation 0548605fd744 add workspace 'default'
     [EOF]
     ");
 
@@ -596,13 +596,13 @@ fn test_log_evolog_divergence() {
     insta::assert_snapshot!(output, @r"
     [1m[38;5;2m@[0m  [1m[4m[38;5;1mq[24mpvuntsm[38;5;9m??[39m [38;5;3mtest.user@example.com[39m [38;5;14m2001-02-03 08:05:08[39m [38;5;12m55[38;5;8m6daeb7[39m[0m
     │  [1mdescription 1[0m
-    │  [38;5;8m--[39m operation [38;5;4mfec5a045b947[39m describe commit d0c049cd993a8d3a2e69ba6df98788e264ea9fa1
+    │  [38;5;8m--[39m operation [38;5;4m3bf75932de70[39m describe commit d0c049cd993a8d3a2e69ba6df98788e264ea9fa1
     ○  [1m[39mq[0m[38;5;8mpvuntsm[39m hidden [38;5;3mtest.user@example.com[39m [38;5;6m2001-02-03 08:05:08[39m [1m[38;5;4md[0m[38;5;8m0c049cd[39m
     │  [38;5;3m(no description set)[39m
-    │  [38;5;8m--[39m operation [38;5;4m911e64a1b666[39m snapshot working copy
+    │  [38;5;8m--[39m operation [38;5;4m70053d72d77c[39m snapshot working copy
     ○  [1m[39mq[0m[38;5;8mpvuntsm[39m hidden [38;5;3mtest.user@example.com[39m [38;5;6m2001-02-03 08:05:07[39m [1m[38;5;4me[0m[38;5;8m8849ae1[39m
        [38;5;2m(empty)[39m [38;5;2m(no description set)[39m
-       [38;5;8m--[39m operation [38;5;4m8f47435a3990[39m add workspace 'default'
+       [38;5;8m--[39m operation [38;5;4m0548605fd744[39m add workspace 'default'
     [EOF]
     ");
 }
@@ -932,6 +932,25 @@ fn test_log_contained_in() {
     [EOF]
     ");
 
+    // The same revset can be used more than once
+    let template = r#"
+    separate(" ",
+      description.first_line(),
+      if(self.contained_in("::main"), label("immutable", "merged"), "unmerged"),
+      if(!self.contained_in("::main"), "[todo]"),
+    ) ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["log", "-r::", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  D unmerged [todo]
+    │ ○  C unmerged [todo]
+    │ ○  B merged
+    │ ○  A merged
+    ├─╯
+    ◆  merged
+    [EOF]
+    ");
+
     // Suppress error that could be detected earlier
     let output = work_dir.run_jj(["log", "-r::", "-T", &template_for_revset("unknown_fn()")]);
     insta::assert_snapshot!(output, @r#"
@@ -1380,6 +1399,17 @@ fn test_log_diff_predefined_formats() {
     [EOF]
     ");
 
+    // status and target path of renamed file
+    let template =
+        r#"diff.files().map(|e| e.status() ++ " " ++ e.target().path() ++ "\n").join("")"#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    modified file1
+    modified file2
+    renamed rename-target
+    [EOF]
+    ");
+
     // custom diff stat template
     let template = indoc! {r#"
         concat(
@@ -209,6 +209,7 @@ fn test_bookmark_names() {
     --ignore-immutable	Allow rewriting immutable commits
     --at-operation	Operation to load the repo at
     --debug	Enable debug logging
+    --no-command-defaults	Ignore the default arguments set by the `command-defaults` config
     --color	When to colorize output
     --quiet	Silence non-primary command output
     --no-pager	Disable the pager
@@ -366,18 +367,21 @@ fn test_aliases_are_resolved(shell: Shell) {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
             --revisions
+            --revisions-file
             --reversed[EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
             --revisions:Which revisions to show
+            --revisions-file:Read more revisions from a file, one revset per line
             --reversed:Show revisions in the opposite order (older revisions first)[EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
             --revisions	Which revisions to show
+            --revisions-file	Read more revisions from a file, one revset per line
             --reversed	Show revisions in the opposite order (older revisions first)
             [EOF]
             ");
@@ -419,21 +423,21 @@ fn test_default_command_is_resolved(shell: Shell) {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
             --revisions
-            --limit
+            --revisions-file
             [EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
             --revisions:Which revisions to show
-            --limit:Limit number of revisions to show
+            --revisions-file:Read more revisions from a file, one revset per line
             [EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
             --revisions	Which revisions to show
-            --limit	Limit number of revisions to show
+            --revisions-file	Read more revisions from a file, one revset per line
             [EOF]
             ");
         }
@@ -447,22 +451,22 @@ fn test_default_command_is_resolved(shell: Shell) {
     match shell {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
+            --revisions-file
             --retain-bookmarks
-            --restore-descendants
             [EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
+            --revisions-file:Read more revisions from a file, one revset per line
             --retain-bookmarks:Do not delete bookmarks pointing to the revisions to abandon
-            --restore-descendants:Do not modify the content of the children of the abandoned commits
             [EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
+            --revisions-file	Read more revisions from a file, one revset per line
             --retain-bookmarks	Do not delete bookmarks pointing to the revisions to abandon
-            --restore-descendants	Do not modify the content of the children of the abandoned commits
             [EOF]
             ");
         }
@@ -927,59 +931,59 @@ fn test_operations() {
         .split('\t')
         .next()
         .unwrap();
-    insta::assert_snapshot!(add_workspace_id, @"12f7cbba4278");
+    insta::assert_snapshot!(add_workspace_id, @"3ed55676c4aa");
 
-    let output = work_dir.complete_fish(["op", "show", "8"]);
+    let output = work_dir.complete_fish(["op", "show", "f"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
-    8f47435a3990	(2001-02-03 08:05:07) add workspace 'default'
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    f1c06526f2b7	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
     // make sure global --at-op flag is respected
-    let output = work_dir.complete_fish(["--at-op", "8ed8c16786e6", "op", "show", "8"]);
+    let output = work_dir.complete_fish(["--at-op", "fbc1088cf683", "op", "show", "f"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
-    8f47435a3990	(2001-02-03 08:05:07) add workspace 'default'
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    f1c06526f2b7	(2001-02-03 08:05:10) describe commit dd7390802e3ca4467ffa43f2e0c0374463d056f3
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["--at-op", "8e"]);
+    let output = work_dir.complete_fish(["--at-op", "fb"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "abandon", "8e"]);
+    let output = work_dir.complete_fish(["op", "abandon", "fb"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "diff", "--op", "8e"]);
+    let output = work_dir.complete_fish(["op", "diff", "--op", "fb"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
     [EOF]
     ");
-    let output = work_dir.complete_fish(["op", "diff", "--from", "8e"]);
+    let output = work_dir.complete_fish(["op", "diff", "--from", "fb"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
     [EOF]
     ");
-    let output = work_dir.complete_fish(["op", "diff", "--to", "8e"]);
+    let output = work_dir.complete_fish(["op", "diff", "--to", "fb"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "restore", "8e"]);
+    let output = work_dir.complete_fish(["op", "restore", "fb"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
     [EOF]
     ");
 
-    let output = work_dir.complete_fish(["op", "revert", "8e"]);
+    let output = work_dir.complete_fish(["op", "revert", "fb"]);
     insta::assert_snapshot!(output, @r"
-    8ed8c16786e6	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
+    fbc1088cf683	(2001-02-03 08:05:11) describe commit 3725536d0ae06d69e46911258cee591dbdb66478
     [EOF]
     ");
 }
@@ -1107,6 +1111,7 @@ fn test_merge_tools() {
     :stat
     :types
     :name-only
+    :name-status
     :git
     :color-words
     diffedit3
@@ -1327,6 +1327,9 @@ fn test_config_get_yields_values_consistent_with_schema_defaults() {
             // `LESSCHARSET` and gives the default as a plain string.
             "ui.pager" => insta::assert_snapshot!(schema_default, @r#""less -FRX""#),
 
+            // `git.import-notes` is an array, which `jj config get` cannot print either.
+            "git.import-notes" => insta::assert_snapshot!(schema_default, @"[]"),
+
             // The `immutable_heads()` revset actually defaults to `builtin_immutable_heads()` but
             // this would be a poor starting point for a custom revset, so the schema "inlines"
             // `builtin_immutable_heads()`.
@@ -1684,6 +1687,108 @@ fn test_config_show_paths() {
     ");
 }
 
+#[test]
+fn test_config_unknown_key_warning() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(indoc! {r#"
+        [ui]
+        color = "never"
+        default-comand = "log"
+
+        [revset]
+        log = "@"
+
+        [custom]
+        anything = true
+
+        [some-tool]
+        option = true
+
+        [merge-tools.my-tool]
+        program = "my-tool"
+    "#});
+
+    let output = test_env.run_jj_in(".", ["config", "get", "ui.color"]);
+    insta::assert_snapshot!(output, @r"
+    never
+    [EOF]
+    ------- stderr -------
+    Warning: Unknown config key `ui.default-comand` in $TEST_ENV/config/config0002.toml:3
+    Hint: Did you mean `ui.default-command`?
+    Warning: Unknown config key `revset` in $TEST_ENV/config/config0002.toml:5
+    Hint: Did you mean `revsets`?
+    [EOF]
+    ");
+
+    // Keys passed by --config are also checked
+    let output = test_env.run_jj_in(
+        ".",
+        ["config", "get", "ui.color", "--config=ui.colour=auto"],
+    );
+    insta::assert_snapshot!(output, @r"
+    never
+    [EOF]
+    ------- stderr -------
+    Warning: Unknown config key `ui.default-comand` in $TEST_ENV/config/config0002.toml:3
+    Hint: Did you mean `ui.default-command`?
+    Warning: Unknown config key `revset` in $TEST_ENV/config/config0002.toml:5
+    Hint: Did you mean `revsets`?
+    Warning: Unknown config key `ui.colour` in --config argument
+    Hint: Did you mean `ui.color`?
+    [EOF]
+    ");
+
+    // Warnings can be suppressed per key or table
+    test_env
+        .add_config(r#"ui.ignore-unknown-config = ["ui.default-comand", "ui.colour", "revset"]"#);
+    let output = test_env.run_jj_in(
+        ".",
+        ["config", "get", "ui.color", "--config=ui.colour=auto"],
+    );
+    insta::assert_snapshot!(output, @r"
+    never
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_config_schema() {
+    let test_env = TestEnvironment::default();
+
+    let output = test_env.run_jj_in(".", ["config", "schema", "ui.color"]);
+    insta::assert_snapshot!(output, @r#"
+    ui.color: "always" | "never" | "debug" | "auto"
+        Whether to colorize command output
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(".", ["config", "schema", "ui.movement"]);
+    insta::assert_snapshot!(output, @r"
+    ui.movement.edit: boolean
+        Whether the next and prev commands should behave as if the --edit flag was passed
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(".", ["config", "schema", "merge-tools"]);
+    insta::assert_snapshot!(
+        output.normalize_stdout_with(|s| s.lines().take(4).map(|l| format!("{l}\n")).collect()),
+        @r#"
+    merge-tools.<name>.conflict-marker-style: "diff" | "snapshot" | "git"
+    merge-tools.<name>.diff-args: array
+    merge-tools.<name>.diff-do-chdir: boolean
+        Invoke the tool in the temporary diff directory. This setting will be removed soon
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(".", ["config", "schema", "ui.col"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No known config variables start with `ui.col`
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_config_author_change_warning() {
     let test_env = TestEnvironment::default();
@@ -33,3 +33,166 @@ fn test_simple_rename() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_simple_rename_stat() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("original", "original");
+    work_dir.write_file("something", "something");
+    work_dir.run_jj(["commit", "-mfirst"]).success();
+    work_dir.remove_file("original");
+    work_dir.write_file("modified", "original");
+    work_dir.write_file("something", "changed");
+
+    // Only the content changes are counted
+    let output = work_dir.run_jj(["diff", "--stat"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    {original => modified} | 0
+    something              | 2 +-
+    2 files changed, 1 insertion(+), 1 deletion(-)
+    [EOF]
+    ");
+
+    work_dir.run_jj(["commit", "-msecond"]).success();
+    let output = work_dir.run_jj(["show", "--stat", "-T", "description", "@-"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    second
+    {original => modified} | 0
+    something              | 2 +-
+    2 files changed, 1 insertion(+), 1 deletion(-)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_simple_copy_stat() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("original", "1\n2\n3\n4\n");
+    work_dir.run_jj(["commit", "-mfirst"]).success();
+    work_dir.write_file("original", "1\n5\n3\n");
+    work_dir.write_file("copy", "1\n2\n3\n4\n");
+
+    let output = work_dir.run_jj(["diff", "--stat"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    copy (copied from original) | 0
+    original                    | 3 +--
+    2 files changed, 1 insertion(+), 2 deletions(-)
+    [EOF]
+    ");
+
+    work_dir.run_jj(["commit", "-msecond"]).success();
+    let output = work_dir.run_jj(["show", "--stat", "-T", "description", "@-"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    second
+    copy (copied from original) | 0
+    original                    | 3 +--
+    2 files changed, 1 insertion(+), 2 deletions(-)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_copy_unmodified_source() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("original", "1\n2\n3\n4\n");
+    work_dir.run_jj(["commit", "-mfirst"]).success();
+    work_dir.write_file("copy", "1\n2\n3\n4\n");
+    work_dir.write_file("edited-copy", "1\n2\n3\n4\n5\n");
+
+    // By default, only modified files are considered as sources
+    let output = work_dir.run_jj(["debug", "copy-detection"]);
+    insta::assert_snapshot!(output, @"");
+
+    test_env.add_config("copy-tracking.detect-copies = true");
+    let output = work_dir.run_jj(["debug", "copy-detection"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    original -> copy (copied)
+    original -> edited-copy (copied)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git", "copy"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/original b/copy
+    copy from original
+    copy to copy
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    C {original => copy}
+    C {original => edited-copy}
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_similar_rename() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let lines = |prefix: &str| {
+        (1..=10)
+            .map(|i| format!("{prefix} {i}\n"))
+            .collect::<Vec<_>>()
+    };
+    work_dir.write_file("similar1", lines("line").concat());
+    work_dir.write_file("dissimilar1", lines("other").concat());
+    work_dir.run_jj(["new"]).success();
+    // 90% of the lines are kept
+    let mut similar = lines("line");
+    similar[9] = "line ten\n".to_owned();
+    work_dir.remove_file("similar1");
+    work_dir.write_file("similar2", similar.concat());
+    // 40% of the lines are kept
+    let mut dissimilar = lines("other");
+    for line in &mut dissimilar[4..] {
+        *line = line.replace("other", "changed");
+    }
+    work_dir.remove_file("dissimilar1");
+    work_dir.write_file("dissimilar2", dissimilar.concat());
+
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    D dissimilar1
+    A dissimilar2
+    R {similar1 => similar2}
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "glob:similar*"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file similar2 (similar1 => similar2):
+        ...
+       7    7: line 7
+       8    8: line 8
+       9    9: line 9
+      10   10: line 10ten
+    [EOF]
+    ");
+
+    // The threshold is configurable
+    let output = work_dir.run_jj(["diff", "-s", "--config=copy-tracking.similarity=20"]);
+    insta::assert_snapshot!(output, @r"
+    R {dissimilar1 => dissimilar2}
+    R {similar1 => similar2}
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s", "--config=copy-tracking.similarity=100"]);
+    insta::assert_snapshot!(output, @r"
+    D dissimilar1
+    A dissimilar2
+    D similar1
+    A similar2
+    [EOF]
+    ");
+}
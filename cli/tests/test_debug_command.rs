@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+use std::time::SystemTime;
+
 use insta::assert_snapshot;
 use regex::Regex;
 
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
+use crate::common::create_commit_with_files;
 
 #[test]
 fn test_debug_fileset() {
@@ -253,6 +257,153 @@ fn test_debug_reindex() {
     ");
 }
 
+#[test]
+fn test_debug_index_verify_and_rebuild() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new"]).success();
+    work_dir.run_jj(["new"]).success();
+    let output = work_dir.run_jj(["debug", "index", "verify"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No problems found in the index.
+    [EOF]
+    ");
+
+    // Flip a byte in every commit index segment
+    let segments_dir = work_dir.root().join(".jj/repo/index/segments");
+    for entry in std::fs::read_dir(&segments_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let mut data = std::fs::read(&path).unwrap();
+        *data.last_mut().unwrap() ^= 0xff;
+        std::fs::write(&path, data).unwrap();
+    }
+    let output = work_dir.run_jj(["debug", "index", "verify"]);
+    let regex = Regex::new(r"segment [0-9a-z]+").unwrap();
+    let output = output
+        .normalize_stdout_with(|text| regex.replace_all(&text, "segment [hash]").into_owned());
+    insta::assert_snapshot!(output, @r"
+    Checksum mismatch in segment [hash]
+    Checksum mismatch in segment [hash]
+    [EOF]
+    ------- stderr -------
+    Error: Found 2 problem(s) in the index
+    Hint: Run `jj debug index rebuild` to rebuild the commit index.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["debug", "index", "rebuild"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebuilding index at operation 9bae034fe31e...
+    Finished indexing 4 commits.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["debug", "index", "verify"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No problems found in the index.
+    [EOF]
+    ");
+
+    // Other commands suggest rebuilding the index if it can't be loaded
+    let op_links_dir = work_dir.root().join(".jj/repo/index/op_links");
+    for entry in std::fs::read_dir(&op_links_dir).unwrap() {
+        let path = entry.unwrap().path();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::create_dir(&path).unwrap();
+    }
+    let output = work_dir.run_jj(["log"]);
+    // The OS error message is platform dependent
+    let regex = Regex::new(r"(?m)^3: .*$").unwrap();
+    let output =
+        output.normalize_stderr_with(|text| regex.replace_all(&text, "3: [os error]").into_owned());
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Internal error: Failed to load the repo
+    Caused by:
+    1: Failed to load associated index file names
+    2: Cannot access $TEST_ENV/repo/.jj/repo/index/op_links/9bae034fe31e0199ea8370902e1747ef0f1a265242880833f3b226f44656e28ebf04ca8ec501a9a66536f08cba80905029407b11be51288a83d53b288d91543d
+    3: [os error]
+    Hint: Run `jj debug index rebuild` to rebuild the commit index.
+    [EOF]
+    [exit status: 255]
+    ");
+}
+
+#[test]
+fn test_debug_conflicts() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit_with_files(
+        &work_dir,
+        "base",
+        &[],
+        &[("file", "1\n2\n3\n"), ("deleted", "base\n")],
+    );
+    create_commit_with_files(
+        &work_dir,
+        "a",
+        &["base"],
+        &[("file", "1\na\n3\n"), ("deleted", "modified\n")],
+    );
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "1\nb\nb\n3\n")]);
+    work_dir.remove_file("deleted");
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    let output = work_dir.run_jj(["debug", "conflicts"]);
+    insta::assert_snapshot!(output, @r#"
+    {"path":"deleted","sides":[{"type":"file","id":"2e0996000b7e9019eabcad29391bf0f5c7702f0b","executable":false},null],"bases":[{"type":"file","id":"df967b96a579e45a18b8251732d16804b2e56a55","executable":false}],"hunks":[{"sides":[{"start":1,"len":1},{"start":1,"len":0}],"bases":[{"start":1,"len":1}]}]}
+    {"path":"file","sides":[{"type":"file","id":"09015cd6f4efe82564e60236b239a6d9364dfc13","executable":false},{"type":"file","id":"987c4b6c8eb5715412e7dd630f61b738e5b81fbc","executable":false}],"bases":[{"type":"file","id":"01e79c32a8c99c557f0757da7cb6d65b3414466d","executable":false}],"hunks":[{"sides":[{"start":2,"len":1},{"start":2,"len":2}],"bases":[{"start":2,"len":1}]}]}
+    [EOF]
+    "#);
+
+    let output = work_dir.run_jj(["debug", "conflicts", "deleted"]);
+    insta::assert_snapshot!(output, @r#"
+    {"path":"deleted","sides":[{"type":"file","id":"2e0996000b7e9019eabcad29391bf0f5c7702f0b","executable":false},null],"bases":[{"type":"file","id":"df967b96a579e45a18b8251732d16804b2e56a55","executable":false}],"hunks":[{"sides":[{"start":1,"len":1},{"start":1,"len":0}],"bases":[{"start":1,"len":1}]}]}
+    [EOF]
+    "#);
+
+    // File contents can be fetched by id
+    let output = work_dir.run_jj([
+        "debug",
+        "object",
+        "file",
+        "file",
+        "09015cd6f4efe82564e60236b239a6d9364dfc13",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    1
+    a
+    3
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["debug", "conflicts", "-rbase"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_debug_conflicts_resolved_hunks_of_unequal_length() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "x\ny\n1\n2\n3\n")]);
+    // Removes the leading lines, which is resolved, but shifts the conflict
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "1\na\n3\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "x\ny\n1\nb\nb\n3\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    let output = work_dir.run_jj(["debug", "conflicts"]);
+    insta::assert_snapshot!(output, @r#"
+    {"path":"file","sides":[{"type":"file","id":"09015cd6f4efe82564e60236b239a6d9364dfc13","executable":false},{"type":"file","id":"89387c02d5bd9b0ace3bc8085e1df5bc22768488","executable":false}],"bases":[{"type":"file","id":"e1043cc49d8b11b9466ea2d4cd9c98f7026c12ab","executable":false}],"hunks":[{"sides":[{"start":2,"len":1},{"start":4,"len":2}],"bases":[{"start":4,"len":1}]}]}
+    [EOF]
+    "#);
+}
+
 #[test]
 fn test_debug_tree() {
     let test_env = TestEnvironment::default();
@@ -330,7 +481,191 @@ fn test_debug_tree() {
     );
 }
 
+#[test]
+fn test_debug_working_copy_check_racy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "contents 1");
+    work_dir.write_file("file2", "contents 2");
+    // Pretend file2 was modified at the same time as the snapshot
+    let file = std::fs::File::options()
+        .write(true)
+        .open(work_dir.root().join("file2"))
+        .unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(3600))
+        .unwrap();
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    let output = work_dir.run_jj(["debug", "working-copy", "--check-racy"]);
+    let regex = Regex::new(r"granularity: \d+ms").unwrap();
+    let output = output
+        .normalize_stdout_with(|text| regex.replace_all(&text, "granularity: [ms]").into_owned());
+    assert_snapshot!(output, @r#"
+    Type: "local"
+    Current operation: OperationId("39cfd0b613de2e33506c5b34cdd4409fd6504be1172d005dc715b62afb89248fb47e34d147fee93b0944c5019453d21a22f0c1c249c6d7b526b6b246eb8d3c3e")
+    Current tree: Merge(Resolved(TreeId("6b87c8ebe329e0aff9244cb127234c11455b9c2a")))
+    Timestamp granularity: [ms]
+    Racy files:
+      "file2" (mtime cleared)
+    [EOF]
+    "#);
+}
+
 fn filter_index_stats(output: CommandOutput) -> CommandOutput {
     let regex = Regex::new(r"    Name: [0-9a-z]+").unwrap();
     output.normalize_stdout_with(|text| regex.replace_all(&text, "    Name: [hash]").into_owned())
 }
+
+#[test]
+fn test_debug_op_bundle() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "secret-feature"])
+        .success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+
+    let output = work_dir.run_jj([
+        "debug",
+        "op-bundle",
+        "export",
+        "-o",
+        "../bundle.json",
+        "-n3",
+    ]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Exported 3 operations to ../bundle.json
+    [EOF]
+    ");
+
+    // The bundle can be rendered outside of the repo
+    let output = test_env.run_jj_in(".", ["debug", "op-bundle", "inspect", "bundle.json"]);
+    assert_snapshot!(output, @r"
+    @  9a68e0ccdfcc test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    │  new empty commit
+    │  args: jj new -m second
+    ○  d73eb74c4543 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    │  create bookmark secret-feature pointing to commit 68a505386f936fff6d718f55005e77ea72589bc1
+    │  args: jj bookmark create -r@ secret-feature
+    ○  c900eb21c42f test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    │  describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    ~  args: jj describe -m first
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        ".",
+        [
+            "debug",
+            "op-bundle",
+            "inspect",
+            "bundle.json",
+            "--op-diff",
+            "--no-graph",
+        ],
+    );
+    assert_snapshot!(output, @r"
+    9a68e0ccdfcc test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    new empty commit
+    args: jj new -m second
+
+    Changed heads:
+    + d60626a4d538
+    - 68a505386f93
+
+    Changed working copy default@:
+    + d60626a4d538
+    - 68a505386f93
+    d73eb74c4543 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    create bookmark secret-feature pointing to commit 68a505386f936fff6d718f55005e77ea72589bc1
+    args: jj bookmark create -r@ secret-feature
+
+    Changed local bookmarks:
+    secret-feature:
+    + 68a505386f93
+    - (absent)
+    c900eb21c42f test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    args: jj describe -m first
+    [EOF]
+    ");
+
+    // Bookmark names are hashed everywhere
+    work_dir
+        .run_jj([
+            "debug",
+            "op-bundle",
+            "export",
+            "-o",
+            "../redacted.json",
+            "--redact",
+        ])
+        .success();
+    let bundle = std::fs::read_to_string(test_env.env_root().join("redacted.json")).unwrap();
+    assert!(!bundle.contains("secret-feature"));
+    let output = test_env.run_jj_in(
+        ".",
+        [
+            "debug",
+            "op-bundle",
+            "inspect",
+            "redacted.json",
+            "--op-diff",
+        ],
+    );
+    assert_snapshot!(output, @r"
+    @  9a68e0ccdfcc test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    │  new empty commit
+    │  args: jj new -m second
+    │
+    │  Changed heads:
+    │  + d60626a4d538
+    │  - 68a505386f93
+    │
+    │  Changed working copy default@:
+    │  + d60626a4d538
+    │  - 68a505386f93
+    ○  d73eb74c4543 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    │  create bookmark redacted-2448ad02cd3b pointing to commit 68a505386f936fff6d718f55005e77ea72589bc1
+    │  args: jj bookmark create -r@ redacted-2448ad02cd3b
+    │
+    │  Changed local bookmarks:
+    │  redacted-2448ad02cd3b:
+    │  + 68a505386f93
+    │  - (absent)
+    ○  c900eb21c42f test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    │  describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    │  args: jj describe -m first
+    │
+    │  Changed heads:
+    │  + 68a505386f93
+    │  - e8849ae12c70
+    │
+    │  Changed working copy default@:
+    │  + 68a505386f93
+    │  - e8849ae12c70
+    ○  0548605fd744 test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
+    │  add workspace 'default'
+    │
+    │  Changed heads:
+    │  + e8849ae12c70
+    │  - 000000000000
+    │
+    │  Changed working copy default@:
+    │  + e8849ae12c70
+    │  - (absent)
+    ○  000000000000 root()
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(".", ["debug", "op-bundle", "inspect", "nonexistent.json"]);
+    assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: Failed to read bundle from nonexistent.json
+    [EOF]
+    [exit status: 1]
+    ");
+}
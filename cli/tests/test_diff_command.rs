@@ -14,6 +14,7 @@
 
 use indoc::indoc;
 use itertools::Itertools as _;
+use regex::Regex;
 
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
@@ -205,9 +206,9 @@ fn test_diff_basic() {
 
     let output = work_dir.run_jj(["diff", "--stat"]);
     insta::assert_snapshot!(output, @r"
-    file2            | 3 +--
-    {file1 => file3} | 0
-    {file2 => file4} | 0
+    file2                     | 3 +--
+    {file1 => file3}          | 0
+    file4 (copied from file2) | 0
     3 files changed, 1 insertion(+), 2 deletions(-)
     [EOF]
     ");
@@ -247,6 +248,15 @@ fn test_diff_basic() {
     let output = work_dir.run_jj(["diff", "-s", "--from=@", "file2"]);
     insta::assert_snapshot!(output, @"");
 
+    // Unless --strict-paths is given
+    let output = work_dir.run_jj(["diff", "-s", "--from=@", "--strict-paths", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes match the given paths: file2
+    [EOF]
+    [exit status: 1]
+    ");
+
     // Deprecated config key
     let output = work_dir.run_jj(["diff", "--config=ui.diff.format=git", "file2"]);
     insta::assert_snapshot!(output, @r#"
@@ -324,6 +334,7 @@ fn test_diff_basic() {
       --stat
       --types
       --name-only
+      --name-status
 
     Usage: jj diff --template <TEMPLATE> --summary [FILESETS]...
 
@@ -399,6 +410,85 @@ fn test_diff_empty() {
     ");
 }
 
+#[test]
+fn test_diff_exit_code() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a b\n");
+    work_dir.write_file("file2", "a\n");
+    work_dir.run_jj(["new"]).success();
+
+    // Empty diff
+    let output = work_dir.run_jj(["diff", "--exit-code"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["diff", "--exit-code", "--quiet"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Non-empty diff
+    work_dir.write_file("file1", "a  b\n");
+    work_dir.write_file("file2", "b\n");
+    let output = work_dir.run_jj(["diff", "--exit-code", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1 b/file1
+    index b2901ea97c..5986e6e774 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,1 +1,1 @@
+    -a b
+    +a  b
+    diff --git a/file2 b/file2
+    index 7898192261..6178079822 100644
+    --- a/file2
+    +++ b/file2
+    @@ -1,1 +1,1 @@
+    -a
+    +b
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["diff", "--exit-code", "--quiet"]);
+    insta::assert_snapshot!(output, @"[exit status: 1]");
+
+    // Only the selected paths are checked
+    let output = work_dir.run_jj(["diff", "--exit-code", "--quiet", "file1"]);
+    insta::assert_snapshot!(output, @"[exit status: 1]");
+
+    // Whitespace changes can be ignored
+    let output = work_dir.run_jj(["diff", "--exit-code", "--quiet", "-b", "file1"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["diff", "--exit-code", "-b", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file1:
+       1    1: a  b
+    [EOF]
+    ");
+
+    // Errors aren't reported as differences
+    let output = work_dir.run_jj(["diff", "--exit-code", "-r", "nonexistent"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revision `nonexistent` doesn't exist
+    [EOF]
+    [exit status: 2]
+    ");
+    let output = work_dir.run_jj([
+        "diff",
+        "--exit-code",
+        "--config=diff.color-words.context=bad",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Config error: Invalid type or value for diff.color-words.context
+    Caused by: invalid type: string "bad", expected usize
+
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 2]
+    "#);
+}
+
 #[test]
 fn test_diff_file_mode() {
     let test_env = TestEnvironment::default();
@@ -659,6 +749,137 @@ fn test_diff_name_only() {
     ");
 }
 
+#[test]
+fn test_diff_name_status() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("deleted", "d\n");
+    work_dir.write_file("modified", "m\n");
+    work_dir.write_file("renamed", "a\nb\nc\nd\ne\n");
+    insta::assert_snapshot!(work_dir.run_jj(["diff", "--name-status"]), @r"
+    A	deleted
+    A	modified
+    A	renamed
+    [EOF]
+    ");
+    work_dir.run_jj(["commit", "-mfirst"]).success();
+    work_dir.remove_file("deleted");
+    work_dir.write_file("modified", "mod\n");
+    work_dir.write_file("added", "add\n");
+    work_dir.remove_file("renamed");
+    work_dir.write_file("moved", "a\nb\nX\nd\ne\n");
+    insta::assert_snapshot!(work_dir.run_jj(["diff", "--name-status"]), @r"
+    A	added
+    D	deleted
+    M	modified
+    R080	renamed	moved
+    [EOF]
+    ");
+
+    // Can be selected as the default format
+    let output = work_dir.run_jj(["diff", "--config=ui.diff-formatter=:name-status"]);
+    insta::assert_snapshot!(output, @r"
+    A	added
+    D	deleted
+    M	modified
+    R080	renamed	moved
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--name-status", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--name-status' cannot be used with '--git'
+
+    Usage: jj diff --name-status [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_diff_merge_base() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "base\n");
+    work_dir.write_file("file2", "base\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "base"])
+        .success();
+    work_dir.run_jj(["new", "-mmain"]).success();
+    work_dir.write_file("file1", "main\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    work_dir.run_jj(["new", "base", "-mbranch"]).success();
+    work_dir.write_file("file2", "branch\n");
+    work_dir.write_file("file3", "added\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "branch"])
+        .success();
+
+    // Changes made on main are reverted in the plain diff
+    let output = work_dir.run_jj(["diff", "--from=main", "--to=branch", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    M file2
+    A file3
+    [EOF]
+    ");
+
+    // Only the changes on the branch are shown
+    let output = work_dir.run_jj(["diff", "--from=main", "--merge-base", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file2
+    A file3
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--from=main", "--merge-base", "--stat", "file3"]);
+    insta::assert_snapshot!(output, @r"
+    file3 | 1 +
+    1 file changed, 1 insertion(+), 0 deletions(-)
+    [EOF]
+    ");
+
+    // Criss-cross merges have multiple merge bases
+    work_dir.run_jj(["new", "main", "branch", "-mm1"]).success();
+    work_dir.run_jj(["new", "main", "branch", "-mm2"]).success();
+    let output = work_dir.run_jj([
+        "diff",
+        "--from=description(m1)",
+        "--to=description(m2)",
+        "--merge-base",
+    ]);
+    let hash_re = Regex::new(r"\b[0-9a-f]{12}\b").unwrap();
+    insta::assert_snapshot!(
+        output.normalize_stderr_with(|s| hash_re.replace_all(&s, "<hash>").into_owned()), @r"
+    ------- stderr -------
+    Error: Revisions <hash> and <hash> have 2 merge bases
+    Hint: Use --from with one of them instead: <hash>, <hash>
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--merge-base"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --from <REVSET>
+
+    Usage: jj diff --from <REVSET> --merge-base [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_diff_renamed_file_and_dir() {
     let test_env = TestEnvironment::default();
@@ -861,6 +1082,99 @@ fn test_diff_relative_paths() {
     ");
 }
 
+#[test]
+fn test_diff_root_relative_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.create_dir_all("dir1/subdir1");
+    work_dir.create_dir("dir2");
+    work_dir.write_file("file1", "foo1\n");
+    work_dir.write_file("dir1/subdir1/file2", "foo2\n");
+    work_dir.write_file("dir2/file3", "foo3\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "bar1\n");
+    work_dir.write_file("dir1/subdir1/file2", "bar2\n");
+    work_dir.write_file("dir2/file3", "bar3\n");
+
+    let sub_dir1 = work_dir.dir("dir1");
+    let output = sub_dir1.run_jj(["diff", "--no-relative-paths"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    Modified regular file dir1/subdir1/file2:
+       1    1: foo2bar2
+    Modified regular file dir2/file3:
+       1    1: foo3bar3
+    Modified regular file file1:
+       1    1: foo1bar1
+    [EOF]
+    ");
+
+    let output = sub_dir1.run_jj(["diff", "--no-relative-paths", "-s"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    M dir1/subdir1/file2
+    M dir2/file3
+    M file1
+    [EOF]
+    ");
+
+    let output = sub_dir1.run_jj(["diff", "--no-relative-paths", "--stat"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    dir1/subdir1/file2 | 2 +-
+    dir2/file3         | 2 +-
+    file1              | 2 +-
+    3 files changed, 3 insertions(+), 3 deletions(-)
+    [EOF]
+    ");
+
+    let output = sub_dir1.run_jj(["diff", "--no-relative-paths", "--name-only"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    dir1/subdir1/file2
+    dir2/file3
+    file1
+    [EOF]
+    ");
+
+    // The default can be set by config, and overridden by --relative-paths
+    test_env.add_config("diff.relative-paths = false");
+    let output = sub_dir1.run_jj(["diff", "--types"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    FF dir1/subdir1/file2
+    FF dir2/file3
+    FF file1
+    [EOF]
+    ");
+    let output = sub_dir1.run_jj(["diff", "--types", "--relative-paths"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    FF subdir1/file2
+    FF ../dir2/file3
+    FF ../file1
+    [EOF]
+    ");
+
+    // Git diffs are always relative to the workspace root
+    let output = sub_dir1.run_jj(["diff", "--git", "--relative-paths", "subdir1"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/dir1/subdir1/file2 b/dir1/subdir1/file2
+    index 54b060eee9..1fe912cdd8 100644
+    --- a/dir1/subdir1/file2
+    +++ b/dir1/subdir1/file2
+    @@ -1,1 +1,1 @@
+    -foo2
+    +bar2
+    [EOF]
+    ");
+
+    // Applies to log-like commands too
+    let output = sub_dir1.run_jj(["show", "-s", "-T", "''"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    M dir1/subdir1/file2
+    M dir2/file3
+    M file1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_hunks() {
     let test_env = TestEnvironment::default();
@@ -956,6 +1270,55 @@ fn test_diff_hunks() {
     ");
 }
 
+#[test]
+fn test_diff_color_words_tokenizer() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "x = foo.bar(1)\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "x = foo.baz(1)\n");
+
+    let output = work_dir.run_jj(["diff", "--tokenizer=words"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file:
+       1    1: x = foo.barbaz(1)
+    [EOF]
+    ");
+
+    // Punctuation is part of the word
+    let output = work_dir.run_jj(["diff", "--tokenizer=whitespace"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file:
+       1    1: x = foo.bar(1)foo.baz(1)
+    [EOF]
+    ");
+
+    // A period between letters doesn't break a Unicode word
+    let output = work_dir.run_jj(["diff", "--tokenizer=unicode-words"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file:
+       1    1: x = foo.barfoo.baz(1)
+    [EOF]
+    ");
+
+    // The flag overrides the config
+    test_env.add_config("diff.color-words.tokenizer = 'whitespace'");
+    let output = work_dir.run_jj(["diff"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file:
+       1    1: x = foo.bar(1)foo.baz(1)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--tokenizer=words"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file:
+       1    1: x = foo.barbaz(1)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_color_words_inlining_threshold() {
     let test_env = TestEnvironment::default();
@@ -3038,7 +3401,7 @@ fn test_diff_external_tool() {
     work_dir.write_file("file2", "foo\nbar\n");
     work_dir.write_file("file3", "foo\n");
 
-    // nonzero exit codes should print a warning
+    // unexpected nonzero exit codes are errors
     std::fs::write(&edit_script, "fail").unwrap();
     let output = work_dir.run_jj(["diff", "--config=ui.diff-formatter=fake-diff-editor"]);
     let insta_portable_exit_status = {
@@ -3049,12 +3412,14 @@ fn test_diff_external_tool() {
     insta_portable_exit_status.bind(|| {
         insta::assert_snapshot!(output, @r"
         ------- stderr -------
-        Warning: Tool exited with <exit status>: 1 (run with --debug to see the exact invocation)
+        Error: Failed to generate diff
+        Caused by: Tool exited with <exit status>: 1 (run with --debug to see the exact invocation)
         [EOF]
+        [<exit status>: 1]
         ");
     });
 
-    // nonzero exit codes should not print a warning if it's an expected exit code
+    // nonzero exit codes aren't errors if they're expected
     std::fs::write(&edit_script, "fail").unwrap();
     let output = work_dir.run_jj([
         "diff",
@@ -3177,7 +3542,7 @@ fn test_diff_external_tool() {
     [EOF]
     ");
 
-    // Non-zero exit code isn't an error
+    // The output is shown even if the tool fails
     std::fs::write(&edit_script, "print diff\0fail").unwrap();
     let output = work_dir.run_jj(["show", "--tool=fake-diff-editor"]);
     insta::assert_snapshot!(output.normalize_stderr_exit_status(), @r"
@@ -3191,8 +3556,10 @@ fn test_diff_external_tool() {
     diff
     [EOF]
     ------- stderr -------
-    Warning: Tool exited with exit status: 1 (run with --debug to see the exact invocation)
+    Error: Failed to generate diff
+    Caused by: Tool exited with exit status: 1 (run with --debug to see the exact invocation)
     [EOF]
+    [exit status: 1]
     ");
 
     // Deprecated config key
@@ -3947,3 +4314,132 @@ fn test_diff_revisions() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_diff_conflict_sides() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "1\n2\n3\n4\n5\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "1a\n2\n3\n4\n5a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "1b\n2\n3\n4\n5b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    let output = work_dir.run_jj(["diff", "--conflict"]);
+    insta::assert_snapshot!(output, @r"
+    Conflict 1 of 2 in file, side #1 (from a) => side #2 (from b):
+       1    1: 1a1b
+    Conflict 2 of 2 in file, side #1 (from a) => side #2 (from b):
+       1    1: 5a5b
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--conflict", "--conflict-base", "file"]);
+    insta::assert_snapshot!(output, @r"
+    Conflict 1 of 2 in file, side #1 (from a) => side #2 (from b):
+       1    1: 1a1b
+    Conflict 1 of 2 in file, base #1 => side #1 (from a):
+       1    1: 11a
+    Conflict 1 of 2 in file, base #1 => side #2 (from b):
+       1    1: 11b
+    Conflict 2 of 2 in file, side #1 (from a) => side #2 (from b):
+       1    1: 5a5b
+    Conflict 2 of 2 in file, base #1 => side #1 (from a):
+       1    1: 55a
+    Conflict 2 of 2 in file, base #1 => side #2 (from b):
+       1    1: 55b
+    [EOF]
+    ");
+
+    // Only the side which came from the new parent can be attributed after
+    // rebasing.
+    work_dir.run_jj(["rebase", "-r", "b", "-d", "a"]).success();
+    let output = work_dir.run_jj(["diff", "--conflict", "-r", "b"]);
+    insta::assert_snapshot!(output, @r"
+    Conflict 1 of 2 in file, side #1 (from a) => side #2:
+       1    1: 1a1b
+    Conflict 2 of 2 in file, side #1 (from a) => side #2:
+       1    1: 5a5b
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--conflict", "-r", "base"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No conflicts found at this revision
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["diff", "--conflict", "-r", "a", "-r", "b"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: --conflict requires a single revision
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_diff_conflict_sides_3_sided() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "base\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "b\n")]);
+    create_commit_with_files(&work_dir, "c", &["base"], &[("file", "c\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b", "c"], &[]);
+
+    // Each side is compared to the first side
+    let output = work_dir.run_jj(["diff", "--conflict"]);
+    insta::assert_snapshot!(output, @r"
+    Conflict 1 of 1 in file, side #1 (from a) => side #2 (from b):
+       1    1: ab
+    Conflict 1 of 1 in file, side #1 (from a) => side #3 (from c):
+       1    1: ac
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--conflict", "--conflict-base"]);
+    insta::assert_snapshot!(output, @r"
+    Conflict 1 of 1 in file, side #1 (from a) => side #2 (from b):
+       1    1: ab
+    Conflict 1 of 1 in file, side #1 (from a) => side #3 (from c):
+       1    1: ac
+    Conflict 1 of 1 in file, base #1 => side #1 (from a):
+       1    1: basea
+    Conflict 1 of 1 in file, base #1 => side #2 (from b):
+       1    1: baseb
+    Conflict 1 of 1 in file, base #2 => side #3 (from c):
+       1    1: basec
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_diff_materialized_conflict_cache_stats() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "base\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    // The conflict is materialized once, and reused by the second format
+    let output = work_dir
+        .run_jj(["diff", "--from=a", "--stat", "--git", "--debug"])
+        .success();
+    let stats = output
+        .stderr
+        .normalized()
+        .lines()
+        .filter(|line| line.starts_with("Materialized conflict cache:"))
+        .join("\n");
+    insta::assert_snapshot!(stats, @"Materialized conflict cache: 1 hits, 1 misses");
+}
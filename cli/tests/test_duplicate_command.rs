@@ -73,7 +73,7 @@ fn test_duplicate() {
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 9fdb56d75a27 (2001-02-03 08:05:13) create bookmark c pointing to commit 387b928721d9f2efff819ccce81868f32537d71f
+    Restored to operation: 31842257196e (2001-02-03 08:05:13) create bookmark c pointing to commit 387b928721d9f2efff819ccce81868f32537d71f
     [EOF]
     ");
     let output = work_dir.run_jj(["duplicate" /* duplicates `c` */]);
@@ -2319,6 +2319,66 @@ fn test_duplicate_insert_after_before() {
     ");
 }
 
+#[test]
+fn test_duplicate_descendants_and_ancestors() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    // Originals are labeled by their bookmarks, duplicates by their description
+    test_env.add_config(
+        r#"
+        [template-aliases]
+        'name_of(c)' = '''if(c.root(), "root", if(c.bookmarks(), c.bookmarks(), c.description().first_line() ++ "'"))'''
+        "#,
+    );
+    let template = r#"name_of(self) ++ " <- " ++ parents.map(|c| name_of(c)).join(", ") ++ "\n""#;
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["a"]);
+    create_commit(&work_dir, "d", &["b", "c"]);
+    create_commit(&work_dir, "e", &["d"]);
+
+    // The merge keeps its parent outside of the duplicated set
+    work_dir
+        .run_jj(["duplicate", "--descendants", "b"])
+        .success();
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r=~bookmarks() & ~root()",
+        "-T",
+        template,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    e' <- d'
+    d' <- b', c
+    b' <- a
+    [EOF]
+    ");
+
+    // The whole tree is duplicated, including the merge
+    work_dir.run_jj(["undo"]).success();
+    work_dir
+        .run_jj(["duplicate", "--ancestors-to", "a", "e"])
+        .success();
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r=~bookmarks() & ~root()",
+        "-T",
+        template,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    e' <- d'
+    d' <- b', c'
+    c' <- a'
+    b' <- a'
+    a' <- root
+    [EOF]
+    ");
+}
+
 // https://github.com/jj-vcs/jj/issues/1050
 #[test]
 fn test_undo_after_duplicate() {
@@ -2350,7 +2410,7 @@ fn test_undo_after_duplicate() {
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 73a36404358e (2001-02-03 08:05:09) create bookmark a pointing to commit 7d980be7a1d499e4d316ab4c01242885032f7eaf
+    Restored to operation: 58e1f32e4bf2 (2001-02-03 08:05:09) create bookmark a pointing to commit 7d980be7a1d499e4d316ab4c01242885032f7eaf
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
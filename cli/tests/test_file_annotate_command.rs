@@ -263,3 +263,32 @@ fn test_annotate_with_template() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_annotate_follow_rename() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("old.txt", "line1\nline2\nline3\nline4\nline5\n");
+    work_dir.run_jj(["commit", "-m=create"]).success();
+
+    work_dir.remove_file("old.txt");
+    work_dir.write_file("new.txt", "line1\nline2\nedited in rename\nline4\nline5\n");
+    work_dir.run_jj(["commit", "-m=rename"]).success();
+
+    append_to_file(&work_dir.root().join("new.txt"), "added after rename");
+    work_dir.run_jj(["describe", "-m=edit"]).success();
+
+    let template = r#"pad_end(8, commit.description().first_line()) ++ content"#;
+    let output = work_dir.run_jj(["file", "annotate", "new.txt", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    create  line1
+    create  line2
+    rename  edited in rename
+    create  line4
+    create  line5
+    edit    added after rename
+    [EOF]
+    ");
+}
@@ -132,7 +132,11 @@ fn test_track_untrack_sparse() {
     // Trying to manually track a file that's not included in the sparse working has
     // no effect. TODO: At least a warning would be useful
     let output = work_dir.run_jj(["file", "track", "file2"]);
-    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No matching files
+    [EOF]
+    ");
     let output = work_dir.run_jj(["file", "list"]);
     insta::assert_snapshot!(output, @r"
     file1
@@ -160,7 +164,12 @@ fn test_auto_track() {
 
     // Can manually track paths
     let output = work_dir.run_jj(["file", "track", "file3.md"]);
-    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Started tracking 1 files:
+      file3.md
+    [EOF]
+    ");
     let output = work_dir.run_jj(["file", "list"]);
     insta::assert_snapshot!(output, @r"
     file1.rs
@@ -188,7 +197,12 @@ fn test_auto_track() {
 
     // But `jj file track` wants CWD-relative paths
     let output = sub_dir.run_jj(["file", "track", "file1.rs"]);
-    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Started tracking 1 files:
+      file1.rs
+    [EOF]
+    ");
     let output = sub_dir.run_jj(["file", "list"]);
     insta::assert_snapshot!(output.normalize_backslash(), @r"
     ../file1.rs
@@ -210,7 +224,12 @@ fn test_track_ignored() {
 
     // Track an unignored path
     let output = work_dir.run_jj(["file", "track", "file1"]);
-    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Started tracking 1 files:
+      file1
+    [EOF]
+    ");
     let output = work_dir.run_jj(["file", "list"]);
     insta::assert_snapshot!(output, @r"
     file1
@@ -218,7 +237,12 @@ fn test_track_ignored() {
     ");
     // Track an ignored path
     let output = work_dir.run_jj(["file", "track", "file1.bak"]);
-    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Skipped 1 ignored files:
+      file1.bak: ignored by `*.bak` in .gitignore
+    [EOF]
+    ");
     // TODO: We should teach `jj file track` to track ignored paths (possibly
     // requiring a flag)
     let output = work_dir.run_jj(["file", "list"]);
@@ -227,3 +251,66 @@ fn test_track_ignored() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_track_glob_report() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(r#"snapshot.auto-track = 'none()'"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file(".gitignore", "*.bak\n");
+    work_dir.create_dir_all("sub/out");
+    work_dir.write_file("sub/.gitignore", "out/\n");
+    work_dir.write_file("sub/a.txt", "a");
+    work_dir.write_file("sub/b.txt", "b");
+    work_dir.write_file("sub/c.bak", "c");
+    work_dir.write_file("sub/out/d.txt", "d");
+    work_dir.write_file("sub/e.rs", "e");
+    work_dir.run_jj(["file", "track", "sub/a.txt"]).success();
+
+    // Dry run from a subdirectory with a relative pattern
+    let sub_dir = work_dir.dir("sub");
+    let output = sub_dir.run_jj(["file", "track", "--dry-run", "glob:**/*.txt", "glob:*.bak"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Would start tracking 1 files:
+      b.txt
+    Already tracked 1 files
+    Skipped 2 ignored files:
+      c.bak: ignored by `*.bak` in ../.gitignore
+      out/d.txt: ignored by `out/` in .gitignore
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    sub/a.txt
+    [EOF]
+    ");
+
+    let output = sub_dir.run_jj(["file", "track", "glob:**/*.txt", "glob:*.bak"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Started tracking 1 files:
+      b.txt
+    Already tracked 1 files
+    Skipped 2 ignored files:
+      c.bak: ignored by `*.bak` in ../.gitignore
+      out/d.txt: ignored by `out/` in .gitignore
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    sub/a.txt
+    sub/b.txt
+    [EOF]
+    ");
+
+    // Nothing on disk matches
+    let output = work_dir.run_jj(["file", "track", "glob:*.md"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No matching files
+    [EOF]
+    ");
+}
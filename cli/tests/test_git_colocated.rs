@@ -395,7 +395,7 @@ fn test_git_colocated_rebase_on_import() {
     Working copy  (@) now at: zsuskuln d4658336 (empty) (no description set)
     Parent commit (@-)      : qpvuntsm cbd6c887 master | add a file
     Added 0 files, modified 1 files, removed 0 files
-    Done importing changes from the underlying Git repo.
+    Imported 1 refs from the underlying Git repo.
     [EOF]
     ");
 }
@@ -459,11 +459,163 @@ fn test_git_colocated_bookmarks() {
     Abandoned 1 commits that are no longer reachable.
     Working copy  (@) now at: yqosqzyt 507c0edc (empty) (no description set)
     Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
-    Done importing changes from the underlying Git repo.
+    Imported 1 refs from the underlying Git repo.
     [EOF]
     ");
 }
 
+#[test]
+fn test_git_colocated_auto_import() {
+    let test_env = TestEnvironment::default();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::init(work_dir.root());
+    work_dir
+        .run_jj(["git", "init", "--git-repo", "."])
+        .success();
+    work_dir.run_jj(["describe", "-m", "foo"]).success();
+    work_dir.run_jj(["new"]).success();
+    let target_id = work_dir
+        .run_jj(["log", "--no-graph", "-T=commit_id", "-r=description(foo)"])
+        .success()
+        .stdout
+        .into_raw();
+    let create_git_ref = |name: &str| {
+        git_repo
+            .reference(
+                name,
+                gix::ObjectId::from_hex(target_id.as_bytes()).unwrap(),
+                gix::refs::transaction::PreviousValue::Any,
+                "test",
+            )
+            .unwrap();
+    };
+    let list_bookmarks = || work_dir.run_jj(["bookmark", "list", "-T", r#"name ++ "\n""#]);
+
+    // Nothing is imported implicitly
+    test_env.add_config(r#"git.auto-import = "never""#);
+    create_git_ref("refs/heads/b1");
+    insta::assert_snapshot!(list_bookmarks(), @"");
+    work_dir.run_jj(["git", "import"]).success();
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    b1
+    [EOF]
+    ");
+
+    // Refs are imported only if the working copy changed
+    test_env.add_config(r#"git.auto-import = "on-snapshot""#);
+    create_git_ref("refs/heads/b2");
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    b1
+    [EOF]
+    ");
+    work_dir.write_file("file", "contents");
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    b1
+    b2
+    [EOF]
+    ------- stderr -------
+    Imported 1 refs from the underlying Git repo.
+    [EOF]
+    ");
+
+    // Refs are imported by every command
+    test_env.add_config(r#"git.auto-import = "always""#);
+    create_git_ref("refs/heads/b3");
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    b1
+    b2
+    b3
+    [EOF]
+    ------- stderr -------
+    Imported 1 refs from the underlying Git repo.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_colocated_auto_import_unchanged() {
+    let test_env = TestEnvironment::default();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::init(work_dir.root());
+    work_dir
+        .run_jj(["git", "init", "--git-repo", "."])
+        .success();
+    work_dir.run_jj(["describe", "-m", "foo"]).success();
+    work_dir.run_jj(["new"]).success();
+    let state_path = work_dir.root().join(".jj/repo/git_import_state");
+    let read_state = || std::fs::read_to_string(&state_path).unwrap();
+
+    // The state of the refs is recorded, and isn't updated if nothing changed
+    let state = read_state();
+    let output = work_dir.run_jj(["bookmark", "list"]);
+    insta::assert_snapshot!(output, @"");
+    assert_eq!(read_state(), state);
+
+    // The refs are imported again if they change in Git
+    let target_id = work_dir
+        .run_jj(["log", "--no-graph", "-T=commit_id", "-r=description(foo)"])
+        .success()
+        .stdout
+        .into_raw();
+    git_repo
+        .reference(
+            "refs/heads/master",
+            gix::ObjectId::from_hex(target_id.as_bytes()).unwrap(),
+            gix::refs::transaction::PreviousValue::Any,
+            "test",
+        )
+        .unwrap();
+    let output = work_dir.run_jj(["bookmark", "list", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    master
+    [EOF]
+    ------- stderr -------
+    Imported 1 refs from the underlying Git repo.
+    [EOF]
+    ");
+    assert_ne!(read_state(), state);
+
+    // The refs are scanned again if the refs known to jj change
+    let state = read_state();
+    work_dir.run_jj(["bookmark", "delete", "master"]).success();
+    let output = work_dir.run_jj(["bookmark", "list"]);
+    insta::assert_snapshot!(output, @"");
+    assert_ne!(read_state(), state);
+
+    // A ref file rewritten with the same size and modification time is still
+    // noticed
+    git_repo
+        .reference(
+            "refs/heads/master",
+            gix::ObjectId::from_hex(target_id.as_bytes()).unwrap(),
+            gix::refs::transaction::PreviousValue::Any,
+            "test",
+        )
+        .unwrap();
+    work_dir.run_jj(["bookmark", "list"]).success();
+    let ref_path = work_dir.root().join(".git/refs/heads/master");
+    let mtime = std::fs::metadata(&ref_path).unwrap().modified().unwrap();
+    let wc_commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-T=commit_id", "-r=@"])
+        .success()
+        .stdout
+        .into_raw();
+    std::fs::write(&ref_path, format!("{wc_commit_id}\n")).unwrap();
+    let file = std::fs::File::options().write(true).open(&ref_path).unwrap();
+    file.set_modified(mtime).unwrap();
+    drop(file);
+    let output = work_dir.run_jj(["bookmark", "list", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    master
+    [EOF]
+    ------- stderr -------
+    Imported 1 refs from the underlying Git repo.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-T=description", "-r=master"]);
+    insta::assert_snapshot!(output, @"");
+}
+
 #[test]
 fn test_git_colocated_bookmark_forget() {
     let test_env = TestEnvironment::default();
@@ -720,7 +872,7 @@ fn test_git_colocated_rebase_dirty_working_copy() {
     ------- stderr -------
     Warning: Failed to export some bookmarks:
       feature@git: Modified ref had been deleted in Git
-    Done importing changes from the underlying Git repo.
+    Imported 1 refs from the underlying Git repo.
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&work_dir), @r"
@@ -966,7 +1118,7 @@ fn test_git_colocated_undo_head_move() {
     let output = work_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 28f10852fc94 (2001-02-03 08:05:12) new empty commit
+    Restored to operation: 48397dec8fa2 (2001-02-03 08:05:12) new empty commit
     Working copy  (@) now at: royxmykx e7d0d5fd (empty) (no description set)
     Parent commit (@-)      : qpvuntsm e8849ae1 (empty) (no description set)
     [EOF]
@@ -1637,6 +1789,43 @@ fn test_git_colocated_operation_cleanup() {
     insta::assert_snapshot!(String::from_utf8(output.stdout).unwrap(), @"");
 }
 
+#[test]
+fn test_git_colocated_restore_jj_gitignore() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let work_dir = test_env.work_dir("repo");
+    let jj_gitignore_path = work_dir.root().join(".jj").join(".gitignore");
+
+    // The file is recreated when snapshotting
+    std::fs::remove_file(&jj_gitignore_path).unwrap();
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Recreated missing .jj/.gitignore file to hide the .jj directory from Git
+    [EOF]
+    ");
+    assert_eq!(std::fs::read_to_string(&jj_gitignore_path).unwrap(), "/*\n");
+
+    // Nothing happens if the file exists
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @"");
+
+    // The file isn't needed if .jj is excluded by the Git repo
+    std::fs::remove_file(&jj_gitignore_path).unwrap();
+    work_dir.write_file(".git/info/exclude", "/.jj/\n");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @"");
+    assert!(!jj_gitignore_path.exists());
+
+    // The check can be disabled
+    work_dir.write_file(".git/info/exclude", "");
+    let output = work_dir.run_jj(["file", "list", "--config=git.restore-jj-gitignore=false"]);
+    insta::assert_snapshot!(output, @"");
+    assert!(!jj_gitignore_path.exists());
+}
+
 #[must_use]
 fn get_bookmark_output(work_dir: &TestWorkDir) -> CommandOutput {
     // --quiet to suppress deleted bookmarks hint
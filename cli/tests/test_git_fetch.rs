@@ -14,6 +14,7 @@
 
 use std::io::Write as _;
 
+use gix::refs::transaction::PreviousValue;
 use testutils::git;
 
 use crate::common::CommandOutput;
@@ -524,7 +525,7 @@ fn test_git_fetch_from_remote_named_git() {
       @git: vkponlun 400c483d message
     [EOF]
     ------- stderr -------
-    Done importing changes from the underlying Git repo.
+    Imported 1 refs from the underlying Git repo.
     [EOF]
     ");
 }
@@ -1243,7 +1244,7 @@ fn test_git_fetch_undo() {
     let output = target_dir.run_jj(["undo"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+    Restored to operation: 0548605fd744 (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     // The undo works as expected
@@ -1332,7 +1333,7 @@ fn test_fetch_undo_what() {
     let output = work_dir.run_jj(["op", "restore", "--what", "repo", &base_operation_id]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+    Restored to operation: 0548605fd744 (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -1363,7 +1364,7 @@ fn test_fetch_undo_what() {
     ]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+    Restored to operation: 0548605fd744 (2001-02-03 08:05:07) add workspace 'default'
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -2035,3 +2036,211 @@ fn test_git_fetch_tracked_multiple_remotes() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_git_fetch_tags() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "origin");
+    let commit_id = git_repo
+        .find_reference("refs/heads/origin")
+        .unwrap()
+        .peel_to_id_in_place()
+        .unwrap()
+        .detach();
+    let tree_id = git_repo
+        .find_commit(commit_id)
+        .unwrap()
+        .tree_id()
+        .unwrap()
+        .detach();
+    git_repo
+        .tag_reference("v1.0", commit_id, PreviousValue::MustNotExist)
+        .unwrap();
+    git::write_annotated_tag(&git_repo, "v2.0", commit_id, "Release 2.0");
+    git_repo
+        .tag_reference("tree", tree_id, PreviousValue::MustNotExist)
+        .unwrap();
+    let template = r#"description.first_line() ++ " " ++ tags ++ "\n""#;
+
+    let output = work_dir.run_jj(["git", "fetch", "--config=git.fetch-tags=false"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: origin@origin [new] untracked
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["tag", "list"]);
+    insta::assert_snapshot!(output, @"");
+
+    // The annotated tag is peeled, and the tag pointing to a tree is skipped
+    let output = work_dir.run_jj(["git", "fetch", "--tags"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    tag: v1.0@git [new] 
+    tag: v2.0@git [new] 
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=tags(v2.0)", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    message v1.0 v2.0
+    [EOF]
+    ");
+
+    // --no-tags overrides the setting
+    git_repo
+        .tag_reference("v3.0", commit_id, PreviousValue::MustNotExist)
+        .unwrap();
+    let output = work_dir.run_jj(["git", "fetch", "--no-tags", "--config=git.fetch-tags=true"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // A known tag moved to a non-commit object is reported once
+    let backend_repo = git::open(work_dir.root().join(".jj/repo/store/git"));
+    backend_repo
+        .tag_reference("v1.0", tree_id, PreviousValue::Any)
+        .unwrap();
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    tag: v1.0@git [deleted] 
+    Warning: Skipped Git refs pointing to non-commit objects:
+      refs/tags/v1.0
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_fetch_import_notes() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.import-notes = ['commits', 'review']");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "origin");
+    let commit_id = git_repo
+        .find_reference("refs/heads/origin")
+        .unwrap()
+        .peel_to_id_in_place()
+        .unwrap()
+        .detach();
+    let notes_commit_id = git::add_commit(
+        &git_repo,
+        "refs/notes/commits",
+        &commit_id.to_string(),
+        b"CI passed\n",
+        "Notes added by 'git notes add'",
+        &[],
+    )
+    .commit_id;
+    // Notes of commits that aren't fetched are skipped
+    git::add_commit(
+        &git_repo,
+        "refs/notes/commits",
+        "0123456789abcdef0123456789abcdef01234567",
+        b"unknown commit\n",
+        "Notes added by 'git notes add'",
+        &[notes_commit_id],
+    );
+
+    // The remote doesn't have refs/notes/review
+    work_dir.run_jj(["git", "fetch"]).success();
+    let template = r#"
+        separate(" ",
+          description.first_line(),
+          git_notes.map(|n| n.key() ++ "=" ++ n.value())
+        ) ++ "\n"
+    "#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=origin@origin", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    message commits=CI passed
+    [EOF]
+    ");
+
+    // Imported notes are read-only
+    let output = work_dir.run_jj([
+        "note",
+        "set",
+        "-r=origin@origin",
+        "--key=git:commits",
+        "-m=x",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Notes with keys starting with `git:` are imported from Git and can't be modified
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_git_fetch_unshallow() {
+    let test_env = TestEnvironment::default();
+    let root_dir = test_env.work_dir("");
+    let clone_dir = test_env.work_dir("clone");
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    add_commit_to_branch(&git_repo, "main", "first");
+    add_commit_to_branch(&git_repo, "main", "second");
+    add_commit_to_branch(&git_repo, "main", "third");
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+
+    root_dir
+        .run_jj(["git", "clone", "--depth", "1", "origin", "clone"])
+        .success();
+    let log_history = || {
+        clone_dir.run_jj([
+            "log",
+            "-r::main@origin ~ root()",
+            "-Tdescription.first_line()",
+        ])
+    };
+    insta::assert_snapshot!(log_history(), @r"
+    ◆  third
+    │
+    ~
+    [EOF]
+    ");
+
+    // Fetching doesn't deepen the history by default
+    add_commit_to_branch(&git_repo, "main", "fourth");
+    clone_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(log_history(), @r"
+    ◆  fourth
+    ◆  third
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = clone_dir.run_jj(["git", "fetch", "--unshallow"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+    insta::assert_snapshot!(log_history(), @r"
+    ◆  fourth
+    ◆  third
+    ◆  second
+    ◆  first
+    │
+    ~
+    [EOF]
+    ");
+
+    // Unshallowing a complete repository is a no-op
+    let output = clone_dir.run_jj(["git", "fetch", "--unshallow"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
@@ -116,8 +116,8 @@ fn test_git_push_current_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f
       Add bookmark my-bookmark to 88ca14a7d46f
+      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -125,8 +125,8 @@ fn test_git_push_current_bookmark() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f
       Add bookmark my-bookmark to 88ca14a7d46f
+      Move forward bookmark bookmark2 from 38a204733702 to 88ca14a7d46f
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -282,6 +282,126 @@ fn test_git_push_other_remote_has_bookmark() {
     ");
 }
 
+#[test]
+fn test_git_push_multiple_remotes() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    test_env.add_config(r#"revset-aliases."immutable_heads()" = "none()""#);
+    // Create a second, empty remote
+    test_env.run_jj_in(".", ["git", "init", "mirror"]).success();
+    let mirror_git_repo_path = git_repo_dir_for_jj_repo(&test_env.work_dir("mirror"));
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "mirror",
+            mirror_git_repo_path.to_str().unwrap(),
+        ])
+        .success();
+    work_dir
+        .run_jj(["git", "remote", "add", "broken", "nonexistent"])
+        .success();
+
+    // The same bookmark is pushed to each remote
+    work_dir.run_jj(["edit", "bookmark1"]).success();
+    work_dir.run_jj(["describe", "-m=modified"]).success();
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--allow-new",
+        "-b=bookmark1",
+        "--remote=origin",
+        "--remote=mirror",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to 75a0d11d1fe5
+    Changes to push to mirror:
+      Add bookmark bookmark1 to 75a0d11d1fe5
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 75a0d11d (empty) modified
+      @mirror: qpvuntsm 75a0d11d (empty) modified
+      @origin: qpvuntsm 75a0d11d (empty) modified
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin: zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+
+    // Failure to push to one remote doesn't stop pushing to the others
+    work_dir.run_jj(["describe", "-m=modified again"]).success();
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--allow-new",
+        "-b=bookmark1",
+        "--remote=broken",
+        "--remote=origin",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to broken:
+      Add bookmark bookmark1 to de150e2551b6
+    Warning: Failed to push to broken: Could not find repository at '$TEST_ENV/local/nonexistent'
+    Changes to push to origin:
+      Move sideways bookmark bookmark1 from 75a0d11d1fe5 to de150e2551b6
+    Error: Failed to push to 1 of 2 remotes: broken
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm de150e25 (empty) modified again
+      @mirror (ahead by 1 commits, behind by 1 commits): qpvuntsm hidden 75a0d11d (empty) modified
+      @origin: qpvuntsm de150e25 (empty) modified again
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin: zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+
+    // Unless --atomic-across-remotes is given
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--allow-new",
+        "-b=bookmark1",
+        "--remote=broken",
+        "--remote=mirror",
+        "--atomic-across-remotes",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to broken:
+      Add bookmark bookmark1 to de150e2551b6
+    Error: Could not find repository at '$TEST_ENV/local/nonexistent'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The remotes can be configured
+    test_env.add_config(r#"git.push-remotes = ["origin", "mirror"]"#);
+    let output = work_dir.run_jj(["git", "push", "-b=bookmark1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Bookmark bookmark1@origin already matches bookmark1
+    Nothing changed.
+    Changes to push to mirror:
+      Move sideways bookmark bookmark1 from 75a0d11d1fe5 to de150e2551b6
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm de150e25 (empty) modified again
+      @mirror: qpvuntsm de150e25 (empty) modified again
+      @origin: qpvuntsm de150e25 (empty) modified again
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin: zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_forward_unexpectedly_moved() {
     let test_env = TestEnvironment::default();
@@ -656,9 +776,9 @@ fn test_git_push_multiple() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Delete bookmark bookmark1 from 9b2e76de3920
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
       Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
+      Delete bookmark bookmark1 from 9b2e76de3920
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -673,9 +793,12 @@ fn test_git_push_multiple() {
     ]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: The following bookmarks on ancestors of the pushed commits are out of date on origin:
+      bookmark2
+    Hint: Push them together with `--bookmark`, or use `--also-stale-ancestors` to include them.
     Changes to push to origin:
-      Delete bookmark bookmark1 from 9b2e76de3920
       Add bookmark my-bookmark to 352fa1879f75
+      Delete bookmark bookmark1 from 9b2e76de3920
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -692,9 +815,12 @@ fn test_git_push_multiple() {
     ]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: The following bookmarks on ancestors of the pushed commits are out of date on origin:
+      bookmark2
+    Hint: Push them together with `--bookmark`, or use `--also-stale-ancestors` to include them.
     Changes to push to origin:
-      Delete bookmark bookmark1 from 9b2e76de3920
       Add bookmark my-bookmark to 352fa1879f75
+      Delete bookmark bookmark1 from 9b2e76de3920
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -703,8 +829,8 @@ fn test_git_push_multiple() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Delete bookmark bookmark1 from 9b2e76de3920
       Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
+      Delete bookmark bookmark1 from 9b2e76de3920
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -732,8 +858,8 @@ fn test_git_push_multiple() {
     Warning: Refusing to push deleted bookmark bookmark1
     Hint: Push deleted bookmarks with --deleted or forget the bookmark to suppress this warning.
     Changes to push to origin:
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
       Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -741,9 +867,9 @@ fn test_git_push_multiple() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Delete bookmark bookmark1 from 9b2e76de3920
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
       Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
+      Delete bookmark bookmark1 from 9b2e76de3920
     Dry-run requested, not pushing.
     [EOF]
     ");
@@ -752,9 +878,9 @@ fn test_git_push_multiple() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
-      Delete bookmark bookmark1 from 9b2e76de3920
-      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
       Add bookmark my-bookmark to 352fa1879f75
+      Move sideways bookmark bookmark2 from 38a204733702 to 352fa1879f75
+      Delete bookmark bookmark1 from 9b2e76de3920
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
@@ -802,8 +928,8 @@ fn test_git_push_changes() {
     ------- stderr -------
     Creating bookmark push-yqosqzytrlsw for revision yqosqzytrlsw
     Changes to push to origin:
-      Move sideways bookmark push-yostqsxwqrlt from 916414184c47 to 2723f6111cb9
       Add bookmark push-yqosqzytrlsw to 0f8164cd580b
+      Move sideways bookmark push-yostqsxwqrlt from 916414184c47 to 2723f6111cb9
     [EOF]
     ");
     // specifying the same change twice doesn't break things
@@ -908,6 +1034,22 @@ fn test_git_push_changes() {
     [EOF]
     [exit status: 1]
     ");
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        r#"--config=templates.git_push_bookmark='"push.." ++ change_id.short()'"#,
+        "--change=@",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Invalid bookmark name generated: "push..yostqsxwqrlt"
+    Caused by:
+    1: A reference must be a valid tag name as well
+    2: A ref must not contain '..' as it may be mistaken for a range
+    Hint: Check the `templates.git_push_bookmark` config
+    [EOF]
+    [exit status: 1]
+    "#);
 }
 
 #[test]
@@ -1018,6 +1160,9 @@ fn test_git_push_changes_with_name() {
     let output = work_dir.run_jj(["git", "push", "--named=b2=@", "-b=b2"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: The following bookmarks on ancestors of the pushed commits are out of date on origin:
+      b1
+    Hint: Push them together with `--bookmark`, or use `--also-stale-ancestors` to include them.
     Changes to push to origin:
       Add bookmark b2 to 95ba7bdacb38
     [EOF]
@@ -1929,6 +2074,136 @@ fn test_git_push_tracked_vs_all() {
     ");
 }
 
+#[test]
+fn test_git_push_tracked_untracked_and_deleted() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["new", "bookmark1", "-mmoved bookmark1"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark1", "-r@"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark2"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "bookmark3"])
+        .success();
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: vruxwmqv d7607a25 (empty) moved bookmark1
+      @origin (behind by 1 commits): qpvuntsm 9b2e76de (empty) description 1
+    bookmark2 (deleted)
+      @origin: zsuskuln 38a20473 (empty) description 2
+    bookmark3: vruxwmqv d7607a25 (empty) moved bookmark1
+    [EOF]
+    ");
+
+    // --tracked pushes the moved bookmark, but not the deleted one unless
+    // --deleted is also given
+    let output = work_dir.run_jj(["git", "push", "--tracked", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Refusing to push deleted bookmark bookmark2
+    Hint: Push deleted bookmarks with --deleted or forget the bookmark to suppress this warning.
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to d7607a2503de
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--tracked", "--deleted", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to d7607a2503de
+      Delete bookmark bookmark2 from 38a204733702
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // --deleted alone pushes only the deletion
+    let output = work_dir.run_jj(["git", "push", "--deleted", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark2 from 38a204733702
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // --all also pushes the untracked bookmark, and the changes are grouped by
+    // action
+    let output = work_dir.run_jj(["git", "push", "--all", "--deleted", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add bookmark bookmark3 to d7607a2503de
+      Move forward bookmark bookmark1 from 9b2e76de3920 to d7607a2503de
+      Delete bookmark bookmark2 from 38a204733702
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    // With multiple remotes, --all requires the remote to be specified
+    test_env.run_jj_in(".", ["git", "init", "mirror"]).success();
+    let mirror_git_repo_path = git_repo_dir_for_jj_repo(&test_env.work_dir("mirror"));
+    work_dir
+        .run_jj([
+            "git",
+            "remote",
+            "add",
+            "mirror",
+            mirror_git_repo_path.to_str().unwrap(),
+        ])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--all", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to push all bookmarks without an explicit remote when multiple remotes exist
+    Hint: Use --remote to specify the remote to push to, or set `git.push`.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["git", "push", "--all", "--remote=mirror", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to mirror:
+      Add bookmark bookmark1 to d7607a2503de
+      Add bookmark bookmark3 to d7607a2503de
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--all",
+        "--config=git.push=origin",
+        "--dry-run",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Refusing to push deleted bookmark bookmark2
+    Hint: Push deleted bookmarks with --deleted or forget the bookmark to suppress this warning.
+    Changes to push to origin:
+      Add bookmark bookmark3 to d7607a2503de
+      Move forward bookmark bookmark1 from 9b2e76de3920 to d7607a2503de
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    // --tracked still falls back to the default remote
+    let output = work_dir.run_jj(["git", "push", "--tracked", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Refusing to push deleted bookmark bookmark2
+    Hint: Push deleted bookmarks with --deleted or forget the bookmark to suppress this warning.
+    Changes to push to origin:
+      Move forward bookmark bookmark1 from 9b2e76de3920 to d7607a2503de
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_moved_forward_untracked() {
     let test_env = TestEnvironment::default();
@@ -2174,6 +2449,166 @@ fn test_git_push_sign_on_push() {
     ");
 }
 
+#[test]
+fn test_git_push_require_signed() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    test_env.add_config(
+        r#"
+    signing.backend = "test"
+    signing.key = "impeccable"
+    git.push-require-signed = true
+    "#,
+    );
+    work_dir
+        .run_jj(["new", "bookmark2", "-m", "unsigned commit 1"])
+        .success();
+    work_dir
+        .run_jj(["new", "-m", "unsigned commit 2"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+
+    // Unsigned commits are rejected
+    let output = work_dir.run_jj(["git", "push", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Won't push 2 commit(s) without a good signature
+    Hint: Rejected commit (unsigned): yostqsxw 18d47a82 bookmark2* | (empty) unsigned commit 2
+    Hint: Rejected commit (unsigned): vruxwmqv b1dcc369 (empty) unsigned commit 1
+    Hint: Configured git.push-require-signed: true
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Signed commits can be pushed
+    work_dir.run_jj(["sign", "-r", "@-"]).success();
+    let output = work_dir.run_jj(["git", "push", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Won't push 1 commit(s) without a good signature
+    Hint: Rejected commit (unsigned): yostqsxw ea187cc4 bookmark2* | (empty) unsigned commit 2
+    Hint: Configured git.push-require-signed: true
+    [EOF]
+    [exit status: 1]
+    ");
+    work_dir.run_jj(["sign", "-r", "@"]).success();
+    let output = work_dir.run_jj(["git", "push"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 38a204733702 to 5ed4e6694437
+    [EOF]
+    ");
+
+    // Commits signed on push are accepted
+    work_dir.run_jj(["new", "-m", "signed on push"]).success();
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-r@"])
+        .success();
+    test_env.add_config("git.sign-on-push = true");
+    let output = work_dir.run_jj(["git", "push"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Updated signatures of 1 commits
+    Changes to push to origin:
+      Move forward bookmark bookmark2 from 5ed4e6694437 to 4845c0c65e0a
+    Working copy  (@) now at: nkmrtpmo 4845c0c6 bookmark2 | (empty) signed on push
+    Parent commit (@-)      : yostqsxw 5ed4e669 (empty) unsigned commit 2
+    [EOF]
+    ");
+
+    // Commits with a bad signature are rejected, and aren't re-signed on push
+    let git_repo = git::open(git_repo_dir_for_jj_repo(&work_dir));
+    let commit_id = work_dir
+        .run_jj(["log", "-Tcommit_id", "--no-graph", "-r@"])
+        .success()
+        .stdout
+        .into_raw();
+    let commit_id = gix::ObjectId::from_hex(commit_id.as_bytes()).unwrap();
+    git::write_commit_with_message(&git_repo, "refs/heads/tampered", commit_id, "tampered\n");
+    work_dir.run_jj(["git", "import"]).success();
+    let output = work_dir.run_jj(["git", "push", "--dry-run", "-b", "tampered", "--allow-new"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Won't push 1 commit(s) without a good signature
+    Hint: Rejected commit (bad signature): nkmrtpmo?? 5ad47521 tampered | (empty) tampered
+    Hint: Configured git.push-require-signed: true
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_git_push_stale_ancestor_bookmarks() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    // Stack bookmark3 on top of bookmark1, and push it
+    work_dir
+        .run_jj(["new", "bookmark1", "-m", "description 3"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "bookmark3"])
+        .success();
+    work_dir
+        .run_jj(["git", "push", "--allow-new", "-b", "bookmark3"])
+        .success();
+
+    // Amend the lower bookmark, which also rewrites the upper one
+    work_dir
+        .run_jj(["describe", "bookmark1", "-m", "description 1 amended"])
+        .success();
+
+    let output = work_dir.run_jj(["git", "push", "-b", "bookmark3", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: The following bookmarks on ancestors of the pushed commits are out of date on origin:
+      bookmark1
+    Hint: Push them together with `--bookmark`, or use `--also-stale-ancestors` to include them.
+    Changes to push to origin:
+      Move sideways bookmark bookmark3 from 395ce5050ab3 to d76883fd845c
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["git", "push", "-b", "bookmark3", "--also-stale-ancestors"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move sideways bookmark bookmark3 from 395ce5050ab3 to d76883fd845c
+      Move sideways bookmark bookmark1 from 9b2e76de3920 to 16fb5486a7e1
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 16fb5486 (empty) description 1 amended
+      @origin: qpvuntsm 16fb5486 (empty) description 1 amended
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin: zsuskuln 38a20473 (empty) description 2
+    bookmark3: vruxwmqv d76883fd (empty) description 3
+      @origin: vruxwmqv d76883fd (empty) description 3
+    [EOF]
+    ");
+
+    // Bookmarks on unrelated commits aren't reported
+    work_dir
+        .run_jj(["describe", "bookmark2", "-m", "description 2 amended"])
+        .success();
+    work_dir
+        .run_jj(["describe", "bookmark3", "-m", "description 3 amended"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "-b", "bookmark3", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move sideways bookmark bookmark3 from d76883fd845c to 8ef7f0395672
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_push_rejected_by_remote() {
     let test_env = TestEnvironment::default();
@@ -2244,6 +2679,103 @@ fn test_git_push_rejected_by_remote() {
     });
 }
 
+#[test]
+fn test_git_push_to_url() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    let fork_path = test_env.env_root().join("fork");
+    git::init_bare(&fork_path);
+    let fork_url = fork_path.to_str().unwrap();
+
+    let output = work_dir.run_jj(["git", "push", "--to-url", fork_url, "-bbookmark1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to $TEST_ENV/fork:
+      Add bookmark bookmark1 to 9b2e76de3920
+    [EOF]
+    ");
+    let fork_repo = git::open(&fork_path);
+    assert!(fork_repo.find_reference("refs/heads/bookmark1").is_ok());
+
+    // No remote-tracking bookmarks are recorded
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 9b2e76de (empty) description 1
+      @origin: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @origin: zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+
+    // Pushed bookmarks can be recorded under a remote name
+    let output = work_dir.run_jj([
+        "git",
+        "push",
+        "--to-url",
+        fork_url,
+        "--track-as=fork",
+        "-bbookmark2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to fork:
+      Add bookmark bookmark2 to 38a204733702
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&work_dir), @r"
+    bookmark1: qpvuntsm 9b2e76de (empty) description 1
+      @origin: qpvuntsm 9b2e76de (empty) description 1
+    bookmark2: zsuskuln 38a20473 (empty) description 2
+      @fork: zsuskuln 38a20473 (empty) description 2
+      @origin: zsuskuln 38a20473 (empty) description 2
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "remote", "list"]);
+    insta::assert_snapshot!(output, @r"
+    origin $TEST_ENV/origin/.jj/repo/store/git
+    [EOF]
+    ");
+
+    // Without --track-as, bookmarks are expected to not exist on the remote
+    work_dir
+        .run_jj(["bookmark", "set", "bookmark2", "-rbookmark1", "-B"])
+        .success();
+    let output = work_dir.run_jj(["git", "push", "--to-url", fork_url, "-bbookmark2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to $TEST_ENV/fork:
+      Add bookmark bookmark2 to 9b2e76de3920
+    Error: Failed to push some bookmarks
+    Hint: The following references unexpectedly moved on the remote:
+      refs/heads/bookmark2 (reason: stale info)
+    Hint: Try fetching from the remote, then make the bookmark point to where you want it to be, and push again.
+    Hint: No remote was configured for $TEST_ENV/fork.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Bookmarks to push must be specified since there are no remote bookmarks
+    // to compare against
+    let output = work_dir.run_jj(["git", "push", "--to-url", fork_url]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No bookmarks to push to the URL were specified
+    Hint: Use --bookmark, --revisions, --change, --named, or --all to select the bookmarks to push.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Configured remotes can't be used as the recorded name
+    let output = work_dir.run_jj(["git", "push", "--to-url", fork_url, "--track-as=origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Remote origin is configured and can't be used with --track-as
+    Hint: Use --remote to push to a configured remote.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[must_use]
 fn get_bookmark_output(work_dir: &TestWorkDir) -> CommandOutput {
     // --quiet to suppress deleted bookmarks hint
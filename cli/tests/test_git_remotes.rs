@@ -332,6 +332,141 @@ fn test_git_remote_rename() {
     "#);
 }
 
+#[test]
+fn test_git_remote_rename_with_remote_bookmarks() {
+    let test_env = TestEnvironment::default();
+    let git_repo = git::init(test_env.env_root().join("remote"));
+    let main = git::add_commit(&git_repo, "refs/heads/main", "file", b"main", "main", &[]);
+    let feature1 = git::add_commit(
+        &git_repo,
+        "refs/heads/feature",
+        "file",
+        b"feature 1",
+        "feature 1",
+        &[main.commit_id],
+    );
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../remote"])
+        .success();
+    work_dir.run_jj(["git", "fetch"]).success();
+    work_dir
+        .run_jj(["bookmark", "track", "main@origin", "feature@origin"])
+        .success();
+
+    // Make feature@origin conflicted by fetching concurrently
+    let base_op_id = work_dir.current_operation_id();
+    git::add_commit(
+        &git_repo,
+        "refs/heads/feature",
+        "file",
+        b"feature 2",
+        "feature 2",
+        &[feature1.commit_id],
+    );
+    work_dir.run_jj(["git", "fetch"]).success();
+    git_repo
+        .reference(
+            "refs/heads/feature",
+            feature1.commit_id,
+            gix::refs::transaction::PreviousValue::Any,
+            "",
+        )
+        .unwrap();
+    git::add_commit(
+        &git_repo,
+        "refs/heads/feature",
+        "file",
+        b"feature 3",
+        "feature 3",
+        &[feature1.commit_id],
+    );
+    work_dir
+        .run_jj(["git", "fetch", "--at-op", &base_op_id])
+        .success();
+
+    let template = r#"
+    concat(
+      name ++ if(remote, "@" ++ remote),
+      if(conflict, " (conflicted)"),
+      if(tracked, " (tracked)"),
+      "\n",
+    )
+    "#;
+    let list_bookmarks = || {
+        work_dir.run_jj([
+            "bookmark",
+            "list",
+            "--all-remotes",
+            "--quiet",
+            "-T",
+            template,
+        ])
+    };
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    feature (conflicted)
+    feature@origin (conflicted) (tracked)
+    main
+    main@origin (tracked)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["git", "remote", "rename", "origin", "upstream"]);
+    insta::assert_snapshot!(output, @"");
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    feature (conflicted)
+    feature@upstream (conflicted) (tracked)
+    main
+    main@upstream (tracked)
+    [EOF]
+    ");
+
+    // Undoing the rename restores the remote bookmarks. The Git config isn't
+    // recorded in the operation log, so the mismatch with the Git remote is
+    // reported.
+    let output = work_dir.run_jj(["undo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: 9c4a8a73ec06 (2001-02-03 08:05:13) reconcile divergent operations
+    Warning: Restored remote bookmarks of Git remotes that don't exist:
+      origin
+    Hint: The Git remote upstream may have been renamed from origin. Run `jj git remote rename upstream origin` to rename it back.
+    [EOF]
+    ");
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    feature (conflicted)
+    feature@origin (conflicted) (tracked)
+    main
+    main@origin (tracked)
+    [EOF]
+    ");
+
+    // Following the hint makes the Git remote consistent with the bookmarks.
+    let output = work_dir.run_jj(["git", "remote", "rename", "upstream", "origin"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["git", "remote", "list"]);
+    insta::assert_snapshot!(output, @r"
+    origin $TEST_ENV/remote
+    [EOF]
+    ");
+    insta::assert_snapshot!(list_bookmarks(), @r"
+    feature (conflicted)
+    feature@origin (conflicted) (tracked)
+    main
+    main@origin (tracked)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["git", "fetch"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: feature@origin [updated] tracked
+    Abandoned 1 commits that are no longer reachable.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_remote_named_git() {
     let test_env = TestEnvironment::default();
@@ -140,6 +140,54 @@ fn test_no_subcommand() {
     ");
 }
 
+#[test]
+fn test_default_command_list() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    // Arguments in the list are passed to the command
+    test_env.add_config(r#"ui.default-command = ["log", "-r", "@", "-T", "description"]"#);
+    let output = work_dir.run_jj([""; 0]);
+    insta::assert_snapshot!(output, @r"
+    @  first
+    │
+    ~
+    [EOF]
+    ");
+
+    // Global arguments on the command line are kept
+    let output = work_dir.run_jj(["--color=never"]);
+    insta::assert_snapshot!(output, @r"
+    @  first
+    │
+    ~
+    [EOF]
+    ");
+
+    // A list without a command isn't expanded again
+    test_env.add_config(r#"ui.default-command = ["--no-pager"]"#);
+    let output = work_dir.run_jj([""; 0]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: ui.default-command must start with a command name
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    test_env.add_config("ui.default-command = []");
+    let output = work_dir.run_jj([""; 0]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: ui.default-command must start with a command name
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_ignore_working_copy() {
     let test_env = TestEnvironment::default();
@@ -169,6 +217,112 @@ fn test_ignore_working_copy() {
     ");
 }
 
+#[test]
+fn test_snapshot_auto_queries_skip() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"snapshot.auto = "queries-skip""#);
+    let work_dir = test_env.work_dir("repo");
+    let get_op_log = || work_dir.run_jj(["op", "log", "--no-graph", "-T", "description ++ '\n'"]);
+
+    // Queries which don't read the working-copy commit don't snapshot
+    work_dir.write_file("file", "initial");
+    let output = work_dir.run_jj(["log", "-T", "commit_id"]);
+    insta::assert_snapshot!(output, @r"
+    @  e8849ae12c709f2321908879bc724fdb2ab8a781
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+    work_dir.run_jj(["show", "@-"]).success();
+    work_dir.run_jj(["diff", "-r", "@-"]).success();
+    work_dir.run_jj(["bookmark", "list"]).success();
+    insta::assert_snapshot!(get_op_log(), @r"
+    add workspace 'default'
+
+    [EOF]
+    ");
+
+    // Queries which read the working-copy commit snapshot
+    let output = work_dir.run_jj(["show", "@", "--summary", "-T", "commit_id ++ '\n'"]);
+    insta::assert_snapshot!(output, @r"
+    cf473a14e9790d3cbc7f12d84fcabd16147939c2
+    A file
+    [EOF]
+    ");
+    work_dir.write_file("file", "modified");
+    let output = work_dir.run_jj(["diff", "--from", "@-", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    A file
+    [EOF]
+    ");
+    work_dir.write_file("file", "modified again");
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..59283352c8
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,1 @@
+    +modified again
+    \ No newline at end of file
+    [EOF]
+    ");
+    work_dir.write_file("file", "modified once more");
+    let output = work_dir.run_jj(["log", "-r", "@", "--summary", "-T", "commit_id"]);
+    insta::assert_snapshot!(output, @r"
+    @  a3739cebdbf15e58fca6babd67c0f0369cec83c1
+    │  A file
+    ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_op_log(), @r"
+    snapshot working copy
+    snapshot working copy
+    snapshot working copy
+    snapshot working copy
+    add workspace 'default'
+
+    [EOF]
+    ");
+
+    // Mutating commands snapshot as usual
+    work_dir.write_file("file", "final");
+    work_dir.run_jj(["describe", "-m", "final"]).success();
+    let output = work_dir.run_jj(["file", "show", "-r", "@", "file"]);
+    insta::assert_snapshot!(output, @"final[EOF]");
+}
+
+#[test]
+fn test_snapshot_auto_never() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"snapshot.auto = "never""#);
+    let work_dir = test_env.work_dir("repo");
+
+    // The working copy isn't snapshotted even if the working-copy commit is
+    // diffed
+    work_dir.write_file("file", "initial");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @"");
+    let output = work_dir.run_jj(["op", "log", "--no-graph", "-T", "description ++ '\n'"]);
+    insta::assert_snapshot!(output, @r"
+    add workspace 'default'
+
+    [EOF]
+    ");
+
+    // Commands which mutate the working copy can't be run
+    let output = work_dir.run_jj(["file", "untrack", "file"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: This command must be able to update the working copy.
+    Hint: Don't set `snapshot.auto = "never"`.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
 #[test]
 fn test_repo_arg_with_git_init() {
     let test_env = TestEnvironment::default();
@@ -1059,6 +1213,8 @@ fn test_help() {
           --ignore-immutable             Allow rewriting immutable commits
           --at-operation <AT_OPERATION>  Operation to load the repo at [aliases: --at-op]
           --debug                        Enable debug logging
+          --no-command-defaults          Ignore the default arguments set by the `command-defaults`
+                                         config
           --color <WHEN>                 When to colorize output [possible values: always, never, debug,
                                          auto]
           --quiet                        Silence non-primary command output
@@ -0,0 +1,148 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_identity_use() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+        [identities.work]
+        name = "Work Name"
+        email = "work@example.com"
+        signing-key = "work-key"
+
+        [identities.oss]
+        email = "oss@example.org"
+        "#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // No profile matches the default test user
+    let output = work_dir.run_jj(["identity", "list"]);
+    insta::assert_snapshot!(output, @r"
+    oss: <oss@example.org>
+    work: Work Name <work@example.com>
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["identity", "use", "work", "--repo"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Switched to identity profile work in $TEST_ENV/repo/.jj/repo/config.toml
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file(".jj/repo/config.toml"), @r#"
+    #:schema https://jj-vcs.github.io/jj/latest/config-schema.json
+
+    [user]
+    name = "Work Name"
+    email = "work@example.com"
+
+    [signing]
+    key = "work-key"
+    "#);
+    // The test environment sets user.name and user.email by env vars
+    let run_jj_without_user_env = |args: &[&str]| {
+        work_dir.run_jj_with(|cmd| cmd.args(args).env_remove("JJ_USER").env_remove("JJ_EMAIL"))
+    };
+    let output = run_jj_without_user_env(&["identity", "list"]);
+    insta::assert_snapshot!(output, @r"
+    oss: <oss@example.org>
+    work: Work Name <work@example.com> (active)
+    [EOF]
+    ");
+    run_jj_without_user_env(&["new"]).success();
+    let output = work_dir.run_jj(["log", "-r@", "-T", "author ++ '\n'", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    Work Name <work@example.com>
+    [EOF]
+    ");
+
+    // Profiles may define only some of the settings
+    work_dir
+        .run_jj(["identity", "use", "oss", "--repo"])
+        .success();
+    let output = run_jj_without_user_env(&["identity", "list"]);
+    insta::assert_snapshot!(output, @r"
+    oss: <oss@example.org> (active)
+    work: Work Name <work@example.com>
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["identity", "use", "unknown", "--repo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such identity profile: unknown
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_identity_warn_unknown() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+        identities.work.email = "work@example.com"
+        identities.oss.email = "oss@example.org"
+        "#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // The warning is opt-in
+    let output = work_dir.run_jj(["describe", "-m", "first"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: qpvuntsm 68a50538 (empty) first
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+
+    test_env.add_config("ui.warn-unknown-identity = true");
+    let output = work_dir.run_jj(["describe", "-m", "second"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: The configured email <test.user@example.com> doesn't match any identity profile
+    Hint: Use `jj identity use <profile>` to switch to one of: oss, work
+    Working copy  (@) now at: qpvuntsm 579039bc (empty) second
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["commit", "-m", "third"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: The configured email <test.user@example.com> doesn't match any identity profile
+    Hint: Use `jj identity use <profile>` to switch to one of: oss, work
+    Working copy  (@) now at: zsuskuln 5f2add5b (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 815ae39f (empty) third
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "commit",
+        "-m",
+        "fourth",
+        "--config=user.email=work@example.com",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: mzvwutvl 35c978d5 (empty) (no description set)
+    Parent commit (@-)      : zsuskuln 938eaed1 (empty) fourth
+    [EOF]
+    ");
+}
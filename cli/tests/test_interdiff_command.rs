@@ -134,6 +134,89 @@ fn test_interdiff_paths() {
        1    1: barbaz
     [EOF]
     ");
+
+    // Stats only include the matched paths
+    let output = work_dir.run_jj([
+        "interdiff",
+        "--from",
+        "left",
+        "--to",
+        "right",
+        "--stat",
+        "file1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    file1 | 2 +-
+    1 file changed, 1 insertion(+), 1 deletion(-)
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "interdiff",
+        "--from",
+        "left",
+        "--to",
+        "right",
+        "-s",
+        "file2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    M file2
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_interdiff_exit_code() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "left"])
+        .success();
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("file1", "foo\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "right"])
+        .success();
+
+    let output = work_dir.run_jj([
+        "interdiff",
+        "--exit-code",
+        "--from",
+        "left",
+        "--to",
+        "right",
+    ]);
+    insta::assert_snapshot!(output, @"");
+
+    work_dir.write_file("file1", "bar\n");
+    let output = work_dir.run_jj([
+        "interdiff",
+        "--exit-code",
+        "--from",
+        "left",
+        "--to",
+        "right",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file1:
+       1    1: foobar
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj([
+        "interdiff",
+        "--exit-code",
+        "--quiet",
+        "--from",
+        "left",
+        "--to",
+        "right",
+    ]);
+    insta::assert_snapshot!(output, @"[exit status: 1]");
 }
 
 #[test]
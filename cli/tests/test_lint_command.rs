@@ -0,0 +1,160 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_lint_no_checks() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "WIP"]).success();
+
+    // All checks are disabled by default
+    let output = work_dir.run_jj(["lint"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Checked 2 commits, no problems found
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_lint_description_checks() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(
+        r#"
+        [lint]
+        forbid-empty-description = true
+        forbidden-patterns = ["WIP", "fixup!"]
+        max-subject-length = 20
+        "#,
+    );
+    work_dir
+        .run_jj(["commit", "-m", "good subject\n\nA long body that is fine."])
+        .success();
+    work_dir
+        .run_jj(["commit", "-m", "a subject which is much too long"])
+        .success();
+    work_dir.run_jj(["commit", "-m", "fixup! WIP"]).success();
+
+    let output = work_dir.run_jj(["lint"]);
+    insta::assert_snapshot!(output, @r"
+    zsuskuln fc230316 (empty) (no description set)
+      Description is empty
+    kkmpptxz 3e0a0f90 (empty) fixup! WIP
+      Description contains forbidden pattern: WIP
+      Description contains forbidden pattern: fixup!
+    rlvkpnrz b2b2c75d (empty) a subject which is much too long
+      Subject is 32 characters long, more than 20
+    [EOF]
+    ------- stderr -------
+    Error: 3 of 4 commits failed the checks
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Only the selected revisions are checked
+    let output = work_dir.run_jj(["lint", "-r", "@---"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Checked 1 commits, no problems found
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_lint_merge_commits() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config("lint.forbid-merge-commits = true");
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "b"]).success();
+    work_dir
+        .run_jj(["new", "description(a)", "description(b)", "-m", "merge"])
+        .success();
+
+    let output = work_dir.run_jj(["lint"]);
+    insta::assert_snapshot!(output, @r"
+    zsuskuln 1c5fdc74 (empty) merge
+      Merge commits are not allowed
+    [EOF]
+    ------- stderr -------
+    Error: 1 of 3 commits failed the checks
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_lint_require_signed() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+        [signing]
+        behavior = "drop"
+        backend = "test"
+        key = "impeccable"
+
+        [lint]
+        require-signed = true
+        "#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "signed"]).success();
+    work_dir.run_jj(["sign", "-r", "@"]).success();
+    work_dir.run_jj(["new", "-m", "unsigned"]).success();
+
+    let output = work_dir.run_jj(["lint"]);
+    insta::assert_snapshot!(output, @r"
+    zsuskuln 1fefc107 (empty) unsigned
+      Commit doesn't have a good signature (unsigned)
+    [EOF]
+    ------- stderr -------
+    Error: 1 of 2 commits failed the checks
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_lint_max_file_size() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"lint.max-file-size = "10B""#);
+    work_dir.write_file("small", "small");
+    work_dir.write_file("large", "more than ten bytes");
+    work_dir.run_jj(["commit", "-m", "add files"]).success();
+    // Unchanged files aren't checked again
+    work_dir.write_file("small", "smaller");
+    work_dir
+        .run_jj(["describe", "-m", "modify small"])
+        .success();
+
+    let output = work_dir.run_jj(["lint"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsm 82fd8117 add files
+      File large is larger than 10.0B
+    [EOF]
+    ------- stderr -------
+    Error: 1 of 2 commits failed the checks
+    [EOF]
+    [exit status: 1]
+    ");
+}
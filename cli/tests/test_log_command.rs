@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools as _;
+use regex::Regex;
+
+use testutils::git;
+
 use crate::common::TestEnvironment;
+use crate::common::create_commit;
 use crate::common::to_toml_value;
 
 #[test]
@@ -348,6 +354,52 @@ fn test_log_with_or_without_diff() {
     ");
 }
 
+#[test]
+fn test_log_merge_diff_parents() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "base", &[]);
+    create_commit(&work_dir, "left", &["base"]);
+    create_commit(&work_dir, "right", &["base"]);
+    create_commit(&work_dir, "merge", &["left", "right"]);
+
+    let output = work_dir.run_jj(["log", "-Tdescription", "-rbase::", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    @    merge
+    ├─╮  A merge
+    │ ○  right
+    │ │  A right
+    ○ │  left
+    ├─╯  A left
+    ○  base
+    │  A base
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-Tdescription",
+        "-rbase::",
+        "--summary",
+        "--diff-parent=2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @    merge
+    ├─╮  A left
+    │ │  A merge
+    │ ○  right
+    │ │  A right
+    ○ │  left
+    ├─╯  A left
+    ○  base
+    │  A base
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_null_terminate_multiline_descriptions() {
     let test_env = TestEnvironment::default();
@@ -722,7 +774,9 @@ fn test_log_prefix_highlight_counts_hidden_commits() {
             .run_jj(["duplicate", "description(extra)"])
             .success();
     }
-    work_dir.run_jj(["abandon", "description(extra)"]).success();
+    work_dir
+        .run_jj(["abandon", "--no-limit", "description(extra)"])
+        .success();
 
     // The unique prefixes became longer.
     insta::assert_snapshot!(work_dir.run_jj(["log", "-T", prefix_format]), @r"
@@ -746,6 +800,74 @@ fn test_log_prefix_highlight_counts_hidden_commits() {
     ");
 }
 
+#[test]
+fn test_log_hidden() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "lost\n");
+    work_dir.run_jj(["describe", "-m", "lost work"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "draft"]).success();
+    work_dir
+        .run_jj(["abandon", "description('lost work')"])
+        .success();
+    work_dir.run_jj(["describe", "-m", "rewritten"]).success();
+
+    // Hidden commits aren't visible by default
+    let output = work_dir.run_jj(["log", "-r", "description('lost work')"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Abandoned commit can be found by description
+    let output = work_dir.run_jj(["log", "--hidden", "-r", "description('lost work')"]);
+    insta::assert_snapshot!(output, @r"
+    ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 77514018
+    │  lost work
+    ~
+    [EOF]
+    ");
+
+    // Rewritten commit is annotated with its visible successor
+    let output = work_dir.run_jj(["log", "--hidden", "-r", "description(draft)"]);
+    insta::assert_snapshot!(output, @r"
+    ○  kkmpptxz hidden test.user@example.com 2001-02-03 08:05:09 874fe50d
+    │  (empty) draft
+    ~  (rewritten as a3145e443889)
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "log",
+        "--hidden",
+        "--no-graph",
+        "-r",
+        "description(draft)",
+        "-T",
+        "commit_id.short() ++ '\n'",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    874fe50df8bd
+    (rewritten as a3145e443889)
+    [EOF]
+    ");
+
+    // Hidden commits are labeled
+    let output = work_dir.run_jj([
+        "log",
+        "--hidden",
+        "--color=debug",
+        "-r",
+        "description(draft)",
+        "-T",
+        "description",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    <<log commit node mutable::○>>  [38;5;8m<<hidden log commit description::draft>>[39m
+    │  [38;5;8m<<hidden rewritten::(rewritten as a3145e443889)>>[39m
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_author_format() {
     let test_env = TestEnvironment::default();
@@ -809,6 +931,67 @@ fn test_log_divergence() {
     ");
 }
 
+#[test]
+fn test_log_index_only_template() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "first"]).success();
+    work_dir.run_jj(["commit", "-m", "second"]).success();
+
+    let render = |template: &str, extra_args: &[&str]| {
+        let mut args = vec![
+            "log",
+            "--no-graph",
+            "--debug",
+            "-r",
+            "all()",
+            "-T",
+            template,
+        ];
+        args.extend_from_slice(extra_args);
+        let output = work_dir.run_jj(args).success();
+        let ansi_regex = Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        let stats = output
+            .stderr
+            .raw()
+            .lines()
+            .find_map(|line| {
+                let line = ansi_regex.replace_all(line, "");
+                let (_, stats) = line.split_once("rendered log without graph ")?;
+                Some(stats.to_owned())
+            })
+            .unwrap();
+        (output.stdout.into_raw(), stats)
+    };
+
+    // Templates using only ids are rendered from the index
+    let fast_template = r#"commit_id.short() ++ " " ++ change_id.shortest(4) ++ "\n""#;
+    let (fast_output, stats) = render(fast_template, &[]);
+    insta::assert_snapshot!(fast_output, @r"
+    848a549309fd kkmp
+    9439bf06c61e rlvk
+    68a505386f93 qpvu
+    000000000000 zzzz
+    ");
+    insta::assert_snapshot!(stats, @"index_only=true num_loaded_commits=0");
+
+    // Other templates load commit objects, and render the same ids
+    let slow_template =
+        r#"commit_id.short() ++ " " ++ change_id.shortest(4) ++ description.substr(0, 0) ++ "\n""#;
+    let (slow_output, stats) = render(slow_template, &[]);
+    assert_eq!(fast_output, slow_output);
+    insta::assert_snapshot!(stats, @"index_only=false num_loaded_commits=4");
+
+    let (fast_output, _) = render(fast_template, &["--reversed", "--limit=2"]);
+    let (slow_output, _) = render(slow_template, &["--reversed", "--limit=2"]);
+    assert_eq!(fast_output, slow_output);
+    insta::assert_snapshot!(fast_output, @r"
+    9439bf06c61e rlvk
+    848a549309fd kkmp
+    ");
+}
+
 #[test]
 fn test_log_reversed() {
     let test_env = TestEnvironment::default();
@@ -834,6 +1017,182 @@ fn test_log_reversed() {
     ");
 }
 
+#[test]
+fn test_log_revisions_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+    work_dir.run_jj(["new", "-m", "third"]).success();
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        "@- | @--",
+        "-T",
+        r#"change_id ++ "\n""#,
+    ]);
+    let change_ids = output
+        .stdout
+        .raw()
+        .lines()
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    assert_eq!(change_ids.len(), 2);
+
+    let lines = change_ids.iter().join("\n");
+    std::fs::write(work_dir.root().join("revisions.txt"), lines + "\n\n").unwrap();
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--revisions-file",
+        "revisions.txt",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  second
+    ○  first
+    │
+    ~
+    [EOF]
+    ");
+
+    // The revisions are added to the ones given by --revisions
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "-r",
+        "@",
+        "--revisions-file",
+        "revisions.txt",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  third
+    ○  second
+    ○  first
+    │
+    ~
+    [EOF]
+    ");
+
+    // Read from stdin
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["log", "-T", "description", "--revisions-file", "-"])
+            .write_stdin("@\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    @  third
+    │
+    ~
+    [EOF]
+    ");
+
+    // An empty file selects no revisions
+    std::fs::write(work_dir.root().join("empty.txt"), "").unwrap();
+    let output = work_dir.run_jj(["log", "-T", "description", "--revisions-file", "empty.txt"]);
+    insta::assert_snapshot!(output, @"");
+
+    let output = work_dir.run_jj(["log", "--revisions-file", "missing.txt"]);
+    assert!(
+        output
+            .stderr
+            .raw()
+            .starts_with("Error: Failed to read missing.txt\n")
+    );
+}
+
+#[test]
+fn test_log_revisions_file_many() {
+    let test_env = TestEnvironment::default();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::init(work_dir.root());
+    let empty_tree_id = gix::ObjectId::empty_tree(git_repo.object_hash());
+    let mut commit_ids = vec![];
+    for i in 0..10_000 {
+        let parents = commit_ids.last().into_iter().copied().collect_vec();
+        let message = format!("commit {i}");
+        let commit_id = git::write_commit(
+            &git_repo,
+            "refs/heads/main",
+            empty_tree_id,
+            &message,
+            &parents,
+        );
+        commit_ids.push(commit_id);
+    }
+    work_dir.run_jj(["git", "init", "--git-repo=."]).success();
+
+    // Thousands of revisions are too many to pass as arguments
+    let lines = commit_ids.iter().map(|id| id.to_string()).join("\n");
+    std::fs::write(work_dir.root().join("revisions.txt"), lines).unwrap();
+    let output = work_dir
+        .run_jj([
+            "log",
+            "--no-graph",
+            "-T",
+            r#"description.first_line() ++ "\n""#,
+            "--revisions-file",
+            "revisions.txt",
+        ])
+        .success();
+    let descriptions = output.stdout.raw().lines().collect_vec();
+    assert_eq!(descriptions.len(), 10_000);
+    assert_eq!(descriptions[0], "commit 9999");
+    assert_eq!(descriptions[9_999], "commit 0");
+}
+
+#[test]
+fn test_log_template_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    std::fs::write(
+        work_dir.root().join("template.txt"),
+        "separate(\" \",\n  \"<\",\n  description.first_line(),\n  \">\",\n)\n",
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["log", "-r", "@", "--template-file", "template.txt"]);
+    insta::assert_snapshot!(output, @r"
+    @  < first >
+    │
+    ~
+    [EOF]
+    ");
+
+    // Read from stdin
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["log", "-r", "@", "--no-graph", "--template-file", "-"])
+            .write_stdin("description")
+    });
+    insta::assert_snapshot!(output, @r"
+    first
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--template-file",
+        "template.txt",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--template <TEMPLATE>' cannot be used with '--template-file <PATH>'
+
+    Usage: jj log --template <TEMPLATE> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_log_filtered_by_path() {
     let test_env = TestEnvironment::default();
@@ -930,6 +1289,82 @@ fn test_log_filtered_by_path() {
     ");
 }
 
+#[test]
+fn test_log_follow_renames() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("oldname", "1\n");
+    work_dir.run_jj(["commit", "-m", "add"]).success();
+    work_dir.write_file("other", "other\n");
+    work_dir.run_jj(["commit", "-m", "unrelated"]).success();
+    work_dir.write_file("oldname", "1\n2\n");
+    work_dir
+        .run_jj(["commit", "-m", "edit before rename"])
+        .success();
+    work_dir.remove_file("oldname");
+    work_dir.write_file("newname", "1\n2\n");
+    work_dir.run_jj(["commit", "-m", "rename"]).success();
+    work_dir.write_file("newname", "1\n2\n3\n");
+    work_dir
+        .run_jj(["commit", "-m", "edit after rename"])
+        .success();
+
+    // Without --follow, history stops at the rename
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "newname"]);
+    insta::assert_snapshot!(output, @r"
+    ○  edit after rename
+    │  M newname
+    ○  rename
+    │  R {oldname => newname}
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "--follow", "newname"]);
+    insta::assert_snapshot!(output, @r"
+    ○  edit after rename
+    │  M newname
+    ○  rename
+    │  R {oldname => newname}
+    ○  edit before rename
+    │  M oldname
+    ~  (elided revisions)
+    ○  add
+    │  A oldname
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--git",
+        "--no-graph",
+        "--follow",
+        "newname",
+        "-r",
+        r#"description(exact:"rename\n")"#,
+    ]);
+    insta::assert_snapshot!(output, @r"
+    rename
+    diff --git a/oldname b/newname
+    rename from oldname
+    rename to newname
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "--follow", "newname", "other"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: --follow requires a single file path
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_log_limit() {
     let test_env = TestEnvironment::default();
@@ -1152,6 +1587,72 @@ fn test_multiple_revsets() {
     ");
 }
 
+#[test]
+fn test_log_revset_labels() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "human"]).success();
+    work_dir
+        .run_jj([
+            "new",
+            "-m",
+            "bot",
+            "--config=user.email=renovate-bot@example.com",
+        ])
+        .success();
+    work_dir.run_jj(["new", "-m", "human again"]).success();
+
+    test_env.add_config(
+        r#"
+        [colors.revsets]
+        bot = 'author_email(glob:"*bot*")'
+        "#,
+    );
+    test_env.add_config(
+        r#"
+        [colors]
+        bot = "bright black"
+        "bot description" = "red"
+        "#,
+    );
+
+    let template = "description";
+    let output = work_dir.run_jj(["--color=always", "log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    [1m[38;5;2m@[0m  human again
+    ○  [38;5;1mbot[39m
+    ○  human
+    [1m[38;5;14m◆[0m
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["--color=debug", "log", "-T", template, "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    <<log commit description::human again>>
+    [38;5;1m<<bot log commit description::bot>>[39m
+    <<log commit description::human>>
+    [EOF]
+    ");
+
+    // Invalid revsets are reported
+    test_env.add_config("colors.revsets.bad = 'bad('");
+    let output = work_dir.run_jj(["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid `colors.revsets.bad`
+    Caused by:  --> 1:5
+      |
+    1 | bad(
+      |     ^---
+      |
+      = expected <strict_identifier> or <expression>
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_graph_template_color() {
     // Test that color codes from a multi-line template don't span the graph lines.
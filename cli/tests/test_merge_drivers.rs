@@ -0,0 +1,356 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+use crate::common::force_interactive;
+
+#[cfg(unix)]
+fn write_script(work_dir: &TestWorkDir, name: &str, content: &str) {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let path = work_dir.root().join(name);
+    std::fs::write(&path, content).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_merge_driver_resolves_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    // Union of the lines in the left and right sides
+    write_script(
+        &work_dir,
+        "merge-union",
+        "#!/bin/sh\ncat \"$2\" \"$3\" | awk '!seen[$0]++'\n",
+    );
+    write_script(&work_dir, "merge-fail", "#!/bin/sh\nexit 1\n");
+    work_dir
+        .run_jj([
+            "config",
+            "set",
+            "--repo",
+            r#"merge-drivers."CHANGELOG.md".program"#,
+            "./merge-union",
+        ])
+        .success();
+    work_dir
+        .run_jj([
+            "config",
+            "set",
+            "--repo",
+            r#"merge-drivers."*.txt".program"#,
+            "./merge-fail",
+        ])
+        .success();
+
+    work_dir.write_file("CHANGELOG.md", "a\n");
+    work_dir.write_file("file.txt", "a\n");
+    // No warning is printed unless a driver would run
+    let output = work_dir.run_jj(["commit", "-m", "base"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: zsuskuln 62b62069 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 0acd6419 base
+    [EOF]
+    ");
+    work_dir.write_file("CHANGELOG.md", "a\nb\n");
+    work_dir.write_file("file.txt", "a\nb\n");
+    work_dir.run_jj(["commit", "-m", "left"]).success();
+    work_dir.run_jj(["new", "description(base)"]).success();
+    work_dir.write_file("CHANGELOG.md", "a\nc\n");
+    work_dir.write_file("file.txt", "a\nc\n");
+    work_dir.run_jj(["describe", "-m", "right"]).success();
+
+    // Untrusted drivers don't run
+    let output = work_dir.run_jj(["rebase", "-r", "description(left)", "-d", "@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    Warning: Ignoring untrusted merge driver `./merge-fail` for *.txt
+    Warning: Ignoring untrusted merge driver `./merge-union` for CHANGELOG.md
+    New conflicts appeared in 1 commits:
+      zsuskuln 11569a01 (conflict) left
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new zsuskuln
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    work_dir.run_jj(["undo"]).success();
+
+    // Once trusted, the driver resolves the conflict. The failing driver falls
+    // back to the normal conflict.
+    let output = work_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args(["rebase", "-r", "description(left)", "-d", "@"])
+            .write_stdin("y\ny\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    The repo config defines merge driver `./merge-fail` for *.txt. Allow it to run? (yN): The repo config defines merge driver `./merge-union` for CHANGELOG.md. Allow it to run? (yN): Rebased 1 commits to destination
+    New conflicts appeared in 1 commits:
+      zsuskuln fe47d429 (conflict) left
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new zsuskuln
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "description(left)", "CHANGELOG.md"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    c
+    b
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["resolve", "--list", "-r", "description(left)"]);
+    insta::assert_snapshot!(output, @r"
+    file.txt    2-sided conflict
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_merge_driver_in_secondary_workspace() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj([
+            "config",
+            "set",
+            "--repo",
+            r#"merge-drivers."CHANGELOG.md".program"#,
+            "./merge-union",
+        ])
+        .success();
+    work_dir.write_file("CHANGELOG.md", "a\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("CHANGELOG.md", "a\nb\n");
+    work_dir.run_jj(["commit", "-m", "left"]).success();
+    work_dir.run_jj(["new", "description(base)"]).success();
+    work_dir.write_file("CHANGELOG.md", "a\nc\n");
+    work_dir.run_jj(["describe", "-m", "right"]).success();
+    work_dir
+        .run_jj([
+            "workspace",
+            "add",
+            "../secondary",
+            "-r",
+            "description(right)",
+        ])
+        .success();
+
+    // The driver only exists in the secondary workspace, and is resolved
+    // against its root
+    let secondary_dir = test_env.work_dir("secondary");
+    write_script(
+        &secondary_dir,
+        "merge-union",
+        "#!/bin/sh\ncat \"$2\" \"$3\" | awk '!seen[$0]++'\n",
+    );
+    let output = secondary_dir.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .args([
+                "rebase",
+                "-r",
+                "description(left)",
+                "-d",
+                "description(right)",
+            ])
+            .write_stdin("y\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    The repo config defines merge driver `./merge-union` for CHANGELOG.md. Allow it to run? (yN): Rebased 1 commits to destination
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["file", "show", "-r", "description(left)", "CHANGELOG.md"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    c
+    b
+    [EOF]
+    ");
+
+    // The driver of the default workspace is a different command, which
+    // isn't trusted
+    work_dir.run_jj(["undo"]).success();
+    let output = work_dir.run_jj([
+        "rebase",
+        "-r",
+        "description(left)",
+        "-d",
+        "description(right)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    Warning: Ignoring untrusted merge driver `./merge-union` for CHANGELOG.md
+    New conflicts appeared in 1 commits:
+      kkmpptxz 264f541c (conflict) left
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new kkmpptxz
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_merge_driver_only_runs_when_rewriting() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    // Union of the lines in the left and right sides, recording each run
+    // outside of the workspace
+    let log_path = test_env.env_root().join("driver.log");
+    let union_script =
+        "#!/bin/sh\necho run >> ../driver.log\ncat \"$2\" \"$3\" | awk '!seen[$0]++'\n";
+    write_script(&work_dir, "merge-union", union_script);
+    work_dir
+        .run_jj([
+            "config",
+            "set",
+            "--repo",
+            r#"merge-drivers."CHANGELOG.md".program"#,
+            "./merge-union",
+        ])
+        .success();
+    work_dir.write_file("CHANGELOG.md", "a\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("CHANGELOG.md", "a\nb\n");
+    work_dir.run_jj(["commit", "-m", "left"]).success();
+    work_dir.run_jj(["new", "description(base)"]).success();
+    work_dir.write_file("CHANGELOG.md", "a\nc\n");
+    work_dir.run_jj(["commit", "-m", "right"]).success();
+    work_dir
+        .run_jj_with(|cmd| force_interactive(cmd).args(["status"]).write_stdin("y\n"))
+        .success();
+
+    // Merging the parents of a new commit, or of a commit being displayed,
+    // doesn't run the driver
+    let output = work_dir.run_jj(["new", "description(left)", "description(right)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: vruxwmqv ff1415fb (conflict) (empty) (no description set)
+    Parent commit (@-)      : kkmpptxz 64da656d left
+    Parent commit (@-)      : mzvwutvl a2564eeb right
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    CHANGELOG.md    2-sided conflict
+    [EOF]
+    ");
+    work_dir.run_jj(["diff", "-r", "@"]).success();
+    assert!(!log_path.exists());
+
+    // Rebasing runs the driver
+    work_dir
+        .run_jj([
+            "rebase",
+            "-r",
+            "description(left)",
+            "-d",
+            "description(right)",
+        ])
+        .success();
+    assert!(log_path.exists());
+    let output = work_dir.run_jj(["file", "show", "-r", "description(left)", "CHANGELOG.md"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    c
+    b
+    [EOF]
+    ");
+    work_dir.run_jj(["undo"]).success();
+
+    // A modified program has to be trusted again
+    write_script(
+        &work_dir,
+        "merge-union",
+        &format!("{union_script}# modified\n"),
+    );
+    let output = work_dir.run_jj([
+        "rebase",
+        "-r",
+        "description(left)",
+        "-d",
+        "description(right)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    Rebased 1 descendant commits
+    Warning: Ignoring untrusted merge driver `./merge-union` for CHANGELOG.md
+    Working copy  (@) now at: vruxwmqv be2b1f00 (no description set)
+    Parent commit (@-)      : qpvuntsm a3dec2fc base
+    Parent commit (@-)      : mzvwutvl a2564eeb right
+    Added 0 files, modified 1 files, removed 0 files
+    Existing conflicts were resolved or abandoned from 1 commits.
+    New conflicts appeared in 1 commits:
+      kkmpptxz 78a59e06 (conflict) left
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new kkmpptxz
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_merge_driver_from_user_config_is_ignored() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(
+        r#"
+        [merge-drivers."file"]
+        program = "true"
+        "#,
+    );
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["commit", "-m", "left"]).success();
+    work_dir.run_jj(["new", "description(base)"]).success();
+    work_dir.write_file("file", "c\n");
+    let output = work_dir.run_jj(["rebase", "-r", "description(left)", "-d", "@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits to destination
+    New conflicts appeared in 1 commits:
+      rlvkpnrz af17f323 (conflict) left
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new rlvkpnrz
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+}
@@ -73,6 +73,44 @@ fn test_new() {
     ");
 }
 
+#[test]
+fn test_new_message_paragraphs() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir
+        .run_jj(["new", "root()", "-m", "second", "--no-edit"])
+        .success();
+
+    // Multiple -m arguments become paragraphs of the new merge commit, and the
+    // previous working-copy commit keeps its description.
+    work_dir
+        .run_jj([
+            "new",
+            "description(first)",
+            "description(second)",
+            "-m",
+            "subject",
+            "-m",
+            "body",
+        ])
+        .success();
+    let output = work_dir.run_jj(["log", "-T", r#"description ++ "--\n""#]);
+    insta::assert_snapshot!(output, @r"
+    @    subject
+    ├─╮
+    │ │  body
+    │ │  --
+    │ ○  second
+    │ │  --
+    ○ │  first
+    ├─╯  --
+    ◆  --
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_new_merge() {
     let test_env = TestEnvironment::default();
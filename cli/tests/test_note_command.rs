@@ -0,0 +1,103 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_note() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "a"]).success();
+    work_dir.run_jj(["new", "-m", "b"]).success();
+    work_dir.run_jj(["new", "root()", "-m", "c"]).success();
+
+    work_dir
+        .run_jj([
+            "note",
+            "set",
+            "-r=description(a)",
+            "--key=ci-status",
+            "-m=passed",
+        ])
+        .success();
+    work_dir
+        .run_jj([
+            "note",
+            "add",
+            "-r=description(a)",
+            "--key=review",
+            "-m=https://example.com/1",
+        ])
+        .success();
+    let output = work_dir.run_jj(["note", "list", "-r=all()"]);
+    insta::assert_snapshot!(output, @r"
+    a
+      ci-status: passed
+      review: https://example.com/1
+    [EOF]
+    ");
+
+    let template = r#"separate(" ", description.first_line(), self.note("ci-status")) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=~root()", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    c
+    b
+    a passed
+    [EOF]
+    ");
+
+    // Notes are keyed by change id, so they survive rewrites
+    work_dir
+        .run_jj(["rebase", "-r=description(a)", "-d=description(c)"])
+        .success();
+    let template = r#"notes.map(|n| n.key() ++ "=" ++ n.value()).join(",") ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=description(a)", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    ci-status=passed,review=https://example.com/1
+    [EOF]
+    ");
+
+    work_dir
+        .run_jj(["note", "remove", "-r=description(a)", "--key=review"])
+        .success();
+    let output = work_dir.run_jj(["note", "list", "-r=all()"]);
+    insta::assert_snapshot!(output, @r"
+    a
+      ci-status: passed
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["note", "remove", "-r=description(a)", "--key=review"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such note: review
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["note", "set", "--key=ci-status", "-m="]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The note content cannot be empty. Use `jj note remove` to remove a note.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Notes don't rewrite the annotated commits
+    let output = work_dir.run_jj(["note", "set", "-r=root()", "--key=k", "-m=v"]);
+    insta::assert_snapshot!(output, @"");
+}
@@ -0,0 +1,163 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+// These tests exist to catch accidental changes to the output of the plumbing
+// commands, which are guaranteed to be stable. Don't update the snapshots
+// unless the change is backward compatible and documented.
+
+#[test]
+fn test_plumbing_resolve_rev() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "-m=first"]).success();
+    work_dir.run_jj(["new", "-m=second"]).success();
+
+    let output = work_dir.run_jj(["plumbing", "resolve-rev", "@"]);
+    insta::assert_snapshot!(output, @r"
+    08a70ab33d7143b7130ed8594d8216ef688623c0
+    [EOF]
+    ");
+
+    // Multiple revsets are combined, and listed in log order
+    let output = work_dir.run_jj(["plumbing", "resolve-rev", "root()", "@-", "@"]);
+    insta::assert_snapshot!(output, @r"
+    08a70ab33d7143b7130ed8594d8216ef688623c0
+    25cb82357bdcbe96521d6577807de10f729f3a9c
+    0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    // Empty revsets are an error unless --allow-empty is given
+    let output = work_dir.run_jj(["plumbing", "resolve-rev", "none()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Empty revision set
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["plumbing", "resolve-rev", "--allow-empty", "none()"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_plumbing_tree_entry() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file", "contents\n");
+    work_dir.write_file("script", "#!/bin/sh\n");
+    work_dir.run_jj(["file", "chmod", "x", "script"]).success();
+    work_dir.run_jj(["new"]).success();
+    work_dir.remove_file("dir/file");
+
+    let output = work_dir.run_jj(["plumbing", "tree-entry", "-r=@-", "dir/file"]);
+    insta::assert_snapshot!(output, @r"
+    file 12f00e90b6ef79117ce6e650416b8cf517099b78 false
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["plumbing", "tree-entry", "script"]);
+    insta::assert_snapshot!(output, @r"
+    file 1a2485251c33a70432394c93fb89330ef214bfc9 true
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["plumbing", "tree-entry", "-r=@-", "dir"]);
+    insta::assert_snapshot!(output, @r"
+    tree 8add0d07efc6ba027407c82740a001cfcbc7b772 false
+    [EOF]
+    ");
+
+    // Paths are relative to the current directory
+    let sub_dir = work_dir.dir("dir");
+    let output = sub_dir.run_jj(["plumbing", "tree-entry", "-r=@-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    file 12f00e90b6ef79117ce6e650416b8cf517099b78 false
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["plumbing", "tree-entry", "dir/file"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Error: No such path: dir/file
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_plumbing_tree_entry_symlink() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    std::os::unix::fs::symlink("target", work_dir.root().join("link")).unwrap();
+
+    let output = work_dir.run_jj(["plumbing", "tree-entry", "link"]);
+    insta::assert_snapshot!(output, @r"
+    symlink 1de565933b05f74c75ff9a6520af5f9f8a5a2f1d false
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_plumbing_tree_entry_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "base\n");
+    work_dir.run_jj(["new", "-m=a"]).success();
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["new", "-m=b", "@-"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir
+        .run_jj(["new", "description(a)", "description(b)"])
+        .success();
+
+    let output = work_dir.run_jj(["plumbing", "tree-entry", "file"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Path is conflicted: file
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_plumbing_wc_commit() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    let output = work_dir.run_jj(["plumbing", "wc-commit"]);
+    insta::assert_snapshot!(output, @r"
+    e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+
+    // The working copy isn't snapshotted
+    work_dir.write_file("file", "contents\n");
+    let output = work_dir.run_jj(["plumbing", "wc-commit"]);
+    insta::assert_snapshot!(output, @r"
+    e8849ae12c709f2321908879bc724fdb2ab8a781
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["plumbing", "resolve-rev", "@"]);
+    insta::assert_snapshot!(output, @r"
+    687618c1843887a96c0bc108306d0e6f5d3d7d79
+    [EOF]
+    ");
+}
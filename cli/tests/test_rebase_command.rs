@@ -260,6 +260,62 @@ fn test_rebase_bookmark() {
     ");
 }
 
+#[test]
+fn test_rebase_bookmark_exclude() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["b"]);
+    create_commit(&work_dir, "e", &["a"]);
+    let setup_opid = work_dir.current_operation_id();
+
+    // The shared ancestor is rebased once, and the excluded commit is left
+    // behind
+    let output = work_dir.run_jj(["rebase", "-b=c", "-b=d", "--exclude=d", "-d=e"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 commits to destination
+    Rebased 1 descendant commits
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    ○  c: b
+    ○  b: e
+    @  e: a
+    │ ○  d: a
+    ├─╯
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // Descendants of the excluded commits are also left behind
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj(["rebase", "-b=c", "--exclude=b", "-d=e"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // --exclude can't be used with -s or -r
+    let output = work_dir.run_jj(["rebase", "-s=c", "--exclude=d", "-d=e"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--source <REVSETS>' cannot be used with '--exclude <REVSETS>'
+
+    Usage: jj rebase --source <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_rebase_bookmark_with_merge() {
     let test_env = TestEnvironment::default();
@@ -707,7 +763,7 @@ fn test_rebase_revision_onto_descendant() {
     let output = work_dir.run_jj(["op", "restore", &setup_opid]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Restored to operation: cb005d7a588c (2001-02-03 08:05:15) create bookmark merge pointing to commit 08c0951bf69d0362708a5223a78446d664823b50
+    Restored to operation: 0a1e2702a474 (2001-02-03 08:05:15) create bookmark merge pointing to commit 08c0951bf69d0362708a5223a78446d664823b50
     Working copy  (@) now at: vruxwmqv 08c0951b merge | merge
     Parent commit (@-)      : royxmykx 6a7081ef b | b
     Parent commit (@-)      : zsuskuln 68fbc443 a | a
@@ -3058,6 +3114,169 @@ fn test_rebase_skip_duplicate_divergent() {
     ");
 }
 
+#[test]
+fn test_rebase_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "a", &[], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["a"], &[("file", "b\n")]);
+    create_commit_with_files(&work_dir, "c", &["b"], &[("other", "c\n")]);
+    create_commit_with_files(&work_dir, "d", &["a"], &[("file", "d\n")]);
+    create_commit_with_files(&work_dir, "e", &["a"], &[("other", "e\n")]);
+    // Test the setup
+    insta::assert_snapshot!(get_long_log_output(&work_dir), @r"
+    @  e  znkkpsqq  12f9ad32:  a
+    │ ○  d  vruxwmqv  56393049:  a
+    ├─╯
+    │ ○  c  royxmykx  4e08b31b:  b
+    │ ○  b  zsuskuln  7ad22752:  a
+    ├─╯
+    ○  a  rlvkpnrz  b27cb722
+    ◆    zzzzzzzz  00000000
+    [EOF]
+    ");
+    let setup_opid = work_dir.current_operation_id();
+    let count_git_objects = || {
+        let objects_dir = work_dir.root().join(".jj/repo/store/git/objects");
+        std::fs::read_dir(objects_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.file_name().unwrap().len() == 2)
+            .map(|path| std::fs::read_dir(path).unwrap().count())
+            .sum::<usize>()
+    };
+    let num_git_objects = count_git_objects();
+
+    // The plan refers to rebased parents by their current commit IDs
+    let output = work_dir.run_jj(["rebase", "-s", "b", "-d", "e", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would rebase 2 commits:
+      4e08b31b2353 -> (7ad227529523): royxmykx 4e08b31b c | c
+      7ad227529523 -> (12f9ad32f958): zsuskuln 7ad22752 b | b
+    Dry-run requested, not rebasing.
+    [EOF]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+    // No commits are written
+    assert_eq!(count_git_objects(), num_git_objects);
+
+    // Predicted conflicts make the command fail
+    let output = work_dir.run_jj(["rebase", "-s", "b", "-d", "d", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would rebase 2 commits:
+      4e08b31b2353 -> (7ad227529523): royxmykx 4e08b31b c | c
+      7ad227529523 -> (56393049bfdc): zsuskuln 7ad22752 b | b
+    Dry-run requested, not rebasing.
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "rebase",
+        "-s",
+        "b",
+        "-d",
+        "d",
+        "--dry-run",
+        "--check-conflicts",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would rebase 2 commits:
+      4e08b31b2353 -> (7ad227529523) (conflict): royxmykx 4e08b31b c | c
+      7ad227529523 -> (56393049bfdc) (conflict): zsuskuln 7ad22752 b | b
+    Dry-run requested, not rebasing.
+    Error: Rebasing would create conflicts in 2 commits
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(work_dir.current_operation_id(), setup_opid);
+
+    // The real rebase matches the plan
+    work_dir.run_jj(["rebase", "-s", "b", "-d", "d"]).success();
+    insta::assert_snapshot!(get_long_log_output(&work_dir), @r"
+    @  e  znkkpsqq  12f9ad32:  a
+    │ ×  c  royxmykx  104eed2a:  b
+    │ ×  b  zsuskuln  280d8b87:  d
+    │ ○  d  vruxwmqv  56393049:  a
+    ├─╯
+    ○  a  rlvkpnrz  b27cb722
+    ◆    zzzzzzzz  00000000
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_rebase_detect_renames() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let content = "1\n2\n3\n4\n5\n6\n7\n8\n";
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", content)]);
+    create_commit_with_files(&work_dir, "rename", &["base"], &[("renamed", content)]);
+    work_dir.remove_file("file");
+    create_commit_with_files(&work_dir, "rename2", &["base"], &[("other", content)]);
+    work_dir.remove_file("file");
+    create_commit_with_files(
+        &work_dir,
+        "modify",
+        &["base"],
+        &[("file", "one\n2\n3\n4\n5\n6\n7\n8\n")],
+    );
+    let setup_opid = work_dir.current_operation_id();
+
+    // Without rename detection, the modification conflicts with the deletion
+    // of the old name.
+    work_dir
+        .run_jj(["rebase", "-r", "modify", "-d", "rename"])
+        .success();
+    let output = work_dir.run_jj(["resolve", "--list", "-r", "modify"]);
+    insta::assert_snapshot!(output, @r"
+    file    2-sided conflict including 1 deletion
+    [EOF]
+    ");
+
+    // With rename detection, the modification is applied to the new name
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    test_env.add_config("merge.detect-renames = true");
+    work_dir
+        .run_jj(["rebase", "-r", "modify", "-d", "rename"])
+        .success();
+    let output = work_dir.run_jj(["diff", "--summary", "-r", "modify"]);
+    insta::assert_snapshot!(output, @r"
+    M renamed
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "modify", "renamed"]);
+    insta::assert_snapshot!(output, @r"
+    one
+    2
+    3
+    4
+    5
+    6
+    7
+    8
+    [EOF]
+    ");
+
+    // A file renamed to different names on both sides isn't treated as a
+    // rename, so both names are kept like without rename detection
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj(["rebase", "-r", "rename2", "-d", "rename"])
+        .success();
+    let output = work_dir.run_jj(["file", "list", "-r", "rename2"]);
+    insta::assert_snapshot!(output, @r"
+    other
+    renamed
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = "bookmarks ++ surround(': ', '', parents.map(|c| c.bookmarks()))";
@@ -16,6 +16,7 @@ use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
 use crate::common::create_commit_with_files;
+use crate::common::force_interactive;
 
 #[test]
 fn test_restore() {
@@ -574,6 +575,154 @@ fn test_restore_interactive_with_paths() {
     ");
 }
 
+#[test]
+fn test_restore_unmatched_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "b\n");
+
+    // An unchanged path is reported as well as a typo'd one
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file2", "c\n");
+    let output = work_dir.run_jj(["restore", "file1", "flie2", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No changes match the given paths: file1, flie2
+    Working copy  (@) now at: rlvkpnrz 9cf9668d (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 112d5fc7 (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+
+    work_dir.write_file("file2", "c\n");
+    let output = work_dir.run_jj(["restore", "--strict-paths", "flie2", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes match the given paths: flie2
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    M file2
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_restore_confirmation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "b\n");
+
+    // Declining the prompt doesn't discard anything
+    let output =
+        work_dir.run_jj_with(|cmd| force_interactive(cmd).arg("restore").write_stdin("\n"));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Discard all changes in 2 files? (y = yes, n = no, i = choose interactively) [n]: Nothing changed.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    A file2
+    [EOF]
+    ");
+
+    // Paths don't need confirmation
+    let output = work_dir.run_jj_with(|cmd| force_interactive(cmd).args(["restore", "file1"]));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: qpvuntsm 5c44f5e9 (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+
+    // Neither does --yes
+    let output = work_dir.run_jj_with(|cmd| force_interactive(cmd).args(["restore", "--yes"]));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: qpvuntsm b167e679 (empty) (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+
+    // Accepting the prompt discards all changes
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "b\n");
+    let output =
+        work_dir.run_jj_with(|cmd| force_interactive(cmd).arg("restore").write_stdin("y\n"));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Discard all changes in 2 files? (y = yes, n = no, i = choose interactively) [n]: Working copy  (@) now at: qpvuntsm 1c63b524 (empty) (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_restore_confirmation_interactive() {
+    let mut test_env = TestEnvironment::default();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\nb\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "a2\nb2\n");
+    work_dir.write_file("file2", "c\n");
+
+    // Discard the change to the first line of file1 only, keep file2
+    let diff_script = [
+        "files-before file1 file2",
+        "files-after JJ-INSTRUCTIONS file1",
+        "reset file2",
+        "write file1\na\nb2\n",
+    ]
+    .join("\0");
+    std::fs::write(diff_editor, diff_script).unwrap();
+    let output =
+        work_dir.run_jj_with(|cmd| force_interactive(cmd).arg("restore").write_stdin("i\n"));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Discard all changes in 2 files? (y = yes, n = no, i = choose interactively) [n]: Working copy  (@) now at: rlvkpnrz f19864e0 (no description set)
+    Parent commit (@-)      : qpvuntsm 218faa23 (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1 b/file1
+    index 422c2b7ab3..6670a6874d 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,2 +1,2 @@
+     a
+    -b
+    +b2
+    diff --git a/file2 b/file2
+    new file mode 100644
+    index 0000000000..f2ad6c76f0
+    --- /dev/null
+    +++ b/file2
+    @@ -0,0 +1,1 @@
+    +c
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     work_dir.run_jj(["log", "-T", "bookmarks"])
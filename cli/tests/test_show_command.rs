@@ -15,6 +15,7 @@
 use regex::Regex;
 
 use crate::common::TestEnvironment;
+use crate::common::create_commit;
 
 #[test]
 fn test_show() {
@@ -255,6 +256,27 @@ fn test_show_with_template() {
     ");
 }
 
+#[test]
+fn test_show_with_template_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "-m", "a new commit"]).success();
+    std::fs::write(test_env.env_root().join("template.txt"), "description").unwrap();
+
+    let output = work_dir.run_jj(["show", "--template-file", "../template.txt"]);
+    insta::assert_snapshot!(output, @r"
+    a new commit
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["show", "--template-file", "-"])
+            .write_stdin("\"<\" ++ description.first_line() ++ \">\"")
+    });
+    insta::assert_snapshot!(output, @"<a new commit>[EOF]");
+}
+
 #[test]
 fn test_show_with_template_no_patch() {
     let test_env = TestEnvironment::default();
@@ -293,6 +315,81 @@ fn test_show_with_no_patch() {
     ");
 }
 
+#[test]
+fn test_show_merge_diff_parents() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commit(&work_dir, "base", &[]);
+    create_commit(&work_dir, "left", &["base"]);
+    create_commit(&work_dir, "right", &["base"]);
+    create_commit(&work_dir, "merge", &["left", "right"]);
+    let commit_hash = |name: &str| {
+        work_dir
+            .run_jj(["log", "--no-graph", "-r", name, "-Tcommit_id.short()"])
+            .success()
+            .stdout
+            .into_raw()
+    };
+    let left_hash = commit_hash("left");
+    let right_hash = commit_hash("right");
+    let merge_hash = commit_hash("merge");
+
+    // By default, the merge commit is compared to the auto-merged parents
+    let output = work_dir.run_jj(["show", "-T", "", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    A merge
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["show", "-T", "", "--summary", "--first-parent-diffs"]);
+    insta::assert_snapshot!(output, @r"
+    A merge
+    A right
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["show", "-T", "", "--summary", "--diff-parent=2"]);
+    insta::assert_snapshot!(output, @r"
+    A left
+    A merge
+    [EOF]
+    ");
+
+    let output = work_dir
+        .run_jj(["show", "-T", "", "--summary", "--all-parent-diffs"])
+        .normalize_stdout_with(|s| {
+            s.replace(&left_hash, "<left>")
+                .replace(&right_hash, "<right>")
+        });
+    insta::assert_snapshot!(output, @r"
+    Diff against parent 1 (<left>):
+    A merge
+    A right
+    Diff against parent 2 (<right>):
+    A left
+    A merge
+    [EOF]
+    ");
+
+    // Non-merge commits are always compared to their only parent
+    let output = work_dir.run_jj(["show", "-T", "", "--summary", "--diff-parent=2", "left"]);
+    insta::assert_snapshot!(output, @r"
+    A left
+    [EOF]
+    ");
+
+    let output = work_dir
+        .run_jj(["show", "-T", "", "--diff-parent=3"])
+        .normalize_stderr_with(|s| s.replace(&merge_hash, "<merge>"));
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Merge commit <merge> has no parent number 3
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_show_with_no_template() {
     let test_env = TestEnvironment::default();
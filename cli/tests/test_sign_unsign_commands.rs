@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use testutils::git;
+
 use crate::common::TestEnvironment;
 
 #[test]
@@ -428,3 +430,72 @@ backend = "test"
     [EOF]
     ");
 }
+
+#[test]
+fn test_log_show_signatures() {
+    let test_env = TestEnvironment::default();
+
+    test_env.add_config(
+        r#"
+[signing]
+behavior = "keep"
+backend = "test"
+key = "impeccable"
+"#,
+    );
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["commit", "-m", "one"]).success();
+    work_dir.run_jj(["commit", "-m", "two"]).success();
+    work_dir.run_jj(["sign", "-r", "@-"]).success();
+
+    let output = work_dir.run_jj(["log", "--show-signatures", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  [GOOD: impeccable] two
+    ○  one
+    ◆
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "show",
+        "--show-signatures",
+        "--no-patch",
+        "-r",
+        "@-",
+        "-T",
+        "description",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    [GOOD: impeccable] two
+    [EOF]
+    ");
+
+    // The signature no longer verifies once the signed commit is tampered with
+    let git_repo = git::open(work_dir.root().join(".jj/repo/store/git"));
+    let commit_id = work_dir
+        .run_jj(["log", "-Tcommit_id", "--no-graph", "-r@-"])
+        .success()
+        .stdout
+        .into_raw();
+    let commit_id = gix::ObjectId::from_hex(commit_id.as_bytes()).unwrap();
+    git::write_commit_with_message(&git_repo, "refs/heads/tampered", commit_id, "tampered\n");
+    work_dir.run_jj(["git", "import"]).success();
+
+    let output = work_dir.run_jj([
+        "log",
+        "--show-signatures",
+        "-r",
+        "tampered",
+        "-T",
+        "description",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  [BAD] tampered
+    │
+    ~
+    [EOF]
+    ");
+}
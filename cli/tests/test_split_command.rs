@@ -14,6 +14,7 @@
 
 use std::path::PathBuf;
 
+use indoc::indoc;
 use test_case::test_case;
 
 use crate::common::CommandOutput;
@@ -158,6 +159,7 @@ fn test_split_by_paths() {
     let output = work_dir.run_jj(["split", "-r", "@-", "nonexistent"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: No changes match the given paths: nonexistent
     Warning: No changes have been selected, so the new revision will be empty
     Rebased 1 descendant commits
     Selected changes : qpvuntsm 49416632 (empty) (no description set)
@@ -305,6 +307,119 @@ fn test_split_with_default_description() {
     "#);
 }
 
+#[test]
+fn test_split_both_inherit_trailers() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.add_config(r#"split.inherited-trailers = ["Bug"]"#);
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir
+        .run_jj([
+            "describe",
+            "-m",
+            "Add file1 & file2\n\nBug: 123\nReviewed-by: Someone",
+        ])
+        .success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // Without the flag, the given description is used as is
+    work_dir
+        .run_jj(["split", "-m", "Add file1", "file1"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  kkmpptxzrspx false Add file1 & file2
+    │
+    │  Bug: 123
+    │  Reviewed-by: Someone
+    ○  qpvuntsmwlqt false Add file1
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+
+    // With --message, only the configured trailers are added to the given
+    // description
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    work_dir
+        .run_jj([
+            "split",
+            "--both-inherit-trailers",
+            "-m",
+            "Add file1",
+            "file1",
+        ])
+        .success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  royxmykxtrkr false Add file1 & file2
+    │
+    │  Bug: 123
+    │  Reviewed-by: Someone
+    ○  qpvuntsmwlqt false Add file1
+    │
+    │  Bug: 123
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+
+    // The editor templates of both parts contain the trailer. The trailer is
+    // removed from the part whose description is emptied.
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    std::fs::write(
+        &edit_script,
+        [
+            "dump editor1",
+            "write\nAdd file1\n\nBug: 123\n",
+            "next invocation\n",
+            "dump editor2",
+            "write\nBug: 123\n",
+        ]
+        .join("\0"),
+    )
+    .unwrap();
+    work_dir
+        .run_jj(["split", "--both-inherit-trailers", "file1"])
+        .success();
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor1")).unwrap(), @r#"
+    JJ: Enter a description for the selected changes.
+    Add file1 & file2
+
+    Bug: 123
+    Reviewed-by: Someone
+
+    JJ: Change ID: qpvuntsm
+    JJ: This commit contains the following changes:
+    JJ:     A file1
+    JJ:
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor2")).unwrap(), @r#"
+    JJ: Enter a description for the remaining changes.
+    Add file1 & file2
+
+    Bug: 123
+    Reviewed-by: Someone
+
+    JJ: Change ID: yostqsxw
+    JJ: This commit contains the following changes:
+    JJ:     A file2
+    JJ:
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  yostqsxwqrlt false
+    ○  qpvuntsmwlqt false Add file1
+    │
+    │  Bug: 123
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_split_with_descendants() {
     // Configure the environment and make the initial commits.
@@ -399,16 +514,16 @@ fn test_split_with_descendants() {
     insta::assert_snapshot!(evolog_1, @r"
     ○  qpvuntsm test.user@example.com 2001-02-03 08:05:12 74306e35
     │  Add file1
-    │  -- operation 994b490f285d split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
+    │  -- operation d67c59f7daa8 split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 1d2499e7
     │  Add file1 & file2
-    │  -- operation adf4f33386c9 commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 1c0a370d4a69 commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 78ead2155fcc snapshot working copy
+    │  -- operation 132e47411556 snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 add workspace 'default'
+       -- operation 0548605fd744 add workspace 'default'
     [EOF]
     ");
 
@@ -418,16 +533,222 @@ fn test_split_with_descendants() {
     insta::assert_snapshot!(evolog_2, @r"
     ○  royxmykx test.user@example.com 2001-02-03 08:05:12 0a37745e
     │  Add file2
-    │  -- operation 994b490f285d split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
+    │  -- operation d67c59f7daa8 split commit 1d2499e72cefc8a2b87ebb47569140857b96189f
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 1d2499e7
     │  Add file1 & file2
-    │  -- operation adf4f33386c9 commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 1c0a370d4a69 commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 78ead2155fcc snapshot working copy
+    │  -- operation 132e47411556 snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 add workspace 'default'
+       -- operation 0548605fd744 add workspace 'default'
+    [EOF]
+    ");
+}
+
+// Paths conflicted in the merged parents of a merge commit can't be split since
+// their changes in the merge commit aren't well defined.
+#[test]
+fn test_split_merge_with_conflicted_parents() {
+    let mut test_env = TestEnvironment::default();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("conflicted", "base\n");
+    work_dir.run_jj(["new", "-m=left"]).success();
+    work_dir.write_file("conflicted", "left\n");
+    work_dir
+        .run_jj(["new", "description(left)-", "-m=right"])
+        .success();
+    work_dir.write_file("conflicted", "right\n");
+    work_dir
+        .run_jj(["new", "description(left)", "description(right)", "-m=merge"])
+        .success();
+    work_dir.write_file("conflicted", "resolved\n");
+    work_dir.write_file("clean", "clean\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // Conflicted paths can't be named
+    let output = work_dir.run_jj(["split", "conflicted", "clean"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot split paths conflicted in the parents of the merge commit: conflicted
+    Hint: Only paths without conflicts in the merged parents can be split.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Other paths can be split
+    work_dir.run_jj(["split", "-m=first", "clean"]).success();
+    let output = work_dir.run_jj(["diff", "-s", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    A clean
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    M conflicted
+    [EOF]
+    ");
+
+    // The interactive selection leaves the conflicted paths out
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    std::fs::write(&diff_editor, "files-after JJ-INSTRUCTIONS clean").unwrap();
+    let output = work_dir.run_jj(["split", "-m=first"]);
+    insta::assert_snapshot!(output.success().stderr.take_n_lines(2), @r"
+    Warning: The following paths are conflicted in the parents of the merge commit and cannot be split interactively: conflicted
+    Hint: Their changes will be kept in the remaining changes.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    A clean
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_split_from_patch() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\nb\nc\nd\ne\nf\ng\nh\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "a\nB\nc\nd\ne\nf\nG\nh\n");
+
+    // A patch that doesn't apply to the parent is reported
+    let bad_patch = indoc! {"
+        diff --git a/file b/file
+        --- a/file
+        +++ b/file
+        @@ -1,3 +1,3 @@
+         a
+        -x
+        +B
+         c
+    "};
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["split", "-m=first", "--from-patch=-"])
+            .write_stdin(bad_patch)
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Patch for file does not apply: hunk #1 at patch line 4: line 2 of the file doesn't match
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Only the first hunk is selected
+    let patch = indoc! {"
+        diff --git a/file b/file
+        --- a/file
+        +++ b/file
+        @@ -1,3 +1,3 @@
+         a
+        -b
+        +B
+         c
+    "};
+    work_dir
+        .run_jj_with(|cmd| {
+            cmd.args(["split", "-m=first", "--from-patch=-"])
+                .write_stdin(patch)
+        })
+        .success();
+    let output = work_dir.run_jj(["file", "show", "-r@-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    B
+    c
+    d
+    e
+    f
+    g
+    h
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r@", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    B
+    c
+    d
+    e
+    f
+    G
+    h
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_split_select_matching() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\nb\nc\nd\ne\nf\ng\nh\n");
+    work_dir.write_file("third", "x\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "a\nlog::debug!()\nc\nd\ne\nf\nG\nh\n");
+    work_dir.write_file("other", "tracing::info!()\n");
+    work_dir.write_file("third", "y\n");
+
+    let output = work_dir.run_jj(["split", "--select-matching=log::|tracing::", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    file:
+      @@ -2,1 +2,1 @@
+    other:
+      @@ -0,0 +1,1 @@
+    [EOF]
+    ");
+
+    // Only the hunks in the given files are selected
+    let output = work_dir.run_jj([
+        "split",
+        "--select-matching=log::|tracing::",
+        "--dry-run",
+        "file",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    file:
+      @@ -2,1 +2,1 @@
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["split", "--select-matching=nothing", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No hunks match the pattern
+    [EOF]
+    ");
+
+    work_dir
+        .run_jj(["split", "-m=first", "--select-matching=log::|tracing::"])
+        .success();
+    let output = work_dir.run_jj(["diff", "-r@-", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file
+    A other
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-r@", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file
+    M third
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r@-", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    log::debug!()
+    c
+    d
+    e
+    f
+    g
+    h
     [EOF]
     ");
 }
@@ -562,13 +883,13 @@ fn test_split_parallel_no_descendants() {
     insta::assert_snapshot!(evolog_1, @r#"
     ○  qpvuntsm test.user@example.com 2001-02-03 08:05:09 7bcd474c
     │  TESTED=TODO
-    │  -- operation 2b21c33e1596 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 20d79e33dbb8 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 1663cd1cc445 snapshot working copy
+    │  -- operation f76e6be6c61c snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 add workspace 'default'
+       -- operation 0548605fd744 add workspace 'default'
     [EOF]
     ------- stderr -------
     Warning: Deprecated user-level config: ui.default-description is updated to template-aliases.default_commit_description = '"\n\nTESTED=TODO\n"'
@@ -581,13 +902,13 @@ fn test_split_parallel_no_descendants() {
     insta::assert_snapshot!(evolog_2, @r#"
     @  kkmpptxz test.user@example.com 2001-02-03 08:05:09 431886f6
     │  (no description set)
-    │  -- operation 2b21c33e1596 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
+    │  -- operation 20d79e33dbb8 split commit f5700f8ef89e290e4e90ae6adc0908707e0d8c85
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:08 f5700f8e
     │  (no description set)
-    │  -- operation 1663cd1cc445 snapshot working copy
+    │  -- operation f76e6be6c61c snapshot working copy
     ○  qpvuntsm hidden test.user@example.com 2001-02-03 08:05:07 e8849ae1
        (empty) (no description set)
-       -- operation 8f47435a3990 add workspace 'default'
+       -- operation 0548605fd744 add workspace 'default'
     [EOF]
     ------- stderr -------
     Warning: Deprecated user-level config: ui.default-description is updated to template-aliases.default_commit_description = '"\n\nTESTED=TODO\n"'
@@ -744,6 +1065,52 @@ fn test_split_parallel_with_merge_child() {
     ");
 }
 
+#[test]
+fn test_split_parallel_adjacent_hunks() {
+    let mut test_env = TestEnvironment::default();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "A\nB\n");
+
+    // Select the change to the first line only. The change to the second line
+    // is next to it, but the remaining changes shouldn't be conflicted.
+    std::fs::write(diff_editor, "write file\nA\nb\n").unwrap();
+    let output = work_dir.run_jj(["split", "--parallel", "-i", "-m", "first line"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : rlvkpnrz f99d28e5 first line
+    Remaining changes: kkmpptxz aa024579 (no description set)
+    Working copy  (@) now at: kkmpptxz aa024579 (no description set)
+    Parent commit (@-)      : qpvuntsm c0556e29 (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  kkmpptxzrspx false
+    │ ○  rlvkpnrzqnoo false first line
+    ├─╯
+    ○  qpvuntsmwlqt false
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "description('first line')", "file"]);
+    insta::assert_snapshot!(output, @r"
+    A
+    b
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "@", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    B
+    [EOF]
+    ");
+}
+
 // Make sure `jj split` would refuse to split an empty commit.
 #[test]
 fn test_split_empty() {
@@ -829,6 +1196,7 @@ fn test_split_interactive() {
     JJ: This commit contains the following changes:
     JJ:     A file1
     JJ:
+    JJ: Add a line "JJ: restart-selection" to select the changes again.
     JJ: Lines starting with "JJ:" (like this one) will be removed.
     "#);
 
@@ -846,78 +1214,314 @@ fn test_split_interactive() {
 }
 
 #[test]
-fn test_split_interactive_with_paths() {
+fn test_split_interactive_restart_selection() {
     let mut test_env = TestEnvironment::default();
     let edit_script = test_env.set_up_fake_editor();
     let diff_editor = test_env.set_up_fake_diff_editor();
     test_env.run_jj_in(".", ["git", "init", "repo"]).success();
     let work_dir = test_env.work_dir("repo");
 
-    work_dir.write_file("file2", "");
-    work_dir.write_file("file3", "");
-    work_dir.run_jj(["new"]).success();
     work_dir.write_file("file1", "foo\n");
     work_dir.write_file("file2", "bar\n");
     work_dir.write_file("file3", "baz\n");
 
-    std::fs::write(edit_script, ["dump editor"].join("\0")).unwrap();
-    // On the before side, file2 is empty. On the after side, it contains "bar".
-    // The "reset file2" copies the empty version from the before side to the
-    // after side, effectively "unselecting" the changes and leaving only the
-    // changes made to file1. file3 doesn't appear on either side since it isn't
-    // in the filesets passed to `jj split`.
+    // Select only file1 at first, then add file2 to the previous selection
     let diff_script = [
-        "files-before file2",
-        "files-after JJ-INSTRUCTIONS file1 file2",
-        "reset file2",
+        "rm file2",
+        "rm file3",
+        "next invocation\n",
+        "write file2\nbar\n",
     ]
     .join("\0");
     std::fs::write(diff_editor, diff_script).unwrap();
+    // Enter a description and go back to the selection, then accept the
+    // description entered previously
+    std::fs::write(
+        edit_script,
+        [
+            "write\nfirst part\nJJ: restart-selection\n",
+            "next invocation\n",
+            "dump editor",
+        ]
+        .join("\0"),
+    )
+    .unwrap();
+    work_dir.run_jj(["split"]).success();
 
-    // Select file1 and file2 by args, then select file1 interactively via the diff
-    // script.
-    let output = work_dir.run_jj(["split", "-i", "file1", "file2"]);
-    insta::assert_snapshot!(output, @r"
-    ------- stderr -------
-    Selected changes : rlvkpnrz cdc9960a (no description set)
-    Remaining changes: kkmpptxz 7255f070 (no description set)
-    Working copy  (@) now at: kkmpptxz 7255f070 (no description set)
-    Parent commit (@-)      : rlvkpnrz cdc9960a (no description set)
-    [EOF]
-    ");
-
+    // The second selection started from the first one, so file3 stays out of it
     insta::assert_snapshot!(
         std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r#"
     JJ: Enter a description for the selected changes.
+    first part
 
-
-    JJ: Change ID: rlvkpnrz
+    JJ: Change ID: qpvuntsm
     JJ: This commit contains the following changes:
     JJ:     A file1
+    JJ:     A file2
     JJ:
+    JJ: Add a line "JJ: restart-selection" to select the changes again.
     JJ: Lines starting with "JJ:" (like this one) will be removed.
     "#);
 
-    let output = work_dir.run_jj(["log", "--summary"]);
+    let output = work_dir.run_jj(["log", "--no-graph", "-T", "description", "-r", "@-"]);
     insta::assert_snapshot!(output, @r"
-    @  kkmpptxz test.user@example.com 2001-02-03 08:05:09 7255f070
-    │  (no description set)
-    │  M file2
-    │  M file3
-    ○  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 cdc9960a
-    │  (no description set)
-    │  A file1
-    ○  qpvuntsm test.user@example.com 2001-02-03 08:05:08 ff687a2f
-    │  (no description set)
-    │  A file2
-    │  A file3
-    ◆  zzzzzzzz root() 00000000
+    first part
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    A file2
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    A file3
     [EOF]
     ");
 }
 
-// When a commit is split, the second commit produced by the split becomes the
-// working copy commit for all workspaces whose working copy commit was the
+#[test]
+fn test_split_num_parts() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.write_file("file3", "baz\n");
+    work_dir.write_file("file4", "qux\n");
+    work_dir.run_jj(["describe", "-m", "big change"]).success();
+    work_dir.run_jj(["new", "-m", "child"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // Select file1, then file2, and leave the rest for the last part
+    std::fs::write(
+        &diff_editor,
+        [
+            "rm file2",
+            "rm file3",
+            "rm file4",
+            "dump JJ-INSTRUCTIONS instrs",
+            "next invocation\n",
+            "rm file3",
+            "rm file4",
+        ]
+        .join("\0"),
+    )
+    .unwrap();
+    std::fs::write(
+        &edit_script,
+        [
+            "dump editor1",
+            "write\npart 1",
+            "next invocation\n",
+            "dump editor2",
+            "write\npart 2",
+            "next invocation\n",
+            "write\npart 3",
+        ]
+        .join("\0"),
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["split", "-r@-", "--num=3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Selected changes : qpvuntsm bbb6e8ec part 1
+    Selected changes : zsuskuln 8c739ab6 part 2
+    Remaining changes: rzvqmyuk 067286c2 part 3
+    Working copy  (@) now at: kkmpptxz 5cb6a145 (empty) child
+    Parent commit (@-)      : rzvqmyuk 067286c2 part 3
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("instrs")).unwrap(), @r"
+    You are splitting a commit into up to 3 parts: qpvuntsm 8e3123f0 big change
+
+    The diff initially shows the changes left for part 1 and later.
+
+    Adjust the right side until it shows the contents you want to split into
+    part 1.
+    The changes that are not selected will be left for the next parts.
+    ");
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor2")).unwrap(), @r#"
+    JJ: Enter a description for part 2 of the split.
+    big change
+
+    JJ: Change ID: zsuskuln
+    JJ: This commit contains the following changes:
+    JJ:     A file2
+    JJ:
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  kkmpptxzrspx child
+    ○  rzvqmyukvvwz part 3
+    │  A file3
+    │  A file4
+    ○  zsuskulnrvyr part 2
+    │  A file2
+    ○  qpvuntsmwlqt part 1
+    │  A file1
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["op", "log", "-n1", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @  split commit 8e3123f011fec4b9e68bd02bfb04221e3135cbb4 into 3 parts
+    [EOF]
+    ");
+
+    // Selecting all the remaining changes stops early
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    std::fs::write(
+        &diff_editor,
+        ["rm file3", "rm file4", "next invocation\n"].join("\0"),
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["split", "-r@-", "--num=4", "--no-edit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: All changes have been selected, so the original revision will become empty
+    Rebased 1 descendant commits
+    Selected changes : qpvuntsm 30ef8f1c big change
+    Selected changes : vruxwmqv f837b08e (no description set)
+    Remaining changes: nuwvvtmy a66c4773 (empty) (no description set)
+    Working copy  (@) now at: kkmpptxz 180fe7a0 (empty) child
+    Parent commit (@-)      : nuwvvtmy a66c4773 (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  kkmpptxzrspx child
+    ○  nuwvvtmyvtkm
+    ○  vruxwmqvtpmx
+    │  A file3
+    │  A file4
+    ○  qpvuntsmwlqt big change
+    │  A file1
+    │  A file2
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // Selecting no changes in the first round is an error
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    std::fs::write(
+        &diff_editor,
+        ["reset file1", "reset file2", "reset file3", "reset file4"].join("\0"),
+    )
+    .unwrap();
+    let output = work_dir.run_jj(["split", "-r@-", "--num=3", "--no-edit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes selected
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // At least two parts must be requested
+    let output = work_dir.run_jj(["split", "--num=1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value '1' for '--num <N>': 1 is not in 2..=4294967295
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+
+    // The parts can't be moved into an existing revision
+    let output = work_dir.run_jj(["split", "--num=3", "--into=root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--num <N>' cannot be used with '--into <REVSET>'
+
+    Usage: jj split --num <N> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_split_interactive_with_paths() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file2", "");
+    work_dir.write_file("file3", "");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.write_file("file3", "baz\n");
+
+    std::fs::write(edit_script, ["dump editor"].join("\0")).unwrap();
+    // On the before side, file2 is empty. On the after side, it contains "bar".
+    // The "reset file2" copies the empty version from the before side to the
+    // after side, effectively "unselecting" the changes and leaving only the
+    // changes made to file1. file3 doesn't appear on either side since it isn't
+    // in the filesets passed to `jj split`.
+    let diff_script = [
+        "files-before file2",
+        "files-after JJ-INSTRUCTIONS file1 file2",
+        "reset file2",
+    ]
+    .join("\0");
+    std::fs::write(diff_editor, diff_script).unwrap();
+
+    // Select file1 and file2 by args, then select file1 interactively via the diff
+    // script.
+    let output = work_dir.run_jj(["split", "-i", "file1", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : rlvkpnrz cdc9960a (no description set)
+    Remaining changes: kkmpptxz 7255f070 (no description set)
+    Working copy  (@) now at: kkmpptxz 7255f070 (no description set)
+    Parent commit (@-)      : rlvkpnrz cdc9960a (no description set)
+    [EOF]
+    ");
+
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r#"
+    JJ: Enter a description for the selected changes.
+
+
+    JJ: Change ID: rlvkpnrz
+    JJ: This commit contains the following changes:
+    JJ:     A file1
+    JJ:
+    JJ: Add a line "JJ: restart-selection" to select the changes again.
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+
+    let output = work_dir.run_jj(["log", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    @  kkmpptxz test.user@example.com 2001-02-03 08:05:09 7255f070
+    │  (no description set)
+    │  M file2
+    │  M file3
+    ○  rlvkpnrz test.user@example.com 2001-02-03 08:05:09 cdc9960a
+    │  (no description set)
+    │  A file1
+    ○  qpvuntsm test.user@example.com 2001-02-03 08:05:08 ff687a2f
+    │  (no description set)
+    │  A file2
+    │  A file3
+    ◆  zzzzzzzz root() 00000000
+    [EOF]
+    ");
+}
+
+// When a commit is split, the second commit produced by the split becomes the
+// working copy commit for all workspaces whose working copy commit was the
 // target of the split. This test does a split where the target commit is the
 // working copy commit for two different workspaces.
 #[test]
@@ -982,6 +1586,98 @@ fn test_split_with_multiple_workspaces_same_working_copy() {
     ");
 }
 
+#[test]
+fn test_split_edit_first() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir.run_jj(["desc", "-m", "original"]).success();
+    main_dir.write_file("file1", "foo");
+    main_dir.write_file("file2", "foo");
+    main_dir
+        .run_jj(["workspace", "add", "--name", "second", "../secondary"])
+        .success();
+    secondary_dir
+        .run_jj(["edit", "-r", "description(original)"])
+        .success();
+    let setup_opid = main_dir.current_operation_id();
+
+    // By default, the working copy moves to the commit with the remaining
+    // changes.
+    let output = main_dir.run_jj(["split", "file1", "-m", "selected"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm 9696aa24 selected
+    Remaining changes: mzvwutvl e99f1f17 original
+    Working copy  (@) now at: mzvwutvl e99f1f17 original
+    Parent commit (@-)      : qpvuntsm 9696aa24 selected
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_workspace_log_output(&main_dir), @r"
+    @  mzvwutvlkqwt default@ second@ original
+    ○  qpvuntsmwlqt selected
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // With --edit-first, all workspaces editing the split commit stay on the
+    // commit with the selected changes.
+    main_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = main_dir.run_jj(["split", "file1", "-m", "selected", "--edit-first"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm cac3518f selected
+    Remaining changes: vruxwmqv ff97d63c original
+    Working copy  (@) now at: qpvuntsm cac3518f selected
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_workspace_log_output(&main_dir), @r"
+    ○  vruxwmqvtpmx original
+    @  qpvuntsmwlqt default@ second@ selected
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    insta::assert_snapshot!(main_dir.run_jj(["file", "list"]), @r"
+    file1
+    [EOF]
+    ");
+
+    // Test again with a --parallel split.
+    main_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = main_dir.run_jj([
+        "split",
+        "file1",
+        "-m",
+        "selected",
+        "--edit-first",
+        "--parallel",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm 552a1ec1 selected
+    Remaining changes: kmkuslsw d318427e original
+    Working copy  (@) now at: qpvuntsm 552a1ec1 selected
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_workspace_log_output(&main_dir), @r"
+    @  qpvuntsmwlqt default@ second@ selected
+    │ ○  kmkuslswpqwq original
+    ├─╯
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    insta::assert_snapshot!(main_dir.run_jj(["file", "list"]), @r"
+    file1
+    [EOF]
+    ");
+}
+
 // A workspace should only have its working copy commit updated if the target
 // commit is the working copy commit.
 #[test]
@@ -1105,76 +1801,355 @@ fn test_split_with_non_empty_description_and_trailers() {
 
     Signed-off-by: test.user@example.com
 
-    JJ: Change ID: kkmpptxz
-    JJ: This commit contains the following changes:
-    JJ:     A file2
-    JJ:
-    JJ: Lines starting with "JJ:" (like this one) will be removed.
-    "#);
-    insta::assert_snapshot!(get_log_output(&work_dir), @r#"
-    @  kkmpptxzrspx false part 2
-    ○  qpvuntsmwlqt false part 1
-    ◆  zzzzzzzzzzzz true
+    JJ: Change ID: kkmpptxz
+    JJ: This commit contains the following changes:
+    JJ:     A file2
+    JJ:
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+    insta::assert_snapshot!(get_log_output(&work_dir), @r#"
+    @  kkmpptxzrspx false part 2
+    ○  qpvuntsmwlqt false part 1
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ------- stderr -------
+    Warning: Deprecated user-level config: ui.default-description is updated to template-aliases.default_commit_description = '"\n\nTESTED=TODO\n"'
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_split_with_message() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.run_jj(["describe", "-m", "my feature"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    let output = work_dir.run_jj(["split", "-m", "fix in file1", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm f2a70519 fix in file1
+    Remaining changes: kkmpptxz cac11766 my feature
+    Working copy  (@) now at: kkmpptxz cac11766 my feature
+    Parent commit (@-)      : qpvuntsm f2a70519 fix in file1
+    [EOF]
+    ");
+
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  kkmpptxzrspx false my feature
+    ○  qpvuntsmwlqt false fix in file1
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+
+    // trailers should be added to the message
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj([
+        "split",
+        "--config",
+        r#"templates.commit_trailers='"CC: " ++ committer.email()'"#,
+        "-m",
+        "fix in file1",
+        "file1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm d01cf12d fix in file1
+    Remaining changes: royxmykx b1556ed9 my feature
+    Working copy  (@) now at: royxmykx b1556ed9 my feature
+    Parent commit (@-)      : qpvuntsm d01cf12d fix in file1
+    [EOF]
+    ");
+
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  royxmykxtrkr false my feature
+    ○  qpvuntsmwlqt false fix in file1
+    │
+    │  CC: test.user@example.com
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_split_with_message_parallel() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(&edit_script, "fail").unwrap();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.write_file("file3", "baz\n");
+    work_dir.run_jj(["describe", "-m", "my feature"]).success();
+
+    // Repeated -m flags are joined as paragraphs, and the remaining changes
+    // keep the original description.
+    let output = work_dir.run_jj([
+        "split",
+        "--parallel",
+        "-m",
+        "fix in file1",
+        "-m",
+        "and file2",
+        "file1",
+        "file2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm c14646a5 fix in file1
+    Remaining changes: kkmpptxz 570a0d7c my feature
+    Working copy  (@) now at: kkmpptxz 570a0d7c my feature
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  kkmpptxzrspx my feature
+    │  A file3
+    │ ○  qpvuntsmwlqt fix in file1
+    ├─╯
+    │    and file2
+    │    A file1
+    │    A file2
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_split_no_edit() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(&edit_script, "fail").unwrap();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.run_jj(["describe", "-m", "my feature"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // The selected changes keep the original description, and the remaining
+    // changes get an empty one.
+    let output = work_dir.run_jj(["split", "--no-edit", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm 335b3c35 my feature
+    Remaining changes: kkmpptxz b402a012 (no description set)
+    Working copy  (@) now at: kkmpptxz b402a012 (no description set)
+    Parent commit (@-)      : qpvuntsm 335b3c35 my feature
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  kkmpptxzrspx
+    │  A file2
+    ○  qpvuntsmwlqt my feature
+    │  A file1
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj(["split", "--no-edit", "--parallel", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Selected changes : qpvuntsm 0fd81b5d my feature
+    Remaining changes: royxmykx 33dd3374 (no description set)
+    Working copy  (@) now at: royxmykx 33dd3374 (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 0 files, modified 0 files, removed 1 files
     [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  royxmykxtrkr
+    │  A file2
+    │ ○  qpvuntsmwlqt my feature
+    ├─╯  A file1
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // --no-edit conflicts with --message
+    let output = work_dir.run_jj(["split", "--no-edit", "-m", "foo", "file1"]);
+    insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Warning: Deprecated user-level config: ui.default-description is updated to template-aliases.default_commit_description = '"\n\nTESTED=TODO\n"'
+    error: the argument '--no-edit' cannot be used with '--message <MESSAGE>'
+
+    Usage: jj split --no-edit <FILESETS>...
+
+    For more information, try '--help'.
     [EOF]
-    "#);
+    [exit status: 2]
+    ");
 }
 
 #[test]
-fn test_split_with_message() {
+fn test_split_into() {
     let test_env = TestEnvironment::default();
     test_env.run_jj_in(".", ["git", "init", "repo"]).success();
     let work_dir = test_env.work_dir("repo");
 
-    work_dir.write_file("file1", "foo\n");
-    work_dir.write_file("file2", "bar\n");
-    work_dir.run_jj(["describe", "-m", "my feature"]).success();
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["commit", "-m", "parent"]).success();
+    work_dir.write_file("file2", "b\n");
+    work_dir.write_file("file3", "c\n");
+    work_dir.run_jj(["describe", "-m", "target"]).success();
+    work_dir.run_jj(["new", "-m", "child"]).success();
+    work_dir
+        .run_jj(["new", "description(parent)", "-m", "sibling"])
+        .success();
+    work_dir.write_file("file4", "d\n");
+    work_dir.run_jj(["edit", "description(child)"]).success();
     let setup_opid = work_dir.current_operation_id();
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  zsuskulnrvyr child
+    ○  rlvkpnrzqnoo target
+    │  A file2
+    │  A file3
+    │ ○  mzvwutvlkqwt sibling
+    ├─╯  A file4
+    ○  qpvuntsmwlqt parent
+    │  A file1
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
 
-    let output = work_dir.run_jj(["split", "-m", "fix in file1", "file1"]);
+    // Move changes into a sibling
+    let output = work_dir.run_jj([
+        "split",
+        "-r",
+        "description(target)",
+        "--into",
+        "description(sibling)",
+        "file2",
+    ]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Selected changes : qpvuntsm f2a70519 fix in file1
-    Remaining changes: kkmpptxz cac11766 my feature
-    Working copy  (@) now at: kkmpptxz cac11766 my feature
-    Parent commit (@-)      : qpvuntsm f2a70519 fix in file1
+    Rebased 1 descendant commits
+    Selected changes : mzvwutvl 587493d0 sibling
+    Remaining changes: rlvkpnrz 6785369a target
+    Working copy  (@) now at: zsuskuln 3b1d9022 (empty) child
+    Parent commit (@-)      : rlvkpnrz 6785369a target
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  zsuskulnrvyr child
+    ○  rlvkpnrzqnoo target
+    │  A file3
+    │ ○  mzvwutvlkqwt sibling
+    ├─╯  A file2
+    │    A file4
+    ○  qpvuntsmwlqt parent
+    │  A file1
+    ◆  zzzzzzzzzzzz
     [EOF]
     ");
 
-    insta::assert_snapshot!(get_log_output(&work_dir), @r"
-    @  kkmpptxzrspx false my feature
-    ○  qpvuntsmwlqt false fix in file1
-    ◆  zzzzzzzzzzzz true
+    // Move changes into the parent
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj([
+        "split",
+        "-r",
+        "description(target)",
+        "--into",
+        "description(parent)",
+        "file2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 descendant commits
+    Selected changes : qpvuntsm aa8611f5 parent
+    Remaining changes: rlvkpnrz 5a94fa82 target
+    Working copy  (@) now at: zsuskuln 15aafd1b (empty) child
+    Parent commit (@-)      : rlvkpnrz 5a94fa82 target
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  zsuskulnrvyr child
+    ○  rlvkpnrzqnoo target
+    │  A file3
+    │ ○  mzvwutvlkqwt sibling
+    ├─╯  A file4
+    ○  qpvuntsmwlqt parent
+    │  A file1
+    │  A file2
+    ◆  zzzzzzzzzzzz
     [EOF]
     ");
 
-    // trailers should be added to the message
+    // Moving changes into the revision itself or a descendant is refused
     work_dir.run_jj(["op", "restore", &setup_opid]).success();
     let output = work_dir.run_jj([
         "split",
-        "--config",
-        r#"templates.commit_trailers='"CC: " ++ committer.email()'"#,
-        "-m",
-        "fix in file1",
-        "file1",
+        "-r",
+        "description(target)",
+        "--into",
+        "description(child)",
+        "file2",
     ]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Selected changes : qpvuntsm d01cf12d fix in file1
-    Remaining changes: royxmykx b1556ed9 my feature
-    Working copy  (@) now at: royxmykx b1556ed9 my feature
-    Parent commit (@-)      : qpvuntsm d01cf12d fix in file1
+    Error: Cannot split dd8ee2bc51cc into its descendant 54e8a75c1962
+    Hint: Use `jj squash --from` to move the changes into a descendant.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj([
+        "split",
+        "-r",
+        "description(target)",
+        "--into",
+        "description(target)",
+        "file2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot split dd8ee2bc51cc into itself
     [EOF]
+    [exit status: 1]
     ");
+}
 
-    insta::assert_snapshot!(get_log_output(&work_dir), @r"
-    @  royxmykxtrkr false my feature
-    ○  qpvuntsmwlqt false fix in file1
-    │
-    │  CC: test.user@example.com
-    ◆  zzzzzzzzzzzz true
+#[test]
+fn test_split_into_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m", "parent"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["describe", "-m", "target"]).success();
+    work_dir.run_jj(["new", "@-", "-m", "sibling"]).success();
+    work_dir.write_file("file", "c\n");
+
+    // Conflicts in the destination are reported like in other rewrites
+    let output = work_dir.run_jj(["split", "-r", "description(target)", "--into", "@", "file"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: All changes have been selected, so the original revision will become empty
+    Selected changes : zsuskuln ac665d7e (conflict) sibling
+    Remaining changes: rlvkpnrz daec09ea (empty) target
+    Working copy  (@) now at: zsuskuln ac665d7e (conflict) sibling
+    Parent commit (@-)      : qpvuntsm 58c059d4 parent
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    New conflicts appeared in 1 commits:
+      zsuskuln ac665d7e (conflict) sibling
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the conflicted commit:
+      jj new zsuskuln
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
     [EOF]
     ");
 }
@@ -1438,6 +2413,122 @@ fn test_split_move_first_commit() {
     ");
 }
 
+#[test]
+fn test_split_move_remaining_commit() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.write_file("file2", "bar\n");
+    work_dir.run_jj(["commit", "-m", "file2"]).success();
+    work_dir.write_file("file3", "bar\n");
+    work_dir.run_jj(["commit", "-m", "file3"]).success();
+    work_dir.write_file("file4", "bar\n");
+    work_dir.run_jj(["commit", "-m", "file4"]).success();
+    work_dir.run_jj(["new", "root()"]).success();
+    work_dir.write_file("file5", "bar\n");
+    work_dir.run_jj(["commit", "-m", "file5"]).success();
+    let setup_opid = work_dir.current_operation_id();
+
+    // insert the remaining changes after a commit on another branch
+    let output = work_dir.run_jj([
+        "split",
+        "-m",
+        "file1",
+        "-r",
+        "qpvuntsmwlqt",
+        "--insert-remaining-after",
+        "mzvwutvlkqwt",
+        "file1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 3 descendant commits
+    Selected changes : qpvuntsm a04fab94 file1
+      with parent    : zzzzzzzz 00000000 (empty) (no description set)
+    Remaining changes: yqosqzyt 6de1382b file2
+      with parent    : mzvwutvl 3c410f84 file5
+    Working copy  (@) now at: royxmykx 29eeef95 (empty) (no description set)
+    Parent commit (@-)      : yqosqzyt 6de1382b file2
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  royxmykxtrkr
+    ○  yqosqzytrlsw file2
+    │  A file2
+    ○  mzvwutvlkqwt file5
+    │  A file5
+    │ ○  kkmpptxzrspx file4
+    │ │  A file4
+    │ ○  rlvkpnrzqnoo file3
+    │ │  A file3
+    │ ○  qpvuntsmwlqt file1
+    ├─╯  A file1
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // insert the remaining changes before a descendant
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj([
+        "split",
+        "-m",
+        "file1",
+        "-r",
+        "qpvuntsmwlqt",
+        "--insert-remaining-before",
+        "kkmpptxzrspx",
+        "file1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 descendant commits
+    Selected changes : qpvuntsm e0af6183 file1
+      with parent    : zzzzzzzz 00000000 (empty) (no description set)
+    Remaining changes: znkkpsqq a64a373e file2
+      with parent    : rlvkpnrz 3be9b575 file3
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_with_summary(&work_dir), @r"
+    @  royxmykxtrkr
+    ○  mzvwutvlkqwt file5
+    │  A file5
+    │ ○  kkmpptxzrspx file4
+    │ │  A file4
+    │ ○  znkkpsqqskkl file2
+    │ │  A file2
+    │ ○  rlvkpnrzqnoo file3
+    │ │  A file3
+    │ ○  qpvuntsmwlqt file1
+    ├─╯  A file1
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // the remaining changes can't be inserted in a cycle
+    work_dir.run_jj(["op", "restore", &setup_opid]).success();
+    let output = work_dir.run_jj([
+        "split",
+        "-m",
+        "file1",
+        "-r",
+        "qpvuntsmwlqt",
+        "--insert-remaining-after",
+        "kkmpptxzrspx",
+        "--insert-remaining-before",
+        "qpvuntsmwlqt",
+        "file1",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Refusing to create a loop: commit 4c3edca70b77 would be both an ancestor and a descendant of the remaining commit
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 enum BookmarkBehavior {
     Default,
     MoveBookmarkToChild,
@@ -1563,3 +2654,86 @@ fn test_split_with_bookmarks(bookmark_behavior: BookmarkBehavior) {
         }
     }
 }
+
+#[test]
+fn test_split_bookmark_to() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    // The flag overrides the config.
+    test_env.add_config("split.legacy-bookmark-behavior=false");
+
+    main_dir.run_jj(["desc", "-m", "first-commit"]).success();
+    main_dir.write_file("file1", "foo");
+    main_dir.write_file("file2", "foo");
+    main_dir
+        .run_jj(["bookmark", "set", "foo", "-r", "@"])
+        .success();
+    let setup_opid = main_dir.current_operation_id();
+    let write_edit_script = || {
+        std::fs::write(
+            &edit_script,
+            ["", "next invocation\n", "write\nsecond-commit"].join("\0"),
+        )
+        .unwrap();
+    };
+
+    // Sequential split, bookmarks moved to the first commit.
+    write_edit_script();
+    main_dir
+        .run_jj(["split", "file2", "--bookmark-to", "first"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&main_dir), @r"
+    @  zsuskulnrvyr false second-commit
+    ○  qpvuntsmwlqt false foo first-commit
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+
+    // Sequential split, bookmarks moved to the second commit.
+    main_dir.run_jj(["op", "restore", &setup_opid]).success();
+    write_edit_script();
+    main_dir
+        .run_jj(["split", "file2", "--bookmark-to", "second"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&main_dir), @r"
+    @  yqosqzytrlsw false foo second-commit
+    ○  qpvuntsmwlqt false first-commit
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+    let output = main_dir.run_jj(["op", "log", "-n1", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  split commit c8102db4895163944eba310c0c6750e270c5dbd4 with bookmarks moved to the second commit
+    [EOF]
+    ");
+
+    // Parallel split, bookmarks moved to the first commit.
+    main_dir.run_jj(["op", "restore", &setup_opid]).success();
+    write_edit_script();
+    main_dir
+        .run_jj(["split", "file2", "--parallel", "--bookmark-to", "first"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&main_dir), @r"
+    @  kpqxywonksrl false second-commit
+    │ ○  qpvuntsmwlqt false foo first-commit
+    ├─╯
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+
+    // Parallel split, bookmarks moved to the second commit.
+    main_dir.run_jj(["op", "restore", &setup_opid]).success();
+    write_edit_script();
+    main_dir
+        .run_jj(["split", "file2", "--parallel", "--bookmark-to", "second"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&main_dir), @r"
+    @  lylxulplsnyw false foo second-commit
+    │ ○  qpvuntsmwlqt false first-commit
+    ├─╯
+    ◆  zzzzzzzzzzzz true
+    [EOF]
+    ");
+}
@@ -315,6 +315,7 @@ fn test_squash_partial() {
     let output = work_dir.run_jj(["squash", "-r", "b", "nonexistent"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: No changes match the given paths: nonexistent
     Nothing changed.
     [EOF]
     ");
@@ -324,6 +325,7 @@ fn test_squash_partial() {
     let output = work_dir.run_jj(["squash", "b"]);
     insta::assert_snapshot!(output, @r#"
     ------- stderr -------
+    Warning: No changes match the given paths: b
     Warning: The argument "b" is being interpreted as a fileset expression. To specify a revset, pass -r "b" instead.
     Nothing changed.
     [EOF]
@@ -803,6 +805,7 @@ fn test_squash_from_to_partial() {
     let output = work_dir.run_jj(["squash", "--from", "c", "nonexistent"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: No changes match the given paths: nonexistent
     Nothing changed.
     [EOF]
     ");
@@ -1196,6 +1199,7 @@ fn test_squash_from_multiple_partial_no_op() {
     let output = work_dir.run_jj(["squash", "--from=@-+ ~ @", "--into=@", "-m=d", "a"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: No changes match the given paths: a
     Nothing changed.
     [EOF]
     ");
@@ -1211,6 +1215,212 @@ fn test_squash_from_multiple_partial_no_op() {
     ");
 }
 
+#[test]
+fn test_squash_into_other_workspace() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir.write_file("file1", "a\n");
+    main_dir.run_jj(["new"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "--name", "secondary", "../secondary"])
+        .success();
+    main_dir.write_file("file2", "b\n");
+
+    // The secondary workspace's working-copy commit is rewritten and remains
+    // checked out there
+    let output = main_dir.run_jj(["squash", "--into", "secondary@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Squashed into the working-copy commit of workspace secondary
+    Hint: Run `jj workspace update-stale` in that workspace to update its files. Changes made there since its last snapshot may conflict with the squashed changes.
+    Working copy  (@) now at: zsuskuln 7475f888 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm eb7b8a1f (no description set)
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    let output = main_dir.run_jj(["log", "-T", "working_copies"]);
+    insta::assert_snapshot!(output, @r"
+    @  default@
+    │ ○  secondary@
+    ├─╯
+    ○
+    ◆
+    [EOF]
+    ");
+
+    // The secondary workspace's files are updated by `update-stale`
+    let output = secondary_dir.run_jj(["workspace", "update-stale"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: pmmvwywv 79d80454 (no description set)
+    Parent commit (@-)      : qpvuntsm eb7b8a1f (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    Updated working copy to fresh commit 79d804541e09
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file2
+    [EOF]
+    ");
+
+    // Squashing into a commit which isn't checked out elsewhere doesn't warn
+    main_dir.write_file("file3", "c\n");
+    let output = main_dir.run_jj(["squash"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy  (@) now at: vruxwmqv 4b07a866 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm e83c9f72 (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_squash_into_other_workspace_with_unsnapshotted_changes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir.write_file("file1", "a\n");
+    main_dir.run_jj(["new"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "--name", "secondary", "../secondary"])
+        .success();
+
+    // The secondary workspace has changes which haven't been snapshotted, so
+    // its working-copy commit can't be rewritten
+    secondary_dir.write_file("file2", "b\n");
+    main_dir.write_file("file3", "c\n");
+    let output = main_dir.run_jj(["squash", "--into", "secondary@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace secondary has changes which haven't been snapshotted
+    Hint: Run a command such as `jj status` in that workspace to snapshot them, or use `--ignore-unsnapshotted-workspaces` to squash anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = main_dir.run_jj(["log", "-T", "working_copies"]);
+    insta::assert_snapshot!(output, @r"
+    @  default@
+    │ ○  secondary@
+    ├─╯
+    ○
+    ◆
+    [EOF]
+    ");
+
+    // Same for the workspace containing the repo, whose root isn't recorded
+    main_dir.write_file("file4", "d\n");
+    let output = secondary_dir.run_jj(["squash", "--into", "default@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace default has changes which haven't been snapshotted
+    Hint: Run a command such as `jj status` in that workspace to snapshot them, or use `--ignore-unsnapshotted-workspaces` to squash anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Once the changes are snapshotted, the squash succeeds
+    secondary_dir.run_jj(["status"]).success();
+    let output = main_dir.run_jj(["squash", "--into", "secondary@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Squashed into the working-copy commit of workspace secondary
+    Hint: Run `jj workspace update-stale` in that workspace to update its files. Changes made there since its last snapshot may conflict with the squashed changes.
+    Working copy  (@) now at: vruxwmqv 6ae1e732 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm eb7b8a1f (no description set)
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["workspace", "update-stale"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: pmmvwywv 4e745d03 (no description set)
+    Parent commit (@-)      : qpvuntsm eb7b8a1f (no description set)
+    Added 2 files, modified 0 files, removed 0 files
+    Updated working copy to fresh commit 4e745d03a163
+    [EOF]
+    ");
+    let output = secondary_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file2
+    file3
+    file4
+    [EOF]
+    ");
+
+    // The check can be overridden
+    secondary_dir.write_file("file5", "e\n");
+    main_dir.write_file("file6", "f\n");
+    let output = main_dir.run_jj([
+        "squash",
+        "--into",
+        "secondary@",
+        "--ignore-unsnapshotted-workspaces",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Squashed into the working-copy commit of workspace secondary
+    Hint: Run `jj workspace update-stale` in that workspace to update its files. Changes made there since its last snapshot may conflict with the squashed changes.
+    Working copy  (@) now at: kpqxywon 2de1e27c (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm eb7b8a1f (no description set)
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_squash_detect_renames() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("merge.detect-renames = true");
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "base"]).success();
+    work_dir.write_file("file", "1\n2\n3\n4\n5\n6\n7\n8\n");
+    work_dir.run_jj(["new", "-m", "rename"]).success();
+    work_dir.write_file("renamed", "1\n2\n3\n4\n5\n6\n7\n8\n");
+    work_dir.remove_file("file");
+    work_dir
+        .run_jj(["new", "-m", "modify", "description(base)"])
+        .success();
+    work_dir.write_file("file", "one\n2\n3\n4\n5\n6\n7\n8\n");
+
+    // The modification is moved into the renamed file
+    work_dir
+        .run_jj([
+            "squash",
+            "--into",
+            "description(rename)",
+            "--use-destination-message",
+        ])
+        .success();
+    let output = work_dir.run_jj(["diff", "--summary", "-r", "description(rename)"]);
+    insta::assert_snapshot!(output, @r"
+    R {file => renamed}
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "description(rename)", "renamed"]);
+    insta::assert_snapshot!(output, @r"
+    one
+    2
+    3
+    4
+    5
+    6
+    7
+    8
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(
@@ -1554,6 +1764,68 @@ fn test_squash_empty() {
     ");
 }
 
+#[test]
+fn test_squash_unmatched_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.write_file("file2", "b\n");
+
+    // A typo'd path is reported, and the other paths are still squashed
+    let output = work_dir.run_jj(["squash", "file2", "flie1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No changes match the given paths: flie1
+    Rebased 1 descendant commits
+    Working copy  (@) now at: rlvkpnrz d3cd9783 (no description set)
+    Parent commit (@-)      : qpvuntsm a5fb51d1 (no description set)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    [EOF]
+    ");
+
+    // A glob that happens to match nothing is only a warning by default
+    let output = work_dir.run_jj(["squash", "file1", "glob:*.c"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No changes match the given paths: glob:*.c
+    Working copy  (@) now at: mzvwutvl 70d80783 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 6d522ab4 (no description set)
+    [EOF]
+    ");
+
+    // With --strict-paths, nothing is squashed
+    work_dir.write_file("file1", "c\n");
+    let output = work_dir.run_jj(["squash", "--strict-paths", "file1", "flie1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes match the given paths: flie1
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    [EOF]
+    ");
+
+    // Same with the config setting
+    let output = work_dir.run_jj(["squash", "--config=ui.strict-filesets=true", "flie1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes match the given paths: flie1
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_squash_use_destination_message() {
     let test_env = TestEnvironment::default();
@@ -2016,31 +2288,31 @@ fn test_squash_to_new_commit() {
     insta::assert_snapshot!(output, @r"
     ○    xlzxqlsl test.user@example.com 2001-02-03 08:05:31 8ceb6c68
     ├─╮  file 3&4
-    │ │  -- operation dc662694ff45 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
+    │ │  -- operation fd85f8467da1 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
     │ ○  zsuskuln hidden test.user@example.com 2001-02-03 08:05:31 c7946a56
     │ │  file4
-    │ │  -- operation dc662694ff45 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
+    │ │  -- operation fd85f8467da1 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
     │ ○  zsuskuln hidden test.user@example.com 2001-02-03 08:05:11 38778966
     │ │  file4
-    │ │  -- operation 83489d186f66 commit 89a30a7539466ed176c1ef122a020fd9cb15848e
+    │ │  -- operation 9f04ccc8751b commit 89a30a7539466ed176c1ef122a020fd9cb15848e
     │ ○  zsuskuln hidden test.user@example.com 2001-02-03 08:05:11 89a30a75
     │ │  (no description set)
-    │ │  -- operation e23fd04aab50 snapshot working copy
+    │ │  -- operation 33060aab9cd3 snapshot working copy
     │ ○  zsuskuln hidden test.user@example.com 2001-02-03 08:05:10 bbf04d26
     │    (empty) (no description set)
-    │    -- operation 19d57874b952 commit c23c424826221bc4fdee9487926595324e50ee95
+    │    -- operation 4179ac4d70d7 commit c23c424826221bc4fdee9487926595324e50ee95
     ○  kkmpptxz hidden test.user@example.com 2001-02-03 08:05:31 3ab8a4a5
     │  file3
-    │  -- operation dc662694ff45 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
+    │  -- operation fd85f8467da1 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
     ○  kkmpptxz hidden test.user@example.com 2001-02-03 08:05:10 0d254956
     │  file3
-    │  -- operation 19d57874b952 commit c23c424826221bc4fdee9487926595324e50ee95
+    │  -- operation 4179ac4d70d7 commit c23c424826221bc4fdee9487926595324e50ee95
     ○  kkmpptxz hidden test.user@example.com 2001-02-03 08:05:10 c23c4248
     │  (no description set)
-    │  -- operation d19ad3734aa6 snapshot working copy
+    │  -- operation c469424b6e9a snapshot working copy
     ○  kkmpptxz hidden test.user@example.com 2001-02-03 08:05:09 c1272e87
        (empty) (no description set)
-       -- operation fdee458ae5f2 commit cb58ff1c6f1af92f827661e7275941ceb4d910c5
+       -- operation 937f3a9c6eba commit cb58ff1c6f1af92f827661e7275941ceb4d910c5
     [EOF]
     ");
 
@@ -2115,7 +2387,7 @@ fn test_squash_to_new_commit() {
     insta::assert_snapshot!(output, @r"
     ○  zowrlwsv test.user@example.com 2001-02-03 08:05:38 5feda7c2
        (empty) (no description set)
-       -- operation 8dc838fe4842 squash 0 commits
+       -- operation b4bbe125aca9 squash 0 commits
     [EOF]
     ");
 
@@ -2132,6 +2404,7 @@ fn test_squash_to_new_commit() {
     ]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
+    Warning: No changes match the given paths: no file
     Created new commit nsrwusvy c2183685 (empty) (no description set)
     Rebased 5 descendant commits
     Working copy  (@) now at: mzvwutvl cb96ecf9 (empty) (no description set)
@@ -2158,7 +2431,7 @@ fn test_squash_to_new_commit() {
     insta::assert_snapshot!(output, @r"
     ○  nsrwusvy test.user@example.com 2001-02-03 08:05:42 c2183685
        (empty) (no description set)
-       -- operation 2b93f729fd60 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
+       -- operation 13148cdbe9f1 squash commit 0d254956d33ed5bb11d93eb795c5e514aadc81b5 and 1 more
     [EOF]
     ");
 
@@ -2205,10 +2478,10 @@ fn test_squash_to_new_commit() {
     insta::assert_snapshot!(output, @r"
     ○  ukwxllxp test.user@example.com 2001-02-03 08:05:46 43a4b8e0
     │  (empty) (no description set)
-    │  -- operation ff0dd67ed609 squash commit 7eff41c8d17b8b4d2e7110402719e9d245dba975
+    │  -- operation e74c8a2e415b squash commit 7eff41c8d17b8b4d2e7110402719e9d245dba975
     ○  wtlqussy hidden test.user@example.com 2001-02-03 08:05:46 7eff41c8
        (empty) (no description set)
-       -- operation a8bb9104802c new empty commit
+       -- operation fb9d6e7514b3 new empty commit
     [EOF]
     ");
 }
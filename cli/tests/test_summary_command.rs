@@ -0,0 +1,122 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_summary() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(
+        r#"
+        templates.commit_summary = 'description.first_line()'
+        templates.log = 'description.first_line()'
+        "#,
+    );
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+
+    // With no remote, trunk() is the root commit
+    let output = work_dir.run_jj(["summary"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy  (@) : second
+    Parent commit (@-): first
+
+    @  second
+    ○  first
+    │
+    ~
+    [EOF]
+    ");
+
+    // Conflicted bookmarks are listed last
+    work_dir
+        .run_jj(["new", "--no-edit", "root()", "-m", "side"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "foo", "-r", "description(first)"])
+        .success();
+    work_dir
+        .run_jj([
+            "bookmark",
+            "create",
+            "foo",
+            "-r",
+            "description(side)",
+            "--at-op=@-",
+        ])
+        .success();
+    work_dir.run_jj(["status"]).success();
+    let output = work_dir.run_jj(["summary"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy  (@) : second
+    Parent commit (@-): first
+
+    @  second
+    ○  first
+    │
+    ~
+    Warning: These bookmarks have conflicts:
+      foo
+    Hint: Use `jj bookmark list` to see details. Use `jj bookmark set <name> -r <rev>` to resolve.
+    [EOF]
+    ");
+
+    // Can be used as the default command
+    test_env.add_config(r#"ui.default-command = "summary""#);
+    assert_eq!(work_dir.run_jj([""; 0]), output);
+}
+
+#[test]
+fn test_summary_trunk() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(
+        r#"
+        templates.commit_summary = 'description.first_line()'
+        templates.log = 'description.first_line()'
+        revset-aliases.'trunk()' = 'description(second)'
+        revset-aliases.'immutable_heads()' = 'none()'
+        "#,
+    );
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+    work_dir.run_jj(["new", "-m", "second"]).success();
+
+    // The graph is omitted if the working copy is trunk()
+    let output = work_dir.run_jj(["summary"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy  (@) : second
+    Parent commit (@-): first
+    [EOF]
+    ");
+
+    // Only the revisions after trunk() are shown
+    work_dir.run_jj(["new", "-m", "third"]).success();
+    work_dir.run_jj(["new", "-m", "fourth"]).success();
+    let output = work_dir.run_jj(["summary"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy  (@) : fourth
+    Parent commit (@-): third
+
+    @  fourth
+    ○  third
+    │
+    ~
+    [EOF]
+    ");
+}
@@ -363,25 +363,25 @@ fn test_new_change_id() {
     insta::assert_snapshot!(work_dir.run_jj(["evolog", "-r", "yqosqzytrlswkspswpqrmlplxylrzsnz"]), @r"
     ○  yqosqzyt test.user@example.com 2001-02-03 08:05:13 b 01d6741e
     │  (no description set)
-    │  -- operation c1ee90a05107 touch commit 75591b1896b4990e7695701fd7cdbb32dba3ff50
+    │  -- operation 4568afdd2b56 touch commit 75591b1896b4990e7695701fd7cdbb32dba3ff50
     ○  kkmpptxz hidden test.user@example.com 2001-02-03 08:05:11 75591b18
     │  (no description set)
-    │  -- operation 4b33c26502f8 snapshot working copy
+    │  -- operation e9db910d8c2d snapshot working copy
     ○  kkmpptxz hidden test.user@example.com 2001-02-03 08:05:09 acebf2bd
        (empty) (no description set)
-       -- operation 686c6e44c08d new empty commit
+       -- operation ddcd3062bbb3 new empty commit
     [EOF]
     ");
     insta::assert_snapshot!(work_dir.run_jj(["evolog", "-r", "mzvwut"]), @r"
     @  mzvwutvl test.user@example.com 2001-02-03 08:05:13 c 0c3fe2d8
     │  (no description set)
-    │  -- operation c1ee90a05107 touch commit 75591b1896b4990e7695701fd7cdbb32dba3ff50
+    │  -- operation 4568afdd2b56 touch commit 75591b1896b4990e7695701fd7cdbb32dba3ff50
     ○  mzvwutvl hidden test.user@example.com 2001-02-03 08:05:13 22be6c4e
     │  (no description set)
-    │  -- operation 6cd93c8c2f48 snapshot working copy
+    │  -- operation 8d2d7f61f8ec snapshot working copy
     ○  mzvwutvl hidden test.user@example.com 2001-02-03 08:05:11 b9f5490a
        (empty) (no description set)
-       -- operation e3fbc5040416 new empty commit
+       -- operation af58948316dd new empty commit
     [EOF]
     ");
 }
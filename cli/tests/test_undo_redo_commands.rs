@@ -124,15 +124,15 @@ fn test_undo_with_rev_arg_falls_back_to_revert() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Warning: `jj undo <operation>` is deprecated; use `jj op revert <operation>` instead
-    Reverted operation: 8f47435a3990 (2001-02-03 08:05:07) add workspace 'default'
+    Reverted operation: 0548605fd744 (2001-02-03 08:05:07) add workspace 'default'
     Rebased 1 descendant commits
     [EOF]
     ");
 
     let output = work_dir.run_jj(["op", "log", "-n1"]);
     insta::assert_snapshot!(output, @r"
-    @  20c0ef5cef23 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
-    │  revert operation 8f47435a3990362feaf967ca6de2eb0a31c8b883dfcb66fba5c22200d12bbe61e3dc8bc855f1f6879285fcafaf85ac792f9a43bcc36e57d28737d18347d5e752
+    @  69ce6afca016 test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
+    │  revert operation 0548605fd74446e9ceea95e026f79a393b163a910572400113028583e288ad98729e2e59b266b254a78e42fddb4afd930974b69f6014eefe794fa461248d60c5
     │  args: jj undo @-
     [EOF]
     ");
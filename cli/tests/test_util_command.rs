@@ -14,6 +14,7 @@
 
 use insta::assert_snapshot;
 
+use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 
 #[test]
@@ -96,13 +97,150 @@ fn test_gc_operation_log() {
     ------- stderr -------
     Internal error: Failed to load an operation
     Caused by:
-    1: Object b50d0a8f111a9d30d45d429d62c8e54016cc7c891706921a6493756c8074e883671cf3dac0ac9f94ef0fa8c79738a3dfe38c3e1f6c5e1a4a4d0857d266ef2040 of type operation not found
-    2: Cannot access $TEST_ENV/repo/.jj/repo/op_store/operations/b50d0a8f111a9d30d45d429d62c8e54016cc7c891706921a6493756c8074e883671cf3dac0ac9f94ef0fa8c79738a3dfe38c3e1f6c5e1a4a4d0857d266ef2040
+    1: Object 5e999b40bcfbbe8915fd4197c25be0029bb0fcaa8936324c1ddec5a4aa5e0e6b703d4037e201b8d6d293c7e1c94c3ee02976f2a237b123a6fde5281c29445271 of type operation not found
+    2: Cannot access $TEST_ENV/repo/.jj/repo/op_store/operations/5e999b40bcfbbe8915fd4197c25be0029bb0fcaa8936324c1ddec5a4aa5e0e6b703d4037e201b8d6d293c7e1c94c3ee02976f2a237b123a6fde5281c29445271
     [EOF]
     [exit status: 255]
     ");
 }
 
+#[test]
+fn test_gc_auto() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config("gc.auto = true\ngc.auto-interval = 0");
+    let ran_gc = |output: &CommandOutput| {
+        output
+            .stderr
+            .raw()
+            .contains("Ran automatic garbage collection")
+    };
+
+    // Read-only commands don't trigger garbage collection.
+    let output = work_dir.run_jj(["log"]).success();
+    assert!(!ran_gc(&output));
+    assert!(!work_dir.root().join(".jj/repo/last_auto_gc").exists());
+
+    // Garbage collection runs after a command creating an operation, but not
+    // with --ignore-working-copy. Operations older than two weeks (all of
+    // them in tests) are abandoned except for the current one.
+    work_dir
+        .run_jj_with(|cmd| {
+            cmd.args(["describe", "-m", "first"])
+                .env("JJ_NO_AUTO_GC", "1")
+        })
+        .success();
+    let op_log_template = r#"description ++ "\n""#;
+    let output = work_dir.run_jj(["op", "log", "--no-graph", "-T", op_log_template]);
+    insta::assert_snapshot!(output, @r"
+    describe commit e8849ae12c709f2321908879bc724fdb2ab8a781
+    add workspace 'default'
+
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["new"]).success();
+    assert!(ran_gc(&output));
+    assert!(work_dir.root().join(".jj/repo/last_auto_gc").exists());
+    let output = work_dir.run_jj(["op", "log", "--no-graph", "-T", op_log_template]);
+    insta::assert_snapshot!(output, @r"
+    new empty commit
+
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  first
+    ◆
+    [EOF]
+    ");
+    let output = work_dir
+        .run_jj(["bookmark", "create", "-r@-", "b1", "--ignore-working-copy"])
+        .success();
+    assert!(!ran_gc(&output));
+
+    // It can be skipped by an environment variable.
+    let output = work_dir
+        .run_jj_with(|cmd| {
+            cmd.args(["bookmark", "create", "-r@-", "b2"])
+                .env("JJ_NO_AUTO_GC", "1")
+        })
+        .success();
+    assert!(!ran_gc(&output));
+
+    // Garbage collection runs again since the interval is zero.
+    let output = work_dir.run_jj(["new"]).success();
+    assert!(ran_gc(&output));
+}
+
+#[test]
+fn test_gc_auto_interval() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config("gc.auto = true");
+    let ran_gc = |output: &CommandOutput| {
+        output
+            .stderr
+            .raw()
+            .contains("Ran automatic garbage collection")
+    };
+
+    // Only the first of two operations runs garbage collection.
+    let output = work_dir.run_jj(["new"]).success();
+    assert!(ran_gc(&output));
+    let output = work_dir.run_jj(["new"]).success();
+    assert!(!ran_gc(&output));
+
+    let output = work_dir.run_jj(["util", "gc", "--auto"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing to do, garbage collection ran recently.
+    [EOF]
+    ");
+
+    // Automatic garbage collection is disabled by default, but --auto still
+    // honors the interval.
+    let output = work_dir.run_jj(["util", "gc", "--auto", "--config=gc.auto=false"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing to do, garbage collection ran recently.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["util", "gc", "--auto", "--config=gc.auto-interval=0"]);
+    insta::assert_snapshot!(output, @"");
+
+    let output = work_dir.run_jj(["util", "gc", "--auto", "--expire=now"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--auto' cannot be used with '--expire <EXPIRE>'
+
+    Usage: jj util gc --auto
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_gc_auto_invalid_interval() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config("gc.auto = true\ngc.auto-interval = \"soon\"");
+
+    // The command succeeds despite the failure.
+    let output = work_dir.run_jj(["bookmark", "create", "-r@", "b"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created 1 bookmarks pointing to qpvuntsm e8849ae1 b | (empty) (no description set)
+    Warning: Automatic garbage collection failed: Invalid type or value for gc.auto-interval
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_shell_completions() {
     #[track_caller]
@@ -507,7 +507,7 @@ fn test_workspaces_conflicting_edits() {
     secondary_dir.write_file("file", "changed in second\n");
     // Squash the changes from the main workspace into the initial commit (before
     // running any command in the secondary workspace
-    let output = main_dir.run_jj(["squash"]);
+    let output = main_dir.run_jj(["squash", "--ignore-unsnapshotted-workspaces"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Rebased 1 descendant commits
@@ -528,7 +528,7 @@ fn test_workspaces_conflicting_edits() {
     let output = secondary_dir.run_jj(["st"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Error: The working copy is stale (not updated since operation bd4f780d0422).
+    Error: The working copy is stale (not updated since operation e564b31d34ea).
     Hint: Run `jj workspace update-stale` to update it.
     See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy for more information.
     [EOF]
@@ -538,7 +538,7 @@ fn test_workspaces_conflicting_edits() {
     let output = secondary_dir.run_jj(["log"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Error: The working copy is stale (not updated since operation bd4f780d0422).
+    Error: The working copy is stale (not updated since operation e564b31d34ea).
     Hint: Run `jj workspace update-stale` to update it.
     See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy for more information.
     [EOF]
@@ -629,7 +629,7 @@ fn test_workspaces_updated_by_other() {
     let output = secondary_dir.run_jj(["st"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Error: The working copy is stale (not updated since operation bd4f780d0422).
+    Error: The working copy is stale (not updated since operation e564b31d34ea).
     Hint: Run `jj workspace update-stale` to update it.
     See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy for more information.
     [EOF]
@@ -779,14 +779,14 @@ fn test_workspaces_current_op_discarded_by_other(automatic: bool) {
     ]);
     insta::allow_duplicates! {
         insta::assert_snapshot!(output, @r"
-        @  b0789def13 abandon commit de90575a14d8b9198dc0930f9de4a69f846ded36
-        ○  778c9aae54 create initial working-copy commit in workspace secondary
-        ○  219d4aca5c add workspace 'secondary'
-        ○  31ad55e98c new empty commit
-        ○  4ba7680cbe snapshot working copy
-        ○  9739176f19 new empty commit
-        ○  4b5baa44b7 snapshot working copy
-        ○  8f47435a39 add workspace 'default'
+        @  bde4f03d4f abandon commit de90575a14d8b9198dc0930f9de4a69f846ded36
+        ○  50c2b1d92c create initial working-copy commit in workspace secondary
+        ○  39019ed2d6 add workspace 'secondary'
+        ○  8771d238d5 new empty commit
+        ○  e9f886797a snapshot working copy
+        ○  b4575c48d4 new empty commit
+        ○  ab98be46ab snapshot working copy
+        ○  0548605fd7 add workspace 'default'
         ○  0000000000
         [EOF]
         ");
@@ -821,7 +821,7 @@ fn test_workspaces_current_op_discarded_by_other(automatic: bool) {
         Parent commit (@-): rzvqmyuk 891f0006 (empty) (no description set)
         [EOF]
         ------- stderr -------
-        Failed to read working copy's current operation; attempting recovery. Error message from read attempt: Object 778c9aae54957e842bede2223fda227be33e08061732276a4cfb7b431a3e146e5c62187d640aa883095d3b2c6cf43d31ad5fde72076bb9a88b8594fb8b5e6606 of type operation not found
+        Failed to read working copy's current operation; attempting recovery. Error message from read attempt: Object 50c2b1d92c18f74147d2aa9bc48df0dafd1186e37b6632fc89404c03cdf255c66415f34009b1a0090c915b02c02fd07ba98ee55353719f1842b95095dc329252 of type operation not found
         Created and checked out recovery commit 866928d1e0fd
         [EOF]
         ");
@@ -839,7 +839,7 @@ fn test_workspaces_current_op_discarded_by_other(automatic: bool) {
         let output = secondary_dir.run_jj(["workspace", "update-stale"]);
         insta::assert_snapshot!(output, @r"
         ------- stderr -------
-        Failed to read working copy's current operation; attempting recovery. Error message from read attempt: Object 778c9aae54957e842bede2223fda227be33e08061732276a4cfb7b431a3e146e5c62187d640aa883095d3b2c6cf43d31ad5fde72076bb9a88b8594fb8b5e6606 of type operation not found
+        Failed to read working copy's current operation; attempting recovery. Error message from read attempt: Object 50c2b1d92c18f74147d2aa9bc48df0dafd1186e37b6632fc89404c03cdf255c66415f34009b1a0090c915b02c02fd07ba98ee55353719f1842b95095dc329252 of type operation not found
         Created and checked out recovery commit 866928d1e0fd
         [EOF]
         ");
@@ -890,25 +890,61 @@ fn test_workspaces_current_op_discarded_by_other(automatic: bool) {
         insta::assert_snapshot!(output, @r"
         @  kmkuslsw test.user@example.com 2001-02-03 08:05:18 secondary@ 18851b39
         │  RECOVERY COMMIT FROM `jj workspace update-stale`
-        │  -- operation 0a26da4b0149 snapshot working copy
+        │  -- operation 9576a4adfb4d snapshot working copy
         ○  kmkuslsw hidden test.user@example.com 2001-02-03 08:05:18 866928d1
            (empty) RECOVERY COMMIT FROM `jj workspace update-stale`
-           -- operation 83f707034db1 recovery commit
+           -- operation 1a22b912a5a2 recovery commit
         [EOF]
         ");
     } else {
         insta::assert_snapshot!(output, @r"
         @  kmkuslsw test.user@example.com 2001-02-03 08:05:18 secondary@ 18851b39
         │  RECOVERY COMMIT FROM `jj workspace update-stale`
-        │  -- operation 0f876590219e snapshot working copy
+        │  -- operation 0d31dd3178b8 snapshot working copy
         ○  kmkuslsw hidden test.user@example.com 2001-02-03 08:05:18 866928d1
            (empty) RECOVERY COMMIT FROM `jj workspace update-stale`
-           -- operation 83f707034db1 recovery commit
+           -- operation 1a22b912a5a2 recovery commit
         [EOF]
         ");
     }
 }
 
+#[test]
+fn test_workspaces_undo_warns_about_other_workspaces() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+    main_dir.write_file("file", "contents\n");
+    main_dir.run_jj(["new"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "../secondary"])
+        .success();
+
+    // Undoing an operation in the default workspace that moved the
+    // secondary workspace's working-copy commit
+    secondary_dir.run_jj(["new"]).success();
+    let output = main_dir.run_jj(["undo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: e564b31d34ea (2001-02-03 08:05:09) create initial working-copy commit in workspace secondary
+    Warning: The working-copy commits of other workspaces will be changed:
+      secondary
+    Hint: Run `jj workspace update-stale` in those workspaces to update their working copies.
+    [EOF]
+    ");
+
+    // Restoring to an operation before the secondary workspace was added
+    let output = main_dir.run_jj(["op", "restore", "@----"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: f30120a500c9 (2001-02-03 08:05:08) new empty commit
+    Warning: The working-copy commits of other workspaces will be changed:
+      secondary (forgotten)
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_workspaces_update_stale_noop() {
     let test_env = TestEnvironment::default();
@@ -1082,7 +1118,7 @@ fn test_workspaces_forget_multi_transaction() {
     // the op log should have multiple workspaces forgotten in a single tx
     let output = main_dir.run_jj(["op", "log", "--limit", "1"]);
     insta::assert_snapshot!(output, @r"
-    @  d3aded9a10b6 test-username@host.example.com 2001-02-03 04:05:12.000 +07:00 - 2001-02-03 04:05:12.000 +07:00
+    @  5623c5cfdbaa test-username@host.example.com 2001-02-03 04:05:12.000 +07:00 - 2001-02-03 04:05:12.000 +07:00
     │  forget workspaces second, third
     │  args: jj workspace forget second third
     [EOF]
@@ -1215,6 +1251,117 @@ fn test_list_workspaces_template() {
     ");
 }
 
+/// Test workspace descriptions given by `jj workspace add --message`
+#[test]
+fn test_workspaces_add_with_description() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    let secondary_dir = test_env.work_dir("secondary");
+
+    main_dir
+        .run_jj([
+            "workspace",
+            "add",
+            "--name=second",
+            "-m=bisecting flaky test",
+            "../secondary",
+        ])
+        .success();
+    main_dir
+        .run_jj(["workspace", "add", "--name=third", "../third"])
+        .success();
+    let output = main_dir.run_jj(["workspace", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default: qpvuntsm e8849ae1 (empty) (no description set)
+    second: uuqppmxq 94f41578 (empty) (no description set)
+      bisecting flaky test
+    third: pmmvwywv 337ba39f (empty) (no description set)
+    [EOF]
+    ");
+    let template = r#"name ++ ": " ++ description ++ "\n""#;
+    let output = main_dir.run_jj(["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    default: 
+    second: bisecting flaky test
+    third: 
+    [EOF]
+    ");
+
+    // The description follows the renamed workspace
+    secondary_dir
+        .run_jj(["workspace", "rename", "bisect"])
+        .success();
+    let output = main_dir.run_jj(["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    bisect: bisecting flaky test
+    default: 
+    third: 
+    [EOF]
+    ");
+
+    // Forgetting the workspace removes its description
+    main_dir.run_jj(["workspace", "forget", "bisect"]).success();
+    main_dir
+        .run_jj(["workspace", "add", "--name=bisect", "../bisect"])
+        .success();
+    let output = main_dir.run_jj(["workspace", "list", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    bisect: 
+    default: 
+    third: 
+    [EOF]
+    ");
+}
+
+/// Test generating workspace names by `jj workspace add --name-template`
+#[test]
+fn test_workspaces_add_with_name_template() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+
+    let output = main_dir.run_jj([
+        "workspace",
+        "add",
+        "--name-template",
+        r#"'ci-' ++ email.local() ++ '-' ++ timestamp.format('%Y%m%d')"#,
+        "../ci",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Created workspace in "../ci"
+    Working copy  (@) now at: uuqppmxq 94f41578 (empty) (no description set)
+    Parent commit (@-)      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    "#);
+    let output = main_dir.run_jj(["workspace", "list", "-T", r#"name ++ "\n""#]);
+    insta::assert_snapshot!(output, @r"
+    ci-test.user-20010203
+    default
+    [EOF]
+    ");
+
+    // Generated names are validated
+    let output = main_dir.run_jj(["workspace", "add", "--name-template", "''", "../empty"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace name cannot be empty
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = main_dir.run_jj(["workspace", "add", "--name", "a\tb", "../tab"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Workspace name cannot contain control characters: "a\tb"
+    [EOF]
+    [exit status: 1]
+    "#);
+    // No directory is left behind
+    assert!(!test_env.env_root().join("empty").exists());
+    assert!(!test_env.env_root().join("tab").exists());
+}
+
 /// Test getting the workspace root from primary and secondary workspaces
 #[test]
 fn test_workspaces_root() {
@@ -1261,10 +1408,10 @@ fn test_debug_snapshot() {
     work_dir.run_jj(["debug", "snapshot"]).success();
     let output = work_dir.run_jj(["op", "log"]);
     insta::assert_snapshot!(output, @r"
-    @  594dfebf2565 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    @  837c2a5da44e test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
     │  snapshot working copy
     │  args: jj debug snapshot
-    ○  8f47435a3990 test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
+    ○  0548605fd744 test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
     │  add workspace 'default'
     ○  000000000000 root()
     [EOF]
@@ -1272,13 +1419,13 @@ fn test_debug_snapshot() {
     work_dir.run_jj(["describe", "-m", "initial"]).success();
     let output = work_dir.run_jj(["op", "log"]);
     insta::assert_snapshot!(output, @r"
-    @  81e4a0f2e793 test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    @  302701d9e631 test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
     │  describe commit 006bd1130b84e90ab082adeabd7409270d5a86da
     │  args: jj describe -m initial
-    ○  594dfebf2565 test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
+    ○  837c2a5da44e test-username@host.example.com 2001-02-03 04:05:08.000 +07:00 - 2001-02-03 04:05:08.000 +07:00
     │  snapshot working copy
     │  args: jj debug snapshot
-    ○  8f47435a3990 test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
+    ○  0548605fd744 test-username@host.example.com 2001-02-03 04:05:07.000 +07:00 - 2001-02-03 04:05:07.000 +07:00
     │  add workspace 'default'
     ○  000000000000 root()
     [EOF]
@@ -26,6 +26,7 @@ use std::rc::Rc;
 
 use bstr::BStr;
 use bstr::BString;
+use futures::TryStreamExt as _;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
 
@@ -146,10 +147,11 @@ impl FileAnnotation {
 }
 
 /// Annotation process for a specific file.
+///
+/// The file is followed across renames detected by the backend. Lines edited
+/// while renaming are attributed to the renaming commit.
 #[derive(Clone, Debug)]
 pub struct FileAnnotator {
-    // If we add copy-tracing support, file_path might be tracked by state.
-    file_path: RepoPathBuf,
     starting_text: BString,
     state: AnnotationState,
 }
@@ -160,7 +162,7 @@ impl FileAnnotator {
     /// If the file is not found, the result would be empty.
     pub fn from_commit(starting_commit: &Commit, file_path: &RepoPath) -> BackendResult<Self> {
         let source = Source::load(starting_commit, file_path)?;
-        Ok(Self::with_source(starting_commit.id(), file_path, source))
+        Ok(Self::with_source(starting_commit.id(), source))
     }
 
     /// Initializes annotator for a specific file path starting with the given
@@ -174,15 +176,11 @@ impl FileAnnotator {
         file_path: &RepoPath,
         starting_text: impl Into<Vec<u8>>,
     ) -> Self {
-        let source = Source::new(BString::new(starting_text.into()));
-        Self::with_source(starting_commit_id, file_path, source)
+        let source = Source::new(file_path.to_owned(), BString::new(starting_text.into()));
+        Self::with_source(starting_commit_id, source)
     }
 
-    fn with_source(
-        starting_commit_id: &CommitId,
-        file_path: &RepoPath,
-        mut source: Source,
-    ) -> Self {
+    fn with_source(starting_commit_id: &CommitId, mut source: Source) -> Self {
         source.fill_line_map();
         let starting_text = source.text.clone();
         let state = AnnotationState {
@@ -198,7 +196,6 @@ impl FileAnnotator {
             num_unresolved_roots: 0,
         };
         Self {
-            file_path: file_path.to_owned(),
             starting_text,
             state,
         }
@@ -207,14 +204,15 @@ impl FileAnnotator {
     /// Computes line-by-line annotation within the `domain`.
     ///
     /// The `domain` expression narrows the range of ancestors to search. It
-    /// will be intersected as `domain & ::pending_commits & files(file_path)`.
+    /// will be intersected as `domain & ::pending_commits & files(file_path)`,
+    /// where `file_path` also includes the paths the file was renamed from.
     /// The `pending_commits` is assumed to be included in the `domain`.
     pub fn compute(
         &mut self,
         repo: &dyn Repo,
         domain: &Rc<ResolvedRevsetExpression>,
     ) -> Result<(), RevsetEvaluationError> {
-        process_commits(repo, &mut self.state, domain, &self.file_path)
+        process_commits(repo, &mut self.state, domain)
     }
 
     /// Remaining commit ids to visit from.
@@ -247,6 +245,8 @@ struct AnnotationState {
 /// Line mapping and file content at a certain commit.
 #[derive(Clone, Debug)]
 struct Source {
+    /// Path of the file at the current commit.
+    path: RepoPathBuf,
     /// Mapping of line numbers in the file at the current commit to the
     /// starting file, sorted by the line numbers at the current commit.
     line_map: Vec<(usize, usize)>,
@@ -255,8 +255,9 @@ struct Source {
 }
 
 impl Source {
-    fn new(text: BString) -> Self {
+    fn new(path: RepoPathBuf, text: BString) -> Self {
         Self {
+            path,
             line_map: Vec::new(),
             text,
         }
@@ -265,7 +266,7 @@ impl Source {
     fn load(commit: &Commit, file_path: &RepoPath) -> Result<Self, BackendError> {
         let tree = commit.tree()?;
         let text = get_file_contents(commit.store(), file_path, &tree).block_on()?;
-        Ok(Self::new(text))
+        Ok(Self::new(file_path.to_owned(), text))
     }
 
     fn fill_line_map(&mut self) {
@@ -289,48 +290,128 @@ pub struct LineOrigin {
 
 /// Starting from the source commits, compute changes at that commit relative to
 /// its direct parents, updating the mappings as we go.
+///
+/// When a commit turns out to have renamed the file, the commits are queried
+/// again with the source path of the rename.
 fn process_commits(
     repo: &dyn Repo,
     state: &mut AnnotationState,
     domain: &Rc<ResolvedRevsetExpression>,
-    file_name: &RepoPath,
 ) -> Result<(), RevsetEvaluationError> {
-    let predicate = RevsetFilterPredicate::File(FilesetExpression::file_path(file_name.to_owned()));
-    // TODO: If the domain isn't a contiguous range, changes masked out by it
-    // might not be caught by the closest ancestor revision. For example,
-    // domain=merges() would pick up almost nothing because merge revisions
-    // are usually empty. Perhaps, we want to query `files(file_path,
-    // within_sub_graph=domain)`, not `domain & files(file_path)`.
-    let heads = RevsetExpression::commits(state.commit_source_map.keys().cloned().collect());
-    let revset = heads
-        .union(&domain.intersection(&heads.ancestors()).filtered(predicate))
-        .evaluate(repo)?;
-
+    let domain_revset = domain.clone().evaluate(repo)?;
+    let in_domain = domain_revset.containing_fn();
+    let mut head_ids = state.commit_source_map.keys().cloned().collect_vec();
     state.num_unresolved_roots = 0;
-    for node in revset.iter_graph() {
-        let (commit_id, edge_list) = node?;
-        process_commit(repo, file_name, state, &commit_id, &edge_list)?;
-        if state.commit_source_map.len() == state.num_unresolved_roots {
-            // No more lines to propagate to ancestors.
-            break;
+    'query: loop {
+        let file_paths = state
+            .commit_source_map
+            .values()
+            .map(|source| source.path.clone())
+            .unique()
+            .map(FilesetExpression::file_path)
+            .collect();
+        let predicate = RevsetFilterPredicate::File(FilesetExpression::union_all(file_paths));
+        // TODO: If the domain isn't a contiguous range, changes masked out by
+        // it might not be caught by the closest ancestor revision. For example,
+        // domain=merges() would pick up almost nothing because merge revisions
+        // are usually empty. Perhaps, we want to query `files(file_path,
+        // within_sub_graph=domain)`, not `domain & files(file_path)`.
+        let heads = RevsetExpression::commits(head_ids);
+        let revset = heads
+            .union(&domain.intersection(&heads.ancestors()).filtered(predicate))
+            .evaluate(repo)?;
+
+        for node in revset.iter_graph() {
+            let (commit_id, edge_list) = node?;
+            if let Some((commit, copy_source)) = find_copy_source(repo, state, &commit_id)? {
+                // The ancestors of the parents weren't queried with the
+                // source path, so compare against the direct parents and
+                // start over.
+                let edge_list: Vec<_> = commit
+                    .parent_ids()
+                    .iter()
+                    .map(|parent_id| {
+                        Ok(if in_domain(parent_id)? {
+                            GraphEdge::direct(parent_id.clone())
+                        } else {
+                            GraphEdge::missing(parent_id.clone())
+                        })
+                    })
+                    .try_collect::<_, _, RevsetEvaluationError>()?;
+                process_commit(repo, state, &commit_id, &edge_list, Some(&copy_source))?;
+                // Unresolved roots are outside of the domain, and shouldn't
+                // be visited again.
+                head_ids = Vec::new();
+                state.num_unresolved_roots = 0;
+                for id in state.commit_source_map.keys() {
+                    if in_domain(id)? {
+                        head_ids.push(id.clone());
+                    } else {
+                        state.num_unresolved_roots += 1;
+                    }
+                }
+                continue 'query;
+            }
+            process_commit(repo, state, &commit_id, &edge_list, None)?;
+            if state.commit_source_map.len() == state.num_unresolved_roots {
+                // No more lines to propagate to ancestors.
+                break;
+            }
+        }
+        return Ok(());
+    }
+}
+
+/// Returns the commit and the path the file was renamed or copied from if the
+/// file doesn't exist in any parent of the commit.
+fn find_copy_source(
+    repo: &dyn Repo,
+    state: &AnnotationState,
+    commit_id: &CommitId,
+) -> BackendResult<Option<(Commit, RepoPathBuf)>> {
+    let Some(source) = state.commit_source_map.get(commit_id) else {
+        return Ok(None);
+    };
+    let commit = repo.store().get_commit(commit_id)?;
+    let mut parent_ids = Vec::new();
+    for parent in commit.parents() {
+        let parent = parent?;
+        if parent.tree()?.path_value(&source.path)?.is_present() {
+            return Ok(None);
         }
+        parent_ids.push(parent.id().clone());
     }
-    Ok(())
+    let paths = [source.path.clone()];
+    for parent_id in &parent_ids {
+        let records: Vec<_> = repo
+            .store()
+            .get_copy_records(Some(&paths), parent_id, commit_id)?
+            .try_collect()
+            .block_on()?;
+        if let Some(record) = records.into_iter().find(|r| r.target == source.path) {
+            return Ok(Some((commit, record.source)));
+        }
+    }
+    Ok(None)
 }
 
 /// For a given commit, for each parent, we compare the version in the parent
 /// tree with the current version, updating the mappings for any lines in
 /// common. If the parent doesn't have the file, we skip it.
+///
+/// If `copy_source` is specified, the file is looked up by that path in the
+/// parents.
 fn process_commit(
     repo: &dyn Repo,
-    file_name: &RepoPath,
     state: &mut AnnotationState,
     current_commit_id: &CommitId,
     edges: &[GraphEdge<CommitId>],
+    copy_source: Option<&RepoPath>,
 ) -> Result<(), BackendError> {
     let Some(mut current_source) = state.commit_source_map.remove(current_commit_id) else {
         return Ok(());
     };
+    let parent_path = copy_source.unwrap_or(&current_source.path).to_owned();
 
     for parent_edge in edges {
         let parent_commit_id = &parent_edge.target;
@@ -338,7 +419,7 @@ fn process_commit(
             hash_map::Entry::Occupied(entry) => entry.into_mut(),
             hash_map::Entry::Vacant(entry) => {
                 let commit = repo.store().get_commit(entry.key())?;
-                entry.insert(Source::load(&commit, file_name)?)
+                entry.insert(Source::load(&commit, &parent_path)?)
             }
         };
 
@@ -38,6 +38,7 @@ use crate::backend::FileId;
 use crate::backend::SymlinkId;
 use crate::backend::TreeId;
 use crate::backend::TreeValue;
+use crate::content_hash::ContentHash;
 use crate::copies::CopiesTreeDiffEntry;
 use crate::copies::CopiesTreeDiffEntryPath;
 use crate::diff::Diff;
@@ -284,7 +285,7 @@ pub fn resolve_file_executable(merge: &Merge<Option<bool>>) -> Option<bool> {
 }
 
 /// Describes what style should be used when materializing conflicts.
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, ContentHash, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ConflictMarkerStyle {
     /// Style which shows a snapshot and a series of diffs to apply.
@@ -447,6 +448,21 @@ pub fn materialize_merge_result_to_bytes<T: AsRef<[u8]>>(
     }
 }
 
+/// Like [`materialize_merge_result_to_bytes()`], but reuses the text
+/// materialized earlier for the same file ids and marker style.
+pub fn materialize_file_conflict_to_bytes(
+    store: &Store,
+    file: &MaterializedFileConflictValue,
+    conflict_marker_style: ConflictMarkerStyle,
+) -> BString {
+    if let Some(text) = store.get_cached_materialized_conflict(&file.ids, conflict_marker_style) {
+        return text;
+    }
+    let text = materialize_merge_result_to_bytes(&file.contents, conflict_marker_style);
+    store.cache_materialized_conflict(file.ids.clone(), conflict_marker_style, text.clone());
+    text
+}
+
 pub fn materialize_merge_result_to_bytes_with_marker_len<T: AsRef<[u8]>>(
     single_hunk: &Merge<T>,
     conflict_marker_style: ConflictMarkerStyle,
@@ -16,19 +16,25 @@
 
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 use std::task::ready;
 
 use futures::Stream;
+use futures::StreamExt as _;
 
 use crate::backend::BackendResult;
+use crate::backend::CommitId;
 use crate::backend::CopyRecord;
+use crate::merge::Merge;
 use crate::merge::MergedTreeValue;
 use crate::merged_tree::MergedTree;
+use crate::merged_tree::MergedTreeBuilder;
 use crate::merged_tree::TreeDiffStream;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
+use crate::store::Store;
 
 /// A collection of CopyRecords.
 #[derive(Default, Debug)]
@@ -215,3 +221,93 @@ impl Stream for CopiesTreeDiffStream<'_> {
         Poll::Ready(None)
     }
 }
+
+async fn read_copy_records(
+    store: &Arc<Store>,
+    root: &CommitId,
+    head: &CommitId,
+) -> BackendResult<CopyRecords> {
+    let records: Vec<_> = store.get_copy_records(None, root, head)?.collect().await;
+    let mut copy_records = CopyRecords::default();
+    copy_records.add_records(records)?;
+    Ok(copy_records)
+}
+
+/// Merges `side1` and `side2` relative to `base` like
+/// [`MergedTree::merge_with_merge_drivers()`], but follows the renames the backend detects between the `base` commit and
+/// the commit of each side.
+///
+/// If one side renamed a file and the other side modified it, the modification
+/// is merged into the renamed file. Files with several rename candidates, or
+/// renamed on both sides, are merged as if no renames were detected. The trees
+/// don't have to be the trees of the given commits; renames that don't apply
+/// to the trees are ignored.
+pub async fn merge_trees_following_renames(
+    store: &Arc<Store>,
+    base_id: &CommitId,
+    base: MergedTree,
+    side1_id: &CommitId,
+    side1: MergedTree,
+    side2_id: &CommitId,
+    side2: MergedTree,
+) -> BackendResult<MergedTree> {
+    let records1 = read_copy_records(store, base_id, side1_id).await?;
+    let records2 = read_copy_records(store, base_id, side2_id).await?;
+    let mut base_builder = MergedTreeBuilder::new(base.id());
+    let mut side1_builder = MergedTreeBuilder::new(side1.id());
+    let mut side2_builder = MergedTreeBuilder::new(side2.id());
+    let mut num_moved = 0;
+    // Move the file to its new name in the base and in the side that didn't
+    // rename it, so the regular merge sees the modification at the new name.
+    for (records, renamed, other_records, other, other_builder) in [
+        (&records1, &side1, &records2, &side2, &mut side2_builder),
+        (&records2, &side2, &records1, &side1, &mut side1_builder),
+    ] {
+        for record in records.iter() {
+            let (source, target) = (&record.source, &record.target);
+            let is_unambiguous = records.for_source(source) == Some(record)
+                && records.for_target(target) == Some(record);
+            if !is_unambiguous || other_records.has_source(source) {
+                continue;
+            }
+            let base_value = base.path_value_async(source).await?;
+            let other_value = other.path_value_async(source).await?;
+            if !base_value.is_resolved()
+                || !other_value.is_resolved()
+                || base_value.is_absent()
+                || other_value.is_absent()
+                || other_value.is_tree()
+                || other_value == base_value
+            {
+                // Nothing to carry over to the new name.
+                continue;
+            }
+            if renamed.path_value_async(source).await?.is_present()
+                || renamed.path_value_async(target).await?.is_absent()
+                || base.path_value_async(target).await?.is_present()
+                || other.path_value_async(target).await?.is_present()
+            {
+                // Not a rename between these trees.
+                continue;
+            }
+            base_builder.set_or_remove(source.clone(), Merge::absent());
+            base_builder.set_or_remove(target.clone(), base_value);
+            other_builder.set_or_remove(source.clone(), Merge::absent());
+            other_builder.set_or_remove(target.clone(), other_value);
+            num_moved += 1;
+        }
+    }
+    if num_moved == 0 {
+        return side1.merge_with_merge_drivers(base, side2).await;
+    }
+    let base = store
+        .get_root_tree_async(&base_builder.write_tree(store)?)
+        .await?;
+    let side1 = store
+        .get_root_tree_async(&side1_builder.write_tree(store)?)
+        .await?;
+    let side2 = store
+        .get_root_tree_async(&side2_builder.write_tree(store)?)
+        .await?;
+    side1.merge_with_merge_drivers(base, side2).await
+}
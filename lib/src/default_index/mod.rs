@@ -43,6 +43,7 @@ pub use self::readonly::ReadonlyIndexLoadError;
 pub use self::store::DefaultIndexStore;
 pub use self::store::DefaultIndexStoreError;
 pub use self::store::DefaultIndexStoreInitError;
+pub use self::store::IndexVerificationIssue;
 
 #[cfg(test)]
 #[rustversion::attr(
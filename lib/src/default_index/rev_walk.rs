@@ -165,16 +165,17 @@ where
 #[must_use]
 pub(super) struct PeekableRevWalk<I: ?Sized, W: RevWalk<I>> {
     walk: W,
-    // Since RevWalk is fused, we don't need a nested Option<Option<_>>.
-    peeked: Option<W::Item>,
+    // RevWalk is fused, but polling an exhausted walk isn't necessarily cheap.
+    // For example, a nested union would poll all of its exhausted inputs, so
+    // the end of the walk is remembered as Some(None).
+    peeked: Option<Option<W::Item>>,
 }
 
 impl<I: ?Sized, W: RevWalk<I>> PeekableRevWalk<I, W> {
     pub fn peek(&mut self, index: &I) -> Option<&W::Item> {
-        if self.peeked.is_none() {
-            self.peeked = self.walk.next(index);
-        }
-        self.peeked.as_ref()
+        self.peeked
+            .get_or_insert_with(|| self.walk.next(index))
+            .as_ref()
     }
 
     pub fn next_if(
@@ -185,8 +186,7 @@ impl<I: ?Sized, W: RevWalk<I>> PeekableRevWalk<I, W> {
         match self.next(index) {
             Some(item) if predicate(&item) => Some(item),
             other => {
-                assert!(self.peeked.is_none());
-                self.peeked = other;
+                self.peeked = Some(other);
                 None
             }
         }
@@ -197,7 +197,14 @@ impl<I: ?Sized, W: RevWalk<I>> RevWalk<I> for PeekableRevWalk<I, W> {
     type Item = W::Item;
 
     fn next(&mut self, index: &I) -> Option<Self::Item> {
-        self.peeked.take().or_else(|| self.walk.next(index))
+        let item = match self.peeked.take() {
+            Some(item) => item,
+            None => self.walk.next(index),
+        };
+        if item.is_none() {
+            self.peeked = Some(None);
+        }
+        item
     }
 }
 
@@ -758,6 +765,7 @@ mod tests {
         assert_eq!(peekable.next_if(&(), |&v| v == 3), Some(3));
         assert_eq!(peekable.peeked, None);
         assert_eq!(peekable.next(&()), None);
+        assert_eq!(peekable.peeked, Some(None));
         assert_eq!(peekable.next(&()), None);
 
         let source = EagerRevWalk::new((vec![] as Vec<i32>).into_iter());
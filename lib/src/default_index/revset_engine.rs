@@ -1243,6 +1243,11 @@ fn build_predicate_fn(
                 Ok(pattern.is_match(commit.description()))
             })
         }
+        RevsetFilterPredicate::EmptyDescription => box_pure_predicate_fn(move |index, pos| {
+            let entry = index.commits().entry_by_pos(pos);
+            let commit = store.get_commit(&entry.commit_id())?;
+            Ok(commit.description().trim().is_empty())
+        }),
         RevsetFilterPredicate::Subject(pattern) => {
             let pattern = pattern.clone();
             box_pure_predicate_fn(move |index, pos| {
@@ -1342,6 +1347,24 @@ fn build_predicate_fn(
             let commit = store.get_commit(&entry.commit_id())?;
             Ok(commit.has_conflict()?)
         }),
+        RevsetFilterPredicate::HasConflictInFiles(expr) => {
+            let matcher: Rc<dyn Matcher> = expr.to_matcher().into();
+            box_pure_predicate_fn(move |index, pos| {
+                let entry = index.commits().entry_by_pos(pos);
+                let commit = store.get_commit(&entry.commit_id())?;
+                if !commit.has_conflict()? {
+                    return Ok(false);
+                }
+                let tree = commit.tree()?;
+                for (path, value) in tree.conflicts() {
+                    value?;
+                    if matcher.matches(&path) {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            })
+        }
         RevsetFilterPredicate::Signed => box_pure_predicate_fn(move |index, pos| {
             let entry = index.commits().entry_by_pos(pos);
             let commit = store.get_commit(&entry.commit_id())?;
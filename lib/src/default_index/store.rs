@@ -20,11 +20,14 @@ use std::collections::HashSet;
 use std::fs;
 use std::io;
 use std::io::Write as _;
+use std::iter;
 use std::path::Path;
 use std::path::PathBuf;
 use std::slice;
 use std::sync::Arc;
 
+use blake2::Blake2b512;
+use blake2::Digest as _;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
 use prost::Message as _;
@@ -57,6 +60,8 @@ use crate::index::IndexStore;
 use crate::index::IndexWriteError;
 use crate::index::MutableIndex;
 use crate::index::ReadonlyIndex;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
 use crate::object_id::ObjectId as _;
 use crate::op_store::OpStoreError;
 use crate::op_store::OperationId;
@@ -98,6 +103,25 @@ pub enum DefaultIndexStoreError {
     },
     #[error(transparent)]
     OpStore(#[from] OpStoreError),
+    #[error("Failed to lock index store")]
+    Lock(#[source] FileLockError),
+}
+
+/// Problem found by [`DefaultIndexStore::verify_index_at_operation()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexVerificationIssue {
+    /// Content of the segment file doesn't match the hash in its file name.
+    ChecksumMismatch { segment_name: String },
+    /// Commit referenced by the operation isn't indexed.
+    MissingCommit { commit_id: CommitId },
+    /// Indexed commit doesn't exist in the commit store.
+    UnknownCommit { commit_id: CommitId },
+    /// Indexed parents differ from the parents recorded in the commit object.
+    ParentMismatch {
+        commit_id: CommitId,
+        indexed_parent_ids: Vec<CommitId>,
+        actual_parent_ids: Vec<CommitId>,
+    },
 }
 
 #[derive(Debug)]
@@ -392,6 +416,111 @@ impl DefaultIndexStore {
         Ok(index)
     }
 
+    /// Discards all index files and builds index for the given `operation`
+    /// from scratch.
+    ///
+    /// Concurrent rebuilds are serialized by a lock file in the store
+    /// directory.
+    pub async fn rebuild_index_at_operation(
+        &self,
+        operation: &Operation,
+        store: &Arc<Store>,
+    ) -> Result<DefaultReadonlyIndex, DefaultIndexStoreError> {
+        let _lock = FileLock::lock(self.dir.join("lock")).map_err(DefaultIndexStoreError::Lock)?;
+        self.reinit()
+            .map_err(|DefaultIndexStoreInitError(err)| DefaultIndexStoreError::SaveIndex(err))?;
+        self.build_index_at_operation(operation, store).await
+    }
+
+    /// Checks integrity of the index associated with the given `operation`.
+    ///
+    /// Segment files are first validated against their checksums. If they
+    /// look intact, the indexed commits are cross-checked with the
+    /// operation's view and the commit store. The `progress` callback is
+    /// called with the numbers of checked and total commits.
+    pub fn verify_index_at_operation(
+        &self,
+        operation: &Operation,
+        store: &Arc<Store>,
+        progress: &mut dyn FnMut(u32, u32),
+    ) -> Result<Vec<IndexVerificationIssue>, DefaultIndexStoreError> {
+        let field_lengths = FieldLengths {
+            commit_id: store.commit_id_length(),
+            change_id: store.change_id_length(),
+        };
+        let index = self.load_index_at_operation(operation.id(), field_lengths)?;
+        let mut issues = vec![];
+        let segments = iter::once(index.readonly_commits()).chain(
+            index
+                .readonly_commits()
+                .as_composite()
+                .ancestor_files_without_local(),
+        );
+        for segment in segments {
+            let segment_name = segment.id().hex();
+            let path = self.commit_segments_dir().join(&segment_name);
+            let data = fs::read(&path).map_err(|err| {
+                DefaultIndexStoreError::LoadIndex(ReadonlyIndexLoadError::from_io_err(
+                    "commit",
+                    &segment_name,
+                    err,
+                ))
+            })?;
+            if Blake2b512::digest(&data).as_slice() != segment.id().as_bytes() {
+                issues.push(IndexVerificationIssue::ChecksumMismatch { segment_name });
+            }
+        }
+        // Positions stored in a corrupted segment can't be trusted.
+        if !issues.is_empty() {
+            return Ok(issues);
+        }
+
+        let view = operation.view()?;
+        let referenced_ids: HashSet<&CommitId> = itertools::chain(
+            operation.all_referenced_commit_ids(),
+            view.all_referenced_commit_ids(),
+        )
+        .collect();
+        issues.extend(
+            referenced_ids
+                .into_iter()
+                .filter(|id| !index.has_id(id))
+                .sorted()
+                .map(|id| IndexVerificationIssue::MissingCommit {
+                    commit_id: id.clone(),
+                }),
+        );
+
+        let to_index_err = |source| DefaultIndexStoreError::IndexCommits {
+            op_id: operation.id().clone(),
+            source,
+        };
+        let commits = index.as_composite().commits();
+        let num_commits = commits.num_commits();
+        for pos in 0..num_commits {
+            let entry = commits.entry_by_pos(GlobalCommitPosition(pos));
+            let commit_id = entry.commit_id();
+            match store.get_commit(&commit_id) {
+                Ok(commit) => {
+                    let indexed_parent_ids = entry.parents().map(|e| e.commit_id()).collect_vec();
+                    if indexed_parent_ids != commit.parent_ids() {
+                        issues.push(IndexVerificationIssue::ParentMismatch {
+                            commit_id,
+                            indexed_parent_ids,
+                            actual_parent_ids: commit.parent_ids().to_vec(),
+                        });
+                    }
+                }
+                Err(BackendError::ObjectNotFound { .. }) => {
+                    issues.push(IndexVerificationIssue::UnknownCommit { commit_id });
+                }
+                Err(err) => return Err(to_index_err(err)),
+            }
+            progress(pos + 1, num_commits);
+        }
+        Ok(issues)
+    }
+
     /// Builds changed-path index for the specified operation.
     ///
     /// At most `max_commits` number of commits will be scanned from the latest
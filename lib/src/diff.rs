@@ -24,6 +24,7 @@ use std::ops::Range;
 use std::slice;
 
 use bstr::BStr;
+use bstr::ByteSlice as _;
 use hashbrown::HashTable;
 use itertools::Itertools as _;
 use smallvec::SmallVec;
@@ -49,7 +50,7 @@ fn is_word_byte(b: u8) -> bool {
     )
 }
 
-pub fn find_word_ranges(text: &[u8]) -> Vec<Range<usize>> {
+fn find_byte_run_ranges(text: &[u8], is_word_byte: impl Fn(u8) -> bool) -> Vec<Range<usize>> {
     let mut word_ranges = vec![];
     let mut word_start_pos = 0;
     let mut in_word = false;
@@ -69,6 +70,10 @@ pub fn find_word_ranges(text: &[u8]) -> Vec<Range<usize>> {
     word_ranges
 }
 
+pub fn find_word_ranges(text: &[u8]) -> Vec<Range<usize>> {
+    find_byte_run_ranges(text, is_word_byte)
+}
+
 pub fn find_nonword_ranges(text: &[u8]) -> Vec<Range<usize>> {
     text.iter()
         .positions(|b| !is_word_byte(*b))
@@ -76,6 +81,39 @@ pub fn find_nonword_ranges(text: &[u8]) -> Vec<Range<usize>> {
         .collect()
 }
 
+/// Finds ranges of non-whitespace runs, so that punctuation stays attached to
+/// the adjacent word.
+pub fn find_whitespace_delimited_ranges(text: &[u8]) -> Vec<Range<usize>> {
+    find_byte_run_ranges(text, |b| !b.is_ascii_whitespace())
+}
+
+pub fn find_whitespace_ranges(text: &[u8]) -> Vec<Range<usize>> {
+    text.iter()
+        .positions(|b| b.is_ascii_whitespace())
+        .map(|i| i..i + 1)
+        .collect()
+}
+
+/// Finds ranges of words as defined by the Unicode word boundary rules.
+/// Unlike [`find_word_ranges()`], non-ASCII punctuation and symbols aren't
+/// parts of words.
+pub fn find_unicode_word_ranges(text: &[u8]) -> Vec<Range<usize>> {
+    text.word_indices()
+        .map(|(start, end, _)| start..end)
+        .collect()
+}
+
+pub fn find_unicode_nonword_ranges(text: &[u8]) -> Vec<Range<usize>> {
+    let mut word_ranges = find_unicode_word_ranges(text).into_iter().peekable();
+    text.char_indices()
+        .filter(|&(start, _, _)| {
+            while word_ranges.next_if(|range| range.end <= start).is_some() {}
+            word_ranges.peek().is_none_or(|range| start < range.start)
+        })
+        .map(|(start, end, _)| start..end)
+        .collect()
+}
+
 fn bytes_ignore_all_whitespace(text: &[u8]) -> impl Iterator<Item = u8> + use<'_> {
     text.iter().copied().filter(|b| !b.is_ascii_whitespace())
 }
@@ -1144,6 +1182,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_whitespace_delimited_ranges() {
+        let text = b"fn find_words(text: &[u8])";
+        assert_eq!(
+            find_whitespace_delimited_ranges(text),
+            vec![0..2, 3..19, 20..26]
+        );
+        assert_eq!(find_whitespace_ranges(text), vec![2..3, 19..20]);
+    }
+
+    #[test]
+    fn test_find_unicode_word_ranges() {
+        let text = "a⊢b café".as_bytes();
+        assert_eq!(find_unicode_word_ranges(text), vec![0..1, 4..5, 6..11]);
+        assert_eq!(find_unicode_nonword_ranges(text), vec![1..4, 5..6]);
+    }
+
     #[test]
     fn test_compare_bytes_ignore_all_whitespace() {
         let comp = WordComparator::new(CompareBytesIgnoreAllWhitespace);
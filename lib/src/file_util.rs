@@ -242,6 +242,47 @@ pub fn persist_content_addressed_temp_file<P: AsRef<Path>>(
     }
 }
 
+/// Creates a new file at `to` sharing the data blocks of `from`, on filesystems
+/// supporting copy-on-write clones (such as Btrfs, XFS, or APFS).
+///
+/// Fails if the platform or the filesystem doesn't support cloning, or if `to`
+/// already exists. No file is left at `to` on failure.
+pub fn reflink_file(from: &Path, to: &Path) -> io::Result<()> {
+    reflink_file_impl(from, to)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn reflink_file_impl(from: &Path, to: &Path) -> io::Result<()> {
+    let src = File::open(from)?;
+    let dest = File::options().write(true).create_new(true).open(to)?;
+    if let Err(errno) = rustix::fs::ioctl_ficlone(&dest, &src) {
+        drop(dest);
+        fs::remove_file(to).ok();
+        return Err(errno.into());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn reflink_file_impl(from: &Path, to: &Path) -> io::Result<()> {
+    let (Some(dir), Some(name)) = (to.parent(), to.file_name()) else {
+        return Err(io::ErrorKind::InvalidInput.into());
+    };
+    let src = File::open(from)?;
+    let dir = File::open(if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    })?;
+    rustix::fs::fclonefileat(&src, &dir, name, rustix::fs::CloneFlags::empty())?;
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn reflink_file_impl(_from: &Path, _to: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
 /// Reads from an async source and writes to a sync destination. Does not spawn
 /// a task, so writes will block.
 pub async fn copy_async_to_sync<R: AsyncRead, W: Write + ?Sized>(
@@ -408,9 +408,13 @@ pub struct GitImportStats {
     pub changed_remote_tags: Vec<(RemoteRefSymbolBuf, (RemoteRef, RefTarget))>,
     /// Git ref names that couldn't be imported, sorted by name.
     ///
-    /// This list doesn't include refs that are supposed to be ignored, such as
-    /// refs pointing to non-commit objects.
+    /// Refs pointing to non-commit objects aren't included. See
+    /// `non_commit_ref_names` for them.
     pub failed_ref_names: Vec<BString>,
+    /// Git ref names which pointed to commits when last imported, but now
+    /// point to non-commit objects, sorted by name. These refs are skipped,
+    /// and removed from the view.
+    pub non_commit_ref_names: Vec<GitRefNameBuf>,
 }
 
 #[derive(Debug)]
@@ -426,6 +430,8 @@ struct RefsToImport {
     changed_remote_tags: Vec<(RemoteRefSymbolBuf, (RemoteRef, RefTarget))>,
     /// Git ref names that couldn't be imported, sorted by name.
     failed_ref_names: Vec<BString>,
+    /// Known Git ref names now pointing to non-commit objects, sorted by name.
+    non_commit_ref_names: Vec<GitRefNameBuf>,
 }
 
 /// Reflect changes made in the underlying Git repo in the Jujutsu repo.
@@ -457,6 +463,7 @@ pub fn import_some_refs(
         changed_remote_bookmarks,
         changed_remote_tags,
         failed_ref_names,
+        non_commit_ref_names,
     } = diff_refs_to_import(mut_repo.view(), &git_repo, git_ref_filter)?;
 
     // Bulk-import all reachable Git commits to the backend to reduce overhead
@@ -548,6 +555,7 @@ pub fn import_some_refs(
         changed_remote_bookmarks,
         changed_remote_tags,
         failed_ref_names,
+        non_commit_ref_names,
     };
     Ok(stats)
 }
@@ -631,6 +639,7 @@ fn diff_refs_to_import(
     let mut changed_remote_bookmarks = Vec::new();
     let mut changed_remote_tags = Vec::new();
     let mut failed_ref_names = Vec::new();
+    let mut non_commit_ref_names = Vec::new();
     let actual = git_repo.references().map_err(GitImportError::from_git)?;
     collect_changed_refs_to_import(
         actual.local_branches().map_err(GitImportError::from_git)?,
@@ -639,6 +648,7 @@ fn diff_refs_to_import(
         &mut changed_git_refs,
         &mut changed_remote_bookmarks,
         &mut failed_ref_names,
+        &mut non_commit_ref_names,
         &git_ref_filter,
     )?;
     collect_changed_refs_to_import(
@@ -648,6 +658,7 @@ fn diff_refs_to_import(
         &mut changed_git_refs,
         &mut changed_remote_bookmarks,
         &mut failed_ref_names,
+        &mut non_commit_ref_names,
         &git_ref_filter,
     )?;
     collect_changed_refs_to_import(
@@ -657,6 +668,7 @@ fn diff_refs_to_import(
         &mut changed_git_refs,
         &mut changed_remote_tags,
         &mut failed_ref_names,
+        &mut non_commit_ref_names,
         &git_ref_filter,
     )?;
     for full_name in known_git_refs.into_keys() {
@@ -682,14 +694,17 @@ fn diff_refs_to_import(
     changed_remote_bookmarks.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
     changed_remote_tags.sort_unstable_by(|(sym1, _), (sym2, _)| sym1.cmp(sym2));
     failed_ref_names.sort_unstable();
+    non_commit_ref_names.sort_unstable();
     Ok(RefsToImport {
         changed_git_refs,
         changed_remote_bookmarks,
         changed_remote_tags,
         failed_ref_names,
+        non_commit_ref_names,
     })
 }
 
+#[expect(clippy::too_many_arguments)]
 fn collect_changed_refs_to_import(
     actual_git_refs: gix::reference::iter::Iter<'_>,
     known_git_refs: &mut HashMap<&GitRefName, &RefTarget>,
@@ -697,6 +712,7 @@ fn collect_changed_refs_to_import(
     changed_git_refs: &mut Vec<(GitRefNameBuf, RefTarget)>,
     changed_remote_refs: &mut Vec<(RemoteRefSymbolBuf, (RemoteRef, RefTarget))>,
     failed_ref_names: &mut Vec<BString>,
+    non_commit_ref_names: &mut Vec<GitRefNameBuf>,
     git_ref_filter: impl Fn(GitRefKind, RemoteRefSymbol<'_>) -> bool,
 ) -> Result<(), GitImportError> {
     for git_ref in actual_git_refs {
@@ -721,7 +737,12 @@ fn collect_changed_refs_to_import(
         }
         let old_git_target = known_git_refs.get(full_name).copied().flatten();
         let Some(id) = resolve_git_ref_to_commit_id(&git_ref, old_git_target) else {
-            // Skip (or remove existing) invalid refs.
+            // Skip (or remove existing) invalid refs. Only the refs that
+            // changed since the last import are reported since unknown refs
+            // would otherwise be reported again on every import.
+            if old_git_target.is_present() {
+                non_commit_ref_names.push(full_name.to_owned());
+            }
             continue;
         };
         let new_target = RefTarget::normal(id);
@@ -2336,6 +2357,7 @@ pub struct GitFetch<'a> {
     git_ctx: GitSubprocessContext<'a>,
     git_settings: &'a GitSettings,
     fetched: Vec<FetchedBranches>,
+    fetched_notes_remotes: Vec<RemoteNameBuf>,
 }
 
 impl<'a> GitFetch<'a> {
@@ -2353,6 +2375,7 @@ impl<'a> GitFetch<'a> {
             git_ctx,
             git_settings,
             fetched: vec![],
+            fetched_notes_remotes: vec![],
         })
     }
 
@@ -2389,6 +2412,14 @@ impl<'a> GitFetch<'a> {
             // Don't fall back to the base refspecs.
             return Ok(());
         }
+        // Notes refs that don't exist on the remote are dropped below like
+        // missing branches.
+        remaining_refspecs.extend(self.git_settings.import_notes.iter().map(|name| {
+            RefSpec::forced(
+                format!("refs/notes/{name}"),
+                remote_notes_ref_name(remote_name, name),
+            )
+        }));
 
         let mut branches_to_prune = Vec::new();
         // git unfortunately errors out if one of the many refspecs is not found
@@ -2425,6 +2456,9 @@ impl<'a> GitFetch<'a> {
             remote: remote_name.to_owned(),
             branches: expected_branch_names,
         });
+        if !self.git_settings.import_notes.is_empty() {
+            self.fetched_notes_remotes.push(remote_name.to_owned());
+        }
         Ok(())
     }
 
@@ -2477,10 +2511,89 @@ impl<'a> GitFetch<'a> {
 
         self.fetched.clear();
 
+        for remote in self.fetched_notes_remotes.drain(..) {
+            for name in &self.git_settings.import_notes {
+                import_git_notes(self.mut_repo, &self.git_repo, &remote, name)?;
+            }
+        }
+
         Ok(import_stats)
     }
 }
 
+/// Prefix of the keys of notes imported from Git notes refs.
+pub const GIT_NOTES_KEY_PREFIX: &str = "git:";
+
+fn remote_notes_ref_name(remote: &RemoteName, name: &str) -> String {
+    format!(
+        "refs/notes/remotes/{remote}/{name}",
+        remote = remote.as_str()
+    )
+}
+
+/// Imports the Git notes fetched from `refs/notes/<name>` of the `remote` as
+/// notes keyed by `git:<name>`. Notes on commits unknown to the repo are
+/// skipped.
+fn import_git_notes(
+    mut_repo: &mut MutableRepo,
+    git_repo: &gix::Repository,
+    remote: &RemoteName,
+    name: &str,
+) -> Result<(), GitImportError> {
+    let ref_name = remote_notes_ref_name(remote, name);
+    let Ok(mut git_ref) = git_repo.find_reference(ref_name.as_str()) else {
+        return Ok(());
+    };
+    let notes_tree = git_ref
+        .peel_to_commit()
+        .map_err(GitImportError::from_git)?
+        .tree()
+        .map_err(GitImportError::from_git)?;
+    let key = format!("{GIT_NOTES_KEY_PREFIX}{name}");
+    // Notes trees may fan out the annotated commit ids into subdirectories,
+    // e.g. "ab/cdef...".
+    let mut pending_trees = vec![(String::new(), notes_tree)];
+    while let Some((hex_prefix, tree)) = pending_trees.pop() {
+        for entry in tree.iter() {
+            let entry = entry.map_err(GitImportError::from_git)?;
+            let Ok(file_name) = str::from_utf8(entry.filename()) else {
+                continue;
+            };
+            let hex = format!("{hex_prefix}{file_name}");
+            match entry.mode().kind() {
+                gix::object::tree::EntryKind::Tree => {
+                    let subtree = entry
+                        .object()
+                        .map_err(GitImportError::from_git)?
+                        .try_into_tree()
+                        .map_err(GitImportError::from_git)?;
+                    pending_trees.push((hex, subtree));
+                }
+                gix::object::tree::EntryKind::Blob
+                | gix::object::tree::EntryKind::BlobExecutable => {
+                    let Some(commit_id) = CommitId::try_from_hex(&hex) else {
+                        continue;
+                    };
+                    if !mut_repo.index().has_id(&commit_id) {
+                        continue;
+                    }
+                    let commit = mut_repo
+                        .store()
+                        .get_commit(&commit_id)
+                        .map_err(GitImportError::Backend)?;
+                    let blob = entry.object().map_err(GitImportError::from_git)?;
+                    let text = String::from_utf8_lossy(&blob.data).trim_end().to_owned();
+                    if !text.is_empty() {
+                        mut_repo.set_note(commit.change_id(), &key, text);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
 #[derive(Error, Debug)]
 pub enum GitPushError {
     #[error("No git remote named '{}'", .0.as_symbol())]
@@ -2518,16 +2631,7 @@ pub fn push_branches(
 ) -> Result<GitPushStats, GitPushError> {
     validate_remote_name(remote)?;
 
-    let ref_updates = targets
-        .branch_updates
-        .iter()
-        .map(|(name, update)| GitRefUpdate {
-            qualified_name: format!("refs/heads/{name}", name = name.as_str()).into(),
-            expected_current_target: update.old_target.clone(),
-            new_target: update.new_target.clone(),
-        })
-        .collect_vec();
-
+    let ref_updates = to_branch_ref_updates(targets);
     let push_stats = push_updates(mut_repo, git_settings, remote, &ref_updates, callbacks)?;
     tracing::debug!(?push_stats);
 
@@ -2554,6 +2658,56 @@ pub fn push_branches(
     Ok(push_stats)
 }
 
+/// Pushes the specified branches to the Git repository at `url` without
+/// configuring a remote.
+///
+/// Remote-tracking branches aren't updated unless `track_as` is specified, in
+/// which case the pushed branches are recorded in the repo view as tracked
+/// remote bookmarks of that remote name. No Git remote-tracking refs are
+/// created.
+pub fn push_branches_to_url(
+    mut_repo: &mut MutableRepo,
+    git_settings: &GitSettings,
+    url: &str,
+    track_as: Option<&RemoteName>,
+    targets: &GitBranchPushTargets,
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<GitPushStats, GitPushError> {
+    if let Some(remote) = track_as {
+        validate_remote_name(remote)?;
+    }
+
+    let ref_updates = to_branch_ref_updates(targets);
+    let git_backend = get_git_backend(mut_repo.store())?;
+    let push_stats =
+        spawn_push_updates(git_backend, git_settings, url, &ref_updates, &mut callbacks)?;
+    tracing::debug!(?push_stats);
+
+    if let Some(remote) = track_as.filter(|_| push_stats.all_ok()) {
+        for (name, update) in &targets.branch_updates {
+            let new_remote_ref = RemoteRef {
+                target: RefTarget::resolved(update.new_target.clone()),
+                state: RemoteRefState::Tracked,
+            };
+            mut_repo.set_remote_bookmark(name.to_remote_symbol(remote), new_remote_ref);
+        }
+    }
+
+    Ok(push_stats)
+}
+
+fn to_branch_ref_updates(targets: &GitBranchPushTargets) -> Vec<GitRefUpdate> {
+    targets
+        .branch_updates
+        .iter()
+        .map(|(name, update)| GitRefUpdate {
+            qualified_name: format!("refs/heads/{name}", name = name.as_str()).into(),
+            expected_current_target: update.old_target.clone(),
+            new_target: update.new_target.clone(),
+        })
+        .collect_vec()
+}
+
 /// Pushes the specified Git refs without updating the repo view.
 pub fn push_updates(
     repo: &dyn Repo,
@@ -2561,6 +2715,32 @@ pub fn push_updates(
     remote_name: &RemoteName,
     updates: &[GitRefUpdate],
     mut callbacks: RemoteCallbacks<'_>,
+) -> Result<GitPushStats, GitPushError> {
+    let git_backend = get_git_backend(repo.store())?;
+    let git_repo = git_backend.git_repo();
+
+    // check the remote exists
+    if git_repo.try_find_remote(remote_name.as_str()).is_none() {
+        return Err(GitPushError::NoSuchRemote(remote_name.to_owned()));
+    }
+
+    spawn_push_updates(
+        git_backend,
+        git_settings,
+        remote_name.as_str(),
+        updates,
+        &mut callbacks,
+    )
+}
+
+/// Pushes the specified Git refs to the `destination`, which may be a remote
+/// name or URL.
+fn spawn_push_updates(
+    git_backend: &GitBackend,
+    git_settings: &GitSettings,
+    destination: &str,
+    updates: &[GitRefUpdate],
+    callbacks: &mut RemoteCallbacks<'_>,
 ) -> Result<GitPushStats, GitPushError> {
     let mut qualified_remote_refs_expected_locations = HashMap::new();
     let mut refspecs = vec![];
@@ -2582,22 +2762,15 @@ pub fn push_updates(
         }
     }
 
-    let git_backend = get_git_backend(repo.store())?;
-    let git_repo = git_backend.git_repo();
     let git_ctx =
         GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
 
-    // check the remote exists
-    if git_repo.try_find_remote(remote_name.as_str()).is_none() {
-        return Err(GitPushError::NoSuchRemote(remote_name.to_owned()));
-    }
-
     let refs_to_push: Vec<RefToPush> = refspecs
         .iter()
         .map(|full_refspec| RefToPush::new(full_refspec, &qualified_remote_refs_expected_locations))
         .collect();
 
-    let mut push_stats = git_ctx.spawn_push(remote_name, &refs_to_push, &mut callbacks)?;
+    let mut push_stats = git_ctx.spawn_push(destination, &refs_to_push, callbacks)?;
     push_stats.pushed.sort();
     push_stats.rejected.sort();
     push_stats.remote_rejected.sort();
@@ -89,6 +89,7 @@ use crate::object_id::ObjectId;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathComponentBuf;
+use crate::settings::CopyTrackingSettings;
 use crate::settings::GitSettings;
 use crate::settings::UserSettings;
 use crate::stacked_table::MutableTable;
@@ -182,6 +183,7 @@ pub struct GitBackend {
     cached_extra_metadata: Mutex<Option<Arc<ReadonlyTable>>>,
     git_executable: PathBuf,
     write_change_id_header: bool,
+    copy_tracking: CopyTrackingSettings,
 }
 
 impl GitBackend {
@@ -209,6 +211,7 @@ impl GitBackend {
             cached_extra_metadata: Mutex::new(None),
             git_executable: git_settings.executable_path,
             write_change_id_header: git_settings.write_change_id_header,
+            copy_tracking: git_settings.copy_tracking,
         }
     }
 
@@ -1450,6 +1453,14 @@ impl Backend for GitBackend {
                 }))
             };
 
+        // No percentage means that only exact copies are detected.
+        let percentage = (self.copy_tracking.similarity < 100)
+            .then(|| f32::from(self.copy_tracking.similarity) / 100.0);
+        let copy_source = if self.copy_tracking.detect_copies {
+            gix::diff::rewrites::CopySource::FromSetOfModifiedFilesAndAllSources
+        } else {
+            gix::diff::rewrites::CopySource::FromSetOfModifiedFiles
+        };
         let mut records: Vec<BackendResult<CopyRecord>> = Vec::new();
         root_tree
             .changes()
@@ -1457,11 +1468,11 @@ impl Backend for GitBackend {
             .options(|opts| {
                 opts.track_path().track_rewrites(Some(gix::diff::Rewrites {
                     copies: Some(gix::diff::rewrites::Copies {
-                        source: gix::diff::rewrites::CopySource::FromSetOfModifiedFiles,
-                        percentage: Some(0.5),
+                        source: copy_source,
+                        percentage,
                     }),
-                    percentage: Some(0.5),
-                    limit: 1000,
+                    percentage,
+                    limit: self.copy_tracking.max_candidates,
                     track_empty: false,
                 }));
             })
@@ -254,9 +254,11 @@ impl<'a> GitSubprocessContext<'a> {
     /// Return tuple with
     ///     1. refs that failed to push
     ///     2. refs that succeeded to push
+    ///
+    /// The `destination` may be a remote name or URL.
     pub(crate) fn spawn_push(
         &self,
-        remote_name: &RemoteName,
+        destination: &str,
         references: &[RefToPush],
         callbacks: &mut RemoteCallbacks<'_>,
     ) -> Result<GitPushStats, GitSubprocessError> {
@@ -276,7 +278,7 @@ impl<'a> GitSubprocessContext<'a> {
                 .iter()
                 .map(|reference| format!("--force-with-lease={}", reference.to_git_lease())),
         );
-        command.args(["--", remote_name.as_str()]);
+        command.args(["--", destination]);
         // with --force-with-lease we cannot have the forced refspec,
         // as it ignores the lease
         command.args(
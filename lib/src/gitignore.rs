@@ -42,6 +42,16 @@ pub enum GitIgnoreError {
     },
 }
 
+/// Ignore pattern which decided that a path is ignored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitIgnoreMatch {
+    /// Path to the file the pattern was read from. May be empty if the
+    /// patterns didn't come from a file.
+    pub source: PathBuf,
+    /// The pattern as written in the file.
+    pub pattern: String,
+}
+
 /// Models the effective contents of multiple .gitignore files.
 #[derive(Debug)]
 pub struct GitIgnoreFile {
@@ -76,11 +86,10 @@ impl GitIgnoreFile {
                     line: String::from_utf8_lossy(input_line).to_string(),
                     source: err,
                 })?;
-            // The `from` argument doesn't provide any diagnostics or correctness, so it is
-            // not required. It only allows retrieving the path from the `Glob` later, which
-            // we never do.
+            // The `from` argument doesn't provide any diagnostics or correctness. It only
+            // allows retrieving the path from the `Glob` later in `matching_pattern()`.
             builder
-                .add_line(None, line)
+                .add_line(Some(ignore_path.to_path_buf()), line)
                 .map_err(|err| GitIgnoreError::Underlying {
                     path: ignore_path.to_path_buf(),
                     source: err,
@@ -118,7 +127,7 @@ impl GitIgnoreFile {
         }
     }
 
-    fn matches_helper(&self, path: &str, is_dir: bool) -> bool {
+    fn matched_glob(&self, path: &str, is_dir: bool) -> Option<&gitignore::Glob> {
         iter::successors(Some(self), |file| file.parent.as_deref())
             .find_map(|file| {
                 // TODO: the documentation warns that
@@ -126,11 +135,15 @@ impl GitIgnoreFile {
                 // ideally, we would switch to that.
                 match file.matcher.matched_path_or_any_parents(path, is_dir) {
                     ignore::Match::None => None,
-                    ignore::Match::Ignore(_) => Some(true),
-                    ignore::Match::Whitelist(_) => Some(false),
+                    ignore::Match::Ignore(glob) => Some(Some(glob)),
+                    ignore::Match::Whitelist(_) => Some(None),
                 }
             })
-            .unwrap_or_default()
+            .flatten()
+    }
+
+    fn matches_helper(&self, path: &str, is_dir: bool) -> bool {
+        self.matched_glob(path, is_dir).is_some()
     }
 
     /// Returns whether specified path (not just file!) should be ignored. This
@@ -148,6 +161,20 @@ impl GitIgnoreFile {
         };
         self.matches_helper(path, is_dir)
     }
+
+    /// Returns the pattern which makes the specified path ignored, or `None`
+    /// if the path isn't ignored. See [`GitIgnoreFile::matches()`] for caveats.
+    pub fn matching_pattern(&self, path: &str) -> Option<GitIgnoreMatch> {
+        let (path, is_dir) = match path.strip_suffix('/') {
+            Some(path) => (path, true),
+            None => (path, false),
+        };
+        let glob = self.matched_glob(path, is_dir)?;
+        Some(GitIgnoreMatch {
+            source: glob.from().map(Path::to_path_buf).unwrap_or_default(),
+            pattern: glob.original().to_owned(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -457,4 +484,29 @@ mod tests {
             .unwrap();
         assert!(ignore.matches("foo/bar.ext"));
     }
+
+    #[test]
+    fn test_gitignore_matching_pattern() {
+        let ignore = GitIgnoreFile::empty()
+            .chain("", Path::new(".gitignore"), b"*.bak\n/build/\n")
+            .unwrap()
+            .chain("dir/", Path::new("dir/.gitignore"), b"!keep.bak\n")
+            .unwrap();
+        assert_eq!(
+            ignore.matching_pattern("file.bak"),
+            Some(GitIgnoreMatch {
+                source: PathBuf::from(".gitignore"),
+                pattern: "*.bak".to_owned(),
+            })
+        );
+        assert_eq!(
+            ignore.matching_pattern("build/out"),
+            Some(GitIgnoreMatch {
+                source: PathBuf::from(".gitignore"),
+                pattern: "/build/".to_owned(),
+            })
+        );
+        assert_eq!(ignore.matching_pattern("dir/keep.bak"), None);
+        assert_eq!(ignore.matching_pattern("file"), None);
+    }
 }
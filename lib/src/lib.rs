@@ -59,6 +59,10 @@ pub mod git;
 /// in the `git` module.
 pub mod git {
     use crate::ref_name::RemoteName;
+
+    /// Prefix of the keys of notes imported from Git notes refs.
+    pub const GIT_NOTES_KEY_PREFIX: &str = "git:";
+
     /// Determine, by its name, if a remote refers to the special local-only
     /// "git" remote that is used in the Git backend.
     ///
@@ -81,6 +85,7 @@ pub mod local_working_copy;
 pub mod lock;
 pub mod matchers;
 pub mod merge;
+pub mod merge_driver;
 pub mod merged_tree;
 pub mod object_id;
 pub mod op_heads_store;
@@ -90,6 +95,7 @@ pub mod operation;
 #[expect(missing_docs)]
 pub mod protos;
 pub mod ref_name;
+pub(crate) mod reflink_cache;
 pub mod refs;
 pub mod repo;
 pub mod repo_path;
@@ -26,6 +26,8 @@ use std::fs::Metadata;
 use std::fs::OpenOptions;
 use std::io;
 use std::io::Read as _;
+use std::io::Seek as _;
+use std::io::SeekFrom;
 use std::io::Write as _;
 use std::iter;
 use std::mem;
@@ -107,6 +109,8 @@ use crate::object_id::ObjectId as _;
 use crate::op_store::OperationId;
 use crate::ref_name::WorkspaceName;
 use crate::ref_name::WorkspaceNameBuf;
+use crate::reflink_cache::ReflinkCache;
+use crate::reflink_cache::create_reflink_cache;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathComponent;
@@ -691,6 +695,21 @@ fn reject_reserved_existing_path(disk_path: &Path) -> Result<(), CheckoutError>
     Ok(())
 }
 
+/// Guesses the filesystem's timestamp resolution from a single mtime sample.
+///
+/// A sample may look coarser than the real resolution by chance, so callers
+/// should keep the finest estimate seen so far.
+fn estimate_mtime_granularity(mtime: MillisSinceEpoch) -> u64 {
+    if mtime.0 % 1000 != 0 {
+        1
+    } else if mtime.0 / 1000 % 2 != 0 {
+        1000
+    } else {
+        // FAT only stores even seconds
+        2000
+    }
+}
+
 fn mtime_from_metadata(metadata: &Metadata) -> MillisSinceEpoch {
     let time = metadata
         .modified()
@@ -745,6 +764,9 @@ pub struct TreeStateSettings {
     /// file to the backend, and vice versa when it checks out code onto your
     /// filesystem.
     pub eol_conversion_mode: EolConversionMode,
+    /// Whether to materialize files as copy-on-write clones of cached
+    /// contents, on filesystems supporting reflinks.
+    pub use_reflinks: bool,
 }
 
 impl TreeStateSettings {
@@ -752,6 +774,7 @@ impl TreeStateSettings {
     pub fn try_from_user_settings(user_settings: &UserSettings) -> Result<Self, ConfigGetError> {
         Ok(Self {
             eol_conversion_mode: EolConversionMode::try_from_settings(user_settings)?,
+            use_reflinks: user_settings.get_bool("working-copy.use-reflinks")?,
         })
     }
 }
@@ -765,6 +788,9 @@ pub struct TreeState {
     // Currently only path prefixes
     sparse_patterns: Vec<RepoPathBuf>,
     own_mtime: MillisSinceEpoch,
+    /// Finest mtime resolution (in milliseconds) observed on the filesystem,
+    /// or 0 if unknown.
+    mtime_granularity: u64,
     symlink_support: bool,
 
     /// The most recent clock value returned by Watchman. Will only be set if
@@ -773,6 +799,7 @@ pub struct TreeState {
     watchman_clock: Option<crate::protos::local_working_copy::WatchmanClock>,
 
     target_eol_strategy: TargetEolStrategy,
+    reflink_cache: Option<ReflinkCache>,
 }
 
 #[derive(Debug, Error)]
@@ -809,6 +836,25 @@ impl TreeState {
         &self.sparse_patterns
     }
 
+    /// Timestamp resolution of the filesystem in milliseconds, as far as it's
+    /// been observed.
+    pub fn mtime_granularity_millis(&self) -> u64 {
+        self.mtime_granularity.max(1)
+    }
+
+    /// Returns true if the file state was recorded too close to the time this
+    /// tree state was written to tell whether the file has been modified since.
+    ///
+    /// Such files must be re-hashed even if their metadata looks unchanged.
+    pub fn is_racy(&self, file_state: &FileState) -> bool {
+        self.is_racy_mtime(file_state.mtime)
+    }
+
+    fn is_racy_mtime(&self, mtime: MillisSinceEpoch) -> bool {
+        let granularity = i64::try_from(self.mtime_granularity_millis()).unwrap_or(i64::MAX);
+        mtime.0.saturating_add(granularity) > self.own_mtime.0
+    }
+
     fn sparse_matcher(&self) -> Box<dyn Matcher> {
         Box::new(PrefixMatcher::new(&self.sparse_patterns))
     }
@@ -820,7 +866,14 @@ impl TreeState {
         tree_state_settings: &TreeStateSettings,
     ) -> Result<Self, TreeStateError> {
         let target_eol_strategy = create_target_eol_strategy(tree_state_settings);
-        let mut wc = Self::empty(store, working_copy_path, state_path, target_eol_strategy);
+        let reflink_cache = create_reflink_cache(tree_state_settings, &state_path);
+        let mut wc = Self::empty(
+            store,
+            working_copy_path,
+            state_path,
+            target_eol_strategy,
+            reflink_cache,
+        );
         wc.save()?;
         Ok(wc)
     }
@@ -830,6 +883,7 @@ impl TreeState {
         working_copy_path: PathBuf,
         state_path: PathBuf,
         target_eol_strategy: TargetEolStrategy,
+        reflink_cache: Option<ReflinkCache>,
     ) -> Self {
         let tree_id = store.empty_merged_tree_id();
         Self {
@@ -840,9 +894,11 @@ impl TreeState {
             file_states: FileStatesMap::new(),
             sparse_patterns: vec![RepoPathBuf::root()],
             own_mtime: MillisSinceEpoch(0),
+            mtime_granularity: 0,
             symlink_support: check_symlink_support().unwrap_or(false),
             watchman_clock: None,
             target_eol_strategy,
+            reflink_cache,
         }
     }
 
@@ -867,21 +923,38 @@ impl TreeState {
             Ok(file) => file,
         };
 
-        let mut wc = Self::empty(store, working_copy_path, state_path, target_eol_strategy);
+        let reflink_cache = create_reflink_cache(tree_state_settings, &state_path);
+        let mut wc = Self::empty(
+            store,
+            working_copy_path,
+            state_path,
+            target_eol_strategy,
+            reflink_cache,
+        );
         wc.read(&tree_state_path, file)?;
         Ok(wc)
     }
 
     fn update_own_mtime(&mut self) {
         if let Ok(metadata) = self.state_path.join("tree_state").symlink_metadata() {
-            self.own_mtime = mtime_from_metadata(&metadata);
+            self.set_own_mtime(mtime_from_metadata(&metadata));
         } else {
             self.own_mtime = MillisSinceEpoch(0);
         }
     }
 
+    fn set_own_mtime(&mut self, mtime: MillisSinceEpoch) {
+        self.own_mtime = mtime;
+        if mtime.0 != 0 {
+            let estimate = estimate_mtime_granularity(mtime);
+            self.mtime_granularity = match self.mtime_granularity {
+                0 => estimate,
+                known => known.min(estimate),
+            };
+        }
+    }
+
     fn read(&mut self, tree_state_path: &Path, mut file: File) -> Result<(), TreeStateError> {
-        self.update_own_mtime();
         let mut buf = Vec::new();
         file.read_to_end(&mut buf)
             .map_err(|err| TreeStateError::ReadTreeState {
@@ -908,6 +981,8 @@ impl TreeState {
             FileStatesMap::from_proto(proto.file_states, proto.is_file_states_sorted);
         self.sparse_patterns = sparse_patterns_from_proto(proto.sparse_patterns.as_ref());
         self.watchman_clock = proto.watchman_clock;
+        self.mtime_granularity = proto.mtime_granularity_millis;
+        self.update_own_mtime();
         Ok(())
     }
 
@@ -934,19 +1009,45 @@ impl TreeState {
         }
         proto.sparse_patterns = Some(sparse_patterns);
         proto.watchman_clock = self.watchman_clock.clone();
+        proto.mtime_granularity_millis = self.mtime_granularity;
 
+        let state_path = self.state_path.clone();
         let wrap_write_err = |source| TreeStateError::WriteTreeState {
-            path: self.state_path.clone(),
+            path: state_path.clone(),
             source,
         };
-        let mut temp_file = NamedTempFile::new_in(&self.state_path).map_err(wrap_write_err)?;
+        let mut temp_file = NamedTempFile::new_in(&state_path).map_err(wrap_write_err)?;
         temp_file
             .as_file_mut()
             .write_all(&proto.encode_to_vec())
             .map_err(wrap_write_err)?;
         // update own write time while we before we rename it, so we know
         // there is no unknown data in it
-        self.update_own_mtime();
+        let metadata = temp_file.as_file().metadata().map_err(wrap_write_err)?;
+        self.set_own_mtime(mtime_from_metadata(&metadata));
+        // Files modified within the timestamp granularity of the new state file
+        // could be changed again without their metadata changing. Clear their
+        // recorded mtime so they're re-hashed even if a later save advances
+        // the state file's mtime without looking at them.
+        let mut num_smudged = 0;
+        for entry in &mut proto.file_states {
+            if let Some(state) = &mut entry.state {
+                let mtime = MillisSinceEpoch(state.mtime_millis_since_epoch);
+                if mtime.0 != 0 && self.is_racy_mtime(mtime) {
+                    state.mtime_millis_since_epoch = 0;
+                    num_smudged += 1;
+                }
+            }
+        }
+        if num_smudged > 0 || proto.mtime_granularity_millis != self.mtime_granularity {
+            tracing::debug!(num_smudged, "rewriting tree state with racy files cleared");
+            proto.mtime_granularity_millis = self.mtime_granularity;
+            let file = temp_file.as_file_mut();
+            file.set_len(0).map_err(wrap_write_err)?;
+            file.seek(SeekFrom::Start(0)).map_err(wrap_write_err)?;
+            file.write_all(&proto.encode_to_vec())
+                .map_err(wrap_write_err)?;
+        }
         // TODO: Retry if persisting fails (it will on Windows if the file happened to
         // be open for read).
         let target_path = self.state_path.join("tree_state");
@@ -1489,7 +1590,7 @@ impl FileSnapshotter<'_> {
                 // If the file's mtime was set at the same time as this state file's own mtime,
                 // then we don't know if the file was modified before or after this state file.
                 new_file_state.is_clean(current_file_state)
-                    && current_file_state.mtime < self.tree_state.own_mtime
+                    && !self.tree_state.is_racy(current_file_state)
             }
         };
         if clean {
@@ -1751,6 +1852,37 @@ impl TreeState {
         ))
     }
 
+    /// Writes the file `id` from the store, cloning the file from the reflink
+    /// cache if possible.
+    async fn write_file_from_store(
+        &self,
+        disk_path: &Path,
+        id: &FileId,
+        contents: impl AsyncRead + Send + Unpin,
+        executable: bool,
+    ) -> Result<FileState, CheckoutError> {
+        let Some(reflink_cache) = &self.reflink_cache else {
+            return self.write_file(disk_path, contents, executable, true).await;
+        };
+        if !reflink_cache.clone_to(id, disk_path) {
+            let file_state = self
+                .write_file(disk_path, contents, executable, true)
+                .await?;
+            reflink_cache.insert_from(id, disk_path);
+            return Ok(file_state);
+        }
+        self.set_executable(disk_path, executable)?;
+        let metadata = disk_path
+            .symlink_metadata()
+            .map_err(|err| checkout_error_for_stat_error(err, disk_path))?;
+        Ok(FileState::for_file(
+            executable,
+            metadata.len(),
+            &metadata,
+            None,
+        ))
+    }
+
     fn write_symlink(&self, disk_path: &Path, target: String) -> Result<FileState, CheckoutError> {
         let target = PathBuf::from(&target);
         try_symlink(&target, disk_path).map_err(|err| CheckoutError::Other {
@@ -1842,6 +1974,26 @@ impl TreeState {
         Ok(stats)
     }
 
+    /// Removes the contents of files which aren't in the current tree from the
+    /// reflink cache.
+    pub fn prune_reflink_cache(&self) -> BackendResult<()> {
+        let Some(reflink_cache) = &self.reflink_cache else {
+            return Ok(());
+        };
+        let sparse_matcher = self.sparse_matcher();
+        let mut referenced = HashSet::new();
+        for (_, value) in self
+            .current_tree()?
+            .entries_matching(sparse_matcher.as_ref())
+        {
+            if let Ok(Some(TreeValue::File { id, .. })) = value?.into_resolved() {
+                referenced.insert(id);
+            }
+        }
+        reflink_cache.retain(&referenced);
+        Ok(())
+    }
+
     pub fn set_sparse_patterns(
         &mut self,
         sparse_patterns: Vec<RepoPathBuf>,
@@ -1905,6 +2057,7 @@ impl TreeState {
         };
         let mut changed_file_states = Vec::new();
         let mut deleted_files = HashSet::new();
+        let mut num_unchanged_files = 0;
         let mut diff_stream = old_tree
             .diff_stream_for_file_system(new_tree, matcher)
             .map(async |TreeDiffEntry { path, values }| match values {
@@ -1917,6 +2070,22 @@ impl TreeState {
             .buffered(self.store.concurrency().max(1));
         while let Some((path, data)) = diff_stream.next().await {
             let (before, after) = data?;
+            // Don't rewrite a file if only its copy id changed. Its file state
+            // is still valid, and keeping the mtime avoids needless rebuilds.
+            if let (
+                Some(TreeValue::File {
+                    id: before_id,
+                    executable: before_executable,
+                    copy_id: _,
+                }),
+                MaterializedTreeValue::File(file),
+            ) = (before.as_normal(), &after)
+            {
+                if *before_id == file.id && *before_executable == file.executable {
+                    num_unchanged_files += 1;
+                    continue;
+                }
+            }
             if after.is_absent() {
                 stats.removed_files += 1;
             } else if before.is_absent() {
@@ -1971,7 +2140,7 @@ impl TreeState {
                     continue;
                 }
                 MaterializedTreeValue::File(file) => {
-                    self.write_file(&disk_path, file.reader, file.executable, true)
+                    self.write_file_from_store(&disk_path, &file.id, file.reader, file.executable)
                         .await?
                 }
                 MaterializedTreeValue::Symlink { id: _, target } => {
@@ -2020,6 +2189,10 @@ impl TreeState {
             };
             changed_file_states.push((path, file_state));
         }
+        tracing::debug!(
+            num_unchanged_files,
+            "skipped writing files with unchanged contents"
+        );
         self.file_states
             .merge_in(changed_file_states, &deleted_files);
         Ok(stats)
@@ -2314,6 +2487,37 @@ impl LocalWorkingCopy {
         Ok(self.tree_state()?.file_states())
     }
 
+    /// Removes the contents of files which aren't in the current tree from the
+    /// reflink cache. The cache isn't pruned on checkout, so switching back to
+    /// a previous revision can reuse the cached contents.
+    pub fn prune_reflink_cache(&self) -> Result<(), WorkingCopyStateError> {
+        self.tree_state()?
+            .prune_reflink_cache()
+            .map_err(|err| WorkingCopyStateError {
+                message: "Failed to prune the reflink cache".to_owned(),
+                err: err.into(),
+            })
+    }
+
+    /// Timestamp resolution observed on the filesystem, in milliseconds.
+    pub fn mtime_granularity_millis(&self) -> Result<u64, WorkingCopyStateError> {
+        Ok(self.tree_state()?.mtime_granularity_millis())
+    }
+
+    /// Returns the file states that the next snapshot will re-hash even if
+    /// their metadata is unchanged, either because they were modified within
+    /// the timestamp granularity of the tree state, or because their mtime was
+    /// cleared for that reason when the tree state was saved.
+    pub fn racy_file_states(&self) -> Result<Vec<(RepoPathBuf, FileState)>, WorkingCopyStateError> {
+        let tree_state = self.tree_state()?;
+        Ok(tree_state
+            .file_states()
+            .iter()
+            .filter(|(_, state)| state.mtime.0 == 0 || tree_state.is_racy(state))
+            .map(|(path, state)| (path.to_owned(), state))
+            .collect())
+    }
+
     #[cfg(feature = "watchman")]
     pub fn query_watchman(
         &self,
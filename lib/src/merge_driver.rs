@@ -0,0 +1,396 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External programs resolving file conflicts in tree merges.
+//!
+//! Merge drivers are configured per file pattern in the repo config:
+//!
+//! ```toml
+//! [merge-drivers."CHANGELOG.md"]
+//! program = "./scripts/merge-changelog"
+//! ```
+//!
+//! The program is called with the base, left, and right file contents as
+//! arguments, and its standard output becomes the merged content. Since the
+//! program usually comes from the repository contents, a driver only runs
+//! once the user has trusted it. Trust is recorded per repo, per command, and
+//! per content of the program, so a changed program path or a modified program
+//! has to be trusted again.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::mem;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::Mutex;
+
+use blake2::Blake2b512;
+use blake2::Digest as _;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::config::ConfigGetError;
+use crate::config::ConfigGetResultExt as _;
+use crate::config::ConfigSource;
+use crate::config::StackedConfig;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::file_util::persist_temp_file;
+use crate::hex_util;
+use crate::merge::Merge;
+use crate::repo_path::RepoPath;
+use crate::settings::UserSettings;
+
+const TRUST_FILE_NAME: &str = "trusted_merge_drivers.toml";
+
+/// Error while loading merge drivers.
+#[derive(Debug, Error)]
+pub enum MergeDriverLoadError {
+    /// Invalid `merge-drivers` config.
+    #[error(transparent)]
+    Config(#[from] ConfigGetError),
+    /// Invalid file pattern.
+    #[error("Invalid merge driver pattern {pattern:?}")]
+    Pattern {
+        /// The pattern string.
+        pattern: String,
+        /// Underlying error.
+        source: globset::Error,
+    },
+    /// Failed to read or write the trust file.
+    #[error(transparent)]
+    Io(#[from] PathError),
+    /// Failed to parse or serialize the trust file.
+    #[error("Failed to parse merge driver trust file")]
+    TrustFile(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct MergeDriverConfig {
+    program: String,
+}
+
+/// Answers of the user keyed by repo path and then by command.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct TrustFile {
+    #[serde(default)]
+    repos: BTreeMap<String, BTreeMap<String, TrustEntry>>,
+}
+
+/// Answer of the user for the program with the given content hash.
+#[derive(Debug, Deserialize, Serialize)]
+struct TrustEntry {
+    hash: String,
+    trusted: bool,
+}
+
+/// External program resolving conflicts in files matching the pattern.
+#[derive(Clone, Debug)]
+pub struct MergeDriver {
+    pattern: String,
+    matcher: GlobMatcher,
+    program: String,
+    command: PathBuf,
+    content_hash: String,
+    trusted: Option<bool>,
+}
+
+impl MergeDriver {
+    /// File pattern this driver applies to.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Program to run as configured.
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Program to run with relative paths resolved against the workspace
+    /// root.
+    pub fn command(&self) -> &Path {
+        &self.command
+    }
+
+    /// Whether the user trusted the program, or `None` if not asked yet or
+    /// the program has changed since.
+    pub fn trusted(&self) -> Option<bool> {
+        self.trusted
+    }
+}
+
+/// Merge drivers configured for the repo.
+#[derive(Debug, Default)]
+pub struct MergeDrivers {
+    drivers: Vec<MergeDriver>,
+    workspace_root: PathBuf,
+    repo_key: String,
+    trust_path: PathBuf,
+    /// Indices of the drivers which weren't run because the user hasn't
+    /// been asked whether to trust them.
+    skipped: Mutex<BTreeSet<usize>>,
+}
+
+impl MergeDrivers {
+    /// Loads merge drivers from the repo config of the repo at `repo_path`.
+    /// The drivers run in `workspace_root`.
+    ///
+    /// Drivers defined in the user or command-line config are ignored.
+    pub fn load(
+        settings: &UserSettings,
+        repo_path: &Path,
+        workspace_root: &Path,
+    ) -> Result<Self, MergeDriverLoadError> {
+        let mut repo_config = StackedConfig::empty();
+        repo_config.extend_layers(
+            settings
+                .config()
+                .layers_for(ConfigSource::Repo)
+                .iter()
+                .cloned(),
+        );
+        let configs: BTreeMap<String, MergeDriverConfig> = repo_config
+            .get("merge-drivers")
+            .optional()?
+            .unwrap_or_default();
+        let trust_path = repo_path.join(TRUST_FILE_NAME);
+        let repo_key = repo_path.to_string_lossy().into_owned();
+        let mut trust_file = if configs.is_empty() {
+            TrustFile::default()
+        } else {
+            load_trust_file(&trust_path)?
+        };
+        let trusted_commands = trust_file.repos.remove(&repo_key).unwrap_or_default();
+        let drivers = configs
+            .into_iter()
+            .map(|(pattern, config)| {
+                let matcher = GlobBuilder::new(&pattern)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|source| MergeDriverLoadError::Pattern {
+                        pattern: pattern.clone(),
+                        source,
+                    })?
+                    .compile_matcher();
+                let command = resolve_program(workspace_root, &config.program);
+                let content_hash = hash_program(&command);
+                let trusted = trusted_commands
+                    .get(command.to_string_lossy().as_ref())
+                    .filter(|entry| entry.hash == content_hash)
+                    .map(|entry| entry.trusted);
+                Ok(MergeDriver {
+                    pattern,
+                    matcher,
+                    program: config.program,
+                    command,
+                    content_hash,
+                    trusted,
+                })
+            })
+            .collect::<Result<_, MergeDriverLoadError>>()?;
+        Ok(Self {
+            drivers,
+            workspace_root: workspace_root.to_owned(),
+            repo_key,
+            trust_path,
+            skipped: Mutex::new(BTreeSet::new()),
+        })
+    }
+
+    /// All configured drivers including untrusted ones.
+    pub fn drivers(&self) -> &[MergeDriver] {
+        &self.drivers
+    }
+
+    /// Records whether the user trusts the `command` with its current content
+    /// in this repo.
+    pub fn set_trusted(
+        &mut self,
+        command: &Path,
+        trusted: bool,
+    ) -> Result<(), MergeDriverLoadError> {
+        let Some(hash) = self
+            .drivers
+            .iter()
+            .find(|driver| driver.command == command)
+            .map(|driver| driver.content_hash.clone())
+        else {
+            return Ok(());
+        };
+        let mut trust_file = load_trust_file(&self.trust_path)?;
+        trust_file
+            .repos
+            .entry(self.repo_key.clone())
+            .or_default()
+            .insert(
+                command.to_string_lossy().into_owned(),
+                TrustEntry { hash, trusted },
+            );
+        let text = toml_edit::ser::to_string_pretty(&trust_file)
+            .map_err(|err| MergeDriverLoadError::TrustFile(err.into()))?;
+        let dir = self.trust_path.parent().unwrap_or(Path::new("."));
+        let mut temp_file = tempfile::NamedTempFile::new_in(dir).context(dir)?;
+        temp_file
+            .write_all(text.as_bytes())
+            .context(temp_file.path())?;
+        persist_temp_file(temp_file, &self.trust_path).context(&self.trust_path)?;
+        for driver in &mut self.drivers {
+            if driver.command == command {
+                driver.trusted = Some(trusted);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the first trusted driver matching the `path`.
+    ///
+    /// Matching drivers which the user hasn't been asked about yet are
+    /// recorded, and can be retrieved by [`Self::take_skipped()`].
+    pub fn find(&self, path: &RepoPath) -> Option<&MergeDriver> {
+        let path = path.as_internal_file_string();
+        for (index, driver) in self.drivers.iter().enumerate() {
+            if !driver.matcher.is_match(path) {
+                continue;
+            }
+            match driver.trusted {
+                Some(true) => return Some(driver),
+                Some(false) => {}
+                None => {
+                    self.skipped.lock().unwrap().insert(index);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the drivers which would have run if they were trusted, and
+    /// clears the record.
+    pub fn take_skipped(&self) -> Vec<&MergeDriver> {
+        let skipped = mem::take(&mut *self.skipped.lock().unwrap());
+        skipped
+            .into_iter()
+            .map(|index| &self.drivers[index])
+            .collect()
+    }
+
+    /// Merges the file `contents` by the driver configured for the `path`.
+    ///
+    /// Returns `None` if there's no trusted driver for the path, the contents
+    /// aren't a 3-way merge, or the driver failed.
+    pub fn try_merge<T: AsRef<[u8]>>(
+        &self,
+        path: &RepoPath,
+        contents: &Merge<T>,
+    ) -> Option<Vec<u8>> {
+        if contents.num_sides() != 2 {
+            return None;
+        }
+        let driver = self.find(path)?;
+        let base = contents.get_remove(0)?.as_ref();
+        let left = contents.get_add(0)?.as_ref();
+        let right = contents.get_add(1)?.as_ref();
+        match self.run(driver, base, left, right) {
+            Ok(output) => output,
+            Err(err) => {
+                tracing::warn!(?err, program = driver.program, "failed to run merge driver");
+                None
+            }
+        }
+    }
+
+    fn run(
+        &self,
+        driver: &MergeDriver,
+        base: &[u8],
+        left: &[u8],
+        right: &[u8],
+    ) -> io::Result<Option<Vec<u8>>> {
+        let files = [base, left, right]
+            .into_iter()
+            .map(|content| {
+                let mut file = tempfile::NamedTempFile::new()?;
+                file.write_all(content)?;
+                file.flush()?;
+                Ok(file)
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let output = Command::new(&driver.command)
+            .args(files.iter().map(|file| file.path()))
+            .current_dir(&self.workspace_root)
+            .stdin(Stdio::null())
+            .output()?;
+        if output.status.success() {
+            Ok(Some(output.stdout))
+        } else {
+            tracing::info!(
+                status = ?output.status,
+                stderr = %String::from_utf8_lossy(&output.stderr),
+                program = driver.program,
+                "merge driver failed"
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Resolves relative paths like "./scripts/merge" against the workspace root,
+/// whereas bare command names are looked up in $PATH.
+fn resolve_program(workspace_root: &Path, program: &str) -> PathBuf {
+    let path = Path::new(program);
+    if path.is_relative() && path.components().count() > 1 {
+        workspace_root.join(path)
+    } else {
+        path.to_owned()
+    }
+}
+
+/// Hashes the content of the program, looking up bare command names in
+/// $PATH. Returns an empty string, which no file content hashes to, if the
+/// program can't be read.
+fn hash_program(command: &Path) -> String {
+    let path = if command.components().count() > 1 {
+        Some(command.to_owned())
+    } else {
+        env::var_os("PATH").and_then(|paths| {
+            env::split_paths(&paths)
+                .map(|dir| dir.join(command))
+                .find(|path| path.is_file())
+        })
+    };
+    match path.map(fs::read) {
+        Some(Ok(content)) => hex_util::encode_hex(&Blake2b512::digest(content)),
+        _ => String::new(),
+    }
+}
+
+fn load_trust_file(path: &Path) -> Result<TrustFile, MergeDriverLoadError> {
+    match fs::read_to_string(path) {
+        Ok(text) => toml_edit::de::from_str(&text)
+            .map_err(|err| MergeDriverLoadError::TrustFile(err.into())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(TrustFile::default()),
+        Err(err) => Err(MergeDriverLoadError::Io(PathError {
+            path: path.to_owned(),
+            source: err,
+        })),
+    }
+}
@@ -169,7 +169,18 @@ impl MergedTree {
     /// Tries to resolve any conflicts, resolving any conflicts that can be
     /// automatically resolved and leaving the rest unresolved.
     pub async fn resolve(self) -> BackendResult<Self> {
-        let merged = merge_trees(self.trees).await?;
+        self.resolve_impl(false).await
+    }
+
+    /// Like [`Self::resolve()`], but also lets the repo's merge drivers resolve
+    /// file conflicts. Since drivers run external programs, this should only be
+    /// used when rewriting commits.
+    pub async fn resolve_with_merge_drivers(self) -> BackendResult<Self> {
+        self.resolve_impl(true).await
+    }
+
+    async fn resolve_impl(self, use_merge_drivers: bool) -> BackendResult<Self> {
+        let merged = merge_trees(self.trees, use_merge_drivers).await?;
         // If the result can be resolved, then `merge_trees()` above would have returned
         // a resolved merge. However, that function will always preserve the arity of
         // conflicts it cannot resolve. So we simplify the conflict again
@@ -179,7 +190,9 @@ impl MergedTree {
         // particular,  that this last simplification doesn't enable further automatic
         // resolutions
         if cfg!(debug_assertions) {
-            let re_merged = merge_trees(simplified.clone()).await.unwrap();
+            let re_merged = merge_trees(simplified.clone(), use_merge_drivers)
+                .await
+                .unwrap();
             debug_assert_eq!(re_merged, simplified);
         }
         Ok(Self { trees: simplified })
@@ -367,6 +380,14 @@ impl MergedTree {
         self.merge_no_resolve(base, other).resolve().await
     }
 
+    /// Like [`Self::merge()`], but also lets the repo's merge drivers resolve
+    /// file conflicts. See [`Self::resolve_with_merge_drivers()`].
+    pub async fn merge_with_merge_drivers(self, base: Self, other: Self) -> BackendResult<Self> {
+        self.merge_no_resolve(base, other)
+            .resolve_with_merge_drivers()
+            .await
+    }
+
     /// Merges this tree with `other`, using `base` as base, without attempting
     /// to resolve file conflicts.
     pub fn merge_no_resolve(self, base: Self, other: Self) -> Self {
@@ -577,6 +598,14 @@ struct TreeMerger {
     work: FuturesUnordered<BoxFuture<'static, TreeMergerWorkOutput>>,
     // Futures we haven't started polling yet, in order to respect the backend's concurrency limit.
     unstarted_work: BTreeMap<TreeMergeWorkItemKey, BoxFuture<'static, TreeMergerWorkOutput>>,
+    // Input tree ids of directories being merged, to cache the result under once written.
+    cache_keys: BTreeMap<RepoPathBuf, Merge<TreeId>>,
+    // Number of directories merged, and how many of them were found in the cache.
+    num_dirs_merged: usize,
+    num_cache_hits: usize,
+    // Whether file conflicts are passed to the merge drivers. The results then
+    // depend on the drivers, so the tree merge cache isn't used.
+    use_merge_drivers: bool,
 }
 
 impl TreeMerger {
@@ -585,14 +614,35 @@ impl TreeMerger {
             match work_item {
                 TreeMergerWorkOutput::ReadTrees { dir, result } => {
                     let tree = result?;
-                    self.process_tree(dir, tree);
+                    let tree_ids = tree.map(|tree| tree.id().clone());
+                    self.num_dirs_merged += 1;
+                    if self.use_merge_drivers {
+                        self.process_tree(dir, tree);
+                    } else if let Some(merged_ids) =
+                        self.store.get_cached_tree_merge(&dir, &tree_ids)
+                    {
+                        self.num_cache_hits += 1;
+                        self.enqueue_cached_tree(dir, merged_ids);
+                    } else {
+                        self.cache_keys.insert(dir.clone(), tree_ids);
+                        self.process_tree(dir, tree);
+                    }
                 }
                 TreeMergerWorkOutput::WrittenTrees { dir, result } => {
                     let tree = result?;
+                    if let Some(tree_ids) = self.cache_keys.remove(&dir) {
+                        let merged_ids = tree.map(|tree| tree.id().clone());
+                        self.store.cache_tree_merge(&dir, tree_ids, merged_ids);
+                    }
                     if dir.is_root() {
                         assert!(self.trees_to_resolve.is_empty());
                         assert!(self.work.is_empty());
                         assert!(self.unstarted_work.is_empty());
+                        tracing::debug!(
+                            dirs_merged = self.num_dirs_merged,
+                            cache_hits = self.num_cache_hits,
+                            "merged trees"
+                        );
                         return Ok(tree);
                     }
                     // Propagate the write to the parent tree, replacing empty trees by `None`.
@@ -670,6 +720,19 @@ impl TreeMerger {
         }
     }
 
+    fn enqueue_cached_tree(&mut self, dir: RepoPathBuf, merged_ids: Merge<TreeId>) {
+        let store = self.store.clone();
+        let tree_dir = dir.clone();
+        let work_fut = async move {
+            merged_ids
+                .try_map_async(|id| store.get_tree_async(tree_dir.clone(), id))
+                .await
+        }
+        .map(|result| TreeMergerWorkOutput::WrittenTrees { dir, result });
+        // Like tree writes, this completes a directory so it bypasses the queue.
+        self.work.push(Box::pin(work_fut));
+    }
+
     fn enqueue_tree_write(&mut self, dir: RepoPathBuf, backend_trees: Merge<backend::Tree>) {
         let work_fut = write_trees(self.store.clone(), dir.clone(), backend_trees)
             .map(|result| TreeMergerWorkOutput::WrittenTrees { dir, result });
@@ -680,8 +743,13 @@ impl TreeMerger {
 
     fn enqueue_file_merge(&mut self, path: RepoPathBuf, value: MergedTreeValue) {
         let key = TreeMergeWorkItemKey::MergeFiles { path: path.clone() };
-        let work_fut = resolve_file_values_owned(self.store.clone(), path.clone(), value)
-            .map(|result| TreeMergerWorkOutput::MergedFiles { path, result });
+        let work_fut = resolve_file_values_owned(
+            self.store.clone(),
+            path.clone(),
+            value,
+            self.use_merge_drivers,
+        )
+        .map(|result| TreeMergerWorkOutput::MergedFiles { path, result });
         if self.work.len() < self.store.concurrency() {
             self.work.push(Box::pin(work_fut));
         } else {
@@ -734,25 +802,31 @@ async fn resolve_file_values_owned(
     store: Arc<Store>,
     path: RepoPathBuf,
     values: MergedTreeValue,
+    use_merge_drivers: bool,
 ) -> BackendResult<MergedTreeValue> {
-    let maybe_resolved = try_resolve_file_values(&store, &path, &values).await?;
+    let maybe_resolved = try_resolve_file_values(&store, &path, &values, use_merge_drivers).await?;
     Ok(maybe_resolved.unwrap_or(values))
 }
 
 /// The returned conflict will either be resolved or have the same number of
 /// sides as the input.
-async fn merge_trees(merge: Merge<Tree>) -> BackendResult<Merge<Tree>> {
+async fn merge_trees(merge: Merge<Tree>, use_merge_drivers: bool) -> BackendResult<Merge<Tree>> {
     let merge = match merge.into_resolved() {
         Ok(tree) => return Ok(Merge::resolved(tree)),
         Err(merge) => merge,
     };
 
     let store = merge.first().store().clone();
+    let use_merge_drivers = use_merge_drivers && !store.merge_drivers().drivers().is_empty();
     let merger = TreeMerger {
         store,
         trees_to_resolve: BTreeMap::new(),
         work: FuturesUnordered::new(),
         unstarted_work: BTreeMap::new(),
+        cache_keys: BTreeMap::new(),
+        num_dirs_merged: 0,
+        num_cache_hits: 0,
+        use_merge_drivers,
     };
     merger.work.push(Box::pin(std::future::ready(
         TreeMergerWorkOutput::ReadTrees {
@@ -775,7 +849,7 @@ pub async fn resolve_file_values(
         return Ok(Merge::resolved(resolved.clone()));
     }
 
-    let maybe_resolved = try_resolve_file_values(store, path, &values).await?;
+    let maybe_resolved = try_resolve_file_values(store, path, &values, false).await?;
     Ok(maybe_resolved.unwrap_or(values))
 }
 
@@ -783,6 +857,7 @@ async fn try_resolve_file_values<T: Borrow<TreeValue>>(
     store: &Arc<Store>,
     path: &RepoPath,
     values: &Merge<Option<T>>,
+    use_merge_drivers: bool,
 ) -> BackendResult<Option<MergedTreeValue>> {
     // The values may contain trees canceling each other (notably padded absent
     // trees), so we need to simplify them first.
@@ -791,7 +866,10 @@ async fn try_resolve_file_values<T: Borrow<TreeValue>>(
         .simplify();
     // No fast path for simplified.is_resolved(). If it could be resolved, it would
     // have been caught by values.resolve_trivial() above.
-    if let Some(resolved) = try_resolve_file_conflict(store, path, &simplified).await? {
+    let merge_drivers = use_merge_drivers.then(|| store.merge_drivers());
+    if let Some(resolved) =
+        try_resolve_file_conflict(store, path, &simplified, merge_drivers).await?
+    {
         Ok(Some(Merge::normal(resolved)))
     } else {
         // Failed to merge the files, or the paths are not files
@@ -26,6 +26,7 @@ use std::time::SystemTime;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::backend::MillisSinceEpoch;
 use crate::backend::Timestamp;
@@ -42,6 +43,7 @@ use crate::ref_name::RemoteName;
 use crate::ref_name::RemoteNameBuf;
 use crate::ref_name::RemoteRefSymbol;
 use crate::ref_name::WorkspaceNameBuf;
+use crate::repo_path::RepoPathBuf;
 
 id_type!(pub ViewId { hex() });
 id_type!(pub OperationId { hex() });
@@ -245,7 +247,7 @@ pub struct BookmarkTarget<'a> {
 
 /// Represents the way the repo looks at a given time, just like how a Tree
 /// object represents how the file system looks at a given time.
-#[derive(ContentHash, PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub struct View {
     /// All head commits
     pub head_ids: HashSet<CommitId>,
@@ -261,6 +263,48 @@ pub struct View {
     // (.jj/working_copy/) has the source of truth about which commit *is* checked out (to be
     // precise: the commit to which we most recently completed an update to).
     pub wc_commit_ids: BTreeMap<WorkspaceNameBuf, CommitId>,
+    /// Descriptions of local bookmarks. Only present bookmarks have an entry.
+    pub bookmark_descriptions: BTreeMap<RefNameBuf, String>,
+    /// Notes attached to changes, keyed by change id and then by note key.
+    /// Empty note maps aren't stored.
+    pub notes: BTreeMap<ChangeId, BTreeMap<String, String>>,
+    /// User-defined names of changes.
+    pub change_names: BTreeMap<String, ChangeId>,
+}
+
+impl ContentHash for View {
+    fn hash(&self, state: &mut impl crate::content_hash::DigestUpdate) {
+        let Self {
+            head_ids,
+            local_bookmarks,
+            tags,
+            remote_views,
+            git_refs,
+            git_head,
+            wc_commit_ids,
+            bookmark_descriptions,
+            notes,
+            change_names,
+        } = self;
+        head_ids.hash(state);
+        local_bookmarks.hash(state);
+        tags.hash(state);
+        remote_views.hash(state);
+        git_refs.hash(state);
+        git_head.hash(state);
+        wc_commit_ids.hash(state);
+        // Only hashed if set so the ids of views without bookmark descriptions
+        // stay the same as before the field was added.
+        if !bookmark_descriptions.is_empty() {
+            bookmark_descriptions.hash(state);
+        }
+        if !notes.is_empty() {
+            notes.hash(state);
+        }
+        if !change_names.is_empty() {
+            change_names.hash(state);
+        }
+    }
 }
 
 impl View {
@@ -277,6 +321,9 @@ impl View {
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
             wc_commit_ids: BTreeMap::new(),
+            bookmark_descriptions: BTreeMap::new(),
+            notes: BTreeMap::new(),
+            change_names: BTreeMap::new(),
         }
     }
 
@@ -290,6 +337,9 @@ impl View {
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
             wc_commit_ids: BTreeMap::new(),
+            bookmark_descriptions: BTreeMap::new(),
+            notes: BTreeMap::new(),
+            change_names: BTreeMap::new(),
         }
     }
 }
@@ -414,6 +464,8 @@ impl Operation {
             username: "".to_string(),
             is_snapshot: false,
             tags: HashMap::new(),
+            workspace_name: None,
+            cwd: None,
         };
         Self {
             view_id: root_view_id,
@@ -439,6 +491,11 @@ pub struct OperationMetadata {
     /// copy.
     pub is_snapshot: bool,
     pub tags: HashMap<String, String>,
+    /// Name of the workspace the operation was made from, if known.
+    pub workspace_name: Option<WorkspaceNameBuf>,
+    /// Working directory of the command that made the operation, relative to
+    /// the workspace root. `None` if unknown or outside the workspace.
+    pub cwd: Option<RepoPathBuf>,
 }
 
 /// Data to be loaded into the root operation/view.
@@ -48,6 +48,11 @@ pub struct TreeState {
     pub sparse_patterns: ::core::option::Option<SparsePatterns>,
     #[prost(message, optional, tag = "4")]
     pub watchman_clock: ::core::option::Option<WatchmanClock>,
+    /// Finest timestamp resolution observed on the filesystem holding the working
+    /// copy, or 0 if unknown. Files modified within this window of the tree state
+    /// are considered "racy" and re-hashed on snapshot.
+    #[prost(uint64, tag = "7")]
+    pub mtime_granularity_millis: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WatchmanClock {
@@ -86,6 +86,16 @@ pub struct Tag {
     pub target: ::core::option::Option<RefTarget>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeNotes {
+    #[prost(bytes = "vec", tag = "1")]
+    pub change_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(map = "string, string", tag = "2")]
+    pub notes: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct View {
     #[prost(bytes = "vec", repeated, tag = "1")]
     pub head_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
@@ -112,6 +122,21 @@ pub struct View {
     pub git_head_legacy: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "9")]
     pub git_head: ::core::option::Option<RefTarget>,
+    /// Descriptions of local bookmarks, keyed by bookmark name.
+    #[prost(map = "string, string", tag = "11")]
+    pub bookmark_descriptions: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+    /// Notes attached to changes. Not exported to Git.
+    #[prost(message, repeated, tag = "12")]
+    pub notes: ::prost::alloc::vec::Vec<ChangeNotes>,
+    /// User-defined names of changes, mapped to change ids.
+    #[prost(map = "string, bytes", tag = "13")]
+    pub change_names: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::vec::Vec<u8>,
+    >,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Operation {
@@ -155,6 +180,13 @@ pub struct OperationMetadata {
         ::prost::alloc::string::String,
         ::prost::alloc::string::String,
     >,
+    /// Name of the workspace the command was run in. Unset if unknown.
+    #[prost(string, optional, tag = "8")]
+    pub workspace_name: ::core::option::Option<::prost::alloc::string::String>,
+    /// Working directory of the command, relative to the workspace root. Unset if
+    /// unknown or outside the workspace.
+    #[prost(string, optional, tag = "9")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CommitPredecessors {
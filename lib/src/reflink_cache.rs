@@ -0,0 +1,193 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cache of file contents used to materialize working-copy files as
+//! copy-on-write clones (reflinks) instead of writing their bytes.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use crate::backend::FileId;
+use crate::eol::EolConversionMode;
+use crate::file_util::create_or_reuse_dir;
+use crate::file_util::reflink_file;
+use crate::local_working_copy::TreeStateSettings;
+use crate::object_id::ObjectId as _;
+
+/// Creates the reflink cache stored in `state_path` if enabled by the
+/// settings.
+///
+/// Reflinks aren't used if EOL conversion is enabled since the converted
+/// contents can differ from the contents stored in the backend.
+pub(crate) fn create_reflink_cache(
+    tree_state_settings: &TreeStateSettings,
+    state_path: &Path,
+) -> Option<ReflinkCache> {
+    (tree_state_settings.use_reflinks
+        && tree_state_settings.eol_conversion_mode == EolConversionMode::None)
+        .then(|| ReflinkCache::new(state_path.join("reflink_cache")))
+}
+
+/// Content-addressed store of files which can be cloned into the working copy.
+///
+/// All operations fall back silently: if the filesystem doesn't support
+/// reflinks, the cache disables itself and files are written normally.
+#[derive(Debug)]
+pub(crate) struct ReflinkCache {
+    dir: PathBuf,
+    disabled: AtomicBool,
+}
+
+impl ReflinkCache {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            disabled: AtomicBool::new(false),
+        }
+    }
+
+    fn path_for(&self, id: &FileId) -> PathBuf {
+        self.dir.join(id.hex())
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.disabled.load(Ordering::Relaxed)
+    }
+
+    fn disable(&self, err: &io::Error) {
+        tracing::debug!(?err, dir = ?self.dir, "disabling reflink cache");
+        self.disabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Creates a new file at `disk_path` by cloning the cached contents of
+    /// `id`. Returns `false` if the contents aren't cached or can't be cloned,
+    /// in which case no file is created.
+    pub fn clone_to(&self, id: &FileId, disk_path: &Path) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        match reflink_file(&self.path_for(id), disk_path) {
+            Ok(()) => true,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+            Err(err) => {
+                self.disable(&err);
+                false
+            }
+        }
+    }
+
+    /// Adds the contents of the file at `disk_path` to the cache as `id` by
+    /// cloning the file.
+    pub fn insert_from(&self, id: &FileId, disk_path: &Path) {
+        if self.is_disabled() {
+            return;
+        }
+        let cache_path = self.path_for(id);
+        let temp_path = self.dir.join(format!("{}.{}.tmp", id.hex(), process::id()));
+        let result = create_or_reuse_dir(&self.dir)
+            .and_then(|()| reflink_file(disk_path, &temp_path))
+            .and_then(|()| {
+                fs::rename(&temp_path, &cache_path).inspect_err(|_| {
+                    fs::remove_file(&temp_path).ok();
+                })
+            });
+        if let Err(err) = result {
+            self.disable(&err);
+        }
+    }
+
+    /// Removes the cached contents other than the `referenced` ones.
+    ///
+    /// Failures are ignored since the cache can be deleted at any time.
+    pub fn retain(&self, referenced: &HashSet<FileId>) {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return,
+            Err(err) => {
+                tracing::debug!(?err, dir = ?self.dir, "failed to list reflink cache");
+                return;
+            }
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            // Skip temporary files, which may be in use by another process
+            let Some(id) = name.to_str().and_then(FileId::try_from_hex) else {
+                continue;
+            };
+            if !referenced.contains(&id) {
+                if let Err(err) = fs::remove_file(entry.path()) {
+                    tracing::debug!(?err, ?name, "failed to remove reflink cache entry");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::new_temp_dir;
+
+    #[test]
+    fn test_reflink_cache_roundtrip_or_fallback() {
+        let temp_dir = new_temp_dir();
+        let cache = ReflinkCache::new(temp_dir.path().join("cache"));
+        let id = FileId::new(vec![0xab; 20]);
+        let source_path = temp_dir.path().join("source");
+        let dest_path = temp_dir.path().join("dest");
+        fs::write(&source_path, b"contents").unwrap();
+
+        // Nothing is cached yet
+        assert!(!cache.clone_to(&id, &dest_path));
+        assert!(!dest_path.exists());
+
+        cache.insert_from(&id, &source_path);
+        if cache.clone_to(&id, &dest_path) {
+            assert_eq!(fs::read(&dest_path).unwrap(), b"contents");
+            assert!(!cache.is_disabled());
+        } else {
+            // The filesystem doesn't support reflinks
+            assert!(cache.is_disabled());
+            assert!(!dest_path.exists());
+            assert!(!cache.path_for(&id).exists());
+        }
+    }
+
+    #[test]
+    fn test_reflink_cache_retain() {
+        let temp_dir = new_temp_dir();
+        let cache = ReflinkCache::new(temp_dir.path().join("cache"));
+        let kept_id = FileId::new(vec![0xab; 20]);
+        let removed_id = FileId::new(vec![0xcd; 20]);
+        let temp_path = cache.dir.join(format!("{}.123.tmp", removed_id.hex()));
+
+        // Nothing is cached yet
+        cache.retain(&HashSet::new());
+
+        fs::create_dir(&cache.dir).unwrap();
+        fs::write(cache.path_for(&kept_id), b"kept").unwrap();
+        fs::write(cache.path_for(&removed_id), b"removed").unwrap();
+        fs::write(&temp_path, b"temp").unwrap();
+        cache.retain(&HashSet::from([kept_id.clone()]));
+        assert!(cache.path_for(&kept_id).exists());
+        assert!(!cache.path_for(&removed_id).exists());
+        assert!(temp_path.exists());
+    }
+}
@@ -16,6 +16,7 @@
 
 use itertools::EitherOrBoth;
 
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::index::Index;
 use crate::merge::Merge;
@@ -86,6 +87,38 @@ pub fn diff_named_commit_ids<'a, 'b, K: Ord>(
     .filter(|(_, (target1, target2))| target1 != target2)
 }
 
+/// Compares `ids1` and `ids2` change ids, yields entry if they differ.
+///
+/// `ids1` and `ids2` must be sorted by `K`.
+pub fn diff_named_change_ids<'a, 'b, K: Ord>(
+    ids1: impl IntoIterator<Item = (K, &'a ChangeId)>,
+    ids2: impl IntoIterator<Item = (K, &'b ChangeId)>,
+) -> impl Iterator<Item = (K, (Option<&'a ChangeId>, Option<&'b ChangeId>))> {
+    iter_named_pairs(
+        ids1.into_iter().map(|(k, v)| (k, Some(v))),
+        ids2.into_iter().map(|(k, v)| (k, Some(v))),
+        || None,
+        || None,
+    )
+    .filter(|(_, (id1, id2))| id1 != id2)
+}
+
+/// Compares `descriptions1` and `descriptions2`, yields entry if they differ.
+///
+/// `descriptions1` and `descriptions2` must be sorted by `K`.
+pub fn diff_named_descriptions<'a, 'b, K: Ord>(
+    descriptions1: impl IntoIterator<Item = (K, &'a str)>,
+    descriptions2: impl IntoIterator<Item = (K, &'b str)>,
+) -> impl Iterator<Item = (K, (Option<&'a str>, Option<&'b str>))> {
+    iter_named_pairs(
+        descriptions1.into_iter().map(|(k, v)| (k, Some(v))),
+        descriptions2.into_iter().map(|(k, v)| (k, Some(v))),
+        || None,
+        || None,
+    )
+    .filter(|(_, (description1, description2))| description1 != description2)
+}
+
 fn iter_named_pairs<K: Ord, V1, V2>(
     refs1: impl IntoIterator<Item = (K, V1)>,
     refs2: impl IntoIterator<Item = (K, V2)>,
@@ -58,6 +58,8 @@ use crate::index::MutableIndex;
 use crate::index::ReadonlyIndex;
 use crate::merge::MergeBuilder;
 use crate::merge::trivial_merge;
+use crate::merge_driver::MergeDriverLoadError;
+use crate::merge_driver::MergeDrivers;
 use crate::object_id::HexPrefix;
 use crate::object_id::PrefixResolution;
 use crate::op_heads_store;
@@ -80,7 +82,9 @@ use crate::ref_name::RemoteName;
 use crate::ref_name::RemoteRefSymbol;
 use crate::ref_name::WorkspaceName;
 use crate::ref_name::WorkspaceNameBuf;
+use crate::refs::diff_named_change_ids;
 use crate::refs::diff_named_commit_ids;
+use crate::refs::diff_named_descriptions;
 use crate::refs::diff_named_ref_targets;
 use crate::refs::diff_named_remote_refs;
 use crate::refs::merge_ref_targets;
@@ -202,7 +206,7 @@ impl ReadonlyRepo {
         let backend = backend_initializer(settings, &store_path)?;
         let backend_path = store_path.join("type");
         fs::write(&backend_path, backend.name()).context(&backend_path)?;
-        let store = Store::new(backend, signer);
+        let store = Store::new(backend, signer, MergeDrivers::default());
 
         let op_store_path = repo_path.join("op_store");
         fs::create_dir(&op_store_path).context(&op_store_path)?;
@@ -474,6 +478,8 @@ pub enum StoreLoadError {
     Backend(#[from] BackendLoadError),
     #[error(transparent)]
     Signing(#[from] SignInitError),
+    #[error(transparent)]
+    MergeDrivers(#[from] MergeDriverLoadError),
 }
 
 impl StoreFactories {
@@ -673,10 +679,37 @@ impl RepoLoader {
         settings: &UserSettings,
         repo_path: &Path,
         store_factories: &StoreFactories,
+    ) -> Result<Self, StoreLoadError> {
+        Self::init_with_merge_drivers(
+            settings,
+            repo_path,
+            store_factories,
+            MergeDrivers::default(),
+        )
+    }
+
+    /// Like [`Self::init_from_file_system()`], but also loads the merge
+    /// drivers configured for the repo, which run in `workspace_root`.
+    pub fn init_for_workspace(
+        settings: &UserSettings,
+        repo_path: &Path,
+        workspace_root: &Path,
+        store_factories: &StoreFactories,
+    ) -> Result<Self, StoreLoadError> {
+        let merge_drivers = MergeDrivers::load(settings, repo_path, workspace_root)?;
+        Self::init_with_merge_drivers(settings, repo_path, store_factories, merge_drivers)
+    }
+
+    fn init_with_merge_drivers(
+        settings: &UserSettings,
+        repo_path: &Path,
+        store_factories: &StoreFactories,
+        merge_drivers: MergeDrivers,
     ) -> Result<Self, StoreLoadError> {
         let store = Store::new(
             store_factories.load_backend(settings, &repo_path.join("store"))?,
             Signer::from_settings(settings)?,
+            merge_drivers,
         );
         let root_op_data = RootOperationData {
             root_commit_id: store.root_commit_id().clone(),
@@ -1645,6 +1678,83 @@ impl MutableRepo {
         self.view.mark_dirty();
     }
 
+    pub fn get_local_bookmark_description(&self, name: &RefName) -> Option<String> {
+        self.view
+            .with_ref(|v| v.get_local_bookmark_description(name).map(str::to_owned))
+    }
+
+    /// Sets the description of the local bookmark. An empty description
+    /// removes it.
+    pub fn set_local_bookmark_description(&mut self, name: &RefName, description: String) {
+        self.view_mut()
+            .set_local_bookmark_description(name, description);
+        self.view.mark_dirty();
+    }
+
+    fn merge_local_bookmark_description(
+        &mut self,
+        name: &RefName,
+        base_description: Option<&str>,
+        other_description: Option<&str>,
+    ) {
+        let self_description = self.get_local_bookmark_description(name);
+        // Keep our description if both sides changed it.
+        if self_description.as_deref() == base_description {
+            let new_description = other_description.unwrap_or_default().to_owned();
+            self.set_local_bookmark_description(name, new_description);
+        }
+    }
+
+    pub fn get_note(&self, change_id: &ChangeId, key: &str) -> Option<String> {
+        self.view
+            .with_ref(|v| v.get_note(change_id, key).map(str::to_owned))
+    }
+
+    /// Sets the note with the given key attached to the change. An empty value
+    /// removes it.
+    pub fn set_note(&mut self, change_id: &ChangeId, key: &str, value: String) {
+        self.view_mut().set_note(change_id, key, value);
+        self.view.mark_dirty();
+    }
+
+    fn merge_note(
+        &mut self,
+        change_id: &ChangeId,
+        key: &str,
+        base_value: Option<&str>,
+        other_value: Option<&str>,
+    ) {
+        let self_value = self.get_note(change_id, key);
+        // Keep our value if both sides changed it.
+        if self_value.as_deref() == base_value {
+            let new_value = other_value.unwrap_or_default().to_owned();
+            self.set_note(change_id, key, new_value);
+        }
+    }
+
+    pub fn get_change_name(&self, name: &str) -> Option<ChangeId> {
+        self.view.with_ref(|v| v.get_change_name(name).cloned())
+    }
+
+    /// Sets the change the given name refers to. `None` removes the name.
+    pub fn set_change_name(&mut self, name: &str, change_id: Option<ChangeId>) {
+        self.view_mut().set_change_name(name, change_id);
+        self.view.mark_dirty();
+    }
+
+    fn merge_change_name(
+        &mut self,
+        name: &str,
+        base_change_id: Option<&ChangeId>,
+        other_change_id: Option<&ChangeId>,
+    ) {
+        let self_change_id = self.get_change_name(name);
+        // Keep our change if both sides changed the name.
+        if self_change_id.as_ref() == base_change_id {
+            self.set_change_name(name, other_change_id.cloned());
+        }
+    }
+
     pub fn merge_local_bookmark(
         &mut self,
         name: &RefName,
@@ -1813,6 +1923,24 @@ impl MutableRepo {
             self.merge_local_bookmark(name, base_target, other_target);
         }
 
+        let changed_bookmark_descriptions = diff_named_descriptions(
+            base.local_bookmark_descriptions(),
+            other.local_bookmark_descriptions(),
+        );
+        for (name, (base_description, other_description)) in changed_bookmark_descriptions {
+            self.merge_local_bookmark_description(name, base_description, other_description);
+        }
+
+        let changed_notes = diff_named_descriptions(base.all_notes(), other.all_notes());
+        for ((change_id, key), (base_value, other_value)) in changed_notes {
+            self.merge_note(change_id, key, base_value, other_value);
+        }
+
+        let changed_change_names = diff_named_change_ids(base.change_names(), other.change_names());
+        for (name, (base_change_id, other_change_id)) in changed_change_names {
+            self.merge_change_name(name, base_change_id, other_change_id);
+        }
+
         let changed_tags = diff_named_ref_targets(base.tags(), other.tags());
         for (name, (base_target, other_target)) in changed_tags {
             self.merge_tag(name, base_target, other_target);
@@ -92,6 +92,8 @@ pub enum RevsetResolutionError {
         symbol: String,
         targets: Vec<CommitId>,
     },
+    #[error("Change name `{name}` refers to a change with no visible revisions")]
+    ChangeNameHidden { name: String },
     #[error("Name `{symbol}` is conflicted")]
     ConflictedRef {
         kind: &'static str,
@@ -175,6 +177,8 @@ pub enum RevsetFilterPredicate {
     ParentCount(Range<u32>),
     /// Commits with description matching the pattern.
     Description(StringPattern),
+    /// Commits with empty or whitespace-only description.
+    EmptyDescription,
     /// Commits with first line of the description matching the pattern.
     Subject(StringPattern),
     /// Commits with author name matching the pattern.
@@ -198,6 +202,8 @@ pub enum RevsetFilterPredicate {
     },
     /// Commits with conflicts
     HasConflict,
+    /// Commits with conflicts at the paths specified by the fileset.
+    HasConflictInFiles(FilesetExpression),
     /// Commits that are cryptographically signed.
     Signed,
     /// Custom predicates provided by extensions
@@ -612,6 +618,22 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
             _ => None,
         }
     }
+
+    /// Returns the names of all symbols referenced in this expression.
+    pub fn symbols(self: &Rc<Self>) -> Vec<String> {
+        let mut symbols = Vec::new();
+        transform_expression(
+            self,
+            |expression| {
+                if let Some(name) = expression.as_symbol() {
+                    symbols.push(name.to_owned());
+                }
+                ControlFlow::Continue(())
+            },
+            |_| None,
+        );
+        symbols
+    }
 }
 
 impl UserRevsetExpression {
@@ -940,6 +962,12 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
             RevsetFilterPredicate::Description(pattern),
         ))
     });
+    map.insert("empty_description", |_diagnostics, function, _context| {
+        function.expect_no_arguments()?;
+        Ok(RevsetExpression::filter(
+            RevsetFilterPredicate::EmptyDescription,
+        ))
+    });
     map.insert("subject", |diagnostics, function, _context| {
         let [arg] = function.expect_exact_arguments()?;
         let pattern = expect_string_pattern(diagnostics, arg)?;
@@ -1049,9 +1077,21 @@ static BUILTIN_FUNCTION_MAP: LazyLock<HashMap<&str, RevsetFunction>> = LazyLock:
             RevsetFilterPredicate::DiffContains { text, files },
         ))
     });
-    map.insert("conflicts", |_diagnostics, function, _context| {
-        function.expect_no_arguments()?;
-        Ok(RevsetExpression::filter(RevsetFilterPredicate::HasConflict))
+    map.insert("conflicts", |diagnostics, function, context| {
+        let ([], [files_opt_arg]) = function.expect_arguments()?;
+        let Some(files_arg) = files_opt_arg else {
+            return Ok(RevsetExpression::filter(RevsetFilterPredicate::HasConflict));
+        };
+        let ctx = context.workspace.as_ref().ok_or_else(|| {
+            RevsetParseError::with_span(
+                RevsetParseErrorKind::FsPathWithoutWorkspace,
+                files_arg.span,
+            )
+        })?;
+        let files = expect_fileset_expression(diagnostics, files_arg, ctx.path_converter)?;
+        Ok(RevsetExpression::filter(
+            RevsetFilterPredicate::HasConflictInFiles(files),
+        ))
     });
     map.insert("present", |diagnostics, function, context| {
         let [arg] = function.expect_exact_arguments()?;
@@ -2559,8 +2599,36 @@ impl PartialSymbolResolver for GitRefResolver {
     }
 }
 
-const DEFAULT_RESOLVERS: &[&dyn PartialSymbolResolver] =
-    &[&TagResolver, &BookmarkResolver, &GitRefResolver];
+struct ChangeNameResolver;
+
+impl PartialSymbolResolver for ChangeNameResolver {
+    fn resolve_symbol(
+        &self,
+        repo: &dyn Repo,
+        symbol: &str,
+    ) -> Result<Option<CommitId>, RevsetResolutionError> {
+        let Some(change_id) = repo.view().get_change_name(symbol) else {
+            return Ok(None);
+        };
+        match repo.resolve_change_id(change_id) {
+            Some(targets) if targets.len() == 1 => Ok(targets.into_iter().next()),
+            Some(targets) => Err(RevsetResolutionError::DivergentChangeId {
+                symbol: change_id.reverse_hex(),
+                targets,
+            }),
+            None => Err(RevsetResolutionError::ChangeNameHidden {
+                name: symbol.to_owned(),
+            }),
+        }
+    }
+}
+
+const DEFAULT_RESOLVERS: &[&dyn PartialSymbolResolver] = &[
+    &TagResolver,
+    &BookmarkResolver,
+    &GitRefResolver,
+    &ChangeNameResolver,
+];
 
 struct CommitPrefixResolver<'a> {
     context_repo: &'a dyn Repo,
@@ -2852,7 +2920,8 @@ impl ExpressionStateFolder<UserExpressionState, ResolvedExpressionState>
             RevsetExpression::Present(candidates) => {
                 self.fold_expression(candidates).or_else(|err| match err {
                     RevsetResolutionError::NoSuchRevision { .. }
-                    | RevsetResolutionError::WorkspaceMissingWorkingCopy { .. } => {
+                    | RevsetResolutionError::WorkspaceMissingWorkingCopy { .. }
+                    | RevsetResolutionError::ChangeNameHidden { .. } => {
                         Ok(RevsetExpression::none())
                     }
                     RevsetResolutionError::EmptyString
@@ -26,20 +26,29 @@ use indexmap::IndexMap;
 use indexmap::IndexSet;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
+use tokio::io::AsyncReadExt as _;
 use tracing::instrument;
 
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::CommitId;
+use crate::backend::FileId;
 use crate::backend::MergedTreeId;
+use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::commit::CommitIteratorExt as _;
 use crate::commit_builder::CommitBuilder;
+use crate::copies::merge_trees_following_renames;
+use crate::dag_walk;
+use crate::diff::Diff;
+use crate::diff::DiffHunkKind;
 use crate::index::Index;
 use crate::index::IndexError;
+use crate::matchers::EverythingMatcher;
 use crate::matchers::Matcher;
 use crate::matchers::Visit;
 use crate::merge::Merge;
+use crate::merge::MergedTreeValue;
 use crate::merged_tree::MergedTree;
 use crate::merged_tree::MergedTreeBuilder;
 use crate::merged_tree::TreeDiffEntry;
@@ -275,10 +284,29 @@ impl<'repo> CommitRewriter<'repo> {
             let old_tree_fut = self.old_commit.tree_async();
             let (old_base_tree, new_base_tree, old_tree) =
                 try_join!(old_base_tree_fut, new_base_tree_fut, old_tree_fut)?;
-            (
-                old_base_tree.id() == *self.old_commit.tree_id(),
-                new_base_tree.merge(old_base_tree, old_tree).await?.id(),
-            )
+            let was_empty = old_base_tree.id() == *self.old_commit.tree_id();
+            let new_tree = match (&old_parents[..], &new_parents[..]) {
+                ([old_parent], [new_parent])
+                    if self.mut_repo.base_repo().settings().merge_detect_renames() =>
+                {
+                    merge_trees_following_renames(
+                        self.mut_repo.store(),
+                        old_parent.id(),
+                        old_base_tree,
+                        new_parent.id(),
+                        new_base_tree,
+                        self.old_commit.id(),
+                        old_tree,
+                    )
+                    .await?
+                }
+                _ => {
+                    new_base_tree
+                        .merge_with_merge_drivers(old_base_tree, old_tree)
+                        .await?
+                }
+            };
+            (was_empty, new_tree.id())
         };
         // Ensure we don't abandon commits with multiple parents (merge commits), even
         // if they're empty.
@@ -484,6 +512,171 @@ impl ComputedMoveCommits {
     ) -> BackendResult<MoveCommitsStats> {
         apply_move_commits(mut_repo, self, options)
     }
+
+    /// Predicts which commits [`Self::apply()`] would rebase or abandon
+    /// without writing any commits. The commits are returned in the order
+    /// they would be rebased, parents first.
+    ///
+    /// If `compute_trees` is true, or if empty commits are to be abandoned,
+    /// the trees of the rebased commits are merged, which may write trees to
+    /// the store. Renames aren't followed when merging, and
+    /// `options.simplify_ancestor_merge` isn't taken into account.
+    pub fn plan(
+        &self,
+        repo: &dyn Repo,
+        options: &RebaseOptions,
+        compute_trees: bool,
+    ) -> BackendResult<Vec<PlannedRebase>> {
+        let compute_trees = compute_trees || options.empty != EmptyBehavior::Keep;
+        let commits_by_id: HashMap<&CommitId, &Commit> = self
+            .descendants
+            .iter()
+            .map(|commit| (commit.id(), commit))
+            .collect();
+        let planned_parent_ids = |commit: &Commit| -> Vec<CommitId> {
+            self.commit_new_parents_map
+                .get(commit.id())
+                .map_or(commit.parent_ids(), |parent_ids| parent_ids)
+                .to_vec()
+        };
+        let ordered = dag_walk::topo_order_reverse(
+            self.descendants.iter().cloned(),
+            |commit| commit.id().clone(),
+            |commit| {
+                planned_parent_ids(commit)
+                    .iter()
+                    .filter_map(|id| commits_by_id.get(id).copied().cloned())
+                    .collect_vec()
+            },
+        );
+
+        // Parents of the abandoned commits, which replace them as parents of
+        // their children.
+        let mut replacements: HashMap<CommitId, Vec<CommitId>> = HashMap::new();
+        let mut rebased_ids: HashSet<CommitId> = HashSet::new();
+        let mut new_trees: HashMap<CommitId, MergedTree> = HashMap::new();
+        let mut plan = vec![];
+        for old_commit in ordered.into_iter().rev() {
+            let new_parent_ids = planned_parent_ids(&old_commit)
+                .iter()
+                .flat_map(|id| {
+                    replacements
+                        .get(id)
+                        .map_or(slice::from_ref(id), Vec::as_slice)
+                })
+                .unique()
+                .cloned()
+                .collect_vec();
+            if self.to_abandon.contains(old_commit.id()) {
+                replacements.insert(old_commit.id().clone(), new_parent_ids);
+                plan.push(PlannedRebase {
+                    old_commit,
+                    new_parent_ids: None,
+                    new_tree: None,
+                });
+                continue;
+            }
+            if new_parent_ids == old_commit.parent_ids()
+                && !new_parent_ids.iter().any(|id| rebased_ids.contains(id))
+            {
+                continue;
+            }
+            let new_tree = if compute_trees {
+                let (was_empty, new_tree) =
+                    predict_rebased_tree(repo, &old_commit, &new_parent_ids, &new_trees)?;
+                let empty = if self.target_commit_ids.contains(old_commit.id()) {
+                    options.empty
+                } else {
+                    EmptyBehavior::Keep
+                };
+                if let [parent_id] = &new_parent_ids[..] {
+                    let parent_tree_id = match new_trees.get(parent_id) {
+                        Some(tree) => tree.id(),
+                        None => repo.store().get_commit(parent_id)?.tree_id().clone(),
+                    };
+                    let should_abandon = match empty {
+                        EmptyBehavior::Keep => false,
+                        EmptyBehavior::AbandonNewlyEmpty => {
+                            parent_tree_id == new_tree.id() && !was_empty
+                        }
+                        EmptyBehavior::AbandonAllEmpty => parent_tree_id == new_tree.id(),
+                    };
+                    if should_abandon {
+                        replacements.insert(old_commit.id().clone(), new_parent_ids);
+                        plan.push(PlannedRebase {
+                            old_commit,
+                            new_parent_ids: None,
+                            new_tree: None,
+                        });
+                        continue;
+                    }
+                }
+                new_trees.insert(old_commit.id().clone(), new_tree.clone());
+                Some(new_tree)
+            } else {
+                None
+            };
+            rebased_ids.insert(old_commit.id().clone());
+            plan.push(PlannedRebase {
+                old_commit,
+                new_parent_ids: Some(new_parent_ids),
+                new_tree,
+            });
+        }
+        Ok(plan)
+    }
+}
+
+/// Commit that would be rebased or abandoned by [`ComputedMoveCommits::apply()`].
+#[derive(Clone, Debug)]
+pub struct PlannedRebase {
+    pub old_commit: Commit,
+    /// New parents of the commit, or `None` if the commit would be abandoned.
+    /// Parents that would be rebased themselves are referred to by their
+    /// current IDs.
+    pub new_parent_ids: Option<Vec<CommitId>>,
+    /// Tree of the rebased commit, if trees were computed.
+    pub new_tree: Option<MergedTree>,
+}
+
+/// Merges the tree of `old_commit` onto `new_parent_ids` like
+/// [`CommitRewriter::rebase_with_empty_behavior()`] does. The trees of the
+/// parents that are rebased themselves are looked up in `new_trees`. Returns
+/// whether the commit was empty, and the new tree.
+fn predict_rebased_tree(
+    repo: &dyn Repo,
+    old_commit: &Commit,
+    new_parent_ids: &[CommitId],
+    new_trees: &HashMap<CommitId, MergedTree>,
+) -> BackendResult<(bool, MergedTree)> {
+    let store = repo.store();
+    let get_tree = |id: &CommitId| match new_trees.get(id) {
+        Some(tree) => Ok(tree.clone()),
+        None => store.get_commit(id)?.tree(),
+    };
+    let old_parents: Vec<_> = old_commit.parents().try_collect()?;
+    let new_base_tree = if let [parent_id] = new_parent_ids {
+        get_tree(parent_id)?
+    } else {
+        let commit_id_merge =
+            find_recursive_merge_commits(store, repo.index(), new_parent_ids.to_vec())?;
+        let tree_merge = commit_id_merge.try_map(|id| get_tree(id).map(MergedTree::take))?;
+        MergedTree::new(tree_merge.flatten().simplify())
+            .resolve()
+            .block_on()?
+    };
+    let old_parent_tree_ids = old_parents.iter().map(Commit::tree_id).collect_vec();
+    if let [old_parent_tree_id] = &old_parent_tree_ids[..] {
+        if **old_parent_tree_id == new_base_tree.id() {
+            return Ok((true, old_commit.tree()?));
+        }
+    }
+    let old_base_tree = merge_commit_trees(repo, &old_parents).block_on()?;
+    let was_empty = old_base_tree.id() == *old_commit.tree_id();
+    let new_tree = new_base_tree
+        .merge_with_merge_drivers(old_base_tree, old_commit.tree()?)
+        .block_on()?;
+    Ok((was_empty, new_tree))
 }
 
 /// Moves `loc.target` commits from their current location to a new location in
@@ -1133,6 +1326,151 @@ impl CommitWithSelection {
     pub fn is_empty_selection(&self) -> bool {
         self.selected_tree.id() == self.parent_tree.id()
     }
+
+    /// Returns the parent tree with the changes in the commit that aren't part
+    /// of the selection applied.
+    ///
+    /// Unlike merging the commit tree into the parent tree with the selected
+    /// tree as base, unselected changes next to selected changes in the same
+    /// file don't result in conflicts.
+    pub async fn unselected_tree(&self) -> BackendResult<MergedTree> {
+        let store = self.parent_tree.store();
+        let target_tree = self.commit.tree_async().await?;
+        let mut tree_builder = MergedTreeBuilder::new(self.parent_tree.id());
+        let mut merged_tree: Option<MergedTree> = None;
+        let mut diff_stream = self
+            .parent_tree
+            .diff_stream(&target_tree, &EverythingMatcher);
+        while let Some(TreeDiffEntry { path, values }) = diff_stream.next().await {
+            let (parent_value, target_value) = values?;
+            let selected_value = self.selected_tree.path_value_async(&path).await?;
+            let new_value = if selected_value == parent_value {
+                target_value
+            } else if selected_value == target_value {
+                parent_value
+            } else if let Some(value) = unselected_file_value(
+                store,
+                &path,
+                [&parent_value, &selected_value, &target_value],
+            )
+            .await?
+            {
+                value
+            } else {
+                // Fall back to a 3-way merge if the unselected changes can't
+                // be applied to the parent file on their own.
+                let merged_tree = match &mut merged_tree {
+                    Some(merged_tree) => merged_tree,
+                    None => merged_tree.insert(
+                        target_tree
+                            .clone()
+                            .merge(self.selected_tree.clone(), self.parent_tree.clone())
+                            .await?,
+                    ),
+                };
+                merged_tree.path_value_async(&path).await?
+            };
+            tree_builder.set_or_remove(path, new_value);
+        }
+        let tree_id = tree_builder.write_tree(store)?;
+        store.get_root_tree_async(&tree_id).await
+    }
+}
+
+/// Computes the value of a partially selected file with only the unselected
+/// changes applied to the parent file. Returns `None` if any of the values
+/// isn't a resolved file, or if the changes can't be applied.
+async fn unselected_file_value(
+    store: &Arc<Store>,
+    path: &RepoPath,
+    [parent_value, selected_value, target_value]: [&MergedTreeValue; 3],
+) -> BackendResult<Option<MergedTreeValue>> {
+    let as_file = |value: &MergedTreeValue| match value.as_normal() {
+        Some(TreeValue::File {
+            id,
+            executable,
+            copy_id,
+        }) => Some((id.clone(), *executable, copy_id.clone())),
+        _ => None,
+    };
+    let (Some(parent_file), Some(selected_file), Some(target_file)) = (
+        as_file(parent_value),
+        as_file(selected_value),
+        as_file(target_value),
+    ) else {
+        return Ok(None);
+    };
+    let read_file = async |id: &FileId| -> BackendResult<Vec<u8>> {
+        let mut content = vec![];
+        store
+            .read_file(path, id)
+            .await?
+            .read_to_end(&mut content)
+            .await
+            .map_err(|err| BackendError::ReadFile {
+                path: path.to_owned(),
+                id: id.clone(),
+                source: err.into(),
+            })?;
+        Ok(content)
+    };
+    let parent_content = read_file(&parent_file.0).await?;
+    let selected_content = read_file(&selected_file.0).await?;
+    let target_content = read_file(&target_file.0).await?;
+    let Some(content) = apply_unselected_hunks(&parent_content, &selected_content, &target_content)
+    else {
+        return Ok(None);
+    };
+    let id = store.write_file(path, &mut content.as_slice()).await?;
+    let executable = if selected_file.1 == parent_file.1 {
+        target_file.1
+    } else {
+        parent_file.1
+    };
+    Ok(Some(Merge::normal(TreeValue::File {
+        id,
+        executable,
+        copy_id: target_file.2,
+    })))
+}
+
+/// Applies the changes from `selected` to `target` to `parent`, where
+/// `selected` is `parent` with some of the changes from `target` applied.
+///
+/// Returns `None` if one of the changes doesn't fall within lines that are
+/// unchanged between `parent` and `selected`.
+fn apply_unselected_hunks(parent: &[u8], selected: &[u8], target: &[u8]) -> Option<Vec<u8>> {
+    let unchanged_regions = Diff::by_line([parent, selected])
+        .hunk_ranges()
+        .filter(|hunk| hunk.kind == DiffHunkKind::Matching)
+        .collect_vec();
+    let mut content = vec![];
+    let mut parent_pos = 0;
+    for hunk in Diff::by_line([selected, target]).hunk_ranges() {
+        if hunk.kind == DiffHunkKind::Matching {
+            continue;
+        }
+        let (selected_range, target_range) = (&hunk.ranges[0], &hunk.ranges[1]);
+        // The lines replaced by the hunk must be unchanged in the selection,
+        // and an insertion point must be unambiguous.
+        let (region,) = unchanged_regions
+            .iter()
+            .filter(|region| {
+                region.ranges[1].start <= selected_range.start
+                    && selected_range.end <= region.ranges[1].end
+            })
+            .collect_tuple()?;
+        let parent_start = region.ranges[0].start + (selected_range.start - region.ranges[1].start);
+        let parent_end = region.ranges[0].start + (selected_range.end - region.ranges[1].start);
+        if parent_start < parent_pos {
+            return None;
+        }
+        content.extend_from_slice(&parent[parent_pos..parent_start]);
+        content.extend_from_slice(&target[target_range.clone()]);
+        parent_pos = parent_end;
+    }
+    content.extend_from_slice(&parent[parent_pos..]);
+    Some(content)
 }
 
 /// Resulting commit builder and stats to be returned by [`squash_commits()`].
@@ -1188,7 +1526,7 @@ pub fn squash_commits<'repo>(
             let source_tree = source.commit.commit.tree()?;
             // Apply the reverse of the selected changes onto the source
             let new_source_tree = source_tree
-                .merge(
+                .merge_with_merge_drivers(
                     source.commit.selected_tree.clone(),
                     source.commit.parent_tree.clone(),
                 )
@@ -1220,14 +1558,28 @@ pub fn squash_commits<'repo>(
         })?;
     }
     // Apply the selected changes onto the destination
+    let detect_renames = repo.base_repo().settings().merge_detect_renames();
     let mut destination_tree = rewritten_destination.tree()?;
     for source in &source_commits {
-        destination_tree = destination_tree
-            .merge(
+        let source_commit = &source.commit.commit;
+        destination_tree = match source_commit.parent_ids() {
+            [parent_id] if detect_renames => merge_trees_following_renames(
+                repo.store(),
+                parent_id,
                 source.commit.parent_tree.clone(),
+                rewritten_destination.id(),
+                destination_tree,
+                source_commit.id(),
                 source.commit.selected_tree.clone(),
             )
-            .block_on()?;
+            .block_on()?,
+            _ => destination_tree
+                .merge_with_merge_drivers(
+                    source.commit.parent_tree.clone(),
+                    source.commit.selected_tree.clone(),
+                )
+                .block_on()?,
+        };
     }
     let mut predecessors = vec![destination.id().clone()];
     predecessors.extend(
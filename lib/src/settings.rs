@@ -55,6 +55,7 @@ struct UserSettingsData {
     operation_username: String,
     signing_behavior: SignBehavior,
     signing_key: Option<String>,
+    merge_detect_renames: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -64,6 +65,9 @@ pub struct GitSettings {
     pub executable_path: PathBuf,
     pub write_change_id_header: bool,
     pub colocate: bool,
+    pub copy_tracking: CopyTrackingSettings,
+    /// Names of the Git notes refs (`refs/notes/<name>`) to import on fetch.
+    pub import_notes: Vec<String>,
 }
 
 impl GitSettings {
@@ -74,6 +78,8 @@ impl GitSettings {
             executable_path: settings.get("git.executable-path")?,
             write_change_id_header: settings.get("git.write-change-id-header")?,
             colocate: settings.get("git.colocate")?,
+            copy_tracking: CopyTrackingSettings::from_settings(settings)?,
+            import_notes: settings.get("git.import-notes")?,
         })
     }
 }
@@ -86,6 +92,48 @@ impl Default for GitSettings {
             executable_path: PathBuf::from("git"),
             write_change_id_header: true,
             colocate: false,
+            copy_tracking: CopyTrackingSettings::default(),
+            import_notes: vec![],
+        }
+    }
+}
+
+/// Settings of the detection of copied and renamed files.
+#[derive(Debug, Clone)]
+pub struct CopyTrackingSettings {
+    /// Minimum percentage of similar content for a file to be detected as a
+    /// copy of another file. 100 only detects exact copies.
+    pub similarity: u8,
+    /// Maximum number of candidate files to compare by content. Only exact
+    /// copies are detected if there are more candidates.
+    pub max_candidates: usize,
+    /// Whether to also detect copies of files that weren't modified. This
+    /// compares added files against all files of the parent tree.
+    pub detect_copies: bool,
+}
+
+impl CopyTrackingSettings {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        Ok(Self {
+            similarity: settings.get_value_with("copy-tracking.similarity", |value| {
+                value
+                    .as_integer()
+                    .and_then(|n| u8::try_from(n).ok())
+                    .filter(|&n| n <= 100)
+                    .ok_or("Expected an integer between 0 and 100")
+            })?,
+            max_candidates: settings.get("copy-tracking.max-candidates")?,
+            detect_copies: settings.get_bool("copy-tracking.detect-copies")?,
+        })
+    }
+}
+
+impl Default for CopyTrackingSettings {
+    fn default() -> Self {
+        Self {
+            similarity: 50,
+            max_candidates: 1000,
+            detect_copies: false,
         }
     }
 }
@@ -151,6 +199,7 @@ impl UserSettings {
         let operation_username = config.get("operation.username")?;
         let signing_behavior = config.get("signing.behavior")?;
         let signing_key = config.get("signing.key").optional()?;
+        let merge_detect_renames = config.get("merge.detect-renames")?;
         let data = UserSettingsData {
             user_name,
             user_email,
@@ -160,6 +209,7 @@ impl UserSettings {
             operation_username,
             signing_behavior,
             signing_key,
+            merge_detect_renames,
         };
         Ok(Self {
             config: Arc::new(config),
@@ -215,6 +265,11 @@ impl UserSettings {
         &self.data.operation_username
     }
 
+    /// Whether tree merges should follow the renames detected by the backend.
+    pub fn merge_detect_renames(&self) -> bool {
+        self.data.merge_detect_renames
+    }
+
     pub fn signature(&self) -> Signature {
         let timestamp = self.data.commit_timestamp.unwrap_or_else(Timestamp::now);
         Signature {
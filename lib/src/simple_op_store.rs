@@ -33,6 +33,7 @@ use tempfile::NamedTempFile;
 use thiserror::Error;
 
 use crate::backend::BackendInitError;
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::backend::MillisSinceEpoch;
 use crate::backend::Timestamp;
@@ -65,6 +66,7 @@ use crate::ref_name::RefNameBuf;
 use crate::ref_name::RemoteNameBuf;
 use crate::ref_name::WorkspaceName;
 use crate::ref_name::WorkspaceNameBuf;
+use crate::repo_path::RepoPathBuf;
 
 // BLAKE2b-512 hash length in bytes
 const OPERATION_ID_LENGTH: usize = 64;
@@ -441,6 +443,14 @@ fn operation_metadata_to_proto(
         username: metadata.username.clone(),
         is_snapshot: metadata.is_snapshot,
         tags: metadata.tags.clone(),
+        workspace_name: metadata
+            .workspace_name
+            .as_ref()
+            .map(|name| name.as_str().to_owned()),
+        cwd: metadata
+            .cwd
+            .as_ref()
+            .map(|path| path.as_internal_file_string().to_owned()),
     }
 }
 
@@ -458,6 +468,11 @@ fn operation_metadata_from_proto(
         username: proto.username,
         is_snapshot: proto.is_snapshot,
         tags: proto.tags,
+        workspace_name: proto.workspace_name.map(WorkspaceNameBuf::from),
+        // Ignore invalid paths rather than failing to load the operation.
+        cwd: proto
+            .cwd
+            .and_then(|cwd| RepoPathBuf::from_internal_string(cwd).ok()),
     }
 }
 
@@ -562,6 +577,27 @@ fn view_to_proto(view: &View) -> crate::protos::simple_op_store::View {
 
     proto.git_head = ref_target_to_proto(&view.git_head);
 
+    for (name, description) in &view.bookmark_descriptions {
+        proto
+            .bookmark_descriptions
+            .insert(name.into(), description.clone());
+    }
+
+    for (change_id, notes) in &view.notes {
+        proto
+            .notes
+            .push(crate::protos::simple_op_store::ChangeNotes {
+                change_id: change_id.to_bytes(),
+                notes: notes.clone().into_iter().collect(),
+            });
+    }
+
+    for (name, change_id) in &view.change_names {
+        proto
+            .change_names
+            .insert(name.clone(), change_id.to_bytes());
+    }
+
     proto
 }
 
@@ -613,6 +649,24 @@ fn view_from_proto(proto: crate::protos::simple_op_store::View) -> View {
         view.git_head = RefTarget::normal(CommitId::new(proto.git_head_legacy));
     }
 
+    for (name, description) in proto.bookmark_descriptions {
+        view.bookmark_descriptions
+            .insert(RefNameBuf::from(name), description);
+    }
+
+    for change_notes in proto.notes {
+        if !change_notes.notes.is_empty() {
+            view.notes.insert(
+                ChangeId::new(change_notes.change_id),
+                change_notes.notes.into_iter().collect(),
+            );
+        }
+    }
+
+    for (name, change_id) in proto.change_names {
+        view.change_names.insert(name, ChangeId::new(change_id));
+    }
+
     view
 }
 
@@ -850,6 +904,9 @@ mod tests {
                 WorkspaceName::DEFAULT.to_owned() => default_wc_commit_id,
                 "test".into() => test_wc_commit_id,
             },
+            bookmark_descriptions: btreemap! {},
+            notes: btreemap! {},
+            change_names: btreemap! {},
         }
     }
 
@@ -884,6 +941,8 @@ mod tests {
                     "key1".to_string() => "value1".to_string(),
                     "key2".to_string() => "value2".to_string(),
                 },
+                workspace_name: Some("default".into()),
+                cwd: Some(RepoPathBuf::from_internal_string("src/foo").unwrap()),
             },
             commit_predecessors: Some(btreemap! {
                 CommitId::from_hex("111111") => vec![],
@@ -909,7 +968,7 @@ mod tests {
         // Test exact output so we detect regressions in compatibility
         assert_snapshot!(
             OperationId::new(blake2b_hash(&create_operation()).to_vec()).hex(),
-            @"b544c80b5ededdd64d0f10468fa636a06b83c45d94dd9bdac95319f7fe11fee536506c5c110681dee6233e69db7647683e732939a3ec88e867250efd765fea18"
+            @"f830dd61b51957517b17dd05cf9a884bb1a16f47cfe00e8977f088c5a467921b384ed29c44fe707ea135d8c92281b77a3ca3f9fcbb4d00e1869d177b90ccbccc"
         );
     }
 
@@ -926,6 +985,45 @@ mod tests {
         assert_eq!(read_view, view);
     }
 
+    #[test]
+    fn test_read_write_view_with_bookmark_descriptions() {
+        let temp_dir = new_temp_dir();
+        let root_data = RootOperationData {
+            root_commit_id: CommitId::from_hex("000000"),
+        };
+        let store = SimpleOpStore::init(temp_dir.path(), root_data).unwrap();
+        let mut view = create_view();
+        let view_id_without_descriptions = store.write_view(&view).unwrap();
+        view.bookmark_descriptions
+            .insert("main".into(), "frozen for 1.0".to_owned());
+        let view_id = store.write_view(&view).unwrap();
+        assert_ne!(view_id, view_id_without_descriptions);
+        let read_view = store.read_view(&view_id).unwrap();
+        assert_eq!(read_view, view);
+    }
+
+    #[test]
+    fn test_read_write_view_with_notes() {
+        let temp_dir = new_temp_dir();
+        let root_data = RootOperationData {
+            root_commit_id: CommitId::from_hex("000000"),
+        };
+        let store = SimpleOpStore::init(temp_dir.path(), root_data).unwrap();
+        let mut view = create_view();
+        let view_id_without_notes = store.write_view(&view).unwrap();
+        view.notes.insert(
+            ChangeId::from_hex("aaa111"),
+            btreemap! {
+                "ci-status".to_owned() => "passed".to_owned(),
+                "review".to_owned() => "https://example.com/1".to_owned(),
+            },
+        );
+        let view_id = store.write_view(&view).unwrap();
+        assert_ne!(view_id, view_id_without_notes);
+        let read_view = store.read_view(&view_id).unwrap();
+        assert_eq!(read_view, view);
+    }
+
     #[test]
     fn test_read_write_operation() {
         let temp_dir = new_temp_dir();
@@ -17,14 +17,24 @@
 use std::any::Any;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::SystemTime;
 
+use bstr::BString;
 use clru::CLruCache;
 use futures::stream::BoxStream;
 use pollster::FutureExt as _;
+use tempfile::NamedTempFile;
 use tokio::io::AsyncRead;
 
 use crate::backend;
@@ -40,9 +50,14 @@ use crate::backend::SigningFn;
 use crate::backend::SymlinkId;
 use crate::backend::TreeId;
 use crate::commit::Commit;
+use crate::conflicts::ConflictMarkerStyle;
+use crate::content_hash::blake2b_hash;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::hex_util;
 use crate::index::Index;
 use crate::merge::Merge;
 use crate::merge::MergedTreeValue;
+use crate::merge_driver::MergeDrivers;
 use crate::merged_tree::MergedTree;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
@@ -53,14 +68,50 @@ use crate::tree::Tree;
 // commits.
 pub(crate) const COMMIT_CACHE_CAPACITY: usize = 100;
 const TREE_CACHE_CAPACITY: usize = 1000;
+const TREE_MERGE_CACHE_CAPACITY: usize = 1000;
+const MATERIALIZED_CONFLICT_CACHE_CAPACITY: usize = 1000;
+
+/// Maps the trees merged at a directory to the merged trees.
+type TreeMergeCache = CLruCache<(RepoPathBuf, Merge<TreeId>), Merge<TreeId>>;
+
+/// Number of directory-level tree merges that were looked up in the cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TreeMergeCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Maps the file ids of a conflict and the marker style to the materialized
+/// conflict text.
+type MaterializedConflictCache = CLruCache<(Merge<Option<FileId>>, ConflictMarkerStyle), BString>;
+
+/// Number of materialized conflicts that were looked up in the cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MaterializedConflictCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
 
 /// Wraps the low-level backend and makes it return more convenient types. Also
 /// adds caching.
 pub struct Store {
     backend: Box<dyn Backend>,
     signer: Signer,
+    merge_drivers: MergeDrivers,
     commit_cache: Mutex<CLruCache<CommitId, Arc<backend::Commit>>>,
     tree_cache: Mutex<CLruCache<(RepoPathBuf, TreeId), Arc<backend::Tree>>>,
+    /// Results of merging the trees at a directory, so that batch rewrites
+    /// don't re-merge subtrees that are the same across commits.
+    tree_merge_cache: Mutex<TreeMergeCache>,
+    tree_merge_cache_hits: AtomicU64,
+    tree_merge_cache_misses: AtomicU64,
+    /// Conflict texts with markers, so that conflicted files aren't
+    /// re-materialized every time they are diffed.
+    materialized_conflict_cache: Mutex<MaterializedConflictCache>,
+    /// Directory where materialized conflicts are also persisted, if enabled.
+    materialized_conflict_cache_dir: OnceLock<PathBuf>,
+    materialized_conflict_cache_hits: AtomicU64,
+    materialized_conflict_cache_misses: AtomicU64,
 }
 
 impl Debug for Store {
@@ -72,12 +123,28 @@ impl Debug for Store {
 }
 
 impl Store {
-    pub fn new(backend: Box<dyn Backend>, signer: Signer) -> Arc<Self> {
+    pub fn new(
+        backend: Box<dyn Backend>,
+        signer: Signer,
+        merge_drivers: MergeDrivers,
+    ) -> Arc<Self> {
         Arc::new(Self {
             backend,
             signer,
+            merge_drivers,
             commit_cache: Mutex::new(CLruCache::new(COMMIT_CACHE_CAPACITY.try_into().unwrap())),
             tree_cache: Mutex::new(CLruCache::new(TREE_CACHE_CAPACITY.try_into().unwrap())),
+            tree_merge_cache: Mutex::new(CLruCache::new(
+                TREE_MERGE_CACHE_CAPACITY.try_into().unwrap(),
+            )),
+            tree_merge_cache_hits: AtomicU64::new(0),
+            tree_merge_cache_misses: AtomicU64::new(0),
+            materialized_conflict_cache: Mutex::new(CLruCache::new(
+                MATERIALIZED_CONFLICT_CACHE_CAPACITY.try_into().unwrap(),
+            )),
+            materialized_conflict_cache_dir: OnceLock::new(),
+            materialized_conflict_cache_hits: AtomicU64::new(0),
+            materialized_conflict_cache_misses: AtomicU64::new(0),
         })
     }
 
@@ -93,6 +160,10 @@ impl Store {
         &self.signer
     }
 
+    pub fn merge_drivers(&self) -> &MergeDrivers {
+        &self.merge_drivers
+    }
+
     pub fn get_copy_records(
         &self,
         paths: Option<&[RepoPathBuf]>,
@@ -243,6 +314,124 @@ impl Store {
         Ok(Tree::new(self.clone(), path.to_owned(), tree_id, data))
     }
 
+    /// Looks up the result of a previous merge of the given trees at `dir`.
+    pub(crate) fn get_cached_tree_merge(
+        &self,
+        dir: &RepoPath,
+        tree_ids: &Merge<TreeId>,
+    ) -> Option<Merge<TreeId>> {
+        let key = (dir.to_owned(), tree_ids.clone());
+        let cached = self.tree_merge_cache.lock().unwrap().get(&key).cloned();
+        let counter = if cached.is_some() {
+            &self.tree_merge_cache_hits
+        } else {
+            &self.tree_merge_cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    pub(crate) fn cache_tree_merge(
+        &self,
+        dir: &RepoPath,
+        tree_ids: Merge<TreeId>,
+        merged_ids: Merge<TreeId>,
+    ) {
+        let mut locked_cache = self.tree_merge_cache.lock().unwrap();
+        locked_cache.put((dir.to_owned(), tree_ids), merged_ids);
+    }
+
+    /// Returns how often directory-level tree merges were served from the
+    /// cache since this store was created or its caches were last cleared.
+    pub fn tree_merge_cache_stats(&self) -> TreeMergeCacheStats {
+        TreeMergeCacheStats {
+            hits: self.tree_merge_cache_hits.load(Ordering::Relaxed),
+            misses: self.tree_merge_cache_misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Persists materialized conflicts in `dir` in addition to the in-memory
+    /// cache, so they can be reused by later processes. Entries are keyed by
+    /// the file ids, so they never become stale. Has no effect if a directory
+    /// was already set.
+    pub fn set_materialized_conflict_cache_dir(&self, dir: PathBuf) {
+        self.materialized_conflict_cache_dir.set(dir).ok();
+    }
+
+    /// Looks up the previously materialized text of the conflict between the
+    /// given files.
+    pub(crate) fn get_cached_materialized_conflict(
+        &self,
+        file_ids: &Merge<Option<FileId>>,
+        marker_style: ConflictMarkerStyle,
+    ) -> Option<BString> {
+        let key = (file_ids.clone(), marker_style);
+        let mut cached = self
+            .materialized_conflict_cache
+            .lock()
+            .unwrap()
+            .get(&key)
+            .cloned();
+        if cached.is_none() {
+            let persisted = self
+                .materialized_conflict_cache_dir
+                .get()
+                .and_then(|dir| fs::read(dir.join(materialized_conflict_file_name(&key))).ok());
+            if let Some(text) = persisted {
+                let text = BString::from(text);
+                let mut locked_cache = self.materialized_conflict_cache.lock().unwrap();
+                locked_cache.put(key, text.clone());
+                cached = Some(text);
+            }
+        }
+        let counter = if cached.is_some() {
+            &self.materialized_conflict_cache_hits
+        } else {
+            &self.materialized_conflict_cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    pub(crate) fn cache_materialized_conflict(
+        &self,
+        file_ids: Merge<Option<FileId>>,
+        marker_style: ConflictMarkerStyle,
+        text: BString,
+    ) {
+        let key = (file_ids, marker_style);
+        if let Some(dir) = self.materialized_conflict_cache_dir.get() {
+            // The on-disk cache is best effort. Failing to write it only
+            // means that the conflict will be materialized again.
+            let result = fs::create_dir_all(dir).and_then(|()| {
+                let mut temp_file = NamedTempFile::new_in(dir)?;
+                temp_file.write_all(&text)?;
+                persist_content_addressed_temp_file(
+                    temp_file,
+                    dir.join(materialized_conflict_file_name(&key)),
+                )
+            });
+            if let Err(err) = result {
+                tracing::debug!(?err, "failed to persist materialized conflict");
+            }
+        }
+        let mut locked_cache = self.materialized_conflict_cache.lock().unwrap();
+        locked_cache.put(key, text);
+    }
+
+    /// Returns how often materialized conflicts were served from the cache
+    /// since this store was created or its caches were last cleared.
+    pub fn materialized_conflict_cache_stats(&self) -> MaterializedConflictCacheStats {
+        MaterializedConflictCacheStats {
+            hits: self
+                .materialized_conflict_cache_hits
+                .load(Ordering::Relaxed),
+            misses: self
+                .materialized_conflict_cache_misses
+                .load(Ordering::Relaxed),
+        }
+    }
+
     pub async fn read_file(
         &self,
         path: &RepoPath,
@@ -285,13 +474,49 @@ impl Store {
             .write_conflict(path, &contents.clone().into_backend_conflict())
     }
 
+    /// Removes unreachable objects from the backend, and materialized
+    /// conflicts which haven't been persisted since `keep_newer`.
     pub fn gc(&self, index: &dyn Index, keep_newer: SystemTime) -> BackendResult<()> {
-        self.backend.gc(index, keep_newer)
+        self.backend.gc(index, keep_newer)?;
+        if let Some(dir) = self.materialized_conflict_cache_dir.get() {
+            // Like writing the on-disk cache, pruning it is best effort.
+            if let Err(err) = prune_materialized_conflicts(dir, keep_newer) {
+                tracing::debug!(?err, "failed to prune materialized conflicts");
+            }
+        }
+        Ok(())
     }
 
     /// Clear cached objects. Mainly intended for testing.
     pub fn clear_caches(&self) {
         self.commit_cache.lock().unwrap().clear();
         self.tree_cache.lock().unwrap().clear();
+        self.tree_merge_cache.lock().unwrap().clear();
+        self.tree_merge_cache_hits.store(0, Ordering::Relaxed);
+        self.tree_merge_cache_misses.store(0, Ordering::Relaxed);
+        self.materialized_conflict_cache.lock().unwrap().clear();
+        self.materialized_conflict_cache_hits
+            .store(0, Ordering::Relaxed);
+        self.materialized_conflict_cache_misses
+            .store(0, Ordering::Relaxed);
     }
 }
+
+fn prune_materialized_conflicts(dir: &Path, keep_newer: SystemTime) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        if entry.metadata()?.modified()? < keep_newer {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+fn materialized_conflict_file_name(key: &(Merge<Option<FileId>>, ConflictMarkerStyle)) -> String {
+    hex_util::encode_hex(&blake2b_hash(key))
+}
@@ -30,11 +30,13 @@ use crate::op_store::OpStoreError;
 use crate::op_store::OperationMetadata;
 use crate::op_store::TimestampRange;
 use crate::operation::Operation;
+use crate::ref_name::WorkspaceNameBuf;
 use crate::repo::MutableRepo;
 use crate::repo::ReadonlyRepo;
 use crate::repo::Repo as _;
 use crate::repo::RepoLoader;
 use crate::repo::RepoLoaderError;
+use crate::repo_path::RepoPathBuf;
 use crate::settings::UserSettings;
 use crate::view::View;
 
@@ -115,6 +117,13 @@ impl Transaction {
         self.op_metadata.is_snapshot = is_snapshot;
     }
 
+    /// Records the workspace and the working directory (relative to the
+    /// workspace root) the operation is made from.
+    pub fn set_workspace(&mut self, workspace_name: WorkspaceNameBuf, cwd: Option<RepoPathBuf>) {
+        self.op_metadata.workspace_name = Some(workspace_name);
+        self.op_metadata.cwd = cwd;
+    }
+
     /// Writes the transaction to the operation store and publishes it.
     pub fn commit(
         self,
@@ -180,6 +189,8 @@ pub fn create_op_metadata(
         username,
         is_snapshot,
         tags: Default::default(),
+        workspace_name: None,
+        cwd: None,
     }
 }
 
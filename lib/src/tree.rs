@@ -36,6 +36,7 @@ use crate::files;
 use crate::matchers::EverythingMatcher;
 use crate::matchers::Matcher;
 use crate::merge::MergedTreeVal;
+use crate::merge_driver::MergeDrivers;
 use crate::object_id::ObjectId as _;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
@@ -258,10 +259,14 @@ impl Iterator for TreeEntriesIterator<'_> {
 ///
 /// The input `conflict` is supposed to be simplified. It shouldn't contain
 /// non-file values that cancel each other.
+///
+/// If `merge_drivers` are given, a conflict in the file contents is passed to
+/// the driver configured for the path, which runs an external program.
 pub async fn try_resolve_file_conflict(
     store: &Store,
     filename: &RepoPath,
     conflict: &MergedTreeVal<'_>,
+    merge_drivers: Option<&MergeDrivers>,
 ) -> BackendResult<Option<TreeValue>> {
     // If there are any non-file or any missing parts in the conflict, we can't
     // merge it. We check early so we don't waste time reading file contents if
@@ -338,7 +343,10 @@ pub async fn try_resolve_file_conflict(
             BackendResult::Ok(content)
         })
         .await?;
-    if let Some(merged_content) = files::try_merge(&contents) {
+    let merged_content = files::try_merge(&contents)
+        .map(Vec::from)
+        .or_else(|| merge_drivers?.try_merge(filename, &contents));
+    if let Some(merged_content) = merged_content {
         let id = store
             .write_file(filename, &mut merged_content.as_slice())
             .await?;
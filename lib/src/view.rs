@@ -20,6 +20,7 @@ use std::collections::HashSet;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::op_store;
 use crate::op_store::BookmarkTarget;
@@ -170,6 +171,108 @@ impl View {
             self.data.local_bookmarks.insert(name.to_owned(), target);
         } else {
             self.data.local_bookmarks.remove(name);
+            self.data.bookmark_descriptions.remove(name);
+        }
+    }
+
+    /// Iterates local bookmark `(name, description)`s in lexicographical
+    /// order.
+    pub fn local_bookmark_descriptions(&self) -> impl Iterator<Item = (&RefName, &str)> {
+        self.data
+            .bookmark_descriptions
+            .iter()
+            .map(|(name, description)| (name.as_ref(), description.as_str()))
+    }
+
+    /// Returns the description of the local bookmark, if any.
+    pub fn get_local_bookmark_description(&self, name: &RefName) -> Option<&str> {
+        self.data
+            .bookmark_descriptions
+            .get(name)
+            .map(|description| description.as_str())
+    }
+
+    /// Sets the description of the local bookmark. An empty description
+    /// removes it. Descriptions of absent bookmarks are ignored.
+    pub fn set_local_bookmark_description(&mut self, name: &RefName, description: String) {
+        if description.is_empty() || !self.data.local_bookmarks.contains_key(name) {
+            self.data.bookmark_descriptions.remove(name);
+        } else {
+            self.data
+                .bookmark_descriptions
+                .insert(name.to_owned(), description);
+        }
+    }
+
+    /// Iterates `((change_id, key), value)`s of all notes ordered by change id
+    /// and key.
+    pub fn all_notes(&self) -> impl Iterator<Item = ((&ChangeId, &str), &str)> {
+        self.data.notes.iter().flat_map(|(change_id, notes)| {
+            notes
+                .iter()
+                .map(move |(key, value)| ((change_id, key.as_str()), value.as_str()))
+        })
+    }
+
+    /// Iterates `(key, value)`s of the notes attached to the change in
+    /// lexicographical order.
+    pub fn notes(&self, change_id: &ChangeId) -> impl Iterator<Item = (&str, &str)> {
+        self.data
+            .notes
+            .get(change_id)
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns the note with the given key attached to the change, if any.
+    pub fn get_note(&self, change_id: &ChangeId, key: &str) -> Option<&str> {
+        self.data
+            .notes
+            .get(change_id)
+            .and_then(|notes| notes.get(key))
+            .map(|value| value.as_str())
+    }
+
+    /// Sets the note with the given key attached to the change. An empty value
+    /// removes it.
+    pub fn set_note(&mut self, change_id: &ChangeId, key: &str, value: String) {
+        if value.is_empty() {
+            if let Some(notes) = self.data.notes.get_mut(change_id) {
+                notes.remove(key);
+                if notes.is_empty() {
+                    self.data.notes.remove(change_id);
+                }
+            }
+        } else {
+            self.data
+                .notes
+                .entry(change_id.clone())
+                .or_default()
+                .insert(key.to_owned(), value);
+        }
+    }
+
+    /// Iterates `(name, change_id)`s of all change names in lexicographical
+    /// order.
+    pub fn change_names(&self) -> impl Iterator<Item = (&str, &ChangeId)> {
+        self.data
+            .change_names
+            .iter()
+            .map(|(name, change_id)| (name.as_str(), change_id))
+    }
+
+    /// Returns the change the given name refers to, if any.
+    pub fn get_change_name(&self, name: &str) -> Option<&ChangeId> {
+        self.data.change_names.get(name)
+    }
+
+    /// Sets the change the given name refers to. `None` removes the name.
+    pub fn set_change_name(&mut self, name: &str, change_id: Option<ChangeId>) {
+        if let Some(change_id) = change_id {
+            self.data.change_names.insert(name.to_owned(), change_id);
+        } else {
+            self.data.change_names.remove(name);
         }
     }
 
@@ -381,6 +484,9 @@ impl View {
             git_refs,
             git_head,
             wc_commit_ids,
+            bookmark_descriptions: _,
+            notes: _,
+            change_names: _,
         } = &self.data;
         itertools::chain!(
             head_ids,
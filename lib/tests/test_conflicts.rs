@@ -12,13 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+use std::time::SystemTime;
+
 use indoc::indoc;
 use itertools::Itertools as _;
+use jj_lib::backend::CopyId;
 use jj_lib::backend::FileId;
 use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::conflicts::MIN_CONFLICT_MARKER_LEN;
+use jj_lib::conflicts::MaterializedFileConflictValue;
 use jj_lib::conflicts::choose_materialized_conflict_marker_len;
 use jj_lib::conflicts::extract_as_single_hunk;
+use jj_lib::conflicts::materialize_file_conflict_to_bytes;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
 use jj_lib::conflicts::parse_conflict;
 use jj_lib::conflicts::update_from_content;
@@ -813,6 +819,73 @@ fn test_materialize_conflict_two_forward_diffs() {
     );
 }
 
+#[test]
+fn test_materialize_conflict_cached() {
+    let test_repo = TestRepo::init();
+    let store = test_repo.repo.store();
+    let cache_dir = testutils::new_temp_dir();
+    store.set_materialized_conflict_cache_dir(cache_dir.path().to_owned());
+
+    let path = repo_path("file");
+    let base_id = testutils::write_file(store, path, "line 1\nline 2\nline 3\n");
+    let left_id = testutils::write_file(store, path, "line 1\nleft 2\nline 3\n");
+    let right_id = testutils::write_file(store, path, "line 1\nright 2\nline 3\n");
+    let ids = Merge::from_removes_adds(vec![Some(base_id)], vec![Some(left_id), Some(right_id)]);
+    let file = MaterializedFileConflictValue {
+        unsimplified_ids: ids.clone(),
+        contents: extract_as_single_hunk(&ids, store, path)
+            .block_on()
+            .unwrap(),
+        ids,
+        executable: Some(false),
+        copy_id: Some(CopyId::placeholder()),
+    };
+
+    let styles = [
+        ConflictMarkerStyle::Diff,
+        ConflictMarkerStyle::Snapshot,
+        ConflictMarkerStyle::Git,
+    ];
+    for style in styles {
+        let uncached = materialize_merge_result_to_bytes(&file.contents, style);
+        // First lookup materializes the text, second one is served from memory
+        assert_eq!(
+            materialize_file_conflict_to_bytes(store, &file, style),
+            uncached
+        );
+        assert_eq!(
+            materialize_file_conflict_to_bytes(store, &file, style),
+            uncached
+        );
+    }
+    let stats = store.materialized_conflict_cache_stats();
+    assert_eq!((stats.hits, stats.misses), (3, 3));
+
+    // Dropping the in-memory cache falls back to the on-disk cache
+    store.clear_caches();
+    for style in styles {
+        let uncached = materialize_merge_result_to_bytes(&file.contents, style);
+        assert_eq!(
+            materialize_file_conflict_to_bytes(store, &file, style),
+            uncached
+        );
+    }
+    let stats = store.materialized_conflict_cache_stats();
+    assert_eq!((stats.hits, stats.misses), (3, 0));
+
+    // Garbage collection prunes the entries persisted before the cutoff
+    let count_entries = || std::fs::read_dir(cache_dir.path()).unwrap().count();
+    let index = test_repo.repo.index();
+    store
+        .gc(index, SystemTime::now() - Duration::from_secs(3600))
+        .unwrap();
+    assert_eq!(count_entries(), 3);
+    store
+        .gc(index, SystemTime::now() + Duration::from_secs(3600))
+        .unwrap();
+    assert_eq!(count_entries(), 0);
+}
+
 #[test]
 fn test_parse_conflict_resolved() {
     assert_eq!(
@@ -23,6 +23,8 @@ use futures::executor::block_on_stream;
 use jj_lib::backend::CommitId;
 use jj_lib::backend::CopyRecord;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
 use jj_lib::git_backend::GitBackend;
 use jj_lib::git_backend::JJ_TREES_COMMIT_HEADER;
 use jj_lib::object_id::ObjectId as _;
@@ -30,6 +32,7 @@ use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
 use jj_lib::transaction::Transaction;
 use maplit::hashmap;
@@ -334,6 +337,114 @@ fn test_copy_detection_file_and_dir() {
     );
 }
 
+#[test]
+fn test_copy_detection_similarity() {
+    // 10 lines, of which the first `num_same` are shared by all versions
+    let file_content = |name: &str, num_same: usize| {
+        (0..10)
+            .map(|i| {
+                if i < num_same {
+                    format!("{name} line {i}\n")
+                } else {
+                    format!("{name} edited line {i}\n")
+                }
+            })
+            .collect::<String>()
+    };
+    let similar_before = file_content("similar", 10);
+    let similar_after = file_content("similar", 9);
+    let dissimilar_before = file_content("dissimilar", 10);
+    let dissimilar_after = file_content("dissimilar", 4);
+
+    let get_copies = |settings: &UserSettings| {
+        let test_repo = TestRepo::init_with_backend_and_settings(TestRepoBackend::Git, settings);
+        let repo = &test_repo.repo;
+        let mut tx = repo.start_transaction();
+        let commit_a = make_commit(
+            &mut tx,
+            vec![repo.store().root_commit_id().clone()],
+            &[
+                (repo_path("similar1"), &similar_before),
+                (repo_path("dissimilar1"), &dissimilar_before),
+            ],
+        );
+        let commit_b = make_commit(
+            &mut tx,
+            vec![commit_a.id().clone()],
+            &[
+                (repo_path("similar2"), &similar_after),
+                (repo_path("dissimilar2"), &dissimilar_after),
+            ],
+        );
+        get_copy_records(repo.store(), None, &commit_a, &commit_b)
+    };
+    let settings_with_similarity = |similarity: u8| {
+        let mut config = testutils::base_user_config();
+        let text = format!("copy-tracking.similarity = {similarity}");
+        config.add_layer(ConfigLayer::parse(ConfigSource::User, &text).unwrap());
+        UserSettings::from_config(config).unwrap()
+    };
+
+    // With the default threshold, only the 90% similar file is detected.
+    assert_eq!(
+        get_copies(&testutils::user_settings()),
+        hashmap! {
+            "similar2".to_owned() => "similar1".to_owned(),
+        }
+    );
+    assert_eq!(
+        get_copies(&settings_with_similarity(20)),
+        hashmap! {
+            "similar2".to_owned() => "similar1".to_owned(),
+            "dissimilar2".to_owned() => "dissimilar1".to_owned(),
+        }
+    );
+    assert_eq!(get_copies(&settings_with_similarity(100)), hashmap! {});
+}
+
+#[test]
+fn test_copy_detection_unmodified_source() {
+    let content = "line 1\nline 2\nline 3\nline 4\n";
+    let edited_content = "line 1\nline 2\nline 3\nline 4\nline 5\n";
+
+    let get_copies = |settings: &UserSettings| {
+        let test_repo = TestRepo::init_with_backend_and_settings(TestRepoBackend::Git, settings);
+        let repo = &test_repo.repo;
+        let mut tx = repo.start_transaction();
+        let commit_a = make_commit(
+            &mut tx,
+            vec![repo.store().root_commit_id().clone()],
+            &[(repo_path("original"), content)],
+        );
+        let commit_b = make_commit(
+            &mut tx,
+            vec![commit_a.id().clone()],
+            &[
+                (repo_path("original"), content),
+                (repo_path("copy"), content),
+                (repo_path("edited-copy"), edited_content),
+            ],
+        );
+        get_copy_records(repo.store(), None, &commit_a, &commit_b)
+    };
+
+    // By default, only modified files are considered as sources.
+    assert_eq!(get_copies(&testutils::user_settings()), hashmap! {});
+
+    let mut config = testutils::base_user_config();
+    config.add_layer(
+        ConfigLayer::parse(ConfigSource::User, "copy-tracking.detect-copies = true").unwrap(),
+    );
+    let settings = UserSettings::from_config(config).unwrap();
+    assert_eq!(
+        get_copies(&settings),
+        hashmap! {
+            "copy".to_owned() => "original".to_owned(),
+            "edited-copy".to_owned() => "original".to_owned(),
+        }
+    );
+}
+
 #[test]
 fn test_jj_trees_header_with_one_tree() {
     let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
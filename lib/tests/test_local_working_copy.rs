@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs::File;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt as _;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use assert_matches::assert_matches;
 use indoc::indoc;
@@ -25,7 +28,10 @@ use jj_lib::backend::CopyId;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::backend::TreeId;
 use jj_lib::backend::TreeValue;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
 use jj_lib::file_util::check_symlink_support;
+use jj_lib::file_util::reflink_file;
 use jj_lib::file_util::try_symlink;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::gitignore::GitIgnoreFile;
@@ -41,6 +47,7 @@ use jj_lib::repo::Repo as _;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::secret_backend::SecretBackend;
+use jj_lib::settings::UserSettings;
 use jj_lib::tree_builder::TreeBuilder;
 use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::CheckoutOptions;
@@ -55,6 +62,7 @@ use pollster::FutureExt as _;
 use test_case::test_case;
 use testutils::TestRepoBackend;
 use testutils::TestWorkspace;
+use testutils::base_user_config;
 use testutils::commit_with_tree;
 use testutils::create_tree;
 use testutils::create_tree_with;
@@ -87,6 +95,18 @@ fn check_hfs_plus(dir: &Path) -> bool {
     dir.join(stripped_name).try_exists().unwrap()
 }
 
+/// Returns true if files in the directory can be cloned by reflinks.
+fn check_reflink_support(dir: &Path) -> bool {
+    let test_file = tempfile::Builder::new()
+        .prefix("reflink-")
+        .tempfile_in(dir)
+        .unwrap();
+    let clone_path = test_file.path().with_extension("clone");
+    let supported = reflink_file(test_file.path(), &clone_path).is_ok();
+    std::fs::remove_file(clone_path).ok();
+    supported
+}
+
 /// Returns true if the directory appears to support Windows short file names.
 fn check_vfat(dir: &Path) -> bool {
     let _test_file = tempfile::Builder::new()
@@ -429,6 +449,211 @@ fn test_checkout_no_op() {
     assert_eq!(*wc.operation_id(), new_op_id);
 }
 
+#[test_case(false; "reflinks disabled")]
+#[test_case(true; "reflinks enabled")]
+fn test_checkout_with_reflinks(use_reflinks: bool) {
+    // Files checked out from the reflink cache should be identical to files
+    // written normally, whether or not the filesystem supports reflinks.
+    let mut config = base_user_config();
+    config.add_layer(
+        ConfigLayer::parse(
+            ConfigSource::User,
+            &format!("working-copy.use-reflinks = {use_reflinks}"),
+        )
+        .unwrap(),
+    );
+    let settings = UserSettings::from_config(config).unwrap();
+    let mut test_workspace = TestWorkspace::init_with_settings(&settings);
+    let repo = test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let cache_dir = test_workspace
+        .workspace
+        .repo_path()
+        .parent()
+        .unwrap()
+        .join("working_copy")
+        .join("reflink_cache");
+
+    let normal_path = repo_path("normal");
+    let executable_path = repo_path("dir/executable");
+    let tree1 = create_tree_with(&repo, |builder| {
+        builder.file(normal_path, "normal contents");
+        builder
+            .file(executable_path, "executable contents")
+            .executable(true);
+    });
+    let commit1 = commit_with_tree(repo.store(), tree1.id());
+    let commit2 = commit_with_tree(repo.store(), repo.store().empty_merged_tree_id());
+
+    let ws = &mut test_workspace.workspace;
+    for commit in [&commit1, &commit2, &commit1] {
+        ws.check_out(
+            repo.op_id().clone(),
+            None,
+            commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    }
+    let normal_disk_path = normal_path.to_fs_path_unchecked(&workspace_root);
+    let executable_disk_path = executable_path.to_fs_path_unchecked(&workspace_root);
+    assert_eq!(
+        std::fs::read(&normal_disk_path).unwrap(),
+        b"normal contents"
+    );
+    assert_eq!(
+        std::fs::read(&executable_disk_path).unwrap(),
+        b"executable contents"
+    );
+    #[cfg(unix)]
+    {
+        let mode = |path: &Path| path.metadata().unwrap().permissions().mode();
+        assert_eq!(mode(&normal_disk_path) & 0o111, 0);
+        assert_ne!(mode(&executable_disk_path) & 0o111, 0);
+    }
+    let num_cached_files = cache_dir.read_dir().map_or(0, |entries| entries.count());
+    assert_eq!(
+        num_cached_files,
+        if use_reflinks && check_reflink_support(&workspace_root) {
+            2
+        } else {
+            0
+        }
+    );
+
+    // The working copy should be clean
+    assert_eq!(test_workspace.snapshot().unwrap().id(), tree1.id());
+
+    // Modifying the checked-out file doesn't affect the cached contents
+    std::fs::write(&normal_disk_path, "modified").unwrap();
+    test_workspace.snapshot().unwrap();
+    let ws = &mut test_workspace.workspace;
+    for commit in [&commit2, &commit1] {
+        ws.check_out(
+            repo.op_id().clone(),
+            None,
+            commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        std::fs::read(&normal_disk_path).unwrap(),
+        b"normal contents"
+    );
+
+    // Checkouts don't remove contents from the cache
+    let other_path = repo_path("other");
+    let tree3 = create_tree_with(&repo, |builder| {
+        builder.file(other_path, "other contents");
+    });
+    let commit3 = commit_with_tree(repo.store(), tree3.id());
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit3,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    let num_cached_files = || cache_dir.read_dir().map_or(0, |entries| entries.count());
+    let reflinks_supported = use_reflinks && check_reflink_support(&workspace_root);
+    assert_eq!(num_cached_files(), if reflinks_supported { 3 } else { 0 });
+
+    // Contents which aren't in the current tree are removed by pruning
+    let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
+    wc.prune_reflink_cache().unwrap();
+    assert_eq!(num_cached_files(), if reflinks_supported { 1 } else { 0 });
+}
+
+#[test]
+fn test_checkout_unchanged_files_not_rewritten() {
+    // Files with the same contents and mode aren't rewritten, even if the tree
+    // values differ (e.g. by copy id), so their mtimes are preserved.
+    let mut test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+    let store = repo.store();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let modified_path = repo_path("modified");
+    let unchanged_path = repo_path("dir/unchanged");
+    let copy_changed_path = repo_path("dir/copy-changed");
+    let file_value = |path: &RepoPath, contents: &str, copy_id: &[u8]| {
+        Merge::normal(TreeValue::File {
+            id: testutils::write_file(store, path, contents),
+            executable: false,
+            copy_id: CopyId::new(copy_id.to_vec()),
+        })
+    };
+    let mut tree_builder = MergedTreeBuilder::new(store.empty_merged_tree_id());
+    tree_builder.set_or_remove(
+        modified_path.to_owned(),
+        file_value(modified_path, "1", b""),
+    );
+    tree_builder.set_or_remove(
+        unchanged_path.to_owned(),
+        file_value(unchanged_path, "1", b""),
+    );
+    tree_builder.set_or_remove(
+        copy_changed_path.to_owned(),
+        file_value(copy_changed_path, "1", b""),
+    );
+    let tree_id1 = tree_builder.write_tree(store).unwrap();
+    let mut tree_builder = MergedTreeBuilder::new(tree_id1.clone());
+    tree_builder.set_or_remove(
+        modified_path.to_owned(),
+        file_value(modified_path, "2", b""),
+    );
+    tree_builder.set_or_remove(
+        copy_changed_path.to_owned(),
+        file_value(copy_changed_path, "1", b"copy"),
+    );
+    let tree_id2 = tree_builder.write_tree(store).unwrap();
+    let commit1 = commit_with_tree(store, tree_id1);
+    let commit2 = commit_with_tree(store, tree_id2);
+
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit1,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+
+    let old_mtime = SystemTime::now() - Duration::from_secs(3600);
+    let get_mtime = |path: &RepoPath| {
+        let disk_path = path.to_fs_path_unchecked(&workspace_root);
+        disk_path.metadata().unwrap().modified().unwrap()
+    };
+    for path in [modified_path, unchanged_path, copy_changed_path] {
+        let disk_path = path.to_fs_path_unchecked(&workspace_root);
+        let file = File::options().write(true).open(disk_path).unwrap();
+        file.set_modified(old_mtime).unwrap();
+    }
+
+    let stats = ws
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit2,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    assert_eq!(
+        stats,
+        CheckoutStats {
+            updated_files: 1,
+            added_files: 0,
+            removed_files: 0,
+            skipped_files: 0,
+        }
+    );
+    assert_ne!(get_mtime(modified_path), old_mtime);
+    assert_eq!(get_mtime(unchanged_path), old_mtime);
+    assert_eq!(get_mtime(copy_changed_path), old_mtime);
+}
+
 // Test case for issue #2165
 #[test]
 fn test_conflict_subdirectory() {
@@ -1046,6 +1271,62 @@ fn test_snapshot_racy_timestamps() {
     }
 }
 
+#[test]
+fn test_snapshot_racy_rewrite_with_mtime_restored() {
+    // Tests that a file rewritten with the same size and its mtime restored is
+    // detected if it was racily clean when the state was saved, even after the
+    // state file's own mtime has moved on.
+    let mut test_workspace = TestWorkspace::init();
+    let repo = test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let wc: &LocalWorkingCopy = test_workspace
+        .workspace
+        .working_copy()
+        .as_any()
+        .downcast_ref()
+        .unwrap();
+    let state_path = wc.state_path().to_path_buf();
+
+    let path = repo_path("file");
+    let disk_path = path.to_fs_path_unchecked(&workspace_root);
+    let set_mtime = |path: &Path, mtime: SystemTime| {
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(mtime).unwrap();
+    };
+    // Pretend the file was modified at the same time as the state is written
+    let mtime = SystemTime::now() + Duration::from_secs(3600);
+    std::fs::write(&disk_path, "contents 1").unwrap();
+    set_mtime(&disk_path, mtime);
+    let tree = test_workspace.snapshot().unwrap();
+    assert_eq!(tree.id(), create_tree(&repo, &[(path, "contents 1")]).id());
+
+    let wc = LocalWorkingCopy::load(
+        repo.store().clone(),
+        workspace_root.clone(),
+        state_path.clone(),
+        repo.settings(),
+    )
+    .unwrap();
+    let racy_paths = wc
+        .racy_file_states()
+        .unwrap()
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect_vec();
+    assert_eq!(racy_paths, vec![path.to_owned()]);
+
+    // Rewrite the file without changing its metadata, and then make the state
+    // file look as if it had been saved later without looking at the file.
+    std::fs::write(&disk_path, "contents 2").unwrap();
+    set_mtime(&disk_path, mtime);
+    set_mtime(
+        &state_path.join("tree_state"),
+        mtime + Duration::from_secs(3600),
+    );
+    let tree = test_workspace.snapshot().unwrap();
+    assert_eq!(tree.id(), create_tree(&repo, &[(path, "contents 2")]).id());
+}
+
 #[cfg(unix)]
 #[test]
 fn test_snapshot_special_file() {
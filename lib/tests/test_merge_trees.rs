@@ -12,13 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use jj_lib::backend::CommitId;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::backend::TreeValue;
+use jj_lib::commit::Commit;
+use jj_lib::copies::merge_trees_following_renames;
 use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::rebase_commit;
+use jj_lib::transaction::Transaction;
 use pollster::FutureExt as _;
 use testutils::TestRepo;
+use testutils::TestRepoBackend;
 use testutils::create_tree;
 use testutils::repo_path;
 
@@ -235,3 +241,100 @@ fn test_rebase_on_lossy_merge() {
     .flatten();
     assert_eq!(*commit_d2.tree_id(), MergedTreeId::Merge(expected_tree_id));
 }
+
+#[test]
+fn test_merge_trees_following_renames() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let store = repo.store();
+
+    // Side 1 renames the file, side 2 modifies it. The modification should end
+    // up in the renamed file.
+    let old_path = repo_path("old");
+    let new_path = repo_path("new");
+    let content = "line 1\nline 2\nline 3\nline 4\nline 5\n";
+    let modified_content = "line 1\nline 2\nline 3\nline 4\nline 5 modified\n";
+    let mut tx = repo.start_transaction();
+    let tree_base = create_tree(repo, &[(old_path, content)]);
+    let tree1 = create_tree(repo, &[(new_path, content)]);
+    let tree2 = create_tree(repo, &[(old_path, modified_content)]);
+    let commit_base = write_commit(&mut tx, repo.store().root_commit_id(), &tree_base);
+    let commit1 = write_commit(&mut tx, commit_base.id(), &tree1);
+    let commit2 = write_commit(&mut tx, commit_base.id(), &tree2);
+
+    let merged = merge_trees_following_renames(
+        store,
+        commit_base.id(),
+        tree_base.clone(),
+        commit1.id(),
+        tree1.clone(),
+        commit2.id(),
+        tree2.clone(),
+    )
+    .block_on()
+    .unwrap();
+    let expected_tree = create_tree(repo, &[(new_path, modified_content)]);
+    assert_eq!(merged.id(), expected_tree.id());
+
+    // The same applies with the sides swapped
+    let merged = merge_trees_following_renames(
+        store,
+        commit_base.id(),
+        tree_base.clone(),
+        commit2.id(),
+        tree2.clone(),
+        commit1.id(),
+        tree1.clone(),
+    )
+    .block_on()
+    .unwrap();
+    assert_eq!(merged.id(), expected_tree.id());
+
+    // A regular merge sees a modify/delete conflict at the old path
+    let merged = tree1.merge(tree_base, tree2).block_on().unwrap();
+    assert!(merged.has_conflict());
+}
+
+#[test]
+fn test_merge_trees_following_renames_ambiguous() {
+    let test_repo = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_repo.repo;
+    let store = repo.store();
+
+    // Both sides rename the file to different names, so the renames aren't
+    // followed and the result is the same as a regular merge.
+    let old_path = repo_path("old");
+    let new_path1 = repo_path("new1");
+    let new_path2 = repo_path("new2");
+    let content = "line 1\nline 2\nline 3\nline 4\nline 5\n";
+    let mut tx = repo.start_transaction();
+    let tree_base = create_tree(repo, &[(old_path, content)]);
+    let tree1 = create_tree(repo, &[(new_path1, content)]);
+    let tree2 = create_tree(repo, &[(new_path2, content)]);
+    let commit_base = write_commit(&mut tx, repo.store().root_commit_id(), &tree_base);
+    let commit1 = write_commit(&mut tx, commit_base.id(), &tree1);
+    let commit2 = write_commit(&mut tx, commit_base.id(), &tree2);
+
+    let merged = merge_trees_following_renames(
+        store,
+        commit_base.id(),
+        tree_base.clone(),
+        commit1.id(),
+        tree1.clone(),
+        commit2.id(),
+        tree2.clone(),
+    )
+    .block_on()
+    .unwrap();
+    let expected_tree = tree1.merge(tree_base, tree2).block_on().unwrap();
+    assert_eq!(merged.id(), expected_tree.id());
+    let expected_tree = create_tree(repo, &[(new_path1, content), (new_path2, content)]);
+    assert_eq!(merged.id(), expected_tree.id());
+}
+
+fn write_commit(tx: &mut Transaction, parent_id: &CommitId, tree: &MergedTree) -> Commit {
+    tx.repo_mut()
+        .new_commit(vec![parent_id.clone()], tree.id())
+        .write()
+        .unwrap()
+}
@@ -1663,3 +1663,67 @@ fn test_merge_simplify_file_conflict_with_absent() {
         .unwrap();
     assert_eq!(merged, expected_merged);
 }
+
+/// Merging the same subtrees across a batch of commits should reuse earlier
+/// results without changing them
+#[test]
+fn test_merge_reuses_cached_subtree_merges() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let store = repo.store();
+
+    // A deep tree with a file in each of 3x3x3 leaf directories
+    let paths = (0..3)
+        .cartesian_product(0..3)
+        .cartesian_product(0..3)
+        .map(|((a, b), c)| repo_path_buf(format!("d{a}/d{b}/d{c}/file")))
+        .collect_vec();
+    let create_tree = |changes: &[(usize, &str)]| {
+        let path_contents = paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let contents = changes
+                    .iter()
+                    .find(|(j, _)| *j == i)
+                    .map_or("base", |(_, contents)| contents);
+                (path.as_ref(), contents)
+            })
+            .collect_vec();
+        MergedTree::resolved(create_single_tree(repo, &path_contents))
+    };
+
+    // Rebase sibling commits onto a new base. Each sibling changes its own file
+    // outside of d0/, and they all share a change to d0/ that merges with the
+    // new base's change to d0/.
+    let old_base = create_tree(&[]);
+    let new_base = create_tree(&[(0, "upstream")]);
+    let siblings = (10..20)
+        .map(|i| create_tree(&[(1, "shared"), (i, "sibling")]))
+        .collect_vec();
+    let rebase_all = |clear_caches: bool| {
+        siblings
+            .iter()
+            .map(|sibling| {
+                if clear_caches {
+                    store.clear_caches();
+                }
+                let merged = new_base
+                    .clone()
+                    .merge(old_base.clone(), sibling.clone())
+                    .block_on()
+                    .unwrap();
+                assert!(!merged.has_conflict());
+                merged.id()
+            })
+            .collect_vec()
+    };
+
+    let uncached = rebase_all(true);
+    store.clear_caches();
+    let cached = rebase_all(false);
+    assert_eq!(cached, uncached);
+    // The d0/ merge is the same for all siblings after the first one
+    let stats = store.tree_merge_cache_stats();
+    assert_eq!(stats.hits, siblings.len() as u64 - 1);
+}
@@ -3191,6 +3191,11 @@ fn test_evaluate_expression_description() {
         .set_description("commit 3\n")
         .write()
         .unwrap();
+    let commit4 = create_random_commit(mut_repo)
+        .set_parents(vec![commit3.id().clone()])
+        .set_description(" \n\t\n")
+        .write()
+        .unwrap();
 
     // Can find multiple matches
     assert_eq!(
@@ -3245,6 +3250,15 @@ fn test_evaluate_expression_description() {
         resolve_commit_ids(mut_repo, "subject(exact:'')"),
         vec![mut_repo.store().root_commit_id().clone()]
     );
+
+    // Whitespace-only description is also empty
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "empty_description()"),
+        vec![
+            commit4.id().clone(),
+            mut_repo.store().root_commit_id().clone()
+        ]
+    );
 }
 
 #[test]
@@ -4603,6 +4617,14 @@ fn test_evaluate_expression_conflict() {
         resolve_commit_ids(mut_repo, "conflicts()"),
         vec![commit4.id().clone()]
     );
+
+    // The conflict is in file1, not in file2
+    let query = |revset_str| {
+        resolve_commit_ids_in_workspace(mut_repo, revset_str, &test_workspace.workspace, None)
+    };
+    assert_eq!(query("conflicts(file1)"), vec![commit4.id().clone()]);
+    assert_eq!(query("conflicts(file2)"), vec![]);
+    assert_eq!(query("conflicts(all())"), vec![commit4.id().clone()]);
 }
 
 #[test]
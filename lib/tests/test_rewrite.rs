@@ -31,9 +31,12 @@ use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::CommitRewriter;
 use jj_lib::rewrite::CommitWithSelection;
 use jj_lib::rewrite::EmptyBehavior;
+use jj_lib::rewrite::MoveCommitsLocation;
 use jj_lib::rewrite::MoveCommitsTarget;
 use jj_lib::rewrite::RebaseOptions;
+use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
+use jj_lib::rewrite::compute_move_commits;
 use jj_lib::rewrite::find_duplicate_divergent_commits;
 use jj_lib::rewrite::find_recursive_merge_commits;
 use jj_lib::rewrite::merge_commit_trees;
@@ -2039,3 +2042,77 @@ fn test_find_duplicate_divergent_commits() {
     // Commit c2 is a duplicate
     assert_eq!(duplicate_commits, std::slice::from_ref(&commit_c2));
 }
+
+#[test]
+fn test_plan_move_commits() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // Commit b and its descendants are moved onto d, which makes the same
+    // change. With AbandonNewlyEmpty, b is abandoned and c is rebased onto d.
+    //
+    // C
+    // |
+    // B D
+    // |/
+    // A
+    let tree_a = create_tree(repo, &[(repo_path("file1"), "a\n")]);
+    let tree_b = create_tree(repo, &[(repo_path("file1"), "b\n")]);
+    let tree_c = create_tree(
+        repo,
+        &[(repo_path("file1"), "b\n"), (repo_path("file2"), "c\n")],
+    );
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let root_id = repo.store().root_commit_id().clone();
+    let commit_a = mut_repo
+        .new_commit(vec![root_id], tree_a.id())
+        .write()
+        .unwrap();
+    let commit_b = mut_repo
+        .new_commit(vec![commit_a.id().clone()], tree_b.id())
+        .write()
+        .unwrap();
+    let commit_c = mut_repo
+        .new_commit(vec![commit_b.id().clone()], tree_c.id())
+        .write()
+        .unwrap();
+    let commit_d = mut_repo
+        .new_commit(vec![commit_a.id().clone()], tree_b.id())
+        .write()
+        .unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx = repo.start_transaction();
+    let loc = MoveCommitsLocation {
+        new_parent_ids: vec![commit_d.id().clone()],
+        new_child_ids: vec![],
+        target: MoveCommitsTarget::Roots(vec![commit_b.id().clone()]),
+    };
+    let options = RebaseOptions {
+        empty: EmptyBehavior::AbandonNewlyEmpty,
+        ..Default::default()
+    };
+    let computed_move = compute_move_commits(tx.repo(), &loc).unwrap();
+    let plan = computed_move.plan(tx.repo(), &options, false).unwrap();
+    assert_eq!(plan.len(), 2);
+    assert_eq!(plan[0].old_commit, commit_b);
+    assert_eq!(plan[0].new_parent_ids, None);
+    assert_eq!(plan[1].old_commit, commit_c);
+    assert_eq!(plan[1].new_parent_ids, Some(vec![commit_d.id().clone()]));
+    // Trees are computed to find empty commits
+    assert_eq!(
+        plan[1].new_tree.as_ref().map(MergedTree::id),
+        Some(tree_c.id())
+    );
+    // Nothing is rewritten by planning
+    assert!(!tx.repo().has_changes());
+
+    let stats = computed_move.apply(tx.repo_mut(), &options).unwrap();
+    assert_eq!(stats.num_abandoned_empty, 1);
+    let RebasedCommit::Rewritten(new_commit_c) = &stats.rebased_commits[commit_c.id()] else {
+        panic!("commit c should be rewritten");
+    };
+    assert_eq!(new_commit_c.parent_ids(), [commit_d.id().clone()]);
+    assert_eq!(*new_commit_c.tree_id(), tree_c.id());
+}
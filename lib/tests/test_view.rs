@@ -307,6 +307,129 @@ fn test_merge_views_bookmarks() {
     );
 }
 
+#[test]
+fn test_merge_views_bookmark_descriptions() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit = write_random_commit(mut_repo);
+    for name in ["main", "feature", "release", "stale"] {
+        mut_repo.set_local_bookmark_target(name.as_ref(), RefTarget::normal(commit.id().clone()));
+    }
+    mut_repo.set_local_bookmark_description("main".as_ref(), "main tx0".to_owned());
+    mut_repo.set_local_bookmark_description("release".as_ref(), "release tx0".to_owned());
+    mut_repo.set_local_bookmark_description("stale".as_ref(), "stale tx0".to_owned());
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx1 = repo.start_transaction();
+    // Both sides change the description: the first one wins.
+    tx1.repo_mut()
+        .set_local_bookmark_description("main".as_ref(), "main tx1".to_owned());
+    // Only one side adds a description.
+    tx1.repo_mut()
+        .set_local_bookmark_description("feature".as_ref(), "feature tx1".to_owned());
+    // The bookmark is deleted on one side, so its description is dropped.
+    tx1.repo_mut()
+        .set_local_bookmark_target("stale".as_ref(), RefTarget::absent());
+
+    let mut tx2 = repo.start_transaction();
+    tx2.repo_mut()
+        .set_local_bookmark_description("main".as_ref(), "main tx2".to_owned());
+    // Only one side removes a description.
+    tx2.repo_mut()
+        .set_local_bookmark_description("release".as_ref(), String::new());
+    tx2.repo_mut()
+        .set_local_bookmark_description("stale".as_ref(), "stale tx2".to_owned());
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.view()
+            .local_bookmark_descriptions()
+            .collect::<Vec<_>>(),
+        vec![
+            ("feature".as_ref(), "feature tx1"),
+            ("main".as_ref(), "main tx1"),
+        ]
+    );
+}
+
+#[test]
+fn test_merge_views_notes() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let change_id1 = commit1.change_id();
+    let change_id2 = commit2.change_id();
+    mut_repo.set_note(change_id1, "ci", "pending".to_owned());
+    mut_repo.set_note(change_id1, "review", "url tx0".to_owned());
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx1 = repo.start_transaction();
+    // Both sides change the note: the first one wins.
+    tx1.repo_mut()
+        .set_note(change_id1, "ci", "passed".to_owned());
+    // Only one side adds a note.
+    tx1.repo_mut()
+        .set_note(change_id2, "ci", "failed".to_owned());
+
+    let mut tx2 = repo.start_transaction();
+    tx2.repo_mut()
+        .set_note(change_id1, "ci", "failed".to_owned());
+    // Only one side removes a note.
+    tx2.repo_mut().set_note(change_id1, "review", String::new());
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.view().notes(change_id1).collect::<Vec<_>>(),
+        vec![("ci", "passed")]
+    );
+    assert_eq!(
+        repo.view().notes(change_id2).collect::<Vec<_>>(),
+        vec![("ci", "failed")]
+    );
+}
+
+#[test]
+fn test_merge_views_change_names() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let change_id1 = commit1.change_id();
+    let change_id2 = commit2.change_id();
+    mut_repo.set_change_name("both", Some(change_id1.clone()));
+    mut_repo.set_change_name("removed", Some(change_id1.clone()));
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx1 = repo.start_transaction();
+    // Both sides move the name: the first one wins.
+    tx1.repo_mut()
+        .set_change_name("both", Some(change_id2.clone()));
+    // Only one side adds a name.
+    tx1.repo_mut()
+        .set_change_name("added", Some(change_id2.clone()));
+
+    let mut tx2 = repo.start_transaction();
+    tx2.repo_mut().set_change_name("both", None);
+    // Only one side removes a name.
+    tx2.repo_mut().set_change_name("removed", None);
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.view().change_names().collect::<Vec<_>>(),
+        vec![("added", change_id2), ("both", change_id2)]
+    );
+}
+
 #[test]
 fn test_merge_views_tags() {
     // Tests merging of tags (by performing divergent operations). See
@@ -163,6 +163,48 @@ pub fn write_commit(
     .detach()
 }
 
+/// Writes a copy of the commit with a different message, keeping the other
+/// headers (such as the signature) as is, and points `reference` to it.
+pub fn write_commit_with_message(
+    repo: &gix::Repository,
+    reference: &str,
+    commit_id: gix::ObjectId,
+    message: &str,
+) -> gix::ObjectId {
+    let commit = repo.find_commit(commit_id).unwrap();
+    let mut new_commit: gix::objs::Commit = commit.decode().unwrap().into();
+    new_commit.message = message.into();
+    let new_commit_id = repo.write_object(&new_commit).unwrap().detach();
+    repo.reference(
+        reference,
+        new_commit_id,
+        gix::refs::transaction::PreviousValue::Any,
+        "write commit with message",
+    )
+    .unwrap();
+    new_commit_id
+}
+
+pub fn write_annotated_tag(
+    repo: &gix::Repository,
+    name: &str,
+    target: gix::ObjectId,
+    message: &str,
+) -> gix::ObjectId {
+    let signature = signature();
+    repo.tag(
+        name,
+        target,
+        gix::object::Kind::Commit,
+        Some(signature.to_ref(&mut TimeBuf::default())),
+        message,
+        gix::refs::transaction::PreviousValue::MustNotExist,
+    )
+    .unwrap()
+    .id()
+    .detach()
+}
+
 pub fn set_head_to_id(repo: &gix::Repository, target: gix::ObjectId) {
     repo.edit_reference(gix::refs::transaction::RefEdit {
         change: gix::refs::transaction::Change::Update {